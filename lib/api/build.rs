@@ -122,6 +122,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.hybrid_vectors_config", ""),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -138,6 +139,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CollectionParams.vectors_config", ""),
             ("ChangeAliases.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
+            ("GetAliasHistoryRequest.alias_name", "length(min = 1, max = 255)"),
             ("HnswConfigDiff.ef_construct", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
             ("WalConfigDiff.wal_capacity_mb", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("OptimizersConfigDiff.deleted_threshold", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
@@ -151,14 +153,16 @@ fn configure_validation(builder: Builder) -> Builder {
             ("VectorParamsDiff.hnsw_config", ""),
             ("VectorParamsDiff.quantization_config", ""),
             ("VectorParamsDiffMap.map", ""),
+            ("HybridVectorParams.dense", ""),
+            ("HybridVectorParamsMap.map", ""),
             ("QuantizationConfig.quantization", ""),
             ("QuantizationConfigDiff.quantization", ""),
             ("ScalarQuantization.quantile", "custom = \"crate::grpc::validate::validate_f32_range_min_0_5_max_1\""),
             ("UpdateCollectionClusterSetupRequest.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateCollectionClusterSetupRequest.operation", ""),
+            ("CollectionParamsDiff.point_history_size", "custom = \"crate::grpc::validate::validate_u32_range_max_100\""),
         ], &[
             "ListCollectionsRequest",
-            "CollectionParamsDiff",
             "ListAliasesRequest",
             "CollectionClusterInfoRequest",
             "UpdateCollectionClusterSetupRequest",
@@ -177,6 +181,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("WaitForShardStateRequest.timeout", "range(min = 1)"),
             ("GetShardRecoveryPointRequest.collection_name", "length(min = 1, max = 255)"),
             ("UpdateShardCutoffPointRequest.collection_name", "length(min = 1, max = 255)"),
+            ("WaitForSessionClocksRequest.collection_name", "length(min = 1, max = 255)"),
         ], &[])
         // Service: points.proto
         .validates(&[
@@ -212,6 +217,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPointGroups.group_size", "range(min = 1)"),
             ("SearchPointGroups.limit", "range(min = 1)"),
             ("SearchPointGroups.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchPointGroups.with_neighbors", ""),
+            ("WithNeighbors.ordinal_by", "length(min = 1)"),
+            ("WithNeighbors.neighbors", "range(min = 1, max = 100)"),
             ("SearchParams.quantization", ""),
             ("QuantizationSearchParams.oversampling", "custom = \"crate::grpc::validate::validate_f64_range_min_1\""),
             ("ScrollPoints.collection_name", "length(min = 1, max = 255)"),
@@ -235,6 +243,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("RecommendPointGroups.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("RecommendPointGroups.positive_vectors", ""),
             ("RecommendPointGroups.negative_vectors", ""),
+            ("RecommendPointGroups.with_neighbors", ""),
             ("DiscoverPoints.collection_name", "length(min = 1, max = 255)"),
             ("DiscoverPoints.filter", ""),
             ("DiscoverPoints.params", ""),
@@ -264,6 +273,15 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QueryPoints.filter", ""),
             ("QueryPoints.params", ""),
             ("QueryPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPoints.budget", ""),
+            ("TextBudget.key", "length(min = 1)"),
+            ("TextBudget.limit", "range(min = 1)"),
+            ("QueryPoints.rerank", ""),
+            ("Rerank.query", "length(min = 1)"),
+            ("Rerank.fields", "length(min = 1)"),
+            ("Rerank.top", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPoints.filter_template", ""),
+            ("FilterTemplateReference.name", "length(min = 1)"),
             ("QueryBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPoints.query_points", ""),
             ("QueryBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
@@ -304,6 +322,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPointsInternal.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPointsInternal.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("GroupAggregatesInternal.collection_name", "length(min = 1, max = 255)"),
+            ("GroupAggregatesInternal.filter", ""),
         ], &[])
         // Service: raft_service.proto
         .validates(&[
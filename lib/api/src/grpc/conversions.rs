@@ -16,10 +16,12 @@ use uuid::Uuid;
 
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
-    raw_query, start_from, BinaryQuantization, CompressionRatio, DatetimeRange, Direction,
-    GeoLineString, GroupId, LookupLocation, MultiVectorComparator, MultiVectorConfig, OrderBy,
-    OrderValue, Range, RawVector, RecommendStrategy, SearchPointGroups, SearchPoints,
-    ShardKeySelector, SparseIndices, StartFrom, WithLookup,
+    raw_query, start_from, BinaryQuantization, BudgetUnit, CompressionRatio, DatetimeRange,
+    Direction, FilterTemplateReference, GeoLineString, GroupId, LookupLocation,
+    MultiVectorComparator, MultiVectorConfig, OrderBy, OrderValue, PointRouting, PrefetchMatch,
+    QueryStats, Range, RawVector, RecommendStrategy, Rerank, SearchPointGroups, SearchPoints,
+    SegmentSearchMode, SegmentSearchStats, ShardKeySelector, SparseIndices, StartFrom, TextBudget,
+    WithAggregates, WithLookup, WithNeighbors,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
@@ -216,6 +218,7 @@ impl From<segment::data_types::text_index::TokenizerType> for TokenizerType {
                 TokenizerType::Multilingual
             }
             segment::data_types::text_index::TokenizerType::Word => TokenizerType::Word,
+            segment::data_types::text_index::TokenizerType::Custom => TokenizerType::Custom,
         }
     }
 }
@@ -229,6 +232,7 @@ impl From<segment::data_types::text_index::TextIndexParams> for PayloadIndexPara
                 lowercase: params.lowercase,
                 min_token_len: params.min_token_len.map(|x| x as u64),
                 max_token_len: params.max_token_len.map(|x| x as u64),
+                custom_tokenizer: params.custom_tokenizer,
             })),
         }
     }
@@ -284,6 +288,7 @@ impl TryFrom<TokenizerType> for segment::data_types::text_index::TokenizerType {
                 Ok(segment::data_types::text_index::TokenizerType::Whitespace)
             }
             TokenizerType::Word => Ok(segment::data_types::text_index::TokenizerType::Word),
+            TokenizerType::Custom => Ok(segment::data_types::text_index::TokenizerType::Custom),
         }
     }
 }
@@ -299,6 +304,7 @@ impl TryFrom<TextIndexParams> for segment::data_types::text_index::TextIndexPara
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            custom_tokenizer: params.custom_tokenizer,
         })
     }
 }
@@ -471,6 +477,7 @@ impl From<SearchParams> for segment::types::SearchParams {
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: params.indexed_only.unwrap_or(false),
+            with_raw_score: params.with_raw_score.unwrap_or(false),
         }
     }
 }
@@ -482,6 +489,7 @@ impl From<segment::types::SearchParams> for SearchParams {
             exact: Some(params.exact),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: Some(params.indexed_only),
+            with_raw_score: Some(params.with_raw_score),
         }
     }
 }
@@ -634,6 +642,165 @@ impl TryFrom<OrderValue> for segment::data_types::order_by::OrderValue {
     }
 }
 
+impl From<segment::types::PrefetchMatch> for PrefetchMatch {
+    fn from(value: segment::types::PrefetchMatch) -> Self {
+        let segment::types::PrefetchMatch {
+            prefetch,
+            rank,
+            score,
+        } = value;
+
+        Self {
+            prefetch: prefetch as u64,
+            rank: rank as u64,
+            score,
+        }
+    }
+}
+
+impl From<PrefetchMatch> for segment::types::PrefetchMatch {
+    fn from(value: PrefetchMatch) -> Self {
+        let PrefetchMatch {
+            prefetch,
+            rank,
+            score,
+        } = value;
+
+        Self {
+            prefetch: prefetch as usize,
+            rank: rank as usize,
+            score,
+        }
+    }
+}
+
+impl From<segment::types::PointRouting> for PointRouting {
+    fn from(value: segment::types::PointRouting) -> Self {
+        let segment::types::PointRouting {
+            shard_key,
+            shard_id,
+            peer_id,
+        } = value;
+
+        Self {
+            shard_key: shard_key.map(convert_shard_key_to_grpc),
+            shard_id,
+            peer_id,
+        }
+    }
+}
+
+impl From<PointRouting> for segment::types::PointRouting {
+    fn from(value: PointRouting) -> Self {
+        let PointRouting {
+            shard_key,
+            shard_id,
+            peer_id,
+        } = value;
+
+        Self {
+            shard_key: convert_shard_key_from_grpc_opt(shard_key),
+            shard_id,
+            peer_id,
+        }
+    }
+}
+
+impl From<segment::types::QueryStats> for QueryStats {
+    fn from(value: segment::types::QueryStats) -> Self {
+        let segment::types::QueryStats {
+            vectors_scored,
+            filter_checks,
+            plain_searches,
+            hnsw_searches,
+            sparse_searches,
+            segments,
+        } = value;
+
+        Self {
+            vectors_scored: vectors_scored as u64,
+            filter_checks: filter_checks as u64,
+            plain_searches: plain_searches as u64,
+            hnsw_searches: hnsw_searches as u64,
+            sparse_searches: sparse_searches as u64,
+            segments: segments.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl From<QueryStats> for segment::types::QueryStats {
+    fn from(value: QueryStats) -> Self {
+        let QueryStats {
+            vectors_scored,
+            filter_checks,
+            plain_searches,
+            hnsw_searches,
+            sparse_searches,
+            segments,
+        } = value;
+
+        Self {
+            vectors_scored: vectors_scored as usize,
+            filter_checks: filter_checks as usize,
+            plain_searches: plain_searches as usize,
+            hnsw_searches: hnsw_searches as usize,
+            sparse_searches: sparse_searches as usize,
+            // Modes unknown to this version are left out
+            segments: segments
+                .into_iter()
+                .filter_map(|s| s.try_into().ok())
+                .collect(),
+        }
+    }
+}
+
+impl From<segment::types::SegmentSearchStats> for SegmentSearchStats {
+    fn from(value: segment::types::SegmentSearchStats) -> Self {
+        let segment::types::SegmentSearchStats {
+            segment,
+            mode,
+            searches,
+        } = value;
+
+        let mode = match mode {
+            segment::types::SegmentSearchMode::Plain => SegmentSearchMode::Plain,
+            segment::types::SegmentSearchMode::Hnsw => SegmentSearchMode::Hnsw,
+            segment::types::SegmentSearchMode::Sparse => SegmentSearchMode::Sparse,
+        };
+
+        Self {
+            segment,
+            mode: mode as i32,
+            searches: searches as u64,
+        }
+    }
+}
+
+impl TryFrom<SegmentSearchStats> for segment::types::SegmentSearchStats {
+    type Error = Status;
+
+    fn try_from(value: SegmentSearchStats) -> Result<Self, Self::Error> {
+        let SegmentSearchStats {
+            segment,
+            mode,
+            searches,
+        } = value;
+
+        let mode = match SegmentSearchMode::from_i32(mode) {
+            Some(SegmentSearchMode::Plain) => segment::types::SegmentSearchMode::Plain,
+            Some(SegmentSearchMode::Hnsw) => segment::types::SegmentSearchMode::Hnsw,
+            Some(SegmentSearchMode::Sparse) => segment::types::SegmentSearchMode::Sparse,
+            None => return Err(Status::invalid_argument("Unknown segment search mode")),
+        };
+
+        Ok(Self {
+            segment,
+            mode,
+            searches: searches as usize,
+        })
+    }
+}
+
 impl From<segment::types::ScoredPoint> for ScoredPoint {
     fn from(point: segment::types::ScoredPoint) -> Self {
         Self {
@@ -644,6 +811,14 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             vectors: point.vector.map(|v| v.into()),
             shard_key: point.shard_key.map(convert_shard_key_to_grpc),
             order_value: point.order_value.map(From::from),
+            prefetch_matches: point
+                .prefetch_matches
+                .into_iter()
+                .flatten()
+                .map(From::from)
+                .collect(),
+            routing: point.routing.map(From::from),
+            raw_score: point.raw_score,
         }
     }
 }
@@ -664,6 +839,19 @@ impl From<segment::data_types::groups::GroupId> for GroupId {
     }
 }
 
+impl TryFrom<GroupId> for segment::data_types::groups::GroupId {
+    type Error = Status;
+
+    fn try_from(key: GroupId) -> Result<Self, Self::Error> {
+        match key.kind {
+            Some(crate::grpc::qdrant::group_id::Kind::StringValue(str)) => Ok(Self::String(str)),
+            Some(crate::grpc::qdrant::group_id::Kind::UnsignedValue(n)) => Ok(Self::NumberU64(n)),
+            Some(crate::grpc::qdrant::group_id::Kind::IntegerValue(n)) => Ok(Self::NumberI64(n)),
+            None => Err(Status::invalid_argument("Group id is missing")),
+        }
+    }
+}
+
 impl TryFrom<NamedVectors> for HashMap<String, segment_vectors::Vector> {
     type Error = Status;
 
@@ -1573,6 +1761,62 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
     }
 }
 
+impl From<segment::types::SearchPoolsConfig> for super::qdrant::SearchPoolsConfig {
+    fn from(value: segment::types::SearchPoolsConfig) -> Self {
+        let segment::types::SearchPoolsConfig {
+            visited_pool_size,
+            reuse_search_contexts,
+        } = value;
+        Self {
+            visited_pool_size: visited_pool_size.map(|size| size as u64),
+            reuse_search_contexts,
+        }
+    }
+}
+
+impl From<super::qdrant::SearchPoolsConfig> for segment::types::SearchPoolsConfig {
+    fn from(value: super::qdrant::SearchPoolsConfig) -> Self {
+        let super::qdrant::SearchPoolsConfig {
+            visited_pool_size,
+            reuse_search_contexts,
+        } = value;
+        Self {
+            visited_pool_size: visited_pool_size.map(|size| size as usize),
+            reuse_search_contexts,
+        }
+    }
+}
+
+impl From<segment::types::PayloadCompressionConfig> for super::qdrant::PayloadCompressionConfig {
+    fn from(value: segment::types::PayloadCompressionConfig) -> Self {
+        let segment::types::PayloadCompressionConfig {
+            fields,
+            level,
+            min_size,
+        } = value;
+        Self {
+            fields,
+            level,
+            min_size: min_size.map(|size| size as u64),
+        }
+    }
+}
+
+impl From<super::qdrant::PayloadCompressionConfig> for segment::types::PayloadCompressionConfig {
+    fn from(value: super::qdrant::PayloadCompressionConfig) -> Self {
+        let super::qdrant::PayloadCompressionConfig {
+            fields,
+            level,
+            min_size,
+        } = value;
+        Self {
+            fields,
+            level,
+            min_size: min_size.map(|size| size as usize),
+        }
+    }
+}
+
 pub fn naive_date_time_to_proto(date_time: NaiveDateTime) -> prost_wkt_types::Timestamp {
     prost_wkt_types::Timestamp {
         seconds: date_time.and_utc().timestamp(), // number of non-leap seconds since the midnight on January 1, 1970.
@@ -1671,6 +1915,7 @@ impl From<UpdateResultInternal> for UpdateResult {
         Self {
             operation_id: res.operation_id,
             status: res.status,
+            session_token: res.session_token,
         }
     }
 }
@@ -1682,6 +1927,7 @@ impl From<UpdateResult> for UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status,
             clock_tag: None,
+            session_token: res.session_token,
         }
     }
 }
@@ -1976,6 +2222,8 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
             timeout: None,
             shard_key_selector: None,
             sparse_indices: value.sparse_indices,
+            session_token: None,
+            with_stats: None,
         };
 
         if let Some(sparse_indices) = &search_points.sparse_indices {
@@ -2014,6 +2262,14 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
                     .with_lookup
                     .map(rest::WithLookupInterface::try_from)
                     .transpose()?,
+                with_neighbors: value
+                    .with_neighbors
+                    .map(rest::WithNeighbors::try_from)
+                    .transpose()?,
+                with_aggregates: value
+                    .with_aggregates
+                    .map(rest::WithAggregates::try_from)
+                    .transpose()?,
             },
         })
     }
@@ -2045,6 +2301,83 @@ impl TryFrom<WithLookup> for rest::WithLookup {
     }
 }
 
+impl TryFrom<WithNeighbors> for rest::WithNeighbors {
+    type Error = Status;
+
+    fn try_from(value: WithNeighbors) -> Result<Self, Self::Error> {
+        Ok(Self {
+            ordinal_by: json_path_from_proto(&value.ordinal_by)?,
+            neighbors: value.neighbors,
+        })
+    }
+}
+
+impl TryFrom<WithAggregates> for rest::WithAggregates {
+    type Error = Status;
+
+    fn try_from(value: WithAggregates) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mean: value
+                .mean
+                .iter()
+                .map(|path| json_path_from_proto(path))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<TextBudget> for rest::TextBudget {
+    type Error = Status;
+
+    fn try_from(value: TextBudget) -> Result<Self, Self::Error> {
+        let unit = match value.unit.map(BudgetUnit::from_i32) {
+            None => rest::BudgetUnit::default(),
+            Some(Some(BudgetUnit::Chars)) => rest::BudgetUnit::Chars,
+            Some(Some(BudgetUnit::Tokens)) => rest::BudgetUnit::Tokens,
+            Some(None) => return Err(Status::invalid_argument("Unknown budget unit")),
+        };
+
+        Ok(Self {
+            key: json_path_from_proto(&value.key)?,
+            limit: value.limit as usize,
+            unit,
+        })
+    }
+}
+
+impl TryFrom<Rerank> for rest::Rerank {
+    type Error = Status;
+
+    fn try_from(value: Rerank) -> Result<Self, Self::Error> {
+        let Rerank { query, fields, top } = value;
+
+        Ok(Self {
+            query,
+            fields: fields
+                .iter()
+                .map(|field| json_path_from_proto(field))
+                .collect::<Result<_, _>>()?,
+            top: top.map(|top| top as usize),
+        })
+    }
+}
+
+impl TryFrom<FilterTemplateReference> for rest::FilterTemplateReference {
+    type Error = Status;
+
+    fn try_from(value: FilterTemplateReference) -> Result<Self, Self::Error> {
+        let FilterTemplateReference { name, params } = value;
+
+        Ok(Self {
+            name,
+            params: params
+                .into_iter()
+                .map(|(name, value)| Ok((name, proto_to_json(value)?)))
+                .collect::<Result<_, Status>>()?,
+        })
+    }
+}
+
 impl From<LookupLocation> for rest::LookupLocation {
     fn from(value: LookupLocation) -> Self {
         Self {
@@ -15,4 +15,7 @@ pub const fn api_crate_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
+/// Key of the gRPC status metadata with the [`models::ErrorCode`] of the error
+pub const ERROR_CODE_METADATA_KEY: &str = "qdrant-error-code";
+
 pub const QDRANT_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("qdrant_descriptor");
@@ -32,8 +32,96 @@ impl Default for VersionInfo {
 #[serde(rename_all = "snake_case")]
 pub enum ApiStatus {
     Ok,
-    Error(String),
     Accepted,
+    #[serde(untagged)]
+    Error(ApiError),
+}
+
+impl ApiStatus {
+    pub fn error(error: impl Into<String>, code: ErrorCode) -> Self {
+        ApiStatus::Error(ApiError {
+            error: error.into(),
+            code,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiError {
+    /// Description of the occurred error
+    pub error: String,
+    /// Machine-readable code of the error
+    pub code: ErrorCode,
+}
+
+/// Stable, machine-readable code of an error, returned by both REST and gRPC APIs.
+///
+/// In gRPC responses, it is given in the `qdrant-error-code` metadata of the status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// The request is malformed or contains invalid values
+    BadRequest,
+    /// The requested collection, point, alias or other entity doesn't exist
+    NotFound,
+    /// The entity to create already exists
+    AlreadyExists,
+    /// The request is not permitted with the given credentials
+    Forbidden,
+    /// Writes are locked on this node
+    Locked,
+    /// The operation didn't complete in time
+    Timeout,
+    /// A checksum of transferred data doesn't match the expected one
+    ChecksumMismatch,
+    /// The system is not in a state which allows the operation
+    PreconditionFailed,
+    /// Unexpected internal error of the service
+    InternalError,
+    /// Not enough replicas of a shard are active, retrying later may succeed
+    ShardUnavailable,
+    /// The operation requires a payload index which doesn't exist
+    PayloadIndexMissing,
+    /// The dimension of a vector doesn't match the configuration of the collection
+    VectorDimMismatch,
+}
+
+impl ErrorCode {
+    const ALL: [ErrorCode; 12] = [
+        ErrorCode::BadRequest,
+        ErrorCode::NotFound,
+        ErrorCode::AlreadyExists,
+        ErrorCode::Forbidden,
+        ErrorCode::Locked,
+        ErrorCode::Timeout,
+        ErrorCode::ChecksumMismatch,
+        ErrorCode::PreconditionFailed,
+        ErrorCode::InternalError,
+        ErrorCode::ShardUnavailable,
+        ErrorCode::PayloadIndexMissing,
+        ErrorCode::VectorDimMismatch,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::AlreadyExists => "ALREADY_EXISTS",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::Locked => "LOCKED",
+            ErrorCode::Timeout => "TIMEOUT",
+            ErrorCode::ChecksumMismatch => "CHECKSUM_MISMATCH",
+            ErrorCode::PreconditionFailed => "PRECONDITION_FAILED",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::ShardUnavailable => "SHARD_UNAVAILABLE",
+            ErrorCode::PayloadIndexMissing => "PAYLOAD_INDEX_MISSING",
+            ErrorCode::VectorDimMismatch => "VECTOR_DIM_MISMATCH",
+        }
+    }
+
+    pub fn parse(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|known| known.as_str() == code)
+    }
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -43,6 +131,12 @@ pub struct ApiResponse<D> {
     pub result: Option<D>,
     pub status: ApiStatus,
     pub time: f64,
+    /// Warnings about the request, which was processed nevertheless
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Work done to execute the request. Only present if requested with `with_stats`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<segment::types::QueryStats>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -56,3 +150,33 @@ pub struct CollectionDescription {
 pub struct CollectionsResponse {
     pub collections: Vec<CollectionDescription>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code_names() {
+        for code in ErrorCode::ALL {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+            assert_eq!(ErrorCode::parse(code.as_str()), Some(code));
+        }
+        assert_eq!(ErrorCode::parse("UNKNOWN"), None);
+    }
+
+    #[test]
+    fn test_api_error_status() {
+        let status = ApiStatus::error(
+            "Not found: Collection `test` doesn't exist!",
+            ErrorCode::NotFound,
+        );
+        assert_eq!(
+            serde_json::to_value(status).unwrap(),
+            serde_json::json!({
+                "error": "Not found: Collection `test` doesn't exist!",
+                "code": "NOT_FOUND",
+            }),
+        );
+        assert_eq!(serde_json::to_value(ApiStatus::Ok).unwrap(), "ok");
+    }
+}
@@ -27,6 +27,9 @@ pub struct VectorParams {
     /// Configuration for multi-vector search
     #[prost(message, optional, tag = "7")]
     pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Checks and transformations applied to the vectors when they are written
+    #[prost(message, optional, tag = "8")]
+    pub ingest: ::core::option::Option<VectorIngestPolicy>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -44,6 +47,9 @@ pub struct VectorParamsDiff {
     /// If true - serve vectors from disk. If set to false, the vectors will be loaded in RAM.
     #[prost(bool, optional, tag = "3")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Update the policy applied to the vectors when they are written. If none - it is left unchanged.
+    #[prost(message, optional, tag = "4")]
+    pub ingest: ::core::option::Option<VectorIngestPolicy>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -137,6 +143,20 @@ pub struct MultiVectorConfig {
     #[prost(enumeration = "MultiVectorComparator", tag = "1")]
     pub comparator: i32,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorIngestPolicy {
+    /// If true - normalize vectors to unit length before they are stored
+    #[prost(bool, tag = "1")]
+    pub normalize: bool,
+    /// If true - reject vectors with NaN or infinite values
+    #[prost(bool, tag = "2")]
+    pub reject_non_finite: bool,
+    /// If true - reject vectors with all values equal to zero
+    #[prost(bool, tag = "3")]
+    pub reject_zero: bool,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -499,6 +519,10 @@ pub struct CreateCollection {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "16")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Pairs of a dense and a sparse vector, which queries target by one name
+    #[prost(message, optional, tag = "17")]
+    #[validate]
+    pub hybrid_vectors_config: ::core::option::Option<HybridVectorParamsMap>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -592,6 +616,27 @@ pub struct CollectionParams {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "10")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Number of versions of each updated point to keep in memory. History is not persisted
+    #[prost(uint32, optional, tag = "11")]
+    pub point_history_size: ::core::option::Option<u32>,
+    /// Choose the vector to search with from a payload field of the points
+    #[prost(message, optional, tag = "12")]
+    pub vector_routing: ::core::option::Option<VectorRouting>,
+    /// If true - the collection is compacted and indexed, and points can't be updated
+    #[prost(bool, tag = "13")]
+    pub frozen: bool,
+    /// Filters with placeholders for parameters, which queries reference by name
+    #[prost(message, optional, tag = "14")]
+    pub filter_templates: ::core::option::Option<FilterTemplates>,
+    /// Pairs of a dense and a sparse vector, by the name queries target them with
+    #[prost(message, optional, tag = "15")]
+    pub hybrid_vectors: ::core::option::Option<HybridVectors>,
+    /// Reuse of memory between searches
+    #[prost(message, optional, tag = "16")]
+    pub search_pools: ::core::option::Option<SearchPoolsConfig>,
+    /// Compression of large payload fields in storage
+    #[prost(message, optional, tag = "17")]
+    pub payload_compression: ::core::option::Option<PayloadCompressionConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -610,6 +655,125 @@ pub struct CollectionParamsDiff {
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
     #[prost(uint32, optional, tag = "4")]
     pub read_fan_out_factor: ::core::option::Option<u32>,
+    /// Number of versions of each updated point to keep in memory, 0 to disable point history. History is not persisted
+    #[prost(uint32, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u32_range_max_100")]
+    pub point_history_size: ::core::option::Option<u32>,
+    /// Choose the vector to search with from a payload field of the points, e.g. one vector per language
+    #[prost(message, optional, tag = "6")]
+    pub vector_routing: ::core::option::Option<VectorRouting>,
+    /// If true - compact and index the collection, and forbid updates of points
+    #[prost(bool, optional, tag = "7")]
+    pub frozen: ::core::option::Option<bool>,
+    /// Filters with placeholders for parameters, which queries reference by name. Replaces all templates.
+    #[prost(message, optional, tag = "8")]
+    pub filter_templates: ::core::option::Option<FilterTemplates>,
+    /// Reuse of memory between searches. Replaces all settings.
+    #[prost(message, optional, tag = "9")]
+    pub search_pools: ::core::option::Option<SearchPoolsConfig>,
+    /// Compression of large payload fields in storage. Replaces the compression of the collection.
+    #[prost(message, optional, tag = "10")]
+    pub payload_compression: ::core::option::Option<PayloadCompressionConfig>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorRouting {
+    /// Payload field selecting the vector, e.g. `lang`
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Name of the vector for each value of the field. Empty to disable routing.
+    #[prost(map = "string, string", tag = "2")]
+    pub vectors: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FilterTemplate {
+    /// Filter with `{"$param": "<name>"}` in place of the values of parameters
+    #[prost(map = "string, message", tag = "1")]
+    pub filter: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FilterTemplates {
+    /// Filter templates by name
+    #[prost(map = "string, message", tag = "1")]
+    pub map: ::std::collections::HashMap<::prost::alloc::string::String, FilterTemplate>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HybridVectorParams {
+    /// Params of the dense vector
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub dense: ::core::option::Option<VectorParams>,
+    /// Params of the sparse vector
+    #[prost(message, optional, tag = "2")]
+    pub sparse: ::core::option::Option<SparseVectorParams>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HybridVectorParamsMap {
+    /// Hybrid vectors by name, each adds the vectors `<name>-dense` and `<name>-sparse`
+    #[prost(map = "string, message", tag = "1")]
+    #[validate]
+    pub map: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        HybridVectorParams,
+    >,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HybridVector {
+    /// Name of the dense vector of the pair
+    #[prost(string, tag = "1")]
+    pub dense: ::prost::alloc::string::String,
+    /// Name of the sparse vector of the pair
+    #[prost(string, tag = "2")]
+    pub sparse: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HybridVectors {
+    /// Hybrid vectors by name
+    #[prost(map = "string, message", tag = "1")]
+    pub map: ::std::collections::HashMap<::prost::alloc::string::String, HybridVector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchPoolsConfig {
+    /// Maximal number of visited lists kept for reuse by the HNSW index of each segment. If 0 - lists are not reused.
+    #[prost(uint64, optional, tag = "1")]
+    pub visited_pool_size: ::core::option::Option<u64>,
+    /// Reuse the score buffers of sparse vector search contexts between searches. Default: true
+    #[prost(bool, optional, tag = "2")]
+    pub reuse_search_contexts: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadCompressionConfig {
+    /// Top-level payload fields to compress. Fields of a point are compressed together.
+    #[prost(string, repeated, tag = "1")]
+    pub fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Zstd compression level, from 1 (fastest) to 22 (smallest). Default: 3
+    #[prost(int32, optional, tag = "2")]
+    pub level: ::core::option::Option<i32>,
+    /// Store the fields uncompressed if they take fewer bytes than this. Default: 128
+    #[prost(uint64, optional, tag = "3")]
+    pub min_size: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -651,6 +815,9 @@ pub struct TextIndexParams {
     /// Maximal token length
     #[prost(uint64, optional, tag = "4")]
     pub max_token_len: ::core::option::Option<u64>,
+    /// Name of the tokenizer to use with the `Custom` tokenizer type
+    #[prost(string, optional, tag = "5")]
+    pub custom_tokenizer: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -834,6 +1001,41 @@ pub struct ListAliasesResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAliasHistoryRequest {
+    /// Name of the alias
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub alias_name: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AliasChange {
+    /// Collection the alias points to since the change, not set if the alias was removed
+    #[prost(string, optional, tag = "1")]
+    pub collection_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Time of the change, unknown for aliases removed together with their collection
+    #[prost(message, optional, tag = "2")]
+    pub changed_at: ::core::option::Option<::prost_wkt_types::Timestamp>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAliasHistoryResponse {
+    /// Name of the alias
+    #[prost(string, tag = "1")]
+    pub alias_name: ::prost::alloc::string::String,
+    /// Changes of the collection the alias points to, oldest first
+    #[prost(message, repeated, tag = "2")]
+    pub changes: ::prost::alloc::vec::Vec<AliasChange>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CollectionClusterInfoRequest {
     /// Name of the collection
     #[prost(string, tag = "1")]
@@ -893,6 +1095,12 @@ pub struct RemoteShardInfo {
     /// User-defined shard key
     #[prost(message, optional, tag = "4")]
     pub shard_key: ::core::option::Option<ShardKey>,
+    /// Number of operations applied by the replica of this peer, but not yet by this replica
+    #[prost(uint64, optional, tag = "5")]
+    pub replication_lag: ::core::option::Option<u64>,
+    /// If true - the replication lag exceeds the configured threshold, the replica may be stuck. Reads are routed to other replicas first.
+    #[prost(bool, tag = "6")]
+    pub degraded: bool,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1427,6 +1635,8 @@ pub enum TokenizerType {
     Whitespace = 2,
     Word = 3,
     Multilingual = 4,
+    /// Tokenizer built into the server, selected by `custom_tokenizer`
+    Custom = 5,
 }
 impl TokenizerType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1440,6 +1650,7 @@ impl TokenizerType {
             TokenizerType::Whitespace => "Whitespace",
             TokenizerType::Word => "Word",
             TokenizerType::Multilingual => "Multilingual",
+            TokenizerType::Custom => "Custom",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1450,6 +1661,7 @@ impl TokenizerType {
             "Whitespace" => Some(Self::Whitespace),
             "Word" => Some(Self::Word),
             "Multilingual" => Some(Self::Multilingual),
+            "Custom" => Some(Self::Custom),
             _ => None,
         }
     }
@@ -1837,6 +2049,33 @@ pub mod collections_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Get changes of the collection the alias points to
+        pub async fn get_alias_history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetAliasHistoryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAliasHistoryResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/GetAliasHistory",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "GetAliasHistory"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Get cluster information for a collection
         pub async fn collection_cluster_info(
             &mut self,
@@ -2055,6 +2294,15 @@ pub mod collections_server {
             tonic::Status,
         >;
         ///
+        /// Get changes of the collection the alias points to
+        async fn get_alias_history(
+            &self,
+            request: tonic::Request<super::GetAliasHistoryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAliasHistoryResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Get cluster information for a collection
         async fn collection_cluster_info(
             &self,
@@ -2548,6 +2796,52 @@ pub mod collections_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Collections/GetAliasHistory" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAliasHistorySvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::GetAliasHistoryRequest>
+                    for GetAliasHistorySvc<T> {
+                        type Response = super::GetAliasHistoryResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetAliasHistoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::get_alias_history(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetAliasHistorySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Collections/CollectionClusterInfo" => {
                     #[allow(non_camel_case_types)]
                     struct CollectionClusterInfoSvc<T: Collections>(pub Arc<T>);
@@ -2930,6 +3224,39 @@ pub struct UpdateShardCutoffPointRequest {
     #[prost(message, optional, tag = "3")]
     pub cutoff: ::core::option::Option<RecoveryPoint>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionClock {
+    /// Peer of the clock, which coordinated the write
+    #[prost(uint64, tag = "1")]
+    pub peer_id: u64,
+    /// Id of the clock
+    #[prost(uint32, tag = "2")]
+    pub clock_id: u32,
+    /// Tick the write was applied with
+    #[prost(uint64, tag = "3")]
+    pub clock_tick: u64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitForSessionClocksRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Clocks of the writes to wait for
+    #[prost(message, repeated, tag = "3")]
+    pub clocks: ::prost::alloc::vec::Vec<SessionClock>,
+    /// Timeout in milliseconds
+    #[prost(uint64, tag = "4")]
+    pub timeout_ms: u64,
+}
 /// Generated client implementations.
 pub mod collections_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -3162,6 +3489,38 @@ pub mod collections_internal_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        ///
+        /// Wait until the writes of a session token are applied to the shard
+        pub async fn wait_for_session_clocks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WaitForSessionClocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/WaitForSessionClocks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "qdrant.CollectionsInternal",
+                        "WaitForSessionClocks",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -3216,6 +3575,15 @@ pub mod collections_internal_server {
             tonic::Response<super::CollectionOperationResponse>,
             tonic::Status,
         >;
+        ///
+        /// Wait until the writes of a session token are applied to the shard
+        async fn wait_for_session_clocks(
+            &self,
+            request: tonic::Request<super::WaitForSessionClocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct CollectionsInternalServer<T: CollectionsInternal> {
@@ -3541,6 +3909,56 @@ pub mod collections_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.CollectionsInternal/WaitForSessionClocks" => {
+                    #[allow(non_camel_case_types)]
+                    struct WaitForSessionClocksSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::WaitForSessionClocksRequest>
+                    for WaitForSessionClocksSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WaitForSessionClocksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::wait_for_session_clocks(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WaitForSessionClocksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -3783,7 +4201,7 @@ pub struct MultiDenseVector {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct VectorInput {
-    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4, 5")]
     pub variant: ::core::option::Option<vector_input::Variant>,
 }
 /// Nested message and enum types in `VectorInput`.
@@ -3800,8 +4218,29 @@ pub mod vector_input {
         Sparse(super::SparseVector),
         #[prost(message, tag = "4")]
         MultiDense(super::MultiDenseVector),
+        /// Weighted sum of vectors, computed before the query is performed
+        #[prost(message, tag = "5")]
+        Sum(super::VectorSum),
     }
 }
+/// Weighted sum of vectors, e.g. vec(A) - vec(B) + vec(C)
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorSum {
+    #[prost(message, repeated, tag = "1")]
+    pub terms: ::prost::alloc::vec::Vec<WeightedVectorInput>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedVectorInput {
+    #[prost(message, optional, tag = "1")]
+    pub vector: ::core::option::Option<VectorInput>,
+    /// Weight of the vector in the sum. Default is 1
+    #[prost(float, optional, tag = "2")]
+    pub weight: ::core::option::Option<f32>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -3878,6 +4317,12 @@ pub struct GetPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "7")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Return points as they were at the given version, requires point history to be enabled
+    #[prost(uint64, optional, tag = "8")]
+    pub as_of_version: ::core::option::Option<u64>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "9")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4196,6 +4641,7 @@ pub struct SearchParams {
     pub exact: ::core::option::Option<bool>,
     ///
     /// If set to true, search will ignore quantized vector data
+    /// If not set, qdrant decides whether to use quantized vectors, depending on the number of points matching the filter
     #[prost(message, optional, tag = "3")]
     #[validate]
     pub quantization: ::core::option::Option<QuantizationSearchParams>,
@@ -4205,6 +4651,12 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[prost(bool, optional, tag = "4")]
     pub indexed_only: ::core::option::Option<bool>,
+    ///
+    /// If enabled, points found by a nearest search on a cosine vector also get a `raw_score`:
+    /// the inner product with the query before both vectors were normalized.
+    /// Points inserted before raw scores were supported get none.
+    #[prost(bool, optional, tag = "5")]
+    pub with_raw_score: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4257,6 +4709,12 @@ pub struct SearchPoints {
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
     #[prost(message, optional, tag = "15")]
     pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "16")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// If true - return the work done to execute the request. Default is false.
+    #[prost(bool, optional, tag = "17")]
+    pub with_stats: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4277,6 +4735,9 @@ pub struct SearchBatchPoints {
     #[prost(uint64, optional, tag = "4")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "5")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4296,6 +4757,28 @@ pub struct WithLookup {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithNeighbors {
+    /// Payload field with the position of the point in its group, must be a number field
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub ordinal_by: ::prost::alloc::string::String,
+    /// Number of points to take before and after the best point of each group
+    #[prost(uint32, tag = "2")]
+    #[validate(range(min = 1, max = 100))]
+    pub neighbors: u32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithAggregates {
+    /// Numeric payload fields to compute the mean of, over all points of each group matching the filter
+    #[prost(string, repeated, tag = "1")]
+    pub mean: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchPointGroups {
     /// Name of the collection
     #[prost(string, tag = "1")]
@@ -4351,6 +4834,19 @@ pub struct SearchPointGroups {
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
     #[prost(message, optional, tag = "16")]
     pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// Return the best point of each group along with its neighbors
+    #[prost(message, optional, tag = "17")]
+    #[validate]
+    pub with_neighbors: ::core::option::Option<WithNeighbors>,
+    /// Return aggregates over all points of each group matching the filter
+    #[prost(message, optional, tag = "18")]
+    pub with_aggregates: ::core::option::Option<WithAggregates>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "19")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// If true - return the work done to execute the request. Default is false.
+    #[prost(bool, optional, tag = "20")]
+    pub with_stats: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4423,6 +4919,9 @@ pub struct ScrollPoints {
     /// Order the records by a payload field
     #[prost(message, optional, tag = "10")]
     pub order_by: ::core::option::Option<OrderBy>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "11")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4502,6 +5001,15 @@ pub struct RecommendPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "20")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If true - examples referencing points which don't exist are skipped, instead of failing the request. Default is false.
+    #[prost(bool, optional, tag = "21")]
+    pub skip_missing: ::core::option::Option<bool>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "22")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// If true - return the work done to execute the request. Default is false.
+    #[prost(bool, optional, tag = "23")]
+    pub with_stats: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4522,6 +5030,9 @@ pub struct RecommendBatchPoints {
     #[prost(uint64, optional, tag = "4")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "5")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4597,6 +5108,19 @@ pub struct RecommendPointGroups {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "21")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Return the best point of each group along with its neighbors
+    #[prost(message, optional, tag = "22")]
+    #[validate]
+    pub with_neighbors: ::core::option::Option<WithNeighbors>,
+    /// Return aggregates over all points of each group matching the filter
+    #[prost(message, optional, tag = "23")]
+    pub with_aggregates: ::core::option::Option<WithAggregates>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "24")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// If true - return the work done to execute the request. Default is false.
+    #[prost(bool, optional, tag = "25")]
+    pub with_stats: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4695,6 +5219,12 @@ pub struct DiscoverPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "14")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "15")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// If true - return the work done to execute the request. Default is false.
+    #[prost(bool, optional, tag = "16")]
+    pub with_stats: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4715,6 +5245,9 @@ pub struct DiscoverBatchPoints {
     #[prost(uint64, optional, tag = "4")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "5")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4738,6 +5271,9 @@ pub struct CountPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Token returned by writes which the request must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "6")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4786,8 +5322,22 @@ pub struct ContextInput {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HybridInput {
+    /// Find the nearest neighbors to this vector with the dense vector of the pair
+    #[prost(message, optional, tag = "1")]
+    pub dense: ::core::option::Option<VectorInput>,
+    /// Find the nearest neighbors to this vector with the sparse vector of the pair
+    #[prost(message, optional, tag = "2")]
+    pub sparse: ::core::option::Option<VectorInput>,
+    /// Fusion of the nearest neighbors of both vectors. Default is RRF
+    #[prost(enumeration = "Fusion", optional, tag = "3")]
+    pub fusion: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Query {
-    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6")]
+    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7")]
     pub variant: ::core::option::Option<query::Variant>,
 }
 /// Nested message and enum types in `Query`.
@@ -4814,6 +5364,9 @@ pub mod query {
         /// Fuse the results of multiple prefetches.
         #[prost(enumeration = "super::Fusion", tag = "6")]
         Fusion(i32),
+        /// Search the hybrid vector named in `using`, fusing the nearest neighbors of both its vectors.
+        #[prost(message, tag = "7")]
+        Hybrid(super::HybridInput),
     }
 }
 #[derive(serde::Serialize)]
@@ -4844,6 +5397,57 @@ pub struct PrefetchQuery {
     /// The location to use for IDs lookup, if not specified - use the current collection and the 'using' vector
     #[prost(message, optional, tag = "8")]
     pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Specify in which shards to perform this prefetch, if not specified - use the shards of the request. Only allowed in prefetches of a fusion query
+    #[prost(message, optional, tag = "9")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextBudget {
+    /// Payload field with the text of each result
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Maximum total size of the texts of the returned results
+    #[prost(uint64, tag = "2")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Unit of the size of the texts. Default is characters.
+    #[prost(enumeration = "BudgetUnit", optional, tag = "3")]
+    pub unit: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Rerank {
+    /// Text of the query to score the results against
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub query: ::prost::alloc::string::String,
+    /// Payload fields with the text of each result. Texts of several fields are joined with new lines.
+    #[prost(string, repeated, tag = "2")]
+    #[validate(length(min = 1))]
+    pub fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Number of top results to rerank, the rest keep their order after them. Default is all results.
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub top: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FilterTemplateReference {
+    /// Name of the filter template of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub name: ::prost::alloc::string::String,
+    /// Values of the parameters of the template, by name
+    #[prost(map = "string, message", tag = "2")]
+    pub params: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4900,6 +5504,36 @@ pub struct QueryPoints {
     #[prost(uint64, optional, tag = "15")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// Apply the filter to prefetches as well. If false, the filter is only applied when rescoring prefetched points. Default is true.
+    #[prost(bool, optional, tag = "16")]
+    pub propagate_filter: ::core::option::Option<bool>,
+    /// Only consider points with these IDs, e.g. to rescore candidates retrieved elsewhere. Applied together with the filter.
+    #[prost(message, repeated, tag = "17")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+    /// Return only the first results whose texts fit in this budget, e.g. the context window of a language model
+    #[prost(message, optional, tag = "18")]
+    #[validate]
+    pub budget: ::core::option::Option<TextBudget>,
+    /// Rerank the top results with the external reranker configured on the server, e.g. a cross-encoder
+    #[prost(message, optional, tag = "19")]
+    #[validate]
+    pub rerank: ::core::option::Option<Rerank>,
+    /// If true - examples referencing points which don't exist are skipped, instead of failing the request. Default is false.
+    #[prost(bool, optional, tag = "20")]
+    pub skip_missing: ::core::option::Option<bool>,
+    /// Token returned by writes which the query must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "21")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// Filter template of the collection to apply, with the values of its parameters. Applied together with the filter, and to all prefetches.
+    #[prost(message, optional, tag = "22")]
+    #[validate]
+    pub filter_template: ::core::option::Option<FilterTemplateReference>,
+    /// If true - return the shard and the replica each point was found in. Default is false.
+    #[prost(bool, optional, tag = "23")]
+    pub with_routing: ::core::option::Option<bool>,
+    /// If true - return the work done to execute the request. Default is false.
+    #[prost(bool, optional, tag = "24")]
+    pub with_stats: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4919,6 +5553,9 @@ pub struct QueryBatchPoints {
     #[prost(uint64, optional, tag = "4")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// Token returned by writes which the query must observe. Shards written to are read from a replica which applied the writes.
+    #[prost(string, optional, tag = "5")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5106,6 +5743,9 @@ pub struct UpdateResult {
     /// Operation status
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
+    /// Token of the write, to attach to subsequent reads which must observe it
+    #[prost(string, optional, tag = "3")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5129,6 +5769,34 @@ pub mod order_value {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PrefetchMatch {
+    /// Index of the prefetch in the request
+    #[prost(uint64, tag = "1")]
+    pub prefetch: u64,
+    /// Position of the point in the results of the prefetch, starting from 0
+    #[prost(uint64, tag = "2")]
+    pub rank: u64,
+    /// Score of the point in the results of the prefetch
+    #[prost(float, tag = "3")]
+    pub score: f32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointRouting {
+    /// Shard key of the shard, if the collection uses custom sharding
+    #[prost(message, optional, tag = "1")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Id of the peer of the replica which returned the point
+    #[prost(uint64, tag = "3")]
+    pub peer_id: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScoredPoint {
     /// Point id
     #[prost(message, optional, tag = "1")]
@@ -5151,6 +5819,15 @@ pub struct ScoredPoint {
     /// Order by value
     #[prost(message, optional, tag = "8")]
     pub order_value: ::core::option::Option<OrderValue>,
+    /// Results of the fused prefetches this point was found in. Only present for fusion queries
+    #[prost(message, repeated, tag = "9")]
+    pub prefetch_matches: ::prost::alloc::vec::Vec<PrefetchMatch>,
+    /// Shard and replica the point was found in. Only present if requested with `with_routing`
+    #[prost(message, optional, tag = "10")]
+    pub routing: ::core::option::Option<PointRouting>,
+    /// Inner product with the query before normalization. Only present if requested with `with_raw_score` on a cosine vector
+    #[prost(float, optional, tag = "11")]
+    pub raw_score: ::core::option::Option<f32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5179,6 +5856,20 @@ pub mod group_id {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupAggregates {
+    /// Number of points in the group matching the filter
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    /// Score of the best hit in the group
+    #[prost(float, tag = "2")]
+    pub best_score: f32,
+    /// Mean of each requested payload field, over the points of the group with numeric values of it
+    #[prost(map = "string, double", tag = "3")]
+    pub mean: ::std::collections::HashMap<::prost::alloc::string::String, f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PointGroup {
     /// Group id
     #[prost(message, optional, tag = "1")]
@@ -5189,6 +5880,12 @@ pub struct PointGroup {
     /// Point(s) from the lookup collection that matches the group id
     #[prost(message, optional, tag = "3")]
     pub lookup: ::core::option::Option<RetrievedPoint>,
+    /// Best point of the group along with its neighbors, ordered by the ordinal field
+    #[prost(message, repeated, tag = "4")]
+    pub neighbors: ::prost::alloc::vec::Vec<RetrievedPoint>,
+    /// Aggregates over the hits of the group, if requested
+    #[prost(message, optional, tag = "5")]
+    pub aggregates: ::core::option::Option<GroupAggregates>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5207,6 +5904,12 @@ pub struct SearchResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the request, if requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5217,6 +5920,12 @@ pub struct QueryResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the request, if requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5227,6 +5936,9 @@ pub struct QueryBatchResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5234,6 +5946,46 @@ pub struct QueryBatchResponse {
 pub struct BatchResult {
     #[prost(message, repeated, tag = "1")]
     pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Work done to execute the query, if requested with `with_stats`. Only returned by the query API.
+    #[prost(message, optional, tag = "2")]
+    pub stats: ::core::option::Option<QueryStats>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SegmentSearchStats {
+    /// Id of the segment
+    #[prost(string, tag = "1")]
+    pub segment: ::prost::alloc::string::String,
+    /// How the segment was searched
+    #[prost(enumeration = "SegmentSearchMode", tag = "2")]
+    pub mode: i32,
+    /// Number of searches of the segment in this mode
+    #[prost(uint64, tag = "3")]
+    pub searches: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryStats {
+    /// Number of vector scores computed, including rescoring with original vectors
+    #[prost(uint64, tag = "1")]
+    pub vectors_scored: u64,
+    /// Number of points checked against the filter one by one
+    #[prost(uint64, tag = "2")]
+    pub filter_checks: u64,
+    /// Number of segment searches, which scored the points without the HNSW graph
+    #[prost(uint64, tag = "3")]
+    pub plain_searches: u64,
+    /// Number of segment searches, which traversed the HNSW graph
+    #[prost(uint64, tag = "4")]
+    pub hnsw_searches: u64,
+    /// Number of segment searches of sparse vectors
+    #[prost(uint64, tag = "5")]
+    pub sparse_searches: u64,
+    /// How each of the searched segments was searched
+    #[prost(message, repeated, tag = "6")]
+    pub segments: ::prost::alloc::vec::Vec<SegmentSearchStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5244,6 +5996,12 @@ pub struct SearchBatchResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the requests of the batch, if any of them is requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5254,6 +6012,12 @@ pub struct SearchGroupsResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the request, if requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5301,6 +6065,9 @@ pub struct RetrievedPoint {
     /// Order-by value
     #[prost(message, optional, tag = "6")]
     pub order_value: ::core::option::Option<OrderValue>,
+    /// Point version
+    #[prost(uint64, optional, tag = "7")]
+    pub version: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5321,6 +6088,12 @@ pub struct RecommendResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the request, if requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5331,6 +6104,12 @@ pub struct RecommendBatchResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the requests of the batch, if any of them is requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5341,6 +6120,12 @@ pub struct DiscoverResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the request, if requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5351,6 +6136,12 @@ pub struct DiscoverBatchResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the requests of the batch, if any of them is requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5361,6 +6152,12 @@ pub struct RecommendGroupsResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Warnings about the query limits the request exceeds, if it was executed nevertheless
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Work done to execute the request, if requested with `with_stats`
+    #[prost(message, optional, tag = "4")]
+    pub stats: ::core::option::Option<QueryStats>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5860,6 +6657,8 @@ impl RecommendStrategy {
 pub enum Fusion {
     /// Reciprocal Rank Fusion
     Rrf = 0,
+    /// Distribution-Based Score Fusion, experimental: must be enabled for the collection
+    Dbsf = 1,
 }
 impl Fusion {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5869,12 +6668,43 @@ impl Fusion {
     pub fn as_str_name(&self) -> &'static str {
         match self {
             Fusion::Rrf => "RRF",
+            Fusion::Dbsf => "DBSF",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
             "RRF" => Some(Self::Rrf),
+            "DBSF" => Some(Self::Dbsf),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum BudgetUnit {
+    /// Number of characters
+    Chars = 0,
+    /// Number of words and numbers, an approximation of the tokens of language models
+    Tokens = 1,
+}
+impl BudgetUnit {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            BudgetUnit::Chars => "Chars",
+            BudgetUnit::Tokens => "Tokens",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Chars" => Some(Self::Chars),
+            "Tokens" => Some(Self::Tokens),
             _ => None,
         }
     }
@@ -5915,6 +6745,39 @@ impl UpdateStatus {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SegmentSearchMode {
+    /// Points were scored one by one, without the HNSW graph
+    Plain = 0,
+    /// HNSW graph was traversed
+    Hnsw = 1,
+    /// Inverted index of sparse vectors was used
+    Sparse = 2,
+}
+impl SegmentSearchMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SegmentSearchMode::Plain => "Plain",
+            SegmentSearchMode::Hnsw => "Hnsw",
+            SegmentSearchMode::Sparse => "Sparse",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Plain" => Some(Self::Plain),
+            "Hnsw" => Some(Self::Hnsw),
+            "Sparse" => Some(Self::Sparse),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -8066,6 +8929,9 @@ pub struct SyncPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8079,6 +8945,9 @@ pub struct UpsertPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8092,6 +8961,9 @@ pub struct DeletePointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8105,6 +8977,9 @@ pub struct UpdateVectorsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8118,6 +8993,9 @@ pub struct DeleteVectorsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8131,6 +9009,9 @@ pub struct SetPayloadPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8144,6 +9025,9 @@ pub struct DeletePayloadPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8157,6 +9041,9 @@ pub struct ClearPayloadPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8172,6 +9059,9 @@ pub struct CreateFieldIndexCollectionInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8187,6 +9077,9 @@ pub struct DeleteFieldIndexCollectionInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Unique id of the update, to not apply it again when retried
+    #[prost(uint64, optional, tag = "4")]
+    pub update_id: ::core::option::Option<u64>,
 }
 /// Has to be backward compatible with `PointsOperationResponse`!
 #[derive(serde::Serialize)]
@@ -8212,6 +9105,8 @@ pub struct UpdateResultInternal {
     pub status: i32,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    #[prost(string, optional, tag = "4")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -8375,6 +9270,9 @@ pub struct CoreSearchBatchPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(uint64, optional, tag = "4")]
     pub timeout: ::core::option::Option<u64>,
+    /// Count the work done to execute the searches
+    #[prost(bool, optional, tag = "5")]
+    pub with_stats: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8528,6 +9426,15 @@ pub struct QueryShardPoints {
     pub with_payload: ::core::option::Option<WithPayloadSelector>,
     #[prost(message, optional, tag = "10")]
     pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Apply the filter to prefetches as well. Default is true.
+    #[prost(bool, optional, tag = "11")]
+    pub propagate_filter: ::core::option::Option<bool>,
+    /// Only this shard is queried, so it can merge the prefetches itself instead of returning the results of each of them
+    #[prost(bool, optional, tag = "12")]
+    pub single_shard: ::core::option::Option<bool>,
+    /// The payload and vectors of the fused points are fetched after fusion, so the results of each prefetch are returned without them
+    #[prost(bool, optional, tag = "13")]
+    pub defer_fusion_payload: ::core::option::Option<bool>,
 }
 /// Nested message and enum types in `QueryShardPoints`.
 pub mod query_shard_points {
@@ -8535,7 +9442,7 @@ pub mod query_shard_points {
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Query {
-        #[prost(oneof = "query::Score", tags = "1, 2, 3")]
+        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4")]
         pub score: ::core::option::Option<query::Score>,
     }
     /// Nested message and enum types in `Query`.
@@ -8553,6 +9460,9 @@ pub mod query_shard_points {
             /// Order by a field
             #[prost(message, tag = "3")]
             OrderBy(super::super::OrderBy),
+            /// Random sample of points, picked with this seed
+            #[prost(uint64, tag = "4")]
+            Sample(u64),
         }
     }
     #[derive(serde::Serialize)]
@@ -8590,6 +9500,9 @@ pub struct QueryBatchPointsInternal {
     #[prost(uint64, optional, tag = "4")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// Count the work done to execute the queries
+    #[prost(bool, optional, tag = "5")]
+    pub with_stats: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -8614,6 +9527,71 @@ pub struct QueryBatchResponseInternal {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Work done to execute the queries, if requested
+    #[prost(message, optional, tag = "3")]
+    pub stats: ::core::option::Option<QueryStats>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupAggregatesInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Only points matching the filter are aggregated
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Payload field with the ids of the groups
+    #[prost(string, tag = "3")]
+    pub group_by: ::prost::alloc::string::String,
+    /// Groups to aggregate the points of
+    #[prost(message, repeated, tag = "4")]
+    pub group_ids: ::prost::alloc::vec::Vec<GroupId>,
+    /// Numeric payload fields to compute the mean of
+    #[prost(string, repeated, tag = "5")]
+    pub mean: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag = "6")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldSumInternal {
+    /// Sum of the numeric values of the field
+    #[prost(double, tag = "1")]
+    pub sum: f64,
+    /// Number of the numeric values of the field
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardGroupAggregatesInternal {
+    /// Group id
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<GroupId>,
+    /// Number of points of the group in the shard
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+    /// Sums of the numeric payload fields over the points of the group
+    #[prost(map = "string, message", tag = "3")]
+    pub sums: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        FieldSumInternal,
+    >,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupAggregatesResponseInternal {
+    #[prost(message, repeated, tag = "1")]
+    pub groups: ::prost::alloc::vec::Vec<ShardGroupAggregatesInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 /// Generated client implementations.
 pub mod points_internal_client {
@@ -9115,6 +10093,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "QueryBatch"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn group_aggregates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GroupAggregatesInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::GroupAggregatesResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/GroupAggregates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "GroupAggregates"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -9234,6 +10237,13 @@ pub mod points_internal_server {
             tonic::Response<super::QueryBatchResponseInternal>,
             tonic::Status,
         >;
+        async fn group_aggregates(
+            &self,
+            request: tonic::Request<super::GroupAggregatesInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::GroupAggregatesResponseInternal>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -10106,6 +11116,53 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/GroupAggregates" => {
+                    #[allow(non_camel_case_types)]
+                    struct GroupAggregatesSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::GroupAggregatesInternal>
+                    for GroupAggregatesSvc<T> {
+                        type Response = super::GroupAggregatesResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GroupAggregatesInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::group_aggregates(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GroupAggregatesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
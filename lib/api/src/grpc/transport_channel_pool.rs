@@ -51,6 +51,28 @@ pub enum RequestError<E: std::error::Error> {
     Tonic(#[from] TonicError),
 }
 
+/// Retries of failed requests, which are safe to repeat
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub retries: usize,
+    /// Backoff before the first retry, doubled for each next retry
+    pub backoff: Duration,
+    /// Status codes of failed requests to retry with backoff.
+    /// Failures to connect to the server are always retried.
+    pub retriable_codes: Vec<Code>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+            retriable_codes: vec![Code::Cancelled, Code::Unavailable, Code::Internal],
+        }
+    }
+}
+
 enum RetryAction {
     Fail(Status),
     RetryOnce(Status),
@@ -286,6 +308,59 @@ impl TransportChannelPool {
         f: impl Fn(InterceptedService<Channel, AddTimeout>) -> O,
         timeout: Option<Duration>,
         retries: usize,
+    ) -> Result<T, RequestError<Status>> {
+        self.with_retries(
+            uri,
+            f,
+            timeout,
+            retries,
+            DEFAULT_BACKOFF,
+            default_status_action,
+            |_| (),
+        )
+        .await
+    }
+
+    /// Same as `with_channel_timeout`, but failed requests are retried according to the policy.
+    /// `on_retry` is called with the error of each failed attempt, which is retried.
+    pub async fn with_channel_retry_policy<T, O: Future<Output = Result<T, Status>>>(
+        &self,
+        uri: &Uri,
+        f: impl Fn(InterceptedService<Channel, AddTimeout>) -> O,
+        timeout: Option<Duration>,
+        policy: &RetryPolicy,
+        on_retry: impl Fn(&Status),
+    ) -> Result<T, RequestError<Status>> {
+        let status_action = |status: Status| {
+            if policy.retriable_codes.contains(&status.code()) {
+                RetryAction::RetryWithBackoff(status)
+            } else {
+                RetryAction::Fail(status)
+            }
+        };
+
+        self.with_retries(
+            uri,
+            f,
+            timeout,
+            policy.retries,
+            policy.backoff,
+            status_action,
+            on_retry,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn with_retries<T, O: Future<Output = Result<T, Status>>>(
+        &self,
+        uri: &Uri,
+        f: impl Fn(InterceptedService<Channel, AddTimeout>) -> O,
+        timeout: Option<Duration>,
+        retries: usize,
+        backoff: Duration,
+        status_action: impl Fn(Status) -> RetryAction,
+        on_retry: impl Fn(&Status),
     ) -> Result<T, RequestError<Status>> {
         let mut retries_left = retries;
         let mut attempt = 0;
@@ -329,24 +404,7 @@ impl TransportChannelPool {
                         }
                     }
                 }
-                RequestFailure::RequestError(status) => {
-                    match status.code() {
-                        Code::Cancelled | Code::Unavailable => {
-                            // Possible situations:
-                            // - Server is frozen and will never respond.
-                            // - Server is overloaded and will respond in the future.
-                            RetryAction::RetryWithBackoff(status)
-                        }
-                        Code::Internal => {
-                            // Something is broken, but let's retry anyway, but only once.
-                            RetryAction::RetryOnce(status)
-                        }
-                        _ => {
-                            // No special handling, just fail already.
-                            RetryAction::Fail(status)
-                        }
-                    }
-                }
+                RequestFailure::RequestError(status) => status_action(status),
                 RequestFailure::RequestConnection(error) => {
                     // Can't establish connection to the server during the request.
                     // Possible situation:
@@ -366,7 +424,7 @@ impl TransportChannelPool {
                 RetryAction::RetryImmediately(fallback_status) => (Duration::ZERO, fallback_status),
                 RetryAction::RetryWithBackoff(fallback_status) => {
                     // Calculate backoff
-                    let backoff = DEFAULT_BACKOFF * 2u32.pow(attempt as u32)
+                    let backoff = backoff * 2u32.pow(attempt as u32)
                         + Duration::from_millis(thread_rng().gen_range(0..100));
 
                     if backoff > max_timeout {
@@ -388,6 +446,7 @@ impl TransportChannelPool {
                 return Err(RequestError::FromClosure(fallback_status));
             }
             retries_left = retries_left.saturating_sub(1);
+            on_retry(&fallback_status);
 
             // Wait for the backoff
             tokio::time::sleep(backoff_time).await;
@@ -404,3 +463,23 @@ impl TransportChannelPool {
             .await
     }
 }
+
+/// How to handle errors returned by the server, if no retry policy is given
+fn default_status_action(status: Status) -> RetryAction {
+    match status.code() {
+        Code::Cancelled | Code::Unavailable => {
+            // Possible situations:
+            // - Server is frozen and will never respond.
+            // - Server is overloaded and will respond in the future.
+            RetryAction::RetryWithBackoff(status)
+        }
+        Code::Internal => {
+            // Something is broken, but let's retry anyway, but only once.
+            RetryAction::RetryOnce(status)
+        }
+        _ => {
+            // No special handling, just fail already.
+            RetryAction::Fail(status)
+        }
+    }
+}
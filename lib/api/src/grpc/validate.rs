@@ -279,6 +279,11 @@ pub fn validate_u32_range_min_1(value: &Option<u32>) -> Result<(), ValidationErr
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
 }
 
+/// Validate the value is in `[0, 100]` or `None`.
+pub fn validate_u32_range_max_100(value: &Option<u32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, None, Some(100)))
+}
+
 /// Validate the value is in `[100, ]` or `None`.
 pub fn validate_u64_range_min_100(value: &Option<u64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(100), None))
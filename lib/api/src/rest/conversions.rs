@@ -164,6 +164,9 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             vector: value.vector.map(From::from),
             shard_key: value.shard_key,
             order_value: value.order_value.map(From::from),
+            prefetch_matches: value.prefetch_matches,
+            routing: value.routing,
+            raw_score: value.raw_score,
         }
     }
 }
@@ -178,6 +181,9 @@ impl From<ScoredPoint> for segment::types::ScoredPoint {
             vector: value.vector.map(From::from),
             shard_key: value.shard_key,
             order_value: value.order_value.map(From::from),
+            prefetch_matches: value.prefetch_matches,
+            routing: value.routing,
+            raw_score: value.raw_score,
         }
     }
 }
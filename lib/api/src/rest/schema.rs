@@ -85,6 +85,15 @@ pub struct ScoredPoint {
     /// Order-by value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order_value: Option<segment::data_types::order_by::OrderValue>,
+    /// Results of the fused prefetches this point was found in. Only present for fusion queries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefetch_matches: Option<Vec<segment::types::PrefetchMatch>>,
+    /// Shard and replica the point was found in. Only present if requested with `with_routing`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing: Option<segment::types::PointRouting>,
+    /// Inner product with the query before normalization. Only present if requested with `with_raw_score` on a cosine vector
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_score: Option<ScoreType>,
 }
 
 /// Point data
@@ -141,10 +150,12 @@ pub enum OrderByInterface {
 /// Fusion algorithm allows to combine results of multiple prefetches.
 /// Available fusion algorithms:
 /// * `rrf` - Rank Reciprocal Fusion
+/// * `dbsf` - Distribution-Based Score Fusion, experimental: must be enabled for the collection
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Fusion {
     Rrf,
+    Dbsf,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -154,6 +165,28 @@ pub enum VectorInput {
     SparseVector(SparseVector),
     MultiDenseVector(MultiDenseVector),
     Id(segment::types::PointIdType),
+    Sum(VectorSum),
+}
+
+/// Weighted sum of vectors, computed before the query is performed.
+///
+/// For example, `vec(A) - vec(B) + vec(C)` is
+/// `{"sum": [{"vector": A}, {"vector": B, "weight": -1}, {"vector": C}]}`
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct VectorSum {
+    #[validate]
+    #[validate(length(min = 1, message = "must specify vectors to sum"))]
+    pub sum: Vec<WeightedVectorInput>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct WeightedVectorInput {
+    /// Vector or ID of the point to take the vector from
+    #[validate]
+    pub vector: VectorInput,
+
+    /// Weight of the vector in the sum. Default is 1
+    pub weight: Option<f32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -175,6 +208,17 @@ pub struct QueryRequestInternal {
     #[validate]
     pub filter: Option<Filter>,
 
+    /// Apply the filter to prefetches as well. If false, the filter is only applied when rescoring prefetched points. Default is true.
+    pub propagate_filter: Option<bool>,
+
+    /// Only consider points with these IDs, e.g. to rescore candidates retrieved elsewhere. Applied together with the filter.
+    #[validate(length(min = 1))]
+    pub ids: Option<Vec<segment::types::PointIdType>>,
+
+    /// Filter template of the collection to apply, with the values of its parameters. Applied together with the filter, and to all prefetches.
+    #[validate]
+    pub filter_template: Option<FilterTemplateReference>,
+
     /// Search params for when there is no prefetch
     #[validate]
     pub params: Option<SearchParams>,
@@ -186,7 +230,7 @@ pub struct QueryRequestInternal {
     #[validate(range(min = 1))]
     pub limit: Option<usize>,
 
-    /// Offset of the result. Skip this many points. Default is 0
+    /// Offset of the result. Skip this many points. Default is 0. With a fusion query, each prefetch fetches this many extra points to fuse
     pub offset: Option<usize>,
 
     /// Options for specifying which vectors to include into the response. Default is false.
@@ -195,10 +239,84 @@ pub struct QueryRequestInternal {
     /// Options for specifying which payload to include or not. Default is false.
     pub with_payload: Option<WithPayloadInterface>,
 
+    /// Return the shard and the replica each point was found in, e.g. to debug the placement of data. Default is false.
+    pub with_routing: Option<bool>,
+
+    /// Return the work done to execute the query: vectors scored, filter checks and how the segments were searched. Default is false.
+    pub with_stats: Option<bool>,
+
     /// The location to use for IDs lookup, if not specified - use the current collection and the 'using' vector
     /// Note: the other collection vectors should have the same vector size as the 'using' vector in the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// Return only the first results whose texts fit in this budget, e.g. the context window of a language model
+    #[validate]
+    pub budget: Option<TextBudget>,
+
+    /// Rerank the top results with the external reranker configured on the server, e.g. a cross-encoder
+    #[validate]
+    pub rerank: Option<Rerank>,
+
+    /// If true, examples referencing points which don't exist are skipped, instead of failing the request. Default is false.
+    pub skip_missing: Option<bool>,
+}
+
+/// Budget on the total size of a payload text field across the results of a query.
+/// Results are accumulated in order, until the next one doesn't fit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct TextBudget {
+    /// Payload field with the text of each result
+    #[schemars(length(min = 1))]
+    #[validate(custom = "JsonPath::validate_not_empty")]
+    pub key: JsonPath,
+
+    /// Maximum total size of the texts of the returned results
+    #[validate(range(min = 1))]
+    pub limit: usize,
+
+    /// Unit of the size of the texts. Default is characters.
+    #[serde(default)]
+    pub unit: BudgetUnit,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetUnit {
+    /// Number of characters
+    #[default]
+    Chars,
+    /// Number of words and numbers, an approximation of the tokens of language models
+    Tokens,
+}
+
+/// Reranking of the top results of a query by an external reranker, whose scores replace the scores of the query.
+/// If the reranker fails or doesn't respond in time, results are returned in their original order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct Rerank {
+    /// Text of the query to score the results against
+    #[validate(length(min = 1))]
+    pub query: String,
+
+    /// Payload fields with the text of each result. Texts of several fields are joined with new lines.
+    #[validate(length(min = 1))]
+    pub fields: Vec<JsonPath>,
+
+    /// Number of top results to rerank, the rest keep their order after them. Default is all results.
+    #[validate(range(min = 1))]
+    pub top: Option<usize>,
+}
+
+/// Reference to a filter template of the collection, see `filter_templates` in the collection params
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct FilterTemplateReference {
+    /// Name of the template
+    #[validate(length(min = 1))]
+    pub name: String,
+
+    /// Values of the parameters of the template, by name
+    #[serde(default)]
+    pub params: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -215,9 +333,21 @@ pub struct QueryRequestBatch {
     pub searches: Vec<QueryRequest>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct QueryDslRequest {
+    /// Query in the textual query language,
+    /// e.g. `nearest([0.2, 0.1, 0.9]) where city = 'Berlin' and price < 10 limit 5`
+    #[validate(length(min = 1))]
+    pub query: String,
+    pub shard_key: Option<ShardKeySelector>,
+}
+
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct QueryResponse {
     pub points: Vec<ScoredPoint>,
+    /// Work done to execute the query. Only present if requested with `with_stats`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<segment::types::QueryStats>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -247,6 +377,9 @@ pub enum Query {
 
     /// Fuse the results of multiple prefetches.
     Fusion(FusionQuery),
+
+    /// Search a hybrid vector of the collection, fusing the nearest points of both its vectors.
+    Hybrid(HybridQuery),
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -285,6 +418,12 @@ pub struct FusionQuery {
     pub fusion: Fusion,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct HybridQuery {
+    pub hybrid: HybridInput,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct Prefetch {
     /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
@@ -319,6 +458,10 @@ pub struct Prefetch {
     /// Note: the other collection vectors should have the same vector size as the 'using' vector in the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// Specify in which shards to perform this prefetch, if not specified - use the shards of the request.
+    /// Only allowed in prefetches of a fusion query
+    pub shard_key: Option<ShardKeySelector>,
 }
 
 /// How to use positive and negative examples to find the results, default is `average_vector`:
@@ -397,6 +540,22 @@ impl ContextPair {
     }
 }
 
+/// Query vectors of a hybrid vector, whose name is given in `using`
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct HybridInput {
+    /// Find the nearest neighbors to this vector with the dense vector of the pair
+    #[validate]
+    pub dense: VectorInput,
+
+    /// Find the nearest neighbors to this vector with the sparse vector of the pair
+    #[validate]
+    pub sparse: VectorInput,
+
+    /// Fusion of the nearest neighbors of both vectors. Default is `rrf`
+    #[validate]
+    pub fusion: Option<Fusion>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct WithLookup {
     /// Name of the collection to use for points lookup
@@ -413,6 +572,26 @@ pub struct WithLookup {
     pub with_vectors: Option<WithVector>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct WithNeighbors {
+    /// Payload field with the position of the point in its group, must be a number field
+    #[schemars(length(min = 1))]
+    #[validate(custom = "JsonPath::validate_not_empty")]
+    pub ordinal_by: JsonPath,
+
+    /// Number of points to take before and after the best point of each group
+    #[validate(range(min = 1, max = 100))]
+    pub neighbors: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct WithAggregates {
+    /// Numeric payload fields to compute the mean of, over all points of each group matching the
+    /// filter
+    #[serde(default)]
+    pub mean: Vec<JsonPath>,
+}
+
 const fn default_with_payload() -> Option<WithPayloadInterface> {
     Some(WithPayloadInterface::Bool(true))
 }
@@ -460,6 +639,15 @@ pub struct BaseGroupRequest {
 
     /// Look for points in another collection using the group ids
     pub with_lookup: Option<WithLookupInterface>,
+
+    /// Return the best point of each group along with its neighbors, such as the chunks around
+    /// the best chunk of a document
+    #[validate]
+    pub with_neighbors: Option<WithNeighbors>,
+
+    /// Return aggregates over all points of each group matching the filter: their number, the best
+    /// score and the mean of numeric payload fields
+    pub with_aggregates: Option<WithAggregates>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -1,12 +1,18 @@
 use std::cmp;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
 use futures::{future, TryStreamExt as _};
+use issues::{Code, Issue as _};
 use lazy_static::lazy_static;
-use segment::types::QuantizationConfig;
+use segment::problems::degraded_hnsw_graph::HnswGraphProblem;
+use segment::problems::DegradedHnswGraph;
+use segment::types::{PointIdType, QuantizationConfig};
 use semver::Version;
+use validator::Validate as _;
 
 use super::Collection;
+use crate::collection_manager::optimizers::OptimizationsProgress;
 use crate::operations::config_diff::*;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
@@ -33,7 +39,17 @@ impl Collection {
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
-            config.params = params_diff.update(&config.params)?;
+            let params = params_diff.update(&config.params)?;
+            if let Some(vector_routing) = &params.vector_routing {
+                vector_routing.check_vector_names(&params)?;
+            }
+            for template in params.filter_templates.iter().flat_map(|t| t.values()) {
+                template.param_names()?;
+            }
+            if let Some(payload_compression) = &params.payload_compression {
+                payload_compression.validate()?;
+            }
+            config.params = params;
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
@@ -286,6 +302,99 @@ impl Collection {
         Ok(info)
     }
 
+    /// Raise issues about degraded HNSW graphs of the local shards, and solve the resolved ones
+    ///
+    /// Statistics of new graphs are computed by walking them, so this runs periodically in the
+    /// background, see `TableOfContent::check_hnsw_graphs`, never on the request path.
+    pub async fn check_hnsw_graphs(&self) -> CollectionResult<()> {
+        let mut problems: HashMap<String, BTreeSet<HnswGraphProblem>> = HashMap::new();
+        let shards_holder = self.shards_holder.read().await;
+        for replica_set in shards_holder.all_shards() {
+            for graph in replica_set.hnsw_graphs_local().await? {
+                problems
+                    .entry(graph.vector_name.clone().unwrap_or_default())
+                    .or_default()
+                    .extend(HnswGraphProblem::of(&graph));
+            }
+        }
+        drop(shards_holder);
+
+        for (vector_name, problems) in problems {
+            if problems.is_empty() {
+                issues::solve(Code::new::<DegradedHnswGraph>(
+                    DegradedHnswGraph::get_instance_id(&self.id, &vector_name),
+                ));
+            } else {
+                DegradedHnswGraph::new(self.id.clone(), vector_name, problems).submit();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Measure the replication lags of the remote replicas of all shards again, see
+    /// [`crate::shards::replica_set::ShardReplicaSet::refresh_replication_lags`]
+    pub async fn refresh_replication_lags(&self) {
+        let shards_holder = self.shards_holder.read().await;
+        let refreshes = shards_holder
+            .all_shards()
+            .map(|replica_set| replica_set.refresh_replication_lags());
+        future::join_all(refreshes).await;
+    }
+
+    /// Worst status of the shards with a replica on this peer, without querying other peers
+    pub async fn local_status(&self) -> CollectionStatus {
+        let shards_holder = self.shards_holder.read().await;
+        let mut status = CollectionStatus::Green;
+        for replica_set in shards_holder.all_shards() {
+            // Shards without a local replica are reported by the peers hosting them
+            if let Ok(info) = replica_set.info(true).await {
+                status = cmp::max(status, info.status);
+            }
+        }
+        status
+    }
+
+    /// Neighbors of the point in the HNSW graphs of the vector, in the local shards only
+    pub async fn hnsw_neighborhood(
+        &self,
+        point_id: PointIdType,
+        vector_name: &str,
+    ) -> CollectionResult<HnswNeighborhood> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut segments = Vec::new();
+        for (shard_id, replica_set) in shards_holder.get_shards() {
+            let neighbors = replica_set
+                .hnsw_neighbors_local(vector_name, point_id)
+                .await?;
+            segments.extend(neighbors.into_iter().map(|(segment_id, levels)| {
+                SegmentHnswNeighbors {
+                    shard_id: *shard_id,
+                    segment_id,
+                    levels,
+                }
+            }));
+        }
+
+        Ok(HnswNeighborhood {
+            point_id,
+            vector: vector_name.to_string(),
+            segments,
+        })
+    }
+
+    /// Progress of the optimizations of the shards with a replica on this peer
+    pub async fn local_optimizations_progress(&self) -> OptimizationsProgress {
+        let shards_holder = self.shards_holder.read().await;
+        let mut progress = OptimizationsProgress::default();
+        for replica_set in shards_holder.all_shards() {
+            if let Some(shard_progress) = replica_set.optimizations_progress_local().await {
+                progress = progress + shard_progress;
+            }
+        }
+        progress
+    }
+
     pub async fn cluster_info(&self, peer_id: PeerId) -> CollectionResult<CollectionClusterInfo> {
         let shards_holder = self.shards_holder.read().await;
         let shard_count = shards_holder.len();
@@ -319,15 +428,19 @@ impl Collection {
                     shard_key: shard_to_key.get(&shard_id).cloned(),
                 })
             }
+            let replication_lags = replica_set.replication_lags();
             for (peer_id, state) in replica_set.peers().into_iter() {
                 if peer_id == replica_set.this_peer_id() {
                     continue;
                 }
+                let replication_lag = replication_lags.get(peer_id);
                 remote_shards.push(RemoteShardInfo {
                     shard_id,
                     peer_id,
                     state,
                     shard_key: shard_to_key.get(&shard_id).cloned(),
+                    replication_lag,
+                    degraded: replication_lags.is_degraded(peer_id),
                 });
             }
         }
@@ -3,7 +3,9 @@ pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
 mod resharding;
+mod score;
 mod search;
+mod segment_upgrade;
 mod shard_transfer;
 mod sharding_keys;
 mod snapshots;
@@ -26,8 +28,12 @@ use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::is_ready::IsReady;
+use crate::common::query_memory::QueryMemoryAggregator;
+use crate::common::query_usage::QueryUsageStatistics;
+use crate::common::resource_usage::ResourceUsage;
 use crate::config::CollectionConfig;
 use crate::operations::config_diff::{DiffConfig, OptimizersConfigDiff};
+use crate::operations::session_token::ShardSessionClock;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
 use crate::optimizers_builder::OptimizersConfig;
@@ -78,6 +84,14 @@ pub struct Collection {
     // Search runtime handle.
     search_runtime: Handle,
     optimizer_cpu_budget: CpuBudget,
+    // Estimated memory usage of the queries to this collection.
+    query_memory: parking_lot::Mutex<QueryMemoryAggregator>,
+    // Query features used on this collection.
+    query_usage: parking_lot::Mutex<QueryUsageStatistics>,
+    // CPU time and disk IO spent on requests to this collection.
+    resource_usage: Arc<ResourceUsage>,
+    // Upgrade of the segments of the local shards to the current storage format.
+    segment_upgrade: parking_lot::Mutex<segment_upgrade::SegmentUpgrade>,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -150,6 +164,8 @@ impl Collection {
         CollectionVersion::save(path)?;
         collection_config.save(path)?;
 
+        let resource_usage = ResourceUsage::new(shared_storage_config.measure_io_usage);
+
         Ok(Self {
             id: name.clone(),
             shards_holder: locked_shard_holder,
@@ -172,6 +188,10 @@ impl Collection {
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
+            query_memory: Default::default(),
+            query_usage: Default::default(),
+            resource_usage: Arc::new(resource_usage),
+            segment_upgrade: Default::default(),
         })
     }
 
@@ -259,6 +279,8 @@ impl Collection {
 
         let locked_shard_holder = Arc::new(LockedShardHolder::new(shard_holder));
 
+        let resource_usage = ResourceUsage::new(shared_storage_config.measure_io_usage);
+
         Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
@@ -281,6 +303,10 @@ impl Collection {
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
+            query_memory: Default::default(),
+            query_usage: Default::default(),
+            resource_usage: Arc::new(resource_usage),
+            segment_upgrade: Default::default(),
         }
     }
 
@@ -311,6 +337,11 @@ impl Collection {
         self.id.clone()
     }
 
+    /// Counters of CPU time and disk IO spent on requests to this collection
+    pub fn resource_usage(&self) -> &Arc<ResourceUsage> {
+        &self.resource_usage
+    }
+
     pub async fn get_shard_keys(&self) -> Vec<ShardKey> {
         self.shards_holder
             .read()
@@ -486,6 +517,26 @@ impl Collection {
         replica_set.update_shard_cutoff_point(cutoff).await
     }
 
+    /// Wait until the local replica of the shard has applied the writes with the given clocks
+    ///
+    /// Returns `false` if it did not apply them within the timeout.
+    pub async fn wait_for_local_session_clocks(
+        &self,
+        shard_id: ShardId,
+        session_clocks: &[ShardSessionClock],
+        timeout: Duration,
+    ) -> CollectionResult<bool> {
+        let shard_holder_read = self.shards_holder.read().await;
+
+        let Some(replica_set) = shard_holder_read.get_shard(&shard_id) else {
+            return Err(shard_not_found_error(shard_id));
+        };
+
+        replica_set
+            .wait_for_local_session_clocks(session_clocks, timeout)
+            .await
+    }
+
     pub async fn state(&self) -> State {
         let shards_holder = self.shards_holder.read().await;
         let transfers = shards_holder.shard_transfers.read().clone();
@@ -706,6 +757,9 @@ impl Collection {
             config: self.collection_config.read().await.clone(),
             shards: shards_telemetry,
             transfers,
+            query_memory: self.query_memory.lock().get_statistics(detail),
+            query_usage: self.query_usage.lock().clone(),
+            resource_usage: self.resource_usage.statistics(),
         }
     }
 
@@ -1,12 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use segment::common::hyperloglog::HyperLogLog;
 use segment::json_path::JsonPath;
-use segment::types::{PayloadFieldSchema, PayloadKeyType};
+use segment::problems::UnindexedField;
+use segment::types::{Filter, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType};
 use serde::{Deserialize, Serialize};
 
 use crate::collection::Collection;
-use crate::operations::types::{CollectionResult, UpdateResult};
+use crate::operations::types::{
+    CollectionError, CollectionResult, PayloadIndexStats, PayloadIndexStatus,
+    ReplicaPayloadIndexState, ReplicaPayloadIndexStatus, UpdateResult,
+};
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::save_on_disk::SaveOnDisk;
 
@@ -84,4 +89,145 @@ impl Collection {
 
         Ok(result)
     }
+
+    /// Check that every active replica of every shard has built the payload index.
+    ///
+    /// A replica which missed the index would still answer filtered requests, but with full
+    /// scans, so it would be much slower than the other replicas.
+    pub async fn payload_index_status(
+        &self,
+        field_name: &JsonPath,
+    ) -> CollectionResult<PayloadIndexStatus> {
+        let Some(field_schema) = self
+            .payload_index_schema
+            .read()
+            .schema
+            .get(field_name)
+            .cloned()
+        else {
+            return Err(CollectionError::not_found(format!(
+                "Payload index for field {field_name}"
+            )));
+        };
+        let expected = PayloadIndexInfo::new(field_schema.clone(), 0);
+
+        let shards_holder = self.shards_holder.read().await;
+        let shard_to_key = shards_holder.get_shard_id_to_key_mapping();
+
+        let mut replicas = Vec::new();
+        for (&shard_id, replica_set) in shards_holder.get_shards() {
+            for (peer_id, info) in replica_set.info_per_active_replica().await {
+                let (state, points, error) = match info {
+                    Ok(mut info) => match info.payload_schema.remove(field_name) {
+                        Some(index)
+                            if index.data_type == expected.data_type
+                                && index.params == expected.params =>
+                        {
+                            (ReplicaPayloadIndexState::Built, Some(index.points), None)
+                        }
+                        Some(index) => {
+                            (ReplicaPayloadIndexState::Mismatch, Some(index.points), None)
+                        }
+                        None => (ReplicaPayloadIndexState::Missing, None, None),
+                    },
+                    Err(err) => (
+                        ReplicaPayloadIndexState::Unavailable,
+                        None,
+                        Some(err.to_string()),
+                    ),
+                };
+
+                replicas.push(ReplicaPayloadIndexStatus {
+                    shard_id,
+                    shard_key: shard_to_key.get(&shard_id).cloned(),
+                    peer_id,
+                    state,
+                    points,
+                    error,
+                });
+            }
+        }
+
+        let consistent = replicas
+            .iter()
+            .all(|replica| replica.state == ReplicaPayloadIndexState::Built);
+
+        Ok(PayloadIndexStatus {
+            field_name: field_name.clone(),
+            field_schema,
+            consistent,
+            replicas,
+        })
+    }
+
+    /// Keys of the fields used in the filter, which have no index suitable for their conditions
+    pub fn unindexed_filter_keys(&self, filter: &Filter) -> Vec<PayloadKeyType> {
+        UnindexedField::unindexed_keys(filter, &self.payload_index_schema.read().schema)
+    }
+
+    /// Distinct values of the keyword index of the field across all shards, if there are at most
+    /// `limit` of them.
+    ///
+    /// Only known if every shard has an active replica on this peer. Values of deleted points may
+    /// be included, so this is a superset of the values present in the collection.
+    pub async fn keyword_values(
+        &self,
+        field_name: &JsonPath,
+        limit: usize,
+    ) -> Option<HashSet<String>> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut values = HashSet::new();
+        for replica_set in shards_holder.all_shards() {
+            values.extend(replica_set.keyword_values_local(field_name, limit).await?);
+            if values.len() > limit {
+                return None;
+            }
+        }
+        Some(values)
+    }
+
+    /// Estimate statistics of the payload index from the replicas on this peer.
+    ///
+    /// The number of distinct values is estimated from a sketch of the keyword index of each
+    /// segment, without reading the points. It is only known if every shard has an active replica
+    /// on this peer.
+    pub async fn payload_index_stats(
+        &self,
+        field_name: &JsonPath,
+    ) -> CollectionResult<PayloadIndexStats> {
+        let Some(field_schema) = self
+            .payload_index_schema
+            .read()
+            .schema
+            .get(field_name)
+            .cloned()
+        else {
+            return Err(CollectionError::not_found(format!(
+                "Payload index for field {field_name}"
+            )));
+        };
+
+        let approximate_unique_values = self
+            .keyword_values_sketch(field_name)
+            .await
+            .map(|sketch| sketch.estimate());
+
+        Ok(PayloadIndexStats {
+            field_name: field_name.clone(),
+            field_schema,
+            approximate_unique_values,
+        })
+    }
+
+    /// Sketch of the distinct values of the keyword index of the field across all shards.
+    ///
+    /// Only known if every shard has an active replica on this peer.
+    async fn keyword_values_sketch(&self, field_name: &JsonPath) -> Option<HyperLogLog> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut sketch = HyperLogLog::new();
+        for replica_set in shards_holder.all_shards() {
+            sketch.merge(&replica_set.keyword_values_sketch_local(field_name).await?);
+        }
+        Some(sketch)
+    }
 }
@@ -1,18 +1,26 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::{Direction, OrderBy};
-use segment::types::{Filter, ShardKey, WithPayload, WithPayloadInterface};
+use segment::types::{
+    Filter, PointIdType, SeqNumberType, ShardKey, WithPayload, WithPayloadInterface,
+};
 use validator::Validate as _;
 
 use super::Collection;
-use crate::operations::consistency_params::ReadConsistency;
+use crate::common::query_memory::estimate_records_size;
+use crate::common::resource_usage;
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
+use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
 use crate::operations::point_ops::WriteOrdering;
+use crate::operations::session_token::SessionToken;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
+use crate::operations::vector_ingest::apply_ingest_policies;
 use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use crate::shards::shard::ShardId;
 
@@ -88,7 +96,7 @@ impl Collection {
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
-        let result = tokio::task::spawn(async move {
+        let result = tokio::task::spawn(resource_usage::scope(&self.resource_usage, async move {
             let _update_lock = update_lock;
 
             let Some(shard) = shard_holder.get_shard(&shard_selection) else {
@@ -112,7 +120,7 @@ impl Collection {
                         .map(Some)
                 }
             }
-        })
+        }))
         .await??;
 
         if let Some(result) = result {
@@ -131,32 +139,44 @@ impl Collection {
     /// This method is cancel safe.
     pub async fn update_from_client(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
 
+        {
+            let config = self.collection_config.read().await;
+            if config.params.frozen {
+                return Err(CollectionError::bad_request(format!(
+                    "Collection {} is frozen, points can't be updated until it is unfrozen",
+                    self.id,
+                )));
+            }
+            apply_ingest_policies(&mut operation, &config.params.vectors)?;
+        }
+
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
-        let mut results = tokio::task::spawn(async move {
-            let _update_lock = update_lock;
+        let mut results =
+            tokio::task::spawn(resource_usage::scope(&self.resource_usage, async move {
+                let _update_lock = update_lock;
 
-            let updates: FuturesUnordered<_> = shard_holder
-                .split_by_shard(operation, &shard_keys_selection)?
-                .into_iter()
-                .map(move |(shard, operation)| {
-                    shard.update_with_consistency(operation, wait, ordering)
-                })
-                .collect();
+                let updates: FuturesUnordered<_> = shard_holder
+                    .split_by_shard(operation, &shard_keys_selection)?
+                    .into_iter()
+                    .map(move |(shard, operation)| {
+                        shard.update_with_consistency(operation, wait, ordering)
+                    })
+                    .collect();
 
-            let results: Vec<_> = updates.collect().await;
+                let results: Vec<_> = updates.collect().await;
 
-            CollectionResult::Ok(results)
-        })
-        .await??;
+                CollectionResult::Ok(results)
+            }))
+            .await??;
 
         if results.is_empty() {
             return Err(CollectionError::bad_request(
@@ -187,8 +207,21 @@ impl Collection {
                 first_err
             }
         } else {
+            // The token covers the writes to all shards
+            let mut session_token = SessionToken::default();
+            for result in results.iter().flatten() {
+                if let Some(token) = &result.session_token {
+                    session_token.merge(token);
+                }
+            }
+
             // At least one result is always present.
-            results.pop().unwrap()
+            results.pop().unwrap().map(|mut result| {
+                if !session_token.is_empty() {
+                    result.session_token = Some(session_token);
+                }
+                result
+            })
         }
     }
 
@@ -279,6 +312,10 @@ impl Collection {
             future::try_join_all(scroll_futures).await?
         };
 
+        // Results of all shards are held until they are merged
+        let memory_bytes = retrieved_points.iter().map(estimate_records_size).sum();
+        self.query_memory.lock().add(memory_bytes);
+
         let retrieved_iter = retrieved_points.into_iter();
 
         let mut points = match &order_by {
@@ -382,6 +419,101 @@ impl Collection {
         Ok(CountResult { count })
     }
 
+    /// Aggregate all points of the requested groups, merging the aggregates of all shards
+    pub async fn group_aggregates(
+        &self,
+        mut request: GroupAggregatesRequestInternal,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        if request.group_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        merge_filters(
+            &mut request.filter,
+            self.shards_holder.read().await.resharding_filter(),
+        );
+
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let request = Arc::new(request);
+        let mut requests: FuturesUnordered<_> = shards
+            .into_iter()
+            .map(|(shard, _shard_key)| {
+                shard.group_aggregates(request.clone(), shard_selection.is_shard_id())
+            })
+            .collect();
+
+        let mut groups: HashMap<GroupId, ShardGroupAggregates> = HashMap::new();
+        while let Some(shard_groups) = requests.try_next().await? {
+            for (group_id, aggregates) in shard_groups {
+                groups.entry(group_id).or_default().merge(aggregates);
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Read points changed since the given cursor in shards, which have a replica on this peer
+    ///
+    /// Cursor positions are WAL positions of local replicas, so changes must always be read
+    /// from the same peer.
+    pub async fn point_changes(
+        &self,
+        request: PointChangesRequest,
+    ) -> CollectionResult<PointChangesResult> {
+        let limit = request.limit.unwrap_or(DEFAULT_POINT_CHANGES_LIMIT);
+        let with_payload = request
+            .with_payload
+            .as_ref()
+            .map_or_else(|| WithPayload::from(true), WithPayload::from);
+
+        let shards_holder = self.shards_holder.read().await;
+        let shard_keys = shards_holder.get_shard_id_to_key_mapping();
+
+        let mut changes = Vec::new();
+        let mut next_cursor = ChangesCursor::new();
+
+        for (&shard_id, replica_set) in shards_holder.get_shards() {
+            let cursor = request
+                .cursor
+                .as_ref()
+                .map(|cursor| cursor.get(&shard_id).copied().unwrap_or_default());
+
+            let Some(shard_changes) = replica_set
+                .changes_since_local(cursor, limit, &with_payload, &request.with_vector)
+                .await?
+            else {
+                continue;
+            };
+
+            let shard_key = shard_keys.get(&shard_id);
+            changes.extend(
+                shard_changes
+                    .changes
+                    .into_iter()
+                    .map(|(id, version, record)| PointChange {
+                        id,
+                        shard_id,
+                        shard_key: shard_key.cloned(),
+                        version,
+                        point: record.map(|mut record| {
+                            record.shard_key = shard_key.cloned();
+                            api::rest::Record::from(record)
+                        }),
+                    }),
+            );
+            next_cursor.insert(shard_id, shard_changes.next_cursor);
+        }
+
+        Ok(PointChangesResult {
+            changes,
+            next_cursor,
+            peer_id: self.this_peer_id,
+        })
+    }
+
     pub async fn retrieve(
         &self,
         request: PointRequestInternal,
@@ -395,6 +527,15 @@ impl Collection {
         let with_payload = WithPayload::from(with_payload_interface);
         let request = Arc::new(request);
 
+        // With majority consistency, pick the newest version of each point and repair replicas
+        // holding stale copies in the background
+        let read_repair = !shard_selection.is_shard_id()
+            && request.as_of_version.is_none()
+            && matches!(
+                read_consistency,
+                Some(ReadConsistency::Type(ReadConsistencyType::Majority)),
+            );
+
         #[allow(unused_assignments)]
         let mut resharding_filter = None;
 
@@ -407,28 +548,52 @@ impl Collection {
             let target_shards = shard_holder.select_shards(shard_selection)?;
             let retrieve_futures = target_shards.into_iter().map(|(shard, shard_key)| {
                 let shard_key = shard_key.cloned();
-                shard
-                    .retrieve(
-                        request.clone(),
-                        &with_payload,
-                        &request.with_vector,
-                        read_consistency,
-                        shard_selection.is_shard_id(),
-                    )
-                    .and_then(move |mut records| async move {
-                        if shard_key.is_none() {
-                            return Ok(records);
+                let request = request.clone();
+                let with_payload = &with_payload;
+
+                async move {
+                    let mut records = if read_repair {
+                        let (records, stale_points) = shard
+                            .retrieve_newest(request.clone(), with_payload, &request.with_vector)
+                            .await?;
+
+                        if !stale_points.is_empty() {
+                            self.schedule_read_repair(shard.shard_id, stale_points);
                         }
+
+                        records
+                    } else {
+                        shard
+                            .retrieve(
+                                request.clone(),
+                                with_payload,
+                                &request.with_vector,
+                                read_consistency,
+                                shard_selection.is_shard_id(),
+                            )
+                            .await?
+                    };
+
+                    if shard_key.is_some() {
                         for point in &mut records {
                             point.shard_key.clone_from(&shard_key);
                         }
-                        Ok(records)
-                    })
+                    }
+
+                    CollectionResult::Ok(records)
+                }
             });
 
             future::try_join_all(retrieve_futures).await?
         };
 
+        // Results of all shards are held until they are merged
+        let memory_bytes = all_shard_collection_results
+            .iter()
+            .map(estimate_records_size)
+            .sum();
+        self.query_memory.lock().add(memory_bytes);
+
         let mut covered_point_ids = HashSet::new();
         let points = all_shard_collection_results
             .into_iter()
@@ -444,6 +609,36 @@ impl Collection {
 
         Ok(points)
     }
+
+    /// Repair stale replicas of the given points in the background.
+    fn schedule_read_repair(
+        &self,
+        shard_id: ShardId,
+        stale_points: Vec<(PointIdType, SeqNumberType)>,
+    ) {
+        let shards_holder = self.shards_holder.clone();
+        let collection_id = self.id.clone();
+
+        self.update_runtime.spawn(async move {
+            let shard_holder = shards_holder.read().await;
+
+            let Some(replica_set) = shard_holder.get_shard(&shard_id) else {
+                return;
+            };
+
+            match replica_set.read_repair(stale_points).await {
+                Ok(0) => {}
+                Ok(repaired) => {
+                    log::debug!(
+                        "Read repair of {repaired} points in shard {collection_id}:{shard_id} finished",
+                    );
+                }
+                Err(err) => {
+                    log::warn!("Read repair in shard {collection_id}:{shard_id} failed: {err}");
+                }
+            }
+        });
+    }
 }
 
 fn merge_filters(filter: &mut Option<Filter>, resharding_filter: Option<Filter>) {
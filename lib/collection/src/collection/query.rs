@@ -1,11 +1,17 @@
+use std::collections::HashMap;
 use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use api::rest::TextBudget;
 use futures::{future, TryFutureExt};
 use itertools::{Either, Itertools};
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
-use segment::types::{Order, ScoredPoint};
+use segment::index::search_stats::SearchStats;
+use segment::types::{
+    Order, PointIdType, QueryStats, ScoredPoint, WithPayloadInterface, WithVector,
+};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::sync::RwLockReadGuard;
 use tokio::time::Instant;
@@ -15,16 +21,45 @@ use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
     build_vector_resolver_queries, resolve_referenced_vectors_batch,
 };
+use crate::common::query_memory::estimate_points_size;
 use crate::common::retrieve_request_trait::RetrieveRequest;
+use crate::common::spilled_points::{SpillWriter, SpilledPoints};
+use crate::common::text_budget::{payload_text_size, trim_to_budget};
 use crate::common::transpose_iterator::transposed_iter;
 use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::filter_template::resolve_filter_template;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, PointRequestInternal, Record};
 use crate::operations::universal_query::collection_query::CollectionQueryRequest;
+use crate::operations::universal_query::fusion::collect_prefetch_matches;
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
+    ScoringQuery, ShardQueryRequest, ShardQueryResponse,
 };
 
+/// Shards to perform each root prefetch of a request on, if they differ from the shards of the batch
+type PrefetchShardSelection = Vec<ShardSelectorInternal>;
+
+/// Shards to query, and the index of the query if it is batched on its own to count its stats
+type QueryBatchKey = (ShardSelectorInternal, Option<usize>);
+
+/// Response of a shard to a request of the batch
+struct ShardQueryResults {
+    /// Leading points of each intermediate result, held in memory
+    held: ShardQueryResponse,
+    /// Remaining points of each intermediate result, spilled to disk as they didn't fit in the
+    /// memory budget for merging
+    spilled: Option<SpilledPoints>,
+}
+
+impl ShardQueryResults {
+    fn in_memory(shard_response: ShardQueryResponse) -> Self {
+        Self {
+            held: shard_response,
+            spilled: None,
+        }
+    }
+}
+
 struct IntermediateQueryInfo<'a> {
     scoring_query: Option<&'a ScoringQuery>,
     /// Limit + offset
@@ -33,24 +68,36 @@ struct IntermediateQueryInfo<'a> {
 
 impl Collection {
     /// Returns a shape of [shard_id, batch_id, intermediate_response, points]
+    ///
+    /// With `with_routing`, each point has the shard and the replica it was found in.
+    /// With `search_stats`, the work of all queried shards is counted into it.
+    #[allow(clippy::too_many_arguments)]
     async fn batch_query_shards_concurrently(
         &self,
         batch_request: Arc<Vec<ShardQueryRequest>>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ShardQueryResponse>>> {
+        with_routing: bool,
+        search_stats: Option<Arc<SearchStats>>,
+    ) -> CollectionResult<Vec<Vec<ShardQueryResults>>> {
+        // Size of the results kept in memory so far
+        let held_bytes = Arc::new(AtomicUsize::new(0));
+
         // query all shards concurrently
         let shard_holder = self.shards_holder.read().await;
         let target_shards = shard_holder.select_shards(shard_selection)?;
         let all_searches = target_shards.iter().map(|(shard, shard_key)| {
             let shard_key = shard_key.cloned();
+            let held_bytes = Arc::clone(&held_bytes);
             shard
                 .query_batch(
                     Arc::clone(&batch_request),
                     read_consistency,
                     shard_selection.is_shard_id(),
                     timeout,
+                    with_routing,
+                    search_stats.clone(),
                 )
                 .and_then(move |mut shard_responses| async move {
                     if shard_key.is_none() {
@@ -60,72 +107,390 @@ impl Collection {
                         .iter_mut()
                         .flatten()
                         .flatten()
-                        .for_each(|point| point.shard_key.clone_from(&shard_key));
+                        .for_each(|point| {
+                            point.shard_key.clone_from(&shard_key);
+                            if let Some(routing) = &mut point.routing {
+                                routing.shard_key.clone_from(&shard_key);
+                            }
+                        });
 
                     Ok(shard_responses)
                 })
+                .and_then(move |shard_responses| self.hold_or_spill(shard_responses, held_bytes))
         });
         future::try_join_all(all_searches).await
     }
 
+    /// Keeps the points of a shard in memory as long as they fit in the memory budget for
+    /// merging, along with the points of the other shards. The remaining points are spilled to
+    /// disk as soon as the shard responds, while other shards are still producing results.
+    async fn hold_or_spill(
+        &self,
+        shard_responses: Vec<ShardQueryResponse>,
+        held_bytes: Arc<AtomicUsize>,
+    ) -> CollectionResult<Vec<ShardQueryResults>> {
+        let Some(memory_budget) = self.shared_storage_config.query_merge_memory_budget else {
+            return Ok(shard_responses
+                .into_iter()
+                .map(ShardQueryResults::in_memory)
+                .collect());
+        };
+
+        let spill_dir = self.shared_storage_config.query_spill_path.clone();
+        let collection_id = self.id.clone();
+
+        // Writing to disk blocks, so it must not hold up the runtime polling the other shards
+        tokio::task::spawn_blocking(move || {
+            shard_responses
+                .into_iter()
+                .map(|shard_response| {
+                    hold_or_spill_shard_response(
+                        shard_response,
+                        &held_bytes,
+                        memory_budget,
+                        &spill_dir,
+                        &collection_id,
+                    )
+                })
+                .collect()
+        })
+        .await?
+    }
+
+    /// Queries the shards and merges their results, returns a shape of [batch_id, intermediate_response, points]
+    #[allow(clippy::too_many_arguments)]
+    async fn query_merged_intermediates(
+        &self,
+        requests_batch: Arc<Vec<ShardQueryRequest>>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+        with_routing: bool,
+        search_stats: Option<Arc<SearchStats>>,
+    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        let all_shards_results = self
+            .batch_query_shards_concurrently(
+                Arc::clone(&requests_batch),
+                read_consistency,
+                shard_selection,
+                timeout,
+                with_routing,
+                search_stats,
+            )
+            .await?;
+
+        let merged_f = transposed_iter(all_shards_results)
+            .zip(requests_batch.iter())
+            .map(|(shards_results, request)| {
+                // shards_results shape: [num_shards, num_intermediate_results, num_points]
+                self.merge_intermediate_results_from_shards(request, shards_results)
+            });
+
+        future::try_join_all(merged_f).await
+    }
+
+    /// Performs each root prefetch of a fusion request on its own shards.
+    ///
+    /// Returns the merged results of every prefetch, in the order of the prefetches.
+    #[allow(clippy::too_many_arguments)]
+    async fn query_prefetches_on_shards(
+        &self,
+        request: &ShardQueryRequest,
+        prefetch_shard_selection: &[ShardSelectorInternal],
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        with_routing: bool,
+        search_stats: Option<Arc<SearchStats>>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        debug_assert_eq!(request.prefetches.len(), prefetch_shard_selection.len());
+
+        // Group prefetches by shard selection, so that each set of shards is only queried once
+        let mut legs: Vec<(&ShardSelectorInternal, Vec<usize>)> = Vec::new();
+        for (prefetch_idx, shard_selection) in prefetch_shard_selection.iter().enumerate() {
+            match legs
+                .iter_mut()
+                .find(|(leg_selection, _)| *leg_selection == shard_selection)
+            {
+                Some((_, prefetch_ids)) => prefetch_ids.push(prefetch_idx),
+                None => legs.push((shard_selection, vec![prefetch_idx])),
+            }
+        }
+
+        let legs_f = legs.iter().map(|(shard_selection, prefetch_ids)| {
+            let leg_request = ShardQueryRequest {
+                prefetches: prefetch_ids
+                    .iter()
+                    .map(|&prefetch_idx| request.prefetches[prefetch_idx].clone())
+                    .collect(),
+                ..request.clone()
+            };
+
+            let search_stats = search_stats.clone();
+            async move {
+                self.query_merged_intermediates(
+                    Arc::new(vec![leg_request]),
+                    read_consistency,
+                    shard_selection,
+                    timeout,
+                    with_routing,
+                    search_stats,
+                )
+                .await?
+                .pop()
+                .ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Prefetch query didn't return expected batch of results",
+                    )
+                })
+            }
+        });
+
+        let legs_results = future::try_join_all(legs_f).await?;
+
+        // Put the results back in the order of the prefetches
+        let mut results = vec![Vec::new(); request.prefetches.len()];
+        for ((_, prefetch_ids), leg_results) in legs.iter().zip(legs_results) {
+            debug_assert_eq!(prefetch_ids.len(), leg_results.len());
+            for (&prefetch_idx, prefetch_results) in prefetch_ids.iter().zip(leg_results) {
+                results[prefetch_idx] = prefetch_results;
+            }
+        }
+
+        Ok(results)
+    }
+
     /// This function is used to query the collection. It will return a list of scored points.
+    #[allow(clippy::too_many_arguments)]
     async fn do_query_batch(
         &self,
-        requests_batch: Vec<ShardQueryRequest>,
+        requests_batch: Vec<(ShardQueryRequest, Option<PrefetchShardSelection>)>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        with_routing: bool,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let instant = Instant::now();
 
+        let (mut requests_batch, prefetch_shard_selections): (Vec<_>, Vec<_>) =
+            requests_batch.into_iter().unzip();
+
+        // A single shard has all the results of the prefetches, so it can fuse them itself
+        let single_shard = self
+            .shards_holder
+            .read()
+            .await
+            .select_shards(&shard_selection)?
+            .len()
+            == 1;
+        if single_shard {
+            for (request, prefetch_shard_selection) in
+                requests_batch.iter_mut().zip(&prefetch_shard_selections)
+            {
+                request.single_shard = prefetch_shard_selection.is_none();
+            }
+        }
+
         let requests_batch = Arc::new(requests_batch);
 
-        let all_shards_results = self
-            .batch_query_shards_concurrently(
-                requests_batch.clone(),
+        // Shape: [batch_id, intermediate_response, points]
+        let all_merged_intermediates = if prefetch_shard_selections.iter().all(Option::is_none) {
+            self.query_merged_intermediates(
+                Arc::clone(&requests_batch),
                 read_consistency,
                 &shard_selection,
                 timeout,
+                with_routing,
+                search_stats,
             )
-            .await?;
+            .await?
+        } else {
+            // Requests with prefetches on other shards can't share the scatter with the rest of the batch
+            let merged_f = requests_batch.iter().zip(&prefetch_shard_selections).map(
+                |(request, prefetch_shard_selection)| async {
+                    match prefetch_shard_selection {
+                        Some(prefetch_shard_selection) => {
+                            self.query_prefetches_on_shards(
+                                request,
+                                prefetch_shard_selection,
+                                read_consistency,
+                                timeout,
+                                with_routing,
+                                search_stats.clone(),
+                            )
+                            .await
+                        }
+                        None => self
+                            .query_merged_intermediates(
+                                Arc::new(vec![request.clone()]),
+                                read_consistency,
+                                &shard_selection,
+                                timeout,
+                                with_routing,
+                                search_stats.clone(),
+                            )
+                            .await?
+                            .pop()
+                            .ok_or_else(|| {
+                                CollectionError::service_error(
+                                    "Query didn't return expected batch of results",
+                                )
+                            }),
+                    }
+                },
+            );
+            future::try_join_all(merged_f).await?
+        };
 
-        let results_f = transposed_iter(all_shards_results)
+        let results = all_merged_intermediates
+            .into_iter()
             .zip(requests_batch.iter())
-            .map(|(shards_results, request)| async {
-                // shards_results shape: [num_shards, num_intermediate_results, num_points]
-                let mut merged_intermediates = self
-                    .merge_intermediate_results_from_shards(request, shards_results)
-                    .await?;
-
-                let result = if let Some(ScoringQuery::Fusion(fusion)) = &request.query {
-                    // If the root query is a Fusion, the returned results correspond to each the prefetches.
-                    match fusion {
-                        Fusion::Rrf => rrf_scoring(merged_intermediates),
+            .map(|(mut merged_intermediates, request)| {
+                let mut prefetch_matches = None;
+
+                let result = match &request.query {
+                    Some(ScoringQuery::Fusion(fusion)) if request.needs_intermediate_results() => {
+                        // If the root query is a Fusion, the returned results correspond to each the prefetches.
+                        prefetch_matches = Some(collect_prefetch_matches(&merged_intermediates));
+                        fusion.strategy()?.fuse(merged_intermediates)
                     }
+                    _ => {
+                        // Otherwise, it will be a list with a single list of scored points.
+                        debug_assert_eq!(merged_intermediates.len(), 1);
+                        merged_intermediates.pop().ok_or_else(|| {
+                            CollectionError::service_error(
+                                "Query response was expected to have one list of results.",
+                            )
+                        })?
+                    }
+                };
+
+                // Keep the remaining fused points as candidates, in case some of the top points
+                // are deleted before their payload and vector are fetched
+                let keep = if Self::fills_fused_points(request) {
+                    usize::MAX
                 } else {
-                    // Otherwise, it will be a list with a single list of scored points.
-                    debug_assert_eq!(merged_intermediates.len(), 1);
-                    merged_intermediates.pop().ok_or_else(|| {
-                        CollectionError::service_error(
-                            "Query response was expected to have one list of results.",
-                        )
-                    })?
+                    request.limit
                 };
+                let mut result: Vec<ScoredPoint> =
+                    result.into_iter().skip(request.offset).take(keep).collect();
 
-                let result: Vec<ScoredPoint> = result
-                    .into_iter()
-                    .skip(request.offset)
-                    .take(request.limit)
-                    .collect();
+                if let Some(mut prefetch_matches) = prefetch_matches {
+                    for point in &mut result {
+                        point.prefetch_matches = prefetch_matches.remove(&point.id);
+                    }
+                }
 
                 let filter_refs = request.filter_refs();
                 self.post_process_if_slow_request(instant.elapsed(), filter_refs);
 
                 Ok::<_, CollectionError>(result)
+            })
+            .collect::<CollectionResult<Vec<_>>>()?;
+
+        // Shards only score the sources of a fusion, the final points are filled in here
+        let all_shards = ShardSelectorInternal::All;
+        let filled_f = results
+            .into_iter()
+            .zip(requests_batch.iter())
+            .zip(&prefetch_shard_selections)
+            .map(|((points, request), prefetch_shard_selection)| {
+                // Points of prefetches on other shards may come from any shard
+                let shard_selection = match prefetch_shard_selection {
+                    Some(_) => &all_shards,
+                    None => &shard_selection,
+                };
+                async move {
+                    if !Self::fills_fused_points(request) {
+                        return Ok(points);
+                    }
+                    self.fill_with_payload_or_vectors(
+                        points,
+                        request.limit,
+                        &request.with_payload,
+                        &request.with_vector,
+                        read_consistency,
+                        shard_selection,
+                    )
+                    .await
+                }
             });
-        let results = future::try_join_all(results_f).await?;
 
-        Ok(results)
+        future::try_join_all(filled_f).await
+    }
+
+    /// Whether the shards return the sources of the fusion without payload and vector, so they are
+    /// fetched for the fused points at collection level
+    fn fills_fused_points(request: &ShardQueryRequest) -> bool {
+        request.needs_intermediate_results() && request.defer_fusion_payload
+    }
+
+    /// Fetches the payload and vector of the top `limit` candidates, if required.
+    ///
+    /// Candidates deleted since they were scored are replaced by the next ones, so the result is
+    /// only shorter than `limit` if there are not enough candidates left.
+    ///
+    /// Points are retrieved with the read consistency of the query, and in the scope of its
+    /// session token.
+    async fn fill_with_payload_or_vectors(
+        &self,
+        candidates: Vec<ScoredPoint>,
+        limit: usize,
+        with_payload: &WithPayloadInterface,
+        with_vector: &WithVector,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let mut candidates = candidates.into_iter();
+
+        if !with_payload.is_required() && !with_vector.is_enabled() {
+            return Ok(candidates.take(limit).collect());
+        }
+
+        let mut result = Vec::with_capacity(limit);
+        loop {
+            let points: Vec<_> = candidates.by_ref().take(limit - result.len()).collect();
+            if points.is_empty() {
+                break;
+            }
+
+            let request = PointRequestInternal {
+                ids: points.iter().map(|point| point.id).collect(),
+                with_payload: Some(with_payload.clone()),
+                with_vector: with_vector.clone(),
+                as_of_version: None,
+            };
+
+            let mut records: HashMap<PointIdType, Record> = self
+                .retrieve(request, read_consistency, shard_selection)
+                .await?
+                .into_iter()
+                .map(|record| (record.id, record))
+                .collect();
+
+            result.extend(points.into_iter().filter_map(|mut point| {
+                let record = records.remove(&point.id)?;
+                point.payload = record.payload;
+                point.vector = record.vector;
+                Some(point)
+            }));
+
+            if result.len() >= limit {
+                break;
+            }
+            log::debug!(
+                "Fused points of collection {} were deleted before fetching their payload, \
+                 filling up with the next candidates",
+                self.id,
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Count a query, which exceeded the query limits, but was executed with warnings
+    pub fn record_query_over_soft_limits(&self) {
+        self.query_usage.lock().over_soft_limits += 1;
     }
 
     /// To be called on the user-responding instance. Resolves ids into vectors, and merges the results from local and remote shards.
@@ -142,78 +507,248 @@ impl Collection {
         F: Fn(String) -> Fut,
         Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
     {
-        // Lift nested prefetches to root queries for vector resolution
-        let resolver_requests = build_vector_resolver_queries(&requests_batch);
-
-        // Build referenced vectors
-        let ids_to_vectors = resolve_referenced_vectors_batch(
-            &resolver_requests,
-            self,
+        self.query_batch_with_stats(
+            requests_batch,
             collection_by_name,
             read_consistency,
+            timeout,
         )
-        .await?;
+        .await
+        .map(|(results, _stats)| results)
+    }
 
-        // Check we actually fetched all referenced vectors from the resolver requests
-        for (resolver_req, _) in &resolver_requests {
-            for point_id in resolver_req.get_referenced_point_ids() {
-                let lookup_collection = resolver_req.get_lookup_collection();
-                if ids_to_vectors.get(&lookup_collection, point_id).is_none() {
-                    return Err(CollectionError::PointNotFound {
-                        missed_point_id: point_id,
-                    });
+    /// Same as `query_batch`, but also returns the work done to execute each query requested
+    /// `with_stats`
+    ///
+    /// Queries with stats are sent to the shards on their own, so the work of the other queries
+    /// of the batch is not counted into their stats.
+    pub async fn query_batch_with_stats<'a, F, Fut>(
+        &self,
+        mut requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        collection_by_name: F,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<Option<QueryStats>>)>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+    {
+        {
+            let mut query_usage = self.query_usage.lock();
+            for (request, _) in &requests_batch {
+                query_usage.add(request);
+            }
+        }
+
+        // Bind filter templates first, the vectors may be routed by their conditions.
+        // Hybrid queries are expanded before as well, so their vectors are resolved like others.
+        {
+            let config = self.collection_config.read().await;
+            for (request, _) in &mut requests_batch {
+                if let Some(reference) = request.filter_template.take() {
+                    let filter = resolve_filter_template(
+                        &reference,
+                        config.params.filter_templates.as_ref(),
+                    )?;
+                    request.add_filter(filter);
                 }
+                request.restrict_to_ids();
+                request.expand_hybrid_queries(config.params.hybrid_vectors.as_ref())?;
+            }
+        }
+
+        // Choose the vectors to search with before resolving referenced vectors
+        if let Some(routing) = &self.collection_config.read().await.params.vector_routing {
+            for (request, _) in &mut requests_batch {
+                request.route_vectors(routing);
             }
         }
 
+        let ids_to_vectors = {
+            // Lift nested prefetches to root queries for vector resolution
+            let resolver_requests = build_vector_resolver_queries(&requests_batch);
+
+            // Build referenced vectors
+            resolve_referenced_vectors_batch(
+                &resolver_requests,
+                self,
+                collection_by_name,
+                read_consistency,
+            )
+            .await?
+        };
+
+        // Skipped examples are not checked below
+        for (request, _) in &mut requests_batch {
+            if request.skip_missing {
+                request.skip_missing_examples(&ids_to_vectors);
+            }
+        }
+
+        // Check we actually fetched all referenced vectors from the resolver requests
+        for (resolver_req, _) in &build_vector_resolver_queries(&requests_batch) {
+            ids_to_vectors.check_fetched(
+                resolver_req.get_lookup_collection(),
+                resolver_req.get_referenced_point_ids(),
+                &self.id,
+            )?;
+        }
+
+        // Shards are queried with routing for the whole batch, it is removed from the other results
+        let with_routing: Vec<_> = requests_batch
+            .iter()
+            .map(|(request, _)| request.with_routing)
+            .collect();
+        let any_with_routing = with_routing.iter().any(|&with_routing| with_routing);
+
+        let budgets: Vec<_> = requests_batch
+            .iter()
+            .map(|(request, shard_selection)| {
+                request
+                    .budget
+                    .clone()
+                    .map(|budget| (budget, shard_selection.clone()))
+            })
+            .collect();
+
+        // Each query with stats is batched on its own, with a key no other query has
+        let keyed_requests = requests_batch
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (req, shard))| {
+                let stats_key = req.with_stats.then_some(idx);
+                (req, (shard, stats_key))
+            });
+
         let futures = batch_requests::<
-            (CollectionQueryRequest, ShardSelectorInternal),
-            ShardSelectorInternal,
-            Vec<ShardQueryRequest>,
+            (CollectionQueryRequest, QueryBatchKey),
+            QueryBatchKey,
+            Vec<(ShardQueryRequest, Option<PrefetchShardSelection>)>,
             Vec<_>,
         >(
-            requests_batch,
-            |(_req, shard)| shard,
-            |(req, _), acc| {
+            keyed_requests,
+            |(_req, key)| key,
+            |(req, (shard, _stats_key)), acc| {
+                let prefetch_shard_selection = req.prefetch_shard_selection(&shard)?;
                 req.try_into_shard_request(&self.id, &ids_to_vectors)
                     .map(|shard_req| {
-                        acc.push(shard_req);
+                        acc.push((shard_req, prefetch_shard_selection));
                     })
             },
-            |shard_selection, shard_requests, futures| {
+            |(shard_selection, stats_key), shard_requests, futures| {
                 if shard_requests.is_empty() {
                     return Ok(());
                 }
 
-                futures.push(self.do_query_batch(
+                let search_stats = stats_key.map(|_| Arc::new(SearchStats::default()));
+                let batch_size = shard_requests.len();
+                let query_f = self.do_query_batch(
                     shard_requests,
                     read_consistency,
                     shard_selection,
                     timeout,
-                ));
+                    any_with_routing,
+                    search_stats.clone(),
+                );
+                futures.push(query_f.map_ok(move |results| {
+                    let stats = search_stats.map(|search_stats| search_stats.stats());
+                    (results, vec![stats; batch_size])
+                }));
 
                 Ok(())
             },
         )?;
 
-        let results = future::try_join_all(futures)
+        let (results, stats): (Vec<_>, Vec<_>) =
+            future::try_join_all(futures).await?.into_iter().unzip();
+        let mut results: Vec<Vec<ScoredPoint>> = results.into_iter().flatten().collect();
+        let stats: Vec<Option<QueryStats>> = stats.into_iter().flatten().collect();
+
+        if any_with_routing {
+            for (points, with_routing) in results.iter_mut().zip(with_routing) {
+                if !with_routing {
+                    points.iter_mut().for_each(|point| point.routing = None);
+                }
+            }
+        }
+
+        if budgets.iter().all(Option::is_none) {
+            return Ok((results, stats));
+        }
+
+        let trimmed_f = results
+            .into_iter()
+            .zip(budgets)
+            .map(|(points, budget)| async move {
+                match budget {
+                    Some((budget, shard_selection)) => {
+                        self.trim_to_text_budget(
+                            points,
+                            &budget,
+                            read_consistency,
+                            &shard_selection,
+                        )
+                        .await
+                    }
+                    None => Ok(points),
+                }
+            });
+
+        let results = future::try_join_all(trimmed_f).await?;
+        Ok((results, stats))
+    }
+
+    /// Keeps the first points whose texts fit in the budget.
+    ///
+    /// Texts are retrieved separately, as they may not be part of the requested payload.
+    async fn trim_to_text_budget(
+        &self,
+        points: Vec<ScoredPoint>,
+        budget: &TextBudget,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        if points.is_empty() {
+            return Ok(points);
+        }
+
+        let request = PointRequestInternal {
+            ids: points.iter().map(|point| point.id).collect(),
+            with_payload: Some(WithPayloadInterface::Fields(vec![budget.key.clone()])),
+            with_vector: WithVector::Bool(false),
+            as_of_version: None,
+        };
+
+        let sizes: HashMap<PointIdType, usize> = self
+            .retrieve(request, read_consistency, shard_selection)
             .await?
             .into_iter()
-            .flatten()
+            .map(|record| {
+                let size = record
+                    .payload
+                    .as_ref()
+                    .map_or(0, |payload| payload_text_size(payload, budget));
+                (record.id, size)
+            })
             .collect();
 
-        Ok(results)
+        Ok(trim_to_budget(points, budget.limit, |point| {
+            sizes.get(&point.id).copied().unwrap_or(0)
+        }))
     }
 
     /// To be called on the remote instance. Only used for the internal service.
     ///
     /// If the root query is a Fusion, the returned results correspond to each the prefetches.
     /// Otherwise, it will be a list with a single list of scored points.
+    ///
+    /// The work of the queried shards is counted into `search_stats`, if given.
     pub async fn query_batch_internal(
         &self,
         requests: Vec<ShardQueryRequest>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let requests_arc = Arc::new(requests);
 
@@ -225,6 +760,8 @@ impl Collection {
                 None,
                 shard_selection,
                 timeout,
+                false,
+                search_stats,
             )
             .await?;
 
@@ -250,79 +787,191 @@ impl Collection {
     async fn merge_intermediate_results_from_shards(
         &self,
         request: &ShardQueryRequest,
-        all_shards_results: Vec<ShardQueryResponse>,
+        all_shards_results: Vec<ShardQueryResults>,
     ) -> CollectionResult<ShardQueryResponse> {
         let query_infos = intermediate_query_infos(request);
-        let results_len = query_infos.len();
-        let mut results = ShardQueryResponse::with_capacity(results_len);
-        debug_assert!(all_shards_results
-            .iter()
-            .all(|shard_results| shard_results.len() == results_len));
-
         let collection_params = self.collection_config.read().await.params.clone();
 
-        // Shape: [num_internal_queries, num_shards, num_scored_points]
-        let all_shards_result_by_transposed = transposed_iter(all_shards_results);
+        // Results of all shards are held until they are merged, except for the spilled points
+        let memory_bytes = all_shards_results
+            .iter()
+            .flat_map(|shard_results| &shard_results.held)
+            .map(estimate_points_size)
+            .sum();
+        self.query_memory.lock().add(memory_bytes);
+
+        let merge_params = query_infos
+            .iter()
+            .map(|query_info| {
+                let order = ScoringQuery::order(query_info.scoring_query, &collection_params)?;
+                Ok((order, query_info.take))
+            })
+            .collect::<CollectionResult<_>>()?;
 
-        for (query_info, shards_results) in
-            query_infos.into_iter().zip(all_shards_result_by_transposed)
+        if all_shards_results
+            .iter()
+            .any(|shard_results| shard_results.spilled.is_some())
         {
-            // `shards_results` shape: [num_shards, num_scored_points]
-            let order = ScoringQuery::order(query_info.scoring_query, &collection_params)?;
-
-            // Equivalent to:
-            //
-            // shards_results
-            //     .into_iter()
-            //     .kmerge_by(match order {
-            //         Order::LargeBetter => |a, b| ScoredPointTies(a) > ScoredPointTies(b),
-            //         Order::SmallBetter => |a, b| ScoredPointTies(a) < ScoredPointTies(b),
-            //     })
-            //
-            // if the `kmerge_by` function were able to work with reference predicates.
-            // Either::Left and Either::Right are used to allow type inference to work.
-            //
-            let intermediate_result = match order {
-                Order::LargeBetter => Either::Left(
-                    shards_results
-                        .into_iter()
-                        .kmerge_by(|a, b| ScoredPointTies(a) > ScoredPointTies(b)),
-                ),
-                Order::SmallBetter => Either::Right(
-                    shards_results
+            // Spilled points are read back from disk while merging, which blocks
+            tokio::task::spawn_blocking(move || {
+                merge_shards_results(all_shards_results, merge_params)
+            })
+            .await?
+        } else {
+            merge_shards_results(all_shards_results, merge_params)
+        }
+    }
+}
+
+/// Merges the results of all shards, for each intermediate query with its order and the number
+/// of points to take
+fn merge_shards_results(
+    all_shards_results: Vec<ShardQueryResults>,
+    merge_params: Vec<(Order, usize)>,
+) -> CollectionResult<ShardQueryResponse> {
+    let results_len = merge_params.len();
+    let mut results = ShardQueryResponse::with_capacity(results_len);
+
+    // Read the spilled points back one by one while merging, after the held ones
+    let all_shards_results: Vec<Vec<_>> = all_shards_results
+        .into_iter()
+        .map(|shard_results| {
+            let ShardQueryResults { held, spilled } = shard_results;
+            let mut spilled_lists = spilled
+                .map(SpilledPoints::into_lists)
+                .transpose()?
+                .into_iter()
+                .flatten();
+
+            Ok(held
+                .into_iter()
+                .map(|points| {
+                    points
                         .into_iter()
-                        .kmerge_by(|a, b| ScoredPointTies(a) < ScoredPointTies(b)),
-                ),
+                        .map(Ok)
+                        .chain(spilled_lists.next().into_iter().flatten())
+                })
+                .collect())
+        })
+        .collect::<CollectionResult<_>>()?;
+    debug_assert!(all_shards_results
+        .iter()
+        .all(|shard_results| shard_results.len() == results_len));
+
+    // Shape: [num_internal_queries, num_shards, num_scored_points]
+    let all_shards_result_by_transposed = transposed_iter(all_shards_results);
+
+    for ((order, take), shards_results) in merge_params
+        .into_iter()
+        .zip(all_shards_result_by_transposed)
+    {
+        // `shards_results` shape: [num_shards, num_scored_points]
+        //
+        // Equivalent to:
+        //
+        // shards_results
+        //     .into_iter()
+        //     .kmerge_by(match order {
+        //         Order::LargeBetter => |a, b| ScoredPointTies(a) > ScoredPointTies(b),
+        //         Order::SmallBetter => |a, b| ScoredPointTies(a) < ScoredPointTies(b),
+        //     })
+        //
+        // if the `kmerge_by` function were able to work with reference predicates.
+        // Either::Left and Either::Right are used to allow type inference to work.
+        //
+        // Points are read from spilled results with a possible error, which comes first
+        // to abort the merge.
+        let intermediate_result = match order {
+            Order::LargeBetter => {
+                Either::Left(shards_results.into_iter().kmerge_by(|a, b| match (a, b) {
+                    (Ok(a), Ok(b)) => ScoredPointTies(a) > ScoredPointTies(b),
+                    (Err(_), _) => true,
+                    (_, Err(_)) => false,
+                }))
             }
-            .dedup()
-            .take(query_info.take)
-            .collect();
+            Order::SmallBetter => {
+                Either::Right(shards_results.into_iter().kmerge_by(|a, b| match (a, b) {
+                    (Ok(a), Ok(b)) => ScoredPointTies(a) < ScoredPointTies(b),
+                    (Err(_), _) => true,
+                    (_, Err(_)) => false,
+                }))
+            }
+        }
+        .dedup_by(|a, b| matches!((a, b), (Ok(a), Ok(b)) if a == b))
+        .take(take)
+        .collect::<CollectionResult<_>>()?;
 
-            results.push(intermediate_result);
+        results.push(intermediate_result);
+    }
+
+    Ok(results)
+}
+
+/// Keeps the points of a shard response in memory as long as they fit in the memory budget, the
+/// remaining points are spilled to disk one at a time, and dropped from memory as soon as they
+/// are written
+fn hold_or_spill_shard_response(
+    shard_response: ShardQueryResponse,
+    held_bytes: &AtomicUsize,
+    memory_budget: usize,
+    spill_dir: &Path,
+    collection_id: &str,
+) -> CollectionResult<ShardQueryResults> {
+    let num_lists = shard_response.len();
+    let mut held = Vec::with_capacity(num_lists);
+    let mut spill_writer: Option<SpillWriter> = None;
+
+    for (list, points) in shard_response.into_iter().enumerate() {
+        let mut held_points = Vec::new();
+        let mut points = points.into_iter();
+
+        if spill_writer.is_none() {
+            for point in points.by_ref() {
+                let bytes = estimate_points_size([&point]);
+                if held_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes <= memory_budget {
+                    held_points.push(point);
+                    continue;
+                }
+                held_bytes.fetch_sub(bytes, Ordering::Relaxed);
+
+                log::debug!(
+                    "Spilling query results of collection {collection_id} to disk, \
+                     as they exceed the memory budget of {memory_budget} bytes",
+                );
+                let writer = spill_writer.insert(SpillWriter::new(spill_dir)?);
+                writer.write(list, point)?;
+                break;
+            }
         }
 
-        Ok(results)
+        if let Some(writer) = &mut spill_writer {
+            for point in points {
+                writer.write(list, point)?;
+            }
+        }
+
+        held.push(held_points);
     }
+
+    let spilled = spill_writer
+        .map(|writer| writer.finish(num_lists))
+        .transpose()?;
+    Ok(ShardQueryResults { held, spilled })
 }
 
 /// Returns a list of the query that corresponds to each of the results in each shard.
 ///
 /// Example: `[info1, info2, info3]` corresponds to `[result1, result2, result3]` of each shard
 fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQueryInfo<'_>> {
-    let needs_intermediate_results = request
-        .query
-        .as_ref()
-        .map(|sq| sq.needs_intermediate_results())
-        .unwrap_or(false);
-
-    if needs_intermediate_results {
+    if request.needs_intermediate_results() {
         // In case of Fusion, expect the propagated intermediate results
         request
             .prefetches
             .iter()
             .map(|prefetch| IntermediateQueryInfo {
                 scoring_query: prefetch.query.as_ref(),
-                take: prefetch.limit,
+                // Prefetches fetch extra points to cover the offset of the fused results
+                take: prefetch.limit + request.offset,
             })
             .collect_vec()
     } else {
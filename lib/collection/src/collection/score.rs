@@ -0,0 +1,143 @@
+use std::iter;
+
+use common::types::ScoreType;
+use itertools::Itertools as _;
+use segment::data_types::vectors::{Vector, DEFAULT_VECTOR_NAME};
+use segment::types::{Distance, PointIdType};
+
+use super::Collection;
+use crate::common::fetch_vectors::{retrieve_points, ReferencedVectors};
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult, ScoreRequestInternal};
+use crate::operations::universal_query::collection_query::VectorInput;
+
+impl Collection {
+    /// Score the query against each of the targets with the distance of the vector, as searches
+    /// would score them, but without using the index. Scores are returned in the order of targets.
+    pub async fn score_vectors(
+        &self,
+        request: ScoreRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<ScoreType>> {
+        let ScoreRequestInternal {
+            query,
+            targets,
+            using,
+        } = request;
+
+        let using = using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string());
+        let distance = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .get_distance(&using)?;
+
+        let query = VectorInput::from(query);
+        let targets: Vec<_> = targets.into_iter().map(VectorInput::from).collect();
+
+        let ids: Vec<PointIdType> = iter::once(&query)
+            .chain(&targets)
+            .flat_map(VectorInput::referenced_ids)
+            .copied()
+            .unique()
+            .collect();
+
+        let mut referenced = ReferencedVectors::default();
+        if !ids.is_empty() {
+            let records = retrieve_points(
+                self,
+                ids.clone(),
+                vec![using.clone()],
+                read_consistency,
+                shard_selection,
+            )
+            .await?;
+            referenced.extend(None, records.into_iter().map(|record| (record.id, record)));
+            referenced.check_fetched(None, ids, &self.id)?;
+        }
+
+        let query = resolve_vector(&referenced, &using, query)?;
+        targets
+            .into_iter()
+            .map(|target| {
+                let target = resolve_vector(&referenced, &using, target)?;
+                score_vector(distance, &query, &target)
+            })
+            .collect()
+    }
+}
+
+fn resolve_vector(
+    referenced: &ReferencedVectors,
+    using: &str,
+    input: VectorInput,
+) -> CollectionResult<Vector> {
+    let mut vector = match input {
+        VectorInput::Vector(vector) => vector,
+        VectorInput::Id(point_id) => referenced
+            .resolve_reference(None, using, VectorInput::Id(point_id))
+            .ok_or_else(|| {
+                CollectionError::bad_input(format!(
+                    "Point {point_id} has no vector named `{using}`"
+                ))
+            })?,
+        VectorInput::Sum(terms) => referenced.resolve_sum(None, using, terms)?,
+    };
+
+    // Sparse vectors are scored by merging the sorted indices
+    if let Vector::Sparse(sparse) = &mut vector {
+        sparse.sort_by_indices();
+    }
+    Ok(vector)
+}
+
+/// Score of the target, computed the same way as for the stored vectors
+fn score_vector(
+    distance: Distance,
+    query: &Vector,
+    target: &Vector,
+) -> CollectionResult<ScoreType> {
+    let check_dim = |query_dim: usize, target_dim: usize| {
+        if query_dim == target_dim {
+            Ok(())
+        } else {
+            Err(CollectionError::bad_input(format!(
+                "Dimension of the target {target_dim} doesn't match the dimension of the query {query_dim}",
+            )))
+        }
+    };
+
+    let similarity = match (query, target) {
+        (Vector::Dense(query), Vector::Dense(target)) => {
+            check_dim(query.len(), target.len())?;
+            distance.similarity(query, target)
+        }
+        (Vector::Sparse(query), Vector::Sparse(target)) => {
+            // Sparse vectors are always scored with the dot product, without postprocessing
+            return Ok(query.score(target).unwrap_or_default());
+        }
+        (Vector::MultiDense(query), Vector::MultiDense(target)) => {
+            check_dim(query.dim, target.dim)?;
+            // Max similarity, the only supported multivector comparator
+            query
+                .multi_vectors()
+                .map(|query| {
+                    target
+                        .multi_vectors()
+                        .map(|target| distance.similarity(query, target))
+                        .fold(ScoreType::NEG_INFINITY, ScoreType::max)
+                })
+                .sum()
+        }
+        _ => {
+            return Err(CollectionError::bad_input(
+                "Query and targets must be vectors of the same type",
+            ));
+        }
+    };
+
+    Ok(distance.postprocess_score(similarity))
+}
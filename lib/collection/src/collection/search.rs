@@ -12,6 +12,7 @@ use segment::types::{
 use tokio::time::Instant;
 
 use super::Collection;
+use crate::common::query_memory::estimate_points_size;
 use crate::events::SlowQueryEvent;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
@@ -217,6 +218,7 @@ impl Collection {
             ids: search_result.iter().map(|x| x.id).collect(),
             with_payload,
             with_vector,
+            as_of_version: None,
         };
         let retrieved_records = self
             .retrieve(retrieve_request, read_consistency, shard_selection)
@@ -266,6 +268,13 @@ impl Collection {
                 Order::LargeBetter
             };
 
+            // Results of all shards are held until they are merged
+            let memory_bytes = all_searches_res
+                .iter()
+                .map(|res| estimate_points_size(&res[batch_index]))
+                .sum();
+            self.query_memory.lock().add(memory_bytes);
+
             let results_from_shards = all_searches_res
                 .iter_mut()
                 .map(|res| mem::take(&mut res[batch_index]));
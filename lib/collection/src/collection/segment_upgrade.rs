@@ -0,0 +1,204 @@
+//! Upgrade of the segments of a collection to the current storage format.
+//!
+//! The upgrade runs in the background and rewrites the outdated segments of the local shards one
+//! at a time, with an optional pause in between. Outdated segments stay marked on disk until they
+//! are rewritten, so an upgrade stopped or interrupted by a restart continues with the remaining
+//! segments when started again.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::cpu::CpuBudget;
+use parking_lot::Mutex;
+use segment::index::hnsw_index::num_rayon_threads;
+
+use super::Collection;
+use crate::operations::types::{
+    CollectionResult, SegmentUpgradeProgress, SegmentUpgradeRequest, SegmentUpgradeStatus,
+};
+use crate::shards::shard_holder::LockedShardHolder;
+
+/// Delay before trying again to rewrite a segment, which was under another optimization
+const SEGMENT_UPGRADE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Upgrade of the segments of the collection, stopped when the collection is dropped
+#[derive(Default)]
+pub(super) struct SegmentUpgrade {
+    progress: Arc<Mutex<SegmentUpgradeProgress>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl Drop for SegmentUpgrade {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Collection {
+    /// Start upgrading the outdated segments of the local shards, or continue a stopped one
+    pub async fn start_segment_upgrade(
+        &self,
+        request: SegmentUpgradeRequest,
+    ) -> CollectionResult<SegmentUpgradeProgress> {
+        let remaining = Self::count_outdated_segments(&self.shards_holder).await;
+        let max_indexing_threads = self
+            .collection_config
+            .read()
+            .await
+            .hnsw_config
+            .max_indexing_threads;
+
+        let mut upgrade = self.segment_upgrade.lock();
+        if upgrade.progress.lock().status == SegmentUpgradeStatus::Running {
+            return Ok(upgrade.progress.lock().clone());
+        }
+
+        upgrade.stopped.store(true, Ordering::Relaxed);
+        upgrade.stopped = Arc::new(AtomicBool::new(false));
+        upgrade.progress = Arc::new(Mutex::new(SegmentUpgradeProgress {
+            status: SegmentUpgradeStatus::Running,
+            total: remaining,
+            upgraded: 0,
+            remaining,
+            error: None,
+        }));
+
+        let task = run_segment_upgrade(
+            self.shards_holder.clone(),
+            upgrade.progress.clone(),
+            upgrade.stopped.clone(),
+            self.optimizer_cpu_budget.clone(),
+            num_rayon_threads(max_indexing_threads),
+            Duration::from_millis(request.throttle_ms.unwrap_or(0)),
+        );
+        self.update_runtime.spawn(task);
+
+        let progress = upgrade.progress.lock().clone();
+        Ok(progress)
+    }
+
+    /// Progress of the latest upgrade of the segments of the local shards
+    pub fn segment_upgrade_progress(&self) -> SegmentUpgradeProgress {
+        self.segment_upgrade.lock().progress.lock().clone()
+    }
+
+    /// Stop the running upgrade of the segments, after the segment currently being rewritten
+    pub fn stop_segment_upgrade(&self) -> SegmentUpgradeProgress {
+        let upgrade = self.segment_upgrade.lock();
+        upgrade.stopped.store(true, Ordering::Relaxed);
+
+        let mut progress = upgrade.progress.lock();
+        if progress.status == SegmentUpgradeStatus::Running {
+            progress.status = SegmentUpgradeStatus::Stopped;
+        }
+        progress.clone()
+    }
+
+    async fn count_outdated_segments(shards_holder: &LockedShardHolder) -> usize {
+        let shards_holder = shards_holder.read().await;
+        let mut count = 0;
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            count += replica_set.outdated_segments().await.len();
+        }
+        count
+    }
+}
+
+async fn run_segment_upgrade(
+    shards_holder: Arc<LockedShardHolder>,
+    progress: Arc<Mutex<SegmentUpgradeProgress>>,
+    stopped: Arc<AtomicBool>,
+    cpu_budget: CpuBudget,
+    desired_cpus: usize,
+    throttle: Duration,
+) {
+    let result = upgrade_segments(
+        &shards_holder,
+        &progress,
+        &stopped,
+        &cpu_budget,
+        desired_cpus,
+        throttle,
+    )
+    .await;
+
+    let mut progress = progress.lock();
+    match result {
+        // Stopped, possibly before the last segment was rewritten
+        Ok(()) if stopped.load(Ordering::Relaxed) => {
+            if progress.status == SegmentUpgradeStatus::Running {
+                progress.status = SegmentUpgradeStatus::Stopped;
+            }
+        }
+        Ok(()) => {
+            log::info!("Upgraded {} segments", progress.upgraded);
+            progress.status = SegmentUpgradeStatus::Done;
+        }
+        Err(err) => {
+            log::error!("Failed to upgrade segments: {err}");
+            progress.status = SegmentUpgradeStatus::Failed;
+            progress.error = Some(err.to_string());
+        }
+    }
+}
+
+/// Rewrite outdated segments of the local shards until there are none left, or stopped
+async fn upgrade_segments(
+    shards_holder: &LockedShardHolder,
+    progress: &Mutex<SegmentUpgradeProgress>,
+    stopped: &Arc<AtomicBool>,
+    cpu_budget: &CpuBudget,
+    desired_cpus: usize,
+    throttle: Duration,
+) -> CollectionResult<()> {
+    while !stopped.load(Ordering::Relaxed) {
+        // Wait for CPU budget without holding the shards, then start rewriting the first outdated
+        // segment found, if the budget is still available
+        cpu_budget.notify_on_budget_available(desired_cpus).await;
+
+        let mut remaining = 0;
+        let mut task = None;
+        {
+            let shards_holder = shards_holder.read().await;
+            for (_shard_id, replica_set) in shards_holder.get_shards() {
+                let outdated_segments = replica_set.outdated_segments().await;
+                remaining += outdated_segments.len();
+
+                let Some(segment_id) = outdated_segments.first().copied() else {
+                    continue;
+                };
+                if task.is_some() {
+                    continue;
+                }
+                if let Some(permit) = cpu_budget.try_acquire(desired_cpus) {
+                    task = Some(
+                        replica_set
+                            .spawn_segment_upgrade(segment_id, permit, stopped.clone())
+                            .await?,
+                    );
+                }
+            }
+        }
+        progress.lock().remaining = remaining;
+
+        if remaining == 0 {
+            break;
+        }
+        let Some(task) = task else {
+            continue;
+        };
+
+        if task.await?? {
+            {
+                let mut progress = progress.lock();
+                progress.upgraded += 1;
+                progress.remaining = progress.remaining.saturating_sub(1);
+            }
+            tokio::time::sleep(throttle).await;
+        } else {
+            tokio::time::sleep(SEGMENT_UPGRADE_RETRY_DELAY).await;
+        }
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@ use std::path::Path;
 
 use io::file_operations::read_json;
 use io::storage_version::StorageVersion as _;
+use segment::types::ShardKey;
 use tokio::fs;
 
 use super::Collection;
@@ -10,8 +11,8 @@ use crate::collection::CollectionVersion;
 use crate::common::snapshots_manager::SnapshotStorageManager;
 use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
 use crate::config::{CollectionConfig, ShardingMethod};
-use crate::operations::snapshot_ops::SnapshotDescription;
-use crate::operations::types::{CollectionError, CollectionResult, NodeType};
+use crate::operations::snapshot_ops::{ShardKeySnapshotDescription, SnapshotDescription};
+use crate::operations::types::{CollectionError, CollectionResult, NodeType, ShardWalCompaction};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::replica_set::ShardReplicaSet;
@@ -167,7 +168,7 @@ impl Collection {
         })?;
 
         let snapshot_manager = self.get_snapshots_storage_manager()?;
-        snapshot_manager
+        let snapshot_description = snapshot_manager
             .store_file(snapshot_temp_arc_file.path(), snapshot_path.as_path())
             .await
             .map_err(|err| {
@@ -175,7 +176,37 @@ impl Collection {
                     "failed to store snapshot archive to {}: {err}",
                     snapshot_temp_arc_file.path().display()
                 ))
-            })
+            })?;
+
+        if self.shared_storage_config.snapshots_config.compact_wal {
+            // The snapshot is created, failing to compact the WAL is not an error of the request
+            match self.compact_wal().await {
+                Ok(compactions) => log::debug!(
+                    "Compacted WAL of collection {} after snapshot: {compactions:?}",
+                    self.name(),
+                ),
+                Err(err) => log::warn!(
+                    "Failed to compact WAL of collection {} after snapshot: {err}",
+                    self.name(),
+                ),
+            }
+        }
+
+        Ok(snapshot_description)
+    }
+
+    /// Compact the WAL of the local shards, see [`ShardReplicaSet::compact_wal`]
+    pub async fn compact_wal(&self) -> CollectionResult<Vec<ShardWalCompaction>> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut compactions = Vec::new();
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            if !replica_set.has_local_shard().await {
+                continue;
+            }
+            compactions.push(replica_set.compact_wal().await?);
+        }
+        compactions.sort_unstable_by_key(|compaction| compaction.shard_id);
+        Ok(compactions)
     }
 
     /// Restore collection from snapshot
@@ -298,6 +329,61 @@ impl Collection {
             .await
     }
 
+    /// Shards of the given shard key, in all peers
+    pub async fn shard_key_shard_ids(
+        &self,
+        shard_key: &ShardKey,
+    ) -> CollectionResult<Vec<ShardId>> {
+        let mut shard_ids: Vec<_> = self
+            .shards_holder
+            .read()
+            .await
+            .get_shard_ids_by_key(shard_key)?
+            .into_iter()
+            .collect();
+        shard_ids.sort_unstable();
+        Ok(shard_ids)
+    }
+
+    /// Create a snapshot of each shard of the given shard key, which is local to this peer
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn create_shard_key_snapshots(
+        &self,
+        shard_key: &ShardKey,
+        temp_dir: &Path,
+    ) -> CollectionResult<Vec<ShardKeySnapshotDescription>> {
+        let shards_holder = self.shards_holder.read().await;
+
+        let mut shard_ids: Vec<_> = shards_holder
+            .get_shard_ids_by_key(shard_key)?
+            .into_iter()
+            .collect();
+        shard_ids.sort_unstable();
+
+        let mut snapshots = Vec::with_capacity(shard_ids.len());
+        for shard_id in shard_ids {
+            if shards_holder.is_shard_local_or_queue_proxy(&shard_id).await != Some(true) {
+                continue;
+            }
+
+            let snapshot = shards_holder
+                .create_shard_snapshot(&self.snapshots_path, &self.name(), shard_id, temp_dir)
+                .await?;
+            snapshots.push(ShardKeySnapshotDescription { shard_id, snapshot });
+        }
+
+        if snapshots.is_empty() {
+            return Err(CollectionError::bad_request(format!(
+                "Shard key {shard_key} has no local shards on this peer"
+            )));
+        }
+
+        Ok(snapshots)
+    }
+
     /// # Cancel safety
     ///
     /// This method is *not* cancel safe.
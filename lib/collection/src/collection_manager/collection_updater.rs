@@ -1,7 +1,8 @@
 use parking_lot::RwLock;
-use segment::types::SeqNumberType;
+use segment::types::{PointIdType, SeqNumberType};
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::collection_manager::point_history::PointHistory;
 use crate::collection_manager::segments_updater::*;
 use crate::operations::types::CollectionResult;
 use crate::operations::CollectionUpdateOperations;
@@ -42,11 +43,39 @@ impl CollectionUpdater {
         }
     }
 
+    /// Point history is best effort, failing to capture it must not fail the update
+    fn capture_point_history(
+        segments: &RwLock<SegmentHolder>,
+        point_ids: &[PointIdType],
+        deleted_at: Option<SeqNumberType>,
+    ) {
+        if let Err(err) = PointHistory::capture(segments, point_ids, deleted_at) {
+            log::warn!("Failed to update point history: {err}");
+        }
+    }
+
     pub fn update(
         segments: &RwLock<SegmentHolder>,
         op_num: SeqNumberType,
         operation: CollectionUpdateOperations,
     ) -> CollectionResult<usize> {
+        // Points to keep previous versions of, if point history is enabled
+        let history_point_ids = {
+            let segments_read = segments.read();
+            if segments_read.point_history.is_enabled() {
+                affected_point_ids(&segments_read, &operation)
+                    .map_err(|err| log::warn!("Failed to collect points for history: {err}"))
+                    .ok()
+            } else {
+                None
+            }
+        };
+
+        // Remember state of points before the update, in case we don't have it yet
+        if let Some(point_ids) = &history_point_ids {
+            CollectionUpdater::capture_point_history(segments, point_ids, None);
+        }
+
         // Allow only one update at a time, ensure no data races between segments.
         // let _lock = self.update_lock.lock().unwrap();
         let operation_result = match operation {
@@ -66,6 +95,10 @@ impl CollectionUpdater {
 
         CollectionUpdater::handle_update_result(segments, op_num, &operation_result);
 
+        if let (Ok(_), Some(point_ids)) = (&operation_result, &history_point_ids) {
+            CollectionUpdater::capture_point_history(segments, point_ids, Some(op_num));
+        }
+
         operation_result
     }
 }
@@ -6,6 +6,7 @@ use std::sync::Arc;
 use bitvec::prelude::BitVec;
 use common::types::{PointOffsetType, TelemetryDetail};
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
+use segment::common::hyperloglog::HyperLogLog;
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::order_by::OrderValue;
@@ -14,7 +15,7 @@ use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
 use segment::json_path::JsonPath;
-use segment::telemetry::SegmentTelemetry;
+use segment::telemetry::{HnswGraphTelemetry, SegmentTelemetry};
 use segment::types::{
     Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
     ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
@@ -662,6 +663,38 @@ impl SegmentEntry for ProxySegment {
         Ok(read_points)
     }
 
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
+    ) -> Vec<(u64, PointIdType)> {
+        let deleted_points = self.deleted_points.read();
+        let mut read_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_random_filtered(limit, filter, seed)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_random_filtered(
+                limit,
+                Some(&wrapped_filter),
+                seed,
+            )
+        };
+        let mut write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_random_filtered(limit, filter, seed);
+        read_points.append(&mut write_segment_points);
+        read_points.sort_unstable_by(|a, b| b.cmp(a));
+        read_points.truncate(limit);
+        read_points
+    }
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
@@ -684,6 +717,42 @@ impl SegmentEntry for ProxySegment {
         };
     }
 
+    fn existing_points(&self, point_ids: &[PointIdType]) -> Vec<PointIdType> {
+        let in_write_segment: HashSet<_> = self
+            .write_segment
+            .get()
+            .read()
+            .existing_points(point_ids)
+            .into_iter()
+            .collect();
+
+        let not_deleted: Vec<_> = {
+            let deleted_points = self.deleted_points.read();
+            point_ids
+                .iter()
+                .copied()
+                .filter(|point_id| {
+                    !in_write_segment.contains(point_id) && !deleted_points.contains(point_id)
+                })
+                .collect()
+        };
+        let in_wrapped_segment: HashSet<_> = self
+            .wrapped_segment
+            .get()
+            .read()
+            .existing_points(&not_deleted)
+            .into_iter()
+            .collect();
+
+        point_ids
+            .iter()
+            .copied()
+            .filter(|point_id| {
+                in_write_segment.contains(point_id) || in_wrapped_segment.contains(point_id)
+            })
+            .collect()
+    }
+
     fn available_point_count(&self) -> usize {
         let deleted_points_count = self.deleted_points.read().len();
         let wrapped_segment_count = self.wrapped_segment.get().read().available_point_count();
@@ -903,6 +972,61 @@ impl SegmentEntry for ProxySegment {
             .collect()
     }
 
+    fn keyword_values(&self, key: PayloadKeyTypeRef, limit: usize) -> Option<HashSet<String>> {
+        if self.deleted_indexes.read().contains(key) {
+            return None;
+        }
+        let mut values = self
+            .wrapped_segment
+            .get()
+            .read()
+            .keyword_values(key, limit)?;
+        values.extend(self.write_segment.get().read().keyword_values(key, limit)?);
+        (values.len() <= limit).then_some(values)
+    }
+
+    fn keyword_values_sketch(&self, key: PayloadKeyTypeRef) -> Option<HyperLogLog> {
+        if self.deleted_indexes.read().contains(key) {
+            return None;
+        }
+        let mut sketch = self
+            .wrapped_segment
+            .get()
+            .read()
+            .keyword_values_sketch(key)?;
+        sketch.merge(&self.write_segment.get().read().keyword_values_sketch(key)?);
+        Some(sketch)
+    }
+
+    fn hnsw_neighbors(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> OperationResult<Option<Vec<Vec<PointIdType>>>> {
+        // Only the wrapped segment may have an HNSW graph
+        let deleted_points = self.deleted_points.read();
+        if deleted_points.contains(&point_id) {
+            return Ok(None);
+        }
+        let neighbors = self
+            .wrapped_segment
+            .get()
+            .read()
+            .hnsw_neighbors(vector_name, point_id)?
+            .map(|levels| {
+                levels
+                    .into_iter()
+                    .map(|level| {
+                        level
+                            .into_iter()
+                            .filter(|neighbor| !deleted_points.contains(neighbor))
+                            .collect()
+                    })
+                    .collect()
+            });
+        Ok(neighbors)
+    }
+
     fn check_error(&self) -> Option<SegmentFailedState> {
         self.write_segment.get().read().check_error()
     }
@@ -985,6 +1109,14 @@ impl SegmentEntry for ProxySegment {
         self.wrapped_segment.get().read().get_telemetry_data(detail)
     }
 
+    fn compute_hnsw_graph_stats(&self) {
+        self.wrapped_segment.get().read().compute_hnsw_graph_stats()
+    }
+
+    fn hnsw_graphs_telemetry(&self) -> Vec<HnswGraphTelemetry> {
+        self.wrapped_segment.get().read().hnsw_graphs_telemetry()
+    }
+
     fn fill_query_context(&self, query_context: &mut QueryContext) {
         // Information from temporary segment is not too important for query context
         self.wrapped_segment
@@ -1333,6 +1465,30 @@ mod tests {
         assert_eq!(original_points.len() - 1, proxy_res.len());
     }
 
+    #[test]
+    fn test_existing_points() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let original_segment = LockedSegment::new(build_segment_1(dir.path()));
+
+        let mut proxy_segment = wrap_proxy(&dir, original_segment);
+
+        proxy_segment.delete_point(100, 2.into()).unwrap();
+        proxy_segment
+            .upsert_point(101, 10.into(), only_default_vector(&[1.0, 1.0, 1.0, 1.0]))
+            .unwrap();
+
+        let point_ids: Vec<PointIdType> = vec![10.into(), 1.into(), 2.into(), 42.into(), 3.into()];
+        let existing_points = proxy_segment.existing_points(&point_ids);
+
+        assert_eq!(existing_points, vec![10.into(), 1.into(), 3.into()]);
+        for point_id in point_ids {
+            assert_eq!(
+                existing_points.contains(&point_id),
+                proxy_segment.has_point(point_id),
+            );
+        }
+    }
+
     #[test]
     fn test_sync_indexes() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -1553,6 +1709,7 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut original_segment = build_segment(dir.path(), &config, true).unwrap();
         let write_segment = build_segment(dir.path(), &config, true).unwrap();
@@ -18,6 +18,7 @@ use segment::types::{PointIdType, SegmentConfig, SeqNumberType};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
+use crate::collection_manager::point_history::PointHistory;
 use crate::config::CollectionParams;
 use crate::operations::types::CollectionError;
 use crate::shards::update_tracker::UpdateTracker;
@@ -152,6 +153,9 @@ pub struct SegmentHolder {
 
     /// Holds the first uncorrected error happened with optimizer
     pub optimizer_errors: Option<CollectionError>,
+
+    /// Previous versions of updated points, if enabled
+    pub point_history: PointHistory,
 }
 
 pub type LockedSegmentHolder = Arc<RwLock<SegmentHolder>>;
@@ -396,10 +400,7 @@ impl<'s> SegmentHolder {
 
     /// Selects point ids, which is stored in this segment
     fn segment_points(&self, ids: &[PointIdType], segment: &dyn SegmentEntry) -> Vec<PointIdType> {
-        ids.iter()
-            .cloned()
-            .filter(|id| segment.has_point(*id))
-            .collect()
+        segment.existing_points(ids)
     }
 
     pub fn for_each_segment<F>(&self, mut f: F) -> OperationResult<usize>
@@ -618,7 +619,7 @@ impl<'s> SegmentHolder {
         for segment in segments {
             let segment_arc = segment.get();
             let read_segment = segment_arc.read();
-            for point in ids.iter().cloned().filter(|id| read_segment.has_point(*id)) {
+            for point in read_segment.existing_points(ids) {
                 let is_ok = f(point, &read_segment)?;
                 read_points += is_ok as usize;
             }
@@ -648,6 +649,19 @@ impl<'s> SegmentHolder {
     /// If there are unsaved changes after flush - detects lowest unsaved change version.
     /// If all changes are saved - returns max version.
     pub fn flush_all(&self, sync: bool) -> OperationResult<SeqNumberType> {
+        self.flush_all_segments(sync)
+            .map(|(flushed_version, _all_persisted)| flushed_version)
+    }
+
+    /// Flushes all segments, see [`SegmentHolder::flush_all`]
+    ///
+    /// Additionally returns whether all changes of all segments are persisted. Proxy segments
+    /// keep some changes in memory, so they never count as fully persisted.
+    pub fn flush_all_segments(&self, sync: bool) -> OperationResult<(SeqNumberType, bool)> {
+        let has_proxies = self
+            .iter()
+            .any(|(_, segment)| matches!(segment, LockedSegment::Proxy(_)));
+
         // Grab and keep to segment RwLock's until the end of this function
         let segments = self.segment_locks(self.segment_flush_ordering())?;
 
@@ -711,9 +725,9 @@ impl<'s> SegmentHolder {
         }
 
         if has_unsaved {
-            Ok(min_unsaved_version)
+            Ok((min_unsaved_version, false))
         } else {
-            Ok(max_persisted_version)
+            Ok((max_persisted_version, !has_proxies))
         }
     }
 
@@ -881,6 +895,7 @@ impl<'s> SegmentHolder {
                     .to_sparse_vector_data()
                     .map_err(|err| OperationError::service_error(format!("Failed to source sparse vector configuration from collection parameters: {err:?}")))?,
                 payload_storage_type: collection_params.payload_storage_type(),
+                payload_compression: collection_params.payload_compression.clone(),
             },
             // Fall back: base config on existing appendable segment
             None => {
@@ -1258,7 +1273,7 @@ mod tests {
     use tempfile::Builder;
 
     use super::*;
-    use crate::collection_manager::fixtures::{build_segment_1, build_segment_2};
+    use crate::collection_manager::fixtures::{build_segment_1, build_segment_2, empty_segment};
 
     #[test]
     fn test_add_and_swap() {
@@ -1477,6 +1492,37 @@ mod tests {
         assert!(!holder.get(sid1).unwrap().get().read().has_point(5.into()));
     }
 
+    #[test]
+    fn test_flush_all_segments() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segment1 = build_segment_1(dir.path());
+        let segment2 = build_segment_2(dir.path());
+        let max_version = segment1.version().max(segment2.version());
+
+        let mut holder = SegmentHolder::default();
+        let sid1 = holder.add_new(segment1);
+        holder.add_new(segment2);
+
+        assert_eq!(
+            holder.flush_all_segments(true).unwrap(),
+            (max_version, true)
+        );
+
+        // Proxies keep changes in memory, so segments never count as fully persisted with them
+        let wrapped_segment = holder.remove(&[sid1]).pop().unwrap();
+        let proxy = ProxySegment::new(
+            wrapped_segment,
+            LockedSegment::new(empty_segment(dir.path())),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        );
+        holder.add_new(proxy);
+
+        let (_, all_persisted) = holder.flush_all_segments(true).unwrap();
+        assert!(!all_persisted);
+    }
+
     #[test]
     fn test_snapshot_all() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -1,6 +1,7 @@
 pub mod collection_updater;
 pub mod holders;
 pub mod optimizers;
+pub mod point_history;
 pub mod segments_searcher;
 
 mod probabilistic_segment_search_sampling;
@@ -126,6 +126,11 @@ impl ConfigMismatchOptimizer {
                     return Some((*idx, vector_size)); // Skip segments with payload mismatch
                 }
 
+                if self.collection_params.payload_compression != segment_config.payload_compression
+                {
+                    return Some((*idx, vector_size)); // Rewrite payload with current compression
+                }
+
                 // Determine whether dense data in segment has mismatch
                 let dense_has_mismatch =
                     segment_config
@@ -10,7 +10,7 @@ use crate::collection_manager::holders::segment_holder::{
     LockedSegmentHolder, SegmentHolder, SegmentId,
 };
 use crate::collection_manager::optimizers::segment_optimizer::{
-    OptimizerThresholds, SegmentOptimizer,
+    OptimizerThresholds, SegmentNumber, SegmentOptimizer,
 };
 use crate::config::CollectionParams;
 
@@ -21,7 +21,7 @@ const BYTES_IN_KB: usize = 1024;
 /// The process of index creation is slow and CPU-bounded, so it is convenient to perform
 /// index building in a same way as segment re-creation.
 pub struct IndexingOptimizer {
-    default_segments_number: usize,
+    default_segments_number: SegmentNumber,
     thresholds_config: OptimizerThresholds,
     segments_path: PathBuf,
     collection_temp_dir: PathBuf,
@@ -33,7 +33,7 @@ pub struct IndexingOptimizer {
 
 impl IndexingOptimizer {
     pub fn new(
-        default_segments_number: usize,
+        default_segments_number: impl Into<SegmentNumber>,
         thresholds_config: OptimizerThresholds,
         segments_path: PathBuf,
         collection_temp_dir: PathBuf,
@@ -42,7 +42,7 @@ impl IndexingOptimizer {
         quantization_config: Option<QuantizationConfig>,
     ) -> Self {
         IndexingOptimizer {
-            default_segments_number,
+            default_segments_number: default_segments_number.into(),
             thresholds_config,
             segments_path,
             collection_temp_dir,
@@ -189,11 +189,14 @@ impl IndexingOptimizer {
         let (selected_segment_id, selected_segment_size) = *selected_segment.unwrap();
 
         let number_of_segments = segments_read_guard.len();
+        let default_segments_number = self
+            .default_segments_number
+            .target_for(&segments_read_guard);
 
         // If the number of segments if equal or bigger than the default_segments_number
         // We want to make sure that we at least do not increase number of segments after optimization, thus we take more than one segment to optimize
 
-        if number_of_segments < self.default_segments_number {
+        if number_of_segments < default_segments_number {
             return vec![selected_segment_id];
         }
 
@@ -11,18 +11,22 @@ use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
 };
 use crate::collection_manager::optimizers::segment_optimizer::{
-    OptimizerThresholds, SegmentOptimizer,
+    OptimizerThresholds, SegmentNumber, SegmentOptimizer,
 };
 use crate::config::CollectionParams;
 
 const BYTES_IN_KB: usize = 1024;
 
+/// Maximal number of segments merged at once, if the number of segments is adjusted automatically.
+/// When the target drops, e.g. after the node lost CPUs, segments are merged gradually.
+const AUTO_MAX_MERGE_CANDIDATES: usize = 3;
+
 /// Optimizer that tries to reduce number of segments until it fits configured value.
 /// It merges 3 smallest segments into a single large segment.
 /// Merging 3 segments instead of 2 guarantees that after the optimization the number of segments
 /// will be less than before.
 pub struct MergeOptimizer {
-    default_segments_number: usize,
+    default_segments_number: SegmentNumber,
     thresholds_config: OptimizerThresholds,
     segments_path: PathBuf,
     collection_temp_dir: PathBuf,
@@ -35,7 +39,7 @@ pub struct MergeOptimizer {
 impl MergeOptimizer {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        default_segments_number: usize,
+        default_segments_number: impl Into<SegmentNumber>,
         thresholds_config: OptimizerThresholds,
         segments_path: PathBuf,
         collection_temp_dir: PathBuf,
@@ -44,7 +48,7 @@ impl MergeOptimizer {
         quantization_config: Option<QuantizationConfig>,
     ) -> Self {
         MergeOptimizer {
-            default_segments_number,
+            default_segments_number: default_segments_number.into(),
             thresholds_config,
             segments_path,
             collection_temp_dir,
@@ -99,10 +103,14 @@ impl SegmentOptimizer for MergeOptimizer {
             })
             .collect_vec();
 
-        if raw_segments.len() <= self.default_segments_number {
+        let default_segments_number = self.default_segments_number.target_for(&read_segments);
+        if raw_segments.len() <= default_segments_number {
             return vec![];
         }
-        let max_candidates = raw_segments.len() - self.default_segments_number + 2;
+        let mut max_candidates = raw_segments.len() - default_segments_number + 2;
+        if self.default_segments_number == SegmentNumber::Auto {
+            max_candidates = max_candidates.min(AUTO_MAX_MERGE_CANDIDATES);
+        }
 
         // Find at least top-3 smallest segments to join.
         // We need 3 segments because in this case we can guarantee that total segments number will be less
@@ -161,6 +169,8 @@ mod tests {
     use super::*;
     use crate::collection_manager::fixtures::{get_merge_optimizer, random_segment};
     use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
+    use crate::optimizers_builder::OptimizersConfig;
+    use crate::tests::fixtures::TEST_OPTIMIZERS_CONFIG;
 
     #[test]
     fn test_max_merge_size() {
@@ -180,7 +190,7 @@ mod tests {
 
         let locked_holder = Arc::new(RwLock::new(holder));
 
-        merge_optimizer.default_segments_number = 1;
+        merge_optimizer.default_segments_number = SegmentNumber::Fixed(1);
 
         merge_optimizer.thresholds_config.max_segment_size_kb = 100;
 
@@ -196,6 +206,43 @@ mod tests {
         assert_eq!(check_result.len(), 3);
     }
 
+    #[test]
+    fn test_auto_merge_small_shard() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+        let dim = 256;
+        let mut merge_optimizer = get_merge_optimizer(dir.path(), temp_dir.path(), dim, None);
+        merge_optimizer.default_segments_number = SegmentNumber::Auto;
+
+        // Segments of a new, empty shard are not merged
+        let optimizers_config = OptimizersConfig {
+            default_segment_number: 0,
+            ..TEST_OPTIMIZERS_CONFIG
+        };
+        let mut holder = SegmentHolder::default();
+        for _ in 0..optimizers_config.get_number_segments() {
+            holder.add_new(random_segment(dir.path(), 100, 0, dim));
+        }
+        let locked_holder = Arc::new(RwLock::new(holder));
+
+        let suggested_for_merge =
+            merge_optimizer.check_condition(locked_holder, &Default::default());
+        assert!(suggested_for_merge.is_empty());
+
+        // Too many segments for the size of the shard, e.g. after the node lost CPUs
+        let mut holder = SegmentHolder::default();
+        for _ in 0..8 {
+            holder.add_new(random_segment(dir.path(), 100, 3, dim));
+        }
+        let locked_holder = Arc::new(RwLock::new(holder));
+
+        // Merged gradually, a few segments at a time
+        let suggested_for_merge =
+            merge_optimizer.check_condition(locked_holder, &Default::default());
+        assert_eq!(suggested_for_merge.len(), AUTO_MAX_MERGE_CANDIDATES);
+    }
+
     #[test]
     fn test_merge_optimizer() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
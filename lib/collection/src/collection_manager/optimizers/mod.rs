@@ -57,6 +57,14 @@ impl TrackerLog {
         });
     }
 
+    /// Number of optimizations, which are still running
+    pub fn running_count(&self) -> usize {
+        self.descriptions
+            .iter()
+            .filter(|tracker| tracker.state.lock().status == TrackerStatus::Optimizing)
+            .count()
+    }
+
     /// Convert log into list of objects usable in telemetry
     pub fn to_telemetry(&self) -> Vec<TrackerTelemetry> {
         self.descriptions
@@ -68,6 +76,26 @@ impl TrackerLog {
     }
 }
 
+/// Progress of the optimizations of shards
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OptimizationsProgress {
+    /// Number of optimizations running right now
+    pub running: usize,
+    /// Number of optimizations finished successfully since the shards were loaded
+    pub finished: usize,
+}
+
+impl std::ops::Add for OptimizationsProgress {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            running: self.running + other.running,
+            finished: self.finished + other.finished,
+        }
+    }
+}
+
 /// Tracks the state of an optimizer
 #[derive(Clone, Debug)]
 pub struct Tracker {
@@ -24,11 +24,12 @@ use segment::types::{
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
-    LockedSegment, LockedSegmentHolder, SegmentId,
+    LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
+use crate::optimizers_builder::HNSW_CHECKPOINTS_PATH;
 
 const BYTES_IN_KB: usize = 1024;
 
@@ -39,6 +40,72 @@ pub struct OptimizerThresholds {
     pub indexing_threshold_kb: usize,
 }
 
+/// Smallest size of vectors per segment, the automatic number of segments aims for
+const AUTO_MIN_SEGMENT_SIZE_KB: usize = 20_000;
+
+/// Smallest automatic number of segments, so that writes are not blocked by optimizing a single
+/// appendable segment
+const AUTO_MIN_SEGMENT_NUMBER: usize = 2;
+
+/// Target number of segments of a shard, the optimizers try to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentNumber {
+    /// Explicitly configured number of segments
+    Fixed(usize),
+    /// Adjusted to the size of the shard and the number of available CPUs.
+    ///
+    /// Small shards are not spread over many tiny segments, while large shards get a segment per
+    /// CPU to search in parallel. The number of CPUs is checked on every optimization, so a node
+    /// resized to more or less CPUs converges to the new target over subsequent optimizations.
+    Auto,
+}
+
+impl From<usize> for SegmentNumber {
+    fn from(number: usize) -> Self {
+        SegmentNumber::Fixed(number)
+    }
+}
+
+impl SegmentNumber {
+    /// Target number of segments for a shard with the given size of vectors
+    pub fn target(self, vectors_size_bytes: usize) -> usize {
+        match self {
+            SegmentNumber::Fixed(number) => number,
+            SegmentNumber::Auto => {
+                let num_cpus = common::cpu::get_num_cpus();
+                Self::auto_target(vectors_size_bytes, num_cpus)
+            }
+        }
+    }
+
+    /// Target number of segments for the current size of vectors of the shard
+    pub fn target_for(self, segments: &SegmentHolder) -> usize {
+        let vectors_size_bytes = match self {
+            // Don't compute the size, if it doesn't matter
+            SegmentNumber::Fixed(_) => 0,
+            SegmentNumber::Auto => segments
+                .iter()
+                .map(|(_, segment)| {
+                    segment
+                        .get()
+                        .read()
+                        .max_available_vectors_size_in_bytes()
+                        .unwrap_or_default()
+                })
+                .sum(),
+        };
+        self.target(vectors_size_bytes)
+    }
+
+    fn auto_target(vectors_size_bytes: usize, num_cpus: usize) -> usize {
+        let by_size = vectors_size_bytes.div_ceil(AUTO_MIN_SEGMENT_SIZE_KB * BYTES_IN_KB);
+        by_size.clamp(
+            AUTO_MIN_SEGMENT_NUMBER,
+            num_cpus.max(AUTO_MIN_SEGMENT_NUMBER),
+        )
+    }
+}
+
 /// SegmentOptimizer - trait implementing common functionality of the optimizers
 ///
 /// It provides functions which allow to re-build specified segments into a new, better one.
@@ -89,6 +156,7 @@ pub trait SegmentOptimizer {
             } else {
                 PayloadStorageType::InMemory
             },
+            payload_compression: collection_params.payload_compression.clone(),
         };
         Ok(LockedSegment::new(build_segment(
             self.segments_path(),
@@ -300,6 +368,7 @@ pub trait SegmentOptimizer {
             } else {
                 PayloadStorageType::InMemory
             },
+            payload_compression: collection_params.payload_compression.clone(),
         };
 
         Ok(SegmentBuilder::new(
@@ -409,6 +478,7 @@ pub trait SegmentOptimizer {
         stopped: &AtomicBool,
     ) -> CollectionResult<Segment> {
         let mut segment_builder = self.optimized_segment_builder(optimizing_segments)?;
+        segment_builder.set_hnsw_checkpoints_path(self.temp_path().join(HNSW_CHECKPOINTS_PATH));
 
         self.check_cancellation(stopped)?;
 
@@ -680,3 +750,27 @@ pub trait SegmentOptimizer {
         Ok(true)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_segment_number() {
+        let mb = 1024 * 1024;
+
+        // Small shards are not over-segmented
+        assert_eq!(SegmentNumber::auto_target(0, 16), 2);
+        assert_eq!(SegmentNumber::auto_target(10 * mb, 16), 2);
+        assert_eq!(SegmentNumber::auto_target(100 * mb, 16), 6);
+
+        // Large shards get a segment per CPU, not capped at a fixed number
+        assert_eq!(SegmentNumber::auto_target(10_000 * mb, 16), 16);
+        assert_eq!(SegmentNumber::auto_target(10_000 * mb, 64), 64);
+
+        // Not less than 2 segments, even with a single CPU
+        assert_eq!(SegmentNumber::auto_target(10_000 * mb, 1), 2);
+
+        assert_eq!(SegmentNumber::Fixed(7).target(10_000 * mb), 7);
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use parking_lot::RwLock;
+use segment::common::operation_error::OperationError;
+use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::{Vector, VectorStructInternal};
+use segment::types::{Payload, PointIdType, SeqNumberType, WithPayload, WithVector};
+
+use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::operations::types::{CollectionResult, Record};
+
+/// Payload and vectors of a point at some version
+#[derive(Debug, Clone, PartialEq)]
+struct PointContent {
+    payload: Payload,
+    vectors: HashMap<String, Vector>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PointVersion {
+    version: SeqNumberType,
+    /// `None` if the point was deleted at this version
+    content: Option<PointContent>,
+}
+
+/// Max number of versions kept for each point
+pub const MAX_POINT_HISTORY_SIZE: usize = 100;
+
+/// Max number of versions kept by a shard, over all points
+pub const MAX_SHARD_HISTORY_VERSIONS: usize = 100_000;
+
+/// Keeps the last N versions of every updated point in memory.
+///
+/// Used to read points as they were at some older version, e.g. to debug ranking changes after
+/// re-embedding. Versions are local to the shard replica, as they are WAL operation numbers.
+///
+/// History is not persisted: it starts empty after a restart and is not transferred with the
+/// shard. Once the shard keeps more than `capacity` versions, history of the least recently
+/// updated points is dropped.
+#[derive(Debug)]
+pub struct PointHistory {
+    /// Number of versions to keep for each point, history is disabled if 0
+    size: usize,
+    /// Max number of versions to keep over all points
+    capacity: usize,
+    versions: HashMap<PointIdType, VecDeque<PointVersion>>,
+    /// Last version of every point with history, least recently updated first
+    last_updates: BTreeSet<(SeqNumberType, PointIdType)>,
+    /// Number of versions kept over all points
+    total_versions: usize,
+}
+
+impl Default for PointHistory {
+    fn default() -> Self {
+        Self {
+            size: 0,
+            capacity: MAX_SHARD_HISTORY_VERSIONS,
+            versions: HashMap::new(),
+            last_updates: BTreeSet::new(),
+            total_versions: 0,
+        }
+    }
+}
+
+impl PointHistory {
+    pub fn is_enabled(&self) -> bool {
+        self.size > 0
+    }
+
+    /// Change number of versions to keep for each point, drops versions that no longer fit
+    pub fn resize(&mut self, size: usize) {
+        let size = size.min(MAX_POINT_HISTORY_SIZE);
+        self.size = size;
+
+        if size == 0 {
+            self.versions = HashMap::new();
+            self.last_updates = BTreeSet::new();
+            self.total_versions = 0;
+            return;
+        }
+
+        for versions in self.versions.values_mut() {
+            while versions.len() > size {
+                versions.pop_front();
+                self.total_versions -= 1;
+            }
+        }
+    }
+
+    fn push(&mut self, point_id: PointIdType, point_version: PointVersion) {
+        let versions = self.versions.entry(point_id).or_default();
+
+        if let Some(last) = versions.back() {
+            // Already known version, e.g. when replaying WAL or if operation didn't change the point
+            if last.version >= point_version.version {
+                return;
+            }
+            // Point is already known to be deleted
+            if last.content.is_none() && point_version.content.is_none() {
+                return;
+            }
+            self.last_updates.remove(&(last.version, point_id));
+        } else if point_version.content.is_none() {
+            // Nothing is known about this point
+            self.versions.remove(&point_id);
+            return;
+        }
+
+        self.last_updates.insert((point_version.version, point_id));
+        versions.push_back(point_version);
+        self.total_versions += 1;
+        while versions.len() > self.size {
+            versions.pop_front();
+            self.total_versions -= 1;
+        }
+
+        // Drop history of the least recently updated points
+        while self.total_versions > self.capacity {
+            let Some((_, point_id)) = self.last_updates.pop_first() else {
+                break;
+            };
+            if let Some(versions) = self.versions.remove(&point_id) {
+                self.total_versions -= versions.len();
+            }
+        }
+    }
+
+    /// Get point as it was at the given version
+    ///
+    /// Returns `None` if there is no history for this point, and `Some(None)` if the point did
+    /// not exist at the given version or that version is no longer retained.
+    pub fn record_as_of(
+        &self,
+        point_id: PointIdType,
+        version: SeqNumberType,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> Option<Option<Record>> {
+        let versions = self.versions.get(&point_id)?;
+
+        let record = versions
+            .iter()
+            .rev()
+            .find(|point_version| point_version.version <= version)
+            .and_then(|point_version| {
+                let content = point_version.content.as_ref()?;
+
+                let payload = if with_payload.enable {
+                    match &with_payload.payload_selector {
+                        Some(selector) => Some(selector.process(content.payload.clone())),
+                        None => Some(content.payload.clone()),
+                    }
+                } else {
+                    None
+                };
+
+                let vector = match with_vector {
+                    WithVector::Bool(true) => Some(NamedVectors::from_map(content.vectors.clone())),
+                    WithVector::Bool(false) => None,
+                    WithVector::Selector(vector_names) => {
+                        let mut selected_vectors = NamedVectors::default();
+                        for vector_name in vector_names {
+                            if let Some(vector) = content.vectors.get(vector_name) {
+                                selected_vectors.insert(vector_name.clone(), vector.clone());
+                            }
+                        }
+                        Some(selected_vectors)
+                    }
+                };
+
+                Some(Record {
+                    id: point_id,
+                    payload,
+                    vector: vector.map(VectorStructInternal::from),
+                    shard_key: None,
+                    order_value: None,
+                    version: Some(point_version.version),
+                })
+            });
+
+        Some(record)
+    }
+
+    /// Store current state of the given points in history
+    ///
+    /// Points which are not found are recorded as deleted at `deleted_at` version, if given.
+    pub fn capture(
+        segments: &RwLock<SegmentHolder>,
+        ids: &[PointIdType],
+        deleted_at: Option<SeqNumberType>,
+    ) -> CollectionResult<()> {
+        let mut found: HashMap<PointIdType, PointVersion> = HashMap::new();
+
+        segments.read().read_points(ids, |id, segment| {
+            let version = segment.point_version(id).ok_or_else(|| {
+                OperationError::service_error(format!("No version for point {id}"))
+            })?;
+            // If this point was not found yet or this segment have later version
+            if found.get(&id).map_or(true, |known| known.version < version) {
+                let content = PointContent {
+                    payload: segment.payload(id)?,
+                    vectors: segment.all_vectors(id)?.into_owned_map(),
+                };
+                found.insert(
+                    id,
+                    PointVersion {
+                        version,
+                        content: Some(content),
+                    },
+                );
+            }
+            Ok(true)
+        })?;
+
+        let mut segments = segments.write();
+        let history = &mut segments.point_history;
+
+        for &id in ids {
+            match found.remove(&id) {
+                Some(point_version) => history.push(id, point_version),
+                None => {
+                    if let Some(version) = deleted_at {
+                        history.push(
+                            id,
+                            PointVersion {
+                                version,
+                                content: None,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn content(value: i64) -> Option<PointContent> {
+        Some(PointContent {
+            payload: json!({ "value": value }).into(),
+            vectors: HashMap::new(),
+        })
+    }
+
+    fn value_as_of(history: &PointHistory, version: SeqNumberType) -> Option<Option<i64>> {
+        history
+            .record_as_of(
+                1.into(),
+                version,
+                &WithPayload::from(true),
+                &WithVector::Bool(false),
+            )
+            .map(|record| record.map(|record| record.payload.unwrap().0["value"].as_i64().unwrap()))
+    }
+
+    #[test]
+    fn test_point_history_as_of() {
+        let mut history = PointHistory::default();
+        history.resize(3);
+
+        history.push(
+            1.into(),
+            PointVersion {
+                version: 2,
+                content: content(10),
+            },
+        );
+        history.push(
+            1.into(),
+            PointVersion {
+                version: 5,
+                content: content(20),
+            },
+        );
+        history.push(
+            1.into(),
+            PointVersion {
+                version: 7,
+                content: None,
+            },
+        );
+
+        assert_eq!(value_as_of(&history, 1), Some(None));
+        assert_eq!(value_as_of(&history, 2), Some(Some(10)));
+        assert_eq!(value_as_of(&history, 4), Some(Some(10)));
+        assert_eq!(value_as_of(&history, 6), Some(Some(20)));
+        assert_eq!(value_as_of(&history, 8), Some(None));
+    }
+
+    #[test]
+    fn test_point_history_retains_last_versions() {
+        let mut history = PointHistory::default();
+        history.resize(2);
+
+        for version in 1..=4 {
+            history.push(
+                1.into(),
+                PointVersion {
+                    version,
+                    content: content(version as i64),
+                },
+            );
+        }
+        // Replayed version is ignored
+        history.push(
+            1.into(),
+            PointVersion {
+                version: 3,
+                content: content(0),
+            },
+        );
+
+        assert_eq!(value_as_of(&history, 2), Some(None));
+        assert_eq!(value_as_of(&history, 3), Some(Some(3)));
+        assert_eq!(value_as_of(&history, 4), Some(Some(4)));
+
+        history.resize(0);
+        assert!(!history.is_enabled());
+        assert_eq!(value_as_of(&history, 4), None);
+    }
+
+    #[test]
+    fn test_point_history_drops_least_recently_updated() {
+        let mut history = PointHistory {
+            capacity: 4,
+            ..Default::default()
+        };
+        history.resize(2);
+
+        let push = |history: &mut PointHistory, point_id: u64, version| {
+            history.push(
+                point_id.into(),
+                PointVersion {
+                    version,
+                    content: content(version as i64),
+                },
+            );
+        };
+        push(&mut history, 1, 1);
+        push(&mut history, 2, 2);
+        push(&mut history, 1, 3);
+        push(&mut history, 3, 4);
+        // Point 2 is updated least recently
+        push(&mut history, 3, 5);
+
+        assert_eq!(history.total_versions, 4);
+        assert!(!history.versions.contains_key(&2.into()));
+        assert_eq!(history.versions[&1.into()].len(), 2);
+        assert_eq!(history.versions[&3.into()].len(), 2);
+
+        // Point 1 is dropped next
+        push(&mut history, 2, 6);
+        assert!(!history.versions.contains_key(&1.into()));
+        assert_eq!(history.total_versions, 3);
+        assert_eq!(value_as_of(&history, 6), None);
+    }
+}
@@ -10,6 +10,7 @@ use segment::common::operation_error::OperationError;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::query_context::QueryContext;
 use segment::data_types::vectors::{QueryVector, VectorStructInternal};
+use segment::index::search_stats::SearchStats;
 use segment::types::{
     Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
     WithPayload, WithPayloadInterface, WithVector,
@@ -22,6 +23,7 @@ use super::holders::segment_holder::LockedSegmentHolder;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
+use crate::common::resource_usage;
 use crate::common::stopping_guard::StoppingGuard;
 use crate::config::CollectionConfig;
 use crate::operations::query_enum::QueryEnum;
@@ -253,14 +255,14 @@ impl SegmentsSearcher {
                     let query_context_arc_segment = query_context_acr.clone();
                     let search = runtime_handle.spawn_blocking({
                         let (segment, batch_request) = (segment.clone(), batch_request.clone());
-                        move || {
+                        resource_usage::measured(move || {
                             search_in_segment(
                                 segment,
                                 batch_request,
                                 use_sampling,
                                 query_context_arc_segment,
                             )
-                        }
+                        })
                     });
                     (segment.clone(), search)
                 })
@@ -301,14 +303,16 @@ impl SegmentsSearcher {
                             .map(|batch_id| batch_request.searches[*batch_id].clone())
                             .collect(),
                     });
-                    res.push(runtime_handle.spawn_blocking(move || {
-                        search_in_segment(
-                            segment,
-                            partial_batch_request,
-                            false,
-                            query_context_arc_segment,
-                        )
-                    }))
+                    res.push(
+                        runtime_handle.spawn_blocking(resource_usage::measured(move || {
+                            search_in_segment(
+                                segment,
+                                partial_batch_request,
+                                false,
+                                query_context_arc_segment,
+                            )
+                        })),
+                    )
                 }
                 res
             };
@@ -388,6 +392,7 @@ impl SegmentsSearcher {
                         },
                         shard_key: None,
                         order_value: None,
+                        version: Some(version),
                     },
                 );
                 point_version.insert(id, version);
@@ -577,7 +582,11 @@ fn execute_batch_search(
     };
 
     let vectors_batch = &vectors_batch.iter().collect_vec();
-    let segment_query_context = query_context.get_segment_query_context();
+    // Count the work of this segment on its own, to report the mode it was searched in
+    let segment_stats = query_context.search_stats().map(|_| SearchStats::default());
+    let segment_query_context = query_context
+        .get_segment_query_context()
+        .with_search_stats(segment_stats.as_ref());
     let res = read_segment.search_batch(
         search_params.vector_name,
         vectors_batch,
@@ -589,6 +598,17 @@ fn execute_batch_search(
         segment_query_context,
     )?;
 
+    if let (Some(search_stats), Some(segment_stats)) =
+        (query_context.search_stats(), &segment_stats)
+    {
+        let data_path = read_segment.data_path();
+        let segment_id = data_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        search_stats.add_segment(&segment_id, segment_stats);
+    }
+
     let further_results = res
         .iter()
         .map(|batch_result| batch_result.len() == top)
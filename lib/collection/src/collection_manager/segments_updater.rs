@@ -19,7 +19,7 @@ use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
-use crate::operations::FieldIndexOperations;
+use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
 
 pub(crate) fn check_unprocessed_points(
     points: &[PointIdType],
@@ -580,6 +580,67 @@ pub(crate) fn process_field_index_operation(
     }
 }
 
+/// Ids of the points, which may be changed by the given operation
+pub(crate) fn affected_point_ids(
+    segments: &SegmentHolder,
+    operation: &CollectionUpdateOperations,
+) -> CollectionResult<Vec<PointIdType>> {
+    let point_ids = match operation {
+        CollectionUpdateOperations::PointOperation(point_operation) => match point_operation {
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsBatch(batch)) => {
+                batch.ids.clone()
+            }
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)) => {
+                points.iter().map(|point| point.id).collect()
+            }
+            PointOperations::DeletePoints { ids } => ids.clone(),
+            PointOperations::DeletePointsByFilter(filter) => points_by_filter(segments, filter)?,
+            PointOperations::SyncPoints(operation) => {
+                let stored_point_ids: HashSet<_> = segments
+                    .iter()
+                    .flat_map(|(_, segment)| {
+                        segment
+                            .get()
+                            .read()
+                            .read_range(operation.from_id, operation.to_id)
+                    })
+                    .chain(operation.points.iter().map(|point| point.id))
+                    .collect();
+                stored_point_ids.into_iter().collect()
+            }
+        },
+        CollectionUpdateOperations::VectorOperation(vector_operation) => match vector_operation {
+            VectorOperations::UpdateVectors(operation) => {
+                operation.points.iter().map(|point| point.id).collect()
+            }
+            VectorOperations::DeleteVectors(ids, _) => ids.points.clone(),
+            VectorOperations::DeleteVectorsByFilter(filter, _) => {
+                points_by_filter(segments, filter)?
+            }
+        },
+        CollectionUpdateOperations::PayloadOperation(payload_operation) => {
+            let (points, filter) = match payload_operation {
+                PayloadOps::SetPayload(op) | PayloadOps::OverwritePayload(op) => {
+                    (&op.points, &op.filter)
+                }
+                PayloadOps::DeletePayload(op) => (&op.points, &op.filter),
+                PayloadOps::ClearPayload { points } => return Ok(points.clone()),
+                PayloadOps::ClearPayloadByFilter(filter) => {
+                    return points_by_filter(segments, filter);
+                }
+            };
+            match (points, filter) {
+                (Some(points), _) => points.clone(),
+                (None, Some(filter)) => points_by_filter(segments, filter)?,
+                (None, None) => Vec::new(),
+            }
+        }
+        CollectionUpdateOperations::FieldIndexOperation(_) => Vec::new(),
+    };
+
+    Ok(point_ids)
+}
+
 /// Deletes points from all segments matching the given filter
 pub(crate) fn delete_points_by_filter(
     segments: &SegmentHolder,
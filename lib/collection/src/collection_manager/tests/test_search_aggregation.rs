@@ -12,6 +12,9 @@ fn score_point(id: usize, score: ScoreType, version: SeqNumberType) -> ScoredPoi
         vector: None,
         shard_key: None,
         order_value: None,
+        prefetch_matches: None,
+        routing: None,
+        raw_score: None,
     }
 }
 
@@ -0,0 +1,488 @@
+//! Reader of indexes serialized by `faiss::write_index`.
+//!
+//! All integers are little-endian. Every index starts with a fourcc code of its type, followed by
+//! a common header, see `write_index_header` in `faiss/impl/index_write.cpp`.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use super::{
+    invalid_index, open_reader, read_u32, read_u64, read_vector, remaining_len,
+    ExternalIndexFormat, ExternalPoint,
+};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Metric types above this one are followed by an argument in the header
+const METRIC_WITHOUT_ARGUMENT_MAX: u32 = 1;
+
+const DIRECT_MAP_HASHTABLE: u8 = 2;
+
+/// Vectors stored contiguously in the file
+#[derive(Debug)]
+struct Chunk {
+    vectors_offset: u64,
+    len: usize,
+    ids: ChunkIds,
+}
+
+#[derive(Debug)]
+enum ChunkIds {
+    /// Internal IDs of the vectors are their position, starting from the given one
+    Sequential(u64),
+    /// Internal IDs of the vectors are stored at the given offset
+    Stored(u64),
+}
+
+/// Location of the vectors of an index in its file
+#[derive(Debug)]
+struct IndexLayout {
+    dim: usize,
+    chunks: Vec<Chunk>,
+    /// Mapping of internal IDs to the IDs of the points, if the index is wrapped in `IndexIDMap`
+    id_map: Option<Vec<i64>>,
+}
+
+struct IndexHeader {
+    dim: usize,
+    ntotal: u64,
+}
+
+pub struct FaissPoints {
+    dim: usize,
+    chunks: std::vec::IntoIter<Chunk>,
+    id_map: Option<Vec<i64>>,
+    vectors: BufReader<File>,
+    ids: BufReader<File>,
+    /// Number of vectors left to read in the current chunk
+    remaining: usize,
+    /// Next internal ID, if the IDs of the current chunk are sequential
+    next_sequential_id: Option<u64>,
+}
+
+impl FaissPoints {
+    pub fn open(path: &Path) -> CollectionResult<Self> {
+        let mut reader = open_reader(path)?;
+        let IndexLayout {
+            dim,
+            chunks,
+            id_map,
+        } = read_index(&mut reader)?;
+
+        Ok(Self {
+            dim,
+            chunks: chunks.into_iter(),
+            id_map,
+            vectors: reader,
+            ids: open_reader(path)?,
+            remaining: 0,
+            next_sequential_id: None,
+        })
+    }
+
+    fn next_chunk(&mut self) -> CollectionResult<bool> {
+        let Some(chunk) = self.chunks.next() else {
+            return Ok(false);
+        };
+
+        self.vectors.seek(SeekFrom::Start(chunk.vectors_offset))?;
+        self.remaining = chunk.len;
+        self.next_sequential_id = match chunk.ids {
+            ChunkIds::Sequential(first_id) => Some(first_id),
+            ChunkIds::Stored(offset) => {
+                self.ids.seek(SeekFrom::Start(offset))?;
+                None
+            }
+        };
+
+        Ok(true)
+    }
+
+    fn read_point(&mut self) -> CollectionResult<ExternalPoint> {
+        // Chunks were checked to be within the file, so the vector is too
+        let vector = read_vector(&mut self.vectors, self.dim)?;
+
+        let internal_id = match &mut self.next_sequential_id {
+            Some(next_id) => {
+                let id = *next_id;
+                *next_id += 1;
+                id
+            }
+            None => read_u64(&mut self.ids)?,
+        };
+
+        let id = match &self.id_map {
+            Some(id_map) => *id_map.get(internal_id as usize).ok_or_else(|| {
+                invalid(format!("internal ID {internal_id} is not in the ID map"))
+            })?,
+            None => internal_id as i64,
+        };
+        let id = u64::try_from(id).map_err(|_| invalid(format!("negative ID {id}")))?;
+
+        Ok(ExternalPoint { id, vector })
+    }
+}
+
+impl Iterator for FaissPoints {
+    type Item = CollectionResult<ExternalPoint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining == 0 {
+            match self.next_chunk() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        self.remaining -= 1;
+
+        Some(self.read_point())
+    }
+}
+
+fn invalid(description: impl AsRef<str>) -> CollectionError {
+    invalid_index(ExternalIndexFormat::Faiss, description)
+}
+
+fn read_index(reader: &mut BufReader<File>) -> CollectionResult<IndexLayout> {
+    let mut fourcc = [0; 4];
+    reader.read_exact(&mut fourcc)?;
+
+    match &fourcc {
+        // Flat indexes with L2, inner product or other metric
+        b"IxF2" | b"IxFI" | b"IxFl" => {
+            let IndexHeader { dim, ntotal } = read_header(reader)?;
+
+            let floats_count = read_u64(reader)?;
+            let expected_count = checked_size(ntotal, dim as u64, "vectors")?;
+            if floats_count != expected_count {
+                return Err(invalid(format!(
+                    "expected {expected_count} floats for {ntotal} vectors of dimension {dim}, got {floats_count}",
+                )));
+            }
+            let vectors_offset = reader.stream_position()?;
+            skip(reader, checked_size(floats_count, 4, "floats")?)?;
+
+            Ok(IndexLayout {
+                dim,
+                chunks: vec![Chunk {
+                    vectors_offset,
+                    len: as_usize(ntotal)?,
+                    ids: ChunkIds::Sequential(0),
+                }],
+                id_map: None,
+            })
+        }
+        // Any index wrapped with custom IDs
+        b"IxMp" | b"IxM2" => {
+            let IndexHeader { ntotal, .. } = read_header(reader)?;
+
+            let mut layout = read_index(reader)?;
+            if layout.id_map.is_some() {
+                return Err(invalid("nested ID maps are not supported"));
+            }
+
+            let ids_count = read_u64(reader)?;
+            if ids_count != ntotal {
+                return Err(invalid(format!(
+                    "expected {ntotal} IDs in the ID map, got {ids_count}",
+                )));
+            }
+            check_remaining(reader, checked_size(ids_count, 8, "IDs")?)?;
+            let id_map = (0..ids_count)
+                .map(|_| read_u64(reader).map(|id| id as i64))
+                .collect::<std::io::Result<_>>()?;
+            layout.id_map = Some(id_map);
+
+            Ok(layout)
+        }
+        // IVF flat index
+        b"IwFl" => {
+            let IndexHeader { dim, .. } = read_header(reader)?;
+
+            let nlist = read_u64(reader)?;
+            let _nprobe = read_u64(reader)?;
+            // Centroids of the lists are not imported
+            let _quantizer = read_index(reader)?;
+            skip_direct_map(reader)?;
+
+            let chunks = read_inverted_lists(reader, dim, nlist)?;
+
+            Ok(IndexLayout {
+                dim,
+                chunks,
+                id_map: None,
+            })
+        }
+        _ => Err(invalid(format!(
+            "unsupported index type {:?}, only flat and IVF flat indexes can be imported",
+            String::from_utf8_lossy(&fourcc),
+        ))),
+    }
+}
+
+fn read_header(reader: &mut BufReader<File>) -> CollectionResult<IndexHeader> {
+    let dim = read_u32(reader)? as usize;
+    let ntotal = read_u64(reader)?;
+    // Two unused values, then `is_trained`
+    skip(reader, 8 + 8 + 1)?;
+
+    let metric_type = read_u32(reader)?;
+    if metric_type > METRIC_WITHOUT_ARGUMENT_MAX {
+        // Metric argument
+        skip(reader, 4)?;
+    }
+
+    if dim == 0 {
+        return Err(invalid("dimension of vectors is 0"));
+    }
+
+    Ok(IndexHeader { dim, ntotal })
+}
+
+fn skip_direct_map(reader: &mut BufReader<File>) -> CollectionResult<()> {
+    let mut map_type = [0; 1];
+    reader.read_exact(&mut map_type)?;
+
+    let array_len = read_u64(reader)?;
+    skip(reader, checked_size(array_len, 8, "direct map entries")?)?;
+
+    if map_type[0] == DIRECT_MAP_HASHTABLE {
+        let pairs_len = read_u64(reader)?;
+        skip(reader, checked_size(pairs_len, 16, "direct map pairs")?)?;
+    }
+
+    Ok(())
+}
+
+fn read_inverted_lists(
+    reader: &mut BufReader<File>,
+    dim: usize,
+    nlist: u64,
+) -> CollectionResult<Vec<Chunk>> {
+    let mut fourcc = [0; 4];
+    reader.read_exact(&mut fourcc)?;
+
+    match &fourcc {
+        b"il00" => return Ok(Vec::new()),
+        b"ilar" => {}
+        _ => {
+            return Err(invalid(format!(
+                "unsupported inverted lists {:?}",
+                String::from_utf8_lossy(&fourcc),
+            )))
+        }
+    }
+
+    let lists_count = read_u64(reader)?;
+    let code_size = read_u64(reader)?;
+    if lists_count != nlist || code_size != dim as u64 * 4 {
+        return Err(invalid(format!(
+            "expected {nlist} lists of vectors of dimension {dim}, got {lists_count} lists with codes of {code_size} bytes",
+        )));
+    }
+
+    let mut list_type = [0; 4];
+    reader.read_exact(&mut list_type)?;
+    let sizes_len = read_u64(reader)?;
+    check_remaining(reader, checked_size(sizes_len, 8, "list sizes")?)?;
+    let sizes = (0..sizes_len)
+        .map(|_| read_u64(reader))
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    // Lengths of non-empty lists, in the order of the lists
+    let lengths: Vec<_> = match &list_type {
+        b"full" => sizes.into_iter().filter(|&len| len > 0).collect(),
+        // Pairs of list number and length
+        b"sprs" => sizes.chunks_exact(2).map(|pair| pair[1]).collect(),
+        _ => {
+            return Err(invalid(format!(
+                "unsupported inverted lists type {:?}",
+                String::from_utf8_lossy(&list_type),
+            )))
+        }
+    };
+
+    // Each list is stored as its codes, followed by its IDs
+    lengths
+        .into_iter()
+        .map(|len| {
+            let vectors_offset = reader.stream_position()?;
+            skip(reader, checked_size(len, code_size, "vectors")?)?;
+            let ids_offset = reader.stream_position()?;
+            skip(reader, checked_size(len, 8, "IDs")?)?;
+
+            Ok(Chunk {
+                vectors_offset,
+                len: as_usize(len)?,
+                ids: ChunkIds::Stored(ids_offset),
+            })
+        })
+        .collect()
+}
+
+fn checked_size(count: u64, size: u64, what: &str) -> CollectionResult<u64> {
+    super::checked_size(ExternalIndexFormat::Faiss, count, size, what)
+}
+
+fn as_usize(value: u64) -> CollectionResult<usize> {
+    usize::try_from(value).map_err(|_| invalid(format!("{value} is too large")))
+}
+
+/// Check that the given number of bytes can be read, before allocating or skipping them
+fn check_remaining(reader: &mut BufReader<File>, bytes: u64) -> CollectionResult<()> {
+    let remaining = remaining_len(reader)?;
+    if bytes > remaining {
+        return Err(invalid(format!(
+            "expected {bytes} more bytes, but only {remaining} are left in the file",
+        )));
+    }
+    Ok(())
+}
+
+fn skip(reader: &mut BufReader<File>, bytes: u64) -> CollectionResult<()> {
+    check_remaining(reader, bytes)?;
+    // Fits into `i64`, as it is below the length of the file
+    reader.seek_relative(bytes as i64)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    fn write_header(file: &mut impl Write, fourcc: &[u8; 4], dim: u32, ntotal: u64) {
+        file.write_all(fourcc).unwrap();
+        file.write_all(&dim.to_le_bytes()).unwrap();
+        file.write_all(&ntotal.to_le_bytes()).unwrap();
+        file.write_all(&(1u64 << 20).to_le_bytes()).unwrap();
+        file.write_all(&(1u64 << 20).to_le_bytes()).unwrap();
+        file.write_all(&[1]).unwrap(); // is_trained
+        file.write_all(&1u32.to_le_bytes()).unwrap(); // METRIC_L2
+    }
+
+    fn write_flat(file: &mut impl Write, vectors: &[[f32; 2]]) {
+        write_header(file, b"IxF2", 2, vectors.len() as u64);
+        file.write_all(&(vectors.len() as u64 * 2).to_le_bytes())
+            .unwrap();
+        for value in vectors.iter().flatten() {
+            file.write_all(&value.to_le_bytes()).unwrap();
+        }
+    }
+
+    fn read_points(file: &NamedTempFile) -> Vec<ExternalPoint> {
+        FaissPoints::open(file.path())
+            .unwrap()
+            .collect::<CollectionResult<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_read_flat_with_id_map() {
+        let mut file = NamedTempFile::new().unwrap();
+        write_header(&mut file, b"IxMp", 2, 2);
+        write_flat(&mut file, &[[1.0, 2.0], [3.0, 4.0]]);
+        file.write_all(&2u64.to_le_bytes()).unwrap();
+        for id in [42u64, 7] {
+            file.write_all(&id.to_le_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        assert_eq!(
+            read_points(&file),
+            vec![
+                ExternalPoint {
+                    id: 42,
+                    vector: vec![1.0, 2.0],
+                },
+                ExternalPoint {
+                    id: 7,
+                    vector: vec![3.0, 4.0],
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_read_ivf_flat() {
+        let mut file = NamedTempFile::new().unwrap();
+        write_header(&mut file, b"IwFl", 2, 3);
+        file.write_all(&3u64.to_le_bytes()).unwrap(); // nlist
+        file.write_all(&1u64.to_le_bytes()).unwrap(); // nprobe
+        write_flat(&mut file, &[[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]]); // quantizer
+        file.write_all(&[0]).unwrap(); // no direct map
+        file.write_all(&0u64.to_le_bytes()).unwrap();
+
+        file.write_all(b"ilar").unwrap();
+        file.write_all(&3u64.to_le_bytes()).unwrap(); // nlist
+        file.write_all(&8u64.to_le_bytes()).unwrap(); // code_size
+        file.write_all(b"sprs").unwrap();
+        file.write_all(&4u64.to_le_bytes()).unwrap();
+        for size in [0u64, 2, 2, 1] {
+            file.write_all(&size.to_le_bytes()).unwrap();
+        }
+        for (vectors, ids) in [
+            (vec![[1.0f32, 2.0], [3.0, 4.0]], vec![0u64, 2]),
+            (vec![[5.0, 6.0]], vec![1u64]),
+        ] {
+            for value in vectors.iter().flatten() {
+                file.write_all(&value.to_le_bytes()).unwrap();
+            }
+            for id in ids {
+                file.write_all(&id.to_le_bytes()).unwrap();
+            }
+        }
+        file.flush().unwrap();
+
+        let points = read_points(&file);
+        let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        assert_eq!(ids, vec![0, 2, 1]);
+        assert_eq!(points[2].vector, vec![5.0, 6.0]);
+    }
+
+    #[test]
+    fn test_reject_sizes_beyond_file() {
+        // Flat index claiming more vectors than the file holds
+        let mut file = NamedTempFile::new().unwrap();
+        write_header(&mut file, b"IxF2", 2, 1 << 40);
+        file.write_all(&(2u64 << 40).to_le_bytes()).unwrap();
+        file.write_all(&[0; 16]).unwrap();
+        file.flush().unwrap();
+        assert!(FaissPoints::open(file.path()).is_err());
+
+        // Number of floats overflows
+        let mut file = NamedTempFile::new().unwrap();
+        write_header(&mut file, b"IxF2", 4, u64::MAX / 2);
+        file.write_all(&u64::MAX.to_le_bytes()).unwrap();
+        file.flush().unwrap();
+        assert!(FaissPoints::open(file.path()).is_err());
+
+        // Dimension too large for a single vector in the file
+        let mut file = NamedTempFile::new().unwrap();
+        write_header(&mut file, b"IxF2", u32::MAX, 1);
+        file.write_all(&u64::from(u32::MAX).to_le_bytes()).unwrap();
+        file.flush().unwrap();
+        assert!(FaissPoints::open(file.path()).is_err());
+
+        // ID map longer than the file
+        let mut file = NamedTempFile::new().unwrap();
+        write_header(&mut file, b"IxMp", 2, u64::MAX);
+        write_flat(&mut file, &[]);
+        file.write_all(&u64::MAX.to_le_bytes()).unwrap();
+        file.flush().unwrap();
+        assert!(FaissPoints::open(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_index() {
+        let mut file = NamedTempFile::new().unwrap();
+        write_header(&mut file, b"IHNf", 2, 0);
+        file.flush().unwrap();
+
+        assert!(FaissPoints::open(file.path()).is_err());
+    }
+}
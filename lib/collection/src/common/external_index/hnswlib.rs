@@ -0,0 +1,218 @@
+//! Reader of indexes serialized by `hnswlib::HierarchicalNSW::saveIndex`.
+//!
+//! The file starts with a header of the index parameters, followed by the level 0 of the graph.
+//! Each element of level 0 holds its links, its vector and its label. Upper levels of the graph
+//! follow, and are not read.
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use super::{
+    checked_size, invalid_index, open_reader, read_u64, remaining_len, ExternalIndexFormat,
+    ExternalPoint,
+};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Offset of the deletion flag in the header of the links of an element
+const DELETED_FLAG_OFFSET: usize = 2;
+const DELETED_MARK: u8 = 0x01;
+
+pub struct HnswlibPoints {
+    reader: BufReader<File>,
+    /// Number of elements left to read
+    remaining: usize,
+    element: Vec<u8>,
+    /// Offset of the header of the links in an element
+    links_offset: usize,
+    /// Offset of the vector in an element
+    data_offset: usize,
+    /// Offset of the label in an element
+    label_offset: usize,
+}
+
+impl HnswlibPoints {
+    pub fn open(path: &Path) -> CollectionResult<Self> {
+        let mut reader = open_reader(path)?;
+
+        let links_offset = read_u64(&mut reader)?;
+        let _max_elements = read_u64(&mut reader)?;
+        let element_count = read_u64(&mut reader)?;
+        let element_size = read_u64(&mut reader)?;
+        let label_offset = read_u64(&mut reader)?;
+        let data_offset = read_u64(&mut reader)?;
+        // `maxlevel_`, `enterpoint_node_`, `maxM_`, `maxM0_`, `M_`, `mult_` and `ef_construction_`
+        // only describe the graph
+        let mut graph_params = [0; 4 + 4 + 8 * 5];
+        reader.read_exact(&mut graph_params)?;
+
+        // Only vectors of floats are supported, so the vector is followed by the label
+        let data_size = label_offset.checked_sub(data_offset).unwrap_or_default();
+        if data_size == 0 || data_size % 4 != 0 {
+            return Err(invalid(format!(
+                "vector at offset {data_offset} with label at offset {label_offset} is not a vector of floats",
+            )));
+        }
+        if label_offset.saturating_add(8) > element_size
+            || links_offset.saturating_add(4) > element_size
+        {
+            return Err(invalid(format!(
+                "element of {element_size} bytes is too small for its links, vector and label",
+            )));
+        }
+
+        // Level 0 of the graph holds all elements, so it must fit in the file
+        let level_0_size = checked_size(
+            ExternalIndexFormat::Hnswlib,
+            element_count,
+            element_size,
+            "elements",
+        )?;
+        let remaining_len = remaining_len(&mut reader)?;
+        if level_0_size > remaining_len {
+            return Err(invalid(format!(
+                "{element_count} elements of {element_size} bytes don't fit in the remaining {remaining_len} bytes of the file",
+            )));
+        }
+
+        // All values are below the length of the file now
+        let as_usize = |value: u64| usize::try_from(value).map_err(|_| invalid("too large index"));
+
+        Ok(Self {
+            reader,
+            remaining: as_usize(element_count)?,
+            element: vec![0; as_usize(element_size)?],
+            links_offset: as_usize(links_offset)?,
+            data_offset: as_usize(data_offset)?,
+            label_offset: as_usize(label_offset)?,
+        })
+    }
+
+    /// Read the next element, returns `None` if it is deleted
+    fn read_element(&mut self) -> CollectionResult<Option<ExternalPoint>> {
+        self.reader.read_exact(&mut self.element)?;
+
+        if self.element[self.links_offset + DELETED_FLAG_OFFSET] & DELETED_MARK != 0 {
+            return Ok(None);
+        }
+
+        let vector = self.element[self.data_offset..self.label_offset]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        let label = &self.element[self.label_offset..self.label_offset + 8];
+        let id = u64::from_le_bytes(label.try_into().unwrap());
+
+        Ok(Some(ExternalPoint { id, vector }))
+    }
+}
+
+impl Iterator for HnswlibPoints {
+    type Item = CollectionResult<ExternalPoint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            self.remaining -= 1;
+
+            match self.read_element() {
+                Ok(Some(point)) => return Some(Ok(point)),
+                Ok(None) => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
+
+        None
+    }
+}
+
+fn invalid(description: impl AsRef<str>) -> CollectionError {
+    invalid_index(ExternalIndexFormat::Hnswlib, description)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    #[test]
+    fn test_read_skips_deleted_elements() {
+        let dim = 3;
+        let max_links = 2;
+        let data_offset = 4 + max_links * 4;
+        let label_offset = data_offset + dim * 4;
+        let element_size = label_offset + 8;
+
+        let mut file = NamedTempFile::new().unwrap();
+        for value in [
+            0,            // offsetLevel0_
+            3,            // max_elements_
+            3,            // cur_element_count
+            element_size, // size_data_per_element_
+            label_offset, // label_offset_
+            data_offset,  // offsetData_
+        ] {
+            file.write_all(&(value as u64).to_le_bytes()).unwrap();
+        }
+        file.write_all(&[0; 4 + 4 + 8 * 5]).unwrap();
+
+        for (label, deleted) in [(10u64, false), (11, true), (12, false)] {
+            let mut links = [0u8; 4 + 2 * 4];
+            links[DELETED_FLAG_OFFSET] = u8::from(deleted);
+            file.write_all(&links).unwrap();
+            for i in 0..dim {
+                file.write_all(&(label as f32 + i as f32).to_le_bytes())
+                    .unwrap();
+            }
+            file.write_all(&label.to_le_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        let points: Vec<_> = HnswlibPoints::open(file.path())
+            .unwrap()
+            .collect::<CollectionResult<_>>()
+            .unwrap();
+
+        assert_eq!(
+            points,
+            vec![
+                ExternalPoint {
+                    id: 10,
+                    vector: vec![10.0, 11.0, 12.0],
+                },
+                ExternalPoint {
+                    id: 12,
+                    vector: vec![12.0, 13.0, 14.0],
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_reject_sizes_beyond_file() {
+        let header = |element_count: u64, element_size: u64| {
+            let mut file = NamedTempFile::new().unwrap();
+            for value in [0, element_count, element_count, element_size, 12, 4] {
+                file.write_all(&u64::to_le_bytes(value)).unwrap();
+            }
+            file.write_all(&[0; 4 + 4 + 8 * 5]).unwrap();
+            file.write_all(&[0; 20]).unwrap();
+            file.flush().unwrap();
+            file
+        };
+
+        // Would allocate the element, or overflow the size of the level 0
+        for (element_count, element_size) in [(1, u64::MAX), (u64::MAX, 20), (2, 20)] {
+            let file = header(element_count, element_size);
+            assert!(
+                HnswlibPoints::open(file.path()).is_err(),
+                "{element_count} x {element_size}",
+            );
+        }
+
+        let file = header(1, 20);
+        assert_eq!(HnswlibPoints::open(file.path()).unwrap().count(), 1);
+    }
+}
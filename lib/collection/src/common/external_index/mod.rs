@@ -0,0 +1,106 @@
+//! Reading of vectors from indexes of other vector search libraries, to migrate them into a
+//! collection.
+//!
+//! Only the vectors and IDs of the indexes are imported. Graphs of HNSW indexes are built with
+//! different parameters and point offsets than the ones of Qdrant, so they are not reused: the
+//! collection indexes the imported points according to its own configuration.
+
+mod faiss;
+mod hnswlib;
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+
+use schemars::JsonSchema;
+use segment::data_types::vectors::DenseVector;
+use serde::{Deserialize, Serialize};
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Format of an index to import points from
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalIndexFormat {
+    /// Index written by `faiss.write_index`. Flat and IVF flat indexes are supported, optionally
+    /// wrapped into an `IndexIDMap`.
+    Faiss,
+    /// Index written by `hnswlib.Index.save_index`, with float vectors
+    Hnswlib,
+}
+
+/// A vector read from an external index, with its ID in that index
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalPoint {
+    pub id: u64,
+    pub vector: DenseVector,
+}
+
+/// Iterator over the points of an external index file
+pub type ExternalPointsIter = Box<dyn Iterator<Item = CollectionResult<ExternalPoint>> + Send>;
+
+/// Opens an index file of the given format, and reads its points one by one
+pub fn read_external_index(
+    path: &Path,
+    format: ExternalIndexFormat,
+) -> CollectionResult<ExternalPointsIter> {
+    match format {
+        ExternalIndexFormat::Faiss => Ok(Box::new(faiss::FaissPoints::open(path)?)),
+        ExternalIndexFormat::Hnswlib => Ok(Box::new(hnswlib::HnswlibPoints::open(path)?)),
+    }
+}
+
+fn open_reader(path: &Path) -> CollectionResult<BufReader<File>> {
+    Ok(BufReader::new(File::open(path)?))
+}
+
+/// Number of bytes left to read in the file.
+/// Sizes from the headers of the index are checked against it, before anything is allocated.
+fn remaining_len(reader: &mut BufReader<File>) -> std::io::Result<u64> {
+    let len = reader.get_ref().metadata()?.len();
+    Ok(len.saturating_sub(reader.stream_position()?))
+}
+
+/// Multiply sizes read from a header, failing on overflow
+fn checked_size(
+    format: ExternalIndexFormat,
+    count: u64,
+    size: u64,
+    what: &str,
+) -> CollectionResult<u64> {
+    count.checked_mul(size).ok_or_else(|| {
+        invalid_index(
+            format,
+            format!("size of {count} {what} of {size} bytes overflows"),
+        )
+    })
+}
+
+fn invalid_index(format: ExternalIndexFormat, description: impl AsRef<str>) -> CollectionError {
+    CollectionError::bad_input(format!(
+        "Invalid {format:?} index: {}",
+        description.as_ref()
+    ))
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Read a vector of floats. The dimension must be checked against the length of the file before.
+fn read_vector(reader: &mut impl Read, dim: usize) -> std::io::Result<DenseVector> {
+    let mut bytes = vec![0; dim * 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect())
+}
@@ -4,12 +4,12 @@ use std::fmt::Debug;
 use api::rest::ShardKeySelector;
 use futures::future::try_join_all;
 use futures::Future;
+use itertools::Itertools as _;
 use segment::data_types::vectors::{Vector, VectorRef};
 use segment::types::{PointIdType, WithPayloadInterface, WithVector};
 use tokio::sync::RwLockReadGuard;
 
 use crate::collection::Collection;
-use crate::common::batching::batch_requests;
 use crate::common::retrieve_request_trait::RetrieveRequest;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
@@ -18,8 +18,9 @@ use crate::operations::types::{
 };
 use crate::operations::universal_query::collection_query;
 use crate::operations::universal_query::collection_query::{
-    CollectionQueryRequest, CollectionQueryResolveRequest, VectorInput,
+    CollectionQueryRequest, CollectionQueryResolveRequest, VectorInput, WeightedVectorInput,
 };
+use crate::recommendations::weighted_sum_vectors;
 
 pub async fn retrieve_points(
     collection: &Collection,
@@ -34,6 +35,7 @@ pub async fn retrieve_points(
                 ids,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: WithVector::Selector(vector_names),
+                as_of_version: None,
             },
             read_consistency,
             shard_selector,
@@ -143,6 +145,33 @@ impl ReferencedVectors {
         }
     }
 
+    /// Check that all the given points were fetched from the lookup collection.
+    ///
+    /// Reports all the missing points at once, with the collection they were looked up in.
+    pub fn check_fetched(
+        &self,
+        lookup_collection_name: Option<&CollectionName>,
+        point_ids: impl IntoIterator<Item = PointIdType>,
+        collection_name: &str,
+    ) -> CollectionResult<()> {
+        let missed_point_ids: Vec<_> = point_ids
+            .into_iter()
+            .filter(|point_id| self.get(&lookup_collection_name, *point_id).is_none())
+            .unique()
+            .collect();
+
+        if missed_point_ids.is_empty() {
+            return Ok(());
+        }
+
+        Err(CollectionError::ReferencedPointsNotFound {
+            missed_point_ids,
+            lookup_collection: lookup_collection_name
+                .map_or(collection_name, String::as_str)
+                .to_string(),
+        })
+    }
+
     /// Convert potential reference to a vector (vector id) into actual vector,
     /// which was resolved by the request to the storage.
     pub fn resolve_reference<'a>(
@@ -157,8 +186,48 @@ impl ReferencedVectors {
                 let rec = self.get(&collection_name, vid)?;
                 rec.get_vector_by_name(vector_name).map(|v| v.to_owned())
             }
+            VectorInput::Sum(terms) => self.resolve_sum(collection_name, vector_name, terms).ok(),
         }
     }
+
+    /// Compute a weighted sum of vectors, resolving the referenced ones.
+    ///
+    /// Unlike single references, all the vectors of a sum must be resolved.
+    pub fn resolve_sum(
+        &self,
+        collection_name: Option<&String>,
+        vector_name: &str,
+        terms: Vec<WeightedVectorInput>,
+    ) -> CollectionResult<Vector> {
+        let weighted_vectors: Vec<_> = terms
+            .into_iter()
+            .map(|term| {
+                let vector = match term.vector {
+                    VectorInput::Vector(vector) => vector,
+                    VectorInput::Id(vid) => {
+                        let rec = self.get(&collection_name, vid).ok_or(
+                            CollectionError::PointNotFound {
+                                missed_point_id: vid,
+                            },
+                        )?;
+                        rec.get_vector_by_name(vector_name)
+                            .ok_or_else(|| {
+                                CollectionError::bad_input(format!(
+                                    "Point {vid} has no vector named `{vector_name}`"
+                                ))
+                            })?
+                            .to_owned()
+                    }
+                    VectorInput::Sum(terms) => {
+                        self.resolve_sum(collection_name, vector_name, terms)?
+                    }
+                };
+                Ok((vector, term.weight))
+            })
+            .collect::<CollectionResult<_>>()?;
+
+        weighted_sum_vectors(weighted_vectors)
+    }
 }
 
 #[derive(Default, Debug)]
@@ -302,6 +371,41 @@ pub fn convert_to_vectors<'a>(
     })
 }
 
+/// Points referenced by a batch of requests, grouped by the shard key to look them up in
+fn referenced_points_per_shard_key<Req: RetrieveRequest>(
+    requests: &[(Req, ShardSelectorInternal)],
+) -> Vec<(Option<ShardKeySelector>, ReferencedPoints)> {
+    // Shard key selectors are not hashable, but there are only a few distinct ones in a batch
+    let mut referenced_points_per_shard_key: Vec<(Option<ShardKeySelector>, ReferencedPoints)> =
+        Vec::new();
+
+    for (request, _) in requests {
+        let shard_key = request.get_lookup_shard_key();
+
+        let referenced_points = match referenced_points_per_shard_key
+            .iter()
+            .position(|(key, _)| key == shard_key)
+        {
+            Some(idx) => &mut referenced_points_per_shard_key[idx].1,
+            None => {
+                referenced_points_per_shard_key.push((shard_key.clone(), ReferencedPoints::new()));
+                &mut referenced_points_per_shard_key.last_mut().unwrap().1
+            }
+        };
+
+        let collection_name = request.get_lookup_collection();
+        let vector_name = request.get_lookup_vector_name();
+        let point_ids_iter = request.get_referenced_point_ids();
+        referenced_points.add_from_iter(point_ids_iter.into_iter(), vector_name, collection_name);
+    }
+
+    referenced_points_per_shard_key
+}
+
+/// Resolves vectors referenced by ids in a batch of requests.
+///
+/// References are deduplicated across the whole batch, so each point is fetched only once
+/// per lookup shard key, no matter how many requests mention it.
 pub async fn resolve_referenced_vectors_batch<'a, 'b, F, Fut, Req: RetrieveRequest>(
     requests: &'b [(Req, ShardSelectorInternal)],
     collection: &Collection,
@@ -312,44 +416,23 @@ where
     F: Fn(String) -> Fut,
     Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
 {
-    let fetch_requests = batch_requests::<
-        &(Req, ShardSelectorInternal),
-        Option<ShardKeySelector>,
-        ReferencedPoints,
-        Vec<_>,
-    >(
-        requests,
-        |(request, _)| request.get_lookup_shard_key(),
-        |(request, _), referenced_points| {
-            let collection_name = request.get_lookup_collection();
-            let vector_name = request.get_lookup_vector_name();
-            let point_ids_iter = request.get_referenced_point_ids();
-            referenced_points.add_from_iter(
-                point_ids_iter.into_iter(),
-                vector_name,
-                collection_name,
-            );
-            Ok(())
-        },
-        |shard_selector, referenced_points, requests| {
-            let shard_selector = match shard_selector {
+    let fetch_requests: Vec<_> = referenced_points_per_shard_key(requests)
+        .into_iter()
+        .filter(|(_, referenced_points)| !referenced_points.is_empty())
+        .map(|(shard_key, referenced_points)| {
+            let shard_selector = match shard_key {
                 None => ShardSelectorInternal::All,
                 Some(shard_key_selector) => ShardSelectorInternal::from(shard_key_selector),
             };
 
-            if referenced_points.is_empty() {
-                return Ok(());
-            }
-            let fetch = referenced_points.fetch_vectors(
+            referenced_points.fetch_vectors(
                 collection,
                 read_consistency,
                 &collection_by_name,
                 shard_selector,
-            );
-            requests.push(fetch);
-            Ok(())
-        },
-    )?;
+            )
+        })
+        .collect();
 
     let batch_reference_vectors: Vec<_> = try_join_all(fetch_requests).await?;
 
@@ -393,3 +476,66 @@ pub fn build_vector_resolver_queries(
     }
     resolve_prefetches
 }
+
+#[cfg(test)]
+mod tests {
+    use api::rest::LookupLocation;
+
+    use super::*;
+    use crate::operations::types::RecommendRequestInternal;
+
+    fn recommend(
+        positive: &[u64],
+        negative: &[u64],
+        lookup_from: Option<LookupLocation>,
+    ) -> (RecommendRequestInternal, ShardSelectorInternal) {
+        let examples = |ids: &[u64]| {
+            ids.iter()
+                .map(|&id| RecommendExample::PointId(id.into()))
+                .collect()
+        };
+        let request = RecommendRequestInternal {
+            positive: examples(positive),
+            negative: examples(negative),
+            lookup_from,
+            limit: 10,
+            ..Default::default()
+        };
+        (request, ShardSelectorInternal::All)
+    }
+
+    #[test]
+    fn test_referenced_points_are_deduplicated_across_batch() {
+        let shard_key = ShardKeySelector::ShardKey("a".into());
+        let requests = vec![
+            recommend(&[1, 2], &[3], None),
+            recommend(&[2, 1], &[1], None),
+            recommend(&[3, 1], &[], None),
+            recommend(
+                &[1],
+                &[],
+                Some(LookupLocation {
+                    collection: "other".to_string(),
+                    vector: None,
+                    shard_key: Some(shard_key.clone()),
+                }),
+            ),
+        ];
+
+        let referenced_points = referenced_points_per_shard_key(&requests);
+        assert_eq!(referenced_points.len(), 2);
+
+        // Each point referenced by the requests without shard key is fetched once
+        let (key, points) = &referenced_points[0];
+        assert_eq!(key, &None);
+        assert_eq!(points.ids_per_collection.len(), 1);
+        let mut ids: Vec<_> = points.ids_per_collection[&None].iter().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1.into(), 2.into(), 3.into()]);
+
+        let (key, points) = &referenced_points[1];
+        assert_eq!(key, &Some(shard_key));
+        let other = "other".to_string();
+        assert_eq!(points.ids_per_collection[&Some(&other)].len(), 1);
+    }
+}
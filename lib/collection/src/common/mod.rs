@@ -1,14 +1,22 @@
 pub mod batching;
 pub mod eta_calculator;
+pub mod external_index;
 pub mod fetch_vectors;
 pub mod file_utils;
 pub mod is_ready;
+pub mod projection;
+pub mod query_memory;
+pub mod query_usage;
+pub mod resource_usage;
 pub mod retrieve_request_trait;
+pub mod search_stats;
 pub mod sha_256;
 pub mod snapshot_stream;
 pub mod snapshots_manager;
+pub mod spilled_points;
 pub mod stoppable_task;
 pub mod stoppable_task_async;
 pub mod stopping_guard;
+pub mod text_budget;
 pub mod transpose_iterator;
 pub mod validate_snapshot_archive;
@@ -0,0 +1,138 @@
+//! Projection of vectors to 2D coordinates, to plot a sample of points.
+
+/// Iterations to converge to each principal component
+const POWER_ITERATIONS: usize = 100;
+
+/// Projects vectors on their 2 principal components (PCA).
+///
+/// Components are computed by power iteration on the centered vectors, so the projection of the
+/// same vectors is always the same. All vectors are expected to have the same dimension.
+pub fn pca_2d(vectors: &[&[f32]]) -> Vec<[f32; 2]> {
+    let Some(dim) = vectors.first().map(|vector| vector.len()) else {
+        return vec![];
+    };
+
+    let mut mean = vec![0.0; dim];
+    for vector in vectors {
+        for (mean, value) in mean.iter_mut().zip(vector.iter()) {
+            *mean += f64::from(*value);
+        }
+    }
+    for mean in &mut mean {
+        *mean /= vectors.len() as f64;
+    }
+
+    let centered: Vec<Vec<f64>> = vectors
+        .iter()
+        .map(|vector| {
+            vector
+                .iter()
+                .zip(&mean)
+                .map(|(value, mean)| f64::from(*value) - mean)
+                .collect()
+        })
+        .collect();
+
+    let first = principal_component(&centered, dim, None);
+    let second = principal_component(&centered, dim, Some(&first));
+
+    centered
+        .iter()
+        .map(|vector| [dot(vector, &first) as f32, dot(vector, &second) as f32])
+        .collect()
+}
+
+/// Direction of the largest variance of the centered vectors, orthogonal to `orthogonal_to`.
+///
+/// Returns a zero vector if there is no variance left.
+fn principal_component(
+    centered: &[Vec<f64>],
+    dim: usize,
+    orthogonal_to: Option<&[f64]>,
+) -> Vec<f64> {
+    // Any start is fine, as long as it is not orthogonal to the component
+    let mut component: Vec<f64> = (0..dim).map(|i| 1.0 + i as f64 / dim as f64).collect();
+
+    for _ in 0..POWER_ITERATIONS {
+        // Multiply by the covariance matrix, without building it: Xᵀ·(X·c)
+        let mut next = vec![0.0; dim];
+        for vector in centered {
+            let weight = dot(vector, &component);
+            for (next, value) in next.iter_mut().zip(vector) {
+                *next += weight * value;
+            }
+        }
+
+        if let Some(other) = orthogonal_to {
+            let overlap = dot(&next, other);
+            for (next, other) in next.iter_mut().zip(other) {
+                *next -= overlap * other;
+            }
+        }
+
+        let norm = dot(&next, &next).sqrt();
+        if norm <= f64::EPSILON {
+            return vec![0.0; dim];
+        }
+        for next in &mut next {
+            *next /= norm;
+        }
+        component = next;
+    }
+
+    component
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+        ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_pca_2d_keeps_distances_within_plane() {
+        // Points of a plane in 4D, their projection is the plane itself
+        let vectors: Vec<Vec<f32>> = [(0.0, 0.0), (3.0, 0.0), (0.0, 1.0), (3.0, 1.0), (1.5, 0.5)]
+            .into_iter()
+            .map(|(x, y)| vec![x + y, x - y, 1.0, 2.0 * y])
+            .collect();
+        let vector_refs: Vec<_> = vectors.iter().map(Vec::as_slice).collect();
+
+        let projection = pca_2d(&vector_refs);
+        assert_eq!(projection.len(), vectors.len());
+
+        for (i, a) in vectors.iter().enumerate() {
+            for (j, b) in vectors.iter().enumerate() {
+                let expected: f32 = a
+                    .iter()
+                    .zip(b)
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f32>()
+                    .sqrt();
+                let actual = distance(projection[i], projection[j]);
+                assert!((expected - actual).abs() < 1e-3, "{expected} != {actual}");
+            }
+        }
+
+        // Largest variance goes to the first coordinate
+        let spread = |axis: usize| {
+            let values = projection.iter().map(|point| point[axis]);
+            values.clone().fold(f32::MIN, f32::max) - values.fold(f32::MAX, f32::min)
+        };
+        assert!(spread(0) > spread(1));
+    }
+
+    #[test]
+    fn test_pca_2d_degenerate() {
+        assert!(pca_2d(&[]).is_empty());
+
+        let vector = [1.0, 2.0, 3.0];
+        assert_eq!(pca_2d(&[&vector, &vector]), vec![[0.0, 0.0], [0.0, 0.0]]);
+    }
+}
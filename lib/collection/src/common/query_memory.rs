@@ -0,0 +1,236 @@
+//! Approximate accounting of the memory held by queries.
+//!
+//! Candidate lists, together with the materialized payloads and vectors of their points, are the
+//! biggest allocations of a query. Their estimated size is recorded for each query, search,
+//! scroll and retrieve request, to find the request patterns which are the most expensive in
+//! memory.
+
+use std::mem::{size_of, size_of_val};
+
+use common::types::TelemetryDetail;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use segment::data_types::vectors::{Vector, VectorStructInternal};
+use segment::types::{Payload, PrefetchMatch, ScoredPoint};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::operations::types::Record;
+
+/// Upper boundaries of the histogram buckets, in bytes
+const BUCKET_BOUNDARIES_BYTES: [usize; 7] = [
+    1 << 10,   // 1 KiB
+    16 << 10,  // 16 KiB
+    256 << 10, // 256 KiB
+    1 << 20,   // 1 MiB
+    16 << 20,  // 16 MiB
+    256 << 20, // 256 MiB
+    1 << 30,   // 1 GiB
+];
+
+#[derive(Serialize, Clone, Default, Debug, JsonSchema)]
+pub struct QueryMemoryStatistics {
+    /// Number of measured queries
+    pub count: usize,
+
+    /// The biggest estimated memory usage of a single query, in bytes
+    pub max_bytes: usize,
+
+    /// The sum of the estimated memory usage of all queries, in bytes
+    pub total_bytes: usize,
+
+    /// The cumulative histogram of the estimated memory usage of queries. Consists of a list of
+    /// pairs of [upper_boundary, cumulative_count], sorted by the upper boundary. The last bucket
+    /// is not stored in this list, and `count` should be used instead.
+    #[serde(skip)]
+    pub bytes_histogram: Vec<(usize, usize)>,
+}
+
+impl Anonymize for QueryMemoryStatistics {
+    fn anonymize(&self) -> Self {
+        Self {
+            count: self.count.anonymize(),
+            max_bytes: self.max_bytes,
+            total_bytes: self.total_bytes,
+            bytes_histogram: self
+                .bytes_histogram
+                .iter()
+                .map(|&(le, count)| (le, count.anonymize()))
+                .collect(),
+        }
+    }
+}
+
+impl std::ops::Add for QueryMemoryStatistics {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // Histograms always have the same buckets, unless they were not requested
+        let bytes_histogram = if self.bytes_histogram.is_empty() {
+            other.bytes_histogram
+        } else if other.bytes_histogram.is_empty() {
+            self.bytes_histogram
+        } else {
+            self.bytes_histogram
+                .iter()
+                .zip(&other.bytes_histogram)
+                .map(|(&(le, count), &(_, other_count))| (le, count + other_count))
+                .collect()
+        };
+
+        Self {
+            count: self.count + other.count,
+            max_bytes: self.max_bytes.max(other.max_bytes),
+            total_bytes: self.total_bytes + other.total_bytes,
+            bytes_histogram,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct QueryMemoryAggregator {
+    count: usize,
+    max_bytes: usize,
+    total_bytes: usize,
+    /// The non-cumulative count of queries in each bucket
+    buckets: [usize; BUCKET_BOUNDARIES_BYTES.len()],
+}
+
+impl QueryMemoryAggregator {
+    pub fn add(&mut self, bytes: usize) {
+        self.count += 1;
+        self.max_bytes = self.max_bytes.max(bytes);
+        self.total_bytes += bytes;
+
+        if let Some(bucket_no) = BUCKET_BOUNDARIES_BYTES.iter().position(|&b| bytes <= b) {
+            self.buckets[bucket_no] += 1;
+        }
+    }
+
+    pub fn get_statistics(&self, detail: TelemetryDetail) -> QueryMemoryStatistics {
+        let bytes_histogram = if detail.histograms {
+            let mut cumulative_count = 0;
+            BUCKET_BOUNDARIES_BYTES
+                .iter()
+                .zip(&self.buckets)
+                .map(|(&le, &count)| {
+                    cumulative_count += count;
+                    (le, cumulative_count)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        QueryMemoryStatistics {
+            count: self.count,
+            max_bytes: self.max_bytes,
+            total_bytes: self.total_bytes,
+            bytes_histogram,
+        }
+    }
+}
+
+/// Estimate the memory held by the points, including their payload and vectors
+pub fn estimate_points_size<'a>(points: impl IntoIterator<Item = &'a ScoredPoint>) -> usize {
+    points.into_iter().map(estimate_point_size).sum()
+}
+
+/// Estimate the memory held by the records, including their payload and vectors
+pub fn estimate_records_size<'a>(records: impl IntoIterator<Item = &'a Record>) -> usize {
+    records.into_iter().map(estimate_record_size).sum()
+}
+
+fn estimate_record_size(record: &Record) -> usize {
+    let payload = record.payload.as_ref().map_or(0, estimate_payload_size);
+    let vector = record.vector.as_ref().map_or(0, estimate_vector_struct_size);
+
+    size_of::<Record>() + payload + vector
+}
+
+fn estimate_point_size(point: &ScoredPoint) -> usize {
+    let payload = point.payload.as_ref().map_or(0, estimate_payload_size);
+    let vector = point.vector.as_ref().map_or(0, estimate_vector_struct_size);
+    let prefetch_matches = point
+        .prefetch_matches
+        .as_ref()
+        .map_or(0, |matches| matches.len() * size_of::<PrefetchMatch>());
+
+    size_of::<ScoredPoint>() + payload + vector + prefetch_matches
+}
+
+fn estimate_payload_size(payload: &Payload) -> usize {
+    payload
+        .0
+        .iter()
+        .map(|(key, value)| key.len() + estimate_value_size(value))
+        .sum()
+}
+
+fn estimate_value_size(value: &Value) -> usize {
+    let content = match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => 0,
+        Value::String(string) => string.len(),
+        Value::Array(array) => array.iter().map(estimate_value_size).sum(),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, value)| key.len() + estimate_value_size(value))
+            .sum(),
+    };
+
+    size_of::<Value>() + content
+}
+
+fn estimate_vector_struct_size(vector: &VectorStructInternal) -> usize {
+    match vector {
+        VectorStructInternal::Single(dense) => size_of_val(dense.as_slice()),
+        VectorStructInternal::MultiDense(multi) => size_of_val(multi.flattened_vectors.as_slice()),
+        VectorStructInternal::Named(named) => named
+            .iter()
+            .map(|(name, vector)| name.len() + estimate_vector_size(vector))
+            .sum(),
+    }
+}
+
+fn estimate_vector_size(vector: &Vector) -> usize {
+    match vector {
+        Vector::Dense(dense) => size_of_val(dense.as_slice()),
+        Vector::Sparse(sparse) => {
+            size_of_val(sparse.indices.as_slice()) + size_of_val(sparse.values.as_slice())
+        }
+        Vector::MultiDense(multi) => size_of_val(multi.flattened_vectors.as_slice()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_is_cumulative() {
+        let mut aggregator = QueryMemoryAggregator::default();
+        aggregator.add(100);
+        aggregator.add(2 << 10);
+        aggregator.add(2 << 30);
+
+        let statistics = aggregator.get_statistics(TelemetryDetail {
+            histograms: true,
+            ..Default::default()
+        });
+
+        assert_eq!(statistics.count, 3);
+        assert_eq!(statistics.max_bytes, 2 << 30);
+        assert_eq!(
+            statistics.bytes_histogram,
+            vec![
+                (1 << 10, 1),
+                (16 << 10, 2),
+                (256 << 10, 2),
+                (1 << 20, 2),
+                (16 << 20, 2),
+                (256 << 20, 2),
+                (1 << 30, 2),
+            ]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Counters of the query features used on a collection.
+//!
+//! Tells which kinds of queries, prefetch structures and filters are actually used, to plan
+//! capacity and to decide on deprecations based on real usage.
+
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+
+use crate::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest, Query, VectorQuery,
+};
+
+#[derive(Serialize, Clone, Default, Debug, JsonSchema)]
+pub struct QueryUsageStatistics {
+    /// Number of queries
+    pub queries: usize,
+
+    /// Number of queries and prefetches, by the kind of their query
+    pub query_types: QueryTypeCounts,
+
+    /// Number of queries with a filter, on the query itself or on any of its prefetches
+    pub with_filter: usize,
+
+    /// Number of queries with prefetches
+    pub with_prefetch: usize,
+
+    /// Number of queries with prefetches nested into other prefetches
+    pub with_nested_prefetch: usize,
+
+    /// The deepest nesting of prefetches in a single query
+    pub max_prefetch_depth: usize,
+
+    /// Number of queries, which exceeded the query limits, but were executed with warnings
+    pub over_soft_limits: usize,
+}
+
+/// Number of queries and prefetches, by the kind of their query
+#[derive(Serialize, Clone, Default, Debug, JsonSchema)]
+pub struct QueryTypeCounts {
+    pub nearest: usize,
+    pub recommend: usize,
+    pub discover: usize,
+    pub context: usize,
+    pub fusion: usize,
+    pub order_by: usize,
+    pub sample: usize,
+    pub hybrid: usize,
+    /// Without a query, only filtering the points
+    pub none: usize,
+}
+
+impl QueryTypeCounts {
+    fn add(&mut self, query: Option<&Query>) {
+        let count = match query {
+            None => &mut self.none,
+            Some(Query::Vector(VectorQuery::Nearest(_))) => &mut self.nearest,
+            Some(Query::Vector(
+                VectorQuery::RecommendAverageVector(_) | VectorQuery::RecommendBestScore(_),
+            )) => &mut self.recommend,
+            Some(Query::Vector(VectorQuery::Discover(_))) => &mut self.discover,
+            Some(Query::Vector(VectorQuery::Context(_))) => &mut self.context,
+            Some(Query::Fusion(_)) => &mut self.fusion,
+            Some(Query::OrderBy(_)) => &mut self.order_by,
+            Some(Query::Sample(_)) => &mut self.sample,
+            Some(Query::Hybrid(_)) => &mut self.hybrid,
+        };
+        *count += 1;
+    }
+
+    /// Iterate over the counts, along with the name of the query type
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, usize)> {
+        let Self {
+            nearest,
+            recommend,
+            discover,
+            context,
+            fusion,
+            order_by,
+            sample,
+            hybrid,
+            none,
+        } = *self;
+
+        [
+            ("nearest", nearest),
+            ("recommend", recommend),
+            ("discover", discover),
+            ("context", context),
+            ("fusion", fusion),
+            ("order_by", order_by),
+            ("sample", sample),
+            ("hybrid", hybrid),
+            ("none", none),
+        ]
+        .into_iter()
+    }
+}
+
+impl QueryUsageStatistics {
+    pub fn add(&mut self, request: &CollectionQueryRequest) {
+        self.queries += 1;
+        self.query_types.add(request.query.as_ref());
+
+        let mut has_filter = request.filter.is_some();
+        let mut depth = 0;
+        for prefetch in &request.prefetch {
+            let (prefetch_depth, prefetch_has_filter) = self.add_prefetch(prefetch);
+            depth = depth.max(prefetch_depth);
+            has_filter |= prefetch_has_filter;
+        }
+
+        if has_filter {
+            self.with_filter += 1;
+        }
+        if depth >= 1 {
+            self.with_prefetch += 1;
+        }
+        if depth >= 2 {
+            self.with_nested_prefetch += 1;
+        }
+        self.max_prefetch_depth = self.max_prefetch_depth.max(depth);
+    }
+
+    /// Counts the query types of the prefetch and of its nested prefetches.
+    ///
+    /// Returns the depth of the prefetch, and whether it or any nested prefetch has a filter.
+    fn add_prefetch(&mut self, prefetch: &CollectionPrefetch) -> (usize, bool) {
+        self.query_types.add(prefetch.query.as_ref());
+
+        let mut has_filter = prefetch.filter.is_some();
+        let mut depth = 0;
+        for nested in &prefetch.prefetch {
+            let (nested_depth, nested_has_filter) = self.add_prefetch(nested);
+            depth = depth.max(nested_depth);
+            has_filter |= nested_has_filter;
+        }
+
+        (depth + 1, has_filter)
+    }
+}
+
+impl std::ops::Add for QueryUsageStatistics {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            queries: self.queries + other.queries,
+            query_types: self.query_types + other.query_types,
+            with_filter: self.with_filter + other.with_filter,
+            with_prefetch: self.with_prefetch + other.with_prefetch,
+            with_nested_prefetch: self.with_nested_prefetch + other.with_nested_prefetch,
+            max_prefetch_depth: self.max_prefetch_depth.max(other.max_prefetch_depth),
+            over_soft_limits: self.over_soft_limits + other.over_soft_limits,
+        }
+    }
+}
+
+impl std::ops::Add for QueryTypeCounts {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            nearest: self.nearest + other.nearest,
+            recommend: self.recommend + other.recommend,
+            discover: self.discover + other.discover,
+            context: self.context + other.context,
+            fusion: self.fusion + other.fusion,
+            order_by: self.order_by + other.order_by,
+            sample: self.sample + other.sample,
+            hybrid: self.hybrid + other.hybrid,
+            none: self.none + other.none,
+        }
+    }
+}
+
+impl Anonymize for QueryUsageStatistics {
+    fn anonymize(&self) -> Self {
+        Self {
+            queries: self.queries.anonymize(),
+            query_types: self.query_types.anonymize(),
+            with_filter: self.with_filter.anonymize(),
+            with_prefetch: self.with_prefetch.anonymize(),
+            with_nested_prefetch: self.with_nested_prefetch.anonymize(),
+            max_prefetch_depth: self.max_prefetch_depth,
+            over_soft_limits: self.over_soft_limits.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for QueryTypeCounts {
+    fn anonymize(&self) -> Self {
+        Self {
+            nearest: self.nearest.anonymize(),
+            recommend: self.recommend.anonymize(),
+            discover: self.discover.anonymize(),
+            context: self.context.anonymize(),
+            fusion: self.fusion.anonymize(),
+            order_by: self.order_by.anonymize(),
+            sample: self.sample.anonymize(),
+            hybrid: self.hybrid.anonymize(),
+            none: self.none.anonymize(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{Filter, WithPayloadInterface, WithVector};
+
+    use super::*;
+    use crate::operations::universal_query::collection_query::VectorInput;
+    use crate::operations::universal_query::shard_query::Fusion;
+
+    fn prefetch(query: Option<Query>, prefetch: Vec<CollectionPrefetch>) -> CollectionPrefetch {
+        CollectionPrefetch {
+            prefetch,
+            query,
+            using: String::new(),
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            params: None,
+            lookup_from: None,
+            shard_selection: None,
+        }
+    }
+
+    #[test]
+    fn test_query_usage() {
+        let nearest = || {
+            Some(Query::Vector(VectorQuery::Nearest(VectorInput::Id(
+                1.into(),
+            ))))
+        };
+
+        let mut filtered_prefetch = prefetch(nearest(), vec![]);
+        filtered_prefetch.filter = Some(Filter::default());
+
+        let request = CollectionQueryRequest {
+            prefetch: vec![
+                prefetch(nearest(), vec![]),
+                prefetch(None, vec![filtered_prefetch]),
+            ],
+            query: Some(Query::Fusion(Fusion::Rrf)),
+            using: String::new(),
+            filter: None,
+            propagate_filter: true,
+            ids: None,
+            filter_template: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+            with_routing: false,
+            with_stats: false,
+            lookup_from: None,
+            budget: None,
+            rerank: None,
+            skip_missing: false,
+        };
+
+        let mut usage = QueryUsageStatistics::default();
+        usage.add(&request);
+
+        assert_eq!(usage.queries, 1);
+        assert_eq!(usage.query_types.fusion, 1);
+        assert_eq!(usage.query_types.nearest, 2);
+        assert_eq!(usage.query_types.none, 1);
+        assert_eq!(usage.with_filter, 1);
+        assert_eq!(usage.with_prefetch, 1);
+        assert_eq!(usage.with_nested_prefetch, 1);
+        assert_eq!(usage.max_prefetch_depth, 2);
+
+        // A plain query, without prefetches or filters
+        let request = CollectionQueryRequest {
+            prefetch: vec![],
+            query: nearest(),
+            ..request
+        };
+        usage.add(&request);
+
+        assert_eq!(usage.queries, 2);
+        assert_eq!(usage.query_types.nearest, 3);
+        assert_eq!(usage.with_filter, 1);
+        assert_eq!(usage.with_prefetch, 1);
+        assert_eq!(usage.max_prefetch_depth, 2);
+    }
+}
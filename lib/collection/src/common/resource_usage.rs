@@ -0,0 +1,223 @@
+//! Attribution of CPU time and disk IO to collections.
+//!
+//! Work for a collection is spread over many threads: futures of requests on the async runtimes,
+//! blocking tasks searching segments on the search runtime, and the update worker applying
+//! operations. Requests to a collection run in the scope of its usage counters, and blocking tasks
+//! and operations created within the scope carry the counters along. Usage of the current thread is
+//! sampled before and after each poll of a request future and each blocking task, and the
+//! difference is attributed to the collection. Polls of a request within a poll of another
+//! request, e.g. looking up vectors from another collection, are attributed to the outer one.
+//!
+//! Only CPU time is sampled on polls, because reading IO counters of a thread is too expensive to
+//! do that often. Blocking tasks sample disk reads as well, if enabled in the config, at the cost of
+//! reading `/proc/thread-self/io` before and after each task. It only counts bytes actually read
+//! from storage, not page cache hits. Writes are not attributed: pages dirtied by a request are
+//! written back later by the flush worker and the kernel, not by the thread of the request. Both
+//! are only available on Linux.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use common::cpu::thread_cpu_time;
+use common::disk::thread_io_read_bytes;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+
+tokio::task_local! {
+    /// Usage counters of the collection, the current request is for
+    static CURRENT: Arc<ResourceUsage>;
+}
+
+thread_local! {
+    /// Whether a poll of a request is measured on this thread already
+    static MEASURING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Counters of the resources used by a collection
+#[derive(Debug, Default)]
+pub struct ResourceUsage {
+    /// Whether disk reads of blocking tasks are sampled
+    measure_io: bool,
+    cpu_time_ns: AtomicU64,
+    io_read_bytes: AtomicU64,
+}
+
+#[derive(Serialize, Clone, Default, Debug, JsonSchema)]
+pub struct ResourceUsageStatistics {
+    /// CPU time spent on requests to the collection, in milliseconds
+    pub cpu_time_ms: usize,
+
+    /// Number of bytes read from storage for requests to the collection.
+    /// Only measured if enabled in the config
+    pub io_read_bytes: usize,
+}
+
+impl ResourceUsage {
+    pub fn new(measure_io: bool) -> Self {
+        Self {
+            measure_io,
+            ..Default::default()
+        }
+    }
+
+    pub fn statistics(&self) -> ResourceUsageStatistics {
+        ResourceUsageStatistics {
+            cpu_time_ms: (self.cpu_time_ns.load(Ordering::Relaxed) / 1_000_000) as usize,
+            io_read_bytes: self.io_read_bytes.load(Ordering::Relaxed) as usize,
+        }
+    }
+
+    fn add_cpu_time(&self, cpu_time: Duration) {
+        self.cpu_time_ns
+            .fetch_add(cpu_time.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    fn add_io_read_bytes(&self, read_bytes: u64) {
+        self.io_read_bytes.fetch_add(read_bytes, Ordering::Relaxed);
+    }
+}
+
+/// Usage counters of the collection, the current request is for, if any
+pub fn current() -> Option<Arc<ResourceUsage>> {
+    CURRENT.try_with(Arc::clone).ok()
+}
+
+/// Run the request future in the scope of the usage counters of a collection, and attribute the
+/// CPU time of its polls to them
+pub fn scope<F: Future>(usage: &Arc<ResourceUsage>, future: F) -> impl Future<Output = F::Output> {
+    let measured = Measured {
+        usage: usage.clone(),
+        future: Box::pin(future),
+    };
+    CURRENT.scope(usage.clone(), measured)
+}
+
+/// Attribute the CPU time and disk reads of the blocking task to the collection of the current request,
+/// if any. Must be called within the request, but the task may run on any thread.
+pub fn measured<T>(task: impl FnOnce() -> T) -> impl FnOnce() -> T {
+    let usage = current();
+    move || measure(usage.as_deref(), task)
+}
+
+/// Run the blocking task, and attribute its CPU time and disk reads to the usage counters, if any
+pub fn measure<T>(usage: Option<&ResourceUsage>, task: impl FnOnce() -> T) -> T {
+    let Some(usage) = usage else {
+        return task();
+    };
+
+    let cpu_time = thread_cpu_time();
+    let io_read_bytes = usage.measure_io.then(thread_io_read_bytes).flatten();
+
+    let result = task();
+
+    if let (Some(start), Some(end)) = (cpu_time, thread_cpu_time()) {
+        usage.add_cpu_time(end.saturating_sub(start));
+    }
+    if let Some(start) = io_read_bytes {
+        if let Some(end) = thread_io_read_bytes() {
+            usage.add_io_read_bytes(end.saturating_sub(start));
+        }
+    }
+
+    result
+}
+
+/// Future, which attributes the CPU time of its polls to the usage counters
+struct Measured<F> {
+    usage: Arc<ResourceUsage>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Measured<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Nested poll, measured by the outer one already
+        if MEASURING.with(|measuring| measuring.replace(true)) {
+            return self.future.as_mut().poll(cx);
+        }
+        let _measuring = MeasuringGuard;
+
+        let start = thread_cpu_time();
+        let poll = self.future.as_mut().poll(cx);
+        if let (Some(start), Some(end)) = (start, thread_cpu_time()) {
+            self.usage.add_cpu_time(end.saturating_sub(start));
+        }
+        poll
+    }
+}
+
+/// Resets [`MEASURING`] once the outermost poll is done, even if it panics
+struct MeasuringGuard;
+
+impl Drop for MeasuringGuard {
+    fn drop(&mut self) {
+        MEASURING.with(|measuring| measuring.set(false));
+    }
+}
+
+impl std::ops::Add for ResourceUsageStatistics {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            cpu_time_ms: self.cpu_time_ms + other.cpu_time_ms,
+            io_read_bytes: self.io_read_bytes + other.io_read_bytes,
+        }
+    }
+}
+
+impl Anonymize for ResourceUsageStatistics {
+    fn anonymize(&self) -> Self {
+        Self {
+            cpu_time_ms: self.cpu_time_ms.anonymize(),
+            io_read_bytes: self.io_read_bytes.anonymize(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_nested_scopes() {
+        let outer = Arc::new(ResourceUsage::default());
+        let inner = Arc::new(ResourceUsage::default());
+
+        let (inner_usage, outer_usage) = scope(&outer, async {
+            let inner_usage = scope(&inner, async { current() }).await;
+            (inner_usage, current())
+        })
+        .await;
+
+        assert!(Arc::ptr_eq(&inner_usage.unwrap(), &inner));
+        assert!(Arc::ptr_eq(&outer_usage.unwrap(), &outer));
+        assert!(current().is_none());
+        assert!(!MEASURING.with(Cell::get));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_measure_cpu_time() {
+        let usage = ResourceUsage::default();
+
+        let sum = measure(Some(&usage), || {
+            let start = std::time::Instant::now();
+            let mut sum = 0u64;
+            while start.elapsed() < Duration::from_millis(20) {
+                sum = std::hint::black_box(sum.wrapping_add(1));
+            }
+            sum
+        });
+
+        assert!(sum > 0);
+        assert!(usage.statistics().cpu_time_ms > 0);
+    }
+}
@@ -137,6 +137,8 @@ impl<'a> RetrieveRequest for CollectionQueryResolveRequest<'a> {
 }
 impl VectorQuery<VectorInput> {
     pub fn get_referenced_ids(&self) -> Vec<&PointIdType> {
-        self.flat_iter().filter_map(VectorInput::as_id).collect()
+        self.flat_iter()
+            .flat_map(VectorInput::referenced_ids)
+            .collect()
     }
 }
@@ -0,0 +1,51 @@
+//! Statistics of the searches of a request.
+//!
+//! Search, recommend and discover requests may ask for the work done to execute them. Such
+//! requests run in the scope of their search statistics, and all shards searched within the scope
+//! count their work into them. Remote shards ask their peer for the statistics of the search, and
+//! add them to the ones of the scope.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use segment::index::search_stats::SearchStats;
+use segment::types::QueryStats;
+
+tokio::task_local! {
+    /// Search statistics of the current request
+    static CURRENT: Arc<SearchStats>;
+}
+
+/// Search statistics of the current request, if it collects them
+pub fn current() -> Option<Arc<SearchStats>> {
+    CURRENT.try_with(Arc::clone).ok()
+}
+
+/// Run the request future, collecting the statistics of its searches if `enabled`
+pub async fn collect<F: Future>(enabled: bool, future: F) -> (F::Output, Option<QueryStats>) {
+    if !enabled {
+        return (future.await, None);
+    }
+
+    let search_stats = Arc::new(SearchStats::default());
+    let output = CURRENT.scope(search_stats.clone(), future).await;
+    (output, Some(search_stats.stats()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_collect_stats() {
+        let ((), stats) = collect(true, async {
+            current().unwrap().add_hnsw_searches(2);
+        })
+        .await;
+        assert_eq!(stats.unwrap().hnsw_searches, 2);
+
+        let (is_collected, stats) = collect(false, async { current().is_some() }).await;
+        assert!(!is_collected);
+        assert!(stats.is_none());
+    }
+}
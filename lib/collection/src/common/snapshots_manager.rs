@@ -22,6 +22,10 @@ use crate::shards::shard_holder::LockedShardHolder;
 pub struct SnapShotsConfig {
     pub snapshots_storage: SnapshotsStorageConfig,
     pub s3_config: Option<S3Config>,
+    /// Compact the WAL of the shards of a collection after creating its snapshot, see
+    /// `ShardReplicaSet::compact_wal`
+    #[serde(default)]
+    pub compact_wal: bool,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -0,0 +1,255 @@
+//! Spilling of query results to disk, to merge huge results of shards within a memory budget.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::mem::size_of_val;
+use std::path::Path;
+use std::sync::Arc;
+
+use prost::Message;
+use segment::types::ScoredPoint;
+use tempfile::NamedTempFile;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::conversions::try_scored_point_from_grpc;
+
+/// Lists of points written to a temporary file, which are read back one point at a time.
+///
+/// Points are stored in their gRPC representation, as they are sent between peers. Each of them
+/// is prefixed with a flag telling whether it has a payload, and the length of its message.
+#[derive(Debug)]
+pub struct SpilledPoints {
+    file: NamedTempFile,
+    /// Offset in the file and number of points of each list
+    lists: Vec<(u64, usize)>,
+}
+
+impl SpilledPoints {
+    pub fn spill(lists: Vec<Vec<ScoredPoint>>, dir: &Path) -> CollectionResult<Self> {
+        let num_lists = lists.len();
+        let mut writer = SpillWriter::new(dir)?;
+        for (list, points) in lists.into_iter().enumerate() {
+            for point in points {
+                writer.write(list, point)?;
+            }
+        }
+        writer.finish(num_lists)
+    }
+
+    /// Read back each of the spilled lists, the file is removed once all of them are dropped
+    pub fn into_lists(self) -> CollectionResult<Vec<SpilledPointsIter>> {
+        let Self { file, lists } = self;
+        let file = Arc::new(file);
+
+        lists
+            .into_iter()
+            .map(|(offset, len)| {
+                let mut reader = file.reopen()?;
+                reader.seek(SeekFrom::Start(offset))?;
+                Ok(SpilledPointsIter {
+                    _file: Arc::clone(&file),
+                    reader: BufReader::new(reader),
+                    remaining: len,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Writes points to disk one at a time, so they can be dropped from memory right away
+pub struct SpillWriter {
+    file: NamedTempFile,
+    writer: BufWriter<File>,
+    /// Offset in the file and number of points of each list written so far
+    lists: Vec<(u64, usize)>,
+    offset: u64,
+}
+
+impl SpillWriter {
+    pub fn new(dir: &Path) -> CollectionResult<Self> {
+        std::fs::create_dir_all(dir)?;
+        let file = NamedTempFile::new_in(dir)?;
+        let writer = BufWriter::new(file.as_file().try_clone()?);
+
+        Ok(Self {
+            file,
+            writer,
+            lists: Vec::new(),
+            offset: 0,
+        })
+    }
+
+    /// Append a point to the list with the given index. Lists must be written in order, lists
+    /// skipped in between are empty.
+    pub fn write(&mut self, list: usize, point: ScoredPoint) -> CollectionResult<()> {
+        debug_assert!(
+            list + 1 >= self.lists.len(),
+            "lists must be written in order"
+        );
+        while self.lists.len() <= list {
+            self.lists.push((self.offset, 0));
+        }
+
+        let has_payload = point.payload.is_some();
+        let message = api::grpc::qdrant::ScoredPoint::from(point).encode_to_vec();
+        let message_len = u32::try_from(message.len()).map_err(|_| {
+            CollectionError::service_error("Point is too large to be spilled to disk")
+        })?;
+
+        self.writer.write_all(&[u8::from(has_payload)])?;
+        self.writer.write_all(&message_len.to_le_bytes())?;
+        self.writer.write_all(&message)?;
+
+        self.offset += (1 + size_of_val(&message_len) + message.len()) as u64;
+        self.lists[list].1 += 1;
+        Ok(())
+    }
+
+    /// Finish writing the given number of lists, the ones without points written are empty
+    pub fn finish(mut self, num_lists: usize) -> CollectionResult<SpilledPoints> {
+        while self.lists.len() < num_lists {
+            self.lists.push((self.offset, 0));
+        }
+
+        self.writer.flush()?;
+
+        Ok(SpilledPoints {
+            file: self.file,
+            lists: self.lists,
+        })
+    }
+}
+
+/// Reads the points of one spilled list
+pub struct SpilledPointsIter {
+    /// Keeps the temporary file until all lists are read
+    _file: Arc<NamedTempFile>,
+    reader: BufReader<File>,
+    remaining: usize,
+}
+
+impl SpilledPointsIter {
+    fn read_point(&mut self) -> CollectionResult<ScoredPoint> {
+        let mut has_payload = [0; 1];
+        self.reader.read_exact(&mut has_payload)?;
+
+        let mut message_len = [0; 4];
+        self.reader.read_exact(&mut message_len)?;
+
+        let mut message = vec![0; u32::from_le_bytes(message_len) as usize];
+        self.reader.read_exact(&mut message)?;
+
+        let point = api::grpc::qdrant::ScoredPoint::decode(message.as_slice()).map_err(|err| {
+            CollectionError::service_error(format!("Failed to decode spilled point: {err}"))
+        })?;
+
+        Ok(try_scored_point_from_grpc(point, has_payload[0] != 0)?)
+    }
+}
+
+impl Iterator for SpilledPointsIter {
+    type Item = CollectionResult<ScoredPoint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        Some(self.read_point())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{ExtendedPointId, Payload};
+    use serde_json::json;
+
+    use super::*;
+
+    fn point(id: u64, payload: Option<Payload>) -> ScoredPoint {
+        ScoredPoint {
+            id: ExtendedPointId::NumId(id),
+            version: id,
+            score: id as f32 / 10.0,
+            payload,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            prefetch_matches: None,
+            routing: None,
+            raw_score: None,
+        }
+    }
+
+    #[test]
+    fn test_spill_and_read_back() {
+        let dir = tempfile::Builder::new()
+            .prefix("spilled_points")
+            .tempdir()
+            .unwrap();
+
+        let payload: Payload = json!({"city": "Berlin"}).into();
+        let lists = vec![
+            vec![
+                point(1, Some(payload.clone())),
+                point(2, Some(Payload::default())),
+            ],
+            vec![],
+            vec![point(3, None)],
+        ];
+
+        let spilled = SpilledPoints::spill(lists.clone(), dir.path()).unwrap();
+        let read_lists: Vec<Vec<_>> = spilled
+            .into_lists()
+            .unwrap()
+            .into_iter()
+            .map(|list| list.collect::<CollectionResult<_>>().unwrap())
+            .collect();
+
+        assert_eq!(read_lists.len(), lists.len());
+        for (read_points, points) in read_lists.iter().zip(&lists) {
+            assert_eq!(read_points.len(), points.len());
+            for (read_point, point) in read_points.iter().zip(points) {
+                assert_eq!(read_point.id, point.id);
+                assert_eq!(read_point.version, point.version);
+                assert_eq!(read_point.score, point.score);
+                assert_eq!(read_point.payload, point.payload);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spill_writer_empty_lists() {
+        let dir = tempfile::Builder::new()
+            .prefix("spilled_points")
+            .tempdir()
+            .unwrap();
+
+        // Only the tail of the second list is spilled
+        let mut writer = SpillWriter::new(dir.path()).unwrap();
+        writer.write(1, point(1, None)).unwrap();
+        writer.write(1, point(2, None)).unwrap();
+        let spilled = writer.finish(3).unwrap();
+
+        let read_ids: Vec<Vec<_>> = spilled
+            .into_lists()
+            .unwrap()
+            .into_iter()
+            .map(|list| list.map(|point| point.unwrap().id).collect())
+            .collect();
+
+        assert_eq!(
+            read_ids,
+            vec![
+                vec![],
+                vec![ExtendedPointId::NumId(1), ExtendedPointId::NumId(2)],
+                vec![],
+            ],
+        );
+    }
+}
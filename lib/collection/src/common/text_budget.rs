@@ -0,0 +1,86 @@
+//! Trimming of query results to a budget on the total size of a payload text field, such as the
+//! context window of a language model.
+
+use api::rest::{BudgetUnit, TextBudget};
+use segment::types::{Payload, PayloadContainer as _};
+
+/// Size of a text in the given unit
+pub fn text_size(text: &str, unit: BudgetUnit) -> usize {
+    match unit {
+        BudgetUnit::Chars => text.chars().count(),
+        // Same split as the `word` tokenizer of full-text indexes
+        BudgetUnit::Tokens => text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .count(),
+    }
+}
+
+/// Size of the text field of a payload, summed over its values if there are several
+pub fn payload_text_size(payload: &Payload, budget: &TextBudget) -> usize {
+    payload
+        .get_value(&budget.key)
+        .iter()
+        .filter_map(|value| value.as_str())
+        .map(|text| text_size(text, budget.unit))
+        .sum()
+}
+
+/// Keeps the first items whose total size fits in the limit, stopping at the first one that
+/// doesn't fit
+pub fn trim_to_budget<T>(items: Vec<T>, limit: usize, size: impl Fn(&T) -> usize) -> Vec<T> {
+    let mut total = 0;
+    items
+        .into_iter()
+        .take_while(|item| {
+            total += size(item);
+            total <= limit
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::json_path::path;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_text_size() {
+        let text = "Qdrant, a vector database — written in Rust!";
+        assert_eq!(text_size(text, BudgetUnit::Chars), 44);
+        assert_eq!(text_size(text, BudgetUnit::Tokens), 7);
+        assert_eq!(text_size("", BudgetUnit::Tokens), 0);
+    }
+
+    #[test]
+    fn test_payload_text_size() {
+        let budget = TextBudget {
+            key: path("text"),
+            limit: 10,
+            unit: BudgetUnit::Chars,
+        };
+
+        let payload = Payload::from(json!({"text": "abc"}));
+        assert_eq!(payload_text_size(&payload, &budget), 3);
+
+        let payload = Payload::from(json!({"text": ["abc", "de"]}));
+        assert_eq!(payload_text_size(&payload, &budget), 5);
+
+        let payload = Payload::from(json!({"text": 42, "other": "abc"}));
+        assert_eq!(payload_text_size(&payload, &budget), 0);
+    }
+
+    #[test]
+    fn test_trim_to_budget() {
+        let sizes = vec![3, 4, 0, 3, 1];
+
+        assert_eq!(
+            trim_to_budget(sizes.clone(), 7, |size| *size),
+            vec![3, 4, 0]
+        );
+        assert_eq!(trim_to_budget(sizes.clone(), 11, |size| *size), sizes);
+        assert!(trim_to_budget(sizes, 2, |size| *size).is_empty());
+    }
+}
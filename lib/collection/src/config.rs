@@ -10,17 +10,21 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
+use segment::json_path::JsonPath;
 use segment::types::{
     default_replication_factor_const, default_shard_number_const,
-    default_write_consistency_factor_const, Distance, HnswConfig, Indexes, PayloadStorageType,
-    QuantizationConfig, SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype,
-    VectorStorageType,
+    default_write_consistency_factor_const, Condition, Distance, FieldCondition, Filter,
+    HnswConfig, Indexes, Match, MatchValue, PayloadCompressionConfig, PayloadStorageType,
+    QuantizationConfig, SearchPoolsConfig, SparseVectorDataConfig, ValueVariants, VectorDataConfig,
+    VectorStorageDatatype, VectorStorageType,
 };
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 use wal::WalOptions;
 
 use crate::operations::config_diff::{DiffConfig, QuantizationConfigDiff};
+use crate::operations::filter_template::FilterTemplate;
+use crate::operations::hybrid_vectors::HybridVector;
 use crate::operations::types::{
     CollectionError, CollectionResult, SparseVectorParams, SparseVectorsConfig, VectorParams,
     VectorParamsDiff, VectorsConfig, VectorsConfigDiff,
@@ -96,6 +100,19 @@ pub struct CollectionParams {
     /// Having more than 0 might be useful to smooth latency spikes of individual nodes.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub read_fan_out_factor: Option<u32>,
+    /// Number of versions of each updated point to keep in memory.
+    /// Allows to read points as they were at some previous version, e.g. to debug ranking changes
+    /// after re-embedding. Each shard keeps at most 100000 versions, dropping history of the least
+    /// recently updated points first. History is not persisted, it is lost on restart and not
+    /// transferred with shards. Point history is disabled if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(max = 100))]
+    pub point_history_size: Option<u32>,
+    /// Choose the vector to search with from a payload field of the points, e.g. one vector per
+    /// language. Queries without `using` and filtering on one value of the field search with the
+    /// vector of that value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_routing: Option<VectorRouting>,
     /// If true - point's payload will not be stored in memory.
     /// It will be read from the disk every time it is requested.
     /// This setting saves RAM by (slightly) increasing the response time.
@@ -106,6 +123,27 @@ pub struct CollectionParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// If true - the collection is frozen: all segments are indexed and compacted to as few as
+    /// possible, and points can't be updated. Useful to publish static datasets.
+    #[serde(default)]
+    pub frozen: bool,
+    /// Filters with placeholders for parameters, by name. Queries reference a template with the
+    /// values of its parameters, e.g. to restrict the points to the ones a tenant may see.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter_templates: Option<BTreeMap<String, FilterTemplate>>,
+    /// Pairs of a dense and a sparse vector, by the name queries target them with.
+    /// Configured with `hybrid_vectors` when creating the collection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hybrid_vectors: Option<BTreeMap<String, HybridVector>>,
+    /// Reuse of memory between searches. Defaults fit most workloads, tuning may reduce
+    /// allocations of collections with very high request rates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_pools: Option<SearchPoolsConfig>,
+    /// Compression of large payload fields in storage, e.g. the text of document chunks.
+    /// Segments are rewritten in the background when it changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub payload_compression: Option<PayloadCompressionConfig>,
 }
 
 impl CollectionParams {
@@ -127,8 +165,71 @@ impl Anonymize for CollectionParams {
             replication_factor: self.replication_factor,
             write_consistency_factor: self.write_consistency_factor,
             read_fan_out_factor: self.read_fan_out_factor,
+            point_history_size: self.point_history_size,
+            vector_routing: self.vector_routing.anonymize(),
             on_disk_payload: self.on_disk_payload,
             sparse_vectors: self.sparse_vectors.anonymize(),
+            frozen: self.frozen,
+            filter_templates: self.filter_templates.anonymize(),
+            hybrid_vectors: self.hybrid_vectors.anonymize(),
+            search_pools: self.search_pools,
+            payload_compression: self.payload_compression.anonymize(),
+        }
+    }
+}
+
+/// Vector to search with, chosen by a payload field of the points, e.g. one vector per language
+/// in a multilingual collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorRouting {
+    /// Payload field selecting the vector, e.g. `lang`
+    pub key: JsonPath,
+    /// Name of the vector for each value of the field, e.g. `{"en": "english", "de": "german"}`.
+    /// Empty to disable routing.
+    pub vectors: BTreeMap<String, String>,
+}
+
+impl VectorRouting {
+    /// Vector to search with for a query with this filter, if the filter requires a single value
+    /// of the routing field
+    pub fn vector_for(&self, filter: Option<&Filter>) -> Option<&str> {
+        filter?
+            .must
+            .iter()
+            .flatten()
+            .find_map(|condition| match condition {
+                Condition::Field(FieldCondition {
+                    key,
+                    r#match:
+                        Some(Match::Value(MatchValue {
+                            value: ValueVariants::Keyword(value),
+                        })),
+                    ..
+                }) if key == &self.key => self.vectors.get(value),
+                _ => None,
+            })
+            .map(String::as_str)
+    }
+
+    /// Check that the routed vectors are part of the given collection
+    pub fn check_vector_names(&self, collection: &CollectionParams) -> CollectionResult<()> {
+        for vector_name in self.vectors.values() {
+            if collection.vectors.get_params(vector_name).is_none() {
+                return Err(CollectionError::bad_input(format!(
+                    "Routed vector {vector_name} doesn't exist in the collection"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Anonymize for VectorRouting {
+    fn anonymize(&self) -> Self {
+        VectorRouting {
+            key: self.key.clone(),
+            vectors: self.vectors.anonymize(),
         }
     }
 }
@@ -204,8 +305,15 @@ impl CollectionParams {
             replication_factor: default_replication_factor(),
             write_consistency_factor: default_write_consistency_factor(),
             read_fan_out_factor: None,
+            point_history_size: None,
+            vector_routing: None,
             on_disk_payload: default_on_disk_payload(),
             sparse_vectors: None,
+            frozen: false,
+            filter_templates: None,
+            hybrid_vectors: None,
+            search_pools: None,
+            payload_compression: None,
         }
     }
 
@@ -279,6 +387,7 @@ impl CollectionParams {
                 hnsw_config,
                 quantization_config,
                 on_disk,
+                ingest,
             } = update_params.clone();
 
             if let Some(hnsw_diff) = hnsw_config {
@@ -307,6 +416,10 @@ impl CollectionParams {
             if let Some(on_disk) = on_disk {
                 vector_params.on_disk = Some(on_disk);
             }
+
+            if let Some(ingest) = ingest {
+                vector_params.ingest = Some(ingest);
+            }
         }
         Ok(())
     }
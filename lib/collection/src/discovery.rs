@@ -35,16 +35,11 @@ fn discovery_into_core_search(
     // Check we actually fetched all referenced vectors in this request
     let referenced_ids = request.get_referenced_point_ids();
 
-    for &point_id in &referenced_ids {
-        if all_vectors_records_map
-            .get(&lookup_collection_name, point_id)
-            .is_none()
-        {
-            return Err(CollectionError::PointNotFound {
-                missed_point_id: point_id,
-            });
-        }
-    }
+    all_vectors_records_map.check_fetched(
+        lookup_collection_name,
+        referenced_ids.iter().copied(),
+        collection_name,
+    )?;
 
     let target = convert_to_vectors(
         request.target.iter(),
@@ -0,0 +1,189 @@
+//! Aggregates over all points of groups.
+//!
+//! Groups of a response only hold their best hits, while aggregates are computed over all points
+//! of a group matching the filter of the request. Each shard aggregates its own points of the
+//! groups, and the partial aggregates of all shards are merged by the collection.
+
+use std::collections::{HashMap, HashSet};
+
+use common::types::ScoreType;
+use segment::data_types::groups::GroupId;
+use segment::json_path::{JsonPath, JsonPathInterface as _};
+use segment::types::{Filter, Payload, PayloadContainer};
+use serde_json::Value;
+
+use super::group_by::match_on;
+use crate::operations::types::GroupAggregates;
+
+/// Request to aggregate the points of groups within a shard
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupAggregatesRequestInternal {
+    /// Only points matching the filter are aggregated
+    pub filter: Option<Filter>,
+    /// Payload field with the ids of the groups
+    pub group_by: JsonPath,
+    /// Groups to aggregate the points of
+    pub group_ids: Vec<GroupId>,
+    /// Numeric payload fields to compute the mean of
+    pub mean: Vec<JsonPath>,
+}
+
+impl GroupAggregatesRequestInternal {
+    /// Filter matching the points of the requested groups
+    pub fn points_filter(&self) -> Filter {
+        let values = self.group_ids.iter().cloned().map(Value::from).collect();
+        let in_groups = Filter {
+            should: Some(match_on(&self.group_by, values)),
+            ..Default::default()
+        };
+        match &self.filter {
+            Some(filter) => filter.merge(&in_groups),
+            None => in_groups,
+        }
+    }
+
+    /// Payload fields needed to aggregate the points
+    pub fn payload_fields(&self) -> Vec<JsonPath> {
+        let mut fields = vec![self.group_by.strip_wildcard_suffix()];
+        fields.extend(self.mean.iter().cloned());
+        fields
+    }
+
+    /// Add the payload of a point to the aggregates of the requested groups it belongs to
+    pub fn aggregate_point(
+        &self,
+        payload: &Payload,
+        groups: &mut HashMap<GroupId, ShardGroupAggregates>,
+    ) {
+        let group_ids: HashSet<_> = payload
+            .get_value(&self.group_by)
+            .into_iter()
+            .flat_map(|value| match value {
+                Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            })
+            .filter_map(|value| GroupId::try_from(value).ok())
+            .filter(|group_id| self.group_ids.contains(group_id))
+            .collect();
+
+        for group_id in group_ids {
+            groups
+                .entry(group_id)
+                .or_default()
+                .add_point(payload, &self.mean);
+        }
+    }
+}
+
+/// Aggregates over the points of a group within a shard, merged with the ones of other shards
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShardGroupAggregates {
+    /// Number of points of the group
+    pub count: usize,
+    /// Sum and number of the numeric values of each field
+    pub sums: HashMap<String, (f64, usize)>,
+}
+
+impl ShardGroupAggregates {
+    fn add_point(&mut self, payload: &Payload, mean: &[JsonPath]) {
+        self.count += 1;
+
+        for path in mean {
+            let values = payload
+                .get_value(path)
+                .into_iter()
+                .flat_map(|value| match value {
+                    Value::Array(values) => values.iter().collect(),
+                    value => vec![value],
+                })
+                .filter_map(Value::as_f64);
+
+            for value in values {
+                let (sum, count) = self.sums.entry(path.to_string()).or_default();
+                *sum += value;
+                *count += 1;
+            }
+        }
+    }
+
+    /// Merge the aggregates of the same group from another shard
+    pub fn merge(&mut self, other: Self) {
+        self.count += other.count;
+        for (field, (sum, count)) in other.sums {
+            let entry = self.sums.entry(field).or_default();
+            entry.0 += sum;
+            entry.1 += count;
+        }
+    }
+
+    /// Final aggregates of the group, with the score of its best hit
+    pub fn into_aggregates(self, best_score: ScoreType) -> GroupAggregates {
+        let mean = self
+            .sums
+            .into_iter()
+            .filter(|(_, (_, count))| *count > 0)
+            .map(|(field, (sum, count))| (field, sum / count as f64))
+            .collect();
+
+        GroupAggregates {
+            count: self.count,
+            best_score,
+            mean,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_aggregate_and_merge() {
+        let request = GroupAggregatesRequestInternal {
+            filter: None,
+            group_by: "docId".parse().unwrap(),
+            group_ids: vec![GroupId::from(1u64), GroupId::from("b")],
+            mean: vec![
+                "price".parse().unwrap(),
+                "rating".parse().unwrap(),
+                "missing".parse().unwrap(),
+            ],
+        };
+
+        // Points of two shards
+        let mut shard_1 = HashMap::new();
+        for point in [
+            json!({ "docId": 1, "price": 10, "rating": [4, 5] }),
+            json!({ "docId": [1, 1, "b"], "price": 20.5, "rating": "n/a" }),
+            // Not a requested group
+            json!({ "docId": 2, "price": 100 }),
+        ] {
+            request.aggregate_point(&Payload::from(point), &mut shard_1);
+        }
+
+        let mut shard_2 = HashMap::new();
+        let point = json!({ "docId": 1, "price": "free" });
+        request.aggregate_point(&Payload::from(point), &mut shard_2);
+
+        let mut groups = shard_1;
+        for (group_id, aggregates) in shard_2 {
+            groups.entry(group_id).or_default().merge(aggregates);
+        }
+        assert_eq!(groups.len(), 2);
+
+        let aggregates = groups.remove(&GroupId::from(1u64)).unwrap();
+        let aggregates = aggregates.into_aggregates(0.9);
+        assert_eq!(aggregates.count, 3);
+        assert_eq!(aggregates.best_score, 0.9);
+        assert_eq!(aggregates.mean.len(), 2);
+        assert_eq!(aggregates.mean["price"], 15.25);
+        assert_eq!(aggregates.mean["rating"], 4.5);
+
+        let aggregates = groups.remove(&GroupId::from("b")).unwrap();
+        let aggregates = aggregates.into_aggregates(0.8);
+        assert_eq!(aggregates.count, 1);
+        assert_eq!(aggregates.mean["price"], 20.5);
+    }
+}
@@ -155,6 +155,16 @@ impl GroupsAggregator {
         best_group_keys.intersection(&self.full_groups).count()
     }
 
+    /// Checks if there is a group for each of the known values of the grouped by field, and no
+    /// other groups, so that no new groups can be found
+    pub(super) fn covers_all_values(&self, values: &HashSet<String>) -> bool {
+        self.groups.len() == values.len()
+            && self.groups.keys().all(|key| match key {
+                GroupId::String(key) => values.contains(key),
+                _ => false,
+            })
+    }
+
     /// Gets the ids of the already present points across all the groups
     pub(super) fn ids(&self) -> &HashSet<ExtendedPointId> {
         &self.all_ids
@@ -203,6 +213,9 @@ mod unit_tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            prefetch_matches: None,
+            routing: None,
+            raw_score: None,
         }
     }
 
@@ -215,6 +228,9 @@ mod unit_tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            prefetch_matches: None,
+            routing: None,
+            raw_score: None,
         }
     }
 
@@ -442,4 +458,23 @@ mod unit_tests {
             assert_eq!(expected_id_score, group_id_score);
         }
     }
+
+    #[test]
+    fn test_covers_all_values() {
+        let mut aggregator =
+            GroupsAggregator::new(3, 2, "docId".parse().unwrap(), Order::LargeBetter);
+
+        let values: HashSet<_> = ["a", "b"].into_iter().map(String::from).collect();
+        assert!(!aggregator.covers_all_values(&values));
+
+        aggregator.add_point(point(1, 0.99, json!("a"))).unwrap();
+        assert!(!aggregator.covers_all_values(&values));
+
+        aggregator.add_point(point(2, 0.98, json!("b"))).unwrap();
+        assert!(aggregator.covers_all_values(&values));
+
+        // a value unknown to the index means the values are not complete
+        aggregator.add_point(point(3, 0.97, json!("c"))).unwrap();
+        assert!(!aggregator.covers_all_values(&values));
+    }
 }
@@ -5,6 +5,7 @@ use itertools::Itertools;
 use tokio::sync::RwLockReadGuard;
 
 use super::group_by::{group_by, GroupRequest};
+use super::neighbors::fill_neighbors;
 use crate::collection::Collection;
 use crate::lookup::lookup_ids;
 use crate::lookup::types::PseudoId;
@@ -76,6 +77,11 @@ where
     /// Does the actual grouping
     async fn run(self) -> CollectionResult<Vec<PointGroup>> {
         let with_lookup = self.group_by.with_lookup.clone();
+        let neighbors_request = self
+            .group_by
+            .with_neighbors
+            .is_some()
+            .then(|| self.group_by.clone());
 
         let core_group_by = self
             .group_by
@@ -96,6 +102,17 @@ where
         )
         .await?;
 
+        if let Some(neighbors_request) = neighbors_request {
+            fill_neighbors(
+                &neighbors_request,
+                &mut groups,
+                self.collection,
+                self.read_consistency,
+                &self.shard_selection,
+            )
+            .await?;
+        }
+
         if let Some(lookup) = with_lookup {
             let mut lookups = {
                 let pseudo_ids = groups
@@ -2,7 +2,10 @@ use std::collections::HashMap;
 use std::future::Future;
 use std::time::Duration;
 
-use api::rest::{BaseGroupRequest, SearchGroupsRequestInternal, SearchRequestInternal};
+use api::rest::{
+    BaseGroupRequest, SearchGroupsRequestInternal, SearchRequestInternal, WithAggregates,
+    WithNeighbors,
+};
 use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
 use segment::json_path::{JsonPath, JsonPathInterface as _};
@@ -12,6 +15,7 @@ use segment::types::{
 use serde_json::Value;
 use tokio::sync::RwLockReadGuard;
 
+use super::aggregates::GroupAggregatesRequestInternal;
 use super::aggregator::GroupsAggregator;
 use super::types::CoreGroupRequest;
 use crate::collection::Collection;
@@ -26,6 +30,8 @@ use crate::recommendations::recommend_into_core_search;
 
 const MAX_GET_GROUPS_REQUESTS: usize = 5;
 const MAX_GROUP_FILLING_REQUESTS: usize = 5;
+/// Max amount of distinct keyword values to look up in the payload index for early termination
+const MAX_EARLY_TERMINATION_VALUES: usize = 256;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum SourceRequest {
@@ -49,6 +55,12 @@ pub struct GroupRequest {
 
     /// Options for specifying how to use the group id to lookup points in another collection
     pub with_lookup: Option<WithLookup>,
+
+    /// Options for returning the neighbors of the best hit of each group
+    pub with_neighbors: Option<WithNeighbors>,
+
+    /// Options for returning aggregates over all points of each group matching the filter
+    pub with_aggregates: Option<WithAggregates>,
 }
 
 impl GroupRequest {
@@ -67,6 +79,8 @@ impl GroupRequest {
             group_size,
             limit,
             with_lookup: None,
+            with_neighbors: None,
+            with_aggregates: None,
         }
     }
 
@@ -102,6 +116,7 @@ impl GroupRequest {
             group_size: self.group_size,
             limit: self.limit,
             with_lookup: self.with_lookup,
+            with_aggregates: self.with_aggregates,
         })
     }
 }
@@ -153,6 +168,8 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
                     group_size,
                     limit,
                     with_lookup: with_lookup_interface,
+                    with_neighbors,
+                    with_aggregates,
                 },
         } = request;
 
@@ -173,6 +190,8 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
             group_size: group_size as usize,
             limit: limit as usize,
             with_lookup: with_lookup_interface.map(Into::into),
+            with_neighbors,
+            with_aggregates,
         }
     }
 }
@@ -196,6 +215,8 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
                     group_size,
                     limit,
                     with_lookup: with_lookup_interface,
+                    with_neighbors,
+                    with_aggregates,
                 },
         } = request;
 
@@ -212,6 +233,7 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             score_threshold,
             using,
             lookup_from,
+            skip_missing: None,
         };
 
         GroupRequest {
@@ -220,6 +242,8 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             group_size: group_size as usize,
             limit: limit as usize,
             with_lookup: with_lookup_interface.map(Into::into),
+            with_neighbors,
+            with_aggregates,
         }
     }
 }
@@ -248,6 +272,17 @@ pub async fn group_by(
         score_ordering,
     );
 
+    // Distinct values of a low-cardinality keyword field are known from the payload index,
+    // so we can stop looking for new groups once each of them has one
+    let distinct_values = collection
+        .keyword_values(&request.group_by, MAX_EARLY_TERMINATION_VALUES)
+        .await;
+    let covers_all_values = |aggregator: &GroupsAggregator| {
+        distinct_values
+            .as_ref()
+            .is_some_and(|values| aggregator.covers_all_values(values))
+    };
+
     // Try to complete amount of groups
     let mut needs_filling = true;
     for _ in 0..MAX_GET_GROUPS_REQUESTS {
@@ -308,6 +343,12 @@ pub async fn group_by(
             needs_filling = false;
             break;
         }
+
+        if covers_all_values(&aggregator) {
+            // No new groups can appear, only the existing ones can be filled up
+            needs_filling = !aggregator.keys_of_unfilled_best_groups().is_empty();
+            break;
+        }
     }
 
     // Try to fill up groups
@@ -366,12 +407,47 @@ pub async fn group_by(
             if aggregator.len_of_filled_best_groups() >= request.limit {
                 break;
             }
+
+            if covers_all_values(&aggregator)
+                && aggregator.keys_of_unfilled_best_groups().is_empty()
+            {
+                break;
+            }
         }
     }
 
     // extract best results
     let mut groups = aggregator.distill();
 
+    // aggregate all points of the groups in the shards, not only the hits
+    let aggregates = match &request.with_aggregates {
+        Some(with_aggregates) => {
+            let aggregates_request = GroupAggregatesRequestInternal {
+                filter: request.source.filter.clone(),
+                group_by: request.group_by.clone(),
+                group_ids: groups.iter().map(|group| group.key.clone()).collect(),
+                mean: with_aggregates.mean.clone(),
+            };
+            let mut shard_aggregates = collection
+                .group_aggregates(aggregates_request, &shard_selection)
+                .await?;
+
+            // Score of the best hit is the best score of the group
+            let aggregates: Vec<_> = groups
+                .iter()
+                .map(|group| {
+                    let best_score = group.hits.first().map_or(0.0, |hit| hit.score);
+                    shard_aggregates
+                        .remove(&group.key)
+                        .unwrap_or_default()
+                        .into_aggregates(best_score)
+                })
+                .collect();
+            Some(aggregates)
+        }
+        None => None,
+    };
+
     // flatten results
     let bare_points = groups
         .iter()
@@ -399,7 +475,13 @@ pub async fn group_by(
         .for_each(|group| group.hydrate_from(&enriched_points));
 
     // turn into output form
-    let groups = groups.into_iter().map(PointGroup::from).collect();
+    let mut groups: Vec<_> = groups.into_iter().map(PointGroup::from).collect();
+
+    if let Some(aggregates) = aggregates {
+        for (group, aggregates) in groups.iter_mut().zip(aggregates) {
+            group.aggregates = Some(aggregates);
+        }
+    }
 
     Ok(groups)
 }
@@ -418,7 +500,7 @@ fn except_on(path: &JsonPath, values: Vec<Value>) -> Vec<Condition> {
 }
 
 /// Uses the set of values to create Match::Any's, if possible
-fn match_on(path: &JsonPath, values: Vec<Value>) -> Vec<Condition> {
+pub(super) fn match_on(path: &JsonPath, values: Vec<Value>) -> Vec<Condition> {
     values_to_any_variants(values)
         .into_iter()
         .map(|any_variants| {
@@ -471,6 +553,9 @@ mod tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            prefetch_matches: None,
+            routing: None,
+            raw_score: None,
         }
     }
 
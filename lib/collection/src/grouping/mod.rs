@@ -1,6 +1,8 @@
+pub mod aggregates;
 mod aggregator;
 mod builder;
 pub mod group_by;
+mod neighbors;
 mod types;
 
 pub use builder::GroupBy;
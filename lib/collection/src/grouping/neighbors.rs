@@ -0,0 +1,217 @@
+//! Neighbors of the best hit of each group.
+//!
+//! When documents are stored as one point per chunk, grouping by the document gives the best
+//! chunks of the best documents. Neighbors extend the best chunk of each group with the chunks
+//! around it, so that a whole passage of the document is returned without another request.
+
+use std::collections::{HashMap, HashSet};
+
+use futures::future::try_join_all;
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, FieldCondition, Filter, Payload, PayloadContainer as _, PointIdType, Range,
+    WithPayload, WithPayloadInterface, WithVector,
+};
+use serde_json::Value;
+
+use super::group_by::{match_on, GroupRequest, SourceRequest};
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionResult, PointGroup, PointRequestInternal, ScrollRequestInternal,
+};
+
+/// Fills the neighbors of the best hit of each group.
+///
+/// Neighbors are the points of the same group whose ordinal is at most `neighbors` away from
+/// the ordinal of the best hit, and which match the filter of the request. If the best hit
+/// has no ordinal, it is its only neighbor.
+pub(super) async fn fill_neighbors(
+    request: &GroupRequest,
+    groups: &mut [PointGroup],
+    collection: &Collection,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: &ShardSelectorInternal,
+) -> CollectionResult<()> {
+    let Some(with_neighbors) = &request.with_neighbors else {
+        return Ok(());
+    };
+    let ordinal_by = &with_neighbors.ordinal_by;
+
+    let (filter, with_payload, with_vector) = match &request.source {
+        SourceRequest::Search(request) => {
+            (&request.filter, &request.with_payload, &request.with_vector)
+        }
+        SourceRequest::Recommend(request) => {
+            (&request.filter, &request.with_payload, &request.with_vector)
+        }
+    };
+    let with_payload = WithPayload::from(
+        with_payload
+            .as_ref()
+            .unwrap_or(&WithPayloadInterface::Bool(false)),
+    );
+
+    let best_ids: HashSet<PointIdType> = groups
+        .iter()
+        .filter_map(|group| group.hits.first().map(|hit| hit.id))
+        .collect();
+    if best_ids.is_empty() {
+        return Ok(());
+    }
+
+    let ordinals: HashMap<PointIdType, f64> = collection
+        .retrieve(
+            PointRequestInternal {
+                ids: best_ids.into_iter().collect(),
+                with_payload: Some(WithPayloadInterface::Fields(vec![ordinal_by.clone()])),
+                with_vector: WithVector::Bool(false),
+                as_of_version: None,
+            },
+            read_consistency,
+            shard_selection,
+        )
+        .await?
+        .into_iter()
+        .filter_map(|record| Some((record.id, ordinal(record.payload.as_ref(), ordinal_by)?)))
+        .collect();
+
+    let distance = f64::from(with_neighbors.neighbors);
+    let limit = 2 * with_neighbors.neighbors as usize + 1;
+
+    let scrolls = groups.iter().map(|group| {
+        let best_id = group.hits.first().map(|hit| hit.id);
+        let window = match best_id.and_then(|id| ordinals.get(&id)) {
+            Some(&best_ordinal) => {
+                let mut conditions =
+                    match_on(&request.group_by, vec![Value::from(group.id.clone())]);
+                conditions.push(Condition::Field(FieldCondition::new_range(
+                    ordinal_by.clone(),
+                    Range {
+                        lt: None,
+                        gt: None,
+                        gte: Some(best_ordinal - distance),
+                        lte: Some(best_ordinal + distance),
+                    },
+                )));
+                Filter {
+                    must: Some(conditions),
+                    ..Default::default()
+                }
+            }
+            None => Filter::new_must(Condition::HasId(
+                best_id.into_iter().collect::<HashSet<_>>().into(),
+            )),
+        };
+
+        let scroll = ScrollRequestInternal {
+            offset: None,
+            limit: Some(limit),
+            filter: Some(
+                filter
+                    .as_ref()
+                    .map(|filter| filter.merge(&window))
+                    .unwrap_or(window),
+            ),
+            // The whole payload is needed to sort by the ordinal, it is selected afterwards
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: with_vector.clone().unwrap_or_default(),
+            order_by: None,
+        };
+
+        async move {
+            let points = collection
+                .scroll_by(scroll, read_consistency, shard_selection)
+                .await?
+                .points;
+            CollectionResult::Ok(points)
+        }
+    });
+
+    let neighbors = try_join_all(scrolls).await?;
+
+    for (group, mut points) in groups.iter_mut().zip(neighbors) {
+        sort_and_select_payload(&mut points, ordinal_by, &with_payload);
+        group.neighbors = Some(points);
+    }
+
+    Ok(())
+}
+
+/// Position of a point in its group, read from the ordinal field of its payload
+fn ordinal(payload: Option<&Payload>, ordinal_by: &JsonPath) -> Option<f64> {
+    payload?
+        .get_value(ordinal_by)
+        .iter()
+        .find_map(|value| value.as_f64())
+}
+
+/// Orders the points by their ordinal, then keeps only the requested payload
+fn sort_and_select_payload(
+    points: &mut [api::rest::Record],
+    ordinal_by: &JsonPath,
+    with_payload: &WithPayload,
+) {
+    points.sort_by(|a, b| {
+        let a = ordinal(a.payload.as_ref(), ordinal_by).unwrap_or(f64::MAX);
+        let b = ordinal(b.payload.as_ref(), ordinal_by).unwrap_or(f64::MAX);
+        a.total_cmp(&b)
+    });
+
+    for point in points.iter_mut() {
+        point.payload = if with_payload.enable {
+            point
+                .payload
+                .take()
+                .map(|payload| match &with_payload.payload_selector {
+                    Some(selector) => selector.process(payload),
+                    None => payload,
+                })
+        } else {
+            None
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::json_path::path;
+    use serde_json::json;
+
+    use super::*;
+
+    fn record(id: u64, payload: Value) -> api::rest::Record {
+        api::rest::Record {
+            id: id.into(),
+            payload: Some(Payload::from(payload)),
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_and_select_payload() {
+        let mut points = vec![
+            record(3, json!({"chunk": 7, "text": "c"})),
+            record(1, json!({"chunk": 5, "text": "a"})),
+            record(4, json!({"text": "no ordinal"})),
+            record(2, json!({"chunk": 6.0, "text": "b"})),
+        ];
+
+        let with_payload = WithPayload::from(&WithPayloadInterface::Fields(vec![path("text")]));
+        sort_and_select_payload(&mut points, &path("chunk"), &with_payload);
+
+        let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        assert_eq!(
+            ids,
+            vec![1u64.into(), 2u64.into(), 3u64.into(), 4u64.into()]
+        );
+        assert_eq!(points[0].payload, Some(Payload::from(json!({"text": "a"}))));
+
+        let with_payload = WithPayload::from(&WithPayloadInterface::Bool(false));
+        sort_and_select_payload(&mut points, &path("chunk"), &with_payload);
+        assert!(points.iter().all(|point| point.payload.is_none()));
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use api::rest::WithAggregates;
 use segment::data_types::groups::GroupId;
 use segment::json_path::JsonPath;
 use segment::types::{PointIdType, ScoredPoint};
@@ -39,6 +40,8 @@ impl From<Group> for PointGroup {
                 .collect(),
             id: group.key,
             lookup: None,
+            neighbors: None,
+            aggregates: None,
         }
     }
 }
@@ -59,6 +62,9 @@ pub struct CoreGroupRequest {
 
     /// Options for specifying how to use the group id to lookup points in another collection
     pub with_lookup: Option<WithLookup>,
+
+    /// Options for returning aggregates over all points of each group matching the filter
+    pub with_aggregates: Option<WithAggregates>,
 }
 
 #[cfg(test)]
@@ -55,6 +55,7 @@ where
         ids,
         with_payload: request.with_payload,
         with_vector: request.with_vectors.unwrap_or_default(),
+        as_of_version: None,
     };
 
     let result = collection
@@ -1,14 +1,19 @@
+use std::collections::BTreeMap;
 use std::num::NonZeroU32;
 
 use merge::Merge;
 use schemars::JsonSchema;
-use segment::types::{BinaryQuantization, HnswConfig, ProductQuantization, ScalarQuantization};
+use segment::types::{
+    BinaryQuantization, HnswConfig, PayloadCompressionConfig, ProductQuantization,
+    ScalarQuantization, SearchPoolsConfig,
+};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::{Validate, ValidationErrors};
 
-use crate::config::{CollectionParams, WalConfig};
+use crate::config::{CollectionParams, VectorRouting, WalConfig};
+use crate::operations::filter_template::FilterTemplate;
 use crate::operations::types::CollectionResult;
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -96,7 +101,9 @@ pub struct WalConfigDiff {
     pub wal_segments_ahead: Option<usize>,
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Merge, PartialEq, Eq, Hash)]
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge, PartialEq, Eq, Hash,
+)]
 pub struct CollectionParamsDiff {
     /// Number of replicas for each shard
     pub replication_factor: Option<NonZeroU32>,
@@ -104,12 +111,34 @@ pub struct CollectionParamsDiff {
     pub write_consistency_factor: Option<NonZeroU32>,
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
     pub read_fan_out_factor: Option<u32>,
+    /// Number of versions of each updated point to keep in memory, 0 to disable point history.
+    /// History is not persisted, it is lost on restart and not transferred with shards.
+    #[validate(range(max = 100))]
+    pub point_history_size: Option<u32>,
+    /// Choose the vector to search with from a payload field of the points, e.g. one vector per language
+    pub vector_routing: Option<VectorRouting>,
     /// If true - point's payload will not be stored in memory.
     /// It will be read from the disk every time it is requested.
     /// This setting saves RAM by (slightly) increasing the response time.
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// If true - compact and index the collection, and forbid updates of points.
+    /// Set to false to allow updates again.
+    #[serde(default)]
+    pub frozen: Option<bool>,
+    /// Filters with placeholders for parameters, which queries reference by name.
+    /// Replaces all templates of the collection.
+    #[serde(default)]
+    pub filter_templates: Option<BTreeMap<String, FilterTemplate>>,
+    /// Reuse of memory between searches, e.g. number of visited lists kept for reuse.
+    /// Replaces all settings of the collection.
+    #[serde(default)]
+    pub search_pools: Option<SearchPoolsConfig>,
+    /// Compression of large payload fields in storage, e.g. the text of document chunks.
+    /// Replaces the compression of the collection, segments are rewritten in the background.
+    #[serde(default)]
+    pub payload_compression: Option<PayloadCompressionConfig>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
@@ -125,7 +154,7 @@ pub struct OptimizersConfigDiff {
     ///
     /// It is recommended to select default number of segments as a factor of the number of search threads,
     /// so that each segment would be handled evenly by one of the threads
-    /// If `default_segment_number = 0`, will be automatically selected by the number of available CPUs
+    /// If `default_segment_number = 0`, will be automatically adjusted to the size of the collection and the number of available CPUs
     pub default_segment_number: Option<usize>,
     /// Do not create segments larger this size (in kilobytes).
     /// Large segments might require disproportionately long indexation times,
@@ -347,7 +376,13 @@ mod tests {
             replication_factor: None,
             write_consistency_factor: Some(NonZeroU32::new(2).unwrap()),
             read_fan_out_factor: None,
+            point_history_size: None,
+            vector_routing: None,
             on_disk_payload: None,
+            frozen: None,
+            filter_templates: None,
+            search_pools: None,
+            payload_compression: None,
         };
 
         let new_params = diff.update(&params).unwrap();
@@ -4,7 +4,8 @@ use std::time::Duration;
 
 use api::grpc::conversions::{
     convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
-    from_grpc_dist, json_path_from_proto, payload_to_proto, proto_to_payloads,
+    from_grpc_dist, json_path_from_proto, naive_date_time_to_proto, payload_to_proto,
+    proto_to_payloads,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
@@ -25,13 +26,14 @@ use tonic::Status;
 
 use super::consistency_params::ReadConsistency;
 use super::types::{
-    ContextExamplePair, CoreSearchRequest, Datatype, DiscoverRequestInternal, GroupsResult,
-    Modifier, PointGroup, RecommendExample, RecommendGroupsRequestInternal, SparseIndexParams,
-    SparseVectorParams, SparseVectorsConfig, VectorParamsDiff, VectorsConfigDiff,
+    ContextExamplePair, CoreSearchRequest, Datatype, DiscoverRequestInternal, GroupAggregates,
+    GroupsResult, HybridVectorParams, Modifier, PointGroup, RecommendExample,
+    RecommendGroupsRequestInternal, SparseIndexParams, SparseVectorParams, SparseVectorsConfig,
+    VectorIngestPolicy, VectorParamsDiff, VectorsConfigDiff,
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    CollectionParams, ShardingMethod, VectorRouting, WalConfig,
 };
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
@@ -45,6 +47,8 @@ use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use crate::operations::filter_template::FilterTemplate;
+use crate::operations::hybrid_vectors::HybridVector;
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
     Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
@@ -52,9 +56,9 @@ use crate::operations::point_ops::{
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, OptimizersStatus, RecommendRequestInternal, Record, RemoteShardInfo,
-    ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
+    AliasChange, AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus,
+    CountResult, LocalShardInfo, OptimizersStatus, RecommendRequestInternal, Record,
+    RemoteShardInfo, ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
 };
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::remote_shard::CollectionCoreSearchRequest;
@@ -79,6 +83,95 @@ pub fn sharding_method_from_proto(sharding_method: i32) -> Result<ShardingMethod
     }
 }
 
+impl From<VectorRouting> for api::grpc::qdrant::VectorRouting {
+    fn from(value: VectorRouting) -> Self {
+        let VectorRouting { key, vectors } = value;
+        Self {
+            key: key.to_string(),
+            vectors: vectors.into_iter().collect(),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::VectorRouting> for VectorRouting {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::VectorRouting) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::VectorRouting { key, vectors } = value;
+        Ok(Self {
+            key: json_path_from_proto(&key)?,
+            vectors: vectors.into_iter().collect(),
+        })
+    }
+}
+
+pub fn filter_templates_to_proto(
+    templates: BTreeMap<String, FilterTemplate>,
+) -> api::grpc::qdrant::FilterTemplates {
+    api::grpc::qdrant::FilterTemplates {
+        map: templates
+            .into_iter()
+            .map(|(name, template)| {
+                let filter = payload_to_proto(template.filter.into());
+                (name, api::grpc::qdrant::FilterTemplate { filter })
+            })
+            .collect(),
+    }
+}
+
+pub fn filter_templates_from_proto(
+    templates: api::grpc::qdrant::FilterTemplates,
+) -> Result<BTreeMap<String, FilterTemplate>, Status> {
+    templates
+        .map
+        .into_iter()
+        .map(|(name, template)| {
+            let filter = proto_to_payloads(template.filter)?.0;
+            Ok((name, FilterTemplate { filter }))
+        })
+        .collect()
+}
+
+pub fn hybrid_vectors_to_proto(
+    hybrid_vectors: BTreeMap<String, HybridVector>,
+) -> api::grpc::qdrant::HybridVectors {
+    api::grpc::qdrant::HybridVectors {
+        map: hybrid_vectors
+            .into_iter()
+            .map(|(name, HybridVector { dense, sparse })| {
+                (name, api::grpc::qdrant::HybridVector { dense, sparse })
+            })
+            .collect(),
+    }
+}
+
+pub fn hybrid_vectors_from_proto(
+    hybrid_vectors: api::grpc::qdrant::HybridVectors,
+) -> BTreeMap<String, HybridVector> {
+    hybrid_vectors
+        .map
+        .into_iter()
+        .map(|(name, pair)| {
+            let api::grpc::qdrant::HybridVector { dense, sparse } = pair;
+            (name, HybridVector { dense, sparse })
+        })
+        .collect()
+}
+
+impl TryFrom<api::grpc::qdrant::HybridVectorParams> for HybridVectorParams {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::HybridVectorParams) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::HybridVectorParams { dense, sparse } = value;
+        Ok(Self {
+            dense: dense
+                .ok_or_else(|| Status::invalid_argument("Hybrid vector requires dense params"))?
+                .try_into()?,
+            sparse: sparse.map(SparseVectorParams::try_from).transpose()?,
+        })
+    }
+}
+
 pub fn write_ordering_to_proto(ordering: WriteOrdering) -> api::grpc::qdrant::WriteOrdering {
     api::grpc::qdrant::WriteOrdering {
         r#type: match ordering {
@@ -143,6 +236,7 @@ pub fn try_record_from_grpc(
         vector,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
         order_value,
+        version: point.version,
     })
 }
 
@@ -174,6 +268,8 @@ pub fn try_discover_request_from_grpc(
         read_consistency,
         timeout,
         shard_key_selector,
+        session_token: _,
+        with_stats: _,
     } = value;
 
     let target = target.map(TryInto::try_into).transpose()?;
@@ -282,7 +378,19 @@ impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
                 })
                 .transpose()?,
             read_fan_out_factor: value.read_fan_out_factor,
+            point_history_size: value.point_history_size,
+            vector_routing: value
+                .vector_routing
+                .map(VectorRouting::try_from)
+                .transpose()?,
             on_disk_payload: value.on_disk_payload,
+            frozen: value.frozen,
+            filter_templates: value
+                .filter_templates
+                .map(filter_templates_from_proto)
+                .transpose()?,
+            search_pools: value.search_pools.map(From::from),
+            payload_compression: value.payload_compression.map(From::from),
         })
     }
 }
@@ -383,6 +491,16 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     on_disk_payload: config.params.on_disk_payload,
                     write_consistency_factor: Some(config.params.write_consistency_factor.get()),
                     read_fan_out_factor: config.params.read_fan_out_factor,
+                    point_history_size: config.params.point_history_size,
+                    vector_routing: config.params.vector_routing.map(From::from),
+                    frozen: config.params.frozen,
+                    filter_templates: config
+                        .params
+                        .filter_templates
+                        .map(filter_templates_to_proto),
+                    hybrid_vectors: config.params.hybrid_vectors.map(hybrid_vectors_to_proto),
+                    search_pools: config.params.search_pools.map(From::from),
+                    payload_compression: config.params.payload_compression.map(From::from),
                     sharding_method: config.params.sharding_method.map(sharding_method_to_proto),
                     sparse_vectors_config: config.params.sparse_vectors.map(|sparse_vectors| {
                         api::grpc::qdrant::SparseVectorConfig {
@@ -447,6 +565,7 @@ impl From<Record> for api::grpc::qdrant::RetrievedPoint {
             vectors: vectors.map(api::grpc::qdrant::Vectors::from),
             shard_key: record.shard_key.map(convert_shard_key_to_grpc),
             order_value: record.order_value.map(From::from),
+            version: record.version,
         }
     }
 }
@@ -554,6 +673,7 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .multivector_config
                 .map(MultiVectorConfig::try_from)
                 .transpose()?,
+            ingest: vector_params.ingest.map(VectorIngestPolicy::from),
         })
     }
 }
@@ -590,10 +710,41 @@ impl TryFrom<api::grpc::qdrant::VectorParamsDiff> for VectorParamsDiff {
                 .map(TryInto::try_into)
                 .transpose()?,
             on_disk: vector_params.on_disk,
+            ingest: vector_params.ingest.map(VectorIngestPolicy::from),
         })
     }
 }
 
+impl From<api::grpc::qdrant::VectorIngestPolicy> for VectorIngestPolicy {
+    fn from(value: api::grpc::qdrant::VectorIngestPolicy) -> Self {
+        let api::grpc::qdrant::VectorIngestPolicy {
+            normalize,
+            reject_non_finite,
+            reject_zero,
+        } = value;
+        Self {
+            normalize,
+            reject_non_finite,
+            reject_zero,
+        }
+    }
+}
+
+impl From<VectorIngestPolicy> for api::grpc::qdrant::VectorIngestPolicy {
+    fn from(value: VectorIngestPolicy) -> Self {
+        let VectorIngestPolicy {
+            normalize,
+            reject_non_finite,
+            reject_zero,
+        } = value;
+        Self {
+            normalize,
+            reject_non_finite,
+            reject_zero,
+        }
+    }
+}
+
 impl From<api::grpc::qdrant::Modifier> for Modifier {
     fn from(value: api::grpc::qdrant::Modifier) -> Self {
         match value {
@@ -734,6 +885,19 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     })?,
 
                     read_fan_out_factor: params.read_fan_out_factor,
+                    point_history_size: params.point_history_size,
+                    vector_routing: params
+                        .vector_routing
+                        .map(VectorRouting::try_from)
+                        .transpose()?,
+                    frozen: params.frozen,
+                    filter_templates: params
+                        .filter_templates
+                        .map(filter_templates_from_proto)
+                        .transpose()?,
+                    hybrid_vectors: params.hybrid_vectors.map(hybrid_vectors_from_proto),
+                    search_pools: params.search_pools.map(From::from),
+                    payload_compression: params.payload_compression.map(From::from),
                     sharding_method: params
                         .sharding_method
                         .map(sharding_method_from_proto)
@@ -925,6 +1089,7 @@ impl From<UpdateResult> for api::grpc::qdrant::UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status.into(),
             clock_tag: res.clock_tag.map(Into::into),
+            session_token: res.session_token.map(|token| token.to_string()),
         }
     }
 }
@@ -943,6 +1108,11 @@ impl TryFrom<api::grpc::qdrant::UpdateResultInternal> for UpdateResult {
             operation_id: res.operation_id,
             status: res.status.try_into()?,
             clock_tag: res.clock_tag.map(Into::into),
+            session_token: res
+                .session_token
+                .map(|token| token.parse())
+                .transpose()
+                .map_err(Status::invalid_argument)?,
         };
 
         Ok(res)
@@ -1024,6 +1194,8 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             timeout: _,
             shard_key_selector: _,
             sparse_indices,
+            session_token: _,
+            with_stats: _,
         } = value;
 
         if let Some(sparse_indices) = &sparse_indices {
@@ -1274,6 +1446,28 @@ impl From<PointGroup> for api::grpc::qdrant::PointGroup {
             lookup: group
                 .lookup
                 .map(|record| api::grpc::qdrant::RetrievedPoint::from(Record::from(record))),
+            neighbors: group
+                .neighbors
+                .unwrap_or_default()
+                .into_iter()
+                .map(|record| api::grpc::qdrant::RetrievedPoint::from(Record::from(record)))
+                .collect(),
+            aggregates: group.aggregates.map(Into::into),
+        }
+    }
+}
+
+impl From<GroupAggregates> for api::grpc::qdrant::GroupAggregates {
+    fn from(aggregates: GroupAggregates) -> Self {
+        let GroupAggregates {
+            count,
+            best_score,
+            mean,
+        } = aggregates;
+        Self {
+            count: count as u64,
+            best_score,
+            mean,
         }
     }
 }
@@ -1424,6 +1618,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequestInternal {
             score_threshold: value.score_threshold,
             using: value.using.map(|name| name.into()),
             lookup_from: value.lookup_from.map(|x| x.into()),
+            skip_missing: value.skip_missing,
         })
     }
 }
@@ -1451,6 +1646,9 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             negative_vectors: value.negative_vectors,
             timeout: None, // Passed as query param
             shard_key_selector: None,
+            skip_missing: None,
+            session_token: None,
+            with_stats: None,
         };
 
         let RecommendRequestInternal {
@@ -1466,6 +1664,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             score_threshold,
             limit: _,
             offset: _,
+            skip_missing: _,
         } = recommend_points.try_into()?;
 
         Ok(RecommendGroupsRequestInternal {
@@ -1484,6 +1683,8 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
                 limit: value.limit,
                 group_size: value.group_size,
                 with_lookup: value.with_lookup.map(|l| l.try_into()).transpose()?,
+                with_neighbors: value.with_neighbors.map(|n| n.try_into()).transpose()?,
+                with_aggregates: value.with_aggregates.map(|a| a.try_into()).transpose()?,
             },
         })
     }
@@ -1517,6 +1718,9 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             multivector_config: value
                 .multivector_config
                 .map(api::grpc::qdrant::MultiVectorConfig::from),
+            ingest: value
+                .ingest
+                .map(api::grpc::qdrant::VectorIngestPolicy::from),
         }
     }
 }
@@ -1540,6 +1744,17 @@ impl From<AliasDescription> for api::grpc::qdrant::AliasDescription {
     }
 }
 
+impl From<AliasChange> for api::grpc::qdrant::AliasChange {
+    fn from(value: AliasChange) -> Self {
+        api::grpc::qdrant::AliasChange {
+            collection_name: value.collection_name,
+            changed_at: value
+                .changed_at
+                .map(|changed_at| naive_date_time_to_proto(changed_at.naive_utc())),
+        }
+    }
+}
+
 impl From<LocalShardInfo> for api::grpc::qdrant::LocalShardInfo {
     fn from(value: LocalShardInfo) -> Self {
         Self {
@@ -1558,6 +1773,8 @@ impl From<RemoteShardInfo> for api::grpc::qdrant::RemoteShardInfo {
             peer_id: value.peer_id,
             state: value.state as i32,
             shard_key: value.shard_key.map(convert_shard_key_to_grpc),
+            replication_lag: value.replication_lag,
+            degraded: value.degraded,
         }
     }
 }
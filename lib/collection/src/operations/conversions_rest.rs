@@ -22,6 +22,7 @@ impl From<api::rest::Record> for Record {
             vector: value.vector.map(VectorStructInternal::from),
             shard_key: value.shard_key,
             order_value: value.order_value,
+            version: None,
         }
     }
 }
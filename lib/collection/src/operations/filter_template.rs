@@ -0,0 +1,218 @@
+//! Filter templates of collections.
+//!
+//! Complex filters, such as restricting the points to the ones a tenant may see, are defined once
+//! in the collection params, with placeholders for their parameters. Queries reference a template
+//! by name with the values of its parameters, and the bound filter is added to their own filter.
+//! This way clients can't compose such filters wrong, e.g. by forgetting a condition.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+
+use api::rest::FilterTemplateReference;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use segment::types::Filter;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Key of the objects standing in for the values of parameters
+const PARAM_KEY: &str = "$param";
+
+/// Filter with placeholders, whose values are given by the queries using the template
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct FilterTemplate {
+    /// Filter with `{"$param": "<name>"}` in place of the values of parameters, e.g.
+    /// `{"must": [{"key": "tenant", "match": {"value": {"$param": "tenant"}}}]}`
+    pub filter: Map<String, Value>,
+}
+
+impl Hash for FilterTemplate {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for (key, value) in &self.filter {
+            key.hash(state);
+            value.to_string().hash(state);
+        }
+    }
+}
+
+impl Anonymize for FilterTemplate {
+    fn anonymize(&self) -> Self {
+        // Values of the template may identify users, e.g. roles allowed to see all points
+        FilterTemplate { filter: Map::new() }
+    }
+}
+
+impl FilterTemplate {
+    /// Names of the parameters of the template
+    pub fn param_names(&self) -> CollectionResult<BTreeSet<&str>> {
+        fn collect<'a>(value: &'a Value, names: &mut BTreeSet<&'a str>) -> CollectionResult<()> {
+            match value {
+                Value::Object(object) => match param_name(object)? {
+                    Some(name) => {
+                        names.insert(name);
+                    }
+                    None => {
+                        for value in object.values() {
+                            collect(value, names)?;
+                        }
+                    }
+                },
+                Value::Array(values) => {
+                    for value in values {
+                        collect(value, names)?;
+                    }
+                }
+                _ => {}
+            }
+            Ok(())
+        }
+
+        let mut names = BTreeSet::new();
+        for value in self.filter.values() {
+            collect(value, &mut names)?;
+        }
+        Ok(names)
+    }
+
+    /// Filter with the values of the parameters in place of their placeholders
+    pub fn bind(&self, params: &HashMap<String, Value>) -> CollectionResult<Filter> {
+        let param_names = self.param_names()?;
+        if let Some(name) = param_names.iter().find(|name| !params.contains_key(**name)) {
+            return Err(CollectionError::bad_input(format!(
+                "Missing value of parameter {name} of the filter template"
+            )));
+        }
+        if let Some(name) = params
+            .keys()
+            .find(|name| !param_names.contains(name.as_str()))
+        {
+            return Err(CollectionError::bad_input(format!(
+                "Filter template has no parameter {name}"
+            )));
+        }
+
+        let filter = bind_value(Value::Object(self.filter.clone()), params)?;
+        serde_json::from_value(filter).map_err(|err| {
+            CollectionError::bad_input(format!(
+                "Filter template with the given parameters is not a valid filter: {err}"
+            ))
+        })
+    }
+}
+
+/// Name of the parameter, if the object is a placeholder
+fn param_name(object: &Map<String, Value>) -> CollectionResult<Option<&str>> {
+    match object.get(PARAM_KEY) {
+        None => Ok(None),
+        Some(Value::String(name)) if object.len() == 1 => Ok(Some(name)),
+        Some(_) => Err(CollectionError::bad_input(format!(
+            "Placeholders of filter templates must be of the form {{\"{PARAM_KEY}\": \"<name>\"}}"
+        ))),
+    }
+}
+
+fn bind_value(value: Value, params: &HashMap<String, Value>) -> CollectionResult<Value> {
+    match value {
+        Value::Object(object) => {
+            if let Some(name) = param_name(&object)? {
+                // All parameters are checked to have a value beforehand
+                return Ok(params.get(name).cloned().unwrap_or_default());
+            }
+            object
+                .into_iter()
+                .map(|(key, value)| Ok((key, bind_value(value, params)?)))
+                .collect::<CollectionResult<_>>()
+                .map(Value::Object)
+        }
+        Value::Array(values) => values
+            .into_iter()
+            .map(|value| bind_value(value, params))
+            .collect::<CollectionResult<_>>()
+            .map(Value::Array),
+        value => Ok(value),
+    }
+}
+
+/// Filter of the referenced template of the collection, with the values of its parameters
+pub fn resolve_filter_template(
+    reference: &FilterTemplateReference,
+    templates: Option<&BTreeMap<String, FilterTemplate>>,
+) -> CollectionResult<Filter> {
+    let template = templates
+        .and_then(|templates| templates.get(&reference.name))
+        .ok_or_else(|| {
+            CollectionError::bad_input(format!(
+                "Filter template {} doesn't exist in the collection",
+                reference.name,
+            ))
+        })?;
+    template.bind(&reference.params)
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{Condition, FieldCondition, Match};
+    use serde_json::json;
+
+    use super::*;
+
+    fn template(filter: Value) -> FilterTemplate {
+        serde_json::from_value(json!({ "filter": filter })).unwrap()
+    }
+
+    fn params(params: Value) -> HashMap<String, Value> {
+        serde_json::from_value(params).unwrap()
+    }
+
+    #[test]
+    fn test_bind() {
+        let template = template(json!({
+            "must": [
+                { "key": "tenant", "match": { "value": { "$param": "tenant" } } },
+                { "key": "level", "range": { "lte": { "$param": "level" } } },
+            ],
+        }));
+        assert_eq!(
+            template.param_names().unwrap(),
+            BTreeSet::from(["level", "tenant"]),
+        );
+
+        let filter = template
+            .bind(&params(json!({ "tenant": "acme", "level": 3 })))
+            .unwrap();
+        let conditions = filter.must.unwrap();
+        assert_eq!(conditions.len(), 2);
+        let Condition::Field(FieldCondition {
+            r#match: Some(Match::Value(value)),
+            ..
+        }) = &conditions[0]
+        else {
+            panic!("unexpected condition {:?}", conditions[0]);
+        };
+        assert_eq!(value.value.to_value(), json!("acme"));
+    }
+
+    #[test]
+    fn test_bind_errors() {
+        let tenant = template(json!({
+            "must": [{ "key": "tenant", "match": { "value": { "$param": "tenant" } } }],
+        }));
+
+        // Missing, unknown and mistyped parameters
+        assert!(tenant.bind(&params(json!({}))).is_err());
+        assert!(tenant
+            .bind(&params(json!({ "tenant": "acme", "other": 1 })))
+            .is_err());
+        assert!(tenant
+            .bind(&params(json!({ "tenant": { "nested": true } })))
+            .is_err());
+
+        let malformed = template(json!({
+            "must": [{ "key": "tenant", "match": { "value": { "$param": 1 } } }],
+        }));
+        assert!(malformed.param_names().is_err());
+    }
+}
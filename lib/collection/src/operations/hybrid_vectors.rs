@@ -0,0 +1,159 @@
+//! Hybrid vectors of collections.
+//!
+//! A hybrid vector pairs a dense and a sparse vector of the same content, e.g. the embeddings of
+//! a text by a dense and a sparse model. It is declared as one logical vector when creating the
+//! collection, which configures a named vector and a sparse vector for it. Queries target the
+//! logical name with both query vectors, and the nearest points of each are fused.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::{Deserialize, Serialize};
+
+use crate::operations::types::{
+    CollectionError, CollectionResult, HybridVectorParams, SparseVectorParams, VectorsConfig,
+};
+
+/// Dense and sparse vector storing the same content, which are queried together
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct HybridVector {
+    /// Name of the dense vector of the pair
+    pub dense: String,
+    /// Name of the sparse vector of the pair
+    pub sparse: String,
+}
+
+impl HybridVector {
+    /// Names of the vectors storing the hybrid vector with the given name
+    pub fn new(name: &str) -> Self {
+        Self {
+            dense: format!("{name}-dense"),
+            sparse: format!("{name}-sparse"),
+        }
+    }
+}
+
+impl Anonymize for HybridVector {
+    fn anonymize(&self) -> Self {
+        HybridVector {
+            dense: self.dense.anonymize(),
+            sparse: self.sparse.anonymize(),
+        }
+    }
+}
+
+/// Add the vectors storing the hybrid vectors to the vectors of a new collection.
+///
+/// Vectors which already exist must have the same params, e.g. when the collection is created
+/// again from its own config.
+pub fn add_hybrid_vectors(
+    hybrid_vectors: BTreeMap<String, HybridVectorParams>,
+    vectors: &mut VectorsConfig,
+    sparse_vectors: &mut Option<BTreeMap<String, SparseVectorParams>>,
+) -> CollectionResult<BTreeMap<String, HybridVector>> {
+    let VectorsConfig::Multi(dense_vectors) = vectors else {
+        return Err(CollectionError::bad_input(
+            "Hybrid vectors can only be added to a collection with named vectors",
+        ));
+    };
+    let sparse_vectors = sparse_vectors.get_or_insert_with(BTreeMap::new);
+
+    let conflict = |name: &str, hybrid_name: &str| {
+        CollectionError::bad_input(format!(
+            "Vector {name} of hybrid vector {hybrid_name} conflicts with another vector of the collection"
+        ))
+    };
+
+    let mut pairs = BTreeMap::new();
+    for (name, params) in hybrid_vectors {
+        let HybridVectorParams { dense, sparse } = params;
+        let pair = HybridVector::new(&name);
+        let sparse = sparse.unwrap_or(SparseVectorParams {
+            index: None,
+            modifier: None,
+        });
+
+        if dense_vectors.contains_key(&name) || sparse_vectors.contains_key(&name) {
+            return Err(conflict(&name, &name));
+        }
+        match dense_vectors.get(&pair.dense) {
+            Some(existing) if existing != &dense => return Err(conflict(&pair.dense, &name)),
+            Some(_) => {}
+            None => {
+                dense_vectors.insert(pair.dense.clone(), dense);
+            }
+        }
+        match sparse_vectors.get(&pair.sparse) {
+            Some(existing) if existing != &sparse => return Err(conflict(&pair.sparse, &name)),
+            Some(_) => {}
+            None => {
+                sparse_vectors.insert(pair.sparse.clone(), sparse);
+            }
+        }
+
+        pairs.insert(name, pair);
+    }
+    Ok(pairs)
+}
+
+/// Params to create the hybrid vectors again, e.g. in a collection with the same config
+pub fn hybrid_vectors_params(
+    hybrid_vectors: &BTreeMap<String, HybridVector>,
+    vectors: &VectorsConfig,
+    sparse_vectors: Option<&BTreeMap<String, SparseVectorParams>>,
+) -> BTreeMap<String, HybridVectorParams> {
+    hybrid_vectors
+        .iter()
+        .filter_map(|(name, pair)| {
+            let params = HybridVectorParams {
+                dense: vectors.get_params(&pair.dense)?.clone(),
+                sparse: sparse_vectors
+                    .and_then(|sparse| sparse.get(&pair.sparse))
+                    .cloned(),
+            };
+            Some((name.clone(), params))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::Distance;
+
+    use super::*;
+    use crate::operations::vector_params_builder::VectorParamsBuilder;
+
+    fn hybrid_params(size: u64) -> HybridVectorParams {
+        HybridVectorParams {
+            dense: VectorParamsBuilder::new(size, Distance::Cosine).build(),
+            sparse: None,
+        }
+    }
+
+    #[test]
+    fn test_add_hybrid_vectors() {
+        let mut vectors = VectorsConfig::default();
+        let mut sparse_vectors = None;
+        let hybrid_vectors = BTreeMap::from([("text".to_string(), hybrid_params(4))]);
+
+        let pairs =
+            add_hybrid_vectors(hybrid_vectors.clone(), &mut vectors, &mut sparse_vectors).unwrap();
+        assert_eq!(pairs["text"], HybridVector::new("text"));
+        assert_eq!(vectors.get_params("text-dense").unwrap().size.get(), 4);
+        assert!(sparse_vectors.as_ref().unwrap().contains_key("text-sparse"));
+
+        // Creating the pair again from the resulting config doesn't conflict
+        let params = hybrid_vectors_params(&pairs, &vectors, sparse_vectors.as_ref());
+        assert!(add_hybrid_vectors(params, &mut vectors, &mut sparse_vectors).is_ok());
+
+        // A different vector with the same name does
+        let different = BTreeMap::from([("text".to_string(), hybrid_params(8))]);
+        assert!(add_hybrid_vectors(different, &mut vectors, &mut sparse_vectors).is_err());
+
+        // Hybrid vectors need named vectors
+        let mut single = VectorsConfig::Single(VectorParamsBuilder::new(4, Distance::Dot).build());
+        assert!(add_hybrid_vectors(hybrid_vectors, &mut single, &mut None).is_err());
+    }
+}
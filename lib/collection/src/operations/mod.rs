@@ -3,10 +3,13 @@ pub mod config_diff;
 pub mod consistency_params;
 pub mod conversions;
 pub mod conversions_rest;
+pub mod filter_template;
+pub mod hybrid_vectors;
 pub mod operation_effect;
 pub mod payload_ops;
 pub mod point_ops;
 pub mod query_enum;
+pub mod session_token;
 pub mod shard_selector_internal;
 pub mod shared_storage_config;
 pub mod snapshot_ops;
@@ -14,6 +17,7 @@ pub mod snapshot_storage_ops;
 pub mod types;
 pub mod universal_query;
 pub mod validation;
+pub mod vector_ingest;
 pub mod vector_ops;
 pub mod vector_params_builder;
 
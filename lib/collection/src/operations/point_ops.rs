@@ -58,6 +58,7 @@ impl TryFrom<Record> for PointStruct {
             vector,
             shard_key: _,
             order_value: _,
+            version: _,
         } = record;
 
         if vector.is_none() {
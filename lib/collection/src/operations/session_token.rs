@@ -0,0 +1,245 @@
+//! Session tokens for read-your-writes.
+//!
+//! Writes return the clock ticks they were applied with on each shard. When the token is attached
+//! to a later read, the read is served only by replicas which have applied these writes. The read
+//! consistency of the request still applies, over the replicas which applied the writes.
+//!
+//! Reads run in the scope of their token, so every shard read within the request is resolved
+//! against it, whichever operation it is part of.
+
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::ClockTag;
+use crate::shards::shard::{PeerId, ShardId};
+
+tokio::task_local! {
+    /// Session token of the current read request, and its timeout
+    static CURRENT: Arc<(SessionToken, Option<Duration>)>;
+}
+
+/// Run the read request future in the scope of the session token, so its reads of each shard are
+/// served by replicas which applied the writes of the token
+pub fn scope<F: Future>(
+    token: Option<&SessionToken>,
+    timeout: Option<Duration>,
+    future: F,
+) -> impl Future<Output = F::Output> {
+    let token = token.cloned().unwrap_or_default();
+    CURRENT.scope(Arc::new((token, timeout)), future)
+}
+
+/// Clocks of the writes to the given shard, the current read request must observe, and the
+/// timeout of the request
+pub(crate) fn current_shard_clocks(
+    shard_id: ShardId,
+) -> (Vec<ShardSessionClock>, Option<Duration>) {
+    CURRENT
+        .try_with(|current| {
+            let (token, timeout) = current.as_ref();
+            (token.shard_clocks(shard_id), *timeout)
+        })
+        .unwrap_or_default()
+}
+
+/// Clock tick a write was applied with on a shard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardSessionClock {
+    pub shard_id: ShardId,
+    /// Peer of the clock, which coordinated the write
+    pub peer_id: PeerId,
+    pub clock_id: u32,
+    pub clock_tick: u64,
+    /// Replica which acknowledged the write, to read from if no other replica has applied it
+    pub replica_peer_id: PeerId,
+}
+
+/// Clocks of the writes of a client session
+///
+/// Rendered as a comma separated list of `shard:peer:clock:tick:replica` entries, so the tokens of
+/// several writes can be combined by joining them with a comma.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionToken {
+    clocks: Vec<ShardSessionClock>,
+}
+
+impl SessionToken {
+    pub fn new(shard_id: ShardId, clock_tag: ClockTag, replica_peer_id: PeerId) -> Self {
+        Self {
+            clocks: vec![ShardSessionClock {
+                shard_id,
+                peer_id: clock_tag.peer_id,
+                clock_id: clock_tag.clock_id,
+                clock_tick: clock_tag.clock_tick,
+                replica_peer_id,
+            }],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clocks.is_empty()
+    }
+
+    /// Adds the clocks of another token, keeping the newest tick of each shard clock
+    pub fn merge(&mut self, other: &Self) {
+        for clock in &other.clocks {
+            self.add(*clock);
+        }
+    }
+
+    fn add(&mut self, clock: ShardSessionClock) {
+        let existing = self.clocks.iter_mut().find(|existing| {
+            existing.shard_id == clock.shard_id
+                && existing.peer_id == clock.peer_id
+                && existing.clock_id == clock.clock_id
+        });
+
+        match existing {
+            Some(existing) if existing.clock_tick < clock.clock_tick => *existing = clock,
+            Some(_) => {}
+            None => self.clocks.push(clock),
+        }
+    }
+
+    /// Clocks of the writes to the given shard
+    pub fn shard_clocks(&self, shard_id: ShardId) -> Vec<ShardSessionClock> {
+        self.clocks
+            .iter()
+            .filter(|clock| clock.shard_id == shard_id)
+            .copied()
+            .collect()
+    }
+}
+
+impl From<ShardSessionClock> for api::grpc::qdrant::SessionClock {
+    fn from(clock: ShardSessionClock) -> Self {
+        Self {
+            peer_id: clock.peer_id,
+            clock_id: clock.clock_id,
+            clock_tick: clock.clock_tick,
+        }
+    }
+}
+
+impl fmt::Display for SessionToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, clock) in self.clocks.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            let ShardSessionClock {
+                shard_id,
+                peer_id,
+                clock_id,
+                clock_tick,
+                replica_peer_id,
+            } = clock;
+            write!(
+                f,
+                "{shard_id}:{peer_id}:{clock_id}:{clock_tick}:{replica_peer_id}"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SessionToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut token = Self::default();
+
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let invalid = || format!("Invalid session token entry `{entry}`");
+
+            let parts: Vec<u64> = entry
+                .split(':')
+                .map(|part| part.parse().map_err(|_| invalid()))
+                .collect::<Result<_, _>>()?;
+
+            let [shard_id, peer_id, clock_id, clock_tick, replica_peer_id] = parts[..] else {
+                return Err(invalid());
+            };
+
+            token.add(ShardSessionClock {
+                shard_id: shard_id.try_into().map_err(|_| invalid())?,
+                peer_id,
+                clock_id: clock_id.try_into().map_err(|_| invalid())?,
+                clock_tick,
+                replica_peer_id,
+            });
+        }
+
+        Ok(token)
+    }
+}
+
+impl Serialize for SessionToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for SessionToken {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for SessionToken {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "SessionToken".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_token_roundtrip() {
+        let mut token = SessionToken::new(0, ClockTag::new(1, 0, 10), 2);
+        token.merge(&SessionToken::new(1, ClockTag::new(1, 0, 3), 1));
+
+        let rendered = token.to_string();
+        assert_eq!(rendered, "0:1:0:10:2,1:1:0:3:1");
+        assert_eq!(rendered.parse::<SessionToken>().unwrap(), token);
+
+        assert!("0:1:0:10".parse::<SessionToken>().is_err());
+        assert!("0:1:0:x:2".parse::<SessionToken>().is_err());
+        assert!("".parse::<SessionToken>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_session_token_merge() {
+        // Joined tokens keep the newest tick of each shard clock
+        let token: SessionToken = "0:1:0:10:2,0:1:0:7:1,0:1:1:4:1,1:1:0:3:1".parse().unwrap();
+
+        assert_eq!(token.to_string(), "0:1:0:10:2,0:1:1:4:1,1:1:0:3:1");
+        assert_eq!(token.shard_clocks(0).len(), 2);
+        assert_eq!(token.shard_clocks(1).len(), 1);
+        assert!(token.shard_clocks(2).is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use std::default;
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::common::snapshots_manager::SnapShotsConfig;
@@ -13,6 +14,7 @@ const DEFAULT_UPDATE_QUEUE_SIZE: usize = 100;
 const DEFAULT_UPDATE_QUEUE_SIZE_LISTENER: usize = 10_000;
 pub const DEFAULT_IO_SHARD_TRANSFER_LIMIT: Option<usize> = Some(1);
 pub const DEFAULT_SNAPSHOTS_PATH: &str = "./snapshots";
+pub const DEFAULT_DEGRADED_REPLICATION_LAG: Option<u64> = Some(10_000);
 
 /// Storage configuration shared between all collections.
 /// Represents a per-node configuration, which might be changes with restart.
@@ -31,6 +33,21 @@ pub struct SharedStorageConfig {
     pub outgoing_shard_transfers_limit: Option<usize>,
     pub snapshots_path: String,
     pub snapshots_config: SnapShotsConfig,
+    /// Results of a query held in memory while merging them, in bytes.
+    /// Points exceeding it are spilled to disk.
+    pub query_merge_memory_budget: Option<usize>,
+    /// Directory for the spilled results of queries
+    pub query_spill_path: PathBuf,
+    /// If a replica doesn't respond to a read within this percentile of recent read latencies,
+    /// the read is also sent to another replica. If `None`, reads are not hedged.
+    pub hedged_reads_percentile: Option<f64>,
+    /// Active replicas lagging behind the local replica by more than this number of operations
+    /// are reported as degraded, and reads are routed to other replicas first. If `None`, replicas
+    /// are never reported as degraded.
+    pub degraded_replication_lag: Option<u64>,
+    /// Whether to sample disk reads of threads working on requests, to attribute them to
+    /// collections. Reading the counters is a system call per sample.
+    pub measure_io_usage: bool,
 }
 
 impl Default for SharedStorageConfig {
@@ -48,6 +65,11 @@ impl Default for SharedStorageConfig {
             outgoing_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             snapshots_path: DEFAULT_SNAPSHOTS_PATH.to_string(),
             snapshots_config: default::Default::default(),
+            query_merge_memory_budget: None,
+            query_spill_path: std::env::temp_dir(),
+            hedged_reads_percentile: None,
+            degraded_replication_lag: DEFAULT_DEGRADED_REPLICATION_LAG,
+            measure_io_usage: false,
         }
     }
 }
@@ -67,6 +89,11 @@ impl SharedStorageConfig {
         outgoing_shard_transfers_limit: Option<usize>,
         snapshots_path: String,
         snapshots_config: SnapShotsConfig,
+        query_merge_memory_budget: Option<usize>,
+        query_spill_path: PathBuf,
+        hedged_reads_percentile: Option<f64>,
+        degraded_replication_lag: Option<u64>,
+        measure_io_usage: bool,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -85,6 +112,11 @@ impl SharedStorageConfig {
             outgoing_shard_transfers_limit,
             snapshots_path,
             snapshots_config,
+            query_merge_memory_budget,
+            query_spill_path,
+            hedged_reads_percentile,
+            degraded_replication_lag,
+            measure_io_usage,
         }
     }
 }
@@ -4,11 +4,13 @@ use std::time::SystemTime;
 use api::grpc::conversions::naive_date_time_to_proto;
 use chrono::{DateTime, NaiveDateTime};
 use schemars::JsonSchema;
+use segment::types::ShardKey;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use validator::Validate;
 
 use crate::operations::types::CollectionResult;
+use crate::shards::shard::ShardId;
 
 /// Defines source of truth for snapshot recovery:
 /// `NoSync` means - restore snapshot without *any* additional synchronization.
@@ -170,6 +172,48 @@ pub struct ShardSnapshotRecover {
     pub api_key: Option<String>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct CreateShardKeySnapshot {
+    /// Shard key to snapshot the local shards of
+    pub shard_key: ShardKey,
+}
+
+/// Snapshot of one of the shards of a shard key
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ShardKeySnapshotDescription {
+    pub shard_id: ShardId,
+    pub snapshot: SnapshotDescription,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ShardKeySnapshotRecover {
+    /// Shard key the snapshots belong to
+    pub shard_key: ShardKey,
+
+    /// Snapshot to recover each of the shards from. Every shard must belong to the shard key.
+    #[validate]
+    pub snapshots: Vec<ShardKeySnapshotLocation>,
+
+    #[serde(default)]
+    pub priority: Option<SnapshotPriority>,
+
+    /// Optional API key used when fetching the snapshots from remote URLs.
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ShardKeySnapshotLocation {
+    pub shard_id: ShardId,
+
+    pub location: ShardSnapshotLocation,
+
+    /// Optional SHA256 checksum to verify snapshot integrity before recovery.
+    #[validate(custom = "common::validation::validate_sha256_hash")]
+    #[serde(default)]
+    pub checksum: Option<String>,
+}
+
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
 #[serde(untagged)]
 pub enum ShardSnapshotLocation {
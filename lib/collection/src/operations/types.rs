@@ -6,16 +6,20 @@ use std::iter;
 use std::num::NonZeroU64;
 use std::time::SystemTimeError;
 
+use api::grpc::models::ErrorCode;
 use api::grpc::transport_channel_pool::RequestError;
+use api::grpc::ERROR_CODE_METADATA_KEY;
 use api::rest::{
     BaseGroupRequest, LookupLocation, OrderByInterface, RecommendStrategy,
     SearchGroupsRequestInternal, SearchRequestInternal, ShardKeySelector,
 };
+use chrono::{DateTime, Utc};
 use common::defaults;
 use common::types::ScoreType;
 use common::validation::validate_range_generic;
 use io::file_operations::FileStorageError;
 use issues::IssueRecord;
+use itertools::Itertools as _;
 use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
@@ -26,9 +30,9 @@ use segment::data_types::vectors::{
     DenseVector, QueryVector, VectorRef, VectorStructInternal, DEFAULT_VECTOR_NAME,
 };
 use segment::types::{
-    Distance, Filter, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType,
-    QuantizationConfig, SearchParams, SeqNumberType, ShardKey, VectorStorageDatatype,
-    WithPayloadInterface, WithVector,
+    Distance, Filter, MultiVectorConfig, Payload, PayloadFieldSchema, PayloadIndexInfo,
+    PayloadKeyType, PointIdType, QuantizationConfig, SearchParams, SeqNumberType, ShardKey,
+    VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use semver::Version;
 use serde;
@@ -43,10 +47,12 @@ use tonic::codegen::http::uri::InvalidUri;
 use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::config_diff::{self};
+use super::session_token::SessionToken;
 use super::ClockTag;
 use crate::config::{CollectionConfig, CollectionParams};
 use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
 use crate::operations::query_enum::QueryEnum;
+use crate::operations::universal_query::shard_query::Sample;
 use crate::save_on_disk;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
@@ -100,6 +106,8 @@ pub struct Record {
     pub shard_key: Option<ShardKey>,
     /// Order value, if used for order_by
     pub order_value: Option<OrderValue>,
+    /// Point version, if known
+    pub version: Option<SeqNumberType>,
 }
 
 /// Current statistics and configuration of the collection
@@ -257,6 +265,94 @@ pub struct RemoteShardInfo {
     pub peer_id: PeerId,
     /// Is replica active
     pub state: ReplicaState,
+    /// Number of operations applied by the replica of this peer, but not yet by this replica.
+    /// Only known for active replicas, on peers with an active replica of the shard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replication_lag: Option<u64>,
+    /// If true - the replication lag exceeds the configured threshold, the replica may be stuck.
+    /// Reads are routed to other replicas first.
+    pub degraded: bool,
+}
+
+/// State of a payload index on all active replicas of the collection
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadIndexStatus {
+    /// Name of the indexed field
+    pub field_name: PayloadKeyType,
+    /// Type of the index, as configured for the collection
+    pub field_schema: PayloadFieldSchema,
+    /// If `true`, every active replica has built the index. Otherwise replicas without the
+    /// index use full scans for filters on the field
+    pub consistent: bool,
+    /// State of the index on each active replica
+    pub replicas: Vec<ReplicaPayloadIndexStatus>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ReplicaPayloadIndexStatus {
+    pub shard_id: ShardId,
+    /// User-defined sharding key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Peer id of the replica
+    pub peer_id: PeerId,
+    pub state: ReplicaPayloadIndexState,
+    /// Number of points indexed on the replica
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<usize>,
+    /// Why the state of the index on the replica is unknown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplicaPayloadIndexState {
+    /// The replica has built the index
+    Built,
+    /// The replica has no index on the field
+    Missing,
+    /// The replica has built an index of another type on the field
+    Mismatch,
+    /// The replica could not be reached
+    Unavailable,
+}
+
+/// Statistics of a payload index, estimated from the replicas on the peer serving the request
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadIndexStats {
+    /// Name of the indexed field
+    pub field_name: PayloadKeyType,
+    /// Type of the index, as configured for the collection
+    pub field_schema: PayloadFieldSchema,
+    /// Approximate number of distinct values of the field, within about 1%. Values of deleted
+    /// points may be counted. Only known for keyword indexes, if every shard has an active
+    /// replica on the peer
+    pub approximate_unique_values: Option<usize>,
+}
+
+/// Neighbors of a point in the HNSW graphs of the local segments, for debugging of the index
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct HnswNeighborhood {
+    pub point_id: PointIdType,
+    /// Name of the indexed vector
+    pub vector: String,
+    /// Graphs of the segments on this peer, which have the point indexed
+    pub segments: Vec<SegmentHnswNeighbors>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentHnswNeighbors {
+    pub shard_id: ShardId,
+    /// Id of the segment, unique within the shard until restart
+    pub segment_id: usize,
+    /// Neighbors of the point on each level of the graph, starting from the bottom one
+    pub levels: Vec<Vec<PointIdType>>,
 }
 
 /// `Acknowledged` - Request is saved to WAL and will be process in a queue.
@@ -285,6 +381,75 @@ pub struct UpdateResult {
     /// Provided if incoming update request also specify clock tick
     #[serde(skip)]
     pub clock_tag: Option<ClockTag>,
+
+    /// Token of the write, to attach to subsequent reads which must observe it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_token: Option<SessionToken>,
+}
+
+/// Upgrade of the segments of the local shards to the current storage format
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentUpgradeRequest {
+    /// Pause after rewriting each segment, in milliseconds, to leave IO to other workloads.
+    /// Default: no pause
+    #[serde(default)]
+    pub throttle_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, PartialEq, Eq, Copy, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentUpgradeStatus {
+    /// No upgrade was started since the collection was loaded
+    #[default]
+    Idle,
+    /// Outdated segments are being rewritten
+    Running,
+    /// Upgrade was stopped, starting it again continues with the remaining segments
+    Stopped,
+    /// All segments are in the current format
+    Done,
+    /// Rewriting a segment failed
+    Failed,
+}
+
+/// Progress of the upgrade of the segments of the local shards to the current storage format
+#[derive(Debug, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentUpgradeProgress {
+    pub status: SegmentUpgradeStatus,
+    /// Number of outdated segments when the upgrade was started
+    pub total: usize,
+    /// Number of segments rewritten in the current format so far
+    pub upgraded: usize,
+    /// Number of segments still in an outdated format
+    pub remaining: usize,
+    /// Error of the failed upgrade
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of the compaction of the WAL of a local shard
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardWalCompaction {
+    pub shard_id: ShardId,
+    /// Entries of the WAL up to this operation were truncated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncated_until: Option<SeqNumberType>,
+    /// Why the WAL of the shard was not compacted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+impl ShardWalCompaction {
+    pub fn skipped(shard_id: ShardId, reason: impl Into<String>) -> Self {
+        Self {
+            shard_id,
+            truncated_until: None,
+            skipped: Some(reason.into()),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -345,6 +510,9 @@ pub struct QueryScrollRequestInternal {
 
     /// Order the records by a payload field.
     pub order_by: Option<OrderByInterface>,
+
+    /// Pick a random sample of the records, instead of ordering them.
+    pub sample: Option<Sample>,
 }
 
 impl ScrollRequestInternal {
@@ -384,6 +552,163 @@ pub struct ScrollResult {
     pub next_page_offset: Option<PointIdType>,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SampleRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub sample_request: SampleRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Sample request - pick random points which match the given condition, to explore the data of
+/// a collection without exporting its vectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SampleRequestInternal {
+    /// Number of points to sample. Default: 10
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: Option<usize>,
+
+    /// Sample only points which satisfy these conditions. If not provided - all points.
+    #[validate]
+    pub filter: Option<Filter>,
+
+    /// Select which payload to return with the response. Default is true.
+    pub with_payload: Option<WithPayloadInterface>,
+
+    /// Project the vectors of the sampled points to 2D coordinates. If not provided - no projection.
+    pub projection: Option<SampleProjection>,
+}
+
+impl SampleRequestInternal {
+    pub const DEFAULT_LIMIT: usize = 10;
+}
+
+/// Projection of the vectors of sampled points to 2D coordinates, with PCA
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SampleProjection {
+    /// Name of the dense vector to project. If not provided - the default vector is used.
+    pub using: Option<String>,
+}
+
+/// Result of the sample request
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SampleResult {
+    /// Randomly picked points
+    pub points: Vec<SampledPoint>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SampledPoint {
+    pub id: PointIdType,
+    /// Payload - values assigned to the point
+    pub payload: Option<Payload>,
+    /// Shard Key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Coordinates of the point in the 2D projection of the sample, if requested.
+    /// Not provided for points without the projected vector.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projection: Option<[f32; 2]>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub score_request: ScoreRequestInternal,
+    /// Specify in which shards to look for the referenced points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Score request - compute the exact scores of the query against the given vectors with the
+/// distance of the vector, without searching the index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreRequestInternal {
+    /// Vector to score the targets against, or the ID of the point to take it from
+    #[validate]
+    pub query: api::rest::VectorInput,
+
+    /// Vectors to score, or the IDs of the points to take them from
+    #[validate]
+    #[validate(length(min = 1, max = 1000))]
+    pub targets: Vec<api::rest::VectorInput>,
+
+    /// Name of the vector whose distance is used, and to take the vectors of the points from.
+    /// If not provided - the default vector is used.
+    pub using: Option<String>,
+}
+
+/// Result of the score request
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreResult {
+    /// Scores of the targets, in the order of the request
+    pub scores: Vec<ScoreType>,
+}
+
+pub const DEFAULT_POINT_CHANGES_LIMIT: usize = 100;
+
+/// Position in the WAL of each local shard, changes are read starting from it
+pub type ChangesCursor = HashMap<ShardId, SeqNumberType>;
+
+/// Point changes request - read points created, updated or deleted since the given cursor
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct PointChangesRequest {
+    /// Cursor returned as `next_cursor` by the previous request.
+    /// If not provided - no changes are returned, only the cursor pointing to the current state.
+    pub cursor: Option<ChangesCursor>,
+
+    /// Max number of changed points to return per shard. Default: 100
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+
+    /// Select which payload to return with the response. Default is true.
+    pub with_payload: Option<WithPayloadInterface>,
+
+    /// Options for specifying which vectors to include into response. Default is false.
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: WithVector,
+}
+
+/// Change of a single point
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PointChange {
+    pub id: PointIdType,
+    /// Shard the point belongs to
+    pub shard_id: ShardId,
+    /// Shard key of the shard, if the collection uses custom sharding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Number of the last operation, which changed this point
+    pub version: SeqNumberType,
+    /// Current state of the point, `null` if the point was deleted
+    pub point: Option<api::rest::Record>,
+}
+
+/// Result of the point changes request
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PointChangesResult {
+    /// Changed points, ordered by version within each shard
+    pub changes: Vec<PointChange>,
+    /// Cursor which should be used to retrieve next changes
+    pub next_cursor: ChangesCursor,
+    /// Peer which read the changes. The cursor is only valid for this peer.
+    pub peer_id: PeerId,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct SearchRequest {
@@ -393,6 +718,9 @@ pub struct SearchRequest {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Return the work done to execute the request: vectors scored, filter checks and how the segments were searched. Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_stats: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -436,6 +764,9 @@ pub struct SearchGroupsRequest {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Return the work done to execute the request: vectors scored, filter checks and how the segments were searched. Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_stats: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
@@ -458,6 +789,10 @@ pub struct PointRequestInternal {
     /// Options for specifying which vectors to include into response. Default is false.
     #[serde(default, alias = "with_vectors")]
     pub with_vector: WithVector,
+    /// Return points as they were at the given version instead of their latest state.
+    /// Requires point history to be enabled for the collection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of_version: Option<SeqNumberType>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
@@ -522,6 +857,9 @@ pub struct RecommendRequest {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Return the work done to execute the request: vectors scored, filter checks and how the segments were searched. Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_stats: Option<bool>,
 }
 
 /// Recommendation request.
@@ -586,6 +924,11 @@ pub struct RecommendRequestInternal {
     /// Note: the other collection should have the same vector size as the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// If true, examples referencing points which don't exist are skipped, instead of failing
+    /// the request. Default is false.
+    #[serde(default)]
+    pub skip_missing: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
@@ -604,6 +947,9 @@ pub struct RecommendGroupsRequest {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Return the work done to execute the request: vectors scored, filter checks and how the segments were searched. Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_stats: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
@@ -676,6 +1022,9 @@ pub struct DiscoverRequest {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Return the work done to execute the request: vectors scored, filter checks and how the segments were searched. Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_stats: Option<bool>,
 }
 
 /// Use context and a target to find the most similar points, constrained by the context.
@@ -754,6 +1103,23 @@ pub struct PointGroup {
     /// Record that has been looked up using the group id
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lookup: Option<api::rest::Record>,
+    /// Best hit of the group along with its neighbors, ordered by the ordinal field
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub neighbors: Option<Vec<api::rest::Record>>,
+    /// Aggregates over the hits of the group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregates: Option<GroupAggregates>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct GroupAggregates {
+    /// Number of points in the group matching the filter
+    pub count: usize,
+    /// Score of the best hit in the group
+    pub best_score: ScoreType,
+    /// Mean of each requested payload field, over the points of the group with numeric values of
+    /// it. Fields without numeric values in any point are omitted.
+    pub mean: HashMap<String, f64>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -807,6 +1173,14 @@ pub enum CollectionError {
     NotFound { what: String },
     #[error("No point with id {missed_point_id} found")]
     PointNotFound { missed_point_id: PointIdType },
+    #[error(
+        "No points with ids [{}] found in collection `{lookup_collection}`",
+        .missed_point_ids.iter().join(", ")
+    )]
+    ReferencedPointsNotFound {
+        missed_point_ids: Vec<PointIdType>,
+        lookup_collection: String,
+    },
     #[error("Service internal error: {error}")]
     ServiceError {
         error: String,
@@ -836,6 +1210,12 @@ pub enum CollectionError {
     PreConditionFailed { description: String },
     #[error("Object Store error: {what}")]
     ObjectStoreError { what: String },
+    #[error("Wrong input: {description}")]
+    VectorDimensionMismatch { description: String },
+    #[error("Wrong input: {description}")]
+    PayloadIndexMissing { description: String },
+    #[error("Service internal error: {description}")]
+    ShardUnavailable { description: String },
 }
 
 impl CollectionError {
@@ -910,6 +1290,12 @@ impl CollectionError {
         }
     }
 
+    pub fn shard_unavailable(description: impl Into<String>) -> CollectionError {
+        CollectionError::ShardUnavailable {
+            description: description.into(),
+        }
+    }
+
     /// Returns true if the error is transient and the operation can be retried.
     /// Returns false if the error is not transient and the operation should fail on all replicas.
     pub fn is_transient(&self) -> bool {
@@ -920,15 +1306,19 @@ impl CollectionError {
             Self::Cancelled { .. } => true,
             Self::OutOfMemory { .. } => true,
             Self::PreConditionFailed { .. } => true,
+            Self::ShardUnavailable { .. } => true,
             // Not transient
             Self::BadInput { .. } => false,
             Self::NotFound { .. } => false,
             Self::PointNotFound { .. } => false,
+            Self::ReferencedPointsNotFound { .. } => false,
             Self::BadRequest { .. } => false,
             Self::BadShardSelection { .. } => false,
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
             Self::ObjectStoreError { .. } => false,
+            Self::VectorDimensionMismatch { .. } => false,
+            Self::PayloadIndexMissing { .. } => false,
         }
     }
 }
@@ -954,7 +1344,7 @@ impl From<String> for CollectionError {
 impl From<OperationError> for CollectionError {
     fn from(err: OperationError) -> Self {
         match err {
-            OperationError::WrongVectorDimension { .. } => Self::BadInput {
+            OperationError::WrongVectorDimension { .. } => Self::VectorDimensionMismatch {
                 description: format!("{err}"),
             },
             OperationError::VectorNameNotExists { .. } => Self::BadInput {
@@ -997,7 +1387,9 @@ impl From<OperationError> for CollectionError {
                 description: "Conversion between multi and regular vectors failed".to_string(),
             },
             OperationError::WrongPayloadKey { description } => Self::BadInput { description },
-            OperationError::MissingRangeIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingRangeIndexForOrderBy { .. } => Self::PayloadIndexMissing {
+                description: format!("{err}"),
+            },
         }
     }
 }
@@ -1076,6 +1468,26 @@ impl From<InvalidUri> for CollectionError {
 
 impl From<tonic::Status> for CollectionError {
     fn from(err: tonic::Status) -> Self {
+        // Errors of other peers keep their specific code
+        let error_code = err
+            .metadata()
+            .get(ERROR_CODE_METADATA_KEY)
+            .and_then(|code| code.to_str().ok())
+            .and_then(ErrorCode::parse);
+        let description = err.message().to_string();
+        match error_code {
+            Some(ErrorCode::VectorDimMismatch) => {
+                return CollectionError::VectorDimensionMismatch { description }
+            }
+            Some(ErrorCode::PayloadIndexMissing) => {
+                return CollectionError::PayloadIndexMissing { description }
+            }
+            Some(ErrorCode::ShardUnavailable) => {
+                return CollectionError::ShardUnavailable { description }
+            }
+            _ => {}
+        }
+
         match err.code() {
             tonic::Code::InvalidArgument => CollectionError::BadInput {
                 description: format!("InvalidArgument: {err}"),
@@ -1264,6 +1676,32 @@ pub struct VectorParams {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multivector_config: Option<MultiVectorConfig>,
+
+    /// Checks and transformations applied to the vectors when they are written.
+    /// If none - vectors are stored as they are.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest: Option<VectorIngestPolicy>,
+}
+
+/// Policy applied to dense vectors when points are upserted or their vectors are updated.
+///
+/// With a policy, the dimensionality of the vectors is always checked, and the error names the
+/// point and the vector which have the wrong size.
+#[derive(Debug, Default, Hash, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorIngestPolicy {
+    /// If true, vectors are normalized to unit length (L2) before they are stored.
+    /// Default: false
+    #[serde(default)]
+    pub normalize: bool,
+    /// If true, vectors with NaN or infinite values are rejected.
+    /// Default: false
+    #[serde(default)]
+    pub reject_non_finite: bool,
+    /// If true, vectors with all values equal to zero are rejected.
+    /// Default: false
+    #[serde(default)]
+    pub reject_zero: bool,
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1321,6 +1759,21 @@ impl Anonymize for SparseVectorParams {
     }
 }
 
+/// Params of a hybrid vector: a dense and a sparse vector of the same content, which are
+/// queried together
+#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct HybridVectorParams {
+    /// Params of the dense vector, e.g. of the embeddings by a dense model
+    #[validate]
+    pub dense: VectorParams,
+    /// Params of the sparse vector, e.g. of the embeddings by a sparse model.
+    /// Default: no modifier and the index params of the collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub sparse: Option<SparseVectorParams>,
+}
+
 /// Configuration for sparse inverted index.
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
@@ -1641,6 +2094,10 @@ pub struct VectorParamsDiff {
     /// If true, vectors are served from disk, improving RAM usage at the cost of latency
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Update the policy applied to the vectors when they are written. If none - it is left
+    /// unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest: Option<VectorIngestPolicy>,
 }
 
 /// Vector update params for multiple vectors
@@ -1723,6 +2180,24 @@ pub struct CollectionsAliasesResponse {
     pub aliases: Vec<AliasDescription>,
 }
 
+/// Change of the collection an alias points to
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct AliasChange {
+    /// Collection the alias points to since the change, none if the alias was removed
+    pub collection_name: Option<String>,
+    /// Time of the change. Unknown for aliases removed together with their collection
+    pub changed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AliasHistoryResponse {
+    pub alias_name: String,
+    /// Changes of the collection the alias points to, oldest first
+    pub changes: Vec<AliasChange>,
+}
+
 #[derive(Clone, Debug, Deserialize, Default, Copy, PartialEq)]
 pub enum NodeType {
     /// Regular node, participates in the cluster
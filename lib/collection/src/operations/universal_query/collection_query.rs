@@ -1,6 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
-use api::rest::{LookupLocation, RecommendStrategy};
+use api::rest::{FilterTemplateReference, LookupLocation, RecommendStrategy, Rerank, TextBudget};
 use common::types::ScoreType;
 use itertools::Itertools;
 use segment::data_types::order_by::OrderBy;
@@ -13,9 +13,12 @@ use segment::types::{
 };
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 
-use super::shard_query::{Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest};
+use super::shard_query::{Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::common::fetch_vectors::ReferencedVectors;
+use crate::config::VectorRouting;
+use crate::operations::hybrid_vectors::HybridVector;
 use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::recommendations::avg_vector_for_recommendation;
 
@@ -26,6 +29,12 @@ pub struct CollectionQueryRequest {
     pub query: Option<Query>,
     pub using: String,
     pub filter: Option<Filter>,
+    /// Apply the filter to prefetches as well, otherwise it is only applied when rescoring
+    pub propagate_filter: bool,
+    /// Only score these points, in the request and all of its prefetches
+    pub ids: Option<HashSet<PointIdType>>,
+    /// Filter template of the collection to apply to the request and all of its prefetches
+    pub filter_template: Option<FilterTemplateReference>,
     pub score_threshold: Option<ScoreType>,
     pub limit: usize,
     pub offset: usize,
@@ -33,7 +42,17 @@ pub struct CollectionQueryRequest {
     pub params: Option<SearchParams>,
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
+    /// Return the shard and the replica each point was found in
+    pub with_routing: bool,
+    /// Return the work done to execute the query
+    pub with_stats: bool,
     pub lookup_from: Option<LookupLocation>,
+    /// Keep only the first results whose texts fit in this budget
+    pub budget: Option<TextBudget>,
+    /// Rerank the top results with the external reranker
+    pub rerank: Option<Rerank>,
+    /// Skip the examples referencing points which don't exist, instead of failing
+    pub skip_missing: bool,
 }
 
 impl CollectionQueryRequest {
@@ -44,6 +63,8 @@ impl CollectionQueryRequest {
     pub const DEFAULT_WITH_VECTOR: WithVector = WithVector::Bool(false);
 
     pub const DEFAULT_WITH_PAYLOAD: WithPayloadInterface = WithPayloadInterface::Bool(false);
+
+    pub const DEFAULT_PROPAGATE_FILTER: bool = true;
 }
 
 /// Lightweight representation of a query request to implement the [RetrieveRequest] trait.
@@ -59,11 +80,26 @@ pub enum Query {
     /// Score points against some vector(s)
     Vector(VectorQuery<VectorInput>),
 
-    /// Reciprocal rank fusion
+    /// Fusion of the results of prefetches
     Fusion(Fusion),
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Random sample of points
+    Sample(Sample),
+
+    /// Fusion of the nearest points to both vectors of a hybrid vector
+    Hybrid(HybridQuery),
+}
+
+/// Query vectors of a hybrid vector of the collection
+#[derive(Debug)]
+pub struct HybridQuery {
+    pub dense: VectorInput,
+    pub sparse: VectorInput,
+    /// Fusion of the nearest points to both vectors, RRF if not set
+    pub fusion: Option<Fusion>,
 }
 
 impl Query {
@@ -77,6 +113,8 @@ impl Query {
         let scoring_query = match self {
             Query::Vector(vector_query) => {
                 let query_enum = vector_query
+                    // Compute the sums of vectors, reporting invalid ones
+                    .resolve_sums(ids_to_vectors, lookup_vector_name, lookup_collection)?
                     // Homogenize the input into raw vectors
                     .ids_into_vectors(ids_to_vectors, lookup_vector_name, lookup_collection)
                     // Turn into QueryEnum
@@ -85,6 +123,12 @@ impl Query {
             }
             Query::Fusion(fusion) => ScoringQuery::Fusion(fusion),
             Query::OrderBy(order_by) => ScoringQuery::OrderBy(order_by),
+            Query::Sample(sample) => ScoringQuery::Sample(sample),
+            Query::Hybrid(_) => {
+                return Err(CollectionError::service_error(
+                    "Hybrid query must be expanded into prefetches before it is performed",
+                ));
+            }
         };
 
         Ok(scoring_query)
@@ -94,13 +138,33 @@ impl Query {
 pub enum VectorInput {
     Id(PointIdType),
     Vector(Vector),
+    /// Weighted sum of vectors, computed once the referenced vectors are resolved
+    Sum(Vec<WeightedVectorInput>),
+}
+
+#[derive(Debug)]
+pub struct WeightedVectorInput {
+    pub vector: VectorInput,
+    pub weight: f32,
 }
 
 impl VectorInput {
     pub fn as_id(&self) -> Option<&PointIdType> {
         match self {
             VectorInput::Id(id) => Some(id),
-            VectorInput::Vector(_) => None,
+            VectorInput::Vector(_) | VectorInput::Sum(_) => None,
+        }
+    }
+
+    /// IDs of all the points to take vectors from, including the ones in sums
+    pub fn referenced_ids(&self) -> Vec<&PointIdType> {
+        match self {
+            VectorInput::Id(id) => vec![id],
+            VectorInput::Vector(_) => vec![],
+            VectorInput::Sum(terms) => terms
+                .iter()
+                .flat_map(|term| term.vector.referenced_ids())
+                .collect(),
         }
     }
 }
@@ -128,6 +192,85 @@ impl<T> VectorQuery<T> {
 }
 
 impl VectorQuery<VectorInput> {
+    /// Replaces all sums of vectors with the resulting vectors, using the provided
+    /// [ReferencedVectors] to look up the vectors of referenced points.
+    fn resolve_sums(
+        self,
+        ids_to_vectors: &ReferencedVectors,
+        lookup_vector_name: &str,
+        lookup_collection: Option<&String>,
+    ) -> CollectionResult<Self> {
+        let resolve = |input: VectorInput| -> CollectionResult<_> {
+            match input {
+                VectorInput::Sum(terms) => ids_to_vectors
+                    .resolve_sum(lookup_collection, lookup_vector_name, terms)
+                    .map(VectorInput::Vector),
+                input => Ok(input),
+            }
+        };
+
+        let resolve_pair = |pair: ContextPair<VectorInput>| -> CollectionResult<_> {
+            Ok(ContextPair {
+                positive: resolve(pair.positive)?,
+                negative: resolve(pair.negative)?,
+            })
+        };
+
+        let query = match self {
+            VectorQuery::Nearest(input) => VectorQuery::Nearest(resolve(input)?),
+            VectorQuery::RecommendAverageVector(reco) => {
+                VectorQuery::RecommendAverageVector(RecoQuery::new(
+                    reco.positives.into_iter().map(resolve).try_collect()?,
+                    reco.negatives.into_iter().map(resolve).try_collect()?,
+                ))
+            }
+            VectorQuery::RecommendBestScore(reco) => {
+                VectorQuery::RecommendBestScore(RecoQuery::new(
+                    reco.positives.into_iter().map(resolve).try_collect()?,
+                    reco.negatives.into_iter().map(resolve).try_collect()?,
+                ))
+            }
+            VectorQuery::Discover(discover) => VectorQuery::Discover(DiscoveryQuery {
+                target: resolve(discover.target)?,
+                pairs: discover.pairs.into_iter().map(resolve_pair).try_collect()?,
+            }),
+            VectorQuery::Context(context) => VectorQuery::Context(ContextQuery {
+                pairs: context.pairs.into_iter().map(resolve_pair).try_collect()?,
+            }),
+        };
+
+        Ok(query)
+    }
+
+    /// Removes the examples which reference points missing from the [ReferencedVectors].
+    ///
+    /// The target of a nearest or discovery query is kept, as the query is meaningless without it.
+    fn skip_missing_examples(
+        &mut self,
+        ids_to_vectors: &ReferencedVectors,
+        lookup_collection: Option<&String>,
+    ) {
+        let is_fetched = |input: &VectorInput| {
+            input
+                .referenced_ids()
+                .into_iter()
+                .all(|id| ids_to_vectors.get(&lookup_collection, *id).is_some())
+        };
+        let is_pair_fetched = |pair: &ContextPair<VectorInput>| {
+            is_fetched(&pair.positive) && is_fetched(&pair.negative)
+        };
+
+        match self {
+            VectorQuery::Nearest(_) => {}
+            VectorQuery::RecommendAverageVector(reco) | VectorQuery::RecommendBestScore(reco) => {
+                reco.positives.retain(is_fetched);
+                reco.negatives.retain(is_fetched);
+            }
+            VectorQuery::Discover(discover) => discover.pairs.retain(is_pair_fetched),
+            VectorQuery::Context(context) => context.pairs.retain(is_pair_fetched),
+        }
+    }
+
     /// Turns all [VectorInput]s into [Vector]s, using the provided [ReferencedVectors] to look up the vectors.
     ///
     /// Will panic if the ids are not found in the [ReferencedVectors].
@@ -277,6 +420,8 @@ pub struct CollectionPrefetch {
     /// Search params for when there is no prefetch
     pub params: Option<SearchParams>,
     pub lookup_from: Option<LookupLocation>,
+    /// Shards to perform this prefetch on, instead of the shards of the request
+    pub shard_selection: Option<ShardSelectorInternal>,
 }
 
 /// Exclude the referenced ids by editing the filter.
@@ -291,7 +436,125 @@ fn exclude_referenced_ids(ids: Vec<ExtendedPointId>, filter: Option<Filter>) ->
     Some(id_filter.merge_owned(filter.unwrap_or_default()))
 }
 
+/// Replaces a hybrid query by the fusion of nearest queries on both vectors of the hybrid vector
+/// named by `using`. The parameters of the query are used for the nearest queries.
+fn expand_hybrid_query(
+    query: &mut Option<Query>,
+    using: &mut String,
+    prefetch: &mut Vec<CollectionPrefetch>,
+    limit: usize,
+    params: Option<&SearchParams>,
+    lookup_from: Option<&LookupLocation>,
+    hybrid_vectors: Option<&BTreeMap<String, HybridVector>>,
+) -> CollectionResult<()> {
+    let hybrid = match query.take() {
+        Some(Query::Hybrid(hybrid)) => hybrid,
+        other => {
+            *query = other;
+            return Ok(());
+        }
+    };
+
+    if !prefetch.is_empty() {
+        return Err(CollectionError::bad_request(
+            "Hybrid queries can't be combined with prefetches.",
+        ));
+    }
+    if lookup_from.is_some_and(|lookup_from| lookup_from.vector.is_some()) {
+        return Err(CollectionError::bad_request(
+            "Hybrid queries look up the vectors of the pair, 'lookup_from.vector' can't be set.",
+        ));
+    }
+    let pair = hybrid_vectors
+        .and_then(|hybrid_vectors| hybrid_vectors.get(using.as_str()))
+        .ok_or_else(|| {
+            CollectionError::bad_request(format!(
+                "Hybrid vector {using} doesn't exist in the collection"
+            ))
+        })?;
+
+    let HybridQuery {
+        dense,
+        sparse,
+        fusion,
+    } = hybrid;
+    let nearest = |input, using: &str| CollectionPrefetch {
+        prefetch: vec![],
+        query: Some(Query::Vector(VectorQuery::Nearest(input))),
+        using: using.to_string(),
+        filter: None,
+        score_threshold: None,
+        limit,
+        params: params.cloned(),
+        lookup_from: lookup_from.cloned(),
+        shard_selection: None,
+    };
+
+    *prefetch = vec![nearest(dense, &pair.dense), nearest(sparse, &pair.sparse)];
+    *query = Some(Query::Fusion(fusion.unwrap_or(Fusion::Rrf)));
+    *using = DEFAULT_VECTOR_NAME.to_string();
+    Ok(())
+}
+
 impl CollectionPrefetch {
+    /// Searches with the routed vector if no vector is specified.
+    /// The routing value is taken from the filter of this prefetch, or else of its parent.
+    fn route_vectors<'a>(&mut self, routing: &'a VectorRouting, parent_vector: Option<&'a str>) {
+        let vector = routing.vector_for(self.filter.as_ref()).or(parent_vector);
+
+        if let (Some(vector), Some(Query::Vector(_))) = (vector, &self.query) {
+            if self.using == DEFAULT_VECTOR_NAME {
+                self.using = vector.to_string();
+            }
+        }
+
+        for prefetch in &mut self.prefetch {
+            prefetch.route_vectors(routing, vector);
+        }
+    }
+
+    /// Replaces hybrid queries of this prefetch and its nested prefetches, see
+    /// [`CollectionQueryRequest::expand_hybrid_queries`]
+    fn expand_hybrid_queries(
+        &mut self,
+        hybrid_vectors: Option<&BTreeMap<String, HybridVector>>,
+    ) -> CollectionResult<()> {
+        expand_hybrid_query(
+            &mut self.query,
+            &mut self.using,
+            &mut self.prefetch,
+            self.limit,
+            self.params.as_ref(),
+            self.lookup_from.as_ref(),
+            hybrid_vectors,
+        )?;
+
+        for prefetch in &mut self.prefetch {
+            prefetch.expand_hybrid_queries(hybrid_vectors)?;
+        }
+        Ok(())
+    }
+
+    /// Restricts this prefetch and its nested prefetches to the points matching the filter
+    fn add_filter(&mut self, filter: &Filter) {
+        self.filter = Some(filter.merge(&self.filter.take().unwrap_or_default()));
+
+        for prefetch in &mut self.prefetch {
+            prefetch.add_filter(filter);
+        }
+    }
+
+    fn skip_missing_examples(&mut self, ids_to_vectors: &ReferencedVectors) {
+        let lookup_collection = self.get_lookup_collection().cloned();
+        if let Some(Query::Vector(vector_query)) = &mut self.query {
+            vector_query.skip_missing_examples(ids_to_vectors, lookup_collection.as_ref());
+        }
+
+        for prefetch in &mut self.prefetch {
+            prefetch.skip_missing_examples(ids_to_vectors);
+        }
+    }
+
     fn get_lookup_collection(&self) -> Option<&String> {
         self.lookup_from.as_ref().map(|x| &x.collection)
     }
@@ -328,6 +591,10 @@ impl CollectionPrefetch {
         refs
     }
 
+    fn has_shard_selection(&self) -> bool {
+        self.shard_selection.is_some() || self.prefetch.iter().any(Self::has_shard_selection)
+    }
+
     fn try_into_shard_prefetch(
         self,
         ids_to_vectors: &ReferencedVectors,
@@ -394,6 +661,82 @@ impl CollectionPrefetch {
 }
 
 impl CollectionQueryRequest {
+    /// Searches with the routed vector if no vector is specified, in this request and its
+    /// prefetches. The routing value is taken from the filter, see [`VectorRouting`].
+    pub fn route_vectors(&mut self, routing: &VectorRouting) {
+        let vector = routing.vector_for(self.filter.as_ref());
+
+        if let (Some(vector), Some(Query::Vector(_))) = (vector, &self.query) {
+            if self.using == DEFAULT_VECTOR_NAME {
+                self.using = vector.to_string();
+            }
+        }
+
+        // Prefetches are only restricted to the routing value if the filter is propagated
+        let parent_vector = vector.filter(|_| self.propagate_filter);
+        for prefetch in &mut self.prefetch {
+            prefetch.route_vectors(routing, parent_vector);
+        }
+    }
+
+    /// Replaces hybrid queries, in this request and its prefetches, by the fusion of nearest
+    /// queries on the dense and the sparse vector of the hybrid vector they use.
+    pub fn expand_hybrid_queries(
+        &mut self,
+        hybrid_vectors: Option<&BTreeMap<String, HybridVector>>,
+    ) -> CollectionResult<()> {
+        // Fusion returns the first results of the request, so each vector needs to find as many
+        expand_hybrid_query(
+            &mut self.query,
+            &mut self.using,
+            &mut self.prefetch,
+            self.limit + self.offset,
+            self.params.as_ref(),
+            self.lookup_from.as_ref(),
+            hybrid_vectors,
+        )?;
+
+        for prefetch in &mut self.prefetch {
+            prefetch.expand_hybrid_queries(hybrid_vectors)?;
+        }
+        Ok(())
+    }
+
+    /// Restricts all searches of this request, including the ones of prefetches, to the points
+    /// given in `ids`. Searches go through the given ids directly, instead of checking them for
+    /// every candidate, as long as there are fewer ids than points matching the rest of the filter.
+    pub fn restrict_to_ids(&mut self) {
+        if let Some(ids) = self.ids.take() {
+            self.add_filter(Filter::new_must(Condition::HasId(HasIdCondition::from(
+                ids,
+            ))));
+        }
+    }
+
+    /// Restricts this request to the points matching the filter. Prefetches are restricted as
+    /// well, also if the filter of the request is not propagated to them.
+    pub fn add_filter(&mut self, filter: Filter) {
+        if !self.propagate_filter {
+            for prefetch in &mut self.prefetch {
+                prefetch.add_filter(&filter);
+            }
+        }
+        self.filter = Some(filter.merge_owned(self.filter.take().unwrap_or_default()));
+    }
+
+    /// Removes the examples which reference points that were not found, in this request and its
+    /// prefetches. Each query looks its points up in its own lookup collection.
+    pub fn skip_missing_examples(&mut self, ids_to_vectors: &ReferencedVectors) {
+        let lookup_collection = self.get_lookup_collection().cloned();
+        if let Some(Query::Vector(vector_query)) = &mut self.query {
+            vector_query.skip_missing_examples(ids_to_vectors, lookup_collection.as_ref());
+        }
+
+        for prefetch in &mut self.prefetch {
+            prefetch.skip_missing_examples(ids_to_vectors);
+        }
+    }
+
     fn get_lookup_collection(&self) -> Option<&String> {
         self.lookup_from.as_ref().map(|x| &x.collection)
     }
@@ -475,6 +818,9 @@ impl CollectionQueryRequest {
             prefetches,
             query,
             filter,
+            propagate_filter: self.propagate_filter,
+            single_shard: false,
+            defer_fusion_payload: true,
             score_threshold: self.score_threshold,
             limit: self.limit,
             offset: self.offset,
@@ -484,6 +830,63 @@ impl CollectionQueryRequest {
         })
     }
 
+    /// Shards to perform each root prefetch on, if any of them overrides the shards of the request.
+    ///
+    /// Only the prefetches of a fusion are merged at collection level, so they are the only ones
+    /// which can be performed on different shards.
+    pub fn prefetch_shard_selection(
+        &self,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Option<Vec<ShardSelectorInternal>>> {
+        let nested_selection = self
+            .prefetch
+            .iter()
+            .flat_map(|prefetch| prefetch.prefetch.iter())
+            .any(CollectionPrefetch::has_shard_selection);
+
+        if nested_selection {
+            return Err(CollectionError::bad_request(
+                "Shard key can only be selected in root prefetches.",
+            ));
+        }
+
+        let overrides_selection = self.prefetch.iter().any(|prefetch| {
+            prefetch
+                .shard_selection
+                .as_ref()
+                .is_some_and(|selection| selection != shard_selection)
+        });
+
+        if !overrides_selection {
+            return Ok(None);
+        }
+
+        if !matches!(self.query, Some(Query::Fusion(_))) {
+            return Err(CollectionError::bad_request(
+                "Shard key can only be selected in prefetches of a fusion query.",
+            ));
+        }
+
+        if shard_selection.is_shard_id() {
+            return Err(CollectionError::bad_request(
+                "Can't select shard key of a prefetch when querying a specific shard.",
+            ));
+        }
+
+        let prefetch_selection = self
+            .prefetch
+            .iter()
+            .map(|prefetch| {
+                prefetch
+                    .shard_selection
+                    .clone()
+                    .unwrap_or_else(|| shard_selection.clone())
+            })
+            .collect();
+
+        Ok(Some(prefetch_selection))
+    }
+
     pub fn validation(
         query: &Option<Query>,
         using: &String,
@@ -505,6 +908,11 @@ impl CollectionQueryRequest {
                         "Can't use score_threshold with an order_by query.",
                     ));
                 }
+                Some(Query::Sample(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "Can't use score_threshold with a sample query.",
+                    ));
+                }
                 None => {
                     return Err(CollectionError::bad_request(
                         "A query is needed to use the score_threshold. Can't have score_threshold without defining a query.",
@@ -539,13 +947,21 @@ mod from_rest {
                 query,
                 using,
                 filter,
+                propagate_filter,
+                ids,
+                filter_template,
                 score_threshold,
                 params,
                 limit,
                 offset,
                 with_vector,
                 with_payload,
+                with_routing,
+                with_stats,
                 lookup_from,
+                budget,
+                rerank,
+                skip_missing,
             } = value;
 
             Self {
@@ -553,13 +969,21 @@ mod from_rest {
                 query: query.map(From::from),
                 using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
                 filter,
+                propagate_filter: propagate_filter.unwrap_or(Self::DEFAULT_PROPAGATE_FILTER),
+                ids: ids.map(|ids| ids.into_iter().collect()),
+                filter_template,
                 score_threshold,
                 limit: limit.unwrap_or(Self::DEFAULT_LIMIT),
                 offset: offset.unwrap_or(Self::DEFAULT_OFFSET),
                 params,
                 with_vector: with_vector.unwrap_or(Self::DEFAULT_WITH_VECTOR),
                 with_payload: with_payload.unwrap_or(Self::DEFAULT_WITH_PAYLOAD),
+                with_routing: with_routing.unwrap_or_default(),
+                with_stats: with_stats.unwrap_or_default(),
                 lookup_from: lookup_from.map(LookupLocation::from),
+                budget,
+                rerank,
+                skip_missing: skip_missing.unwrap_or_default(),
             }
         }
     }
@@ -575,6 +999,7 @@ mod from_rest {
                 params,
                 limit,
                 lookup_from,
+                shard_key,
             } = value;
 
             Self {
@@ -586,6 +1011,7 @@ mod from_rest {
                 limit: limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
                 params,
                 lookup_from,
+                shard_selection: shard_key.map(ShardSelectorInternal::from),
             }
         }
     }
@@ -607,6 +1033,23 @@ mod from_rest {
                 rest::Query::Context(context) => Query::Vector(From::from(context.context)),
                 rest::Query::OrderBy(order_by) => Query::OrderBy(OrderBy::from(order_by.order_by)),
                 rest::Query::Fusion(fusion) => Query::Fusion(Fusion::from(fusion.fusion)),
+                rest::Query::Hybrid(hybrid) => Query::Hybrid(HybridQuery::from(hybrid.hybrid)),
+            }
+        }
+    }
+
+    impl From<rest::HybridInput> for HybridQuery {
+        fn from(value: rest::HybridInput) -> Self {
+            let rest::HybridInput {
+                dense,
+                sparse,
+                fusion,
+            } = value;
+
+            HybridQuery {
+                dense: VectorInput::from(dense),
+                sparse: VectorInput::from(sparse),
+                fusion: fusion.map(Fusion::from),
             }
         }
     }
@@ -671,6 +1114,15 @@ mod from_rest {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(MultiDenseVectorInternal::new_unchecked(multi_dense)),
                 ),
+                rest::VectorInput::Sum(sum) => VectorInput::Sum(
+                    sum.sum
+                        .into_iter()
+                        .map(|term| WeightedVectorInput {
+                            vector: VectorInput::from(term.vector),
+                            weight: term.weight.unwrap_or(1.0),
+                        })
+                        .collect(),
+                ),
             }
         }
     }
@@ -689,6 +1141,7 @@ mod from_rest {
         fn from(value: rest::Fusion) -> Self {
             match value {
                 rest::Fusion::Rrf => Fusion::Rrf,
+                rest::Fusion::Dbsf => Fusion::Dbsf,
             }
         }
     }
@@ -720,8 +1173,27 @@ pub mod from_grpc {
                 shard_key_selector: _,
                 lookup_from,
                 timeout: _,
+                propagate_filter,
+                ids,
+                budget,
+                rerank,
+                skip_missing,
+                session_token: _,
+                filter_template,
+                with_routing,
+                with_stats,
             } = value;
 
+            let ids: Option<HashSet<_>> = if ids.is_empty() {
+                None
+            } else {
+                Some(
+                    ids.into_iter()
+                        .map(PointIdType::try_from)
+                        .collect::<Result<_, _>>()?,
+                )
+            };
+
             let request = CollectionQueryRequest {
                 prefetch: prefetch
                     .into_iter()
@@ -730,6 +1202,10 @@ pub mod from_grpc {
                 query: query.map(TryFrom::try_from).transpose()?,
                 using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
                 filter: filter.map(TryFrom::try_from).transpose()?,
+                propagate_filter: propagate_filter
+                    .unwrap_or(CollectionQueryRequest::DEFAULT_PROPAGATE_FILTER),
+                ids,
+                filter_template: filter_template.map(TryFrom::try_from).transpose()?,
                 score_threshold,
                 limit: limit
                     .map(|l| l as usize)
@@ -745,7 +1221,12 @@ pub mod from_grpc {
                     .map(TryFrom::try_from)
                     .transpose()?
                     .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
+                with_routing: with_routing.unwrap_or_default(),
+                with_stats: with_stats.unwrap_or_default(),
                 lookup_from: lookup_from.map(From::from),
+                budget: budget.map(TryFrom::try_from).transpose()?,
+                rerank: rerank.map(TryFrom::try_from).transpose()?,
+                skip_missing: skip_missing.unwrap_or_default(),
             };
             Ok(request)
         }
@@ -764,6 +1245,7 @@ pub mod from_grpc {
                 score_threshold,
                 limit,
                 lookup_from,
+                shard_key_selector,
             } = value;
 
             let collection_query = Self {
@@ -780,6 +1262,7 @@ pub mod from_grpc {
                     .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
                 params: params.map(From::from),
                 lookup_from: lookup_from.map(From::from),
+                shard_selection: shard_key_selector.map(ShardSelectorInternal::from),
             };
 
             Ok(collection_query)
@@ -805,12 +1288,36 @@ pub mod from_grpc {
                 Variant::Context(context) => Query::Vector(TryFrom::try_from(context)?),
                 Variant::OrderBy(order_by) => Query::OrderBy(OrderBy::try_from(order_by)?),
                 Variant::Fusion(fusion) => Query::Fusion(Fusion::try_from(fusion)?),
+                Variant::Hybrid(hybrid) => Query::Hybrid(HybridQuery::try_from(hybrid)?),
             };
 
             Ok(query)
         }
     }
 
+    impl TryFrom<grpc::HybridInput> for HybridQuery {
+        type Error = Status;
+
+        fn try_from(value: grpc::HybridInput) -> Result<Self, Self::Error> {
+            let grpc::HybridInput {
+                dense,
+                sparse,
+                fusion,
+            } = value;
+
+            let dense =
+                dense.ok_or_else(|| Status::invalid_argument("HybridInput dense is missing"))?;
+            let sparse =
+                sparse.ok_or_else(|| Status::invalid_argument("HybridInput sparse is missing"))?;
+
+            Ok(HybridQuery {
+                dense: VectorInput::try_from(dense)?,
+                sparse: VectorInput::try_from(sparse)?,
+                fusion: fusion.map(Fusion::try_from).transpose()?,
+            })
+        }
+    }
+
     impl TryFrom<grpc::RecommendInput> for VectorQuery<VectorInput> {
         type Error = Status;
 
@@ -897,6 +1404,29 @@ pub mod from_grpc {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(From::from(multi_dense)),
                 ),
+                Variant::Sum(sum) => {
+                    if sum.terms.is_empty() {
+                        return Err(Status::invalid_argument(
+                            "VectorSum must have at least one term",
+                        ));
+                    }
+                    VectorInput::Sum(
+                        sum.terms
+                            .into_iter()
+                            .map(|term| {
+                                let vector = term.vector.ok_or_else(|| {
+                                    Status::invalid_argument(
+                                        "WeightedVectorInput vector is missing",
+                                    )
+                                })?;
+                                Ok(WeightedVectorInput {
+                                    vector: VectorInput::try_from(vector)?,
+                                    weight: term.weight.unwrap_or(1.0),
+                                })
+                            })
+                            .collect::<Result<_, Status>>()?,
+                    )
+                }
             };
 
             Ok(vector_input)
@@ -934,3 +1464,155 @@ pub mod from_grpc {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use segment::json_path::path;
+    use segment::types::{FieldCondition, Match, ValueVariants};
+
+    use super::*;
+    use crate::operations::types::Record;
+
+    fn lang_filter(lang: &str) -> Option<Filter> {
+        Some(Filter::new_must(Condition::Field(
+            FieldCondition::new_match(
+                path("lang"),
+                Match::new_value(ValueVariants::Keyword(lang.to_string())),
+            ),
+        )))
+    }
+
+    fn nearest() -> Option<Query> {
+        Some(Query::Vector(VectorQuery::Nearest(VectorInput::Vector(
+            Vector::Dense(vec![1.0, 0.0]),
+        ))))
+    }
+
+    fn prefetch(using: &str, filter: Option<Filter>) -> CollectionPrefetch {
+        CollectionPrefetch {
+            prefetch: Vec::new(),
+            query: nearest(),
+            using: using.to_string(),
+            filter,
+            score_threshold: None,
+            limit: 10,
+            params: None,
+            lookup_from: None,
+            shard_selection: None,
+        }
+    }
+
+    #[test]
+    fn test_route_vectors() {
+        let routing = VectorRouting {
+            key: path("lang"),
+            vectors: [("en", "english"), ("de", "german")]
+                .into_iter()
+                .map(|(lang, vector)| (lang.to_string(), vector.to_string()))
+                .collect(),
+        };
+
+        let mut request = CollectionQueryRequest {
+            prefetch: vec![
+                prefetch(DEFAULT_VECTOR_NAME, None),
+                prefetch(DEFAULT_VECTOR_NAME, lang_filter("en")),
+                prefetch("custom", None),
+            ],
+            query: nearest(),
+            using: DEFAULT_VECTOR_NAME.to_string(),
+            filter: lang_filter("de"),
+            propagate_filter: true,
+            ids: None,
+            filter_template: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_vector: CollectionQueryRequest::DEFAULT_WITH_VECTOR,
+            with_payload: CollectionQueryRequest::DEFAULT_WITH_PAYLOAD,
+            with_routing: false,
+            with_stats: false,
+            lookup_from: None,
+            budget: None,
+            rerank: None,
+            skip_missing: false,
+        };
+
+        request.route_vectors(&routing);
+
+        assert_eq!(request.using, "german");
+        let prefetch_usings: Vec<_> = request.prefetch.iter().map(|p| p.using.as_str()).collect();
+        assert_eq!(prefetch_usings, vec!["german", "english", "custom"]);
+
+        // Unknown values and other conditions don't route
+        let mut unrouted = prefetch(DEFAULT_VECTOR_NAME, lang_filter("fr"));
+        unrouted.route_vectors(&routing, None);
+        assert_eq!(unrouted.using, DEFAULT_VECTOR_NAME);
+    }
+
+    fn record(id: u64) -> (PointIdType, Record) {
+        let id = PointIdType::from(id);
+        let record = Record {
+            id,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            version: None,
+        };
+        (id, record)
+    }
+
+    fn referenced_ids(inputs: &[VectorInput]) -> Vec<PointIdType> {
+        inputs
+            .iter()
+            .filter_map(|input| input.as_id().copied())
+            .collect()
+    }
+
+    #[test]
+    fn test_skip_missing_examples() {
+        let mut ids_to_vectors = ReferencedVectors::default();
+        ids_to_vectors.extend(None, [record(1), record(2)]);
+        ids_to_vectors.extend(Some("other".to_string()), [record(3)]);
+
+        let id = |id: u64| VectorInput::Id(id.into());
+
+        let mut reco = VectorQuery::RecommendBestScore(RecoQuery::new(
+            vec![id(1), id(3), VectorInput::Vector(Vector::Dense(vec![1.0]))],
+            vec![id(2), id(4)],
+        ));
+        reco.skip_missing_examples(&ids_to_vectors, None);
+        let VectorQuery::RecommendBestScore(reco) = reco else {
+            panic!("unexpected query");
+        };
+        assert_eq!(reco.positives.len(), 2);
+        assert_eq!(referenced_ids(&reco.positives), vec![1.into()]);
+        assert_eq!(referenced_ids(&reco.negatives), vec![2.into()]);
+
+        // Points are looked up in the lookup collection, including the ones in sums
+        let mut context = VectorQuery::Context(ContextQuery::new(vec![
+            ContextPair {
+                positive: id(3),
+                negative: id(1),
+            },
+            ContextPair {
+                positive: id(3),
+                negative: VectorInput::Sum(vec![WeightedVectorInput {
+                    vector: id(3),
+                    weight: -1.0,
+                }]),
+            },
+        ]));
+        context.skip_missing_examples(&ids_to_vectors, Some(&"other".to_string()));
+        let VectorQuery::Context(context) = context else {
+            panic!("unexpected query");
+        };
+        assert_eq!(context.pairs.len(), 1);
+
+        // The target of a nearest query is kept, to be reported as missing
+        let mut nearest = VectorQuery::Nearest(id(4));
+        nearest.skip_missing_examples(&ids_to_vectors, None);
+        assert!(matches!(nearest, VectorQuery::Nearest(VectorInput::Id(_))));
+    }
+}
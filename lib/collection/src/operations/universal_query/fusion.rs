@@ -0,0 +1,127 @@
+//! ## Fusion strategies
+//!
+//! Fusion combines results of multiple prefetches into a single ranking. Every [`Fusion`] variant
+//! is backed by a [`FusionStrategy`] registered in [`FusionRegistry`], so the query execution
+//! does not need to know about particular algorithms.
+//!
+//! To add a new fusion algorithm, add a [`Fusion`] variant, implement [`FusionStrategy`] for it
+//! and register it in [`FusionRegistry::with_default_strategies`]. Experimental fusions are only
+//! allowed for the collections which enable them in the `experimental_features` of the storage
+//! config.
+
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use segment::common::reciprocal_rank_fusion::rrf_scoring;
+use segment::common::score_fusion::dbsf_scoring;
+use segment::types::{Order, PointIdType, PrefetchMatch, ScoredPoint};
+
+use super::shard_query::Fusion;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+lazy_static! {
+    static ref FUSION_REGISTRY: FusionRegistry = FusionRegistry::with_default_strategies();
+}
+
+/// Algorithm to combine results of multiple prefetches into a single ranking
+pub trait FusionStrategy: Send + Sync {
+    /// Combine results of every prefetch into a single list of points, sorted by [`Self::order`]
+    fn fuse(&self, sources: Vec<Vec<ScoredPoint>>) -> Vec<ScoredPoint>;
+
+    /// Order of the fused scores
+    fn order(&self) -> Order {
+        Order::LargeBetter
+    }
+}
+
+/// Reciprocal rank fusion, only the position of points in each prefetch matters
+struct ReciprocalRankFusion;
+
+impl FusionStrategy for ReciprocalRankFusion {
+    fn fuse(&self, sources: Vec<Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+        rrf_scoring(sources)
+    }
+}
+
+/// Distribution-based score fusion, scores of each prefetch are normalized by their distribution
+struct DistributionBasedScoreFusion;
+
+impl FusionStrategy for DistributionBasedScoreFusion {
+    fn fuse(&self, sources: Vec<Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+        dbsf_scoring(sources)
+    }
+}
+
+/// Fusion strategies available for queries
+pub struct FusionRegistry {
+    strategies: HashMap<Fusion, Box<dyn FusionStrategy>>,
+}
+
+impl FusionRegistry {
+    fn with_default_strategies() -> Self {
+        let mut registry = Self {
+            strategies: HashMap::new(),
+        };
+
+        registry.register(Fusion::Rrf, ReciprocalRankFusion);
+        registry.register(Fusion::Dbsf, DistributionBasedScoreFusion);
+
+        registry
+    }
+
+    fn register(&mut self, fusion: Fusion, strategy: impl FusionStrategy + 'static) {
+        let previous = self.strategies.insert(fusion, Box::new(strategy));
+        debug_assert!(previous.is_none(), "fusion strategy registered twice");
+    }
+
+    pub fn get(&self, fusion: &Fusion) -> CollectionResult<&dyn FusionStrategy> {
+        self.strategies
+            .get(fusion)
+            .map(|strategy| strategy.as_ref())
+            .ok_or_else(|| {
+                CollectionError::service_error(format!("No strategy registered for {fusion:?}"))
+            })
+    }
+}
+
+impl Fusion {
+    /// Strategy implementing this fusion
+    pub fn strategy(&self) -> CollectionResult<&'static dyn FusionStrategy> {
+        FUSION_REGISTRY.get(self)
+    }
+}
+
+/// Collects the position of each point in the results of every prefetch of a fusion
+pub fn collect_prefetch_matches(
+    prefetches_results: &[Vec<ScoredPoint>],
+) -> HashMap<PointIdType, Vec<PrefetchMatch>> {
+    let mut prefetch_matches: HashMap<_, Vec<_>> = HashMap::new();
+
+    for (prefetch, points) in prefetches_results.iter().enumerate() {
+        for (rank, point) in points.iter().enumerate() {
+            prefetch_matches
+                .entry(point.id)
+                .or_default()
+                .push(PrefetchMatch {
+                    prefetch,
+                    rank,
+                    score: point.score,
+                });
+        }
+    }
+
+    prefetch_matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_fusions_registered() {
+        // Make sure to add new variants here
+        for fusion in [Fusion::Rrf, Fusion::Dbsf] {
+            assert!(fusion.strategy().is_ok(), "{fusion:?}");
+        }
+    }
+}
@@ -11,5 +11,7 @@
 //! 5. `PlannedQuery`: an easier-to-execute representation of a batch of [ShardQueryRequest]. Created in LocalShard
 
 pub mod collection_query;
+pub mod fusion;
 pub mod planned_query;
+pub mod query_dsl;
 pub mod shard_query;
@@ -37,12 +37,13 @@ pub struct RescoreParams {
     /// Keep this many points from the top
     pub limit: usize,
 
-    /// How many points to skip in the final result set, after re-scoring
-    pub offset: usize,
-
     /// Keep only points with better score than this threshold
     pub score_threshold: Option<ScoreType>,
 
+    /// Keep only points matching this filter.
+    /// Only set for root queries which don't propagate their filter into the prefetches.
+    pub filter: Option<Filter>,
+
     /// The vector(s) to return
     pub with_vector: WithVector,
 
@@ -82,10 +83,14 @@ impl PlannedQuery {
 
     pub fn add(&mut self, request: ShardQueryRequest) -> CollectionResult<()> {
         let depth = request.prefetches_depth();
+        let needs_intermediate_results = request.needs_intermediate_results();
         let ShardQueryRequest {
             prefetches,
             query,
             filter,
+            propagate_filter,
+            single_shard: _,
+            defer_fusion_payload,
             score_threshold,
             limit,
             offset,
@@ -94,6 +99,11 @@ impl PlannedQuery {
             params,
         } = request;
 
+        // The offset is applied at collection level, after merging the results of all shards.
+        // So the root returns the top `limit + offset` points, and the same amount of extra points
+        // is fetched by each prefetch to have enough candidates for them.
+        let root_limit = limit + offset;
+
         let merge_plan = if !prefetches.is_empty() {
             if depth > MAX_PREFETCH_DEPTH {
                 return Err(CollectionError::bad_request(format!(
@@ -106,15 +116,26 @@ impl PlannedQuery {
                 CollectionError::bad_request("cannot have prefetches without a query".to_string())
             })?;
 
-            if rescore.needs_intermediate_results() {
-                // pass `with_vector` and `with_payload` down one level, as the sources will be sent as intermediate results to the collection
+            if rescore.needs_intermediate_results() && !propagate_filter && filter.is_some() {
+                return Err(CollectionError::bad_request(
+                    "filter can't be applied after fusion, propagate_filter must be enabled"
+                        .to_string(),
+                ));
+            }
+
+            if needs_intermediate_results {
+                // The sources are sent as intermediate results to the collection, which fuses them.
+                // Unless the collection fetches the payload and vector of the fused points itself,
+                // we pass `with_vector` and `with_payload` down one level to fetch them on the sources.
+                let with_payload_and_vector =
+                    (!defer_fusion_payload).then_some((with_payload, with_vector));
                 let sources = recurse_prefetches(
                     &mut self.searches,
                     &mut self.scrolls,
                     prefetches,
                     offset,
                     filter,
-                    Some((with_payload, with_vector)),
+                    with_payload_and_vector,
                 )?;
 
                 MergePlan {
@@ -124,12 +145,19 @@ impl PlannedQuery {
                     rescore_params: None,
                 }
             } else {
+                // Without propagation, the filter is only checked on the prefetched points
+                let (prefetch_filter, rescore_filter) = if propagate_filter {
+                    (filter, None)
+                } else {
+                    (None, filter)
+                };
+
                 let sources = recurse_prefetches(
                     &mut self.searches,
                     &mut self.scrolls,
                     prefetches,
                     offset,
-                    filter,
+                    prefetch_filter,
                     None,
                 )?;
 
@@ -137,9 +165,9 @@ impl PlannedQuery {
                     sources,
                     rescore_params: Some(RescoreParams {
                         rescore,
-                        limit,
-                        offset,
+                        limit: root_limit,
                         score_threshold,
+                        filter: rescore_filter,
                         with_vector,
                         with_payload,
                     }),
@@ -155,9 +183,9 @@ impl PlannedQuery {
                         score_threshold,
                         with_vector: Some(with_vector),
                         with_payload: Some(with_payload),
-                        offset,
+                        offset: 0,
                         params,
-                        limit,
+                        limit: root_limit,
                     };
 
                     let idx = self.searches.len();
@@ -174,9 +202,27 @@ impl PlannedQuery {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
                         order_by: Some(OrderByInterface::Struct(order_by)),
-                        limit,
+                        sample: None,
+                        limit: root_limit,
+                        filter,
+                        offset: 0,
+                        with_vector,
+                        with_payload,
+                    };
+
+                    let idx = self.scrolls.len();
+                    self.scrolls.push(scroll);
+
+                    vec![Source::ScrollsIdx(idx)]
+                }
+                Some(ScoringQuery::Sample(sample)) => {
+                    // Everything should come from 1 scroll
+                    let scroll = QueryScrollRequestInternal {
+                        order_by: None,
+                        sample: Some(sample),
+                        limit: root_limit,
                         filter,
-                        offset,
+                        offset: 0,
                         with_vector,
                         with_payload,
                     };
@@ -190,9 +236,10 @@ impl PlannedQuery {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
                         order_by: None,
-                        limit,
+                        sample: None,
+                        limit: root_limit,
                         filter,
-                        offset,
+                        offset: 0,
                         with_vector,
                         with_payload,
                     };
@@ -264,7 +311,7 @@ fn recurse_prefetches(
                     rescore,
                     limit,
                     score_threshold,
-                    offset: 0, // Only apply offset at the root
+                    filter: None,
                     with_vector: with_vector.clone(),
                     with_payload: with_payload.clone(),
                 }),
@@ -299,6 +346,23 @@ fn recurse_prefetches(
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     let scroll = QueryScrollRequestInternal {
                         order_by: Some(OrderByInterface::Struct(order_by)),
+                        sample: None,
+                        filter,
+                        with_vector: with_vector.clone(),
+                        with_payload: with_payload.clone(),
+                        limit,
+                        offset: 0,
+                    };
+
+                    let idx = scrolls.len();
+                    scrolls.push(scroll);
+
+                    Source::ScrollsIdx(idx)
+                }
+                Some(ScoringQuery::Sample(sample)) => {
+                    let scroll = QueryScrollRequestInternal {
+                        order_by: None,
+                        sample: Some(sample),
                         filter,
                         with_vector: with_vector.clone(),
                         with_payload: with_payload.clone(),
@@ -314,6 +378,7 @@ fn recurse_prefetches(
                 None => {
                     let scroll = QueryScrollRequestInternal {
                         order_by: None,
+                        sample: None,
                         filter,
                         with_vector: with_vector.clone(),
                         with_payload: with_payload.clone(),
@@ -407,6 +472,9 @@ mod tests {
                 ),
             ))),
             filter: Some(filter_outer.clone()),
+            propagate_filter: true,
+            single_shard: false,
+            defer_fusion_payload: false,
             score_threshold: None,
             limit: 10,
             offset: 0,
@@ -451,8 +519,8 @@ mod tests {
                             )
                         )),
                         limit: 100,
-                        offset: 0,
                         score_threshold: None,
+                        filter: None,
                         with_vector: WithVector::Bool(false),
                         with_payload: WithPayloadInterface::Bool(false),
                     })
@@ -467,8 +535,8 @@ mod tests {
                         )
                     )),
                     limit: 10,
-                    offset: 0,
                     score_threshold: None,
+                    filter: None,
                     with_vector: WithVector::Bool(true),
                     with_payload: WithPayloadInterface::Bool(true),
                 })
@@ -485,6 +553,9 @@ mod tests {
                 NamedVectorStruct::new_from_vector(Vector::Dense(dummy_vector.clone()), "full"),
             ))),
             filter: Some(Filter::default()),
+            propagate_filter: true,
+            single_shard: false,
+            defer_fusion_payload: false,
             score_threshold: Some(0.5),
             limit: 10,
             offset: 12,
@@ -504,8 +575,8 @@ mod tests {
                 )),
                 filter: Some(Filter::default()),
                 params: Some(SearchParams::default()),
-                limit: 10,
-                offset: 12,
+                limit: 10 + 12, // limit + offset
+                offset: 0,
                 with_vector: Some(WithVector::Bool(true)),
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 score_threshold: Some(0.5),
@@ -570,6 +641,9 @@ mod tests {
             ],
             query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
             filter: Some(filter_outer.clone()),
+            propagate_filter: true,
+            single_shard: false,
+            defer_fusion_payload: true,
             score_threshold: None,
             limit: 50,
             offset: 0,
@@ -592,8 +666,9 @@ mod tests {
                     params: None,
                     limit: 100,
                     offset: 0,
+                    // Fetched for the fused points only
                     with_payload: Some(WithPayloadInterface::Bool(false)),
-                    with_vector: Some(WithVector::Bool(true)),
+                    with_vector: Some(WithVector::Bool(false)),
                     score_threshold: None,
                 },
                 CoreSearchRequest {
@@ -605,8 +680,9 @@ mod tests {
                     params: None,
                     limit: 100,
                     offset: 0,
+                    // Fetched for the fused points only
                     with_payload: Some(WithPayloadInterface::Bool(false)),
-                    with_vector: Some(WithVector::Bool(true)),
+                    with_vector: Some(WithVector::Bool(false)),
                     score_threshold: None,
                 }
             ]
@@ -621,12 +697,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_try_from_single_shard_fusion() {
+        let request = ShardQueryRequest {
+            prefetches: vec![dummy_core_prefetch(30), dummy_scroll_prefetch(40)],
+            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            filter: None,
+            propagate_filter: true,
+            single_shard: true,
+            defer_fusion_payload: false,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_payload: WithPayloadInterface::Bool(true),
+            with_vector: WithVector::Bool(false),
+        };
+
+        let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
+
+        // The shard fuses the prefetches itself, so payload is only fetched for the fused points
+        assert_eq!(
+            planned_query.searches,
+            vec![CoreSearchRequest {
+                query: QueryEnum::Nearest(NamedVectorStruct::Default(vec![0.1, 0.2, 0.3, 0.4])),
+                filter: None,
+                params: None,
+                limit: 30,
+                offset: 0,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: Some(WithVector::Bool(false)),
+                score_threshold: None,
+            }]
+        );
+
+        assert_eq!(
+            planned_query.root_plans,
+            vec![MergePlan {
+                sources: vec![Source::SearchesIdx(0), Source::ScrollsIdx(0)],
+                rescore_params: Some(RescoreParams {
+                    rescore: ScoringQuery::Fusion(Fusion::Rrf),
+                    limit: 10,
+                    score_threshold: None,
+                    filter: None,
+                    with_vector: WithVector::Bool(false),
+                    with_payload: WithPayloadInterface::Bool(true),
+                }),
+            }]
+        );
+    }
+
     #[test]
     fn test_try_from_rrf_without_source() {
         let request = ShardQueryRequest {
             prefetches: vec![],
             query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
             filter: Some(Filter::default()),
+            propagate_filter: true,
+            single_shard: false,
+            defer_fusion_payload: false,
             score_threshold: None,
             limit: 50,
             offset: 0,
@@ -640,6 +769,83 @@ mod tests {
         assert!(planned_query.is_err())
     }
 
+    #[test]
+    fn test_try_from_without_filter_propagation() {
+        let dummy_vector = vec![1.0, 2.0, 3.0];
+        let filter_inner = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "city".try_into().unwrap(),
+            "Berlin".to_string().into(),
+        )));
+        let filter_outer = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "country".try_into().unwrap(),
+            "Germany".to_string().into(),
+        )));
+
+        let nearest_query = || {
+            ScoringQuery::Vector(QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                Vector::Dense(dummy_vector.clone()),
+                "full",
+            )))
+        };
+
+        let request = ShardQueryRequest {
+            prefetches: vec![ShardPrefetch {
+                prefetches: Vec::new(),
+                query: Some(nearest_query()),
+                limit: 100,
+                params: None,
+                filter: Some(filter_inner.clone()),
+                score_threshold: None,
+            }],
+            query: Some(nearest_query()),
+            filter: Some(filter_outer.clone()),
+            propagate_filter: false,
+            single_shard: false,
+            defer_fusion_payload: false,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
+
+        // Root filter is not pushed into the prefetch
+        assert_eq!(planned_query.searches.len(), 1);
+        assert_eq!(planned_query.searches[0].filter, Some(filter_inner));
+
+        // It is applied when rescoring instead
+        let rescore_params = planned_query.root_plans[0].rescore_params.as_ref().unwrap();
+        assert_eq!(rescore_params.filter, Some(filter_outer.clone()));
+
+        // Fusion can't apply the filter afterwards
+        let request = ShardQueryRequest {
+            prefetches: vec![ShardPrefetch {
+                prefetches: Vec::new(),
+                query: Some(nearest_query()),
+                limit: 100,
+                params: None,
+                filter: None,
+                score_threshold: None,
+            }],
+            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            filter: Some(filter_outer),
+            propagate_filter: false,
+            single_shard: false,
+            defer_fusion_payload: false,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        assert!(PlannedQuery::try_from(vec![request]).is_err());
+    }
+
     #[test]
     fn test_base_params_mapping_in_try_from() {
         let dummy_vector = vec![1.0, 2.0, 3.0];
@@ -670,6 +876,9 @@ mod tests {
             }],
             query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
             filter: Some(Filter::default()),
+            propagate_filter: true,
+            single_shard: false,
+            defer_fusion_payload: false,
             score_threshold: Some(0.666),
             limit: 50,
             offset: 49,
@@ -758,6 +967,9 @@ mod tests {
                 NamedVectorStruct::new_from_vector(Vector::Dense(vec![1.0, 2.0, 3.0]), "dense"),
             ))),
             filter: None,
+            propagate_filter: true,
+            single_shard: false,
+            defer_fusion_payload: false,
             score_threshold: None,
             limit: 10,
             offset: 0,
@@ -853,6 +1065,9 @@ mod tests {
                 prefetches: vec![],
                 query: nearest_query(),
                 filter: None,
+                propagate_filter: true,
+                single_shard: false,
+                defer_fusion_payload: false,
                 score_threshold: None,
                 limit: 10,
                 offset: 0,
@@ -865,6 +1080,9 @@ mod tests {
                 prefetches: vec![],
                 query: None,
                 filter: None,
+                propagate_filter: true,
+                single_shard: false,
+                defer_fusion_payload: false,
                 score_threshold: None,
                 limit: 20,
                 offset: 0,
@@ -887,6 +1105,9 @@ mod tests {
                 ],
                 query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
                 filter: None,
+                propagate_filter: true,
+                single_shard: false,
+                defer_fusion_payload: false,
                 score_threshold: None,
                 limit: 10,
                 offset: 0,
@@ -919,8 +1140,8 @@ mod tests {
                             rescore_params: Some(RescoreParams {
                                 rescore: ScoringQuery::Fusion(Fusion::Rrf),
                                 limit: 10,
-                                offset: 0,
                                 score_threshold: None,
+                                filter: None,
                                 with_vector: WithVector::Bool(true),
                                 with_payload: WithPayloadInterface::Bool(true),
                             }),
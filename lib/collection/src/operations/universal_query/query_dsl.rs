@@ -0,0 +1,919 @@
+//! ## Textual query language
+//!
+//! Compact text form of the universal query, handy for exploration and debugging, e.g.:
+//!
+//! ```text
+//! prefetch nearest([0.1, 0.2, 0.3]) prefetch sparse({12: 0.5, 50: 0.1})
+//! fuse rrf where tenant = 'x' and price < 10 limit 20 with payload
+//! ```
+//!
+//! Grammar:
+//!
+//! ```text
+//! query     := clause*
+//! clause    := query_expr
+//!            | "prefetch" ( "(" query ")" | query_expr )
+//!            | "where" condition
+//!            | "using" NAME
+//!            | "limit" INT | "offset" INT | "score_threshold" NUMBER
+//!            | "with" ("payload" | "vector") ("," ("payload" | "vector"))*
+//! query_expr:= "nearest" "(" vector ")"
+//!            | NAME "(" vector ")"                      -- nearest, using vector NAME
+//!            | "order_by" "(" KEY ["asc" | "desc"] ")"
+//!            | "fuse" ("rrf" | "dbsf")
+//! vector    := "[" NUMBER, ... "]" | "[" "[" NUMBER, ... "]", ... "]"
+//!            | "{" INT ":" NUMBER, ... "}" | INT | STRING   -- dense, multi, sparse, point id
+//! condition := and_cond ("or" and_cond)*
+//! and_cond  := unary ("and" unary)*
+//! unary     := "not" unary | "(" condition ")" | "has_id" "(" id, ... ")"
+//!            | KEY ("=" | "!=" | "<" | "<=" | ">" | ">=") VALUE
+//!            | KEY ["not"] "in" "(" VALUE, ... ")"
+//!            | KEY "is" ["not"] ("null" | "empty")
+//! ```
+//!
+//! Parsed query is converted into [`CollectionQueryRequest`] through the REST representation, so
+//! it has exactly the same semantics and validation as a JSON query.
+
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use api::rest;
+use common::types::ScoreType;
+use segment::data_types::order_by::{Direction, OrderBy};
+use segment::json_path::JsonPath;
+use segment::types::{
+    AnyVariants, Condition, FieldCondition, Filter, HasIdCondition, IsEmptyCondition,
+    IsNullCondition, Match, PointIdType, Range, ValueVariants, WithPayloadInterface, WithVector,
+};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+use validator::Validate as _;
+
+use super::collection_query::CollectionQueryRequest;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Parse textual query into a collection query request
+pub fn parse_query_dsl(text: &str) -> CollectionResult<CollectionQueryRequest> {
+    let request = parse_rest_query(text)?;
+    request.validate()?;
+    Ok(CollectionQueryRequest::from(request))
+}
+
+fn parse_rest_query(text: &str) -> CollectionResult<rest::QueryRequestInternal> {
+    let tokens = Tokenizer::new(text).tokenize()?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        end: text.len(),
+        depth: 0,
+    };
+
+    let level = parser.parse_level(false)?;
+    Ok(level.into_request())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Comma,
+    Colon,
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+fn error_at(position: usize, message: impl std::fmt::Display) -> CollectionError {
+    CollectionError::bad_input(format!(
+        "Query syntax error at position {position}: {message}"
+    ))
+}
+
+struct Tokenizer<'a> {
+    text: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            chars: text.char_indices().peekable(),
+        }
+    }
+
+    fn tokenize(mut self) -> CollectionResult<Vec<(Token, usize)>> {
+        let mut tokens = Vec::new();
+
+        while let Some(&(start, c)) = self.chars.peek() {
+            let token = match c {
+                _ if c.is_whitespace() => {
+                    self.chars.next();
+                    continue;
+                }
+                '(' | ')' | '[' | ']' | '{' | '}' | ',' | ':' => {
+                    self.chars.next();
+                    match c {
+                        '(' => Token::LParen,
+                        ')' => Token::RParen,
+                        '[' => Token::LBracket,
+                        ']' => Token::RBracket,
+                        '{' => Token::LBrace,
+                        '}' => Token::RBrace,
+                        ',' => Token::Comma,
+                        _ => Token::Colon,
+                    }
+                }
+                '=' | '!' | '<' | '>' => {
+                    self.chars.next();
+                    let with_eq = self.chars.next_if(|&(_, next)| next == '=').is_some();
+                    match (c, with_eq) {
+                        ('=', _) => Token::Eq,
+                        ('!', true) => Token::NotEq,
+                        ('<', false) => Token::Lt,
+                        ('<', true) => Token::Lte,
+                        ('>', false) => Token::Gt,
+                        ('>', true) => Token::Gte,
+                        _ => return Err(error_at(start, "expected `!=`")),
+                    }
+                }
+                '\'' | '"' => Token::Str(self.string(start, c)?),
+                _ if c.is_ascii_digit() || c == '-' || c == '.' => {
+                    let end = self
+                        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'));
+                    Token::Number(self.text[start..end].to_string())
+                }
+                _ if c.is_alphabetic() || c == '_' => {
+                    let end = self.take_while(|c| {
+                        c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '[' | ']')
+                    });
+                    Token::Ident(self.text[start..end].to_string())
+                }
+                _ => return Err(error_at(start, format!("unexpected character `{c}`"))),
+            };
+
+            tokens.push((token, start));
+        }
+
+        Ok(tokens)
+    }
+
+    /// Consume chars while predicate holds, return end position
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> usize {
+        while self.chars.next_if(|&(_, c)| predicate(c)).is_some() {}
+        self.chars
+            .peek()
+            .map_or(self.text.len(), |&(position, _)| position)
+    }
+
+    fn string(&mut self, start: usize, quote: char) -> CollectionResult<String> {
+        self.chars.next();
+        let mut value = String::new();
+
+        loop {
+            match self.chars.next() {
+                None => return Err(error_at(start, "unterminated string")),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, c)) => value.push(c),
+                    None => return Err(error_at(start, "unterminated string")),
+                },
+                Some((_, c)) if c == quote => return Ok(value),
+                Some((_, c)) => value.push(c),
+            }
+        }
+    }
+}
+
+/// Clauses of a single query level, either the main query or a prefetch
+#[derive(Default)]
+struct Level {
+    prefetch: Vec<rest::Prefetch>,
+    query: Option<rest::QueryInterface>,
+    using: Option<String>,
+    filter: Option<Filter>,
+    score_threshold: Option<ScoreType>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    with_payload: Option<WithPayloadInterface>,
+    with_vector: Option<WithVector>,
+    /// Position of the clause, which is not allowed in prefetch
+    main_only_clause: Option<usize>,
+}
+
+impl Level {
+    fn into_request(self) -> rest::QueryRequestInternal {
+        rest::QueryRequestInternal {
+            prefetch: (!self.prefetch.is_empty()).then_some(self.prefetch),
+            query: self.query,
+            using: self.using,
+            filter: self.filter,
+            propagate_filter: None,
+            ids: None,
+            filter_template: None,
+            params: None,
+            score_threshold: self.score_threshold,
+            limit: self.limit,
+            offset: self.offset,
+            with_vector: self.with_vector,
+            with_payload: self.with_payload,
+            with_routing: None,
+            with_stats: None,
+            lookup_from: None,
+            budget: None,
+            rerank: None,
+            skip_missing: None,
+        }
+    }
+
+    fn into_prefetch(self) -> CollectionResult<rest::Prefetch> {
+        if let Some(position) = self.main_only_clause {
+            return Err(error_at(
+                position,
+                "`offset` and `with` are not supported in prefetch",
+            ));
+        }
+
+        Ok(rest::Prefetch {
+            prefetch: (!self.prefetch.is_empty()).then_some(self.prefetch),
+            query: self.query,
+            using: self.using,
+            filter: self.filter,
+            params: None,
+            score_threshold: self.score_threshold,
+            limit: self.limit,
+            lookup_from: None,
+            shard_key: None,
+        })
+    }
+}
+
+/// Set clause value, if it was not set before
+fn set_once<T>(
+    slot: &mut Option<T>,
+    value: T,
+    name: &str,
+    position: usize,
+) -> CollectionResult<()> {
+    if slot.is_some() {
+        return Err(error_at(
+            position,
+            format!("`{name}` is specified more than once"),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// Max nesting of prefetches, parentheses and negations in a query.
+/// Parser is recursive, so deeper queries are rejected instead of overflowing the stack.
+const MAX_NESTING_DEPTH: usize = 64;
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Position of the end of the text, for errors at the end of input
+    end: usize,
+    /// Current nesting depth
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.end, |&(_, position)| position)
+    }
+
+    fn next(&mut self) -> CollectionResult<Token> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .map(|(token, _)| token.clone())
+            .ok_or_else(|| error_at(self.end, "unexpected end of query"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Parse nested part of the query, limiting the depth of recursion
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> CollectionResult<T>,
+    ) -> CollectionResult<T> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(error_at(
+                self.position(),
+                format!("query is nested deeper than {MAX_NESTING_DEPTH} levels"),
+            ));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn expect(&mut self, expected: Token) -> CollectionResult<()> {
+        let position = self.position();
+        let token = self.next()?;
+        if token != expected {
+            return Err(error_at(
+                position,
+                format!("expected {expected:?}, found {token:?}"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> CollectionResult<()> {
+        if !self.eat_keyword(keyword) {
+            return Err(error_at(self.position(), format!("expected `{keyword}`")));
+        }
+        Ok(())
+    }
+
+    fn ident(&mut self) -> CollectionResult<String> {
+        let position = self.position();
+        match self.next()? {
+            Token::Ident(ident) => Ok(ident),
+            Token::Str(string) => Ok(string),
+            token => Err(error_at(
+                position,
+                format!("expected name, found {token:?}"),
+            )),
+        }
+    }
+
+    fn key(&mut self) -> CollectionResult<JsonPath> {
+        let position = self.position();
+        let key = self.ident()?;
+        JsonPath::from_str(&key).map_err(|_| error_at(position, format!("invalid key `{key}`")))
+    }
+
+    fn number<T: FromStr>(&mut self) -> CollectionResult<T> {
+        let position = self.position();
+        match self.next()? {
+            Token::Number(number) => number
+                .parse()
+                .map_err(|_| error_at(position, format!("invalid number `{number}`"))),
+            token => Err(error_at(
+                position,
+                format!("expected number, found {token:?}"),
+            )),
+        }
+    }
+
+    /// Parse comma separated items until the closing token
+    fn list<T>(
+        &mut self,
+        close: Token,
+        mut item: impl FnMut(&mut Self) -> CollectionResult<T>,
+    ) -> CollectionResult<Vec<T>> {
+        let mut items = Vec::new();
+        while self.peek() != Some(&close) {
+            items.push(item(self)?);
+            if self.peek() != Some(&close) {
+                self.expect(Token::Comma)?;
+            }
+        }
+        self.expect(close)?;
+        Ok(items)
+    }
+
+    fn parse_level(&mut self, nested: bool) -> CollectionResult<Level> {
+        let mut level = Level::default();
+
+        while let Some(token) = self.peek() {
+            if nested && token == &Token::RParen {
+                break;
+            }
+
+            let position = self.position();
+
+            if self.eat_keyword("prefetch") {
+                let prefetch = if self.peek() == Some(&Token::LParen) {
+                    self.pos += 1;
+                    let prefetch = self.nested(|parser| parser.parse_level(true))?;
+                    self.expect(Token::RParen)?;
+                    prefetch
+                } else {
+                    let mut prefetch = Level::default();
+                    let (query, using) = self.query_expr()?;
+                    prefetch.query = Some(query);
+                    prefetch.using = using;
+                    prefetch
+                };
+                level.prefetch.push(prefetch.into_prefetch()?);
+            } else if self.eat_keyword("where") {
+                let filter = match self.condition()? {
+                    Condition::Filter(filter) => filter,
+                    condition => Filter::new_must(condition),
+                };
+                set_once(&mut level.filter, filter, "where", position)?;
+            } else if self.eat_keyword("using") {
+                let using = self.ident()?;
+                set_once(&mut level.using, using, "using", position)?;
+            } else if self.eat_keyword("limit") {
+                let limit = self.number()?;
+                set_once(&mut level.limit, limit, "limit", position)?;
+            } else if self.eat_keyword("offset") {
+                let offset = self.number()?;
+                set_once(&mut level.offset, offset, "offset", position)?;
+                level.main_only_clause.get_or_insert(position);
+            } else if self.eat_keyword("score_threshold") {
+                let threshold = self.number()?;
+                set_once(
+                    &mut level.score_threshold,
+                    threshold,
+                    "score_threshold",
+                    position,
+                )?;
+            } else if self.eat_keyword("with") {
+                loop {
+                    let position = self.position();
+                    if self.eat_keyword("payload") {
+                        let with_payload = WithPayloadInterface::Bool(true);
+                        set_once(
+                            &mut level.with_payload,
+                            with_payload,
+                            "with payload",
+                            position,
+                        )?;
+                    } else if self.eat_keyword("vector") {
+                        let with_vector = WithVector::Bool(true);
+                        set_once(&mut level.with_vector, with_vector, "with vector", position)?;
+                    } else {
+                        return Err(error_at(position, "expected `payload` or `vector`"));
+                    }
+                    if self.peek() != Some(&Token::Comma) {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                level.main_only_clause.get_or_insert(position);
+            } else {
+                let (query, using) = self.query_expr()?;
+                set_once(&mut level.query, query, "query", position)?;
+                if let Some(using) = using {
+                    set_once(&mut level.using, using, "using", position)?;
+                }
+            }
+        }
+
+        Ok(level)
+    }
+
+    /// Parse query, with the name of the vector to use, if given
+    fn query_expr(&mut self) -> CollectionResult<(rest::QueryInterface, Option<String>)> {
+        let position = self.position();
+
+        if self.eat_keyword("fuse") {
+            let fusion = if self.eat_keyword("dbsf") {
+                rest::Fusion::Dbsf
+            } else {
+                self.expect_keyword("rrf")?;
+                rest::Fusion::Rrf
+            };
+            let query = rest::Query::Fusion(rest::FusionQuery { fusion });
+            return Ok((rest::QueryInterface::Query(query), None));
+        }
+
+        if self.eat_keyword("order_by") {
+            self.expect(Token::LParen)?;
+            let key = self.key()?;
+            let direction = if self.eat_keyword("asc") {
+                Some(Direction::Asc)
+            } else if self.eat_keyword("desc") {
+                Some(Direction::Desc)
+            } else {
+                None
+            };
+            self.expect(Token::RParen)?;
+
+            let order_by = rest::OrderByInterface::Struct(OrderBy {
+                key,
+                direction,
+                start_from: None,
+            });
+            let query = rest::Query::OrderBy(rest::OrderByQuery { order_by });
+            return Ok((rest::QueryInterface::Query(query), None));
+        }
+
+        let name = match self.next()? {
+            Token::Ident(name) if is_reserved(&name) => {
+                return Err(error_at(position, format!("unexpected `{name}`")));
+            }
+            Token::Ident(name) => name,
+            token => {
+                return Err(error_at(
+                    position,
+                    format!("expected query, found {token:?}"),
+                ))
+            }
+        };
+        let using = (!name.eq_ignore_ascii_case("nearest")).then_some(name);
+
+        self.expect(Token::LParen)?;
+        let vector = self.vector()?;
+        self.expect(Token::RParen)?;
+
+        Ok((rest::QueryInterface::Nearest(vector), using))
+    }
+
+    fn vector(&mut self) -> CollectionResult<rest::VectorInput> {
+        let position = self.position();
+
+        match self.next()? {
+            Token::LBracket if self.peek() == Some(&Token::LBracket) => {
+                let vectors = self.list(Token::RBracket, |parser| {
+                    parser.expect(Token::LBracket)?;
+                    parser.list(Token::RBracket, |parser| parser.number())
+                })?;
+                Ok(rest::VectorInput::MultiDenseVector(vectors))
+            }
+            Token::LBracket => {
+                let vector = self.list(Token::RBracket, |parser| parser.number())?;
+                Ok(rest::VectorInput::DenseVector(vector))
+            }
+            Token::LBrace => {
+                let (indices, values) = self
+                    .list(Token::RBrace, |parser| {
+                        let index: DimId = parser.number()?;
+                        parser.expect(Token::Colon)?;
+                        let value: DimWeight = parser.number()?;
+                        Ok((index, value))
+                    })?
+                    .into_iter()
+                    .unzip();
+                Ok(rest::VectorInput::SparseVector(SparseVector {
+                    indices,
+                    values,
+                }))
+            }
+            Token::Number(_) | Token::Str(_) => {
+                self.pos -= 1;
+                Ok(rest::VectorInput::Id(self.point_id()?))
+            }
+            token => Err(error_at(
+                position,
+                format!("expected vector or point id, found {token:?}"),
+            )),
+        }
+    }
+
+    fn point_id(&mut self) -> CollectionResult<PointIdType> {
+        let position = self.position();
+        let id = match self.next()? {
+            Token::Number(id) | Token::Str(id) => id,
+            token => {
+                return Err(error_at(
+                    position,
+                    format!("expected point id, found {token:?}"),
+                ))
+            }
+        };
+        PointIdType::from_str(&id)
+            .map_err(|_| error_at(position, format!("invalid point id `{id}`")))
+    }
+
+    fn condition(&mut self) -> CollectionResult<Condition> {
+        let mut conditions = vec![self.and_condition()?];
+        while self.eat_keyword("or") {
+            conditions.push(self.and_condition()?);
+        }
+
+        if conditions.len() == 1 {
+            return Ok(conditions.pop().unwrap());
+        }
+        Ok(Condition::Filter(Filter {
+            should: Some(conditions),
+            min_should: None,
+            must: None,
+            must_not: None,
+        }))
+    }
+
+    fn and_condition(&mut self) -> CollectionResult<Condition> {
+        let mut conditions = vec![self.unary_condition()?];
+        while self.eat_keyword("and") {
+            conditions.push(self.unary_condition()?);
+        }
+
+        if conditions.len() == 1 {
+            return Ok(conditions.pop().unwrap());
+        }
+        Ok(Condition::Filter(Filter {
+            should: None,
+            min_should: None,
+            must: Some(conditions),
+            must_not: None,
+        }))
+    }
+
+    fn unary_condition(&mut self) -> CollectionResult<Condition> {
+        if self.eat_keyword("not") {
+            let condition = self.nested(|parser| parser.unary_condition())?;
+            return Ok(Condition::Filter(Filter::new_must_not(condition)));
+        }
+
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let condition = self.nested(|parser| parser.condition())?;
+            self.expect(Token::RParen)?;
+            return Ok(condition);
+        }
+
+        if self.peek_keyword("has_id")
+            && self.tokens.get(self.pos + 1).map(|(token, _)| token) == Some(&Token::LParen)
+        {
+            self.pos += 2;
+            let has_id = self.list(Token::RParen, |parser| parser.point_id())?;
+            return Ok(Condition::HasId(HasIdCondition {
+                has_id: has_id.into_iter().collect(),
+            }));
+        }
+
+        let key = self.key()?;
+        let position = self.position();
+
+        if self.eat_keyword("is") {
+            let negate = self.eat_keyword("not");
+            let condition = if self.eat_keyword("null") {
+                Condition::IsNull(IsNullCondition::from(key))
+            } else if self.eat_keyword("empty") {
+                Condition::IsEmpty(IsEmptyCondition::from(key))
+            } else {
+                return Err(error_at(self.position(), "expected `null` or `empty`"));
+            };
+            return Ok(negated(condition, negate));
+        }
+
+        let negate_in = self.eat_keyword("not");
+        if self.eat_keyword("in") {
+            self.expect(Token::LParen)?;
+            let values = self.list(Token::RParen, |parser| parser.value())?;
+            let any = any_variants(values).ok_or_else(|| {
+                error_at(
+                    position,
+                    "`in` expects either only strings or only integers",
+                )
+            })?;
+            let condition = Condition::Field(FieldCondition::new_match(key, Match::new_any(any)));
+            return Ok(negated(condition, negate_in));
+        }
+        if negate_in {
+            return Err(error_at(self.position(), "expected `in`"));
+        }
+
+        let condition = match self.next()? {
+            Token::Eq => {
+                let value = self.value()?;
+                Condition::Field(FieldCondition::new_match(key, Match::new_value(value)))
+            }
+            Token::NotEq => {
+                let value = self.value()?;
+                let condition =
+                    Condition::Field(FieldCondition::new_match(key, Match::new_value(value)));
+                negated(condition, true)
+            }
+            token @ (Token::Lt | Token::Lte | Token::Gt | Token::Gte) => {
+                let value = self.number()?;
+                let mut range = Range::default();
+                match token {
+                    Token::Lt => range.lt = Some(value),
+                    Token::Lte => range.lte = Some(value),
+                    Token::Gt => range.gt = Some(value),
+                    _ => range.gte = Some(value),
+                }
+                Condition::Field(FieldCondition::new_range(key, range))
+            }
+            token => {
+                return Err(error_at(
+                    position,
+                    format!("expected comparison, found {token:?}"),
+                ))
+            }
+        };
+
+        Ok(condition)
+    }
+
+    fn value(&mut self) -> CollectionResult<ValueVariants> {
+        let position = self.position();
+        match self.next()? {
+            Token::Str(value) => Ok(ValueVariants::Keyword(value)),
+            Token::Number(number) => number
+                .parse()
+                .map(ValueVariants::Integer)
+                .map_err(|_| error_at(position, format!("expected integer, found `{number}`"))),
+            Token::Ident(ident) if ident == "true" => Ok(ValueVariants::Bool(true)),
+            Token::Ident(ident) if ident == "false" => Ok(ValueVariants::Bool(false)),
+            token => Err(error_at(
+                position,
+                format!("expected value, found {token:?}"),
+            )),
+        }
+    }
+}
+
+fn negated(condition: Condition, negate: bool) -> Condition {
+    if negate {
+        Condition::Filter(Filter::new_must_not(condition))
+    } else {
+        condition
+    }
+}
+
+fn any_variants(values: Vec<ValueVariants>) -> Option<AnyVariants> {
+    if values
+        .iter()
+        .all(|value| matches!(value, ValueVariants::Keyword(_)))
+    {
+        let keywords = values
+            .into_iter()
+            .filter_map(|value| match value {
+                ValueVariants::Keyword(keyword) => Some(keyword),
+                _ => None,
+            })
+            .collect();
+        return Some(AnyVariants::Keywords(keywords));
+    }
+
+    values
+        .into_iter()
+        .map(|value| match value {
+            ValueVariants::Integer(integer) => Some(integer),
+            _ => None,
+        })
+        .collect::<Option<_>>()
+        .map(AnyVariants::Integers)
+}
+
+fn is_reserved(ident: &str) -> bool {
+    [
+        "prefetch",
+        "where",
+        "using",
+        "limit",
+        "offset",
+        "score_threshold",
+        "with",
+        "fuse",
+        "order_by",
+        "and",
+        "or",
+        "not",
+    ]
+    .iter()
+    .any(|keyword| ident.eq_ignore_ascii_case(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn filter(value: serde_json::Value) -> Option<Filter> {
+        Some(serde_json::from_value(value).unwrap())
+    }
+
+    #[test]
+    fn test_parse_hybrid_query() {
+        let request = parse_rest_query(
+            "prefetch nearest([0.5, 0.25]) \
+             prefetch (sparse({3: 0.5, 7: 0.25}) limit 30) \
+             fuse rrf where tenant = 'x' and price < 10 limit 20 with payload",
+        )
+        .unwrap();
+
+        let prefetch = request.prefetch.unwrap();
+        assert_eq!(prefetch.len(), 2);
+
+        assert_eq!(
+            serde_json::to_value(&prefetch[0].query).unwrap(),
+            json!([0.5, 0.25]),
+        );
+        assert_eq!(prefetch[0].using, None);
+
+        assert_eq!(
+            serde_json::to_value(&prefetch[1].query).unwrap(),
+            json!({"indices": [3, 7], "values": [0.5, 0.25]}),
+        );
+        assert_eq!(prefetch[1].using.as_deref(), Some("sparse"));
+        assert_eq!(prefetch[1].limit, Some(30));
+
+        assert_eq!(
+            serde_json::to_value(&request.query).unwrap(),
+            json!({"fusion": "rrf"}),
+        );
+        assert_eq!(
+            request.filter,
+            filter(json!({
+                "must": [
+                    {"key": "tenant", "match": {"value": "x"}},
+                    {"key": "price", "range": {"lt": 10.0}},
+                ],
+            })),
+        );
+        assert_eq!(request.limit, Some(20));
+        assert_eq!(request.with_payload, Some(WithPayloadInterface::Bool(true)));
+        assert!(request.with_vector.is_none());
+    }
+
+    #[test]
+    fn test_parse_conditions() {
+        let request = parse_rest_query(
+            "order_by(price desc) where (color in ('red', 'blue') or not has_id(1, 2)) \
+             and tags is not empty and count != 3",
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request.query).unwrap(),
+            json!({"order_by": {"key": "price", "direction": "desc", "start_from": null}}),
+        );
+        assert_eq!(
+            request.filter,
+            filter(json!({
+                "must": [
+                    {
+                        "should": [
+                            {"key": "color", "match": {"any": ["red", "blue"]}},
+                            {"must_not": [{"has_id": [1, 2]}]},
+                        ],
+                    },
+                    {"must_not": [{"is_empty": {"key": "tags"}}]},
+                    {"must_not": [{"key": "count", "match": {"value": 3}}]},
+                ],
+            })),
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for text in [
+            "nearest([0.1, 0.2]",
+            "nearest([0.1]) limit 10 limit 20",
+            "prefetch (nearest([0.1]) offset 10)",
+            "where color in ('red', 1)",
+            "where price <",
+            "where",
+            "limit",
+        ] {
+            assert!(parse_rest_query(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_parse_nesting_depth() {
+        let nested_conditions = |depth: usize| {
+            format!(
+                "nearest([0.1]) where {}price < 10{}",
+                "(".repeat(depth),
+                ")".repeat(depth),
+            )
+        };
+        let nested_prefetches = |depth: usize| {
+            format!(
+                "{}nearest([0.1]){} fuse rrf",
+                "prefetch (".repeat(depth),
+                ")".repeat(depth),
+            )
+        };
+        let negations =
+            |depth: usize| format!("nearest([0.1]) where {}price < 10", "not ".repeat(depth));
+
+        for query in [nested_conditions, nested_prefetches, negations] {
+            assert!(parse_rest_query(&query(MAX_NESTING_DEPTH)).is_ok());
+
+            // Would overflow the stack without the limit
+            let err = parse_rest_query(&query(100_000)).unwrap_err();
+            assert!(err.to_string().contains("nested deeper"), "{err}");
+        }
+    }
+}
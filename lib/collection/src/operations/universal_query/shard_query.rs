@@ -24,8 +24,16 @@ pub struct ShardQueryRequest {
     pub prefetches: Vec<ShardPrefetch>,
     pub query: Option<ScoringQuery>,
     pub filter: Option<Filter>,
+    /// Apply the filter to prefetches as well, otherwise it is only applied when rescoring
+    pub propagate_filter: bool,
+    /// Only one shard is queried, so it can merge the prefetches itself
+    pub single_shard: bool,
+    /// The collection fetches the payload and vector of the fused points itself, so the sources
+    /// of a fusion are returned without them. Not set by coordinators of older versions.
+    pub defer_fusion_payload: bool,
     pub score_threshold: Option<ScoreType>,
     pub limit: usize,
+    /// Applied once at collection level, shards return the top `limit + offset` points
     pub offset: usize,
     /// Search params for when there is no prefetch
     pub params: Option<SearchParams>,
@@ -41,11 +49,29 @@ impl ShardQueryRequest {
             .max()
             .unwrap_or(0)
     }
+
+    /// Whether the shard returns the results of each prefetch, to be merged at collection level
+    pub fn needs_intermediate_results(&self) -> bool {
+        !self.single_shard
+            && self
+                .query
+                .as_ref()
+                .is_some_and(ScoringQuery::needs_intermediate_results)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Fusion {
     Rrf,
+    /// Experimental, must be enabled for the collection
+    Dbsf,
+}
+
+/// Random sample of points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sample {
+    /// Same seed picks the same sample of points across segments and shards
+    pub seed: u64,
 }
 
 /// Same as `Query`, but with the resolved vector references.
@@ -59,15 +85,17 @@ pub enum ScoringQuery {
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Random sample of points
+    Sample(Sample),
 }
 
 impl ScoringQuery {
     pub fn needs_intermediate_results(&self) -> bool {
         match self {
-            ScoringQuery::Fusion(fusion) => match fusion {
-                Fusion::Rrf => true,
-            },
-            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) => false,
+            // Fusion needs results of each prefetch separately
+            ScoringQuery::Fusion(_) => true,
+            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) | ScoringQuery::Sample(_) => false,
         }
     }
 
@@ -95,10 +123,9 @@ impl ScoringQuery {
                         Order::LargeBetter
                     }
                 }
-                ScoringQuery::Fusion(fusion) => match fusion {
-                    Fusion::Rrf => Order::LargeBetter,
-                },
+                ScoringQuery::Fusion(fusion) => fusion.strategy()?.order(),
                 ScoringQuery::OrderBy(order_by) => Order::from(order_by.direction()),
+                ScoringQuery::Sample(_) => Order::LargeBetter,
             },
             None => {
                 // Order by ID
@@ -171,6 +198,9 @@ impl TryFrom<grpc::QueryShardPoints> for ShardQueryRequest {
             offset,
             with_payload,
             with_vectors,
+            propagate_filter,
+            single_shard,
+            defer_fusion_payload,
         } = value;
 
         let request = Self {
@@ -182,6 +212,9 @@ impl TryFrom<grpc::QueryShardPoints> for ShardQueryRequest {
                 .map(|query| ScoringQuery::try_from_grpc_query(query, using))
                 .transpose()?,
             filter: filter.map(Filter::try_from).transpose()?,
+            propagate_filter: propagate_filter.unwrap_or(true),
+            single_shard: single_shard.unwrap_or(false),
+            defer_fusion_payload: defer_fusion_payload.unwrap_or(false),
             score_threshold,
             limit: limit as usize,
             offset: offset as usize,
@@ -292,6 +325,7 @@ impl From<api::grpc::qdrant::Fusion> for Fusion {
     fn from(fusion: api::grpc::qdrant::Fusion) -> Self {
         match fusion {
             api::grpc::qdrant::Fusion::Rrf => Fusion::Rrf,
+            api::grpc::qdrant::Fusion::Dbsf => Fusion::Dbsf,
         }
     }
 }
@@ -300,6 +334,7 @@ impl From<Fusion> for api::grpc::qdrant::Fusion {
     fn from(fusion: Fusion) -> Self {
         match fusion {
             Fusion::Rrf => api::grpc::qdrant::Fusion::Rrf,
+            Fusion::Dbsf => api::grpc::qdrant::Fusion::Dbsf,
         }
     }
 }
@@ -322,6 +357,9 @@ impl ScoringQuery {
             grpc::query_shard_points::query::Score::OrderBy(order_by) => {
                 ScoringQuery::OrderBy(OrderBy::try_from(order_by)?)
             }
+            grpc::query_shard_points::query::Score::Sample(seed) => {
+                ScoringQuery::Sample(Sample { seed })
+            }
         };
 
         Ok(scoring_query)
@@ -365,6 +403,9 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
             ScoringQuery::OrderBy(order_by) => Self {
                 score: Some(Score::OrderBy(grpc::OrderBy::from(order_by))),
             },
+            ScoringQuery::Sample(Sample { seed }) => Self {
+                score: Some(Score::Sample(seed)),
+            },
         }
     }
 }
@@ -399,6 +440,9 @@ impl From<ShardQueryRequest> for grpc::QueryShardPoints {
             prefetches,
             query,
             filter,
+            propagate_filter,
+            single_shard,
+            defer_fusion_payload,
             score_threshold,
             limit,
             offset,
@@ -423,6 +467,9 @@ impl From<ShardQueryRequest> for grpc::QueryShardPoints {
             offset: offset as u64,
             with_payload: Some(grpc::WithPayloadSelector::from(with_payload)),
             with_vectors: Some(grpc::WithVectorsSelector::from(with_vector)),
+            propagate_filter: Some(propagate_filter),
+            single_shard: Some(single_shard),
+            defer_fusion_payload: Some(defer_fusion_payload),
         }
     }
 }
@@ -0,0 +1,265 @@
+//! Ingest policies of dense vectors, applied to the vectors of upserted and updated points before
+//! they are sent to the shards.
+//!
+//! Bad vectors are rejected with an error naming the point and the vector, instead of being
+//! stored and only noticed later through unexpected scores.
+
+use api::rest::{BatchVectorStruct, Vector, VectorStruct};
+use segment::data_types::vectors::{VectorElementType, DEFAULT_VECTOR_NAME};
+use segment::types::PointIdType;
+
+use super::point_ops::{PointInsertOperationsInternal, PointOperations};
+use super::types::{CollectionError, CollectionResult, VectorIngestPolicy, VectorsConfig};
+use super::vector_ops::VectorOperations;
+use super::CollectionUpdateOperations;
+
+/// Applies the ingest policies of the collection vectors to the vectors of the operation.
+///
+/// Vectors without a policy, and sparse vectors, are left as they are.
+pub fn apply_ingest_policies(
+    operation: &mut CollectionUpdateOperations,
+    vectors_config: &VectorsConfig,
+) -> CollectionResult<()> {
+    let has_policies = vectors_config
+        .params_iter()
+        .any(|(_, params)| params.ingest.is_some());
+    if !has_policies {
+        return Ok(());
+    }
+
+    let policies = VectorPolicies(vectors_config);
+
+    match operation {
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        )) => {
+            for point in points {
+                policies.apply_to_struct(point.id, &mut point.vector)?;
+            }
+        }
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsBatch(batch),
+        )) => match &mut batch.vectors {
+            BatchVectorStruct::Single(vectors) => {
+                for (id, vector) in batch.ids.iter().zip(vectors) {
+                    policies.apply(*id, DEFAULT_VECTOR_NAME, vector)?;
+                }
+            }
+            BatchVectorStruct::MultiDense(vectors) => {
+                for (id, multi_vector) in batch.ids.iter().zip(vectors) {
+                    for vector in multi_vector {
+                        policies.apply(*id, DEFAULT_VECTOR_NAME, vector)?;
+                    }
+                }
+            }
+            BatchVectorStruct::Named(named_vectors) => {
+                for (name, vectors) in named_vectors {
+                    for (id, vector) in batch.ids.iter().zip(vectors) {
+                        policies.apply_to_vector(*id, name, vector)?;
+                    }
+                }
+            }
+        },
+        CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(update)) => {
+            for point in &mut update.points {
+                policies.apply_to_struct(point.id, &mut point.vector)?;
+            }
+        }
+        CollectionUpdateOperations::PointOperation(_)
+        | CollectionUpdateOperations::VectorOperation(_)
+        | CollectionUpdateOperations::PayloadOperation(_)
+        | CollectionUpdateOperations::FieldIndexOperation(_) => {}
+    }
+
+    Ok(())
+}
+
+struct VectorPolicies<'a>(&'a VectorsConfig);
+
+impl VectorPolicies<'_> {
+    fn apply_to_struct(&self, id: PointIdType, vector: &mut VectorStruct) -> CollectionResult<()> {
+        match vector {
+            VectorStruct::Single(vector) => self.apply(id, DEFAULT_VECTOR_NAME, vector),
+            VectorStruct::MultiDense(multi_vector) => multi_vector
+                .iter_mut()
+                .try_for_each(|vector| self.apply(id, DEFAULT_VECTOR_NAME, vector)),
+            VectorStruct::Named(named_vectors) => named_vectors
+                .iter_mut()
+                .try_for_each(|(name, vector)| self.apply_to_vector(id, name, vector)),
+        }
+    }
+
+    fn apply_to_vector(
+        &self,
+        id: PointIdType,
+        name: &str,
+        vector: &mut Vector,
+    ) -> CollectionResult<()> {
+        match vector {
+            Vector::Dense(vector) => self.apply(id, name, vector),
+            Vector::MultiDense(multi_vector) => multi_vector
+                .iter_mut()
+                .try_for_each(|vector| self.apply(id, name, vector)),
+            Vector::Sparse(_) => Ok(()),
+        }
+    }
+
+    fn apply(
+        &self,
+        id: PointIdType,
+        name: &str,
+        vector: &mut [VectorElementType],
+    ) -> CollectionResult<()> {
+        let Some(params) = self.0.get_params(name) else {
+            return Ok(());
+        };
+        let Some(policy) = &params.ingest else {
+            return Ok(());
+        };
+        apply_policy(policy, params.size.get() as usize, vector).map_err(|reason| {
+            CollectionError::bad_input(format!(
+                "Vector {} of point {id} {reason}",
+                display_name(name),
+            ))
+        })
+    }
+}
+
+fn display_name(name: &str) -> String {
+    if name == DEFAULT_VECTOR_NAME {
+        "<default>".to_string()
+    } else {
+        format!("`{name}`")
+    }
+}
+
+/// Checks the vector against the policy, then normalizes it if required.
+///
+/// On rejection, returns the reason, to be prefixed with the vector it applies to.
+fn apply_policy(
+    policy: &VectorIngestPolicy,
+    size: usize,
+    vector: &mut [VectorElementType],
+) -> Result<(), String> {
+    if vector.len() != size {
+        return Err(format!("has dimension {}, expected {size}", vector.len()));
+    }
+
+    if policy.reject_non_finite && vector.iter().any(|value| !value.is_finite()) {
+        return Err("contains NaN or infinite values".to_string());
+    }
+
+    if policy.reject_zero && vector.iter().all(|value| *value == 0.0) {
+        return Err("is a zero vector".to_string());
+    }
+
+    if policy.normalize {
+        let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+        if norm > 0.0 && norm.is_finite() {
+            vector.iter_mut().for_each(|value| *value /= norm);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::rest::DenseVector;
+    use segment::types::Distance;
+
+    use super::*;
+    use crate::operations::point_ops::PointStruct;
+    use crate::operations::vector_params_builder::VectorParamsBuilder;
+
+    #[test]
+    fn test_apply_policy() {
+        let policy = VectorIngestPolicy {
+            normalize: true,
+            reject_non_finite: true,
+            reject_zero: true,
+        };
+
+        let mut vector = vec![3.0, 4.0];
+        apply_policy(&policy, 2, &mut vector).unwrap();
+        assert_eq!(vector, vec![0.6, 0.8]);
+
+        assert_eq!(
+            apply_policy(&policy, 3, &mut vec![3.0, 4.0]).unwrap_err(),
+            "has dimension 2, expected 3",
+        );
+        assert!(apply_policy(&policy, 2, &mut vec![f32::NAN, 1.0]).is_err());
+        assert!(apply_policy(&policy, 2, &mut vec![f32::INFINITY, 1.0]).is_err());
+        assert!(apply_policy(&policy, 2, &mut vec![0.0, 0.0]).is_err());
+
+        // Without the checks, bad vectors are stored as they are
+        let policy = VectorIngestPolicy::default();
+        let mut vector = vec![0.0, f32::NAN];
+        apply_policy(&policy, 2, &mut vector).unwrap();
+        assert!(vector[1].is_nan());
+    }
+
+    #[test]
+    fn test_apply_ingest_policies() {
+        let policy = VectorIngestPolicy {
+            normalize: true,
+            reject_non_finite: true,
+            reject_zero: true,
+        };
+        let vectors_config = VectorsConfig::Multi(
+            [
+                (
+                    "image".to_string(),
+                    VectorParamsBuilder::new(2, Distance::Dot)
+                        .with_ingest(policy)
+                        .build(),
+                ),
+                (
+                    "text".to_string(),
+                    VectorParamsBuilder::new(2, Distance::Dot).build(),
+                ),
+            ]
+            .into(),
+        );
+
+        let upsert = |image: DenseVector, text: DenseVector| {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                    id: 1.into(),
+                    vector: VectorStruct::Named(HashMap::from([
+                        ("image".to_string(), Vector::Dense(image)),
+                        ("text".to_string(), Vector::Dense(text)),
+                    ])),
+                    payload: None,
+                }]),
+            ))
+        };
+
+        let mut operation = upsert(vec![0.0, 2.0], vec![0.0, 2.0]);
+        apply_ingest_policies(&mut operation, &vectors_config).unwrap();
+        let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        )) = operation
+        else {
+            panic!("unexpected operation");
+        };
+        let VectorStruct::Named(vectors) = &points[0].vector else {
+            panic!("unexpected vectors");
+        };
+        assert_eq!(vectors["image"], Vector::Dense(vec![0.0, 1.0]));
+        assert_eq!(vectors["text"], Vector::Dense(vec![0.0, 2.0]));
+
+        let mut operation = upsert(vec![0.0, 0.0], vec![0.0, 0.0]);
+        let err = apply_ingest_policies(&mut operation, &vectors_config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Vector `image` of point 1 is a zero vector"),
+            "{err}",
+        );
+
+        let mut operation = upsert(vec![1.0, 2.0], vec![0.0, 0.0]);
+        apply_ingest_policies(&mut operation, &vectors_config).unwrap();
+    }
+}
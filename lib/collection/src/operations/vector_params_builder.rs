@@ -3,7 +3,7 @@ use std::num::NonZeroU64;
 use segment::types::{Distance, MultiVectorConfig, QuantizationConfig};
 
 use crate::operations::config_diff::HnswConfigDiff;
-use crate::operations::types::{Datatype, VectorParams};
+use crate::operations::types::{Datatype, VectorIngestPolicy, VectorParams};
 
 pub struct VectorParamsBuilder {
     vector_params: VectorParams,
@@ -20,6 +20,7 @@ impl VectorParamsBuilder {
                 on_disk: None,
                 datatype: None,
                 multivector_config: None,
+                ingest: None,
             },
         }
     }
@@ -49,6 +50,11 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_ingest(mut self, ingest: VectorIngestPolicy) -> Self {
+        self.vector_params.ingest = Some(ingest);
+        self
+    }
+
     pub fn build(self) -> VectorParams {
         self.vector_params
     }
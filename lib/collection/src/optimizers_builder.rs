@@ -1,5 +1,6 @@
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use schemars::JsonSchema;
 use segment::index::hnsw_index::num_rayon_threads;
@@ -10,15 +11,23 @@ use validator::Validate;
 use crate::collection_manager::optimizers::config_mismatch_optimizer::ConfigMismatchOptimizer;
 use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
 use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
-use crate::collection_manager::optimizers::segment_optimizer::OptimizerThresholds;
+use crate::collection_manager::optimizers::segment_optimizer::{
+    OptimizerThresholds, SegmentNumber,
+};
 use crate::collection_manager::optimizers::vacuum_optimizer::VacuumOptimizer;
 use crate::config::CollectionParams;
 use crate::update_handler::Optimizer;
 
 const DEFAULT_MAX_SEGMENT_PER_CPU_KB: usize = 200_000;
 pub const DEFAULT_INDEXING_THRESHOLD_KB: usize = 20_000;
+/// Smallest threshold, so that empty appendable segments are not indexed over and over again
+const FROZEN_INDEXING_THRESHOLD_KB: usize = 1;
 const SEGMENTS_PATH: &str = "segments";
 const TEMP_SEGMENTS_PATH: &str = "temp_segments";
+/// Directory in the temp segments directory with checkpoints of interrupted HNSW index builds
+pub const HNSW_CHECKPOINTS_PATH: &str = "hnsw_checkpoints";
+/// Checkpoints not resumed from within this time are unlikely to be resumed anymore
+const HNSW_CHECKPOINT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 pub struct OptimizersConfig {
@@ -35,7 +44,7 @@ pub struct OptimizersConfig {
     ///
     /// It is recommended to select default number of segments as a factor of the number of search threads,
     /// so that each segment would be handled evenly by one of the threads.
-    /// If `default_segment_number = 0`, will be automatically selected by the number of available CPUs.
+    /// If `default_segment_number = 0`, will be automatically adjusted to the size of the collection and the number of available CPUs.
     pub default_segment_number: usize,
     /// Do not create segments larger this size (in kilobytes).
     /// Large segments might require disproportionately long indexation times,
@@ -94,17 +103,33 @@ impl OptimizersConfig {
         }
     }
 
-    pub fn get_number_segments(&self) -> usize {
+    /// Configuration of the optimizers of frozen collections. Indexes segments of any size, and
+    /// merges segments into as few as the maximal segment size allows.
+    pub fn frozen(&self) -> Self {
+        Self {
+            default_segment_number: 1,
+            indexing_threshold: Some(FROZEN_INDEXING_THRESHOLD_KB),
+            ..self.clone()
+        }
+    }
+
+    /// Target number of segments the optimizers try to keep
+    pub fn segment_number(&self) -> SegmentNumber {
         if self.default_segment_number == 0 {
-            let num_cpus = common::cpu::get_num_cpus();
-            // Do not configure less than 2 and more than 8 segments
-            // until it is not explicitly requested
-            num_cpus.clamp(2, 8)
+            SegmentNumber::Auto
         } else {
-            self.default_segment_number
+            SegmentNumber::Fixed(self.default_segment_number)
         }
     }
 
+    /// Number of segments to create in a new, empty shard
+    ///
+    /// With the automatic number of segments, this is the target for an empty shard, so the merge
+    /// optimizer doesn't merge them right away. The optimizers adjust it as the shard grows.
+    pub fn get_number_segments(&self) -> usize {
+        self.segment_number().target(0)
+    }
+
     pub fn optimizer_thresholds(&self, num_indexing_threads: usize) -> OptimizerThresholds {
         let indexing_threshold_kb = match self.indexing_threshold {
             None => DEFAULT_INDEXING_THRESHOLD_KB, // default value
@@ -133,18 +158,56 @@ impl OptimizersConfig {
     }
 }
 
+/// Remove the leftovers of interrupted optimizations, except recent checkpoints of HNSW index
+/// builds, so the optimizations can resume from them
 pub fn clear_temp_segments(shard_path: &Path) {
     let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
-    if temp_segments_path.exists() {
-        log::debug!("Removing temp_segments directory: {:?}", temp_segments_path);
-        if let Err(err) = std::fs::remove_dir_all(&temp_segments_path) {
+    if !temp_segments_path.exists() {
+        return;
+    }
+    log::debug!("Clearing temp_segments directory: {:?}", temp_segments_path);
+
+    let entries = match std::fs::read_dir(&temp_segments_path) {
+        Ok(entries) => entries,
+        Err(err) => {
             log::warn!(
-                "Failed to remove temp_segments directory: {:?}, error: {:?}",
+                "Failed to read temp_segments directory: {:?}, error: {:?}",
                 temp_segments_path,
                 err
             );
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let result = if entry.file_name() == HNSW_CHECKPOINTS_PATH {
+            clear_stale_hnsw_checkpoints(&path)
+        } else if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        if let Err(err) = result {
+            log::warn!(
+                "Failed to remove temp segment: {:?}, error: {:?}",
+                path,
+                err
+            );
+        }
+    }
+}
+
+fn clear_stale_hnsw_checkpoints(checkpoints_path: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(checkpoints_path)? {
+        let entry = entry?;
+        let age = entry.metadata()?.modified()?.elapsed().unwrap_or_default();
+        if age > HNSW_CHECKPOINT_MAX_AGE {
+            log::debug!("Removing stale HNSW build checkpoint: {:?}", entry.path());
+            std::fs::remove_file(entry.path())?;
         }
     }
+    Ok(())
 }
 
 pub fn build_optimizers(
@@ -154,6 +217,11 @@ pub fn build_optimizers(
     hnsw_config: &HnswConfig,
     quantization_config: &Option<QuantizationConfig>,
 ) -> Arc<Vec<Arc<Optimizer>>> {
+    let optimizers_config = if collection_params.frozen {
+        optimizers_config.frozen()
+    } else {
+        optimizers_config.clone()
+    };
     let num_indexing_threads = num_rayon_threads(hnsw_config.max_indexing_threads);
     let segments_path = shard_path.join(SEGMENTS_PATH);
     let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
@@ -161,7 +229,7 @@ pub fn build_optimizers(
 
     Arc::new(vec![
         Arc::new(MergeOptimizer::new(
-            optimizers_config.get_number_segments(),
+            optimizers_config.segment_number(),
             threshold_config,
             segments_path.clone(),
             temp_segments_path.clone(),
@@ -170,7 +238,7 @@ pub fn build_optimizers(
             quantization_config.clone(),
         )),
         Arc::new(IndexingOptimizer::new(
-            optimizers_config.get_number_segments(),
+            optimizers_config.segment_number(),
             threshold_config,
             segments_path.clone(),
             temp_segments_path.clone(),
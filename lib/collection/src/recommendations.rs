@@ -26,7 +26,7 @@ use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, RecommendExample,
     RecommendRequestInternal, UsingVector,
 };
 
@@ -99,6 +99,59 @@ fn avg_vectors<'a>(vectors: impl IntoIterator<Item = VectorRef<'a>>) -> Collecti
     }
 }
 
+/// Sum of vectors multiplied by their weights, all vectors must be dense or sparse
+pub fn weighted_sum_vectors(
+    vectors: impl IntoIterator<Item = (Vector, f32)>,
+) -> CollectionResult<Vector> {
+    let mut vectors = vectors.into_iter();
+    let Some((first, first_weight)) = vectors.next() else {
+        return Err(CollectionError::bad_input(
+            "Sum of vectors should not be empty".to_owned(),
+        ));
+    };
+
+    let mut sum = match first {
+        Vector::Dense(vector) => Vector::Dense(vector.iter().map(|v| v * first_weight).collect()),
+        Vector::Sparse(mut vector) => {
+            vector.values.iter_mut().for_each(|v| *v *= first_weight);
+            Vector::Sparse(vector)
+        }
+        Vector::MultiDense(_) => {
+            return Err(CollectionError::bad_input(
+                "Multivectors can't be summed".to_owned(),
+            ))
+        }
+    };
+
+    for (vector, weight) in vectors {
+        match (&mut sum, vector) {
+            (Vector::Dense(sum), Vector::Dense(vector)) => {
+                if sum.len() != vector.len() {
+                    return Err(CollectionError::bad_input(format!(
+                        "Can't sum vectors of different dimensions: {} and {}",
+                        sum.len(),
+                        vector.len(),
+                    )));
+                }
+                sum.iter_mut()
+                    .zip(vector)
+                    .for_each(|(acc, v)| *acc += v * weight);
+            }
+            (Vector::Sparse(sum), Vector::Sparse(vector)) => {
+                *sum = sum.combine_aggregate(&vector, |acc, v| acc + v * weight);
+            }
+            _ => {
+                return Err(CollectionError::bad_input(
+                    "Summed vectors should be of the same type, either all dense or all sparse"
+                        .to_owned(),
+                ))
+            }
+        }
+    }
+
+    Ok(sum)
+}
+
 fn merge_positive_and_negative_avg(positive: Vector, negative: Vector) -> CollectionResult<Vector> {
     match (positive, negative) {
         (Vector::Dense(positive), Vector::Dense(negative)) => {
@@ -169,9 +222,24 @@ where
 
 pub fn recommend_into_core_search(
     collection_name: &str,
-    request: RecommendRequestInternal,
+    mut request: RecommendRequestInternal,
     all_vectors_records_map: &ReferencedVectors,
 ) -> CollectionResult<CoreSearchRequest> {
+    let lookup_collection = request.lookup_from.as_ref().map(|x| x.collection.clone());
+    let lookup_collection_name = lookup_collection.as_ref();
+
+    if request.skip_missing.unwrap_or_default() {
+        let is_fetched = |example: &RecommendExample| match example.as_point_id() {
+            Some(point_id) => all_vectors_records_map
+                .get(&lookup_collection_name, point_id)
+                .is_some(),
+            None => true,
+        };
+        request.positive.retain(is_fetched);
+        request.negative.retain(is_fetched);
+        check_examples_count(&request)?;
+    }
+
     let reference_vectors_ids = request
         .positive
         .iter()
@@ -179,18 +247,11 @@ pub fn recommend_into_core_search(
         .filter_map(|example| example.as_point_id())
         .collect_vec();
 
-    let lookup_collection_name = request.lookup_from.as_ref().map(|x| &x.collection);
-
-    for &point_id in &reference_vectors_ids {
-        if all_vectors_records_map
-            .get(&lookup_collection_name, point_id)
-            .is_none()
-        {
-            return Err(CollectionError::PointNotFound {
-                missed_point_id: point_id,
-            });
-        }
-    }
+    all_vectors_records_map.check_fetched(
+        lookup_collection_name,
+        reference_vectors_ids.iter().copied(),
+        collection_name,
+    )?;
 
     // do not exclude vector ids from different lookup collection
     let reference_vectors_ids_to_exclude = match lookup_collection_name {
@@ -212,6 +273,30 @@ pub fn recommend_into_core_search(
     }
 }
 
+/// Check that the request has enough examples for its strategy
+fn check_examples_count(request: &RecommendRequestInternal) -> CollectionResult<()> {
+    match request.strategy.unwrap_or_default() {
+        RecommendStrategy::AverageVector => {
+            if request.positive.is_empty() {
+                return Err(CollectionError::BadRequest {
+                    description: "At least one positive vector ID required with this strategy"
+                        .to_owned(),
+                });
+            }
+        }
+        RecommendStrategy::BestScore => {
+            if request.positive.is_empty() && request.negative.is_empty() {
+                return Err(CollectionError::BadRequest {
+                    description:
+                        "At least one positive or negative vector ID required with this strategy"
+                            .to_owned(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Search points in a collection by already existing points in this or another collection.
 ///
 /// Function works in following stages:
@@ -245,27 +330,9 @@ where
     }
 
     // Validate amount of examples
-    request_batch.iter().try_for_each(|(request, _)| {
-        match request.strategy.unwrap_or_default() {
-            RecommendStrategy::AverageVector => {
-                if request.positive.is_empty() {
-                    return Err(CollectionError::BadRequest {
-                        description: "At least one positive vector ID required with this strategy"
-                            .to_owned(),
-                    });
-                }
-            }
-            RecommendStrategy::BestScore => {
-                if request.positive.is_empty() && request.negative.is_empty() {
-                    return Err(CollectionError::BadRequest {
-                        description: "At least one positive or negative vector ID required with this strategy"
-                            .to_owned(),
-                    });
-                }
-            }
-        }
-        Ok(())
-    })?;
+    request_batch
+        .iter()
+        .try_for_each(|(request, _)| check_examples_count(request))?;
 
     let all_vectors_records_map = resolve_referenced_vectors_batch(
         &request_batch,
@@ -404,6 +471,7 @@ fn recommend_by_best_score(
         score_threshold,
         using,
         lookup_from,
+        skip_missing: _,
     } = request;
 
     let lookup_collection_name = lookup_from.as_ref().map(|x| &x.collection);
@@ -453,7 +521,7 @@ mod tests {
     use segment::data_types::vectors::{Vector, VectorRef};
     use sparse::common::sparse_vector::SparseVector;
 
-    use super::avg_vectors;
+    use super::{avg_vectors, weighted_sum_vectors};
 
     #[test]
     fn test_avg_vectors() {
@@ -490,4 +558,55 @@ mod tests {
         ];
         assert!(avg_vectors(vectors.iter().map(VectorRef::from)).is_err());
     }
+
+    #[test]
+    fn test_weighted_sum_vectors() {
+        let vectors: Vec<(Vector, f32)> = vec![
+            (vec![1.0, 2.0, 3.0].into(), 1.0),
+            (vec![1.0, 1.0, 1.0].into(), -1.0),
+            (vec![0.0, 1.0, 0.0].into(), 0.5),
+        ];
+        assert_eq!(
+            weighted_sum_vectors(vectors).unwrap(),
+            vec![0.0, 1.5, 2.0].into(),
+        );
+
+        let vectors: Vec<(Vector, f32)> = vec![
+            (
+                SparseVector::new(vec![0, 1], vec![1.0, 2.0])
+                    .unwrap()
+                    .into(),
+                2.0,
+            ),
+            (
+                SparseVector::new(vec![1, 2], vec![1.0, 1.0])
+                    .unwrap()
+                    .into(),
+                -1.0,
+            ),
+        ];
+        assert_eq!(
+            weighted_sum_vectors(vectors).unwrap(),
+            SparseVector::new(vec![0, 1, 2], vec![2.0, 3.0, -1.0])
+                .unwrap()
+                .into(),
+        );
+
+        let vectors: Vec<(Vector, f32)> = vec![
+            (vec![1.0, 2.0, 3.0].into(), 1.0),
+            (vec![1.0, 2.0].into(), 1.0),
+        ];
+        assert!(weighted_sum_vectors(vectors).is_err());
+
+        let vectors: Vec<(Vector, f32)> = vec![
+            (vec![1.0, 2.0, 3.0].into(), 1.0),
+            (
+                SparseVector::new(vec![0, 1, 2], vec![0.0, 0.1, 0.2])
+                    .unwrap()
+                    .into(),
+                1.0,
+            ),
+        ];
+        assert!(weighted_sum_vectors(vectors).is_err());
+    }
 }
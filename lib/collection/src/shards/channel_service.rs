@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use api::grpc::qdrant::qdrant_internal_client::QdrantInternalClient;
 use api::grpc::qdrant::WaitOnConsensusCommitRequest;
-use api::grpc::transport_channel_pool::{AddTimeout, TransportChannelPool};
+use api::grpc::transport_channel_pool::{AddTimeout, RetryPolicy, TransportChannelPool};
 use futures::future::try_join_all;
 use futures::Future;
 use semver::Version;
@@ -27,6 +27,10 @@ pub struct ChannelService {
     pub current_rest_port: u16,
     /// Instance wide API key if configured, must be used with care.
     pub api_key: Option<String>,
+    /// Retries of search and query requests to shards on other peers
+    pub read_retry_policy: RetryPolicy,
+    /// Number of retried search and query requests, by peer
+    read_retries: Arc<parking_lot::Mutex<HashMap<PeerId, usize>>>,
 }
 
 impl ChannelService {
@@ -38,9 +42,20 @@ impl ChannelService {
             channel_pool: Default::default(),
             current_rest_port,
             api_key,
+            read_retry_policy: Default::default(),
+            read_retries: Default::default(),
         }
     }
 
+    pub fn report_read_retry(&self, peer_id: PeerId) {
+        *self.read_retries.lock().entry(peer_id).or_default() += 1;
+    }
+
+    /// Number of retried search and query requests, by peer
+    pub fn read_retries(&self) -> HashMap<PeerId, usize> {
+        self.read_retries.lock().clone()
+    }
+
     pub async fn remove_peer(&self, peer_id: PeerId) {
         let removed = self.id_to_address.write().remove(&peer_id);
         if let Some(uri) = removed {
@@ -211,6 +226,8 @@ impl Default for ChannelService {
             channel_pool: Default::default(),
             current_rest_port: 6333,
             api_key: None,
+            read_retry_policy: Default::default(),
+            read_retries: Default::default(),
         }
     }
 }
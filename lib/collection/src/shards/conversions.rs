@@ -1,19 +1,26 @@
-use api::grpc::conversions::{convert_shard_key_from_grpc_opt, payload_to_proto};
+use std::collections::HashMap;
+
+use api::grpc::conversions::{
+    convert_shard_key_from_grpc_opt, json_path_from_proto, payload_to_proto,
+};
 use api::grpc::qdrant::points_selector::PointsSelectorOneOf;
 use api::grpc::qdrant::{
     ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
-    DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
+    DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal,
+    FieldSumInternal, GroupAggregatesInternal, PointVectors, PointsIdsList, PointsSelector,
+    SetPayloadPoints, SetPayloadPointsInternal, ShardGroupAggregatesInternal, SyncPoints,
     SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
     UpsertPointsInternal, VectorsSelector,
 };
+use segment::data_types::groups::GroupId;
 use segment::data_types::vectors::VectorStructInternal;
 use segment::json_path::JsonPath;
 use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
 use tonic::Status;
 
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
 use crate::operations::conversions::write_ordering_to_proto;
 use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
 use crate::operations::point_ops::{
@@ -35,6 +42,7 @@ pub fn internal_sync_points(
     Ok(SyncPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         sync_points: Some(SyncPoints {
             collection_name,
             wait: Some(wait),
@@ -61,6 +69,7 @@ pub fn internal_upsert_points(
     Ok(UpsertPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         upsert_points: Some(UpsertPoints {
             collection_name,
             wait: Some(wait),
@@ -88,6 +97,7 @@ pub fn internal_delete_points(
     DeletePointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         delete_points: Some(DeletePoints {
             collection_name,
             wait: Some(wait),
@@ -113,6 +123,7 @@ pub fn internal_delete_points_by_filter(
     DeletePointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         delete_points: Some(DeletePoints {
             collection_name,
             wait: Some(wait),
@@ -136,6 +147,7 @@ pub fn internal_update_vectors(
     UpdateVectorsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         update_vectors: Some(UpdatePointVectors {
             collection_name,
             wait: Some(wait),
@@ -165,6 +177,7 @@ pub fn internal_delete_vectors(
     DeleteVectorsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         delete_vectors: Some(DeletePointVectors {
             collection_name,
             wait: Some(wait),
@@ -194,6 +207,7 @@ pub fn internal_delete_vectors_by_filter(
     DeleteVectorsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         delete_vectors: Some(DeletePointVectors {
             collection_name,
             wait: Some(wait),
@@ -232,6 +246,7 @@ pub fn internal_set_payload(
     SetPayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         set_payload_points: Some(SetPayloadPoints {
             collection_name,
             wait: Some(wait),
@@ -267,6 +282,7 @@ pub fn internal_delete_payload(
     DeletePayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         delete_payload_points: Some(DeletePayloadPoints {
             collection_name,
             wait: Some(wait),
@@ -293,6 +309,7 @@ pub fn internal_clear_payload(
     ClearPayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         clear_payload_points: Some(ClearPayloadPoints {
             collection_name,
             wait: Some(wait),
@@ -318,6 +335,7 @@ pub fn internal_clear_payload_by_filter(
     ClearPayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         clear_payload_points: Some(ClearPayloadPoints {
             collection_name,
             wait: Some(wait),
@@ -384,6 +402,7 @@ pub fn internal_create_index(
     CreateFieldIndexCollectionInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         create_field_index_collection: Some(CreateFieldIndexCollection {
             collection_name,
             wait: Some(wait),
@@ -406,6 +425,7 @@ pub fn internal_delete_index(
     DeleteFieldIndexCollectionInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        update_id: None,
         delete_field_index_collection: Some(DeleteFieldIndexCollection {
             collection_name,
             wait: Some(wait),
@@ -444,5 +464,93 @@ pub fn try_scored_point_from_grpc(
         vector,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
         order_value: point.order_value.map(TryFrom::try_from).transpose()?,
+        prefetch_matches: (!point.prefetch_matches.is_empty())
+            .then(|| point.prefetch_matches.into_iter().map(From::from).collect()),
+        routing: point.routing.map(From::from),
+        raw_score: point.raw_score,
+    })
+}
+
+pub fn internal_group_aggregates(
+    shard_id: Option<ShardId>,
+    collection_name: String,
+    request: &GroupAggregatesRequestInternal,
+) -> GroupAggregatesInternal {
+    GroupAggregatesInternal {
+        collection_name,
+        filter: request.filter.clone().map(Into::into),
+        group_by: request.group_by.to_string(),
+        group_ids: request.group_ids.iter().cloned().map(Into::into).collect(),
+        mean: request.mean.iter().map(ToString::to_string).collect(),
+        shard_id,
+    }
+}
+
+pub fn try_group_aggregates_request_from_grpc(
+    request: GroupAggregatesInternal,
+) -> Result<GroupAggregatesRequestInternal, Status> {
+    let GroupAggregatesInternal {
+        collection_name: _,
+        filter,
+        group_by,
+        group_ids,
+        mean,
+        shard_id: _,
+    } = request;
+
+    Ok(GroupAggregatesRequestInternal {
+        filter: filter.map(Filter::try_from).transpose()?,
+        group_by: json_path_from_proto(&group_by)?,
+        group_ids: group_ids
+            .into_iter()
+            .map(GroupId::try_from)
+            .collect::<Result<_, _>>()?,
+        mean: mean
+            .iter()
+            .map(|path| json_path_from_proto(path))
+            .collect::<Result<_, _>>()?,
     })
 }
+
+pub fn group_aggregates_to_grpc(
+    groups: HashMap<GroupId, ShardGroupAggregates>,
+) -> Vec<ShardGroupAggregatesInternal> {
+    groups
+        .into_iter()
+        .map(|(group_id, aggregates)| ShardGroupAggregatesInternal {
+            id: Some(group_id.into()),
+            count: aggregates.count as u64,
+            sums: aggregates
+                .sums
+                .into_iter()
+                .map(|(field, (sum, count))| {
+                    let count = count as u64;
+                    (field, FieldSumInternal { sum, count })
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn try_group_aggregates_from_grpc(
+    groups: Vec<ShardGroupAggregatesInternal>,
+) -> Result<HashMap<GroupId, ShardGroupAggregates>, Status> {
+    groups
+        .into_iter()
+        .map(|group| {
+            let group_id = group
+                .id
+                .ok_or_else(|| Status::invalid_argument("Group id is missing"))?
+                .try_into()?;
+            let aggregates = ShardGroupAggregates {
+                count: group.count as usize,
+                sums: group
+                    .sums
+                    .into_iter()
+                    .map(|(field, sum)| (field, (sum.sum, sum.count as usize)))
+                    .collect(),
+            };
+            Ok((group_id, aggregates))
+        })
+        .collect()
+}
@@ -1,14 +1,18 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::OrderBy;
+use segment::index::search_stats::SearchStats;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
     CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
@@ -48,6 +52,7 @@ impl DummyShard {
             variant_name: Some("dummy shard".into()),
             segments: vec![],
             optimizations: Default::default(),
+            search_pools: Default::default(),
         }
     }
 
@@ -93,6 +98,13 @@ impl ShardOperation for DummyShard {
         self.dummy()
     }
 
+    async fn group_aggregates(
+        &self,
+        _: Arc<GroupAggregatesRequestInternal>,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        self.dummy()
+    }
+
     async fn retrieve(
         &self,
         _: Arc<PointRequestInternal>,
@@ -107,6 +119,7 @@ impl ShardOperation for DummyShard {
         _requests: Arc<Vec<ShardQueryRequest>>,
         _search_runtime_handle: &Handle,
         _timeout: Option<Duration>,
+        _search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         self.dummy()
     }
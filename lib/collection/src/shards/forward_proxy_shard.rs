@@ -1,10 +1,13 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::OrderBy;
+use segment::index::search_stats::SearchStats;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -14,6 +17,7 @@ use tokio::sync::Mutex;
 
 use super::shard::ShardId;
 use super::update_tracker::UpdateTracker;
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
 use crate::hash_ring::HashRing;
 use crate::operations::point_ops::{
     PointInsertOperationsInternal, PointOperations, PointStruct, PointSyncOperation,
@@ -286,6 +290,15 @@ impl ShardOperation for ForwardProxyShard {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `group_aggregates` to `wrapped_shard`
+    async fn group_aggregates(
+        &self,
+        request: Arc<GroupAggregatesRequestInternal>,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.group_aggregates(request).await
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -303,10 +316,11 @@ impl ShardOperation for ForwardProxyShard {
         requests: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .query_batch(requests, search_runtime_handle, timeout)
+            .query_batch(requests, search_runtime_handle, timeout, search_stats)
             .await
     }
 }
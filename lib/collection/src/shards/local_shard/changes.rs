@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use segment::types::{PointIdType, SeqNumberType, WithPayload, WithVector};
+use tokio::sync::oneshot;
+
+use super::LocalShard;
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::types::{CollectionError, CollectionResult, Record};
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::CollectionUpdateOperations;
+use crate::update_handler::UpdateSignal;
+
+/// Points changed in the shard since some WAL position
+#[derive(Debug)]
+pub struct ShardChanges {
+    /// Changed points with the number of the last operation which changed them, ordered by it.
+    /// Record is `None` if the point was deleted.
+    pub changes: Vec<(PointIdType, SeqNumberType, Option<Record>)>,
+    /// WAL position to read the next changes from
+    pub next_cursor: SeqNumberType,
+}
+
+/// Points touched by a single operation
+enum ChangedPoints {
+    Ids(Vec<PointIdType>),
+    /// Points are selected by filter, but can only be updated, not deleted
+    Filtered,
+    /// Points are selected by filter and may be deleted, there is no way to know which ones
+    Unknown,
+}
+
+fn changed_points(operation: &CollectionUpdateOperations) -> ChangedPoints {
+    match operation {
+        CollectionUpdateOperations::PointOperation(point_operation) => match point_operation {
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsBatch(batch)) => {
+                ChangedPoints::Ids(batch.ids.clone())
+            }
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)) => {
+                ChangedPoints::Ids(points.iter().map(|point| point.id).collect())
+            }
+            PointOperations::DeletePoints { ids } => ChangedPoints::Ids(ids.clone()),
+            PointOperations::DeletePointsByFilter(_) | PointOperations::SyncPoints(_) => {
+                ChangedPoints::Unknown
+            }
+        },
+        CollectionUpdateOperations::VectorOperation(vector_operation) => match vector_operation {
+            VectorOperations::UpdateVectors(operation) => {
+                ChangedPoints::Ids(operation.points.iter().map(|point| point.id).collect())
+            }
+            VectorOperations::DeleteVectors(ids, _) => ChangedPoints::Ids(ids.points.clone()),
+            VectorOperations::DeleteVectorsByFilter(..) => ChangedPoints::Filtered,
+        },
+        CollectionUpdateOperations::PayloadOperation(payload_operation) => {
+            let points = match payload_operation {
+                PayloadOps::SetPayload(op) | PayloadOps::OverwritePayload(op) => &op.points,
+                PayloadOps::DeletePayload(op) => &op.points,
+                PayloadOps::ClearPayload { points } => return ChangedPoints::Ids(points.clone()),
+                PayloadOps::ClearPayloadByFilter(_) => return ChangedPoints::Filtered,
+            };
+            match points {
+                Some(points) => ChangedPoints::Ids(points.clone()),
+                None => ChangedPoints::Filtered,
+            }
+        }
+        CollectionUpdateOperations::FieldIndexOperation(_) => ChangedPoints::Ids(Vec::new()),
+    }
+}
+
+impl LocalShard {
+    /// Read points changed by operations starting from the `cursor` WAL position
+    ///
+    /// Changes are collected from whole operations, until at least `limit` points are found.
+    /// If `cursor` is `None`, no changes are returned, only the position of the current state.
+    pub async fn changes_since(
+        &self,
+        cursor: Option<SeqNumberType>,
+        limit: usize,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> CollectionResult<ShardChanges> {
+        // Position right after the last operation written to WAL.
+        // Operations are sent to the update handler under WAL lock, so the plunger below is
+        // queued after all of them.
+        let wal_end = {
+            let wal = self.wal.wal.lock();
+            wal.first_index() + wal.len(false)
+        };
+
+        let Some(cursor) = cursor else {
+            return Ok(ShardChanges {
+                changes: Vec::new(),
+                next_cursor: wal_end,
+            });
+        };
+
+        // Make sure all operations we are going to read are applied to segments
+        let (tx, rx) = oneshot::channel();
+        self.update_sender
+            .load()
+            .send(UpdateSignal::Plunger(tx))
+            .await?;
+        rx.await?;
+
+        let wal = self.wal.wal.clone();
+        let segments = self.segments.clone();
+        let with_payload = with_payload.clone();
+        let with_vector = with_vector.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut changed: HashMap<PointIdType, SeqNumberType> = HashMap::new();
+            let mut scan_filtered = false;
+            let mut next_cursor = cursor.max(wal_end);
+
+            {
+                let wal = wal.lock();
+
+                if cursor < wal.first_closed_index() {
+                    return Err(CollectionError::bad_request(format!(
+                        "Changes since {cursor} are no longer available, \
+                         full scroll is required to synchronize",
+                    )));
+                }
+
+                for (op_num, operation) in wal.read(cursor) {
+                    if op_num >= wal_end {
+                        break;
+                    }
+
+                    match changed_points(&operation.operation) {
+                        ChangedPoints::Ids(ids) => {
+                            changed.extend(ids.into_iter().map(|id| (id, op_num)));
+                        }
+                        ChangedPoints::Filtered => scan_filtered = true,
+                        ChangedPoints::Unknown => {
+                            return Err(CollectionError::bad_request(format!(
+                                "Operation {op_num} deleted points by filter, \
+                                 full scroll is required to synchronize",
+                            )));
+                        }
+                    }
+
+                    if changed.len() >= limit {
+                        next_cursor = op_num + 1;
+                        break;
+                    }
+                }
+            }
+
+            // Operations by filter don't tell which points they changed, look at point versions
+            if scan_filtered {
+                for (_, segment) in segments.read().iter() {
+                    let segment = segment.get();
+                    let segment = segment.read();
+                    for id in segment.iter_points() {
+                        let Some(version) = segment.point_version(id) else {
+                            continue;
+                        };
+                        if (cursor..next_cursor).contains(&version) {
+                            let known = changed.entry(id).or_insert(version);
+                            *known = (*known).max(version);
+                        }
+                    }
+                }
+            }
+
+            let ids: Vec<_> = changed.keys().copied().collect();
+            let mut records =
+                SegmentsSearcher::retrieve(&segments, &ids, &with_payload, &with_vector)?;
+
+            let mut changes: Vec<_> = changed
+                .into_iter()
+                .map(|(id, op_num)| {
+                    let record = records.remove(&id);
+                    let version = record
+                        .as_ref()
+                        .and_then(|record| record.version)
+                        .map_or(op_num, |version| version.max(op_num));
+                    (id, version, record)
+                })
+                .collect();
+            changes.sort_unstable_by_key(|(id, version, _)| (*version, *id));
+
+            Ok(ShardChanges {
+                changes,
+                next_cursor,
+            })
+        })
+        .await?
+    }
+}
@@ -141,7 +141,6 @@ impl ClockMap {
         }
     }
 
-    #[cfg(test)]
     pub fn current_tick(&self, peer_id: PeerId, clock_id: u32) -> Option<u64> {
         self.clocks
             .get(&Key::new(peer_id, clock_id))
@@ -198,7 +197,6 @@ impl Clock {
         (operation_accepted, self.current_tick)
     }
 
-    #[cfg(test)]
     fn current_tick(&self) -> u64 {
         self.current_tick
     }
@@ -266,6 +264,20 @@ impl RecoveryPoint {
         })
     }
 
+    /// Number of operations seen in this recovery point, but not in the `other`.
+    ///
+    /// A clock that is present in this recovery point, but not in the `other`, counts all of its
+    /// ticks.
+    pub fn count_ticks_ahead_of(&self, other: &Self) -> u64 {
+        self.clocks
+            .iter()
+            .map(|(key, &(tick, _token))| {
+                let other_tick = other.clocks.get(key).map_or(0, |&(tick, _token)| tick);
+                tick.saturating_sub(other_tick)
+            })
+            .sum()
+    }
+
     /// Extend this recovery point with clocks that are only present in the `other`.
     ///
     /// Clocks that are not present in this recovery point are initialized to the tick 1,
@@ -459,6 +471,22 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn recovery_point_count_ticks_ahead_of() {
+        let mut leader = RecoveryPoint::default();
+        leader.insert(1, 0, 10);
+        leader.insert(2, 0, 5);
+
+        let mut follower = RecoveryPoint::default();
+        follower.insert(1, 0, 7);
+        follower.insert(3, 0, 4);
+
+        // 3 ticks of the first clock, and all ticks of the clock unknown to the follower
+        assert_eq!(leader.count_ticks_ahead_of(&follower), 8);
+        assert_eq!(follower.count_ticks_ahead_of(&leader), 4);
+        assert_eq!(leader.count_ticks_ahead_of(&leader), 0);
+    }
+
     #[test]
     fn clock_map_serde_empty() {
         let input = ClockMap::default();
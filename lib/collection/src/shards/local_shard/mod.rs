@@ -1,11 +1,13 @@
+pub mod changes;
 pub mod clock_map;
 pub mod disk_usage_watcher;
 pub(super) mod query;
 pub(super) mod scroll;
 pub(super) mod search;
+pub mod segment_upgrade;
 pub(super) mod shard_ops;
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::mem::size_of;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
@@ -21,11 +23,14 @@ use common::types::TelemetryDetail;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
+use segment::common::hyperloglog::HyperLogLog;
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
+use segment::index::search_pools::SearchPoolCounters;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
+use segment::telemetry::HnswGraphTelemetry;
 use segment::types::{
     CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
     QuantizationConfig, SegmentConfig, SegmentType,
@@ -43,11 +48,12 @@ use super::update_tracker::UpdateTracker;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{
-    LockedSegment, LockedSegmentHolder, SegmentHolder,
+    LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
-use crate::collection_manager::optimizers::TrackerLog;
+use crate::collection_manager::optimizers::{OptimizationsProgress, TrackerLog};
 use crate::common::file_utils::{move_dir, move_file};
 use crate::config::CollectionConfig;
+use crate::operations::session_token::ShardSessionClock;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionInfoInternal,
@@ -58,7 +64,7 @@ use crate::optimizers_builder::{build_optimizers, clear_temp_segments, Optimizer
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::shard::ShardId;
 use crate::shards::shard_config::{ShardConfig, SHARD_CONFIG_FILE};
-use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
+use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry, SearchPoolsTelemetry};
 use crate::shards::CollectionId;
 use crate::update_handler::{Optimizer, UpdateHandler, UpdateSignal};
 use crate::wal::SerdeWal;
@@ -86,6 +92,11 @@ pub struct LocalShard {
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
     update_runtime: Handle,
     disk_usage_watcher: DiskUsageWatcher,
+    /// Last WAL operation and recovery point covered by the latest snapshot of the shard, since
+    /// it was loaded
+    snapshot_coverage: ParkingMutex<Option<(u64, RecoveryPoint)>>,
+    /// Reuse of pooled memory by searches on the segments of the shard
+    pub(super) search_pool_counters: Arc<SearchPoolCounters>,
 }
 
 /// Shard holds information about segments and WAL.
@@ -145,8 +156,12 @@ impl LocalShard {
         clocks: LocalShardClocks,
         update_runtime: Handle,
     ) -> Self {
-        let segment_holder = Arc::new(RwLock::new(segment_holder));
         let config = collection_config.read().await;
+        let mut segment_holder = segment_holder;
+        segment_holder
+            .point_history
+            .resize(config.params.point_history_size.unwrap_or(0) as usize);
+        let segment_holder = Arc::new(RwLock::new(segment_holder));
         let locked_wal = Arc::new(ParkingMutex::new(wal));
         let optimizers_log = Arc::new(ParkingMutex::new(Default::default()));
 
@@ -197,6 +212,8 @@ impl LocalShard {
             optimizers,
             optimizers_log,
             disk_usage_watcher,
+            snapshot_coverage: ParkingMutex::new(None),
+            search_pool_counters: Default::default(),
         }
     }
 
@@ -470,6 +487,7 @@ impl LocalShard {
                 } else {
                     PayloadStorageType::InMemory
                 },
+                payload_compression: config.params.payload_compression.clone(),
             };
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
@@ -703,6 +721,10 @@ impl LocalShard {
         update_handler.optimizers = new_optimizers;
         update_handler.flush_interval_sec = config.optimizer_config.flush_interval_sec;
         update_handler.max_optimization_threads = config.optimizer_config.max_optimization_threads;
+        self.segments
+            .write()
+            .point_history
+            .resize(config.params.point_history_size.unwrap_or(0) as usize);
         update_handler.run_workers(update_receiver);
         self.update_sender.load().send(UpdateSignal::Nop).await?;
 
@@ -772,6 +794,11 @@ impl LocalShard {
             rx.await?;
         }
 
+        // Operations written so far are in the snapshot, either in its segments or in its WAL.
+        // Clocks are advanced before writing to the WAL, so the recovery point covers them all.
+        let snapshot_wal_index = self.wal.wal.lock().last_index();
+        let snapshot_recovery_point = self.wal.recovery_point().await;
+
         let segments_path = Self::segments_path(&self.path);
         let collection_params = self.collection_config.read().await.params.clone();
         let temp_path = temp_path.to_owned();
@@ -804,9 +831,86 @@ impl LocalShard {
         let target_shard_config_path = snapshot_shard_path.join(SHARD_CONFIG_FILE);
         copy(&shard_config_path, &target_shard_config_path).await?;
 
+        *self.snapshot_coverage.lock() = Some((snapshot_wal_index, snapshot_recovery_point));
+
         Ok(())
     }
 
+    /// Last WAL operation and recovery point covered by the latest snapshot of the shard
+    pub fn snapshot_coverage(&self) -> Option<(u64, RecoveryPoint)> {
+        self.snapshot_coverage.lock().clone()
+    }
+
+    /// Truncate the WAL up to the given operation, as far as the operations are persisted in the
+    /// segments and not needed by a shard transfer.
+    ///
+    /// Returns the operation the WAL was truncated until, if any entries could be truncated.
+    pub async fn compact_wal(&self, until: u64) -> CollectionResult<Option<u64>> {
+        // Make sure all operations written so far are applied to the segments.
+        // WAL is locked while sending the plunger, same as in `LocalShard::update`, so all
+        // operations up to `applied_until` are sent to the update handler before it.
+        let (tx, rx) = oneshot::channel();
+        let applied_until = {
+            let update_sender = self.update_sender.load();
+            let channel_permit = update_sender.reserve().await?;
+            let wal = self.wal.wal.lock();
+            channel_permit.send(UpdateSignal::Plunger(tx));
+            wal.last_index()
+        };
+        rx.await?;
+
+        // Persist the segments, failed operations must be kept to be applied again on load.
+        let segments = self.segments.clone();
+        let (flushed_version, all_persisted, failed_operation) =
+            tokio::task::spawn_blocking(move || {
+                let segments = segments.read();
+                let (flushed_version, all_persisted) = segments.flush_all_segments(true)?;
+                CollectionResult::Ok((
+                    flushed_version,
+                    all_persisted,
+                    segments.failed_operation.first().copied(),
+                ))
+            })
+            .await??;
+
+        // Operations which did not change any point don't advance the versions of the segments.
+        // If the segments persisted all changes, every applied operation is persisted as well.
+        // Otherwise, such as with proxies during optimization, never truncate beyond the version
+        // confirmed by the flush.
+        let persisted_version = if all_persisted {
+            flushed_version.max(applied_until)
+        } else {
+            flushed_version
+        };
+
+        // Entries still to be sent by a queue proxy must be kept as well
+        let keep_from = self
+            .update_handler
+            .lock()
+            .await
+            .wal_keep_from
+            .load(std::sync::atomic::Ordering::Relaxed);
+
+        let until = [
+            Some(until),
+            Some(persisted_version),
+            Some(keep_from.saturating_sub(1)),
+            failed_operation.map(|op_num| op_num.saturating_sub(1)),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or_default();
+
+        let mut wal = self.wal.wal.lock();
+        if keep_from == 0 || until <= wal.first_index() {
+            return Ok(None);
+        }
+        wal.ack(until)?;
+
+        Ok(Some(until))
+    }
+
     /// Create empty WAL which is compatible with currently stored data
     pub fn snapshot_empty_wal(wal: LockedWal, snapshot_shard_path: &Path) -> CollectionResult<()> {
         let (segment_capacity, latest_op_num) = {
@@ -922,6 +1026,27 @@ impl LocalShard {
                 optimizations,
                 log: self.optimizers_log.lock().to_telemetry(),
             },
+            search_pools: SearchPoolsTelemetry::from(self.search_pool_counters.as_ref()),
+        }
+    }
+
+    /// Number of running optimizations of this shard, and of finished ones since it was loaded
+    pub fn optimizations_progress(&self) -> OptimizationsProgress {
+        let finished = self
+            .optimizers
+            .iter()
+            .map(|optimizer| {
+                optimizer
+                    .get_telemetry_counter()
+                    .lock()
+                    .get_statistics(TelemetryDetail::default())
+                    .count
+            })
+            .sum();
+
+        OptimizationsProgress {
+            running: self.optimizers_log.lock().running_count(),
+            finished,
         }
     }
 
@@ -1030,6 +1155,76 @@ impl LocalShard {
         &self.update_tracker
     }
 
+    /// Distinct values of the keyword index of the field across all segments, if every segment
+    /// has one and there are at most `limit` values. Values of deleted points may be included.
+    pub fn keyword_values(&self, key: &PayloadKeyType, limit: usize) -> Option<HashSet<String>> {
+        let segments = self.segments.read();
+        let mut values = HashSet::new();
+        for (_, segment) in segments.iter() {
+            values.extend(segment.get().read().keyword_values(key, limit)?);
+            if values.len() > limit {
+                return None;
+            }
+        }
+        Some(values)
+    }
+
+    /// Sketch of the distinct values of the keyword index of the field across all segments, if
+    /// every segment has one. Values of deleted points may be included.
+    pub fn keyword_values_sketch(&self, key: &PayloadKeyType) -> Option<HyperLogLog> {
+        let segments = self.segments.read();
+        let mut sketch = HyperLogLog::new();
+        for (_, segment) in segments.iter() {
+            sketch.merge(&segment.get().read().keyword_values_sketch(key)?);
+        }
+        Some(sketch)
+    }
+
+    /// Neighbors of the point in the HNSW graphs of the vector, for each segment which has the
+    /// point in its graph
+    pub fn hnsw_neighbors(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> CollectionResult<Vec<(SegmentId, Vec<Vec<PointIdType>>)>> {
+        let segments = self.segments.read();
+        let mut neighbors = Vec::new();
+        for (segment_id, segment) in segments.iter() {
+            if let Some(levels) = segment.get().read().hnsw_neighbors(vector_name, point_id)? {
+                neighbors.push((*segment_id, levels));
+            }
+        }
+        Ok(neighbors)
+    }
+
+    /// Structure of the HNSW graphs of all segments
+    ///
+    /// Statistics of graphs are computed on the first call, on a blocking thread. The segment
+    /// holder is only locked to list the segments.
+    pub async fn hnsw_graphs(&self) -> CollectionResult<Vec<HnswGraphTelemetry>> {
+        let segments: Vec<_> = self
+            .segments
+            .read()
+            .iter()
+            .map(|(_, segment)| segment.clone())
+            .collect();
+
+        let graphs: Vec<_> = tokio::task::spawn_blocking(move || {
+            segments
+                .iter()
+                .flat_map(|segment| {
+                    let segment = segment.get();
+                    let segment = segment.read();
+                    segment.compute_hnsw_graph_stats();
+                    segment.hnsw_graphs_telemetry()
+                })
+                .collect()
+        })
+        .await?;
+
+        Ok(graphs)
+    }
+
     /// Get the recovery point for the current shard
     ///
     /// This is sourced from the last seen clocks from other nodes that we know about.
@@ -1043,6 +1238,38 @@ impl LocalShard {
     pub async fn update_cutoff(&self, cutoff: &RecoveryPoint) {
         self.wal.update_cutoff(cutoff).await
     }
+
+    /// Check if the writes with the given session clocks reached this shard, and if so, wait until
+    /// they are applied to the segments
+    ///
+    /// Returns `false` if the writes did not reach this shard yet.
+    pub async fn apply_session_clocks(
+        &self,
+        clocks: &[ShardSessionClock],
+    ) -> CollectionResult<bool> {
+        let is_reached = {
+            let newest_clocks = self.wal.newest_clocks.lock().await;
+            clocks.iter().all(|clock| {
+                newest_clocks
+                    .current_tick(clock.peer_id, clock.clock_id)
+                    .map_or(false, |tick| tick >= clock.clock_tick)
+            })
+        };
+
+        if !is_reached {
+            return Ok(false);
+        }
+
+        // The writes are in the WAL, make sure they are applied to the segments as well
+        let (tx, rx) = oneshot::channel();
+        self.update_sender
+            .load()
+            .send(UpdateSignal::Plunger(tx))
+            .await?;
+        rx.await?;
+
+        Ok(true)
+    }
 }
 
 impl Drop for LocalShard {
@@ -1161,3 +1388,90 @@ impl LocalShardClocks {
         shard_path.join("oldest_clocks.json")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::only_default_vector;
+    use segment::entry::entry_point::SegmentEntry as _;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::{build_segment_1, empty_segment};
+    use crate::collection_manager::holders::proxy_segment::ProxySegment;
+    use crate::shards::shard_trait::ShardOperation;
+    use crate::tests::fixtures::{create_collection_config, delete_point_operation};
+
+    /// WAL compaction must not truncate operations a segment has not persisted yet
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_compact_wal_keeps_unpersisted_operations() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let segments_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let mut config = create_collection_config();
+        // Don't let the flush worker acknowledge the WAL in between
+        config.optimizer_config.flush_interval_sec = 600;
+
+        let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+        let payload_index_schema = Arc::new(
+            SaveOnDisk::load_or_init_default(
+                payload_index_schema_dir.path().join("payload-schema.json"),
+            )
+            .unwrap(),
+        );
+
+        let shard = LocalShard::build(
+            0,
+            "test".to_string(),
+            collection_dir.path(),
+            Arc::new(TokioRwLock::new(config.clone())),
+            Arc::new(Default::default()),
+            payload_index_schema,
+            Handle::current(),
+            CpuBudget::default(),
+            config.optimizer_config.clone(),
+        )
+        .await
+        .unwrap();
+
+        for idx in 0..20 {
+            shard
+                .update(delete_point_operation(idx).into(), true)
+                .await
+                .unwrap();
+        }
+
+        let snapshot_wal_index = shard.wal.wal.lock().last_index();
+
+        // A proxy segment with pending deletes only persists up to its wrapped segment version
+        let wrapped_segment = LockedSegment::new(build_segment_1(segments_dir.path()));
+        let lagging_version = wrapped_segment.get().read().version();
+        assert!(lagging_version + 1 < snapshot_wal_index);
+
+        let mut proxy_segment = ProxySegment::new(
+            wrapped_segment,
+            LockedSegment::new(empty_segment(segments_dir.path())),
+            Arc::new(RwLock::new(HashSet::new())),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(HashSet::new())),
+        );
+        proxy_segment
+            .delete_point(snapshot_wal_index, 1.into())
+            .unwrap();
+        proxy_segment
+            .upsert_point(
+                snapshot_wal_index,
+                100.into(),
+                only_default_vector(&[1.0, 0.0, 0.0, 0.0]),
+            )
+            .unwrap();
+        shard
+            .segments()
+            .write()
+            .add_new_locked(LockedSegment::from(proxy_segment));
+
+        let truncated_until = shard.compact_wal(snapshot_wal_index).await.unwrap();
+
+        assert_eq!(truncated_until, Some(lagging_version));
+        assert!(shard.wal.wal.lock().first_index() <= lagging_version + 1);
+    }
+}
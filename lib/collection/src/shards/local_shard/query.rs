@@ -6,8 +6,10 @@ use std::time::Duration;
 use api::rest::OrderByInterface;
 use futures::future::BoxFuture;
 use futures::FutureExt;
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
-use segment::types::{Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector};
+use segment::index::search_stats::SearchStats;
+use segment::types::{
+    Filter, HasIdCondition, Order, ScoredPoint, WithPayloadInterface, WithVector,
+};
 use tokio::runtime::Handle;
 
 use super::LocalShard;
@@ -16,10 +18,11 @@ use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
     QueryScrollRequestInternal,
 };
+use crate::operations::universal_query::fusion::collect_prefetch_matches;
 use crate::operations::universal_query::planned_query::{
     MergePlan, PlannedQuery, RescoreParams, Source,
 };
-use crate::operations::universal_query::shard_query::{Fusion, ScoringQuery, ShardQueryResponse};
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryResponse};
 
 pub enum FetchedSource {
     Search(usize),
@@ -49,11 +52,13 @@ impl PrefetchResults {
 }
 
 impl LocalShard {
+    /// Execute the planned query, counting the work of its searches into `search_stats` if given
     pub async fn do_planned_query(
         &self,
         request: PlannedQuery,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let start_time = std::time::Instant::now();
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
@@ -64,6 +69,7 @@ impl LocalShard {
             }),
             search_runtime_handle,
             Some(timeout),
+            search_stats.clone(),
         );
 
         let scrolls_f =
@@ -82,6 +88,7 @@ impl LocalShard {
                 &prefetch_holder,
                 search_runtime_handle,
                 timeout,
+                search_stats.as_ref(),
                 0,
             )
         });
@@ -132,12 +139,14 @@ impl LocalShard {
         Ok(query_response)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn recurse_prefetch<'shard, 'query>(
         &'shard self,
         merge_plan: MergePlan,
         prefetch_holder: &'query PrefetchResults,
         search_runtime_handle: &'shard Handle,
         timeout: Duration,
+        search_stats: Option<&'query Arc<SearchStats>>,
         depth: usize,
     ) -> BoxFuture<'query, CollectionResult<Vec<Vec<ScoredPoint>>>>
     where
@@ -163,6 +172,7 @@ impl LocalShard {
                                 prefetch_holder,
                                 search_runtime_handle,
                                 timeout,
+                                search_stats,
                                 depth + 1,
                             )
                             .await?
@@ -181,6 +191,7 @@ impl LocalShard {
                         rescore_params,
                         search_runtime_handle,
                         timeout,
+                        search_stats,
                     )
                     .await?;
 
@@ -203,52 +214,66 @@ impl LocalShard {
         rescore_params: RescoreParams,
         search_runtime_handle: &Handle,
         timeout: Duration,
+        search_stats: Option<&Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         let RescoreParams {
             rescore,
-            offset,
             score_threshold,
+            filter,
             limit,
             with_vector,
             with_payload,
         } = rescore_params;
 
         match rescore {
-            ScoringQuery::Fusion(Fusion::Rrf) => {
+            ScoringQuery::Fusion(fusion) => {
+                // Filter is always propagated into the prefetches of a fusion
+                debug_assert!(filter.is_none());
+
+                let strategy = fusion.strategy()?;
                 let sources: Vec<_> = sources.map(Cow::into_owned).collect();
 
-                let top_rrf = rrf_scoring(sources);
+                let mut prefetch_matches = collect_prefetch_matches(&sources);
+                let top_fused = strategy.fuse(sources);
 
-                let top_rrf: Vec<_> = if let Some(score_threshold) = score_threshold {
-                    top_rrf
+                let mut top_fused: Vec<_> = if let Some(score_threshold) = score_threshold {
+                    let order = strategy.order();
+                    top_fused
                         .into_iter()
-                        .take_while(|point| point.score >= score_threshold)
-                        .skip(offset)
+                        .take_while(|point| match order {
+                            Order::LargeBetter => point.score >= score_threshold,
+                            Order::SmallBetter => point.score <= score_threshold,
+                        })
                         .take(limit)
                         .collect()
                 } else {
-                    top_rrf.into_iter().skip(offset).take(limit).collect()
+                    top_fused.into_iter().take(limit).collect()
                 };
 
-                let filled_top_rrf = self
-                    .fill_with_payload_or_vectors(top_rrf, with_payload, with_vector)
+                for point in &mut top_fused {
+                    point.prefetch_matches = prefetch_matches.remove(&point.id);
+                }
+
+                let filled_top_fused = self
+                    .fill_with_payload_or_vectors(top_fused, with_payload, with_vector)
                     .await?;
 
-                Ok(filled_top_rrf)
+                Ok(filled_top_fused)
             }
             ScoringQuery::OrderBy(order_by) => {
                 // create single scroll request for rescoring query
-                let filter = filter_with_sources_ids(sources);
+                let filter = Filter::merge_opts(Some(filter_with_sources_ids(sources)), filter);
 
                 // Note: score_threshold is not used in this case, as all results will have same score,
                 // but different order_value
                 let scroll_request = QueryScrollRequestInternal {
-                    offset,
+                    offset: 0,
                     limit,
-                    filter: Some(filter),
+                    filter,
                     with_payload,
                     with_vector,
                     order_by: Some(OrderByInterface::Struct(order_by)),
+                    sample: None,
                 };
 
                 self.query_scroll_batch(
@@ -264,16 +289,44 @@ impl LocalShard {
                     )
                 })
             }
+            ScoringQuery::Sample(sample) => {
+                // create single scroll request, sampling among the points of the sources
+                let filter = Filter::merge_opts(Some(filter_with_sources_ids(sources)), filter);
+
+                // Note: score_threshold is not used in this case, as scores of a sample are random
+                let scroll_request = QueryScrollRequestInternal {
+                    offset: 0,
+                    limit,
+                    filter,
+                    with_payload,
+                    with_vector,
+                    order_by: None,
+                    sample: Some(sample),
+                };
+
+                self.query_scroll_batch(
+                    Arc::new(vec![scroll_request]),
+                    search_runtime_handle,
+                    timeout,
+                )
+                .await?
+                .pop()
+                .ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Rescoring with sample query didn't return expected batch of results",
+                    )
+                })
+            }
             ScoringQuery::Vector(query_enum) => {
                 // create single search request for rescoring query
-                let filter = filter_with_sources_ids(sources);
+                let filter = Filter::merge_opts(Some(filter_with_sources_ids(sources)), filter);
 
                 let search_request = CoreSearchRequest {
                     query: query_enum,
-                    filter: Some(filter),
+                    filter,
                     params: None,
                     limit,
-                    offset,
+                    offset: 0,
                     with_payload: Some(with_payload),
                     with_vector: Some(with_vector),
                     score_threshold,
@@ -287,6 +340,7 @@ impl LocalShard {
                     Arc::new(rescoring_core_search_request),
                     search_runtime_handle,
                     Some(timeout),
+                    search_stats.cloned(),
                 )
                 .await?
                 // One search request is sent. We expect only one result
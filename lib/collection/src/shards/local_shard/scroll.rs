@@ -3,6 +3,7 @@ use std::time::Duration;
 
 use futures::future::try_join_all;
 use itertools::Itertools as _;
+use segment::common::sampling::sample_score;
 use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -12,6 +13,7 @@ use tokio::runtime::Handle;
 use super::LocalShard;
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::resource_usage;
 use crate::operations::types::{
     CollectionError, CollectionResult, QueryScrollRequestInternal, Record,
 };
@@ -53,6 +55,7 @@ impl LocalShard {
             with_vector,
             filter,
             order_by,
+            sample,
             with_payload,
         } = request;
 
@@ -60,6 +63,39 @@ impl LocalShard {
 
         let offset_id = None;
 
+        if let Some(sample) = sample {
+            let (records, keys) = self
+                .scroll_by_sample(
+                    limit,
+                    with_payload,
+                    with_vector,
+                    filter.as_ref(),
+                    search_runtime_handle,
+                    sample.seed,
+                )
+                .await?;
+
+            let point_results = records
+                .into_iter()
+                .zip(keys)
+                .skip(*offset)
+                .map(|(record, key)| ScoredPoint {
+                    id: record.id,
+                    version: 0,
+                    score: sample_score(key),
+                    payload: record.payload,
+                    vector: record.vector,
+                    shard_key: record.shard_key,
+                    order_value: None,
+                    prefetch_matches: None,
+                    routing: None,
+                    raw_score: None,
+                })
+                .collect();
+
+            return Ok(point_results);
+        }
+
         let order_by = order_by.clone().map(OrderBy::from);
 
         let point_results = match order_by {
@@ -83,6 +119,9 @@ impl LocalShard {
                     vector: record.vector,
                     shard_key: record.shard_key,
                     order_value: None,
+                    prefetch_matches: None,
+                    routing: None,
+                    raw_score: None,
                 })
                 .collect(),
             Some(order_by) => {
@@ -109,6 +148,9 @@ impl LocalShard {
                         vector: record.vector,
                         shard_key: record.shard_key,
                         order_value: Some(value),
+                        prefetch_matches: None,
+                        routing: None,
+                        raw_score: None,
                     })
                     .collect()
             }
@@ -133,12 +175,12 @@ impl LocalShard {
         let read_filtered = |segment: LockedSegment| {
             let filter = filter.cloned();
 
-            search_runtime_handle.spawn_blocking(move || {
+            search_runtime_handle.spawn_blocking(resource_usage::measured(move || {
                 segment
                     .get()
                     .read()
                     .read_filtered(offset, Some(limit), filter.as_ref())
-            })
+            }))
         };
 
         let non_appendable = try_join_all(non_appendable.into_iter().map(read_filtered)).await?;
@@ -182,12 +224,12 @@ impl LocalShard {
             let filter = filter.cloned();
             let order_by = order_by.clone();
 
-            search_runtime_handle.spawn_blocking(move || {
+            search_runtime_handle.spawn_blocking(resource_usage::measured(move || {
                 segment
                     .get()
                     .read()
                     .read_ordered_filtered(Some(limit), filter.as_ref(), &order_by)
-            })
+            }))
         };
 
         let non_appendable =
@@ -222,4 +264,56 @@ impl LocalShard {
 
         Ok((ordered_records, values))
     }
+
+    /// Read a random sample of points, the same seed picks the same points.
+    ///
+    /// Returns the records along with their sample keys, in descending order of the keys.
+    pub async fn scroll_by_sample(
+        &self,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+        seed: u64,
+    ) -> CollectionResult<(Vec<Record>, Vec<u64>)> {
+        let segments = self.segments();
+
+        let (non_appendable, appendable) = segments.read().split_segments();
+
+        let read_random_filtered = |segment: LockedSegment| {
+            let filter = filter.cloned();
+
+            search_runtime_handle.spawn_blocking(resource_usage::measured(move || {
+                segment
+                    .get()
+                    .read()
+                    .read_random_filtered(limit, filter.as_ref(), seed)
+            }))
+        };
+
+        let non_appendable =
+            try_join_all(non_appendable.into_iter().map(read_random_filtered)).await?;
+        let appendable = try_join_all(appendable.into_iter().map(read_random_filtered)).await?;
+
+        let (keys, point_ids): (Vec<_>, Vec<_>) = non_appendable
+            .into_iter()
+            .chain(appendable)
+            .kmerge_by(|a, b| a >= b)
+            .dedup()
+            .take(limit)
+            .unzip();
+
+        let with_payload = WithPayload::from(with_payload_interface);
+        let records_map =
+            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
+
+        let (ordered_records, keys) = point_ids
+            .iter()
+            .zip(keys)
+            .filter_map(|(point, key)| Some((records_map.get(point).cloned()?, key)))
+            .unzip();
+
+        Ok((ordered_records, keys))
+    }
 }
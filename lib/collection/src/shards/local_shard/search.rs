@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use segment::index::search_stats::SearchStats;
 use segment::types::ScoredPoint;
 use tokio::runtime::Handle;
 
@@ -11,11 +12,13 @@ use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequestBatch};
 
 impl LocalShard {
+    /// Search the segments of the shard, counting the work done into `search_stats` if given
     pub async fn do_search(
         &self,
         core_request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let is_stopped_guard = StoppingGuard::new();
 
@@ -34,6 +37,12 @@ impl LocalShard {
                 // No segments to search
                 return Ok(vec![]);
             };
+            let query_context = query_context
+                .with_search_pools(
+                    collection_config.params.search_pools.unwrap_or_default(),
+                    self.search_pool_counters.clone(),
+                )
+                .with_search_stats(search_stats);
 
             (query_context, collection_config.params.clone())
         };
@@ -0,0 +1,79 @@
+//! Upgrade of segments to the current storage format.
+//!
+//! Segments written by an older version are migrated when loaded, but their data stays in the
+//! format of that version until an optimizer happens to rewrite them. An upgrade rewrites them
+//! ahead of time, one segment at a time, so the IO is spent when the operator chooses to.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use tokio::task::JoinHandle;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentId};
+use crate::collection_manager::optimizers::{Tracker, TrackerStatus};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Name of the segment upgrade in the optimizer log
+const SEGMENT_UPGRADE_NAME: &str = "segment upgrade";
+
+impl LocalShard {
+    /// Segments of the shard with data in the format of an older version
+    ///
+    /// Segments under optimization are not listed, they are rewritten by it anyway.
+    pub fn outdated_segments(&self) -> Vec<SegmentId> {
+        self.segments
+            .read()
+            .iter()
+            .filter(|(_, segment)| match segment {
+                LockedSegment::Original(segment) => segment.read().outdated_format().is_some(),
+                LockedSegment::Proxy(_) => false,
+            })
+            .map(|(segment_id, _)| *segment_id)
+            .collect()
+    }
+
+    /// Start rewriting an outdated segment of the shard in the current format
+    ///
+    /// The task resolves to `false` if the segment is not rewritten, because it is under another
+    /// optimization or not part of the shard anymore.
+    pub async fn spawn_segment_upgrade(
+        &self,
+        segment_id: SegmentId,
+        permit: CpuPermit,
+        stopped: Arc<AtomicBool>,
+    ) -> JoinHandle<CollectionResult<bool>> {
+        // All optimizers build segments from the current collection config, so any of them
+        // rewrites the segment in the current format
+        let optimizer = self.update_handler.lock().await.optimizers.first().cloned();
+
+        let segments = self.segments.clone();
+        let optimizers_log = self.optimizers_log.clone();
+        self.update_runtime.spawn_blocking(move || {
+            let Some(optimizer) = optimizer else {
+                return Ok(false);
+            };
+
+            let tracker = Tracker::start(SEGMENT_UPGRADE_NAME, vec![segment_id]);
+            let tracker_handle = tracker.handle();
+            optimizers_log.lock().register(tracker);
+
+            match optimizer.optimize(segments.clone(), vec![segment_id], permit, &stopped) {
+                Ok(upgraded) => {
+                    tracker_handle.update(TrackerStatus::Done);
+                    Ok(upgraded)
+                }
+                Err(CollectionError::Cancelled { description }) => {
+                    tracker_handle.update(TrackerStatus::Cancelled(description));
+                    Ok(false)
+                }
+                Err(err) => {
+                    segments.write().report_optimizer_error(err.clone());
+                    tracker_handle.update(TrackerStatus::Error(err.to_string()));
+                    Err(err)
+                }
+            }
+        })
+    }
+}
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::OrderBy;
+use segment::index::search_stats::SearchStats;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -10,6 +13,8 @@ use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::{resource_usage, search_stats};
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
     CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
@@ -21,6 +26,9 @@ use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::update_handler::{OperationData, UpdateSignal};
 
+/// Number of points to read the payload of at once, when aggregating groups
+const GROUP_AGGREGATES_BATCH_SIZE: usize = 1024;
+
 #[async_trait]
 impl ShardOperation for LocalShard {
     /// Imply interior mutability.
@@ -72,6 +80,7 @@ impl ShardOperation for LocalShard {
                         operation_id: None,
                         status: UpdateStatus::ClockRejected,
                         clock_tag: operation.clock_tag,
+                        session_token: None,
                     });
                 }
 
@@ -83,6 +92,7 @@ impl ShardOperation for LocalShard {
                 operation: operation.operation,
                 sender: callback_sender,
                 wait,
+                resource_usage: resource_usage::current(),
             }));
 
             operation_id
@@ -94,12 +104,14 @@ impl ShardOperation for LocalShard {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Completed,
                 clock_tag: operation.clock_tag,
+                session_token: None,
             })
         } else {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Acknowledged,
                 clock_tag: operation.clock_tag,
+                session_token: None,
             })
         }
     }
@@ -164,8 +176,13 @@ impl ShardOperation for LocalShard {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        self.do_search(request, search_runtime_handle, timeout)
-            .await
+        self.do_search(
+            request,
+            search_runtime_handle,
+            timeout,
+            search_stats::current(),
+        )
+        .await
     }
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
@@ -178,15 +195,72 @@ impl ShardOperation for LocalShard {
         Ok(CountResult { count: total_count })
     }
 
+    async fn group_aggregates(
+        &self,
+        request: Arc<GroupAggregatesRequestInternal>,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        let filter = request.points_filter();
+        let point_ids: Vec<_> = self.read_filtered(Some(&filter))?.into_iter().collect();
+
+        let with_payload =
+            WithPayload::from(&WithPayloadInterface::Fields(request.payload_fields()));
+        let with_vector = WithVector::Bool(false);
+
+        // Only the payload of a batch of points is held at a time
+        let mut groups = HashMap::new();
+        for batch in point_ids.chunks(GROUP_AGGREGATES_BATCH_SIZE) {
+            let records =
+                SegmentsSearcher::retrieve(self.segments(), batch, &with_payload, &with_vector)?;
+            for record in records.values() {
+                if let Some(payload) = &record.payload {
+                    request.aggregate_point(payload, &mut groups);
+                }
+            }
+        }
+
+        Ok(groups)
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
         with_payload: &WithPayload,
         with_vector: &WithVector,
     ) -> CollectionResult<Vec<Record>> {
-        let records_map =
+        let mut records_map =
             SegmentsSearcher::retrieve(self.segments(), &request.ids, with_payload, with_vector)?;
 
+        if let Some(version) = request.as_of_version {
+            let segments = self.segments().read();
+            let point_history = &segments.point_history;
+
+            if !point_history.is_enabled() {
+                return Err(CollectionError::bad_request(
+                    "Point history is not enabled for this collection",
+                ));
+            }
+
+            for &point_id in &request.ids {
+                let record = match point_history.record_as_of(
+                    point_id,
+                    version,
+                    with_payload,
+                    with_vector,
+                ) {
+                    Some(record) => record,
+                    // No history for this point, it was not changed since history was enabled
+                    None => records_map
+                        .remove(&point_id)
+                        .filter(|record| record.version.is_some_and(|v| v <= version)),
+                };
+
+                match record {
+                    Some(record) => records_map.insert(point_id, record),
+                    None => records_map.remove(&point_id),
+                };
+            }
+        }
+
         let ordered_records = request
             .ids
             .iter()
@@ -201,10 +275,11 @@ impl ShardOperation for LocalShard {
         requests: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let planned_query = PlannedQuery::try_from(requests.as_ref().to_owned())?;
 
-        self.do_planned_query(planned_query, search_runtime_handle, timeout)
+        self.do_planned_query(planned_query, search_runtime_handle, timeout, search_stats)
             .await
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -6,7 +6,9 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::OrderBy;
+use segment::index::search_stats::SearchStats;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -16,6 +18,7 @@ use tokio::sync::{oneshot, RwLock};
 use tokio::time::timeout;
 
 use super::update_tracker::UpdateTracker;
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
 use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
@@ -234,6 +237,15 @@ impl ShardOperation for ProxyShard {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `group_aggregates` to `wrapped_shard`
+    async fn group_aggregates(
+        &self,
+        request: Arc<GroupAggregatesRequestInternal>,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.group_aggregates(request).await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -253,10 +265,11 @@ impl ShardOperation for ProxyShard {
         request: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .query_batch(request, search_runtime_handle, timeout)
+            .query_batch(request, search_runtime_handle, timeout, search_stats)
             .await
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -6,7 +7,9 @@ use std::time::Duration;
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::OrderBy;
+use segment::index::search_stats::SearchStats;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -17,6 +20,7 @@ use super::remote_shard::RemoteShard;
 use super::transfer::driver::MAX_RETRY_COUNT;
 use super::transfer::transfer_tasks_pool::TransferTaskProgress;
 use super::update_tracker::UpdateTracker;
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
@@ -278,6 +282,18 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    /// Forward read-only `group_aggregates` to `wrapped_shard`
+    async fn group_aggregates(
+        &self,
+        request: Arc<GroupAggregatesRequestInternal>,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .group_aggregates(request)
+            .await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -298,12 +314,13 @@ impl ShardOperation for QueueProxyShard {
         requests: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         self.inner
             .as_ref()
             .expect("Queue proxy has been finalized")
             .wrapped_shard
-            .query_batch(requests, search_runtime_handle, timeout)
+            .query_batch(requests, search_runtime_handle, timeout, search_stats)
             .await
     }
 }
@@ -449,9 +466,11 @@ impl Inner {
         }
 
         // Transfer batch with retries and store last transferred ID
+        // Retries reuse the update IDs, so the remote doesn't apply operations of the batch twice
         let last_idx = batch.last().map(|(idx, _)| *idx);
+        let update_ids: Vec<u64> = batch.iter().map(|_| rand::random()).collect();
         for remaining_attempts in (0..BATCH_RETRIES).rev() {
-            match transfer_operations_batch(&batch, &self.remote_shard).await {
+            match transfer_operations_batch(&batch, &update_ids, &self.remote_shard).await {
                 Ok(()) => {
                     if let Some(idx) = last_idx {
                         self.transfer_from.store(idx + 1, Ordering::Relaxed);
@@ -566,6 +585,15 @@ impl ShardOperation for Inner {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `group_aggregates` to `wrapped_shard`
+    async fn group_aggregates(
+        &self,
+        request: Arc<GroupAggregatesRequestInternal>,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.group_aggregates(request).await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -585,10 +613,11 @@ impl ShardOperation for Inner {
         request: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .query_batch(request, search_runtime_handle, timeout)
+            .query_batch(request, search_runtime_handle, timeout, search_stats)
             .await
     }
 }
@@ -602,10 +631,11 @@ impl ShardOperation for Inner {
 /// If cancelled - none, some or all operations of the batch may be transmitted to the remote.
 async fn transfer_operations_batch(
     batch: &[(u64, OperationWithClockTag)],
+    update_ids: &[u64],
     remote_shard: &RemoteShard,
 ) -> CollectionResult<()> {
     // TODO: naive transfer approach, transfer batch of points instead
-    for (_idx, operation) in batch {
+    for ((_idx, operation), &update_id) in batch.iter().zip(update_ids) {
         let mut operation = operation.clone();
 
         // Set force flag because operations from WAL may be unordered if another node is sending
@@ -615,7 +645,7 @@ async fn transfer_operations_batch(
         }
 
         remote_shard
-            .forward_update(operation, true, WriteOrdering::Weak)
+            .forward_update(operation, true, WriteOrdering::Weak, update_id)
             .await?;
     }
     Ok(())
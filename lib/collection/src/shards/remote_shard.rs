@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::future::Future;
 use std::path::Path;
 use std::sync::Arc;
@@ -14,7 +15,7 @@ use api::grpc::qdrant::{
     GetShardRecoveryPointRequest, HealthCheckRequest, InitiateShardTransferRequest,
     QueryBatchPointsInternal, QueryShardPoints, RecoverShardSnapshotRequest,
     RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal, ShardSnapshotLocation,
-    UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    UpdateShardCutoffPointRequest, WaitForSessionClocksRequest, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use async_trait::async_trait;
@@ -24,7 +25,9 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::OrderBy;
+use segment::index::search_stats::SearchStats;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -39,9 +42,12 @@ use super::conversions::{
 };
 use super::local_shard::clock_map::RecoveryPoint;
 use super::replica_set::ReplicaState;
+use crate::common::search_stats;
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
 use crate::operations::conversions::try_record_from_grpc;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointOperations, WriteOrdering};
+use crate::operations::session_token::ShardSessionClock;
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
@@ -54,8 +60,9 @@ use crate::shards::channel_service::ChannelService;
 use crate::shards::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_delete_points_by_filter, internal_group_aggregates, internal_set_payload,
+    internal_sync_points, internal_upsert_points, try_group_aggregates_from_grpc,
+    try_scored_point_from_grpc,
 };
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
@@ -128,6 +135,37 @@ impl RemoteShard {
             .map_err(|err| err.into())
     }
 
+    /// Same as `with_points_client`, but retries according to the read retry policy of the peers
+    async fn with_points_client_for_read<T, O: Future<Output = Result<T, Status>>>(
+        &self,
+        f: impl Fn(PointsInternalClient<InterceptedService<Channel, AddTimeout>>) -> O,
+    ) -> CollectionResult<T> {
+        let current_address = self.current_address()?;
+        self.channel_service
+            .channel_pool
+            .with_channel_retry_policy(
+                &current_address,
+                |channel| {
+                    let client = PointsInternalClient::new(channel);
+                    let client = client.max_decoding_message_size(usize::MAX);
+                    f(client)
+                },
+                None,
+                &self.channel_service.read_retry_policy,
+                |status| {
+                    log::debug!(
+                        "Retrying read of shard {}:{} on peer {}: {status}",
+                        self.collection_id,
+                        self.id,
+                        self.peer_id,
+                    );
+                    self.channel_service.report_read_retry(self.peer_id);
+                },
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
     async fn with_collections_client<T, O: Future<Output = Result<T, Status>>>(
         &self,
         f: impl Fn(CollectionsInternalClient<InterceptedService<Channel, AddTimeout>>) -> O,
@@ -194,6 +232,8 @@ impl RemoteShard {
                 .telemetry_update_durations
                 .lock()
                 .get_statistics(detail),
+            replication_lag: None,
+            degraded: false,
         }
     }
 
@@ -212,6 +252,11 @@ impl RemoteShard {
         Ok(res)
     }
 
+    /// Forward an update to the remote shard.
+    ///
+    /// Callers retrying the same operation must pass the same `update_id`, so the remote doesn't
+    /// apply it twice.
+    ///
     /// # Cancel safety
     ///
     /// This method is cancel safe.
@@ -220,6 +265,7 @@ impl RemoteShard {
         operation: OperationWithClockTag,
         wait: bool,
         ordering: WriteOrdering,
+        update_id: u64,
     ) -> CollectionResult<UpdateResult> {
         // `RemoteShard::execute_update_operation` is cancel safe, so this method is cancel safe.
 
@@ -229,6 +275,7 @@ impl RemoteShard {
             operation,
             wait,
             Some(ordering),
+            update_id,
         )
         .await
     }
@@ -243,6 +290,7 @@ impl RemoteShard {
         operation: OperationWithClockTag,
         wait: bool,
         ordering: Option<WriteOrdering>,
+        update_id: u64,
     ) -> CollectionResult<UpdateResult> {
         // Cancelling remote request should always be safe on the client side and update API
         // *should be* cancel safe on the server side, so this method is cancel safe.
@@ -250,10 +298,14 @@ impl RemoteShard {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_update_durations);
         timer.set_success(false);
 
+        // Retries of the request on transient errors carry the same update id, so the remote
+        // doesn't apply the update twice
+        let update_id = Some(update_id);
+
         let point_operation_response = match operation.operation {
             CollectionUpdateOperations::PointOperation(point_ops) => match point_ops {
                 PointOperations::UpsertPoints(point_insert_operations) => {
-                    let request = &internal_upsert_points(
+                    let mut request = internal_upsert_points(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -261,6 +313,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     )?;
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client.upsert(tonic::Request::new(request.clone())).await
                     })
@@ -268,7 +322,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PointOperations::DeletePoints { ids } => {
-                    let request = &internal_delete_points(
+                    let mut request = internal_delete_points(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -276,6 +330,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client.delete(tonic::Request::new(request.clone())).await
                     })
@@ -283,7 +339,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PointOperations::DeletePointsByFilter(filter) => {
-                    let request = &internal_delete_points_by_filter(
+                    let mut request = internal_delete_points_by_filter(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -291,6 +347,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client.delete(tonic::Request::new(request.clone())).await
                     })
@@ -298,7 +356,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PointOperations::SyncPoints(operation) => {
-                    let request = &internal_sync_points(
+                    let mut request = internal_sync_points(
                         shard_id,
                         None, // TODO!?
                         collection_name,
@@ -306,6 +364,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     )?;
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client.sync(tonic::Request::new(request.clone())).await
                     })
@@ -315,7 +375,7 @@ impl RemoteShard {
             },
             CollectionUpdateOperations::VectorOperation(vector_ops) => match vector_ops {
                 VectorOperations::UpdateVectors(update_operation) => {
-                    let request = &internal_update_vectors(
+                    let mut request = internal_update_vectors(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -323,6 +383,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .update_vectors(tonic::Request::new(request.clone()))
@@ -332,7 +394,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 VectorOperations::DeleteVectors(ids, vector_names) => {
-                    let request = &internal_delete_vectors(
+                    let mut request = internal_delete_vectors(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -341,6 +403,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .delete_vectors(tonic::Request::new(request.clone()))
@@ -350,7 +414,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 VectorOperations::DeleteVectorsByFilter(filter, vector_names) => {
-                    let request = &internal_delete_vectors_by_filter(
+                    let mut request = internal_delete_vectors_by_filter(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -359,6 +423,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .delete_vectors(tonic::Request::new(request.clone()))
@@ -370,7 +436,7 @@ impl RemoteShard {
             },
             CollectionUpdateOperations::PayloadOperation(payload_ops) => match payload_ops {
                 PayloadOps::SetPayload(set_payload) => {
-                    let request = &internal_set_payload(
+                    let mut request = internal_set_payload(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -378,6 +444,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .set_payload(tonic::Request::new(request.clone()))
@@ -387,7 +455,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PayloadOps::DeletePayload(delete_payload) => {
-                    let request = &internal_delete_payload(
+                    let mut request = internal_delete_payload(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -395,6 +463,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .delete_payload(tonic::Request::new(request.clone()))
@@ -404,7 +474,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PayloadOps::ClearPayload { points } => {
-                    let request = &internal_clear_payload(
+                    let mut request = internal_clear_payload(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -412,6 +482,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .clear_payload(tonic::Request::new(request.clone()))
@@ -421,7 +493,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PayloadOps::ClearPayloadByFilter(filter) => {
-                    let request = &internal_clear_payload_by_filter(
+                    let mut request = internal_clear_payload_by_filter(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -429,6 +501,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .clear_payload(tonic::Request::new(request.clone()))
@@ -438,7 +512,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PayloadOps::OverwritePayload(set_payload) => {
-                    let request = &internal_set_payload(
+                    let mut request = internal_set_payload(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -446,6 +520,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .overwrite_payload(tonic::Request::new(request.clone()))
@@ -458,7 +534,7 @@ impl RemoteShard {
             CollectionUpdateOperations::FieldIndexOperation(field_index_op) => match field_index_op
             {
                 FieldIndexOperations::CreateIndex(create_index) => {
-                    let request = &internal_create_index(
+                    let mut request = internal_create_index(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -466,6 +542,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .create_field_index(tonic::Request::new(request.clone()))
@@ -475,7 +553,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 FieldIndexOperations::DeleteIndex(delete_index) => {
-                    let request = &internal_delete_index(
+                    let mut request = internal_delete_index(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -483,6 +561,8 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    request.update_id = update_id;
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .delete_field_index(tonic::Request::new(request.clone()))
@@ -618,6 +698,30 @@ impl RemoteShard {
         Ok(())
     }
 
+    /// Wait until the writes with the given session clocks are applied to the remote shard
+    ///
+    /// Returns `false` if they were not applied within the timeout.
+    pub async fn wait_for_session_clocks(
+        &self,
+        clocks: &[ShardSessionClock],
+        timeout: Duration,
+    ) -> CollectionResult<bool> {
+        let res = self
+            .with_collections_client(|mut client| async move {
+                client
+                    .wait_for_session_clocks(WaitForSessionClocksRequest {
+                        collection_name: self.collection_id.clone(),
+                        shard_id: self.id,
+                        clocks: clocks.iter().copied().map(Into::into).collect(),
+                        timeout_ms: timeout.as_millis() as u64,
+                    })
+                    .await
+            })
+            .await?
+            .into_inner();
+        Ok(res.result)
+    }
+
     pub async fn health_check(&self) -> CollectionResult<()> {
         let _ = self
             .with_qdrant_client(|mut client| async move {
@@ -647,8 +751,18 @@ impl ShardOperation for RemoteShard {
 
         // targets the shard explicitly
         let shard_id = Some(self.id);
-        self.execute_update_operation(shard_id, self.collection_id.clone(), operation, wait, None)
-            .await
+        // Every call is a new operation, an update rejected for its clock is retried with a new
+        // clock tag, which must be applied on its own
+        let update_id = rand::random();
+        self.execute_update_operation(
+            shard_id,
+            self.collection_id.clone(),
+            operation,
+            wait,
+            None,
+            update_id,
+        )
+        .await
     }
 
     async fn scroll_by(
@@ -671,6 +785,7 @@ impl ShardOperation for RemoteShard {
             read_consistency: None,
             shard_key_selector: None,
             order_by: order_by.map(|o| o.clone().into()),
+            session_token: None,
         };
         let request = &ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -729,14 +844,17 @@ impl ShardOperation for RemoteShard {
             .map(|s| CollectionCoreSearchRequest((self.collection_id.clone(), s)).into())
             .collect();
 
+        let search_stats = search_stats::current();
+
         let request = &CoreSearchBatchPointsInternal {
             collection_name: self.collection_id.clone(),
             search_points,
             shard_id: Some(self.id),
             timeout: timeout.map(|t| t.as_secs()),
+            with_stats: search_stats.is_some().then_some(true),
         };
         let search_batch_response = self
-            .with_points_client(|mut client| async move {
+            .with_points_client_for_read(|mut client| async move {
                 let mut request = tonic::Request::new(request.clone());
 
                 if let Some(timeout) = timeout {
@@ -748,6 +866,10 @@ impl ShardOperation for RemoteShard {
             .await?
             .into_inner();
 
+        if let (Some(search_stats), Some(stats)) = (&search_stats, search_batch_response.stats) {
+            search_stats.add(&stats.into());
+        }
+
         let result: Result<Vec<Vec<ScoredPoint>>, Status> = search_batch_response
             .result
             .into_iter()
@@ -779,6 +901,7 @@ impl ShardOperation for RemoteShard {
             exact: Some(request.exact),
             read_consistency: None,
             shard_key_selector: None,
+            session_token: None,
         };
 
         let request = &CountPointsInternal {
@@ -801,6 +924,24 @@ impl ShardOperation for RemoteShard {
         )
     }
 
+    async fn group_aggregates(
+        &self,
+        request: Arc<GroupAggregatesRequestInternal>,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        let request =
+            &internal_group_aggregates(Some(self.id), self.collection_id.clone(), &request);
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .group_aggregates(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+
+        Ok(try_group_aggregates_from_grpc(response.groups)?)
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -814,6 +955,8 @@ impl ShardOperation for RemoteShard {
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
             shard_key_selector: None,
+            as_of_version: request.as_of_version,
+            session_token: None,
         };
         let request = &GetPointsInternal {
             get_points: Some(get_points),
@@ -841,14 +984,16 @@ impl ShardOperation for RemoteShard {
         requests: Arc<Vec<ShardQueryRequest>>,
         _search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
         timer.set_success(false);
 
         let requests = requests.as_ref();
+        let with_stats = search_stats.is_some().then_some(true);
 
         let batch_response = self
-            .with_points_client(|mut client| async move {
+            .with_points_client_for_read(|mut client| async move {
                 let query_points = requests
                     .iter()
                     .map(|request| QueryShardPoints::from(request.clone()))
@@ -859,6 +1004,7 @@ impl ShardOperation for RemoteShard {
                     query_points,
                     shard_id: Some(self.id),
                     timeout: timeout.map(|t| t.as_secs()),
+                    with_stats,
                 };
 
                 let mut request = tonic::Request::new(request.clone());
@@ -872,6 +1018,10 @@ impl ShardOperation for RemoteShard {
             .await?
             .into_inner();
 
+        if let (Some(search_stats), Some(stats)) = (&search_stats, batch_response.stats) {
+            search_stats.add(&stats.into());
+        }
+
         let result = batch_response
             .results
             .into_iter()
@@ -897,4 +1047,8 @@ impl ShardOperation for RemoteShard {
 
         Ok(result)
     }
+
+    fn remote_peer_id(&self) -> Option<PeerId> {
+        Some(self.peer_id)
+    }
 }
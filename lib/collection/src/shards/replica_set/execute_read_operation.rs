@@ -1,19 +1,30 @@
+use std::collections::HashSet;
 use std::fmt::Write as _;
 use std::ops::Deref as _;
+use std::time::{Duration, Instant};
 
 use futures::future::{self, BoxFuture};
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt as _, StreamExt as _};
-use rand::seq::SliceRandom as _;
 
 use super::ShardReplicaSet;
 use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
+use crate::operations::session_token::{self, ShardSessionClock};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::resolve::{Resolve, ResolveCondition};
-use crate::shards::shard::Shard;
+use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_trait::ShardOperation;
 
+/// How long to wait for a replica to apply the writes of a session token, if the read has no timeout
+const DEFAULT_SESSION_CLOCKS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often to check if the writes of a session token have reached the local replica
+const SESSION_CLOCKS_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Timeout of a probe of a peer, which repeatedly timed out on reads
+const CIRCUIT_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
 impl ShardReplicaSet {
     /// Execute read op. on replica set:
     /// 1 - Prefer local replica
@@ -33,7 +44,7 @@ impl ShardReplicaSet {
         }
 
         let mut responses = self
-            .execute_cluster_read_operation(read_operation, 1, None)
+            .execute_cluster_read_operation(read_operation, 1, None, None)
             .await?;
 
         Ok(responses.pop().unwrap())
@@ -88,18 +99,35 @@ impl ShardReplicaSet {
         };
 
         if active_count < required_successful_results {
-            return Err(CollectionError::service_error(format!(
+            return Err(CollectionError::shard_unavailable(format!(
                 "The replica set for shard {} on peer {} does not have enough active replicas",
                 self.shard_id,
                 self.this_peer_id(),
             )));
         }
 
+        // With a session token, the read consistency applies over the replicas which applied the
+        // writes of the token
+        let (session_clocks, timeout) = session_token::current_shard_clocks(self.shard_id);
+
+        let (session_peers, remotes) = if session_clocks.is_empty() {
+            (None, Some(remotes))
+        } else {
+            drop(remotes);
+
+            let session_peers = self
+                .wait_for_session_replicas(&session_clocks, timeout, required_successful_results)
+                .await?;
+
+            (Some(session_peers), None)
+        };
+
         let mut responses = self
             .execute_cluster_read_operation(
                 read_operation,
                 required_successful_results,
-                Some(remotes),
+                remotes,
+                session_peers.as_ref(),
             )
             .await?;
 
@@ -110,6 +138,64 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Execute read op. on all active replicas of the replica set, without resolving the responses.
+    ///
+    /// Returns the responses of the replicas which responded, as long as they are a majority of
+    /// all replicas. Inactive replicas and replicas failing with a transient error are skipped.
+    pub(super) async fn execute_read_operation_on_majority<Res, F>(
+        &self,
+        read_operation: F,
+    ) -> CollectionResult<Vec<Res>>
+    where
+        F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
+    {
+        let local = self.local.read().await;
+        let remotes = self.remotes.read().await;
+
+        let local_count = usize::from(self.peer_state(&self.this_peer_id()).is_some());
+        let local_is_active = self.peer_is_active(&self.this_peer_id());
+
+        let total_count = local_count + remotes.len();
+        let required_successful_results = total_count / 2 + 1;
+
+        // TODO(resharding): Handle resharded shard?
+        let active_remotes: Vec<_> = remotes
+            .iter()
+            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .collect();
+
+        let active_count = usize::from(local_is_active) + active_remotes.len();
+
+        if active_count < required_successful_results {
+            return Err(CollectionError::shard_unavailable(format!(
+                "The replica set for shard {} on peer {} does not have enough active replicas",
+                self.shard_id,
+                self.this_peer_id(),
+            )));
+        }
+
+        let local_operation = local
+            .deref()
+            .as_ref()
+            .filter(|_| local_is_active)
+            .map(|local| read_operation(local.get()).left_future());
+
+        let remote_operations = active_remotes.into_iter().map(|remote| {
+            let peer_id = remote.peer_id;
+            let started = Instant::now();
+            read_operation(remote)
+                .map(move |result| {
+                    self.record_remote_read(peer_id, &result, started.elapsed());
+                    result
+                })
+                .right_future()
+        });
+
+        let results = future::join_all(local_operation.into_iter().chain(remote_operations)).await;
+
+        successful_responses(results, required_successful_results)
+    }
+
     async fn execute_local_read_operation<Res, F>(&self, read_operation: F) -> CollectionResult<Res>
     where
         F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
@@ -126,11 +212,157 @@ impl ShardReplicaSet {
         read_operation(local.get()).await
     }
 
+    /// Wait until `required_replicas` active replicas have applied the writes of the session clocks
+    ///
+    /// Waits on all active replicas concurrently, and returns the peers of the replicas which
+    /// applied the writes first.
+    async fn wait_for_session_replicas(
+        &self,
+        session_clocks: &[ShardSessionClock],
+        timeout: Option<Duration>,
+        required_replicas: usize,
+    ) -> CollectionResult<HashSet<PeerId>> {
+        let timeout = timeout.unwrap_or(DEFAULT_SESSION_CLOCKS_TIMEOUT);
+        let this_peer_id = self.this_peer_id();
+
+        // Don't hold the lock on the remotes, while waiting for them
+        // TODO(resharding): Handle resharded shard?
+        let active_remotes: Vec<_> = self
+            .remotes
+            .read()
+            .await
+            .iter()
+            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .cloned()
+            .collect();
+
+        let local_wait = self.peer_is_active(&this_peer_id).then(|| {
+            self.wait_for_local_session_clocks(session_clocks, timeout)
+                .map(move |result| (this_peer_id, result))
+                .left_future()
+        });
+
+        let remote_waits = active_remotes.iter().map(|remote| {
+            remote
+                .wait_for_session_clocks(session_clocks, timeout)
+                .map(|result| (remote.peer_id, result))
+                .right_future()
+        });
+
+        let mut pending_waits: FuturesUnordered<_> =
+            local_wait.into_iter().chain(remote_waits).collect();
+
+        let mut session_peers = HashSet::with_capacity(required_replicas);
+
+        while let Some((peer_id, result)) = pending_waits.next().await {
+            match result {
+                Ok(true) => {
+                    session_peers.insert(peer_id);
+
+                    if session_peers.len() >= required_replicas {
+                        return Ok(session_peers);
+                    }
+                }
+                Ok(false) => {}
+                Err(err) => log::debug!(
+                    "Failed to wait for session clocks of shard {} on peer {peer_id}: {err}",
+                    self.shard_id,
+                ),
+            }
+        }
+
+        Err(CollectionError::service_error(format!(
+            "Only {} of {required_replicas} required replicas of shard {} applied the writes of the session token within {timeout:?}",
+            session_peers.len(),
+            self.shard_id,
+        )))
+    }
+
+    /// Wait until the local replica has applied the writes with the given session clocks
+    ///
+    /// Returns `false` if it did not apply them within the timeout. The local replica is only
+    /// locked while its clocks are checked, not while waiting.
+    pub async fn wait_for_local_session_clocks(
+        &self,
+        session_clocks: &[ShardSessionClock],
+        timeout: Duration,
+    ) -> CollectionResult<bool> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            {
+                let local = self.local.read().await;
+                if let Some(local) = local.deref() {
+                    if local.apply_session_clocks(session_clocks).await? {
+                        return Ok(true);
+                    }
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+
+            tokio::time::sleep(SESSION_CLOCKS_POLL_INTERVAL).await;
+        }
+    }
+
+    fn record_remote_read<Res>(
+        &self,
+        peer_id: PeerId,
+        result: &CollectionResult<Res>,
+        latency: Duration,
+    ) {
+        match result {
+            Ok(_) => {
+                self.read_circuit_breaker.lock().record_success(peer_id);
+                self.read_latencies.lock().record(latency);
+                self.read_stats.lock().record_success(peer_id, latency);
+            }
+            Err(err) => {
+                if matches!(err, CollectionError::Timeout { .. }) {
+                    self.read_circuit_breaker.lock().record_timeout(peer_id);
+                }
+                if err.is_transient() {
+                    self.read_stats.lock().record_failure(peer_id);
+                }
+            }
+        }
+    }
+
+    /// Check in the background, whether peers with an open circuit respond again
+    fn probe_open_circuits(&self, remotes: &[&RemoteShard]) {
+        let due_probes = self.read_circuit_breaker.lock().start_due_probes();
+
+        for peer_id in due_probes {
+            let Some(remote) = remotes.iter().find(|remote| remote.peer_id == peer_id) else {
+                // Not a replica anymore, try again with the next read
+                self.read_circuit_breaker
+                    .lock()
+                    .record_probe(peer_id, false);
+                continue;
+            };
+
+            let remote = RemoteShard::clone(remote);
+            let read_circuit_breaker = self.read_circuit_breaker.clone();
+
+            self.search_runtime.spawn(async move {
+                let probe = tokio::time::timeout(CIRCUIT_PROBE_TIMEOUT, remote.health_check());
+                let is_successful = matches!(probe.await, Ok(Ok(())));
+
+                read_circuit_breaker
+                    .lock()
+                    .record_probe(peer_id, is_successful);
+            });
+        }
+    }
+
     async fn execute_cluster_read_operation<Res, F>(
         &self,
         read_operation: F,
         required_successful_results: usize,
         remotes: Option<tokio::sync::RwLockReadGuard<'_, Vec<RemoteShard>>>,
+        peers: Option<&HashSet<PeerId>>,
     ) -> CollectionResult<Vec<Res>>
     where
         F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
@@ -159,7 +391,12 @@ impl ShardReplicaSet {
             Err(_) => (self.local.read().right_future(), false, None),
         };
 
-        let local_is_active = self.peer_is_active(&self.this_peer_id());
+        // Only read from the given peers, if any
+        let is_candidate = |peer_id: &PeerId| {
+            self.peer_is_active(peer_id) && peers.map_or(true, |peers| peers.contains(peer_id))
+        };
+
+        let local_is_active = is_candidate(&self.this_peer_id());
 
         let local_operation = if local_is_active {
             let local_operation = async {
@@ -172,7 +409,12 @@ impl ShardReplicaSet {
                     )));
                 };
 
-                read_operation(local.get()).await
+                let started = Instant::now();
+                let result = read_operation(local.get()).await;
+                if result.is_ok() {
+                    self.read_latencies.lock().record(started.elapsed());
+                }
+                result
             };
 
             Some(local_operation.map(|result| (result, true)).left_future())
@@ -181,16 +423,38 @@ impl ShardReplicaSet {
         };
 
         // TODO(resharding): Handle resharded shard?
-        let mut active_remotes: Vec<_> = remotes
+        let active_remotes: Vec<_> = remotes
             .iter()
-            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .filter(|remote| is_candidate(&remote.peer_id))
             .collect();
 
-        active_remotes.shuffle(&mut rand::thread_rng());
+        // Prefer faster replicas, while still spreading the load between them
+        let mut active_remotes = self
+            .read_stats
+            .lock()
+            .order(active_remotes, |remote| remote.peer_id);
+
+        // Prefer replicas on peers, which did not time out repeatedly, and which don't lag behind
+        self.probe_open_circuits(&active_remotes);
+        {
+            let read_circuit_breaker = self.read_circuit_breaker.lock();
+            let replication_lags = self.replication_lags.lock();
+            active_remotes.sort_by_key(|remote| {
+                (
+                    read_circuit_breaker.is_open(remote.peer_id),
+                    replication_lags.is_degraded(remote.peer_id),
+                )
+            });
+        }
 
         let remote_operations = active_remotes.into_iter().map(|remote| {
+            let peer_id = remote.peer_id;
+            let started = Instant::now();
             read_operation(remote)
-                .map(|result| (result, false))
+                .map(move |result| {
+                    self.record_remote_read(peer_id, &result, started.elapsed());
+                    (result, false)
+                })
                 .right_future()
         });
 
@@ -241,6 +505,23 @@ impl ShardReplicaSet {
 
         tokio::pin!(update_watcher);
 
+        // Hedge the read, if replicas respond slower than most recent reads
+        let hedge_delay = self
+            .shared_storage_config
+            .hedged_reads_percentile
+            .and_then(|percentile| self.read_latencies.lock().percentile(percentile));
+
+        let hedge_timer = async move {
+            match hedge_delay {
+                Some(hedge_delay) => tokio::time::sleep(hedge_delay).await,
+                None => future::pending().await,
+            }
+        };
+
+        let hedge_timer = hedge_timer.fuse();
+
+        tokio::pin!(hedge_timer);
+
         loop {
             let result;
 
@@ -263,6 +544,12 @@ impl ShardReplicaSet {
                     pending_operations.extend(operations.next());
                     continue;
                 }
+
+                _ = &mut hedge_timer => {
+                    // Send the read to one more replica, and use whichever responds first
+                    pending_operations.extend(operations.next());
+                    continue;
+                }
             }
 
             match result {
@@ -294,19 +581,115 @@ impl ShardReplicaSet {
         if responses.len() >= required_successful_results {
             Ok(responses)
         } else {
-            let errors_count = errors.len();
-            let operations_count = responses.len() + errors.len();
-            let errors_separator = if !errors.is_empty() { ":" } else { "" };
-
-            let mut message = format!(
-                "{errors_count} of {operations_count} read operations failed{errors_separator}"
-            );
+            Err(failed_read_operations_error(responses.len(), errors))
+        }
+    }
+}
 
-            for error in errors {
-                write!(&mut message, "\n  {error}").expect("writing into String always succeeds");
+/// Responses of the replicas which responded, if there are at least `required_successful_results`
+///
+/// Transient errors of single replicas are skipped, any other error is returned.
+fn successful_responses<Res>(
+    results: Vec<CollectionResult<Res>>,
+    required_successful_results: usize,
+) -> CollectionResult<Vec<Res>> {
+    let mut responses = Vec::with_capacity(results.len());
+    let mut errors = Vec::new();
+
+    for result in results {
+        match result {
+            Ok(response) => responses.push(response),
+            Err(error) if error.is_transient() => {
+                log::debug!("Read operation failed: {error}");
+                errors.push(error);
             }
+            Err(error) => return Err(error),
+        }
+    }
 
-            Err(CollectionError::service_error(message))
+    if responses.len() >= required_successful_results {
+        Ok(responses)
+    } else {
+        Err(failed_read_operations_error(responses.len(), errors))
+    }
+}
+
+fn failed_read_operations_error(
+    responses_count: usize,
+    errors: Vec<CollectionError>,
+) -> CollectionError {
+    let errors_count = errors.len();
+    let operations_count = responses_count + errors.len();
+    let errors_separator = if !errors.is_empty() { ":" } else { "" };
+
+    let mut message =
+        format!("{errors_count} of {operations_count} read operations failed{errors_separator}");
+
+    for error in errors {
+        write!(&mut message, "\n  {error}").expect("writing into String always succeeds");
+    }
+
+    CollectionError::service_error(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::SeqNumberType;
+
+    use super::*;
+    use crate::operations::types::Record;
+    use crate::shards::resolve::resolve_newest_records;
+
+    fn record(id: u64, version: SeqNumberType) -> Record {
+        Record {
+            id: id.into(),
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            version: Some(version),
         }
     }
+
+    fn dead_replica() -> CollectionResult<Vec<Record>> {
+        Err(CollectionError::service_error("peer 3 is unreachable"))
+    }
+
+    #[test]
+    fn test_majority_with_one_dead_replica() {
+        // Three replicas, one of them doesn't respond
+        let results = vec![
+            Ok(vec![record(1, 3), record(2, 5)]),
+            Ok(vec![record(1, 4), record(2, 5)]),
+            dead_replica(),
+        ];
+
+        let responses = successful_responses(results, 2).unwrap();
+        assert_eq!(responses.len(), 2);
+
+        // The newest version is resolved over the replicas which responded
+        let (records, stale) = resolve_newest_records(responses);
+        assert_eq!(records, vec![record(1, 4), record(2, 5)]);
+        assert_eq!(stale, vec![(1.into(), 4)]);
+    }
+
+    #[test]
+    fn test_majority_without_quorum() {
+        let results = vec![Ok(vec![record(1, 3)]), dead_replica(), dead_replica()];
+
+        let err = successful_responses(results, 2).unwrap_err();
+        assert!(err.to_string().contains("2 of 3 read operations failed"));
+    }
+
+    #[test]
+    fn test_majority_with_non_transient_error() {
+        let results = vec![
+            Ok(vec![record(1, 3)]),
+            Ok(vec![record(1, 3)]),
+            Err(CollectionError::bad_input("bad request")),
+        ];
+
+        let err = successful_responses(results, 2).unwrap_err();
+        assert!(matches!(err, CollectionError::BadInput { .. }));
+    }
 }
@@ -1,10 +1,16 @@
 pub mod clock_set;
 mod execute_read_operation;
 mod locally_disabled_peers;
+mod read_circuit_breaker;
+mod read_latencies;
 mod read_ops;
+mod read_stats;
+mod replication_lag;
+mod segment_upgrade;
 mod shard_transfer;
 mod snapshots;
 mod update;
+mod wal_compaction;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref as _;
@@ -88,6 +94,14 @@ pub struct ShardReplicaSet {
     /// If the state of the peer is changed in the consensus, it is removed from the list.
     /// Update and read operations are not performed on the peers marked as dead.
     locally_disabled_peers: parking_lot::RwLock<locally_disabled_peers::Registry>,
+    /// Peers, which repeatedly timed out on reads. Reads are routed to other replicas first.
+    read_circuit_breaker: Arc<parking_lot::Mutex<read_circuit_breaker::Registry>>,
+    /// Latencies of recent reads, to decide when to hedge a read to another replica
+    read_latencies: parking_lot::Mutex<read_latencies::ReadLatencies>,
+    /// Latencies and error rates of reads per peer, to prefer faster replicas
+    read_stats: parking_lot::Mutex<read_stats::Registry>,
+    /// Replication lags of remote replicas, refreshed in the background
+    replication_lags: parking_lot::Mutex<replication_lag::ReplicationLags>,
     pub(crate) shard_path: PathBuf,
     pub(crate) shard_id: ShardId,
     notify_peer_failure_cb: ChangePeerState,
@@ -185,6 +199,10 @@ impl ShardReplicaSet {
             remotes: RwLock::new(remote_shards),
             replica_state: replica_state.into(),
             locally_disabled_peers: Default::default(),
+            read_circuit_breaker: Default::default(),
+            read_latencies: Default::default(),
+            read_stats: Default::default(),
+            replication_lags: Default::default(),
             shard_path,
             abort_shard_transfer_cb: abort_shard_transfer,
             notify_peer_failure_cb: on_peer_failure,
@@ -302,6 +320,10 @@ impl ShardReplicaSet {
             replica_state: replica_state.into(),
             // TODO: move to collection config
             locally_disabled_peers: Default::default(),
+            read_circuit_breaker: Default::default(),
+            read_latencies: Default::default(),
+            read_stats: Default::default(),
+            replication_lags: Default::default(),
             shard_path: shard_path.to_path_buf(),
             notify_peer_failure_cb: on_peer_failure,
             abort_shard_transfer_cb: abort_shard_transfer,
@@ -741,6 +763,8 @@ impl ShardReplicaSet {
     }
 
     pub(crate) async fn get_telemetry_data(&self, detail: TelemetryDetail) -> ReplicaSetTelemetry {
+        let replication_lags = self.replication_lags();
+
         let local_shard = self.local.read().await;
         let local = local_shard
             .as_ref()
@@ -753,7 +777,12 @@ impl ShardReplicaSet {
                 .read()
                 .await
                 .iter()
-                .map(|remote| remote.get_telemetry_data(detail))
+                .map(|remote| {
+                    let mut telemetry = remote.get_telemetry_data(detail);
+                    telemetry.replication_lag = replication_lags.get(remote.peer_id);
+                    telemetry.degraded = replication_lags.is_degraded(remote.peer_id);
+                    telemetry
+                })
                 .collect(),
             replicate_states: self.replica_state.read().peers(),
         }
@@ -0,0 +1,162 @@
+use std::cmp;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::shards::shard::PeerId;
+
+/// Number of consecutive timed out reads, after which the circuit of a peer opens
+const TIMEOUTS_THRESHOLD: usize = 3;
+
+/// How long the circuit stays open before the first probe of the peer
+const INITIAL_OPEN_DURATION: Duration = Duration::from_secs(5);
+
+/// Maximum time between probes of a peer which keeps failing them
+const MAX_OPEN_DURATION: Duration = Duration::from_secs(60);
+
+/// Circuit breaker for reads from remote replicas.
+///
+/// After repeated timeouts of reads from a peer, its circuit opens: reads are routed to other
+/// replicas first, and the peer is probed in the background until it responds again, instead of
+/// every read waiting for its timeout.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    circuits: HashMap<PeerId, Circuit>,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct Circuit {
+    consecutive_timeouts: usize,
+    open: Option<OpenCircuit>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct OpenCircuit {
+    since: Instant,
+    duration: Duration,
+    is_probing: bool,
+}
+
+impl Registry {
+    pub fn is_open(&self, peer_id: PeerId) -> bool {
+        self.circuits
+            .get(&peer_id)
+            .map_or(false, |circuit| circuit.open.is_some())
+    }
+
+    pub fn record_success(&mut self, peer_id: PeerId) {
+        self.circuits.remove(&peer_id);
+    }
+
+    pub fn record_timeout(&mut self, peer_id: PeerId) {
+        let circuit = self.circuits.entry(peer_id).or_default();
+        circuit.consecutive_timeouts += 1;
+
+        if circuit.open.is_none() && circuit.consecutive_timeouts >= TIMEOUTS_THRESHOLD {
+            log::warn!(
+                "Reads from peer {peer_id} timed out {} times in a row, \
+                 routing reads to other replicas until it responds again",
+                circuit.consecutive_timeouts,
+            );
+
+            circuit.open = Some(OpenCircuit {
+                since: Instant::now(),
+                duration: INITIAL_OPEN_DURATION,
+                is_probing: false,
+            });
+        }
+    }
+
+    /// Peers with an open circuit, which are due to be probed.
+    ///
+    /// Returned peers are marked as probed, until the result of the probe is recorded.
+    pub fn start_due_probes(&mut self) -> Vec<PeerId> {
+        self.circuits
+            .iter_mut()
+            .filter_map(|(&peer_id, circuit)| {
+                let open = circuit.open.as_mut()?;
+                if open.is_probing || open.since.elapsed() < open.duration {
+                    return None;
+                }
+
+                open.is_probing = true;
+                Some(peer_id)
+            })
+            .collect()
+    }
+
+    pub fn record_probe(&mut self, peer_id: PeerId, is_successful: bool) {
+        let Some(circuit) = self.circuits.get_mut(&peer_id) else {
+            return;
+        };
+        let Some(open) = circuit.open.as_mut() else {
+            return;
+        };
+
+        if is_successful {
+            log::info!("Peer {peer_id} responds again, routing reads to it");
+
+            // Open the circuit again after a single timeout, if the peer is still flapping
+            circuit.open = None;
+            circuit.consecutive_timeouts = TIMEOUTS_THRESHOLD - 1;
+        } else {
+            open.since = Instant::now();
+            open.duration = cmp::min(open.duration * 2, MAX_OPEN_DURATION);
+            open.is_probing = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_opens_after_repeated_timeouts() {
+        let mut registry = Registry::default();
+
+        registry.record_timeout(1);
+        registry.record_timeout(1);
+        registry.record_success(1);
+        registry.record_timeout(1);
+        registry.record_timeout(1);
+        assert!(!registry.is_open(1));
+
+        registry.record_timeout(1);
+        assert!(registry.is_open(1));
+        assert!(!registry.is_open(2));
+
+        // Not due to be probed yet
+        assert!(registry.start_due_probes().is_empty());
+    }
+
+    #[test]
+    fn test_probes_close_circuit() {
+        let mut registry = Registry::default();
+        for _ in 0..TIMEOUTS_THRESHOLD {
+            registry.record_timeout(1);
+        }
+        registry
+            .circuits
+            .get_mut(&1)
+            .unwrap()
+            .open
+            .as_mut()
+            .unwrap()
+            .duration = Duration::ZERO;
+
+        assert_eq!(registry.start_due_probes(), vec![1]);
+        // Only one probe at a time
+        assert!(registry.start_due_probes().is_empty());
+
+        registry.record_probe(1, false);
+        assert!(registry.is_open(1));
+
+        assert_eq!(registry.start_due_probes(), vec![1]);
+        registry.record_probe(1, true);
+        assert!(!registry.is_open(1));
+
+        // A single timeout opens the circuit of a peer, which just recovered
+        registry.record_timeout(1);
+        assert!(registry.is_open(1));
+    }
+}
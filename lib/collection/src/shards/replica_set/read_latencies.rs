@@ -0,0 +1,66 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of recent reads, of which latencies are kept
+const WINDOW_SIZE: usize = 1000;
+
+/// Minimal number of recent reads to compute percentiles of their latencies
+const MIN_SAMPLES: usize = 100;
+
+/// Latencies of recent successful reads from replicas of a shard
+#[derive(Clone, Debug, Default)]
+pub struct ReadLatencies {
+    latencies: VecDeque<Duration>,
+}
+
+impl ReadLatencies {
+    pub fn record(&mut self, latency: Duration) {
+        if self.latencies.len() >= WINDOW_SIZE {
+            self.latencies.pop_front();
+        }
+        self.latencies.push_back(latency);
+    }
+
+    /// Latency within which the given percentile of recent reads completed.
+    ///
+    /// Returns `None` if there are not enough recent reads yet.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.latencies.len() < MIN_SAMPLES {
+            return None;
+        }
+
+        let mut latencies: Vec<_> = self.latencies.iter().copied().collect();
+        let max_index = latencies.len() - 1;
+        let index = ((percentile / 100.0) * max_index as f64).round() as usize;
+
+        let (_, latency, _) = latencies.select_nth_unstable(index.min(max_index));
+        Some(*latency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_latencies_percentile() {
+        let mut latencies = ReadLatencies::default();
+        for millis in (1..=MIN_SAMPLES as u64).rev() {
+            assert_eq!(latencies.percentile(50.0), None);
+            latencies.record(Duration::from_millis(millis));
+        }
+
+        assert_eq!(latencies.percentile(0.0), Some(Duration::from_millis(1)));
+        assert_eq!(
+            latencies.percentile(100.0),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(latencies.percentile(95.0), Some(Duration::from_millis(95)));
+
+        // Only recent reads are kept
+        for _ in 0..WINDOW_SIZE {
+            latencies.record(Duration::from_millis(7));
+        }
+        assert_eq!(latencies.percentile(100.0), Some(Duration::from_millis(7)));
+    }
+}
@@ -1,14 +1,31 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref as _;
 use std::sync::Arc;
 use std::time::Duration;
 
-use futures::FutureExt as _;
+use futures::{future, FutureExt as _};
+use segment::common::hyperloglog::HyperLogLog;
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::OrderBy;
+use segment::index::search_stats::SearchStats;
+use segment::telemetry::HnswGraphTelemetry;
 use segment::types::*;
 
 use super::ShardReplicaSet;
-use crate::operations::consistency_params::ReadConsistency;
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::collection_manager::optimizers::OptimizationsProgress;
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
+use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
 use crate::operations::types::*;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::local_shard::changes::ShardChanges;
+use crate::shards::resolve::resolve_newest_records;
+use crate::shards::shard::PeerId;
+use crate::shards::shard_trait::ShardOperation as _;
 
 impl ShardReplicaSet {
     #[allow(clippy::too_many_arguments)]
@@ -94,6 +111,22 @@ impl ShardReplicaSet {
         .await
     }
 
+    /// Aggregate the points of the requested groups in the shard, on a single replica
+    pub async fn group_aggregates(
+        &self,
+        request: Arc<GroupAggregatesRequestInternal>,
+        local_only: bool,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>> {
+        self.execute_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.group_aggregates(request).await }.boxed()
+            },
+            local_only,
+        )
+        .await
+    }
+
     pub async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -102,6 +135,18 @@ impl ShardReplicaSet {
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
     ) -> CollectionResult<Vec<Record>> {
+        let is_majority = matches!(
+            read_consistency,
+            Some(ReadConsistency::Type(ReadConsistencyType::Majority)),
+        );
+
+        if !local_only && is_majority && request.as_of_version.is_none() {
+            let (records, _stale_points) = self
+                .retrieve_newest(request, with_payload, with_vector)
+                .await?;
+            return Ok(records);
+        }
+
         let with_payload = Arc::new(with_payload.clone());
         let with_vector = Arc::new(with_vector.clone());
 
@@ -119,6 +164,90 @@ impl ShardReplicaSet {
         .await
     }
 
+    /// Retrieve points from all active replicas, and pick the newest version of each point.
+    ///
+    /// Replicas which are not active or don't respond are skipped, as long as a majority of all
+    /// replicas responds.
+    ///
+    /// Also returns IDs and versions of points that are outdated or missing on some of the
+    /// replicas, so that the caller can trigger a [read repair](Self::read_repair).
+    pub async fn retrieve_newest(
+        &self,
+        request: Arc<PointRequestInternal>,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> CollectionResult<(Vec<Record>, Vec<(PointIdType, SeqNumberType)>)> {
+        let with_payload = Arc::new(with_payload.clone());
+        let with_vector = Arc::new(with_vector.clone());
+
+        let responses = self
+            .execute_read_operation_on_majority(|shard| {
+                let request = request.clone();
+                let with_payload = with_payload.clone();
+                let with_vector = with_vector.clone();
+
+                async move { shard.retrieve(request, &with_payload, &with_vector).await }.boxed()
+            })
+            .await?;
+
+        Ok(resolve_newest_records(responses))
+    }
+
+    /// Re-apply the newest version of the given points to all replicas.
+    ///
+    /// Points are only repaired if their newest version still matches the version observed when
+    /// the inconsistency was detected, to avoid overwriting concurrent updates.
+    ///
+    /// Returns the number of repaired points.
+    pub async fn read_repair(
+        &self,
+        stale_points: Vec<(PointIdType, SeqNumberType)>,
+    ) -> CollectionResult<usize> {
+        let request = Arc::new(PointRequestInternal {
+            ids: stale_points.iter().map(|(point_id, _)| *point_id).collect(),
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: WithVector::Bool(true),
+            as_of_version: None,
+        });
+
+        let (records, still_stale) = self
+            .retrieve_newest(request, &WithPayload::from(true), &WithVector::Bool(true))
+            .await?;
+
+        let stale_points: HashSet<_> = stale_points.into_iter().collect();
+        let still_stale: HashSet<_> = still_stale.into_iter().collect();
+
+        let points: Vec<_> = records
+            .into_iter()
+            .filter(|record| {
+                let point = (record.id, record.version.unwrap_or_default());
+                stale_points.contains(&point) && still_stale.contains(&point)
+            })
+            .filter_map(|record| PointStruct::try_from(record).ok())
+            .collect();
+
+        let repaired = points.len();
+
+        if repaired == 0 {
+            return Ok(0);
+        }
+
+        log::debug!(
+            "Repairing {repaired} stale points in shard {}:{}",
+            self.collection_id,
+            self.shard_id,
+        );
+
+        let operation = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        ));
+
+        self.update_with_consistency(operation, false, WriteOrdering::Weak)
+            .await?;
+
+        Ok(repaired)
+    }
+
     pub async fn info(&self, local_only: bool) -> CollectionResult<CollectionInfo> {
         self.execute_read_operation(
             |shard| async move { shard.info().await }.boxed(),
@@ -127,6 +256,42 @@ impl ShardReplicaSet {
         .await
     }
 
+    /// Information about the shard on each of its active replicas, queried separately
+    pub async fn info_per_active_replica(&self) -> Vec<(PeerId, CollectionResult<CollectionInfo>)> {
+        let this_peer_id = self.this_peer_id();
+        let active_peers = self.active_shards().await;
+
+        let local = self.local.read().await;
+        let remotes = self.remotes.read().await;
+
+        let requests = active_peers.into_iter().map(|peer_id| {
+            let local = &local;
+            let remotes = &remotes;
+            async move {
+                let info = if peer_id == this_peer_id {
+                    match local.deref() {
+                        Some(shard) => shard.get().info().await,
+                        None => Err(CollectionError::service_error(format!(
+                            "Local replica of shard {} is missing",
+                            self.shard_id,
+                        ))),
+                    }
+                } else {
+                    match remotes.iter().find(|remote| remote.peer_id == peer_id) {
+                        Some(remote) => remote.info().await,
+                        None => Err(CollectionError::service_error(format!(
+                            "Replica of shard {} on peer {peer_id} is missing",
+                            self.shard_id,
+                        ))),
+                    }
+                };
+                (peer_id, info)
+            }
+        });
+
+        future::join_all(requests).await
+    }
+
     pub async fn count_local(
         &self,
         request: Arc<CountRequestInternal>,
@@ -138,19 +303,130 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Distinct values of the keyword index of the field on the local replica, see
+    /// [`crate::shards::local_shard::LocalShard::keyword_values`]
+    ///
+    /// Returns `None` if there is no active local replica of this shard, or the values are unknown.
+    pub async fn keyword_values_local(
+        &self,
+        key: &PayloadKeyType,
+        limit: usize,
+    ) -> Option<HashSet<String>> {
+        if !self.peer_is_active(&self.this_peer_id()) {
+            return None;
+        }
+        let local = self.local.read().await;
+        local.as_ref()?.keyword_values(key, limit)
+    }
+
+    /// Sketch of the distinct values of the keyword index of the field on the local replica, see
+    /// [`crate::shards::local_shard::LocalShard::keyword_values_sketch`]
+    ///
+    /// Returns `None` if there is no active local replica of this shard, or the values are unknown.
+    pub async fn keyword_values_sketch_local(&self, key: &PayloadKeyType) -> Option<HyperLogLog> {
+        if !self.peer_is_active(&self.this_peer_id()) {
+            return None;
+        }
+        let local = self.local.read().await;
+        local.as_ref()?.keyword_values_sketch(key)
+    }
+
+    /// Progress of the optimizations of the local replica, see
+    /// [`crate::shards::local_shard::LocalShard::optimizations_progress`]
+    ///
+    /// Returns `None` if there is no local replica of this shard, or the progress is unknown.
+    pub async fn optimizations_progress_local(&self) -> Option<OptimizationsProgress> {
+        let local = self.local.read().await;
+        local.as_ref()?.optimizations_progress()
+    }
+
+    /// Neighbors of the point in the HNSW graphs of the local replica, see
+    /// [`crate::shards::local_shard::LocalShard::hnsw_neighbors`]
+    ///
+    /// Returns no neighbors if there is no local replica of this shard.
+    pub async fn hnsw_neighbors_local(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> CollectionResult<Vec<(SegmentId, Vec<Vec<PointIdType>>)>> {
+        let local = self.local.read().await;
+        match local.as_ref() {
+            Some(shard) => shard.hnsw_neighbors(vector_name, point_id),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Structure of the HNSW graphs of the local replica, see
+    /// [`crate::shards::local_shard::LocalShard::hnsw_graphs`]
+    pub async fn hnsw_graphs_local(&self) -> CollectionResult<Vec<HnswGraphTelemetry>> {
+        let local = self.local.read().await;
+        match local.as_ref() {
+            Some(shard) => shard.hnsw_graphs().await,
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Read changes of the local replica since the given WAL position
+    ///
+    /// Returns `None` if there is no local replica of this shard.
+    pub async fn changes_since_local(
+        &self,
+        cursor: Option<SeqNumberType>,
+        limit: usize,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> CollectionResult<Option<ShardChanges>> {
+        let local = self.local.read().await;
+        match &*local {
+            None => Ok(None),
+            Some(shard) => Ok(Some(
+                shard
+                    .changes_since(cursor, limit, with_payload, with_vector)
+                    .await?,
+            )),
+        }
+    }
+
+    /// Query the shard, with the routing of the points if `with_routing` is set
+    ///
+    /// The work of all queried replicas is counted into `search_stats`, if given.
+    #[allow(clippy::too_many_arguments)]
     pub async fn query_batch(
         &self,
         requests: Arc<Vec<ShardQueryRequest>>,
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
         timeout: Option<Duration>,
+        with_routing: bool,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         self.execute_and_resolve_read_operation(
             |shard| {
                 let requests = Arc::clone(&requests);
                 let search_runtime = self.search_runtime.clone();
+                let search_stats = search_stats.clone();
+                let routing = with_routing.then(|| PointRouting {
+                    shard_key: None,
+                    shard_id: self.shard_id,
+                    peer_id: shard
+                        .remote_peer_id()
+                        .unwrap_or_else(|| self.this_peer_id()),
+                });
 
-                async move { shard.query_batch(requests, &search_runtime, timeout).await }.boxed()
+                async move {
+                    let mut responses = shard
+                        .query_batch(requests, &search_runtime, timeout, search_stats)
+                        .await?;
+                    if let Some(routing) = routing {
+                        responses
+                            .iter_mut()
+                            .flatten()
+                            .flatten()
+                            .for_each(|point| point.routing = Some(routing.clone()));
+                    }
+                    Ok(responses)
+                }
+                .boxed()
             },
             read_consistency,
             local_only,
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::shards::shard::PeerId;
+
+/// Weight of the latest read in the moving averages of a peer
+const SMOOTHING: f64 = 0.1;
+
+/// How much slower than its average latency a peer is considered, if all its reads fail
+const ERROR_PENALTY: f64 = 10.0;
+
+/// Moving statistics of reads from remote replicas, to route reads to faster replicas
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    peers: HashMap<PeerId, ReadStats>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ReadStats {
+    /// Exponential moving average of the latency of successful reads, in seconds
+    latency: Option<f64>,
+    /// Exponential moving average of the fraction of failed reads
+    error_rate: f64,
+}
+
+impl ReadStats {
+    fn score(&self) -> f64 {
+        match self.latency {
+            Some(latency) => latency * (1.0 + ERROR_PENALTY * self.error_rate),
+            // All reads failed so far
+            None => f64::INFINITY,
+        }
+    }
+}
+
+impl Registry {
+    pub fn record_success(&mut self, peer_id: PeerId, latency: Duration) {
+        let latency = latency.as_secs_f64();
+
+        let stats = self.peers.entry(peer_id).or_insert(ReadStats {
+            latency: None,
+            error_rate: 0.0,
+        });
+
+        stats.latency = Some(match stats.latency {
+            Some(average) => average + SMOOTHING * (latency - average),
+            None => latency,
+        });
+        stats.error_rate -= SMOOTHING * stats.error_rate;
+    }
+
+    pub fn record_failure(&mut self, peer_id: PeerId) {
+        let stats = self.peers.entry(peer_id).or_insert(ReadStats {
+            latency: None,
+            error_rate: 0.0,
+        });
+        stats.error_rate += SMOOTHING * (1.0 - stats.error_rate);
+    }
+
+    /// Score of the peer, lower is better. Peers without statistics are preferred, so that they
+    /// get some reads to be measured.
+    fn score(&self, peer_id: PeerId) -> f64 {
+        self.peers.get(&peer_id).map_or(0.0, ReadStats::score)
+    }
+
+    /// Order replicas to send reads to, using the power of two choices: of two random replicas,
+    /// the one with the better score goes first, repeated for the remaining replicas.
+    ///
+    /// Unlike always choosing the best replica, this spreads the load, while still preferring
+    /// faster replicas.
+    pub fn order<T>(&self, mut replicas: Vec<T>, peer_id: impl Fn(&T) -> PeerId) -> Vec<T> {
+        let mut rng = rand::thread_rng();
+        let mut ordered = Vec::with_capacity(replicas.len());
+
+        while replicas.len() > 1 {
+            let first = rng.gen_range(0..replicas.len());
+            let mut second = rng.gen_range(0..replicas.len() - 1);
+            if second >= first {
+                second += 1;
+            }
+
+            let chosen = if self.score(peer_id(&replicas[first]))
+                <= self.score(peer_id(&replicas[second]))
+            {
+                first
+            } else {
+                second
+            };
+
+            ordered.push(replicas.swap_remove(chosen));
+        }
+
+        ordered.extend(replicas);
+        ordered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_faster_replica_goes_first() {
+        let mut registry = Registry::default();
+        registry.record_success(1, Duration::from_millis(20));
+        registry.record_success(2, Duration::from_millis(10));
+
+        for _ in 0..10 {
+            assert_eq!(registry.order(vec![1, 2], |&peer_id| peer_id), vec![2, 1]);
+        }
+
+        // Errors make a replica less preferred
+        for _ in 0..10 {
+            registry.record_failure(2);
+        }
+        assert_eq!(registry.order(vec![1, 2], |&peer_id| peer_id), vec![1, 2]);
+
+        // Replicas which never responded go last
+        registry.record_failure(3);
+        assert_eq!(registry.order(vec![3, 1], |&peer_id| peer_id), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_order_keeps_all_replicas() {
+        let registry = Registry::default();
+
+        let mut ordered = registry.order(vec![1, 2, 3, 4, 5], |&peer_id| peer_id);
+        ordered.sort_unstable();
+        assert_eq!(ordered, vec![1, 2, 3, 4, 5]);
+
+        assert!(registry
+            .order(Vec::<PeerId>::new(), |&peer_id| peer_id)
+            .is_empty());
+    }
+}
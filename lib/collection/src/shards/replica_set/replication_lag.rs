@@ -0,0 +1,144 @@
+//! Replication lag of remote replicas, relative to the local replica.
+//!
+//! The lag of a replica is the number of operations applied by the local replica, but not yet by
+//! the remote one, according to the clocks of their WALs. Asking all remote replicas for their
+//! clocks is too slow for cluster info and telemetry, so the lags are refreshed periodically in
+//! the background and cached here.
+//!
+//! Active replicas lagging more than the configured threshold are reported as degraded, as they
+//! may be stuck. Reads are routed to replicas which are not degraded first, as degraded ones may
+//! return stale results.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures::future;
+
+use super::{ReplicaState, ShardReplicaSet};
+use crate::shards::local_shard::clock_map::RecoveryPoint;
+use crate::shards::shard::PeerId;
+
+/// Time to wait for the recovery point of a remote replica
+const RECOVERY_POINT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Replication lags of the active remote replicas, as of the last refresh
+#[derive(Clone, Debug, Default)]
+pub struct ReplicationLags {
+    lags: HashMap<PeerId, u64>,
+    threshold: Option<u64>,
+}
+
+impl ReplicationLags {
+    fn new(lags: HashMap<PeerId, u64>, threshold: Option<u64>) -> Self {
+        Self { lags, threshold }
+    }
+
+    /// Lag of the replica on the peer, in number of operations. None, if it is not known.
+    pub fn get(&self, peer_id: PeerId) -> Option<u64> {
+        self.lags.get(&peer_id).copied()
+    }
+
+    /// Whether the replica on the peer lags more than the configured threshold
+    pub fn is_degraded(&self, peer_id: PeerId) -> bool {
+        match (self.get(peer_id), self.threshold) {
+            (Some(lag), Some(threshold)) => lag > threshold,
+            _ => false,
+        }
+    }
+
+    fn degraded_peers(&self) -> impl Iterator<Item = PeerId> + '_ {
+        self.lags
+            .keys()
+            .copied()
+            .filter(|&peer_id| self.is_degraded(peer_id))
+    }
+}
+
+impl ShardReplicaSet {
+    /// Replication lags of the remote replicas, as of the last refresh
+    pub fn replication_lags(&self) -> ReplicationLags {
+        self.replication_lags.lock().clone()
+    }
+
+    /// Measure the replication lags of the active remote replicas again.
+    ///
+    /// Lags are empty if the local replica is not active. Replicas which don't respond in time
+    /// are left out.
+    pub async fn refresh_replication_lags(&self) {
+        let lags = if self.peer_state(&self.this_peer_id()) != Some(ReplicaState::Active) {
+            HashMap::new()
+        } else {
+            match self.shard_recovery_point().await {
+                Ok(local_point) => self.replication_lags_behind(&local_point).await,
+                Err(_) => HashMap::new(),
+            }
+        };
+
+        let lags = ReplicationLags::new(lags, self.shared_storage_config.degraded_replication_lag);
+
+        let mut cached = self.replication_lags.lock();
+        for peer_id in lags.degraded_peers() {
+            if !cached.is_degraded(peer_id) {
+                log::warn!(
+                    "Replica of shard {}:{} on peer {peer_id} lags behind by {} operations, \
+                     routing reads to other replicas first",
+                    self.collection_id,
+                    self.shard_id,
+                    lags.get(peer_id).unwrap_or_default(),
+                );
+            }
+        }
+        *cached = lags;
+    }
+
+    /// Number of operations of the recovery point the active remote replicas have not applied
+    /// yet. Replicas which don't respond in time are left out.
+    pub(super) async fn replication_lags_behind(
+        &self,
+        recovery_point: &RecoveryPoint,
+    ) -> HashMap<PeerId, u64> {
+        let remotes = self.remotes.read().await;
+        let lags = remotes
+            .iter()
+            .filter(|remote| self.peer_state(&remote.peer_id) == Some(ReplicaState::Active))
+            .map(|remote| async move {
+                let remote_point = tokio::time::timeout(
+                    RECOVERY_POINT_TIMEOUT,
+                    remote.shard_recovery_point(&self.collection_id, self.shard_id),
+                )
+                .await
+                .ok()?
+                .ok()?;
+                let lag = recovery_point.count_ticks_ahead_of(&remote_point);
+                Some((remote.peer_id, lag))
+            });
+
+        future::join_all(lags).await.into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degraded_above_threshold() {
+        let lags = ReplicationLags::new(HashMap::from([(1, 10), (2, 11), (3, 0)]), Some(10));
+
+        assert!(!lags.is_degraded(1));
+        assert!(lags.is_degraded(2));
+        assert!(!lags.is_degraded(3));
+        // Unknown lag
+        assert!(!lags.is_degraded(4));
+        assert_eq!(lags.degraded_peers().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_never_degraded_without_threshold() {
+        let lags = ReplicationLags::new(HashMap::from([(1, u64::MAX)]), None);
+
+        assert_eq!(lags.get(1), Some(u64::MAX));
+        assert!(!lags.is_degraded(1));
+        assert_eq!(lags.degraded_peers().count(), 0);
+    }
+}
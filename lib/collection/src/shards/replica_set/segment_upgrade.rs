@@ -0,0 +1,42 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use tokio::task::JoinHandle;
+
+use super::ShardReplicaSet;
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::shard::Shard;
+
+impl ShardReplicaSet {
+    /// Outdated segments of the local replica, see
+    /// [`crate::shards::local_shard::LocalShard::outdated_segments`]
+    ///
+    /// Returns no segments if the local replica is missing or being transferred.
+    pub async fn outdated_segments(&self) -> Vec<SegmentId> {
+        match &*self.local.read().await {
+            Some(Shard::Local(local)) => local.outdated_segments(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Start rewriting an outdated segment of the local replica, see
+    /// [`crate::shards::local_shard::LocalShard::spawn_segment_upgrade`]
+    pub async fn spawn_segment_upgrade(
+        &self,
+        segment_id: SegmentId,
+        permit: CpuPermit,
+        stopped: Arc<AtomicBool>,
+    ) -> CollectionResult<JoinHandle<CollectionResult<bool>>> {
+        match &*self.local.read().await {
+            Some(Shard::Local(local)) => Ok(local
+                .spawn_segment_upgrade(segment_id, permit, stopped)
+                .await),
+            _ => Err(CollectionError::service_error(format!(
+                "No local replica of shard {} to upgrade",
+                self.shard_id,
+            ))),
+        }
+    }
+}
@@ -7,6 +7,7 @@ use itertools::Itertools as _;
 
 use super::{clock_set, ReplicaSetState, ReplicaState, ShardReplicaSet};
 use crate::operations::point_ops::WriteOrdering;
+use crate::operations::session_token::SessionToken;
 use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
 use crate::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
 use crate::shards::shard::PeerId;
@@ -210,7 +211,7 @@ impl ShardReplicaSet {
         let local_is_updatable = local.is_some() && self.peer_is_active_or_pending(&this_peer_id);
 
         if active_remote_shards.is_empty() && !local_is_updatable {
-            return Err(CollectionError::service_error(format!(
+            return Err(CollectionError::shard_unavailable(format!(
                 "The replica set for shard {} on peer {} has no active replica",
                 self.shard_id, this_peer_id
             )));
@@ -399,11 +400,13 @@ impl ShardReplicaSet {
         }
 
         // there are enough successes, return the first one
-        let (_, res) = successes
+        let (peer_id, mut res) = successes
             .into_iter()
             .next()
             .expect("successes is not empty");
 
+        res.session_token = Some(SessionToken::new(self.shard_id, clock_tag, peer_id));
+
         Ok(Some(res))
     }
 
@@ -502,7 +505,12 @@ impl ShardReplicaSet {
         };
 
         remote_leader
-            .forward_update(OperationWithClockTag::from(operation), wait, ordering) // `clock_tag` *have to* be `None`!
+            .forward_update(
+                OperationWithClockTag::from(operation), // `clock_tag` *have to* be `None`!
+                wait,
+                ordering,
+                rand::random(),
+            )
             .await
     }
 }
@@ -0,0 +1,65 @@
+//! Compaction of the WAL of the local replica.
+//!
+//! The WAL keeps operations until the segments report them as persisted. Some write patterns
+//! delay that until a restart, e.g. operations not changing any point don't advance the versions
+//! of the segments. Compaction applies all pending operations and flushes the segments. If they
+//! persisted all changes, all applied operations count as persisted, including the ones which did
+//! not advance any version.
+//!
+//! Only entries covered by the latest snapshot of the shard are truncated, once all replicas have
+//! applied them, so no replica can need them to recover anymore.
+
+use super::{ReplicaState, ShardReplicaSet};
+use crate::operations::types::{CollectionResult, ShardWalCompaction};
+use crate::shards::shard::Shard;
+
+impl ShardReplicaSet {
+    /// Truncate the entries of the WAL of the local replica, which are covered by its latest
+    /// snapshot and applied on all replicas.
+    ///
+    /// Compaction is skipped, with the reason in the result, if any check fails.
+    pub async fn compact_wal(&self) -> CollectionResult<ShardWalCompaction> {
+        let skipped = |reason| Ok(ShardWalCompaction::skipped(self.shard_id, reason));
+
+        // Replicas which are not active may need the entries to recover
+        let this_peer_id = self.this_peer_id();
+        let peers = self.peers();
+        if peers.get(&this_peer_id) != Some(&ReplicaState::Active) {
+            return skipped("no active local replica");
+        }
+        if peers.values().any(|state| *state != ReplicaState::Active) {
+            return skipped("not all replicas are active");
+        }
+
+        let local = self.local.read().await;
+        let local = match &*local {
+            Some(Shard::Local(local)) => local,
+            Some(_) => return skipped("local replica is being transferred"),
+            None => return skipped("no local replica"),
+        };
+
+        let Some((snapshot_wal_index, snapshot_recovery_point)) = local.snapshot_coverage() else {
+            return skipped("no snapshot of the shard since it was loaded");
+        };
+
+        let lags = self.replication_lags_behind(&snapshot_recovery_point).await;
+        let all_applied = peers
+            .keys()
+            .filter(|peer_id| **peer_id != this_peer_id)
+            .all(|peer_id| lags.get(peer_id) == Some(&0));
+        if !all_applied {
+            return skipped("not all replicas have applied the operations covered by the snapshot");
+        }
+
+        let truncated_until = local.compact_wal(snapshot_wal_index).await?;
+        if truncated_until.is_none() {
+            return skipped("no entries to truncate");
+        }
+
+        Ok(ShardWalCompaction {
+            shard_id: self.shard_id,
+            truncated_until,
+            skipped: None,
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
-use segment::types::{Payload, ScoredPoint};
+use segment::types::{Payload, PointIdType, ScoredPoint, SeqNumberType};
 use tinyvec::TinyVec;
 
 use crate::common::transpose_iterator::transposed_iter;
@@ -84,6 +84,61 @@ impl Resolve for Vec<ShardQueryResponse> {
     }
 }
 
+/// Resolve records retrieved from multiple replicas by picking the newest version of each point.
+///
+/// A point is returned if it is present on the majority of replicas, in which case the copy with
+/// the highest version wins. Points present only on a minority of replicas are considered deleted
+/// and are not returned.
+///
+/// Also returns IDs and versions of returned points for which at least one replica is missing the
+/// point or holds a different copy. These can be used to repair stale replicas.
+pub fn resolve_newest_records(
+    replicas: Vec<Vec<Record>>,
+) -> (Vec<Record>, Vec<(PointIdType, SeqNumberType)>) {
+    let replicas_count = replicas.len();
+    let majority = replicas_count / 2 + 1;
+
+    // Newest copy of each point, number of replicas that hold this exact copy and number of
+    // replicas that hold any copy of the point
+    let mut newest: HashMap<PointIdType, (Record, usize, usize)> = HashMap::new();
+
+    for record in replicas.into_iter().flatten() {
+        match newest.get_mut(&record.id) {
+            None => {
+                newest.insert(record.id, (record, 1, 1));
+            }
+            Some((current, same_count, present_count)) => {
+                *present_count += 1;
+
+                if current.version == record.version && record_eq(current, &record) {
+                    *same_count += 1;
+                } else if record.version > current.version {
+                    *current = record;
+                    *same_count = 1;
+                }
+            }
+        }
+    }
+
+    let mut stale = Vec::new();
+
+    let mut resolved: Vec<_> = newest
+        .into_values()
+        .filter(|(_, _, present_count)| *present_count >= majority)
+        .map(|(record, same_count, _)| {
+            if same_count < replicas_count {
+                stale.push((record.id, record.version.unwrap_or_default()));
+            }
+            record
+        })
+        .collect();
+
+    resolved.sort_unstable_by_key(|record| record.id);
+    stale.sort_unstable();
+
+    (resolved, stale)
+}
+
 fn record_eq(this: &Record, other: &Record) -> bool {
     this.id == other.id && this.vector == other.vector && payload_eq(&this.payload, &other.payload)
 }
@@ -279,6 +334,9 @@ mod test {
             vector: None,
             shard_key: None,
             order_value: None,
+            prefetch_matches: None,
+            routing: None,
+            raw_score: None,
         }
     }
 
@@ -409,6 +467,56 @@ mod test {
         );
     }
 
+    fn record(id: u64, version: SeqNumberType) -> Record {
+        Record {
+            id: id.into(),
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            version: Some(version),
+        }
+    }
+
+    #[test]
+    fn resolve_newest_records_consistent() {
+        let replica = vec![record(1, 3), record(2, 5)];
+
+        let (resolved, stale) = resolve_newest_records(vec![replica.clone(); 3]);
+
+        assert_eq!(resolved, replica);
+        assert!(stale.is_empty());
+    }
+
+    #[test]
+    fn resolve_newest_records_picks_newest() {
+        let input = vec![
+            vec![record(1, 3), record(2, 5)],
+            vec![record(1, 4), record(2, 5)],
+            vec![record(1, 3), record(2, 5)],
+        ];
+
+        let (resolved, stale) = resolve_newest_records(input);
+
+        assert_eq!(resolved, vec![record(1, 4), record(2, 5)]);
+        assert_eq!(stale, vec![(1.into(), 4)]);
+    }
+
+    #[test]
+    fn resolve_newest_records_missing_points() {
+        let input = vec![
+            vec![record(1, 3), record(2, 5), record(3, 1)],
+            vec![record(1, 3), record(2, 5)],
+            vec![record(1, 3)],
+        ];
+
+        let (resolved, stale) = resolve_newest_records(input);
+
+        // Point 3 is only present on a minority of replicas, it is considered deleted
+        assert_eq!(resolved, vec![record(1, 3), record(2, 5)]);
+        assert_eq!(stale, vec![(2.into(), 5)]);
+    }
+
     fn data_simple() -> [i32; 9] {
         [1, 2, 3, 4, 5, 6, 7, 8, 9]
     }
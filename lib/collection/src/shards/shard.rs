@@ -1,11 +1,19 @@
 use core::marker::{Send, Sync};
+use std::collections::HashSet;
 use std::future::{self, Future};
 use std::path::Path;
 
 use common::types::TelemetryDetail;
+use segment::common::hyperloglog::HyperLogLog;
+use segment::telemetry::HnswGraphTelemetry;
+use segment::types::{PayloadKeyType, PointIdType, SeqNumberType, WithPayload, WithVector};
 
+use super::local_shard::changes::ShardChanges;
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::collection_manager::optimizers::OptimizationsProgress;
+use crate::operations::session_token::ShardSessionClock;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
@@ -162,6 +170,96 @@ impl Shard {
         }
     }
 
+    pub async fn changes_since(
+        &self,
+        cursor: Option<SeqNumberType>,
+        limit: usize,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> CollectionResult<ShardChanges> {
+        match self {
+            Self::Local(local_shard) => {
+                local_shard
+                    .changes_since(cursor, limit, with_payload, with_vector)
+                    .await
+            }
+            Self::ForwardProxy(proxy_shard) => {
+                proxy_shard
+                    .wrapped_shard
+                    .changes_since(cursor, limit, with_payload, with_vector)
+                    .await
+            }
+
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => {
+                Err(CollectionError::service_error(format!(
+                    "Point changes not supported on {}",
+                    self.variant_name(),
+                )))
+            }
+        }
+    }
+
+    /// Distinct values of the keyword index of the field, see [`LocalShard::keyword_values`]
+    ///
+    /// Returns `None` if they are unknown, e.g. for a shard being transferred.
+    pub fn keyword_values(&self, key: &PayloadKeyType, limit: usize) -> Option<HashSet<String>> {
+        match self {
+            Self::Local(local_shard) => local_shard.keyword_values(key, limit),
+            Self::ForwardProxy(proxy_shard) => proxy_shard.wrapped_shard.keyword_values(key, limit),
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => None,
+        }
+    }
+
+    /// Sketch of the distinct values of the keyword index of the field, see
+    /// [`LocalShard::keyword_values_sketch`]
+    ///
+    /// Returns `None` if they are unknown, e.g. for a shard being transferred.
+    pub fn keyword_values_sketch(&self, key: &PayloadKeyType) -> Option<HyperLogLog> {
+        match self {
+            Self::Local(local_shard) => local_shard.keyword_values_sketch(key),
+            Self::ForwardProxy(proxy_shard) => proxy_shard.wrapped_shard.keyword_values_sketch(key),
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => None,
+        }
+    }
+
+    /// Progress of the optimizations of the shard, see [`LocalShard::optimizations_progress`]
+    ///
+    /// Returns `None` if it is unknown, e.g. for a shard being transferred.
+    pub fn optimizations_progress(&self) -> Option<OptimizationsProgress> {
+        match self {
+            Self::Local(local_shard) => Some(local_shard.optimizations_progress()),
+            Self::ForwardProxy(proxy_shard) => {
+                Some(proxy_shard.wrapped_shard.optimizations_progress())
+            }
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => None,
+        }
+    }
+
+    /// Neighbors of the point in the HNSW graphs of the segments, see
+    /// [`LocalShard::hnsw_neighbors`]
+    pub fn hnsw_neighbors(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> CollectionResult<Vec<(SegmentId, Vec<Vec<PointIdType>>)>> {
+        match self {
+            Self::Local(local_shard) => local_shard.hnsw_neighbors(vector_name, point_id),
+            Self::ForwardProxy(proxy_shard) => proxy_shard
+                .wrapped_shard
+                .hnsw_neighbors(vector_name, point_id),
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => Ok(vec![]),
+        }
+    }
+
+    /// Structure of the HNSW graphs of the segments, see [`LocalShard::hnsw_graphs`]
+    pub async fn hnsw_graphs(&self) -> CollectionResult<Vec<HnswGraphTelemetry>> {
+        match self {
+            Self::Local(local_shard) => local_shard.hnsw_graphs().await,
+            Self::ForwardProxy(proxy_shard) => proxy_shard.wrapped_shard.hnsw_graphs().await,
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => Ok(vec![]),
+        }
+    }
+
     pub async fn update_cutoff(&self, cutoff: &RecoveryPoint) -> CollectionResult<()> {
         match self {
             Self::Local(local_shard) => local_shard.update_cutoff(cutoff).await,
@@ -176,6 +274,24 @@ impl Shard {
         Ok(())
     }
 
+    /// Check if the writes with the given session clocks reached this shard, and if so, wait until
+    /// they are applied to the segments
+    ///
+    /// Returns `false` if they did not reach the shard yet, or if the shard can't tell.
+    pub async fn apply_session_clocks(
+        &self,
+        clocks: &[ShardSessionClock],
+    ) -> CollectionResult<bool> {
+        match self {
+            Self::Local(local_shard) => local_shard.apply_session_clocks(clocks).await,
+            Self::ForwardProxy(proxy_shard) => {
+                proxy_shard.wrapped_shard.apply_session_clocks(clocks).await
+            }
+
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => Ok(false),
+        }
+    }
+
     pub async fn resolve_wal_delta(
         &self,
         recovery_point: RecoveryPoint,
@@ -1,14 +1,19 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::OrderBy;
+use segment::index::search_stats::SearchStats;
 use segment::types::*;
 use tokio::runtime::Handle;
 
+use crate::grouping::aggregates::{GroupAggregatesRequestInternal, ShardGroupAggregates};
 use crate::operations::types::*;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
+use crate::shards::shard::PeerId;
 
 #[async_trait]
 pub trait ShardOperation {
@@ -41,6 +46,12 @@ pub trait ShardOperation {
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult>;
 
+    /// Aggregate the points of the requested groups in the shard
+    async fn group_aggregates(
+        &self,
+        request: Arc<GroupAggregatesRequestInternal>,
+    ) -> CollectionResult<HashMap<GroupId, ShardGroupAggregates>>;
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -48,12 +59,19 @@ pub trait ShardOperation {
         with_vector: &WithVector,
     ) -> CollectionResult<Vec<Record>>;
 
+    /// Query the shard, counting the work done into `search_stats` if given
     async fn query_batch(
         &self,
         requests: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> CollectionResult<Vec<ShardQueryResponse>>;
+
+    /// Peer of the replica, if it is on another peer
+    fn remote_peer_id(&self) -> Option<PeerId> {
+        None
+    }
 }
 
 pub type ShardOperationSS = dyn ShardOperation + Send + Sync;
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
+use std::ops::Add;
 
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_time_statistics::OperationDurationStatistics;
+use segment::index::search_pools::{PoolCounter, SearchPoolCounters};
 use segment::telemetry::SegmentTelemetry;
 use serde::Serialize;
 
@@ -25,6 +27,11 @@ pub struct RemoteShardTelemetry {
     pub peer_id: Option<PeerId>,
     pub searches: OperationDurationStatistics,
     pub updates: OperationDurationStatistics,
+    /// Number of operations applied by the local replica, but not yet by this replica
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replication_lag: Option<u64>,
+    /// If true - the replication lag exceeds the configured threshold
+    pub degraded: bool,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -32,6 +39,72 @@ pub struct LocalShardTelemetry {
     pub variant_name: Option<String>,
     pub segments: Vec<SegmentTelemetry>,
     pub optimizations: OptimizerTelemetry,
+    pub search_pools: SearchPoolsTelemetry,
+}
+
+/// Reuse of pooled memory by searches
+#[derive(Serialize, Clone, Copy, Debug, JsonSchema, Default)]
+pub struct SearchPoolsTelemetry {
+    /// Visited lists of HNSW searches
+    pub visited_lists: PoolTelemetry,
+    /// Score buffers of sparse vector searches
+    pub search_contexts: PoolTelemetry,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, JsonSchema, Default)]
+pub struct PoolTelemetry {
+    /// Number of searches which reused pooled memory
+    pub reused: usize,
+    /// Number of searches which allocated memory, because the pool was empty
+    pub allocated: usize,
+}
+
+impl PoolTelemetry {
+    /// Share of searches which reused pooled memory, if there were any searches
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.reused + self.allocated;
+        (total > 0).then(|| self.reused as f64 / total as f64)
+    }
+}
+
+impl From<&PoolCounter> for PoolTelemetry {
+    fn from(counter: &PoolCounter) -> Self {
+        Self {
+            reused: counter.reused(),
+            allocated: counter.allocated(),
+        }
+    }
+}
+
+impl From<&SearchPoolCounters> for SearchPoolsTelemetry {
+    fn from(counters: &SearchPoolCounters) -> Self {
+        Self {
+            visited_lists: PoolTelemetry::from(&counters.visited_lists),
+            search_contexts: PoolTelemetry::from(&counters.search_contexts),
+        }
+    }
+}
+
+impl Add for PoolTelemetry {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            reused: self.reused + other.reused,
+            allocated: self.allocated + other.allocated,
+        }
+    }
+}
+
+impl Add for SearchPoolsTelemetry {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            visited_lists: self.visited_lists + other.visited_lists,
+            search_contexts: self.search_contexts + other.search_contexts,
+        }
+    }
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Default)]
@@ -57,6 +130,7 @@ impl Anonymize for LocalShardTelemetry {
             variant_name: self.variant_name.clone(),
             segments: self.segments.anonymize(),
             optimizations: self.optimizations.anonymize(),
+            search_pools: self.search_pools,
         }
     }
 }
@@ -80,6 +154,8 @@ impl Anonymize for RemoteShardTelemetry {
             peer_id: None,
             searches: self.searches.anonymize(),
             updates: self.updates.anonymize(),
+            replication_lag: self.replication_lag,
+            degraded: self.degraded,
         }
     }
 }
@@ -2,9 +2,12 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use serde::Serialize;
 
+use crate::common::query_memory::QueryMemoryStatistics;
+use crate::common::query_usage::QueryUsageStatistics;
+use crate::common::resource_usage::ResourceUsageStatistics;
 use crate::config::CollectionConfig;
 use crate::operations::types::ShardTransferInfo;
-use crate::shards::telemetry::ReplicaSetTelemetry;
+use crate::shards::telemetry::{ReplicaSetTelemetry, SearchPoolsTelemetry};
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
 pub struct CollectionTelemetry {
@@ -13,6 +16,9 @@ pub struct CollectionTelemetry {
     pub config: CollectionConfig,
     pub shards: Vec<ReplicaSetTelemetry>,
     pub transfers: Vec<ShardTransferInfo>,
+    pub query_memory: QueryMemoryStatistics,
+    pub query_usage: QueryUsageStatistics,
+    pub resource_usage: ResourceUsageStatistics,
 }
 
 impl CollectionTelemetry {
@@ -24,6 +30,32 @@ impl CollectionTelemetry {
             .map(|s| s.info.num_vectors)
             .sum()
     }
+
+    pub fn max_replication_lag(&self) -> u64 {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.remote.iter())
+            .filter_map(|remote| remote.replication_lag)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Reuse of pooled memory by searches on the local shards
+    pub fn search_pools(&self) -> SearchPoolsTelemetry {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.local.as_ref())
+            .map(|local| local.search_pools)
+            .fold(SearchPoolsTelemetry::default(), |acc, x| acc + x)
+    }
+
+    pub fn count_degraded_replicas(&self) -> usize {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.remote.iter())
+            .filter(|remote| remote.degraded)
+            .count()
+    }
 }
 
 impl Anonymize for CollectionTelemetry {
@@ -34,6 +66,9 @@ impl Anonymize for CollectionTelemetry {
             init_time_ms: self.init_time_ms,
             shards: self.shards.anonymize(),
             transfers: vec![],
+            query_memory: self.query_memory.anonymize(),
+            query_usage: self.query_usage.anonymize(),
+            resource_usage: self.resource_usage.anonymize(),
         }
     }
 }
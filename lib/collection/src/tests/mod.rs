@@ -1,6 +1,7 @@
 mod fix_payload_indices;
 pub mod fixtures;
 mod points_dedup;
+mod segment_upgrade_test;
 mod sha_256_test;
 mod shard_query;
 mod snapshot_test;
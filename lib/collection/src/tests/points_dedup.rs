@@ -213,6 +213,7 @@ async fn test_retrieve_dedup() {
                     .collect(),
                 with_payload: Some(false.into()),
                 with_vector: false.into(),
+                as_of_version: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -0,0 +1,106 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::{CpuBudget, CpuPermit};
+use segment::segment::Segment;
+use semver::Version;
+use tempfile::Builder;
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
+
+use crate::save_on_disk::SaveOnDisk;
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard_trait::ShardOperation;
+use crate::tests::fixtures::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_upgrade_outdated_segments() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.optimizer_config.default_segment_number = 2;
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let load_shard = || {
+        LocalShard::load(
+            0,
+            collection_name.clone(),
+            collection_dir.path(),
+            Arc::new(RwLock::new(config.clone())),
+            config.optimizer_config.clone(),
+            Arc::new(Default::default()),
+            payload_index_schema.clone(),
+            current_runtime.clone(),
+            CpuBudget::default(),
+        )
+    };
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema.clone(),
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    shard.update(upsert_operation().into(), true).await.unwrap();
+    assert!(shard.outdated_segments().is_empty());
+    drop(shard);
+
+    // Mark all segments as written by an older format, as done when loading them
+    let segments_path = LocalShard::segments_path(collection_dir.path());
+    for entry in std::fs::read_dir(&segments_path).unwrap() {
+        let segment_path = entry.unwrap().path();
+        if segment_path.is_dir() {
+            Segment::mark_outdated_format(&segment_path, &Version::new(0, 5, 0)).unwrap();
+        }
+    }
+
+    let shard = load_shard().await.unwrap();
+    let outdated_segments = shard.outdated_segments();
+    assert_eq!(outdated_segments.len(), 2);
+
+    // Rewrite a single segment, as if the upgrade was interrupted afterwards
+    let stopped = Arc::new(AtomicBool::new(false));
+    let upgraded = shard
+        .spawn_segment_upgrade(outdated_segments[0], CpuPermit::dummy(1), stopped.clone())
+        .await
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(upgraded);
+    assert_eq!(shard.outdated_segments().len(), 1);
+    drop(shard);
+
+    // The upgrade continues with the remaining segment after a restart
+    let shard = load_shard().await.unwrap();
+    let outdated_segments = shard.outdated_segments();
+    assert_eq!(outdated_segments.len(), 1);
+
+    let upgraded = shard
+        .spawn_segment_upgrade(outdated_segments[0], CpuPermit::dummy(1), stopped)
+        .await
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(upgraded);
+    assert!(shard.outdated_segments().is_empty());
+
+    // No points are lost by rewriting the segments
+    let info = shard.info().await.unwrap();
+    assert_eq!(info.points_count, Some(5));
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use common::cpu::CpuBudget;
 use segment::data_types::vectors::{NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{PointIdType, WithPayloadInterface, WithVector};
+use segment::types::{PointIdType, ScoredPoint, WithPayloadInterface, WithVector};
 use tempfile::Builder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
@@ -10,7 +10,7 @@ use tokio::sync::RwLock;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::CollectionError;
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest,
+    Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest,
 };
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::local_shard::LocalShard;
@@ -55,6 +55,9 @@ async fn test_shard_query_rrf_rescoring() {
         prefetches: vec![],
         query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
         filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
         score_threshold: None,
         limit: 0,
         offset: 0,
@@ -64,7 +67,7 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
         .await;
     let expected_error =
         CollectionError::bad_request("cannot apply Fusion without prefetches".to_string());
@@ -89,6 +92,9 @@ async fn test_shard_query_rrf_rescoring() {
         prefetches: vec![nearest_query_prefetch.clone()],
         query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
         filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
         score_threshold: None,
         limit: outer_limit,
         offset: 0,
@@ -98,7 +104,7 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
         .await
         .unwrap()
         .pop()
@@ -135,6 +141,9 @@ async fn test_shard_query_rrf_rescoring() {
         ],
         query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
         filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
         score_threshold: None,
         limit: outer_limit,
         offset: 0,
@@ -144,7 +153,7 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
         .await
         .unwrap()
         .pop()
@@ -178,6 +187,9 @@ async fn test_shard_query_rrf_rescoring() {
         ],
         query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
         filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
         score_threshold: None,
         limit: outer_limit,
         offset: 0,
@@ -187,7 +199,7 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
         .await
         .unwrap()
         .pop()
@@ -255,6 +267,9 @@ async fn test_shard_query_vector_rescoring() {
         prefetches: vec![],
         query: Some(ScoringQuery::Vector(nearest_query.clone())),
         filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
         score_threshold: None,
         limit: outer_limit,
         offset: 0,
@@ -264,7 +279,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
         .await
         .unwrap()
         .pop()
@@ -281,6 +296,9 @@ async fn test_shard_query_vector_rescoring() {
         prefetches: vec![nearest_query_prefetch.clone()],
         query: Some(ScoringQuery::Vector(nearest_query.clone())),
         filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
         score_threshold: None,
         limit: outer_limit,
         offset: 0,
@@ -290,7 +308,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
         .await
         .unwrap()
         .pop()
@@ -310,6 +328,9 @@ async fn test_shard_query_vector_rescoring() {
         ],
         query: Some(ScoringQuery::Vector(nearest_query)),
         filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
         score_threshold: None,
         limit: outer_limit,
         offset: 0,
@@ -319,7 +340,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
         .await
         .unwrap()
         .pop()
@@ -376,6 +397,9 @@ async fn test_shard_query_payload_vector() {
         prefetches: vec![],
         query: Some(ScoringQuery::Vector(nearest_query)),
         filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
         score_threshold: None,
         limit: outer_limit,
         offset: 0,
@@ -385,7 +409,7 @@ async fn test_shard_query_payload_vector() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
         .await
         .unwrap()
         .pop()
@@ -400,4 +424,145 @@ async fn test_shard_query_payload_vector() {
         assert!(scored_point.vector.is_some());
         assert!(scored_point.payload.is_some());
     });
+
+    // fusion of prefetches, which is done by the collection
+    let nearest_query = QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+        Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+        DEFAULT_VECTOR_NAME,
+    ));
+    let inner_limit = 3;
+    let nearest_prefetch = ShardPrefetch {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Vector(nearest_query)),
+        limit: inner_limit,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+    let mut query = ShardQueryRequest {
+        prefetches: vec![nearest_prefetch.clone(), nearest_prefetch],
+        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+        filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
+        score_threshold: None,
+        limit: outer_limit,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(true), // requesting vector
+        with_payload: WithPayloadInterface::Bool(true), // requesting payload
+    };
+
+    let sources_scores = shard
+        .query_batch(Arc::new(vec![query.clone()]), &current_runtime, None, None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    // one inner result per prefetch
+    assert_eq!(sources_scores.len(), 2);
+    // coordinators of older versions expect payload/vector on the sources
+    sources_scores.iter().flatten().for_each(|scored_point| {
+        assert!(scored_point.vector.is_some());
+        assert!(scored_point.payload.is_some());
+    });
+
+    // the collection fetches payload/vector for the fused points itself
+    query.defer_fusion_payload = true;
+    let sources_scores = shard
+        .query_batch(Arc::new(vec![query]), &current_runtime, None, None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    assert_eq!(sources_scores.len(), 2);
+    // payload/vector are only fetched for the fused points, not for the sources
+    sources_scores.iter().flatten().for_each(|scored_point| {
+        assert!(scored_point.vector.is_none());
+        assert!(scored_point.payload.is_none());
+    });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_sample() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let upsert_ops = upsert_operation();
+
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    let sample_query = |seed, limit| ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Sample(Sample { seed })),
+        filter: None,
+        propagate_filter: true,
+        single_shard: false,
+        defer_fusion_payload: false,
+        score_threshold: None,
+        limit,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(true),
+    };
+
+    let sample = |seed, limit| {
+        let shard = &shard;
+        let current_runtime = &current_runtime;
+        async move {
+            shard
+                .query_batch(
+                    Arc::new(vec![sample_query(seed, limit)]),
+                    current_runtime,
+                    None,
+                    None,
+                )
+                .await
+                .unwrap()
+                .pop()
+                .unwrap()
+                .pop()
+                .unwrap()
+        }
+    };
+
+    let points = sample(42, 3).await;
+    assert_eq!(points.len(), 3);
+    assert!(points.iter().all(|point| point.payload.is_some()));
+    assert!(points.windows(2).all(|w| w[0].score >= w[1].score));
+
+    // The same seed picks the same points
+    let ids = |points: &[ScoredPoint]| points.iter().map(|point| point.id).collect::<Vec<_>>();
+    assert_eq!(ids(&sample(42, 3).await), ids(&points));
+
+    // A smaller sample is the top of a larger one
+    assert_eq!(ids(&sample(42, 2).await), ids(&points[..2]));
 }
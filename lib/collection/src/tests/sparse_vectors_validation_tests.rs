@@ -103,6 +103,8 @@ fn validate_error_sparse_vector_search_groups_request_internal() {
             group_size: 5,
             limit: 5,
             with_lookup: None,
+            with_neighbors: None,
+            with_aggregates: None,
         },
     });
 }
@@ -127,6 +129,7 @@ fn validate_error_sparse_vector_recommend_request_internal() {
         score_threshold: None,
         using: None,
         lookup_from: None,
+        skip_missing: None,
     });
 }
 
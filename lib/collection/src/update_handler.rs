@@ -26,6 +26,7 @@ use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
 use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
+use crate::common::resource_usage::{self, ResourceUsage};
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
 use crate::config::CollectionParams;
 use crate::operations::shared_storage_config::SharedStorageConfig;
@@ -54,6 +55,8 @@ pub struct OperationData {
     pub wait: bool,
     /// Callback notification channel
     pub sender: Option<oneshot::Sender<CollectionResult<usize>>>,
+    /// Counters of the collection, to attribute the resources used by the operation to
+    pub resource_usage: Option<Arc<ResourceUsage>>,
 }
 
 /// Signal, used to inform Updater process
@@ -630,6 +633,7 @@ impl UpdateHandler {
                     operation,
                     sender,
                     wait,
+                    resource_usage,
                 }) => {
                     let flush_res = if wait {
                         wal.lock().flush().map_err(|err| {
@@ -642,8 +646,11 @@ impl UpdateHandler {
                         Ok(())
                     };
 
-                    let operation_result = flush_res
-                        .and_then(|_| CollectionUpdater::update(&segments, op_num, operation));
+                    let operation_result = flush_res.and_then(|_| {
+                        resource_usage::measure(resource_usage.as_deref(), || {
+                            CollectionUpdater::update(&segments, op_num, operation)
+                        })
+                    });
 
                     let res = match operation_result {
                         Ok(update_res) => optimize_sender
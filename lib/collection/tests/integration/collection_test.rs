@@ -1,13 +1,13 @@
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 
-use api::rest::{OrderByInterface, SearchRequestInternal};
+use api::rest::{OrderByInterface, SearchRequestInternal, VectorInput};
 use collection::operations::payload_ops::{PayloadOps, SetPayloadOp};
 use collection::operations::point_ops::{Batch, PointOperations, PointStruct, WriteOrdering};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CountRequestInternal, PointRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
-    UpdateStatus,
+    CountRequestInternal, PointRequestInternal, RecommendRequestInternal, ScoreRequestInternal,
+    ScrollRequestInternal, UpdateStatus,
 };
 use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
@@ -247,6 +247,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
         ids: vec![1.into(), 2.into()],
         with_payload: Some(WithPayloadInterface::Bool(true)),
         with_vector: true.into(),
+        as_of_version: None,
     };
     let retrieved = loaded_collection
         .retrieve(request, None, &ShardSelectorInternal::All)
@@ -432,6 +433,65 @@ async fn test_read_api_with_shards(shard_number: u32) {
     assert_eq!(result.points.len(), 2);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_score_vectors() {
+    test_score_vectors_with_shards(1).await;
+    test_score_vectors_with_shards(N_SHARDS).await;
+}
+
+async fn test_score_vectors_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        Batch {
+            ids: vec![0.into(), 1.into()],
+            vectors: BatchVectorStructInternal::from(vec![
+                vec![1.0, 2.0, 0.0, 0.0],
+                vec![0.0, 0.0, 3.0, 1.0],
+            ])
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    ));
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let request = ScoreRequestInternal {
+        query: VectorInput::DenseVector(vec![1.0, 1.0, 1.0, 1.0]),
+        targets: vec![
+            VectorInput::Id(1.into()),
+            VectorInput::DenseVector(vec![2.0, 0.0, 0.0, 0.0]),
+            VectorInput::Id(0.into()),
+        ],
+        using: None,
+    };
+
+    let scores = collection
+        .score_vectors(request, None, &ShardSelectorInternal::All)
+        .await
+        .unwrap();
+
+    // Dot product, in the order of targets
+    assert_eq!(scores, vec![4.0, 2.0, 3.0]);
+
+    let request = ScoreRequestInternal {
+        query: VectorInput::Id(0.into()),
+        targets: vec![VectorInput::Id(100.into())],
+        using: None,
+    };
+
+    let result = collection
+        .score_vectors(request, None, &ShardSelectorInternal::All)
+        .await;
+
+    assert!(result.is_err());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_ordered_read_api() {
     test_ordered_scroll_api_with_shards(1).await;
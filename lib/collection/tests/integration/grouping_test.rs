@@ -142,6 +142,7 @@ mod group_by {
                 negative: Vec::new(),
                 using: None,
                 lookup_from: None,
+                skip_missing: None,
             }),
             path("docId"),
             2,
@@ -239,3 +239,22 @@ fn set_linux_thread_priority(priority: u8) -> Result<(), ThreadPriorityError> {
     );
     set_current_thread_priority(new_priority).map_err(ThreadPriorityError::SetThreadPriority)
 }
+
+/// CPU time consumed by the current thread so far, if available on this platform
+pub fn thread_cpu_time() -> Option<Duration> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut time = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: `time` is a valid, writable timespec for the duration of the call, and
+        // `CLOCK_THREAD_CPUTIME_ID` is supported on Linux. The result is checked before use.
+        let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+        (result == 0).then(|| Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
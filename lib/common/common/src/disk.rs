@@ -15,3 +15,21 @@ pub fn dir_size(path: impl Into<PathBuf>) -> std::io::Result<u64> {
 
     dir_size(std::fs::read_dir(path.into())?)
 }
+
+/// Number of bytes the current thread caused to be read from storage so far, if available on
+/// this platform. Reads served from the page cache are not counted.
+///
+/// Reads `/proc/thread-self/io`, which is a few system calls.
+pub fn thread_io_read_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let io = std::fs::read_to_string("/proc/thread-self/io").ok()?;
+        io.lines()
+            .find_map(|line| line.strip_prefix("read_bytes")?.strip_prefix(':'))
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
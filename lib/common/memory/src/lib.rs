@@ -1,2 +1,3 @@
 pub mod madvise;
 pub mod mmap_ops;
+pub mod resident;
@@ -0,0 +1,173 @@
+//! Resident memory of memory-mapped files of this process, and eviction of their pages.
+//!
+//! Only supported on Linux, where mappings and their resident sizes are read from
+//! `/proc/self/smaps`. On other platforms listing mappings fails, and eviction is a no-op. Eviction
+//! is a no-op on kernels older than 5.4 too.
+
+use std::io;
+use std::path::PathBuf;
+
+/// Memory mapping of a file in the address space of this process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMapping {
+    /// Start address of the mapping
+    pub start: usize,
+    /// End address of the mapping, exclusive
+    pub end: usize,
+    /// Path of the mapped file
+    pub path: PathBuf,
+    /// Number of bytes of the mapping resident in memory
+    pub resident_bytes: u64,
+}
+
+/// List memory mappings of files of this process
+pub fn file_mappings() -> io::Result<Vec<FileMapping>> {
+    #[cfg(target_os = "linux")]
+    {
+        let smaps = std::fs::read_to_string("/proc/self/smaps")?;
+        Ok(parse_smaps(&smaps))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Listing memory mappings is not supported on this platform",
+        ))
+    }
+}
+
+/// Evict resident pages of the mapping from memory. They are read from the file again on the next
+/// access.
+///
+/// Uses `MADV_PAGEOUT` rather than `MADV_DONTNEED`: the mapping may be unmapped concurrently, and
+/// something else mapped at the same address, e.g. heap memory. `MADV_DONTNEED` would discard its
+/// contents, while `MADV_PAGEOUT` only reclaims pages, which is never destructive.
+pub fn evict(mapping: &FileMapping) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        let result = unsafe {
+            libc::madvise(
+                mapping.start as *mut libc::c_void,
+                mapping.end - mapping.start,
+                libc::MADV_PAGEOUT,
+            )
+        };
+        if result != 0 {
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                // Unmapped concurrently
+                Some(libc::ENOMEM) => {}
+                // `MADV_PAGEOUT` is not supported by kernels older than 5.4, same as eviction on
+                // other platforms
+                Some(libc::EINVAL) => log::debug!(
+                    "Ignore eviction of {}, not supported by the kernel",
+                    mapping.path.display()
+                ),
+                _ => return Err(err),
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    log::debug!(
+        "Ignore eviction of {} on this platform",
+        mapping.path.display()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_smaps(smaps: &str) -> Vec<FileMapping> {
+    let mut mappings = Vec::new();
+    // Mapping of the current header, and if it is a file mapping
+    let mut current: Option<FileMapping> = None;
+
+    for line in smaps.lines() {
+        if let Some(header) = parse_header(line) {
+            mappings.extend(current.take());
+            current = header;
+            continue;
+        }
+
+        let Some(mapping) = &mut current else {
+            continue;
+        };
+        if let Some(rss) = line.strip_prefix("Rss:") {
+            let kilobytes = rss.trim().trim_end_matches("kB").trim();
+            mapping.resident_bytes = kilobytes.parse::<u64>().unwrap_or(0) * 1024;
+        }
+    }
+    mappings.extend(current);
+
+    mappings
+}
+
+/// Parse a header line of a mapping, e.g.
+/// `7f2c3c000000-7f2c3c021000 r--s 00000000 08:01 1234    /path/to/file`
+///
+/// Returns `None` if the line is not a header, and `Some(None)` if the mapping is not of a file.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_header(line: &str) -> Option<Option<FileMapping>> {
+    let mut fields = line.splitn(6, char::is_whitespace);
+    let (start, end) = fields.next()?.split_once('-')?;
+    let start = usize::from_str_radix(start, 16).ok()?;
+    let end = usize::from_str_radix(end, 16).ok()?;
+
+    // Skip permissions, offset, device and inode
+    let path = fields.nth(4).unwrap_or_default().trim();
+    if !path.starts_with('/') {
+        // Anonymous memory, or a pseudo path like `[heap]`
+        return Some(None);
+    }
+
+    Some(Some(FileMapping {
+        start,
+        end,
+        path: PathBuf::from(path),
+        resident_bytes: 0,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_smaps() {
+        let smaps = "\
+7f2c3c000000-7f2c3c021000 r--s 00000000 08:01 1234                       /storage/collections/a/vectors
+Size:                132 kB
+KernelPageSize:        4 kB
+Rss:                  64 kB
+Pss:                  64 kB
+VmFlags: rd sh mr mw me ms sd
+7f2c3c021000-7f2c3c042000 rw-p 00000000 00:00 0
+Size:                132 kB
+Rss:                 132 kB
+VmFlags: rd wr mr mw me ac sd
+7f2c3c042000-7f2c3c043000 rw-s 00000000 08:01 5678                       /storage/collections/b/my file
+Size:                  4 kB
+Rss:                   0 kB
+VmFlags: rd wr sh mr mw me ms sd
+7ffd8c1e0000-7ffd8c201000 rw-p 00000000 00:00 0                          [stack]
+Rss:                  16 kB
+";
+
+        assert_eq!(
+            parse_smaps(smaps),
+            vec![
+                FileMapping {
+                    start: 0x7f2c3c000000,
+                    end: 0x7f2c3c021000,
+                    path: PathBuf::from("/storage/collections/a/vectors"),
+                    resident_bytes: 64 * 1024,
+                },
+                FileMapping {
+                    start: 0x7f2c3c042000,
+                    end: 0x7f2c3c043000,
+                    path: PathBuf::from("/storage/collections/b/my file"),
+                    resident_bytes: 0,
+                },
+            ],
+        );
+    }
+}
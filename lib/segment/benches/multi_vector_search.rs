@@ -52,6 +52,7 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(segment_dir.path(), &segment_config, true).unwrap();
@@ -86,6 +87,7 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
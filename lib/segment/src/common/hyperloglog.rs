@@ -0,0 +1,116 @@
+//! HyperLogLog sketch to estimate the number of distinct values.
+//!
+//! Each value is hashed, and the sketch keeps the longest run of leading zeros seen in the hashes
+//! of each of its registers. Sketches of different segments and shards are merged by taking the
+//! maximum of each register, so the estimate counts values present in several of them once.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Number of bits of the hash selecting the register, gives a standard error of about 0.8%
+const PRECISION: u32 = 14;
+
+const REGISTERS: usize = 1 << PRECISION;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        Self {
+            registers: vec![0; REGISTERS],
+        }
+    }
+
+    pub fn insert<T: Hash + ?Sized>(&mut self, value: &T) {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let register = (hash >> (u64::BITS - PRECISION)) as usize;
+        // Rank of the first set bit in the rest of the hash, a marker bit bounds it
+        let rest = (hash << PRECISION) | (1 << (PRECISION - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+
+        self.registers[register] = self.registers[register].max(rank);
+    }
+
+    /// Add all values of the other sketch to this one
+    pub fn merge(&mut self, other: &Self) {
+        for (register, other) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(*other);
+        }
+    }
+
+    /// Estimated number of distinct inserted values
+    pub fn estimate(&self) -> usize {
+        let registers = REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / registers);
+
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-i32::from(rank)))
+            .sum();
+        let estimate = alpha * registers * registers / sum;
+
+        // Linear counting is more precise for small cardinalities
+        let empty = self.registers.iter().filter(|&&rank| rank == 0).count();
+        if estimate <= 2.5 * registers && empty > 0 {
+            return (registers * (registers / empty as f64).ln()).round() as usize;
+        }
+
+        estimate.round() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(estimate: usize, expected: usize) {
+        let error = (estimate as f64 - expected as f64).abs() / expected as f64;
+        assert!(error < 0.03, "estimate {estimate} of {expected} values");
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(HyperLogLog::new().estimate(), 0);
+    }
+
+    #[test]
+    fn test_estimate() {
+        for count in [10, 1_000, 100_000] {
+            let mut sketch = HyperLogLog::new();
+            for value in 0..count {
+                sketch.insert(&format!("value_{value}"));
+                // Repeated values are counted once
+                sketch.insert(&format!("value_{value}"));
+            }
+            assert_close(sketch.estimate(), count);
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = HyperLogLog::new();
+        let mut b = HyperLogLog::new();
+        for value in 0..60_000 {
+            a.insert(&value);
+        }
+        for value in 40_000..100_000 {
+            b.insert(&value);
+        }
+
+        a.merge(&b);
+        assert_close(a.estimate(), 100_000);
+    }
+}
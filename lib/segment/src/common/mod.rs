@@ -1,5 +1,6 @@
 pub mod anonymize;
 pub mod error_logging;
+pub mod hyperloglog;
 pub mod macros;
 pub mod mmap_type;
 pub mod operation_error;
@@ -8,6 +9,8 @@ pub mod reciprocal_rank_fusion;
 pub mod rocksdb_buffered_delete_wrapper;
 pub mod rocksdb_buffered_update_wrapper;
 pub mod rocksdb_wrapper;
+pub mod sampling;
+pub mod score_fusion;
 pub mod utils;
 pub mod validate_snapshot_archive;
 pub mod vector_utils;
@@ -66,6 +66,9 @@ mod tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            prefetch_matches: None,
+            routing: None,
+            raw_score: None,
         }
     }
 
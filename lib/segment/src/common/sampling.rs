@@ -0,0 +1,27 @@
+use common::types::ScoreType;
+
+use crate::types::PointIdType;
+
+/// Random key of a point in a sample picked with the given seed.
+///
+/// Keys only depend on the point ID and the seed, so the points with the largest keys are the
+/// same random sample regardless of which segments or shards the points are stored in.
+pub fn sample_key(point_id: PointIdType, seed: u64) -> u64 {
+    let k1 = seed;
+    let k2 = seed.rotate_left(16);
+    let k3 = seed.rotate_left(32);
+    let k4 = seed.rotate_left(48);
+
+    match point_id {
+        PointIdType::NumId(id) => seahash::hash_seeded(&id.to_le_bytes(), k1, k2, k3, k4),
+        PointIdType::Uuid(uuid) => seahash::hash_seeded(uuid.as_bytes(), k1, k2, k3, k4),
+    }
+}
+
+/// Score of a point in a sample, in the range [0; 1)
+///
+/// Scores keep the order of the keys, with the precision of a float.
+pub fn sample_score(key: u64) -> ScoreType {
+    (key >> (u64::BITS - f32::MANTISSA_DIGITS)) as ScoreType
+        / (1u64 << f32::MANTISSA_DIGITS) as ScoreType
+}
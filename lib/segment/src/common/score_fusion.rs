@@ -0,0 +1,136 @@
+//! Distribution-Based Score Fusion (DBSF) combines results of multiple sources by their scores.
+//!
+//! Scores of each source are normalized by their distribution: the range of three standard
+//! deviations around the mean is mapped to `[0, 1]`, so that sources with different metrics can
+//! be compared. Normalized scores of a point are summed over the sources.
+
+use std::collections::hash_map::Entry;
+
+use ahash::{HashMap, HashMapExt};
+use ordered_float::OrderedFloat;
+
+use crate::types::{ExtendedPointId, ScoredPoint};
+
+/// Number of standard deviations around the mean mapped to the normalized range
+const DISTRIBUTION_WIDTH: f32 = 3.0;
+
+/// Normalize the scores of a single source, sorted from the best to the worst one.
+fn normalized_scores(points: &[ScoredPoint]) -> Vec<f32> {
+    let count = points.len() as f32;
+    let mean = points.iter().map(|point| point.score).sum::<f32>() / count;
+    let variance = points
+        .iter()
+        .map(|point| (point.score - mean).powi(2))
+        .sum::<f32>()
+        / count;
+    let deviation = variance.sqrt() * DISTRIBUTION_WIDTH;
+
+    // Results of metrics where smaller is better are sorted by ascending score
+    let ascending =
+        points.first().map(|point| point.score) < points.last().map(|point| point.score);
+
+    points
+        .iter()
+        .map(|point| {
+            if deviation <= 0.0 {
+                return 0.5;
+            }
+            let normalized = ((point.score - mean + deviation) / (2.0 * deviation)).clamp(0.0, 1.0);
+            if ascending {
+                1.0 - normalized
+            } else {
+                normalized
+            }
+        })
+        .collect()
+}
+
+/// Compute DBSF scores for multiple results from different sources.
+/// Each response can have a different length, and its scores may be of any metric.
+///
+/// The output is a single sorted list of ScoredPoint.
+/// Does not break ties.
+pub fn dbsf_scoring(responses: impl IntoIterator<Item = Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+    // track scored points by id
+    let mut points_by_id: HashMap<ExtendedPointId, ScoredPoint> = HashMap::new();
+
+    for response in responses {
+        let scores = normalized_scores(&response);
+        for (mut point, score) in response.into_iter().zip(scores) {
+            match points_by_id.entry(point.id) {
+                Entry::Occupied(mut entry) => {
+                    entry.get_mut().score += score;
+                }
+                Entry::Vacant(entry) => {
+                    point.score = score;
+                    entry.insert(point);
+                }
+            }
+        }
+    }
+
+    let mut scores: Vec<_> = points_by_id.into_values().collect();
+    scores.sort_unstable_by(|a, b| {
+        // sort by score descending
+        OrderedFloat(b.score).cmp(&OrderedFloat(a.score))
+    });
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_scored_point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            prefetch_matches: None,
+            routing: None,
+            raw_score: None,
+        }
+    }
+
+    #[test]
+    fn test_dbsf_scoring_empty() {
+        assert!(dbsf_scoring(vec![]).is_empty());
+        assert!(dbsf_scoring(vec![vec![]]).is_empty());
+    }
+
+    #[test]
+    fn test_dbsf_scoring_single_point() {
+        let scored_points = dbsf_scoring(vec![vec![make_scored_point(1, 0.8)]]);
+        assert_eq!(scored_points.len(), 1);
+        assert_eq!(scored_points[0].score, 0.5);
+    }
+
+    #[test]
+    fn test_dbsf_scoring() {
+        // Dot product scores, larger is better
+        let res1 = vec![
+            make_scored_point(1, 10.0),
+            make_scored_point(2, 5.0),
+            make_scored_point(3, 0.0),
+        ];
+        // Euclidean distances, smaller is better
+        let res2 = vec![
+            make_scored_point(2, 0.1),
+            make_scored_point(4, 0.2),
+            make_scored_point(1, 0.3),
+        ];
+
+        let scored_points = dbsf_scoring(vec![res1, res2]);
+        let ids: Vec<_> = scored_points.iter().map(|point| point.id).collect();
+
+        // Middle of the first source and best of the second one outranks the opposite
+        assert_eq!(ids, vec![2.into(), 1.into(), 4.into(), 3.into()]);
+        assert!(scored_points.iter().all(|point| point.score <= 2.0));
+        assert!((scored_points[2].score - 0.5).abs() < 1e-6);
+    }
+}
@@ -7,6 +7,9 @@ use bitvec::prelude::BitSlice;
 use sparse::common::types::{DimId, DimWeight};
 
 use crate::data_types::tiny_map;
+use crate::index::search_pools::{SearchPoolCounters, SearchPools};
+use crate::index::search_stats::SearchStats;
+use crate::types::SearchPoolsConfig;
 
 #[derive(Debug)]
 pub struct QueryContext {
@@ -26,6 +29,15 @@ pub struct QueryContext {
     /// Required for processing sparse vector search with `idf-dot` similarity.
     #[allow(dead_code)]
     idf: tiny_map::TinyMap<String, HashMap<DimId, usize>>,
+
+    /// Reuse of pooled memory by the search, configured for the collection.
+    search_pools: SearchPoolsConfig,
+
+    /// Counters of the reuse of pooled memory, if it is tracked.
+    search_pool_counters: Option<Arc<SearchPoolCounters>>,
+
+    /// Counters of the work done by the search, if requested.
+    search_stats: Option<Arc<SearchStats>>,
 }
 
 impl QueryContext {
@@ -35,6 +47,9 @@ impl QueryContext {
             search_optimized_threshold_kb,
             is_stopped: Arc::new(AtomicBool::new(false)),
             idf: tiny_map::TinyMap::new(),
+            search_pools: SearchPoolsConfig::default(),
+            search_pool_counters: None,
+            search_stats: None,
         }
     }
 
@@ -47,6 +62,25 @@ impl QueryContext {
         self
     }
 
+    pub fn with_search_pools(
+        mut self,
+        config: SearchPoolsConfig,
+        counters: Arc<SearchPoolCounters>,
+    ) -> Self {
+        self.search_pools = config;
+        self.search_pool_counters = Some(counters);
+        self
+    }
+
+    pub fn with_search_stats(mut self, search_stats: Option<Arc<SearchStats>>) -> Self {
+        self.search_stats = search_stats;
+        self
+    }
+
+    pub fn search_stats(&self) -> Option<&SearchStats> {
+        self.search_stats.as_deref()
+    }
+
     pub fn available_point_count(&self) -> usize {
         self.available_point_count
     }
@@ -83,6 +117,7 @@ impl QueryContext {
         SegmentQueryContext {
             query_context: Some(self),
             deleted_points: None,
+            search_stats: None,
         }
     }
 }
@@ -98,6 +133,8 @@ impl Default for QueryContext {
 pub struct SegmentQueryContext<'a> {
     query_context: Option<&'a QueryContext>,
     deleted_points: Option<&'a BitSlice>,
+    /// Counters of the work done to search this segment, instead of the ones of the query
+    search_stats: Option<&'a SearchStats>,
 }
 
 impl<'a> SegmentQueryContext<'a> {
@@ -109,10 +146,16 @@ impl<'a> SegmentQueryContext<'a> {
                 is_stopped: Some(&query_context.is_stopped),
                 idf: query_context.idf.get(vector_name),
                 deleted_points: self.deleted_points,
+                search_pools: SearchPools {
+                    config: query_context.search_pools,
+                    counters: query_context.search_pool_counters.as_deref(),
+                },
+                search_stats: self.search_stats.or(query_context.search_stats.as_deref()),
             }
         } else {
             VectorQueryContext {
                 deleted_points: self.deleted_points,
+                search_stats: self.search_stats,
                 ..Default::default()
             }
         }
@@ -122,6 +165,11 @@ impl<'a> SegmentQueryContext<'a> {
         self.deleted_points = Some(deleted_points);
         self
     }
+
+    pub fn with_search_stats(mut self, search_stats: Option<&'a SearchStats>) -> Self {
+        self.search_stats = search_stats;
+        self
+    }
 }
 
 /// Query context related to a specific vector
@@ -139,6 +187,10 @@ pub struct VectorQueryContext<'a> {
     idf: Option<&'a HashMap<DimId, usize>>,
 
     deleted_points: Option<&'a BitSlice>,
+
+    search_pools: SearchPools<'a>,
+
+    search_stats: Option<&'a SearchStats>,
 }
 
 pub enum SimpleCow<'a, T> {
@@ -170,6 +222,14 @@ impl VectorQueryContext<'_> {
         self.deleted_points
     }
 
+    pub fn search_pools(&self) -> SearchPools<'_> {
+        self.search_pools
+    }
+
+    pub fn search_stats(&self) -> Option<&SearchStats> {
+        self.search_stats
+    }
+
     pub fn is_stopped(&self) -> SimpleCow<'_, AtomicBool> {
         self.is_stopped
             .map(SimpleCow::Borrowed)
@@ -211,6 +271,8 @@ impl Default for VectorQueryContext<'_> {
             is_stopped: None,
             idf: None,
             deleted_points: None,
+            search_pools: SearchPools::default(),
+            search_stats: None,
         }
     }
 }
@@ -1,6 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::index::field_index::full_text_index::custom_tokenizers::TokenizerRegistry;
+
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum TextIndexType {
@@ -16,6 +18,8 @@ pub enum TokenizerType {
     #[default]
     Word,
     Multilingual,
+    /// Tokenizer built into the server, selected by `custom_tokenizer`
+    Custom,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
@@ -32,4 +36,34 @@ pub struct TextIndexParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     /// If true, lowercase all tokens. Default: true
     pub lowercase: Option<bool>,
+    /// Name of the tokenizer to use with the `custom` tokenizer type, such as `code_identifier`
+    /// or `cjk_bigram`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_tokenizer: Option<String>,
+}
+
+impl TextIndexParams {
+    /// Check that a custom tokenizer is named if, and only if, the tokenizer type is `custom`,
+    /// and that it is available
+    pub fn validate_tokenizer(&self) -> Result<(), String> {
+        match (self.tokenizer, &self.custom_tokenizer) {
+            (TokenizerType::Custom, Some(name)) => {
+                let registry = TokenizerRegistry::global();
+                if registry.get(name).is_none() {
+                    return Err(format!(
+                        "Unknown custom tokenizer `{name}`, available tokenizers: {}",
+                        registry.names().join(", "),
+                    ));
+                }
+                Ok(())
+            }
+            (TokenizerType::Custom, None) => {
+                Err("`custom_tokenizer` is required with the `custom` tokenizer type".to_string())
+            }
+            (_, Some(_)) => {
+                Err("`custom_tokenizer` requires the `custom` tokenizer type".to_string())
+            }
+            (_, None) => Ok(()),
+        }
+    }
 }
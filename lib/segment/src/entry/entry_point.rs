@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 
 use common::types::TelemetryDetail;
 
+use crate::common::hyperloglog::HyperLogLog;
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{OrderBy, OrderValue};
@@ -10,7 +11,7 @@ use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::index::field_index::CardinalityEstimation;
 use crate::json_path::JsonPath;
-use crate::telemetry::SegmentTelemetry;
+use crate::telemetry::{HnswGraphTelemetry, SegmentTelemetry};
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
     ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
@@ -126,12 +127,29 @@ pub trait SegmentEntry {
         order_by: &'a OrderBy,
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>>;
 
+    /// Return a random sample of points which satisfies filtering condition, along with their
+    /// sample key. Points are ordered by descending key, see [`sample_key`].
+    ///
+    /// [`sample_key`]: crate::common::sampling::sample_key
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
+    ) -> Vec<(u64, PointIdType)>;
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType>;
 
     /// Check if there is point with `point_id` in this segment.
     fn has_point(&self, point_id: PointIdType) -> bool;
 
+    /// Select the points, which are present in this segment, keeping their order.
+    ///
+    /// Prefer this over calling `has_point` for each point: the ID tracker, and segments wrapped by
+    /// a proxy, are only accessed once for all points, which avoids contention under many reads.
+    fn existing_points(&self, point_ids: &[PointIdType]) -> Vec<PointIdType>;
+
     /// Estimate available point count in this segment for given filter.
     fn estimate_point_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation;
 
@@ -199,6 +217,22 @@ pub trait SegmentEntry {
     /// Get indexed fields
     fn get_indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema>;
 
+    /// Distinct values of the keyword index of the field, if there is one with at most `limit`
+    /// values. Values of deleted points may be included.
+    fn keyword_values(&self, key: PayloadKeyTypeRef, limit: usize) -> Option<HashSet<String>>;
+
+    /// Sketch of the distinct values of the keyword index of the field, if there is one.
+    /// Values of deleted points may be included.
+    fn keyword_values_sketch(&self, key: PayloadKeyTypeRef) -> Option<HyperLogLog>;
+
+    /// Neighbors of the point on each level of the HNSW graph of the vector, starting from the
+    /// bottom one. None, if the vector is not indexed with HNSW, or the point is not in the graph.
+    fn hnsw_neighbors(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> OperationResult<Option<Vec<Vec<PointIdType>>>>;
+
     /// Checks if segment errored during last operations
     fn check_error(&self) -> Option<SegmentFailedState>;
 
@@ -219,5 +253,13 @@ pub trait SegmentEntry {
     // Get collected telemetry data of segment
     fn get_telemetry_data(&self, detail: TelemetryDetail) -> SegmentTelemetry;
 
+    /// Compute the structural statistics of the HNSW graphs, which are not computed yet.
+    /// Walks the whole graphs, so it is expensive the first time.
+    fn compute_hnsw_graph_stats(&self);
+
+    /// Structure of the HNSW graphs of the vectors, which are indexed with HNSW and have their
+    /// statistics computed already, see [`SegmentEntry::compute_hnsw_graph_stats`]
+    fn hnsw_graphs_telemetry(&self) -> Vec<HnswGraphTelemetry>;
+
     fn fill_query_context(&self, query_context: &mut QueryContext);
 }
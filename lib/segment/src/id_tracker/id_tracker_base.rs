@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
 use rand::rngs::StdRng;
@@ -5,6 +7,7 @@ use rand::{Rng, SeedableRng};
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::id_tracker::point_mappings::{PointMappingsHandle, PointMappingsSnapshot};
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::types::{PointIdType, SeqNumberType};
 
@@ -138,6 +141,16 @@ pub trait IdTracker {
         )
     }
 
+    /// Handle of the mapping snapshots this tracker publishes for lock-free lookups, if it does
+    fn point_mappings(&self) -> Option<PointMappingsHandle> {
+        None
+    }
+
+    /// Publish a snapshot of the current mapping, if this tracker publishes snapshots
+    fn publish_point_mappings(&self) -> Option<Arc<PointMappingsSnapshot>> {
+        None
+    }
+
     /// Finds inconsistencies between id mapping and versions storage.
     /// It might happen that point doesn't have version due to un-flushed WAL.
     /// This method makes those points usable again.
@@ -287,6 +300,20 @@ impl IdTracker for IdTrackerEnum {
         }
     }
 
+    fn point_mappings(&self) -> Option<PointMappingsHandle> {
+        match self {
+            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.point_mappings(),
+            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.point_mappings(),
+        }
+    }
+
+    fn publish_point_mappings(&self) -> Option<Arc<PointMappingsSnapshot>> {
+        match self {
+            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.publish_point_mappings(),
+            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.publish_point_mappings(),
+        }
+    }
+
     fn cleanup_versions(&mut self) -> OperationResult<()> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.cleanup_versions(),
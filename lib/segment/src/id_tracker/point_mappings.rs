@@ -0,0 +1,172 @@
+//! Lock-free lookups of internal IDs, RCU style.
+//!
+//! An ID tracker may publish a snapshot of its external to internal ID mapping. Readers load the
+//! published snapshot without borrowing the ID tracker, so concurrent lookups don't contend on it.
+//!
+//! The snapshot shares the mapping with the ID tracker instead of copying it, only deletion flags
+//! are copied, one bit per point. Deletions are marked in the published snapshot atomically, and
+//! the ID tracker keeps the deleted entries in the shared mapping until it is the only owner
+//! again. Any other change of the mapping unpublishes the snapshot, and a new one is published by
+//! the next reader.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use arc_swap::{ArcSwapOption, Guard};
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use uuid::Uuid;
+
+use crate::types::PointIdType;
+
+/// External to internal ID mapping of an ID tracker
+#[derive(Debug, Clone, Default)]
+pub struct PointMappings {
+    pub(crate) external_to_internal_num: BTreeMap<u64, PointOffsetType>,
+    pub(crate) external_to_internal_uuid: BTreeMap<Uuid, PointOffsetType>,
+}
+
+impl PointMappings {
+    /// Internal ID of the point, including points deleted while the mapping was shared
+    pub fn get(&self, external_id: PointIdType) -> Option<PointOffsetType> {
+        match external_id {
+            PointIdType::NumId(idx) => self.external_to_internal_num.get(&idx).copied(),
+            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.get(&uuid).copied(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.external_to_internal_num.len() + self.external_to_internal_uuid.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Shared external to internal ID mapping, with atomic deletion flags
+#[derive(Debug)]
+pub struct PointMappingsSnapshot {
+    mappings: Arc<PointMappings>,
+    deleted: Box<[AtomicU64]>,
+}
+
+impl PointMappingsSnapshot {
+    /// Points marked in `deleted` are deleted, the mapping may still contain them.
+    /// Internal IDs of the mapping must be below the length of `deleted`.
+    pub fn new(mappings: Arc<PointMappings>, deleted: &BitSlice) -> Self {
+        let mut flags: Box<[AtomicU64]> = (0..deleted.len().div_ceil(u64::BITS as usize))
+            .map(|_| AtomicU64::new(0))
+            .collect();
+        for internal_id in deleted.iter_ones() {
+            let (word, bit) = Self::position(internal_id as PointOffsetType);
+            *flags[word].get_mut() |= bit;
+        }
+        Self {
+            mappings,
+            deleted: flags,
+        }
+    }
+
+    /// Returns internal ID of the point, unless it was deleted
+    pub fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
+        let internal_id = self.mappings.get(external_id)?;
+        (!self.is_deleted(internal_id)).then_some(internal_id)
+    }
+
+    fn mark_deleted(&self, internal_id: PointOffsetType) {
+        let (word, bit) = Self::position(internal_id);
+        if let Some(word) = self.deleted.get(word) {
+            word.fetch_or(bit, Ordering::Release);
+        }
+    }
+
+    fn is_deleted(&self, internal_id: PointOffsetType) -> bool {
+        let (word, bit) = Self::position(internal_id);
+        self.deleted
+            .get(word)
+            .map_or(true, |word| word.load(Ordering::Acquire) & bit != 0)
+    }
+
+    fn position(internal_id: PointOffsetType) -> (usize, u64) {
+        let internal_id = internal_id as usize;
+        (
+            internal_id / u64::BITS as usize,
+            1 << (internal_id % u64::BITS as usize),
+        )
+    }
+}
+
+/// Published mapping snapshot of an ID tracker, shared with its readers
+#[derive(Debug, Clone, Default)]
+pub struct PointMappingsHandle(Arc<ArcSwapOption<PointMappingsSnapshot>>);
+
+impl PointMappingsHandle {
+    /// Currently published snapshot, if any.
+    ///
+    /// Loading doesn't touch the reference count of the snapshot, so it scales with the number
+    /// of readers. Don't keep the guard for long, it delays releasing unpublished snapshots.
+    pub fn load(&self) -> Guard<Option<Arc<PointMappingsSnapshot>>> {
+        self.0.load()
+    }
+
+    pub fn publish(&self, snapshot: PointMappingsSnapshot) -> Arc<PointMappingsSnapshot> {
+        let snapshot = Arc::new(snapshot);
+        self.0.store(Some(snapshot.clone()));
+        snapshot
+    }
+
+    pub fn unpublish(&self) {
+        self.0.store(None);
+    }
+
+    pub fn mark_deleted(&self, internal_id: PointOffsetType) {
+        if let Some(snapshot) = self.0.load().as_ref() {
+            snapshot.mark_deleted(internal_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_mappings_snapshot() {
+        let uuid = Uuid::from_u128(42);
+        let handle = PointMappingsHandle::default();
+        assert!(handle.load().is_none());
+
+        // Marking deletions without a published snapshot is a no-op
+        handle.mark_deleted(0);
+
+        let mappings = PointMappings {
+            external_to_internal_num: [(1, 2), (5, 0), (70, 65), (80, 66)].into(),
+            external_to_internal_uuid: [(uuid, 1)].into(),
+        };
+        // Internal ID 66 was deleted before publishing
+        let mut deleted = bitvec::bitvec![0; 67];
+        deleted.set(66, true);
+        let snapshot = handle.publish(PointMappingsSnapshot::new(Arc::new(mappings), &deleted));
+        assert_eq!(snapshot.internal_id(1.into()), Some(2));
+        assert_eq!(snapshot.internal_id(70.into()), Some(65));
+        assert_eq!(snapshot.internal_id(PointIdType::Uuid(uuid)), Some(1));
+        assert_eq!(snapshot.internal_id(2.into()), None);
+        assert_eq!(snapshot.internal_id(80.into()), None);
+        assert_eq!(snapshot.internal_id(PointIdType::Uuid(Uuid::nil())), None);
+
+        handle.mark_deleted(65);
+        handle.mark_deleted(1);
+        let loaded = handle.load();
+        let loaded = loaded.as_ref().unwrap();
+        assert_eq!(loaded.internal_id(70.into()), None);
+        assert_eq!(loaded.internal_id(PointIdType::Uuid(uuid)), None);
+        assert_eq!(loaded.internal_id(5.into()), Some(0));
+
+        // Readers keep using the snapshot they loaded
+        handle.unpublish();
+        assert!(handle.load().is_none());
+        assert_eq!(snapshot.internal_id(1.into()), Some(2));
+    }
+}
@@ -14,6 +14,9 @@ use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDelet
 use crate::common::rocksdb_buffered_update_wrapper::DatabaseColumnScheduledUpdateWrapper;
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_MAPPING_CF, DB_VERSIONS_CF};
 use crate::common::Flusher;
+use crate::id_tracker::point_mappings::{
+    PointMappings, PointMappingsHandle, PointMappingsSnapshot,
+};
 use crate::id_tracker::IdTracker;
 use crate::types::{ExtendedPointId, PointIdType, SeqNumberType};
 
@@ -61,10 +64,14 @@ pub struct SimpleIdTracker {
     deleted: BitVec,
     internal_to_external: Vec<PointIdType>,
     internal_to_version: Vec<SeqNumberType>,
-    external_to_internal_num: BTreeMap<u64, PointOffsetType>,
-    external_to_internal_uuid: BTreeMap<Uuid, PointOffsetType>,
+    /// Shared with published snapshots, see [`PointMappingsSnapshot`]
+    mappings: Arc<PointMappings>,
+    /// Number of deleted points, which are still in `mappings`, because it was shared when they
+    /// were deleted
+    stale_mappings: usize,
     mapping_db_wrapper: DatabaseColumnScheduledDeleteWrapper,
     versions_db_wrapper: DatabaseColumnScheduledUpdateWrapper,
+    point_mappings: PointMappingsHandle,
 }
 
 impl SimpleIdTracker {
@@ -160,10 +167,14 @@ impl SimpleIdTracker {
             deleted,
             internal_to_external,
             internal_to_version,
-            external_to_internal_num,
-            external_to_internal_uuid,
+            mappings: Arc::new(PointMappings {
+                external_to_internal_num,
+                external_to_internal_uuid,
+            }),
+            stale_mappings: 0,
             mapping_db_wrapper,
             versions_db_wrapper,
+            point_mappings: PointMappingsHandle::default(),
         })
     }
 
@@ -190,6 +201,28 @@ impl SimpleIdTracker {
             bincode::serialize(&internal_id).unwrap(),
         )
     }
+
+    /// Exclusive access to the mapping, copying it only if a snapshot is still in use.
+    /// Removes entries of deleted points, which were kept while the mapping was shared.
+    fn mappings_mut(&mut self) -> &mut PointMappings {
+        let mappings = Arc::make_mut(&mut self.mappings);
+        if self.stale_mappings > 0 {
+            let deleted = &self.deleted;
+            let is_live = |internal_id: &PointOffsetType| {
+                deleted
+                    .get(*internal_id as usize)
+                    .is_some_and(|is_deleted| !*is_deleted)
+            };
+            mappings
+                .external_to_internal_num
+                .retain(|_, internal_id| is_live(internal_id));
+            mappings
+                .external_to_internal_uuid
+                .retain(|_, internal_id| is_live(internal_id));
+            self.stale_mappings = 0;
+        }
+        mappings
+    }
 }
 
 impl IdTracker for SimpleIdTracker {
@@ -216,10 +249,8 @@ impl IdTracker for SimpleIdTracker {
     }
 
     fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
-        match external_id {
-            PointIdType::NumId(idx) => self.external_to_internal_num.get(&idx).copied(),
-            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.get(&uuid).copied(),
-        }
+        let internal_id = self.mappings.get(external_id)?;
+        (!self.is_deleted_point(internal_id)).then_some(internal_id)
     }
 
     fn external_id(&self, internal_id: PointOffsetType) -> Option<PointIdType> {
@@ -236,12 +267,15 @@ impl IdTracker for SimpleIdTracker {
         external_id: PointIdType,
         internal_id: PointOffsetType,
     ) -> OperationResult<()> {
+        self.point_mappings.unpublish();
+
+        let mappings = self.mappings_mut();
         match external_id {
             PointIdType::NumId(idx) => {
-                self.external_to_internal_num.insert(idx, internal_id);
+                mappings.external_to_internal_num.insert(idx, internal_id);
             }
             PointIdType::Uuid(uuid) => {
-                self.external_to_internal_uuid.insert(uuid, internal_id);
+                mappings.external_to_internal_uuid.insert(uuid, internal_id);
             }
         }
 
@@ -261,31 +295,28 @@ impl IdTracker for SimpleIdTracker {
     }
 
     fn drop(&mut self, external_id: PointIdType) -> OperationResult<()> {
-        let internal_id = match &external_id {
-            PointIdType::NumId(idx) => self.external_to_internal_num.remove(idx),
-            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.remove(uuid),
-        };
-        if let Some(internal_id) = internal_id {
+        if let Some(internal_id) = self.internal_id(external_id) {
+            self.point_mappings.mark_deleted(internal_id);
             self.deleted.set(internal_id as usize, true);
             self.internal_to_external[internal_id as usize] = PointIdType::NumId(u64::MAX);
+
+            // Don't copy the mapping shared with a snapshot, the deletion flag hides the point
+            match Arc::get_mut(&mut self.mappings) {
+                Some(mappings) => {
+                    match external_id {
+                        PointIdType::NumId(idx) => mappings.external_to_internal_num.remove(&idx),
+                        PointIdType::Uuid(uuid) => mappings.external_to_internal_uuid.remove(&uuid),
+                    };
+                }
+                None => self.stale_mappings += 1,
+            }
         }
         self.delete_key(&external_id)?;
         Ok(())
     }
 
     fn iter_external(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
-        let iter_num = self
-            .external_to_internal_num
-            .keys()
-            .copied()
-            .map(PointIdType::NumId);
-        let iter_uuid = self
-            .external_to_internal_uuid
-            .keys()
-            .copied()
-            .map(PointIdType::Uuid);
-        // order is important here, we want to iterate over the u64 ids first
-        Box::new(iter_num.chain(iter_uuid))
+        Box::new(self.iter_from(None).map(|(external_id, _)| external_id))
     }
 
     fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
@@ -300,27 +331,32 @@ impl IdTracker for SimpleIdTracker {
         external_id: Option<PointIdType>,
     ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
         let full_num_iter = || {
-            self.external_to_internal_num
+            self.mappings
+                .external_to_internal_num
                 .iter()
                 .map(|(k, v)| (PointIdType::NumId(*k), *v))
         };
         let offset_num_iter = |offset: u64| {
-            self.external_to_internal_num
+            self.mappings
+                .external_to_internal_num
                 .range(offset..)
                 .map(|(k, v)| (PointIdType::NumId(*k), *v))
         };
         let full_uuid_iter = || {
-            self.external_to_internal_uuid
+            self.mappings
+                .external_to_internal_uuid
                 .iter()
                 .map(|(k, v)| (PointIdType::Uuid(*k), *v))
         };
         let offset_uuid_iter = |offset: Uuid| {
-            self.external_to_internal_uuid
+            self.mappings
+                .external_to_internal_uuid
                 .range(offset..)
                 .map(|(k, v)| (PointIdType::Uuid(*k), *v))
         };
 
-        match external_id {
+        let iter: Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> = match external_id
+        {
             None => {
                 let iter_num = full_num_iter();
                 let iter_uuid = full_uuid_iter();
@@ -340,7 +376,12 @@ impl IdTracker for SimpleIdTracker {
                     Box::new(offset_uuid_iter(uuid))
                 }
             },
+        };
+
+        if self.stale_mappings == 0 {
+            return iter;
         }
+        Box::new(iter.filter(|(_, internal_id)| !self.is_deleted_point(*internal_id)))
     }
 
     fn total_point_count(&self) -> usize {
@@ -348,7 +389,7 @@ impl IdTracker for SimpleIdTracker {
     }
 
     fn available_point_count(&self) -> usize {
-        self.external_to_internal_num.len() + self.external_to_internal_uuid.len()
+        self.mappings.len() - self.stale_mappings
     }
 
     fn deleted_point_count(&self) -> usize {
@@ -385,6 +426,15 @@ impl IdTracker for SimpleIdTracker {
         &self.deleted
     }
 
+    fn point_mappings(&self) -> Option<PointMappingsHandle> {
+        Some(self.point_mappings.clone())
+    }
+
+    fn publish_point_mappings(&self) -> Option<Arc<PointMappingsSnapshot>> {
+        let snapshot = PointMappingsSnapshot::new(self.mappings.clone(), &self.deleted);
+        Some(self.point_mappings.publish(snapshot))
+    }
+
     fn cleanup_versions(&mut self) -> OperationResult<()> {
         let mut to_remove = Vec::new();
         for internal_id in self.iter_internal() {
@@ -493,4 +543,49 @@ mod tests {
 
         assert_eq!(sorted_from_tracker, values);
     }
+
+    #[test]
+    fn test_published_point_mappings() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let mut id_tracker = SimpleIdTracker::open(db).unwrap();
+        let uuid = PointIdType::Uuid(Uuid::from_u128(123_u128));
+        id_tracker.set_link(100.into(), 0).unwrap();
+        id_tracker.set_link(uuid, 1).unwrap();
+        id_tracker.set_link(150.into(), 2).unwrap();
+
+        let handle = id_tracker.point_mappings().unwrap();
+        assert!(handle.load().is_none());
+
+        let snapshot = id_tracker.publish_point_mappings().unwrap();
+        for point_id in [100.into(), uuid, 150.into(), 42.into()] {
+            assert_eq!(
+                snapshot.internal_id(point_id),
+                id_tracker.internal_id(point_id)
+            );
+        }
+
+        // Deletions are visible in the published snapshot
+        id_tracker.drop(150.into()).unwrap();
+        assert_eq!(snapshot.internal_id(150.into()), None);
+        assert!(handle.load().is_some());
+
+        // The tracker doesn't copy the shared mapping, but hides the deleted point as well
+        assert_eq!(id_tracker.stale_mappings, 1);
+        assert_eq!(id_tracker.internal_id(150.into()), None);
+        assert_eq!(id_tracker.available_point_count(), 2);
+        assert_eq!(
+            id_tracker.iter_external().collect_vec(),
+            vec![100.into(), uuid],
+        );
+
+        // New links unpublish it
+        id_tracker.set_link(150.into(), 3).unwrap();
+        assert!(handle.load().is_none());
+        assert_eq!(id_tracker.stale_mappings, 0);
+        let snapshot = id_tracker.publish_point_mappings().unwrap();
+        assert_eq!(snapshot.internal_id(150.into()), Some(3));
+        assert_eq!(id_tracker.available_point_count(), 3);
+    }
 }
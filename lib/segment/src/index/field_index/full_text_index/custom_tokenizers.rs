@@ -0,0 +1,186 @@
+//! ## Custom tokenizers
+//!
+//! Tokenizers for texts which the built-in tokenizers split poorly, such as CJK texts without
+//! dictionaries or identifiers in source code. A field uses one with the `custom` tokenizer type
+//! and the name of the tokenizer in the parameters of its text index.
+//!
+//! To add a tokenizer, implement [`CustomTokenizer`] and register it in
+//! [`TokenizerRegistry::with_default_tokenizers`]. Tokenizers with heavy dependencies should be
+//! registered behind a cargo feature, like the dictionaries of the multilingual tokenizer. Every
+//! peer of a cluster must be built with the tokenizers its collections use.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::tokenizers::WordTokenizer;
+
+static TOKENIZER_REGISTRY: OnceLock<TokenizerRegistry> = OnceLock::new();
+
+/// Splits a text into tokens, which are then filtered by length and lowercased as configured
+pub trait CustomTokenizer: Send + Sync {
+    fn tokenize(&self, text: &str, callback: &mut dyn FnMut(&str));
+}
+
+/// Splits identifiers of source code into their words, e.g. `parseHTTPRequest` and
+/// `parse_http_request` both into `parse`, `http` and `request`
+struct CodeIdentifierTokenizer;
+
+impl CustomTokenizer for CodeIdentifierTokenizer {
+    fn tokenize(&self, text: &str, callback: &mut dyn FnMut(&str)) {
+        WordTokenizer::tokenize(text, |word| {
+            let chars: Vec<_> = word.char_indices().collect();
+            let mut start = 0;
+            for (i, window) in chars.windows(2).enumerate() {
+                let [(_, current), (end, next)] = *window else {
+                    continue;
+                };
+                // `aB` starts a word, so does `Bc` in `ABc` ending an acronym
+                let ends_acronym = current.is_uppercase()
+                    && next.is_uppercase()
+                    && chars.get(i + 2).is_some_and(|(_, c)| c.is_lowercase());
+                let is_boundary = (current.is_lowercase() && next.is_uppercase())
+                    || (current.is_alphabetic() != next.is_alphabetic())
+                    || ends_acronym;
+                if is_boundary {
+                    callback(&word[start..end]);
+                    start = end;
+                }
+            }
+            callback(&word[start..]);
+        });
+    }
+}
+
+/// Splits runs of CJK characters into overlapping bigrams, and other words like the word
+/// tokenizer. Finds words in CJK texts without a dictionary.
+struct CjkBigramTokenizer;
+
+impl CjkBigramTokenizer {
+    fn is_cjk(c: char) -> bool {
+        matches!(
+            c,
+            '\u{3040}'..='\u{30FF}' // Hiragana and Katakana
+            | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+            | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+            | '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+            | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+            | '\u{20000}'..='\u{2A6DF}' // CJK Unified Ideographs Extension B
+        )
+    }
+
+    fn tokenize_cjk_run(run: &str, callback: &mut dyn FnMut(&str)) {
+        let offsets: Vec<_> = run
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([run.len()])
+            .collect();
+        if offsets.len() <= 3 {
+            callback(run);
+            return;
+        }
+        for window in offsets.windows(3) {
+            callback(&run[window[0]..window[2]]);
+        }
+    }
+}
+
+impl CustomTokenizer for CjkBigramTokenizer {
+    fn tokenize(&self, text: &str, callback: &mut dyn FnMut(&str)) {
+        WordTokenizer::tokenize(text, |word| {
+            let mut start = 0;
+            let mut in_cjk = None;
+            for (offset, c) in word.char_indices() {
+                let is_cjk = Self::is_cjk(c);
+                if in_cjk.is_some_and(|in_cjk| in_cjk != is_cjk) {
+                    let run = &word[start..offset];
+                    if is_cjk {
+                        callback(run);
+                    } else {
+                        Self::tokenize_cjk_run(run, callback);
+                    }
+                    start = offset;
+                }
+                in_cjk = Some(is_cjk);
+            }
+            match in_cjk {
+                Some(true) => Self::tokenize_cjk_run(&word[start..], callback),
+                Some(false) => callback(&word[start..]),
+                None => {}
+            }
+        });
+    }
+}
+
+/// Custom tokenizers available for text indexes, by name
+pub struct TokenizerRegistry {
+    tokenizers: HashMap<&'static str, Box<dyn CustomTokenizer>>,
+}
+
+impl TokenizerRegistry {
+    fn with_default_tokenizers() -> Self {
+        let mut registry = Self {
+            tokenizers: HashMap::new(),
+        };
+
+        registry.register("code_identifier", CodeIdentifierTokenizer);
+        registry.register("cjk_bigram", CjkBigramTokenizer);
+
+        registry
+    }
+
+    fn register(&mut self, name: &'static str, tokenizer: impl CustomTokenizer + 'static) {
+        let previous = self.tokenizers.insert(name, Box::new(tokenizer));
+        debug_assert!(previous.is_none(), "custom tokenizer registered twice");
+    }
+
+    pub fn global() -> &'static Self {
+        TOKENIZER_REGISTRY.get_or_init(Self::with_default_tokenizers)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn CustomTokenizer> {
+        self.tokenizers
+            .get(name)
+            .map(|tokenizer| tokenizer.as_ref())
+    }
+
+    /// Names of the registered tokenizers, in order
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.tokenizers.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenize(name: &str, text: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        TokenizerRegistry::global()
+            .get(name)
+            .unwrap()
+            .tokenize(text, &mut |token| tokens.push(token.to_owned()));
+        tokens
+    }
+
+    #[test]
+    fn test_code_identifier_tokenizer() {
+        assert_eq!(
+            tokenize(
+                "code_identifier",
+                "parseHTTPRequest(parse_http_request, v2)"
+            ),
+            vec!["parse", "HTTP", "Request", "parse", "http", "request", "v", "2"],
+        );
+    }
+
+    #[test]
+    fn test_cjk_bigram_tokenizer() {
+        assert_eq!(
+            tokenize("cjk_bigram", "東京都に住む, Tokyo東京"),
+            vec!["東京", "京都", "都に", "に住", "住む", "Tokyo", "東京"],
+        );
+        assert_eq!(tokenize("cjk_bigram", "本 hello"), vec!["本", "hello"]);
+    }
+}
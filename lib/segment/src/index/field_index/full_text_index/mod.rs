@@ -1,3 +1,4 @@
+pub mod custom_tokenizers;
 mod inverted_index;
 mod posting_list;
 mod postings_iterator;
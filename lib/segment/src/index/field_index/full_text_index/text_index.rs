@@ -271,6 +271,7 @@ mod tests {
             min_token_len: None,
             max_token_len: None,
             lowercase: None,
+            custom_tokenizer: None,
         };
 
         {
@@ -1,5 +1,6 @@
 use charabia::Tokenize;
 
+use super::custom_tokenizers::TokenizerRegistry;
 use crate::data_types::text_index::{TextIndexParams, TokenizerType};
 
 struct WhiteSpaceTokenizer;
@@ -10,10 +11,10 @@ impl WhiteSpaceTokenizer {
     }
 }
 
-struct WordTokenizer;
+pub(super) struct WordTokenizer;
 
 impl WordTokenizer {
-    fn tokenize<C: FnMut(&str)>(text: &str, callback: C) {
+    pub(super) fn tokenize<C: FnMut(&str)>(text: &str, callback: C) {
         text.split(|c| !char::is_alphanumeric(c))
             .filter(|x| !x.is_empty())
             .for_each(callback);
@@ -74,6 +75,18 @@ impl MultilingualTokenizer {
     }
 }
 
+struct CustomTokenizers;
+
+impl CustomTokenizers {
+    /// Unknown tokenizers fall back to the word tokenizer, they are rejected on index creation
+    fn tokenize<C: FnMut(&str)>(name: Option<&str>, text: &str, mut callback: C) {
+        match name.and_then(|name| TokenizerRegistry::global().get(name)) {
+            Some(tokenizer) => tokenizer.tokenize(text, &mut callback),
+            None => WordTokenizer::tokenize(text, callback),
+        }
+    }
+}
+
 pub struct Tokenizer;
 
 impl Tokenizer {
@@ -116,6 +129,9 @@ impl Tokenizer {
                 config.max_token_len.unwrap_or(usize::MAX),
                 token_filter,
             ),
+            TokenizerType::Custom => {
+                CustomTokenizers::tokenize(config.custom_tokenizer.as_deref(), text, token_filter)
+            }
         }
     }
 
@@ -130,6 +146,9 @@ impl Tokenizer {
                 config.max_token_len.unwrap_or(usize::MAX),
                 token_filter,
             ),
+            TokenizerType::Custom => {
+                CustomTokenizers::tokenize(config.custom_tokenizer.as_deref(), text, token_filter)
+            }
         }
     }
 }
@@ -254,6 +273,7 @@ mod tests {
                 min_token_len: Some(1),
                 max_token_len: Some(4),
                 lowercase: Some(true),
+                custom_tokenizer: None,
             },
             |token| tokens.push(token.to_owned()),
         );
@@ -267,4 +287,23 @@ mod tests {
         assert_eq!(tokens.get(5), Some(&"ми".to_owned()));
         assert_eq!(tokens.get(6), Some(&"мир".to_owned()));
     }
+
+    #[test]
+    fn test_custom_tokenizer() {
+        let text = "getHttpResponse";
+        let mut tokens = Vec::new();
+        Tokenizer::tokenize_doc(
+            text,
+            &TextIndexParams {
+                r#type: TextIndexType::Text,
+                tokenizer: TokenizerType::Custom,
+                min_token_len: None,
+                max_token_len: None,
+                lowercase: Some(true),
+                custom_tokenizer: Some("code_identifier".to_string()),
+            },
+            |token| tokens.push(token.to_owned()),
+        );
+        assert_eq!(tokens, vec!["get", "http", "response"]);
+    }
 }
@@ -16,6 +16,7 @@ use rocksdb::DB;
 use serde_json::Value;
 use smol_str::SmolStr;
 
+use crate::common::hyperloglog::HyperLogLog;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
@@ -117,6 +118,22 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    /// All distinct values of the index, if there are at most `limit` of them.
+    /// Values of removed points may be included.
+    pub fn values_up_to(&self, limit: usize) -> Option<impl Iterator<Item = &N> + '_> {
+        (self.get_unique_values_count() <= limit).then(|| self.get_values_iterator())
+    }
+
+    /// Sketch of the distinct values of the index, to estimate their number across segments.
+    /// Values of removed points may be included.
+    pub fn values_sketch(&self) -> HyperLogLog {
+        let mut sketch = HyperLogLog::new();
+        for value in self.get_values_iterator() {
+            sketch.insert(value);
+        }
+        sketch
+    }
+
     pub fn storage_cf_name(field: &str) -> String {
         format!("{field}_map")
     }
@@ -0,0 +1,127 @@
+//! Checkpoints of HNSW index builds.
+//!
+//! Building the main graph of a large segment can take hours. While building, the graph is saved
+//! periodically and when the build is interrupted, so a build of the same vectors can resume from
+//! it after a restart instead of starting over.
+//!
+//! A checkpoint is identified by a fingerprint of the vectors to index: the ids and versions of
+//! the points at each offset, and the parameters of the graph. Only an identical build resumes
+//! from it, e.g. the same optimization of the same segments.
+
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bitvec::slice::BitSlice;
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_bin, read_bin};
+use parking_lot::Mutex;
+use seahash::SeaHasher;
+use serde::{Deserialize, Serialize};
+
+use super::entry_points::EntryPoints;
+use super::graph_layers_builder::LockedLayersContainer;
+use crate::common::operation_error::OperationResult;
+use crate::id_tracker::IdTrackerSS;
+
+/// How often to save the progress of a build
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Number of points to link in parallel between checks whether to save a checkpoint
+pub const CHECKPOINT_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Fingerprint of the build of the graph of the given vectors
+pub fn fingerprint(
+    index_path: &Path,
+    id_tracker: &IdTrackerSS,
+    deleted_bitslice: &BitSlice,
+    graph_params: &[usize],
+    quantized: bool,
+) -> u64 {
+    let mut hasher = SeaHasher::new();
+    // The directory of the index is named after the vector
+    index_path.file_name().hash(&mut hasher);
+    graph_params.hash(&mut hasher);
+    quantized.hash(&mut hasher);
+    for internal_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
+        internal_id.hash(&mut hasher);
+        id_tracker.external_id(internal_id).hash(&mut hasher);
+        id_tracker.internal_version(internal_id).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+pub fn checkpoint_path(checkpoints_path: &Path, fingerprint: u64) -> PathBuf {
+    checkpoints_path.join(format!("hnsw-{fingerprint:016x}.bin"))
+}
+
+/// Graph being built, borrowed from the builder to save it without copying
+#[derive(Serialize)]
+pub(super) struct BuildCheckpointRef<'a> {
+    pub fingerprint: u64,
+    pub max_level: usize,
+    pub links_layers: &'a [LockedLayersContainer],
+    pub ready: Vec<PointOffsetType>,
+    pub entry_points: &'a Mutex<EntryPoints>,
+}
+
+/// Graph being built, loaded to resume the build
+#[derive(Deserialize)]
+pub(super) struct BuildCheckpoint {
+    pub fingerprint: u64,
+    pub max_level: usize,
+    pub links_layers: Vec<LockedLayersContainer>,
+    pub ready: Vec<PointOffsetType>,
+    pub entry_points: EntryPoints,
+}
+
+impl BuildCheckpointRef<'_> {
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        Ok(atomic_save_bin(path, self)?)
+    }
+}
+
+impl BuildCheckpoint {
+    /// Load the checkpoint, if there is a valid one for the build
+    pub fn load(path: &Path, fingerprint: u64, num_points: usize) -> Option<Self> {
+        if !path.exists() {
+            return None;
+        }
+        let checkpoint: Self = match read_bin(path) {
+            Ok(checkpoint) => checkpoint,
+            Err(err) => {
+                log::warn!(
+                    "Failed to load HNSW build checkpoint {}: {err}",
+                    path.display()
+                );
+                return None;
+            }
+        };
+        let is_valid = checkpoint.fingerprint == fingerprint
+            && checkpoint.links_layers.len() == num_points
+            && checkpoint
+                .ready
+                .iter()
+                .all(|&point_id| (point_id as usize) < num_points);
+        if !is_valid {
+            log::warn!("Ignoring invalid HNSW build checkpoint {}", path.display());
+            return None;
+        }
+        Some(checkpoint)
+    }
+}
+
+/// Remove the checkpoint of a finished build
+pub fn remove_checkpoint(path: &Path) {
+    if let Err(err) = std::fs::remove_file(path) {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            log::warn!(
+                "Failed to remove HNSW build checkpoint {}: {err}",
+                path.display()
+            );
+        }
+    }
+}
@@ -91,6 +91,11 @@ impl EntryPoints {
         None
     }
 
+    /// Entry points used for unfiltered searches, excluding the extra ones
+    pub fn iter(&self) -> impl Iterator<Item = &EntryPoint> {
+        self.entry_points.iter()
+    }
+
     /// Find the highest `EntryPoint` which satisfies filtering condition of `checker`
     pub fn get_entry_point<F>(&self, checker: F) -> Option<EntryPoint>
     where
@@ -16,6 +16,7 @@ use crate::index::hnsw_index::entry_points::EntryPoints;
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::search_pools::SearchPools;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 
 pub type LinkContainer = Vec<PointOffsetType>;
@@ -177,6 +178,18 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
         self.links.point_level(point_id)
     }
 
+    /// Links of the point on each level it is included in, starting from the bottom one.
+    /// None, if the point is not in the graph.
+    pub fn point_links(&self, point_id: PointOffsetType) -> Option<Vec<Vec<PointOffsetType>>> {
+        if point_id as usize >= self.links.num_points() {
+            return None;
+        }
+        let links = (0..=self.point_level(point_id))
+            .map(|level| self.links.links(point_id, level).to_vec())
+            .collect();
+        Some(links)
+    }
+
     fn get_entry_point(
         &self,
         points_scorer: &FilteredScorer,
@@ -207,6 +220,7 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
         ef: usize,
         mut points_scorer: FilteredScorer,
         custom_entry_points: Option<&[PointOffsetType]>,
+        pools: &SearchPools,
     ) -> Vec<ScoredPointOffset> {
         let Some(entry_point) = self.get_entry_point(&points_scorer, custom_entry_points) else {
             return Vec::default();
@@ -218,8 +232,18 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
             0,
             &mut points_scorer,
         );
-        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
-        nearest.into_iter().take(top).collect_vec()
+
+        // Same as `search_on_level`, with the visited list taken with the settings of the search
+        let mut visited_list = self.visited_pool.get_pooled(self.num_points(), pools);
+        visited_list.check_and_update_visited(zero_level_entry.idx);
+        let mut search_context = SearchContext::new(zero_level_entry, max(top, ef));
+        self._search_on_level(
+            &mut search_context,
+            0,
+            &mut visited_list,
+            &mut points_scorer,
+        );
+        search_context.nearest.into_iter().take(top).collect_vec()
     }
 
     pub fn get_path(path: &Path) -> PathBuf {
@@ -321,7 +345,7 @@ mod tests {
         let raw_scorer = vector_storage.get_raw_scorer(query.to_owned()).unwrap();
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let ef = 16;
-        graph.search(top, ef, scorer, None)
+        graph.search(top, ef, scorer, None, &Default::default())
     }
 
     const M: usize = 8;
@@ -382,6 +406,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_point_links() {
+        let num_vectors = 100;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (_, graph_layers) = create_graph_layer_fixture::<CosineMetric, _>(
+            num_vectors,
+            M,
+            dim,
+            false,
+            &mut rng,
+            None,
+        );
+
+        for point_id in 0..num_vectors as PointOffsetType {
+            let links = graph_layers.point_links(point_id).unwrap();
+            assert_eq!(links.len(), graph_layers.point_level(point_id) + 1);
+            assert_eq!(links[0], graph_layers.links.links(point_id, 0));
+        }
+
+        assert!(graph_layers
+            .point_links(num_vectors as PointOffsetType)
+            .is_none());
+    }
+
     #[test]
     fn test_save_and_load() {
         let num_vectors = 100;
@@ -10,6 +10,7 @@ use parking_lot::{Mutex, MutexGuard, RwLock};
 use rand::distributions::Uniform;
 use rand::Rng;
 
+use super::build_checkpoint::{BuildCheckpoint, BuildCheckpointRef};
 use super::graph_links::GraphLinks;
 use crate::common::operation_error::OperationResult;
 use crate::index::hnsw_index::entry_points::EntryPoints;
@@ -195,6 +196,48 @@ impl GraphLayersBuilder {
         self.links_layers.len()
     }
 
+    /// Whether the point is linked into the graph already
+    pub fn is_ready(&self, point_id: PointOffsetType) -> bool {
+        self.ready_list.read()[point_id as usize]
+    }
+
+    /// Save the graph built so far, must not be called while points are being linked
+    pub fn save_checkpoint(&self, path: &Path, fingerprint: u64) -> OperationResult<()> {
+        BuildCheckpointRef {
+            fingerprint,
+            max_level: self.max_level.load(std::sync::atomic::Ordering::Relaxed),
+            links_layers: &self.links_layers,
+            ready: self
+                .ready_list
+                .read()
+                .iter_ones()
+                .map(|point_id| point_id as PointOffsetType)
+                .collect(),
+            entry_points: &self.entry_points,
+        }
+        .save(path)
+    }
+
+    /// Continue building the graph of the checkpoint, including the levels of its points
+    ///
+    /// Returns whether there was a valid checkpoint to resume from.
+    pub fn resume_from_checkpoint(&mut self, path: &Path, fingerprint: u64) -> bool {
+        let Some(checkpoint) = BuildCheckpoint::load(path, fingerprint, self.num_points()) else {
+            return false;
+        };
+
+        let mut ready_list = BitVec::repeat(false, checkpoint.links_layers.len());
+        for point_id in checkpoint.ready {
+            ready_list.set(point_id as usize, true);
+        }
+
+        self.max_level = AtomicUsize::new(checkpoint.max_level);
+        self.links_layers = checkpoint.links_layers;
+        self.entry_points = Mutex::new(checkpoint.entry_points);
+        self.ready_list = RwLock::new(ready_list);
+        true
+    }
+
     /// Generate random level for a new point, according to geometric distribution
     pub fn get_random_layer<R>(&self, rng: &mut R) -> usize
     where
@@ -653,7 +696,7 @@ mod tests {
         let raw_scorer = vector_holder.get_raw_scorer(query.clone()).unwrap();
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let ef = 16;
-        let graph_search = graph.search(top, ef, scorer, None);
+        let graph_search = graph.search(top, ef, scorer, None, &Default::default());
 
         assert_eq!(reference_top.into_vec(), graph_search);
     }
@@ -736,7 +779,7 @@ mod tests {
         let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
         let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
         let ef = 16;
-        let graph_search = graph.search(top, ef, scorer, None);
+        let graph_search = graph.search(top, ef, scorer, None, &Default::default());
 
         assert_eq!(reference_top.into_vec(), graph_search);
     }
@@ -889,4 +932,54 @@ mod tests {
             .for_each(|x| result.push(*x));
         assert_eq!(&result, &vec![1, 2, 3, 4, 5, 6]);
     }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let num_vectors = 200;
+        let dim = 8;
+        let fingerprint = 42;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers_builder) =
+            create_graph_layer::<M, _>(num_vectors, dim, false, &mut rng);
+
+        let dir = tempfile::Builder::new()
+            .prefix("checkpoints")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("checkpoint.bin");
+        graph_layers_builder
+            .save_checkpoint(&path, fingerprint)
+            .unwrap();
+
+        let new_builder = || GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, false);
+
+        // Checkpoints of other builds are ignored
+        let mut other_builder = new_builder();
+        assert!(!other_builder.resume_from_checkpoint(&path, fingerprint + 1));
+        assert!(!other_builder.is_ready(0));
+
+        let mut resumed_builder = new_builder();
+        assert!(resumed_builder.resume_from_checkpoint(&path, fingerprint));
+        assert!((0..num_vectors as PointOffsetType).all(|idx| resumed_builder.is_ready(idx)));
+        let entry_point =
+            |builder: &GraphLayersBuilder| builder.entry_points.lock().get_entry_point(|_| true);
+        assert_eq!(
+            entry_point(&resumed_builder),
+            entry_point(&graph_layers_builder),
+        );
+
+        let top = 5;
+        let query = random_vector(&mut rng, dim);
+        let search = |builder: GraphLayersBuilder| {
+            let graph = builder.into_graph_layers::<GraphLinksRam>(None).unwrap();
+            let fake_filter_context = FakeFilterContext {};
+            let raw_scorer = vector_holder.get_raw_scorer(query.clone()).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            graph.search(top, 16, scorer, None, &Default::default())
+        };
+        assert_eq!(search(resumed_builder), search(graph_layers_builder));
+    }
 }
@@ -0,0 +1,185 @@
+use common::types::PointOffsetType;
+
+use super::graph_layers::{GraphLayers, GraphLayersBase};
+use super::graph_links::GraphLinks;
+use crate::telemetry::HnswLevelTelemetry;
+
+/// Structural statistics of a built graph. The graph is immutable, so they are computed once.
+#[derive(Clone, Debug)]
+pub struct GraphStats {
+    pub levels: Vec<HnswLevelTelemetry>,
+    pub entry_points: usize,
+    pub components: usize,
+}
+
+impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
+    pub fn stats(&self) -> GraphStats {
+        let num_points = self.num_points();
+        let entry_points: Vec<_> = self
+            .entry_points
+            .iter()
+            .map(|entry| entry.point_id)
+            .collect();
+
+        let mut levels: Vec<(usize, usize)> = Vec::new();
+        let mut components = Components::new(num_points);
+
+        for point_id in 0..num_points as PointOffsetType {
+            let point_level = self.point_level(point_id);
+            if levels.len() <= point_level {
+                levels.resize(point_level + 1, (0, 0));
+            }
+            for (level, (points, links)) in levels.iter_mut().enumerate().take(point_level + 1) {
+                *points += 1;
+                *links += self.links.links(point_id, level).len();
+            }
+
+            let point_links = self.links.links(point_id, 0);
+            // Points without links are not indexed, unless they are the only entry point
+            if !point_links.is_empty() || entry_points.contains(&point_id) {
+                components.add(point_id);
+            }
+            for &link in point_links {
+                components.join(point_id, link);
+            }
+        }
+
+        let levels = levels
+            .into_iter()
+            .enumerate()
+            .map(|(level, (points, links))| HnswLevelTelemetry {
+                points,
+                avg_out_degree: if points == 0 {
+                    0.0
+                } else {
+                    links as f64 / points as f64
+                },
+                max_out_degree: self.get_m(level),
+            })
+            .collect();
+
+        GraphStats {
+            levels,
+            entry_points: entry_points.len(),
+            components: components.count(),
+        }
+    }
+}
+
+/// Connected components of an undirected graph, tracked with a disjoint-set forest
+struct Components {
+    parents: Vec<Option<PointOffsetType>>,
+}
+
+impl Components {
+    fn new(num_points: usize) -> Self {
+        Self {
+            parents: vec![None; num_points],
+        }
+    }
+
+    fn add(&mut self, point_id: PointOffsetType) {
+        let parent = &mut self.parents[point_id as usize];
+        if parent.is_none() {
+            *parent = Some(point_id);
+        }
+    }
+
+    fn root(&mut self, mut point_id: PointOffsetType) -> PointOffsetType {
+        while let Some(parent) = self.parents[point_id as usize] {
+            if parent == point_id {
+                break;
+            }
+            // Path halving
+            let grandparent = self.parents[parent as usize].unwrap_or(parent);
+            self.parents[point_id as usize] = Some(grandparent);
+            point_id = grandparent;
+        }
+        point_id
+    }
+
+    fn join(&mut self, a: PointOffsetType, b: PointOffsetType) {
+        self.add(a);
+        self.add(b);
+        let (root_a, root_b) = (self.root(a), self.root(b));
+        if root_a != root_b {
+            self.parents[root_a as usize] = Some(root_b);
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(|(point_id, parent)| **parent == Some(*point_id as PointOffsetType))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::index::hnsw_index::graph_links::{GraphLinksConverter, GraphLinksRam};
+    use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+    use crate::spaces::simple::CosineMetric;
+
+    #[test]
+    fn test_components() {
+        let mut components = Components::new(6);
+        components.join(0, 1);
+        components.join(2, 3);
+        components.join(1, 2);
+        components.add(4);
+
+        assert_eq!(components.count(), 2);
+        assert_eq!(components.root(0), components.root(3));
+    }
+
+    #[test]
+    fn test_graph_stats() {
+        let num_vectors = 500;
+        let m = 8;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (_, mut graph_layers) = create_graph_layer_fixture::<CosineMetric, _>(
+            num_vectors,
+            m,
+            dim,
+            false,
+            &mut rng,
+            None,
+        );
+
+        let stats = graph_layers.stats();
+        assert_eq!(stats.components, 1);
+        assert_eq!(stats.levels[0].points, num_vectors);
+        assert_eq!(stats.levels[0].max_out_degree, m * 2);
+        assert!(stats.levels[0].avg_out_degree > 1.0);
+        assert!(stats.levels.windows(2).all(|l| l[0].points >= l[1].points));
+
+        // Cut two points off the rest of the graph, they are only linked to each other
+        let mut links: Vec<Vec<Vec<PointOffsetType>>> = (0..num_vectors as PointOffsetType)
+            .map(|point_id| graph_layers.point_links(point_id).unwrap())
+            .collect();
+        let isolated = [10, 20];
+        for point_links in links.iter_mut() {
+            for level_links in point_links.iter_mut() {
+                level_links.retain(|link| !isolated.contains(link));
+            }
+        }
+        for &point_id in &isolated {
+            let point_links = &mut links[point_id as usize];
+            point_links.iter_mut().for_each(Vec::clear);
+            point_links[0] = vec![isolated[0] + isolated[1] - point_id];
+        }
+        graph_layers.links =
+            GraphLinksRam::from_converter(GraphLinksConverter::new(links)).unwrap();
+
+        assert_eq!(graph_layers.stats().components, 2);
+    }
+}
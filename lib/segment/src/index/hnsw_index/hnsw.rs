@@ -2,8 +2,9 @@ use std::fs::create_dir_all;
 use std::ops::Deref as _;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::thread;
+use std::time::Instant;
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitSlice;
@@ -18,6 +19,7 @@ use rand::thread_rng;
 use rayon::prelude::*;
 use rayon::ThreadPool;
 
+use super::build_checkpoint;
 use super::graph_links::{GraphLinks, GraphLinksMmap};
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::{
@@ -31,13 +33,15 @@ use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::graph_stats::GraphStats;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
+use crate::index::search_stats::SearchStats;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 use crate::index::{PayloadIndex, VectorIndex};
-use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::telemetry::{HnswGraphTelemetry, VectorIndexSearchesTelemetry};
 use crate::types::Condition::Field;
 use crate::types::{
     default_quantization_ignore_value, default_quantization_oversampling_value, FieldCondition,
@@ -58,6 +62,10 @@ const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 32;
 #[cfg(not(debug_assertions))]
 const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 256;
 
+/// Plain searches over at most this many candidates per requested result score the original
+/// vectors, if the request doesn't set how to use quantization.
+const AUTO_ORIGINAL_VECTORS_CANDIDATES_FACTOR: usize = 4;
+
 pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
@@ -66,6 +74,7 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: GraphLayers<TGraphLinks>,
+    graph_stats: OnceLock<GraphStats>,
     searches_telemetry: HNSWSearchesTelemetry,
 }
 
@@ -76,6 +85,8 @@ struct HNSWSearchesTelemetry {
     large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
     exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
     exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+    auto_quantized: Arc<Mutex<OperationDurationsAggregator>>,
+    auto_original: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
 pub struct HnswIndexOpenArgs<'a> {
@@ -87,6 +98,8 @@ pub struct HnswIndexOpenArgs<'a> {
     pub hnsw_config: HnswConfig,
     pub permit: Option<Arc<CpuPermit>>,
     pub stopped: &'a AtomicBool,
+    /// Directory to save checkpoints of the build in, to resume it after an interruption
+    pub checkpoints_path: Option<&'a Path>,
 }
 
 impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
@@ -100,6 +113,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             hnsw_config,
             permit,
             stopped,
+            checkpoints_path,
         } = args;
 
         create_dir_all(path)?;
@@ -158,6 +172,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 hnsw_config,
                 num_cpus,
                 stopped,
+                checkpoints_path,
             )?;
 
             config.save(&config_path)?;
@@ -174,6 +189,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            graph_stats: OnceLock::new(),
             searches_telemetry: HNSWSearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -181,6 +197,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 large_cardinality: OperationDurationsAggregator::new(),
                 exact_filtered: OperationDurationsAggregator::new(),
                 exact_unfiltered: OperationDurationsAggregator::new(),
+                auto_quantized: OperationDurationsAggregator::new(),
+                auto_original: OperationDurationsAggregator::new(),
             },
         })
     }
@@ -190,6 +208,41 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         &self.graph
     }
 
+    /// Links of the point on each level of the graph, starting from the bottom one
+    pub fn point_links(&self, point_id: PointOffsetType) -> Option<Vec<Vec<PointOffsetType>>> {
+        self.graph.point_links(point_id)
+    }
+
+    /// Compute the structural statistics of the graph, if not done yet.
+    /// Walks all links of the graph, so it must not be called on the request path.
+    pub fn compute_graph_stats(&self) {
+        self.graph_stats.get_or_init(|| self.graph.stats());
+    }
+
+    /// Structure of the graph, and the fraction of its points deleted since it was built.
+    /// None, if the statistics are not computed yet, see [`Self::compute_graph_stats`].
+    pub fn graph_telemetry(&self) -> Option<HnswGraphTelemetry> {
+        let stats = self.graph_stats.get()?;
+
+        // Deleted points keep their vectors, so the counts rarely overlap
+        let num_points = self.graph.num_points();
+        let deleted_points = self.id_tracker.borrow().deleted_point_count()
+            + self.vector_storage.borrow().deleted_vector_count();
+        let deleted_points = deleted_points.min(num_points);
+
+        Some(HnswGraphTelemetry {
+            vector_name: None,
+            levels: stats.levels.clone(),
+            entry_points: stats.entry_points,
+            components: stats.components,
+            deleted_fraction: if num_points == 0 {
+                0.0
+            } else {
+                deleted_points as f64 / num_points as f64
+            },
+        })
+    }
+
     pub fn get_quantized_vectors(&self) -> Arc<AtomicRefCell<Option<QuantizedVectors>>> {
         self.quantized_vectors.clone()
     }
@@ -204,6 +257,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         hnsw_config: HnswConfig,
         num_cpus: usize,
         stopped: &AtomicBool,
+        checkpoints_path: Option<&Path>,
     ) -> OperationResult<(HnswGraphConfig, GraphLayers<TGraphLinks>)> {
         let total_vector_count = vector_storage.total_vector_count();
 
@@ -274,12 +328,43 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             })
             .build()?;
 
-        for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
-            check_process_stopped(stopped)?;
-            let level = graph_layers_builder.get_random_layer(&mut rng);
-            graph_layers_builder.set_levels(vector_id, level);
+        let checkpoint = checkpoints_path.map(|checkpoints_path| {
+            let fingerprint = build_checkpoint::fingerprint(
+                path,
+                id_tracker,
+                deleted_bitslice,
+                &[total_vector_count, config.m, config.m0, config.ef_construct],
+                quantized_vectors.is_some(),
+            );
+            let checkpoint_path = build_checkpoint::checkpoint_path(checkpoints_path, fingerprint);
+            (checkpoint_path, fingerprint)
+        });
+
+        let resumed = checkpoint
+            .as_ref()
+            .is_some_and(|(checkpoint_path, fingerprint)| {
+                graph_layers_builder.resume_from_checkpoint(checkpoint_path, *fingerprint)
+            });
+
+        if resumed {
+            debug!("resuming HNSW build from checkpoint");
+        } else {
+            for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
+                check_process_stopped(stopped)?;
+                let level = graph_layers_builder.get_random_layer(&mut rng);
+                graph_layers_builder.set_levels(vector_id, level);
+            }
         }
 
+        let save_checkpoint = |graph_layers_builder: &GraphLayersBuilder| {
+            let Some((checkpoint_path, fingerprint)) = &checkpoint else {
+                return;
+            };
+            if let Err(err) = graph_layers_builder.save_checkpoint(checkpoint_path, *fingerprint) {
+                log::warn!("Failed to save HNSW build checkpoint: {err}");
+            }
+        };
+
         let mut indexed_vectors = 0;
 
         if config.m > 0 {
@@ -293,6 +378,16 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
             indexed_vectors = ids.len() + first_few_ids.len();
 
+            // Points linked before the checkpoint are part of the graph already
+            let first_few_ids: Vec<_> = first_few_ids
+                .into_iter()
+                .filter(|&vector_id| !graph_layers_builder.is_ready(vector_id))
+                .collect();
+            let ids: Vec<_> = ids
+                .into_iter()
+                .filter(|&vector_id| !graph_layers_builder.is_ready(vector_id))
+                .collect();
+
             let insert_point = |vector_id| {
                 check_process_stopped(stopped)?;
                 let vector = vector_storage.get_vector(vector_id);
@@ -317,8 +412,26 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 insert_point(vector_id)?;
             }
 
-            if !ids.is_empty() {
-                pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
+            let build_start = Instant::now();
+            let mut last_checkpoint = build_start;
+            for chunk in ids.chunks(build_checkpoint::CHECKPOINT_CHUNK_SIZE) {
+                let linked = pool.install(|| chunk.par_iter().copied().try_for_each(&insert_point));
+                if let Err(err) = linked {
+                    // No points are being linked anymore, keep the progress for the next build
+                    if build_start.elapsed() >= build_checkpoint::CHECKPOINT_INTERVAL {
+                        save_checkpoint(&graph_layers_builder);
+                    }
+                    return Err(err);
+                }
+                if last_checkpoint.elapsed() >= build_checkpoint::CHECKPOINT_INTERVAL {
+                    save_checkpoint(&graph_layers_builder);
+                    last_checkpoint = Instant::now();
+                }
+            }
+
+            // Don't link the main graph again if building the payload graphs is interrupted
+            if build_start.elapsed() >= build_checkpoint::CHECKPOINT_INTERVAL {
+                save_checkpoint(&graph_layers_builder);
             }
 
             debug!("finish main graph");
@@ -398,6 +511,10 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let graph: GraphLayers<TGraphLinks> =
             graph_layers_builder.into_graph_layers(Some(&graph_links_path))?;
 
+        if let Some((checkpoint_path, _)) = &checkpoint {
+            build_checkpoint::remove_checkpoint(checkpoint_path);
+        }
+
         #[cfg(debug_assertions)]
         {
             for (idx, deleted) in deleted_bitslice.iter().enumerate() {
@@ -527,12 +644,24 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
 
         let filter_context = filter.map(|f| payload_index.filter_context(f));
-        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
-
-        let search_result =
-            self.graph
-                .search(oversampled_top, ef, points_scorer, custom_entry_points);
-        self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref())
+            .with_search_stats(vector_query_context.search_stats());
+
+        let search_result = self.graph.search(
+            oversampled_top,
+            ef,
+            points_scorer,
+            custom_entry_points,
+            &vector_query_context.search_pools(),
+        );
+        self.postprocess_search_result(
+            search_result,
+            vector,
+            params,
+            top,
+            &is_stopped,
+            vector_query_context.search_stats(),
+        )
     }
 
     fn search_vectors_with_graph(
@@ -543,6 +672,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         params: Option<&SearchParams>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        if let Some(search_stats) = vector_query_context.search_stats() {
+            search_stats.add_hnsw_searches(vectors.len());
+        }
         vectors
             .iter()
             .map(|&vector| match vector {
@@ -590,8 +722,19 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
         let search_result =
             raw_scorer.peek_top_iter(&mut filtered_points.iter().copied(), oversampled_top);
+        let search_stats = vector_query_context.search_stats();
+        if let Some(search_stats) = search_stats {
+            search_stats.add_vectors_scored(filtered_points.len());
+        }
 
-        self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+        self.postprocess_search_result(
+            search_result,
+            vector,
+            params,
+            top,
+            &is_stopped,
+            search_stats,
+        )
     }
 
     fn search_vectors_plain(
@@ -604,7 +747,29 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let payload_index = self.payload_index.borrow();
         // share filtered points for all query vectors
-        let filtered_points = payload_index.query_points(filter);
+        let search_stats = vector_query_context.search_stats();
+        let filtered_points = payload_index.query_points_with_stats(filter, search_stats);
+        if let Some(search_stats) = search_stats {
+            search_stats.add_plain_searches(vectors.len());
+        }
+
+        let auto_quantization = self.auto_quantization(filtered_points.len(), top, params);
+        let auto_params = auto_quantization.map(|quantized| SearchParams {
+            quantization: Some(QuantizationSearchParams {
+                ignore: !quantized,
+                ..Default::default()
+            }),
+            ..params.copied().unwrap_or_default()
+        });
+        let params = auto_params.as_ref().or(params);
+        let _timer = auto_quantization.map(|quantized| {
+            ScopeDurationMeasurer::new(if quantized {
+                &self.searches_telemetry.auto_quantized
+            } else {
+                &self.searches_telemetry.auto_original
+            })
+        });
+
         vectors
             .iter()
             .map(|vector| {
@@ -613,6 +778,27 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             .collect()
     }
 
+    /// Choose whether a plain search over the given number of candidates scores the quantized
+    /// vectors, if the request doesn't set how to use quantization
+    ///
+    /// Scoring few candidates with the original vectors costs about as much as scoring them with
+    /// the quantized vectors and rescoring the best ones, but gives exact scores. Quantized
+    /// vectors pay off once there are many more candidates than requested results.
+    ///
+    /// Returns `None` if there is nothing to choose.
+    fn auto_quantization(
+        &self,
+        candidates: usize,
+        top: usize,
+        params: Option<&SearchParams>,
+    ) -> Option<bool> {
+        let quantization_set = params.and_then(|params| params.quantization).is_some();
+        if quantization_set || self.quantized_vectors.borrow().is_none() {
+            return None;
+        }
+        Some(candidates > top.saturating_mul(AUTO_ORIGINAL_VECTORS_CANDIDATES_FACTOR))
+    }
+
     fn discovery_search_with_graph(
         &self,
         discovery_query: DiscoveryQuery<Vector>,
@@ -713,6 +899,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         params: Option<&SearchParams>,
         top: usize,
         is_stopped: &AtomicBool,
+        search_stats: Option<&SearchStats>,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         let id_tracker = self.id_tracker.borrow();
         let vector_storage = self.vector_storage.borrow();
@@ -740,6 +927,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
             let mut ids_iterator = search_result.iter().map(|x| x.idx);
             let mut re_scored = raw_scorer.score_points_unfiltered(&mut ids_iterator);
+            if let Some(search_stats) = search_stats {
+                search_stats.add_vectors_scored(re_scored.len());
+            }
 
             re_scored.sort_unstable();
             re_scored.reverse();
@@ -792,6 +982,12 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                         .unwrap_or(id_tracker.deleted_point_bitslice());
 
                     let is_stopped = query_context.is_stopped();
+                    if let Some(search_stats) = query_context.search_stats() {
+                        search_stats.add_plain_searches(vectors.len());
+                        search_stats.add_vectors_scored(
+                            vectors.len() * vector_storage.available_vector_count(),
+                        );
+                    }
 
                     vectors
                         .iter()
@@ -912,6 +1108,8 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             filtered_sparse: Default::default(),
             unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(detail),
             unfiltered_sparse: Default::default(),
+            filtered_auto_quantized: tm.auto_quantized.lock().get_statistics(detail),
+            filtered_auto_original: tm.auto_original.lock().get_statistics(detail),
         }
     }
 
@@ -1,12 +1,14 @@
 use common::defaults::thread_count_for_hnsw;
 
 mod build_cache;
+pub mod build_checkpoint;
 pub mod build_condition_checker;
 mod config;
 mod entry_points;
 pub mod graph_layers;
 pub mod graph_layers_builder;
 pub mod graph_links;
+pub mod graph_stats;
 pub mod hnsw;
 pub mod point_scorer;
 mod search_context;
@@ -1,5 +1,6 @@
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
+use crate::index::search_stats::SearchStats;
 use crate::payload_storage::FilterContext;
 use crate::vector_storage::RawScorer;
 
@@ -7,6 +8,7 @@ pub struct FilteredScorer<'a> {
     pub raw_scorer: &'a dyn RawScorer,
     pub filter_context: Option<&'a dyn FilterContext>,
     points_buffer: Vec<ScoredPointOffset>,
+    search_stats: Option<&'a SearchStats>,
 }
 
 impl<'a> FilteredScorer<'a> {
@@ -18,13 +20,25 @@ impl<'a> FilteredScorer<'a> {
             raw_scorer,
             filter_context,
             points_buffer: Vec::new(),
+            search_stats: None,
         }
     }
 
+    /// Count the filter checks and the scored vectors into `search_stats`
+    pub fn with_search_stats(mut self, search_stats: Option<&'a SearchStats>) -> Self {
+        self.search_stats = search_stats;
+        self
+    }
+
     pub fn check_vector(&self, point_id: PointOffsetType) -> bool {
         match self.filter_context {
             None => self.raw_scorer.check_vector(point_id),
-            Some(f) => f.check(point_id) && self.raw_scorer.check_vector(point_id),
+            Some(f) => {
+                if let Some(search_stats) = self.search_stats {
+                    search_stats.add_filter_checks(1);
+                }
+                f.check(point_id) && self.raw_scorer.check_vector(point_id)
+            }
         }
     }
 
@@ -56,9 +70,15 @@ impl<'a> FilteredScorer<'a> {
                         filtered_len += 1;
                     }
                 }
+                if let Some(search_stats) = self.search_stats {
+                    search_stats.add_filter_checks(len);
+                }
                 &point_ids[0..filtered_len]
             }
         };
+        if let Some(search_stats) = self.search_stats {
+            search_stats.add_vectors_scored(filtered_point_ids.len());
+        }
         if limit == 0 {
             self.points_buffer
                 .resize_with(filtered_point_ids.len(), ScoredPointOffset::default);
@@ -73,6 +93,9 @@ impl<'a> FilteredScorer<'a> {
     }
 
     pub fn score_point(&self, point_id: PointOffsetType) -> ScoreType {
+        if let Some(search_stats) = self.search_stats {
+            search_stats.add_vectors_scored(1);
+        }
         self.raw_scorer.score_point(point_id)
     }
 
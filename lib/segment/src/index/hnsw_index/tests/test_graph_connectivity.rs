@@ -49,6 +49,7 @@ fn test_graph_connectivity() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
         sparse_vector_data: Default::default(),
     };
 
@@ -87,6 +88,7 @@ fn test_graph_connectivity() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
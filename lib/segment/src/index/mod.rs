@@ -7,6 +7,8 @@ pub mod plain_payload_index;
 pub mod query_estimator;
 mod query_optimization;
 mod sample_estimation;
+pub mod search_pools;
+pub mod search_stats;
 pub mod sparse_index;
 mod struct_filter_context;
 pub mod struct_payload_index;
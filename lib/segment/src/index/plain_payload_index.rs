@@ -278,7 +278,12 @@ impl VectorIndex for PlainIndex {
                 let id_tracker = self.id_tracker.borrow();
                 let payload_index = self.payload_index.borrow();
                 let vector_storage = self.vector_storage.borrow();
-                let filtered_ids_vec = payload_index.query_points(filter);
+                let search_stats = query_context.search_stats();
+                let filtered_ids_vec = payload_index.query_points_with_stats(filter, search_stats);
+                if let Some(search_stats) = search_stats {
+                    search_stats.add_plain_searches(vectors.len());
+                    search_stats.add_vectors_scored(vectors.len() * filtered_ids_vec.len());
+                }
                 let deleted_points = query_context
                     .deleted_points()
                     .unwrap_or(id_tracker.deleted_point_bitslice());
@@ -304,6 +309,12 @@ impl VectorIndex for PlainIndex {
                 let deleted_points = query_context
                     .deleted_points()
                     .unwrap_or(id_tracker.deleted_point_bitslice());
+                if let Some(search_stats) = query_context.search_stats() {
+                    search_stats.add_plain_searches(vectors.len());
+                    search_stats.add_vectors_scored(
+                        vectors.len() * vector_storage.available_vector_count(),
+                    );
+                }
                 vectors
                     .iter()
                     .map(|&vector| {
@@ -338,6 +349,8 @@ impl VectorIndex for PlainIndex {
             filtered_sparse: Default::default(),
             unfiltered_exact: OperationDurationStatistics::default(),
             unfiltered_sparse: OperationDurationStatistics::default(),
+            filtered_auto_quantized: OperationDurationStatistics::default(),
+            filtered_auto_original: OperationDurationStatistics::default(),
         }
     }
 
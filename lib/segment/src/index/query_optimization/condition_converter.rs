@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use bitvec::prelude::BitVec;
 use common::types::PointOffsetType;
 use serde_json::Value;
 
@@ -8,17 +9,21 @@ use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
+use crate::json_path::{JsonPath, JsonPathInterface as _};
 use crate::payload_storage::condition_checker::INDEXSET_ITER_THRESHOLD;
 use crate::payload_storage::query_checker::{
     check_field_condition, check_is_empty_condition, check_is_null_condition, check_payload,
     select_nested_indexes,
 };
 use crate::types::{
-    AnyVariants, Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox,
-    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchText, MatchValue,
-    OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
+    AnyVariants, Condition, DateTimePayloadType, FieldCondition, Filter, FloatPayloadType,
+    GeoBoundingBox, GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchText,
+    MatchValue, OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
 };
 
+/// Approximate memory taken by an id in a hash set of segment offsets, in bits
+const HAS_ID_SET_BITS_PER_ID: usize = 128;
+
 pub fn condition_converter<'a>(
     condition: &'a Condition,
     field_indexes: &'a IndexesMap,
@@ -64,14 +69,25 @@ pub fn condition_converter<'a>(
                 check_is_null_condition(is_null, &payload)
             })
         }),
-        // ToDo: It might be possible to make this condition faster by using `VisitedPool` instead of HashSet
         Condition::HasId(has_id) => {
-            let segment_ids: HashSet<_> = has_id
+            let segment_ids: HashSet<PointOffsetType> = has_id
                 .has_id
                 .iter()
                 .filter_map(|external_id| id_tracker.internal_id(*external_id))
                 .collect();
-            Box::new(move |point_id| segment_ids.contains(&point_id))
+
+            // A bitmap of segment offsets takes a bit for every offset up to the largest one,
+            // so it is only used if the ids are dense enough to take less memory than the set
+            let bitmap_len = segment_ids.iter().max().map_or(0, |&max| max as usize + 1);
+            if bitmap_len > segment_ids.len() * HAS_ID_SET_BITS_PER_ID {
+                return Box::new(move |point_id| segment_ids.contains(&point_id));
+            }
+
+            let mut bitmap: BitVec = BitVec::repeat(false, bitmap_len);
+            for offset in segment_ids {
+                bitmap.set(offset as usize, true);
+            }
+            Box::new(move |point_id| bitmap.get(point_id as usize).is_some_and(|bit| *bit))
         }
         Condition::Nested(nested) => {
             // Select indexes for nested fields. Trim nested part from key, so
@@ -94,9 +110,22 @@ pub fn condition_converter<'a>(
             // can address proper index for nested field.
             let nested_path = nested.array_key();
 
+            let (index_checkers, exact) =
+                nested_index_checkers(&nested_path, nested.filter(), field_indexes);
+            if exact {
+                return Box::new(move |point_id| {
+                    index_checkers.iter().all(|checker| checker(point_id))
+                });
+            }
+
             let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
 
             Box::new(move |point_id| {
+                // Points failing the indexes of the nested fields have no matching object
+                if !index_checkers.iter().all(|checker| checker(point_id)) {
+                    return false;
+                }
+
                 payload_provider.with_payload(point_id, |payload| {
                     let field_values = payload.get_value(&nested_path);
 
@@ -134,6 +163,80 @@ pub fn condition_converter<'a>(
     }
 }
 
+/// If the condition matches a value of a nested object, it matches the point with all values of
+/// the objects of the array.
+///
+/// Indexes of nested fields hold the values of all objects of a point, so such a condition can be
+/// checked on them exactly.
+pub fn matches_any_nested_value(condition: &FieldCondition) -> bool {
+    match condition {
+        FieldCondition {
+            r#match: Some(Match::Value(_) | Match::Any(_)),
+            values_count: None,
+            ..
+        } => true,
+        FieldCondition {
+            r#match: None,
+            values_count: None,
+            range,
+            geo_bounding_box,
+            geo_radius,
+            geo_polygon,
+            ..
+        } => {
+            range.is_some()
+                || geo_bounding_box.is_some()
+                || geo_radius.is_some()
+                || geo_polygon.is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Checks of the conditions of a nested filter on the indexes of the nested fields
+///
+/// An object matches the filter only if it matches all `must` conditions, so a point with a
+/// matching object passes the checks of those conditions on the indexes, which hold the values
+/// of all objects of the point. Points failing them don't need their payload checked.
+///
+/// Returns the checks, and whether they are exact, so that the payload is not needed at all.
+fn nested_index_checkers<'a>(
+    nested_path: &JsonPath,
+    filter: &'a Filter,
+    field_indexes: &'a IndexesMap,
+) -> (Vec<ConditionCheckerFn<'a>>, bool) {
+    let index_checker = |condition: &'a Condition| {
+        let Condition::Field(field_condition) = condition else {
+            return None;
+        };
+        // Text matches tokens of a single object, but the index holds tokens of all of them
+        let is_text = matches!(field_condition.r#match, Some(Match::Text(_)));
+        if !is_text && !matches_any_nested_value(field_condition) {
+            return None;
+        }
+        field_indexes
+            .get(&nested_path.extend(&field_condition.key))?
+            .iter()
+            .find_map(|index| field_condition_index(index, field_condition))
+    };
+
+    if let Some(condition) = filter.single_required_condition() {
+        let exact = matches!(condition, Condition::Field(field) if matches_any_nested_value(field));
+        return match index_checker(condition) {
+            Some(checker) => (vec![checker], exact),
+            None => (vec![], false),
+        };
+    }
+
+    let checkers = filter
+        .must
+        .iter()
+        .flatten()
+        .filter_map(index_checker)
+        .collect();
+    (checkers, false)
+}
+
 pub fn field_condition_index<'a>(
     index: &'a FieldIndex,
     field_condition: &FieldCondition,
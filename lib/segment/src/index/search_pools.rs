@@ -0,0 +1,81 @@
+//! Memory reused between searches.
+//!
+//! Searches take visited lists and score buffers from pools of the indexes, and return them once
+//! done, so concurrent searches don't allocate them again. How much is kept for reuse is set per
+//! collection, and each search counts whether it reused pooled memory or had to allocate it.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::defaults::POOL_KEEP_LIMIT;
+
+use crate::types::SearchPoolsConfig;
+
+/// Number of takes from a pool, which reused pooled memory or allocated new memory
+#[derive(Debug, Default)]
+pub struct PoolCounter {
+    reused: AtomicUsize,
+    allocated: AtomicUsize,
+}
+
+impl PoolCounter {
+    pub fn count(&self, reused: bool) {
+        let counter = if reused {
+            &self.reused
+        } else {
+            &self.allocated
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn reused(&self) -> usize {
+        self.reused.load(Ordering::Relaxed)
+    }
+
+    pub fn allocated(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+}
+
+/// Counters of the pools used by searches
+#[derive(Debug, Default)]
+pub struct SearchPoolCounters {
+    /// Visited lists of HNSW searches
+    pub visited_lists: PoolCounter,
+    /// Score buffers of sparse vector searches
+    pub search_contexts: PoolCounter,
+}
+
+/// Pooling settings of a search, and the counters it reports to
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SearchPools<'a> {
+    pub config: SearchPoolsConfig,
+    pub counters: Option<&'a SearchPoolCounters>,
+}
+
+impl SearchPools<'_> {
+    /// Number of visited lists to keep in the pool of an index
+    pub fn visited_pool_size(&self) -> usize {
+        self.config.visited_pool_size.unwrap_or(*POOL_KEEP_LIMIT)
+    }
+
+    /// Number of score buffers to keep in the pool of a sparse index
+    pub fn search_contexts_pool_size(&self) -> usize {
+        if self.config.reuse_search_contexts.unwrap_or(true) {
+            *POOL_KEEP_LIMIT
+        } else {
+            0
+        }
+    }
+
+    pub fn count_visited_list(&self, reused: bool) {
+        if let Some(counters) = self.counters {
+            counters.visited_lists.count(reused);
+        }
+    }
+
+    pub fn count_search_context(&self, reused: bool) {
+        if let Some(counters) = self.counters {
+            counters.search_contexts.count(reused);
+        }
+    }
+}
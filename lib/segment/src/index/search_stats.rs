@@ -0,0 +1,155 @@
+//! Work done by a search.
+//!
+//! Searches requested with statistics count the vectors they score, the points they check against
+//! the filter, and how each segment was searched. Counting is shared by all segments searched for
+//! the request, and only adds a few atomic increments per batch of scored points. Each segment
+//! counts into its own statistics first, which are added to the ones of the request once the
+//! segment is searched, to know the search mode of each segment.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use parking_lot::Mutex;
+
+use crate::types::{QueryStats, SegmentSearchMode, SegmentSearchStats};
+
+/// Counters of the work done by a search
+#[derive(Debug, Default)]
+pub struct SearchStats {
+    vectors_scored: AtomicUsize,
+    filter_checks: AtomicUsize,
+    plain_searches: AtomicUsize,
+    hnsw_searches: AtomicUsize,
+    sparse_searches: AtomicUsize,
+    /// Number of searches of each segment, by search mode
+    segments: Mutex<BTreeMap<(String, SegmentSearchMode), usize>>,
+}
+
+impl SearchStats {
+    pub fn add_vectors_scored(&self, count: usize) {
+        self.vectors_scored.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn add_filter_checks(&self, count: usize) {
+        self.filter_checks.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn add_plain_searches(&self, count: usize) {
+        self.plain_searches.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn add_hnsw_searches(&self, count: usize) {
+        self.hnsw_searches.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn add_sparse_searches(&self, count: usize) {
+        self.sparse_searches.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Add the work reported by another peer
+    pub fn add(&self, stats: &QueryStats) {
+        self.add_vectors_scored(stats.vectors_scored);
+        self.add_filter_checks(stats.filter_checks);
+        self.add_plain_searches(stats.plain_searches);
+        self.add_hnsw_searches(stats.hnsw_searches);
+        self.add_sparse_searches(stats.sparse_searches);
+
+        let mut segments = self.segments.lock();
+        for segment in &stats.segments {
+            *segments
+                .entry((segment.segment.clone(), segment.mode))
+                .or_default() += segment.searches;
+        }
+    }
+
+    /// Add the work done to search a single segment, with the modes it was searched in
+    pub fn add_segment(&self, segment: &str, segment_stats: &SearchStats) {
+        let stats = segment_stats.stats();
+        self.add(&stats);
+
+        let modes = [
+            (SegmentSearchMode::Plain, stats.plain_searches),
+            (SegmentSearchMode::Hnsw, stats.hnsw_searches),
+            (SegmentSearchMode::Sparse, stats.sparse_searches),
+        ];
+        let mut segments = self.segments.lock();
+        for (mode, searches) in modes {
+            if searches > 0 {
+                *segments.entry((segment.to_string(), mode)).or_default() += searches;
+            }
+        }
+    }
+
+    pub fn stats(&self) -> QueryStats {
+        let segments = self
+            .segments
+            .lock()
+            .iter()
+            .map(|((segment, mode), searches)| SegmentSearchStats {
+                segment: segment.clone(),
+                mode: *mode,
+                searches: *searches,
+            })
+            .collect();
+
+        QueryStats {
+            vectors_scored: self.vectors_scored.load(Ordering::Relaxed),
+            filter_checks: self.filter_checks.load(Ordering::Relaxed),
+            plain_searches: self.plain_searches.load(Ordering::Relaxed),
+            hnsw_searches: self.hnsw_searches.load(Ordering::Relaxed),
+            sparse_searches: self.sparse_searches.load(Ordering::Relaxed),
+            segments,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_modes_of_segments() {
+        let stats = SearchStats::default();
+
+        let segment_stats = SearchStats::default();
+        segment_stats.add_hnsw_searches(2);
+        segment_stats.add_plain_searches(1);
+        segment_stats.add_vectors_scored(100);
+        stats.add_segment("b", &segment_stats);
+
+        let segment_stats = SearchStats::default();
+        segment_stats.add_plain_searches(1);
+        segment_stats.add_vectors_scored(10);
+        stats.add_segment("a", &segment_stats);
+
+        // Same segment searched again, e.g. on another peer
+        stats.add(&QueryStats {
+            plain_searches: 1,
+            segments: vec![SegmentSearchStats {
+                segment: "a".to_string(),
+                mode: SegmentSearchMode::Plain,
+                searches: 1,
+            }],
+            ..Default::default()
+        });
+
+        let stats = stats.stats();
+        assert_eq!(stats.vectors_scored, 110);
+        assert_eq!(stats.plain_searches, 3);
+        assert_eq!(stats.hnsw_searches, 2);
+
+        let segments: Vec<_> = stats
+            .segments
+            .iter()
+            .map(|segment| (segment.segment.as_str(), segment.mode, segment.searches))
+            .collect();
+        assert_eq!(
+            segments,
+            vec![
+                ("a", SegmentSearchMode::Plain, 2),
+                ("b", SegmentSearchMode::Plain, 1),
+                ("b", SegmentSearchMode::Hnsw, 2),
+            ],
+        );
+    }
+}
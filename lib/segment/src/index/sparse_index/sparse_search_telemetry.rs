@@ -37,6 +37,8 @@ impl SparseSearchesTelemetry {
             filtered_sparse: self.filtered_sparse.lock().get_statistics(detail),
             unfiltered_sparse: self.unfiltered_sparse.lock().get_statistics(detail),
             unfiltered_exact: Default::default(),
+            filtered_auto_quantized: Default::default(),
+            filtered_auto_original: Default::default(),
         }
     }
 }
@@ -10,7 +10,7 @@ use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use io::storage_version::{StorageVersion as _, VERSION_FILE};
 use itertools::Itertools;
 use semver::Version;
-use sparse::common::scores_memory_pool::ScoresMemoryPool;
+use sparse::common::scores_memory_pool::{PooledScoresHandle, ScoresMemoryPool};
 use sparse::common::sparse_vector::SparseVector;
 use sparse::common::types::DimId;
 use sparse::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
@@ -348,7 +348,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         .collect_vec();
 
         let sparse_vector = self.indices_tracker.remap_vector(sparse_vector.to_owned());
-        let memory_handle = self.scores_memory_pool.get();
+        let memory_handle = self.get_scores_memory(vector_query_context);
         let mut search_context = SearchContext::new(
             sparse_vector,
             top,
@@ -359,6 +359,16 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         Ok(search_context.plain_search(&ids))
     }
 
+    /// Score buffer for a search, taken with the pooling settings of the query
+    fn get_scores_memory(&self, vector_query_context: &VectorQueryContext) -> PooledScoresHandle {
+        let pools = vector_query_context.search_pools();
+        let (memory_handle, reused) = self
+            .scores_memory_pool
+            .get_with_limit(pools.search_contexts_pool_size());
+        pools.count_search_context(reused);
+        memory_handle
+    }
+
     // search using sparse vector inverted index
     fn search_sparse(
         &self,
@@ -381,7 +391,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let is_stopped = vector_query_context.is_stopped();
 
         let sparse_vector = self.indices_tracker.remap_vector(sparse_vector.to_owned());
-        let memory_handle = self.scores_memory_pool.get();
+        let memory_handle = self.get_scores_memory(vector_query_context);
         let mut search_context = SearchContext::new(
             sparse_vector,
             top,
@@ -459,6 +469,9 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         if top == 0 {
             return Ok(vec![]);
         }
+        if let Some(search_stats) = vector_query_context.search_stats() {
+            search_stats.add_sparse_searches(1);
+        }
 
         match query_vector {
             QueryVector::Nearest(vector) => self.search_nearest_query(
@@ -11,6 +11,7 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 use schemars::_serde_json::Value;
 
+use crate::common::hyperloglog::HyperLogLog;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
 use crate::common::utils::IndexesMap;
@@ -22,7 +23,9 @@ use crate::index::field_index::{
 };
 use crate::index::payload_config::PayloadConfig;
 use crate::index::query_estimator::estimate_filter;
+use crate::index::query_optimization::condition_converter::matches_any_nested_value;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
+use crate::index::search_stats::SearchStats;
 use crate::index::struct_filter_context::StructFilterContext;
 use crate::index::visited_pool::VisitedPool;
 use crate::index::PayloadIndex;
@@ -74,6 +77,34 @@ impl StructPayloadIndex {
         })
     }
 
+    /// Distinct values of the keyword index of the field, if there is one with at most `limit`
+    /// values
+    pub fn keyword_values(&self, key: PayloadKeyTypeRef, limit: usize) -> Option<HashSet<String>> {
+        self.field_indexes
+            .get(key)?
+            .iter()
+            .find_map(|index| match index {
+                FieldIndex::KeywordIndex(index) => Some(
+                    index
+                        .values_up_to(limit)?
+                        .map(|value| value.to_string())
+                        .collect(),
+                ),
+                _ => None,
+            })
+    }
+
+    /// Sketch of the distinct values of the keyword index of the field, if there is one
+    pub fn keyword_values_sketch(&self, key: PayloadKeyTypeRef) -> Option<HyperLogLog> {
+        self.field_indexes
+            .get(key)?
+            .iter()
+            .find_map(|index| match index {
+                FieldIndex::KeywordIndex(index) => Some(index.values_sketch()),
+                _ => None,
+            })
+    }
+
     fn query_field<'a>(
         &'a self,
         field_condition: &'a FieldCondition,
@@ -232,7 +263,23 @@ impl StructPayloadIndex {
             Condition::Nested(nested) => {
                 // propagate complete nested path in case of multiple nested layers
                 let full_path = JsonPath::extend_or_new(nested_path, &nested.array_key());
-                self.estimate_nested_cardinality(nested.filter(), &full_path)
+                let estimation = self.estimate_nested_cardinality(nested.filter(), &full_path);
+
+                // Indexes of nested fields count points with any matching object, but all
+                // conditions must match the same object. Unless there is a single condition, fewer
+                // points than estimated may match.
+                let exact = matches!(
+                    nested.filter().single_required_condition(),
+                    Some(Condition::Field(field)) if matches_any_nested_value(field),
+                );
+                if exact {
+                    estimation
+                } else {
+                    CardinalityEstimation {
+                        min: 0,
+                        ..estimation
+                    }
+                }
             }
             Condition::IsEmpty(IsEmptyCondition { is_empty: field }) => {
                 let available_points = self.available_point_count();
@@ -334,6 +381,73 @@ impl StructPayloadIndex {
     ) -> OperationResult<()> {
         crate::rocksdb_backup::restore(snapshot_path, &segment_path.join("payload_index"))
     }
+
+    /// Points matching the filter, with the filter checks counted into `search_stats`
+    pub fn query_points_with_stats(
+        &self,
+        query: &Filter,
+        search_stats: Option<&SearchStats>,
+    ) -> Vec<PointOffsetType> {
+        // Assume query is already estimated to be small enough so we can iterate over all matched ids
+
+        let query_cardinality = self.estimate_cardinality(query);
+
+        if query_cardinality.primary_clauses.is_empty() {
+            let id_tracker = self.id_tracker.borrow();
+            let full_scan_iterator = id_tracker.iter_ids();
+
+            let struct_filtered_context = self.struct_filtered_context(query);
+            // Worst case: query expected to return few matches, but index can't be used
+            let mut filter_checks = 0;
+            let matched_points: Vec<_> = full_scan_iterator
+                .filter(|i| {
+                    filter_checks += 1;
+                    struct_filtered_context.check(*i)
+                })
+                .collect();
+
+            if let Some(search_stats) = search_stats {
+                search_stats.add_filter_checks(filter_checks);
+            }
+            matched_points
+        } else {
+            let points_iterator_ref = self.id_tracker.borrow();
+            let struct_filtered_context = self.struct_filtered_context(query);
+
+            // CPU-optimized strategy here: points are made unique before applying other filters.
+            // TODO: Implement iterator which holds the `visited_pool` and borrowed `vector_storage_ref` to prevent `preselected` array creation
+            let mut visited_list = self
+                .visited_pool
+                .get(points_iterator_ref.total_point_count());
+
+            let mut filter_checks = 0;
+            let preselected: Vec<PointOffsetType> = query_cardinality
+                .primary_clauses
+                .iter()
+                .flat_map(|clause| {
+                    match clause {
+                        PrimaryCondition::Condition(field_condition) => {
+                            self.query_field(field_condition).unwrap_or_else(
+                                || points_iterator_ref.iter_ids(), /* index is not built */
+                            )
+                        }
+                        PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
+                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
+                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
+                    }
+                })
+                .filter(|&id| !visited_list.check_and_update_visited(id))
+                .filter(|&i| {
+                    filter_checks += 1;
+                    struct_filtered_context.check(i)
+                })
+                .collect();
+            if let Some(search_stats) = search_stats {
+                search_stats.add_filter_checks(filter_checks);
+            }
+            preselected
+        }
+    }
 }
 
 impl PayloadIndex for StructPayloadIndex {
@@ -395,50 +509,7 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn query_points(&self, query: &Filter) -> Vec<PointOffsetType> {
-        // Assume query is already estimated to be small enough so we can iterate over all matched ids
-
-        let query_cardinality = self.estimate_cardinality(query);
-
-        if query_cardinality.primary_clauses.is_empty() {
-            let id_tracker = self.id_tracker.borrow();
-            let full_scan_iterator = id_tracker.iter_ids();
-
-            let struct_filtered_context = self.struct_filtered_context(query);
-            // Worst case: query expected to return few matches, but index can't be used
-            let matched_points =
-                full_scan_iterator.filter(move |i| struct_filtered_context.check(*i));
-
-            matched_points.collect()
-        } else {
-            let points_iterator_ref = self.id_tracker.borrow();
-            let struct_filtered_context = self.struct_filtered_context(query);
-
-            // CPU-optimized strategy here: points are made unique before applying other filters.
-            // TODO: Implement iterator which holds the `visited_pool` and borrowed `vector_storage_ref` to prevent `preselected` array creation
-            let mut visited_list = self
-                .visited_pool
-                .get(points_iterator_ref.total_point_count());
-
-            let preselected: Vec<PointOffsetType> = query_cardinality
-                .primary_clauses
-                .iter()
-                .flat_map(|clause| {
-                    match clause {
-                        PrimaryCondition::Condition(field_condition) => {
-                            self.query_field(field_condition).unwrap_or_else(
-                                || points_iterator_ref.iter_ids(), /* index is not built */
-                            )
-                        }
-                        PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
-                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
-                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
-                    }
-                })
-                .filter(|&id| !visited_list.check_and_update_visited(id))
-                .filter(move |&i| struct_filtered_context.check(i))
-                .collect();
-            preselected
-        }
+        self.query_points_with_stats(query, None)
     }
 
     fn indexed_points(&self, field: PayloadKeyTypeRef) -> usize {
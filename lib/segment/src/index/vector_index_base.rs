@@ -17,7 +17,7 @@ use super::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::common::operation_error::OperationResult;
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, VectorRef};
-use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::telemetry::{HnswGraphTelemetry, VectorIndexSearchesTelemetry};
 use crate::types::{Filter, SearchParams};
 
 /// Trait for vector searching
@@ -90,6 +90,62 @@ impl VectorIndexEnum {
         }
     }
 
+    /// Links of the point on each level of the HNSW graph, starting from the bottom one.
+    /// None, if the vectors are not indexed with HNSW, or the point is not in the graph.
+    pub fn hnsw_point_links(&self, point_id: PointOffsetType) -> Option<Vec<Vec<PointOffsetType>>> {
+        match self {
+            Self::HnswRam(index) => index.point_links(point_id),
+            Self::HnswMmap(index) => index.point_links(point_id),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseImmutableRam(_)
+            | Self::SparseMmap(_)
+            | Self::SparseCompressedImmutableRamF32(_)
+            | Self::SparseCompressedImmutableRamF16(_)
+            | Self::SparseCompressedImmutableRamU8(_)
+            | Self::SparseCompressedMmapF32(_)
+            | Self::SparseCompressedMmapF16(_)
+            | Self::SparseCompressedMmapU8(_) => None,
+        }
+    }
+
+    /// Compute the structural statistics of the HNSW graph, if the vectors are indexed with HNSW
+    pub fn compute_hnsw_graph_stats(&self) {
+        match self {
+            Self::HnswRam(index) => index.compute_graph_stats(),
+            Self::HnswMmap(index) => index.compute_graph_stats(),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseImmutableRam(_)
+            | Self::SparseMmap(_)
+            | Self::SparseCompressedImmutableRamF32(_)
+            | Self::SparseCompressedImmutableRamF16(_)
+            | Self::SparseCompressedImmutableRamU8(_)
+            | Self::SparseCompressedMmapF32(_)
+            | Self::SparseCompressedMmapF16(_)
+            | Self::SparseCompressedMmapU8(_) => {}
+        }
+    }
+
+    /// Structure of the HNSW graph. None, if the vectors are not indexed with HNSW, or the
+    /// statistics of the graph are not computed yet.
+    pub fn hnsw_graph_telemetry(&self) -> Option<HnswGraphTelemetry> {
+        match self {
+            Self::HnswRam(index) => index.graph_telemetry(),
+            Self::HnswMmap(index) => index.graph_telemetry(),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseImmutableRam(_)
+            | Self::SparseMmap(_)
+            | Self::SparseCompressedImmutableRamF32(_)
+            | Self::SparseCompressedImmutableRamF16(_)
+            | Self::SparseCompressedImmutableRamU8(_)
+            | Self::SparseCompressedMmapF32(_)
+            | Self::SparseCompressedMmapF16(_)
+            | Self::SparseCompressedMmapU8(_) => None,
+        }
+    }
+
     pub fn fill_idf_statistics(&self, idf: &mut HashMap<DimId, usize>) {
         match self {
             Self::Plain(_) | Self::HnswRam(_) | Self::HnswMmap(_) => (),
@@ -4,11 +4,15 @@ use common::defaults::POOL_KEEP_LIMIT;
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
 
+use crate::index::search_pools::SearchPools;
+
 /// Visited list handle is an owner of the `VisitedList`, which is returned by `VisitedPool` and returned back to it
 #[derive(Debug)]
 pub struct VisitedListHandle<'a> {
     pool: &'a VisitedPool,
     visited_list: VisitedList,
+    /// Maximal number of lists in the pool, for this list to be returned to it
+    keep_limit: usize,
 }
 
 /// Visited list reuses same memory to keep track of visited points ids among multiple consequent queries
@@ -42,15 +46,16 @@ impl VisitedList {
 impl<'a> Drop for VisitedListHandle<'a> {
     fn drop(&mut self) {
         self.pool
-            .return_back(std::mem::take(&mut self.visited_list));
+            .return_back(std::mem::take(&mut self.visited_list), self.keep_limit);
     }
 }
 
 impl<'a> VisitedListHandle<'a> {
-    fn new(pool: &'a VisitedPool, data: VisitedList) -> Self {
+    fn new(pool: &'a VisitedPool, data: VisitedList, keep_limit: usize) -> Self {
         VisitedListHandle {
             pool,
             visited_list: data,
+            keep_limit,
         }
     }
 
@@ -108,22 +113,30 @@ impl VisitedPool {
     }
 
     pub fn get(&self, num_points: usize) -> VisitedListHandle {
+        self.get_pooled(num_points, &SearchPools::default())
+    }
+
+    /// Get a list with the pooling settings of a search, which counts whether it was reused
+    pub fn get_pooled(&self, num_points: usize, pools: &SearchPools) -> VisitedListHandle {
         // If there are more concurrent requests, a new temporary list is created dynamically.
         // This limit is implemented to prevent memory leakage.
-        match self.pool.write().pop() {
-            None => VisitedListHandle::new(self, VisitedList::new(num_points)),
+        let keep_limit = pools.visited_pool_size();
+        let pooled = self.pool.write().pop();
+        pools.count_visited_list(pooled.is_some());
+        match pooled {
+            None => VisitedListHandle::new(self, VisitedList::new(num_points), keep_limit),
             Some(mut data) => {
                 data.visit_counters.resize(num_points, 0);
-                let mut visited_list = VisitedListHandle::new(self, data);
+                let mut visited_list = VisitedListHandle::new(self, data, keep_limit);
                 visited_list.next_iteration();
                 visited_list
             }
         }
     }
 
-    fn return_back(&self, data: VisitedList) {
+    fn return_back(&self, data: VisitedList, keep_limit: usize) {
         let mut pool = self.pool.write();
-        if pool.len() < *POOL_KEEP_LIMIT {
+        if pool.len() < keep_limit {
             pool.push(data);
         }
     }
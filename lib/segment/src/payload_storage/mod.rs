@@ -4,6 +4,7 @@ pub mod in_memory_payload_storage;
 #[cfg(feature = "testing")]
 pub mod in_memory_payload_storage_impl;
 pub mod on_disk_payload_storage;
+pub mod payload_compression;
 mod payload_storage_base;
 pub mod payload_storage_enum;
 pub mod query_checker;
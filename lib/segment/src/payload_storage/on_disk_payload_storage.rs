@@ -5,23 +5,31 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 
-use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
 use crate::common::Flusher;
 use crate::json_path::JsonPath;
+use crate::payload_storage::payload_compression::PayloadCodec;
 use crate::payload_storage::PayloadStorage;
-use crate::types::Payload;
+use crate::types::{Payload, PayloadCompressionConfig};
 
 /// On-disk implementation of `PayloadStorage`.
 /// Persists all changes to disk using `store`, does not keep payload in memory
 pub struct OnDiskPayloadStorage {
     db_wrapper: DatabaseColumnWrapper,
+    codec: PayloadCodec,
 }
 
 impl OnDiskPayloadStorage {
-    pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
+    pub fn open(
+        database: Arc<RwLock<DB>>,
+        compression: Option<PayloadCompressionConfig>,
+    ) -> OperationResult<Self> {
         let db_wrapper = DatabaseColumnWrapper::new(database, DB_PAYLOAD_CF);
-        Ok(OnDiskPayloadStorage { db_wrapper })
+        Ok(OnDiskPayloadStorage {
+            db_wrapper,
+            codec: PayloadCodec::new(compression),
+        })
     }
 
     pub fn remove_from_storage(&self, point_id: PointOffsetType) -> OperationResult<()> {
@@ -36,16 +44,15 @@ impl OnDiskPayloadStorage {
     ) -> OperationResult<()> {
         self.db_wrapper.put(
             serde_cbor::to_vec(&point_id).unwrap(),
-            serde_cbor::to_vec(payload).unwrap(),
+            self.codec.encode(payload)?,
         )
     }
 
     pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
         let key = serde_cbor::to_vec(&point_id).unwrap();
         self.db_wrapper
-            .get_pinned(&key, |raw| serde_cbor::from_slice(raw))?
+            .get_pinned(&key, PayloadCodec::decode)?
             .transpose()
-            .map_err(OperationError::from)
     }
 
     pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
@@ -53,10 +60,8 @@ impl OnDiskPayloadStorage {
         F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
     {
         for (key, val) in self.db_wrapper.lock_db().iter()? {
-            let do_continue = callback(
-                serde_cbor::from_slice(&key)?,
-                &serde_cbor::from_slice(&val)?,
-            )?;
+            let do_continue =
+                callback(serde_cbor::from_slice(&key)?, &PayloadCodec::decode(&val)?)?;
             if !do_continue {
                 return Ok(());
             }
@@ -0,0 +1,165 @@
+//! Compression of payload fields in storage.
+//!
+//! Large text fields, like the text of document chunks, take most of the space of a stored payload.
+//! Fields configured for compression are compressed together with zstd, the other fields of the
+//! point are stored as before. Payloads stored without compression stay readable, so compression
+//! can be enabled on segments with existing data.
+//!
+//! Layout of a payload with compressed fields:
+//! marker byte | length of the uncompressed fields (u32, LE) | uncompressed fields (CBOR) |
+//! compressed fields (zstd frame of CBOR)
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::types::{Payload, PayloadCompressionConfig};
+
+/// First byte of a payload with compressed fields.
+///
+/// CBOR never starts an item with the "break" byte, so it can't be mistaken for a payload stored
+/// without compression.
+const COMPRESSED_PAYLOAD_MARKER: u8 = 0xff;
+
+const UNCOMPRESSED_LENGTH_SIZE: usize = std::mem::size_of::<u32>();
+
+pub const DEFAULT_PAYLOAD_COMPRESSION_LEVEL: i32 = 3;
+
+pub const DEFAULT_PAYLOAD_COMPRESSION_MIN_SIZE: usize = 128;
+
+/// Encodes payloads for storage, compressing the configured fields
+#[derive(Debug, Default, Clone)]
+pub struct PayloadCodec {
+    compression: Option<PayloadCompressionConfig>,
+}
+
+impl PayloadCodec {
+    pub fn new(compression: Option<PayloadCompressionConfig>) -> Self {
+        Self { compression }
+    }
+
+    pub fn encode(&self, payload: &Payload) -> OperationResult<Vec<u8>> {
+        let Some(compression) = &self.compression else {
+            return Ok(serde_cbor::to_vec(payload)?);
+        };
+
+        let (compressed, uncompressed): (BTreeMap<&str, &Value>, BTreeMap<&str, &Value>) = payload
+            .0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+            .partition(|(key, _)| compression.fields.iter().any(|field| field == key));
+
+        if compressed.is_empty() {
+            return Ok(serde_cbor::to_vec(payload)?);
+        }
+
+        let compressed = serde_cbor::to_vec(&compressed)?;
+        let min_size = compression
+            .min_size
+            .unwrap_or(DEFAULT_PAYLOAD_COMPRESSION_MIN_SIZE);
+        if compressed.len() < min_size {
+            return Ok(serde_cbor::to_vec(payload)?);
+        }
+
+        let level = compression
+            .level
+            .unwrap_or(DEFAULT_PAYLOAD_COMPRESSION_LEVEL);
+        let compressed = zstd::bulk::compress(&compressed, level)?;
+        let uncompressed = serde_cbor::to_vec(&uncompressed)?;
+
+        let mut encoded = Vec::with_capacity(
+            1 + UNCOMPRESSED_LENGTH_SIZE + uncompressed.len() + compressed.len(),
+        );
+        encoded.push(COMPRESSED_PAYLOAD_MARKER);
+        encoded.extend_from_slice(&(uncompressed.len() as u32).to_le_bytes());
+        encoded.extend_from_slice(&uncompressed);
+        encoded.extend_from_slice(&compressed);
+        Ok(encoded)
+    }
+
+    /// Decode a stored payload, with or without compressed fields
+    pub fn decode(raw: &[u8]) -> OperationResult<Payload> {
+        let Some((&COMPRESSED_PAYLOAD_MARKER, encoded)) = raw.split_first() else {
+            return Ok(serde_cbor::from_slice(raw)?);
+        };
+
+        let corrupted = || OperationError::service_error("Compressed payload is corrupted");
+
+        if encoded.len() < UNCOMPRESSED_LENGTH_SIZE {
+            return Err(corrupted());
+        }
+        let (length, encoded) = encoded.split_at(UNCOMPRESSED_LENGTH_SIZE);
+        let length = u32::from_le_bytes(length.try_into().unwrap()) as usize;
+        if encoded.len() < length {
+            return Err(corrupted());
+        }
+        let (uncompressed, compressed) = encoded.split_at(length);
+
+        let mut payload: Payload = serde_cbor::from_slice(uncompressed)?;
+        let compressed: Map<String, Value> =
+            serde_cbor::from_slice(&zstd::decode_all(compressed)?)?;
+        payload.0.extend(compressed);
+        Ok(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn chunk_payload() -> Payload {
+        let text = "Qdrant is a vector similarity search engine. ".repeat(20);
+        serde_json::from_value(json!({
+            "text": text,
+            "title": "About",
+            "page": 3,
+        }))
+        .unwrap()
+    }
+
+    fn codec(fields: &[&str], min_size: Option<usize>) -> PayloadCodec {
+        PayloadCodec::new(Some(PayloadCompressionConfig {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            level: None,
+            min_size,
+        }))
+    }
+
+    #[test]
+    fn test_compressed_payload_roundtrip() {
+        let payload = chunk_payload();
+
+        let plain = PayloadCodec::default().encode(&payload).unwrap();
+        let compressed = codec(&["text"], None).encode(&payload).unwrap();
+
+        assert_eq!(compressed[0], COMPRESSED_PAYLOAD_MARKER);
+        assert!(compressed.len() < plain.len() / 2);
+        assert_eq!(PayloadCodec::decode(&compressed).unwrap(), payload);
+        assert_eq!(PayloadCodec::decode(&plain).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_small_or_missing_fields_are_not_compressed() {
+        let payload = chunk_payload();
+
+        // Only the configured fields are compressed, and only if large enough
+        for codec in [codec(&["title"], None), codec(&["missing"], Some(0))] {
+            let encoded = codec.encode(&payload).unwrap();
+            assert_eq!(encoded, serde_cbor::to_vec(&payload).unwrap());
+        }
+
+        let encoded = codec(&["title", "page"], Some(0)).encode(&payload).unwrap();
+        assert_eq!(encoded[0], COMPRESSED_PAYLOAD_MARKER);
+        assert_eq!(PayloadCodec::decode(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_corrupted_compressed_payload() {
+        let encoded = codec(&["text"], None).encode(&chunk_payload()).unwrap();
+        assert!(PayloadCodec::decode(&encoded[..3]).is_err());
+        assert!(PayloadCodec::decode(&encoded[..encoded.len() - 8]).is_err());
+    }
+}
@@ -130,14 +130,14 @@ mod tests {
     use super::*;
     use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
     use crate::json_path::path;
-    use crate::types::Payload;
+    use crate::types::{Payload, PayloadCompressionConfig};
 
     #[test]
     fn test_storage() {
         let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
         let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
 
-        let mut storage: PayloadStorageEnum = SimplePayloadStorage::open(db).unwrap().into();
+        let mut storage: PayloadStorageEnum = SimplePayloadStorage::open(db, None).unwrap().into();
         let payload: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
         storage.assign(100, &payload).unwrap();
         storage.wipe().unwrap();
@@ -156,7 +156,7 @@ mod tests {
 
         {
             let mut storage: PayloadStorageEnum =
-                SimplePayloadStorage::open(db.clone()).unwrap().into();
+                SimplePayloadStorage::open(db.clone(), None).unwrap().into();
             let payload: Payload = serde_json::from_str(
                 r#"{
                 "name": "John Doe",
@@ -187,7 +187,8 @@ mod tests {
         }
 
         {
-            let mut storage: PayloadStorageEnum = OnDiskPayloadStorage::open(db).unwrap().into();
+            let mut storage: PayloadStorageEnum =
+                OnDiskPayloadStorage::open(db, None).unwrap().into();
 
             let res = storage.payload(100).unwrap();
 
@@ -213,4 +214,49 @@ mod tests {
             eprintln!("res = {res:#?}");
         }
     }
+
+    #[test]
+    fn test_compressed_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let compression = PayloadCompressionConfig {
+            fields: vec!["text".to_string()],
+            level: None,
+            min_size: Some(0),
+        };
+
+        let payload: Payload = serde_json::from_str(
+            r#"{ "text": "Qdrant is a vector similarity search engine", "page": 1 }"#,
+        )
+        .unwrap();
+
+        {
+            let mut storage: PayloadStorageEnum =
+                SimplePayloadStorage::open(db.clone(), Some(compression.clone()))
+                    .unwrap()
+                    .into();
+            storage.assign_all(100, &payload).unwrap();
+        }
+
+        // Compressed fields are read regardless of the configured compression
+        {
+            let storage: PayloadStorageEnum =
+                SimplePayloadStorage::open(db.clone(), None).unwrap().into();
+            assert_eq!(storage.payload(100).unwrap(), payload);
+        }
+
+        {
+            let mut storage: PayloadStorageEnum = OnDiskPayloadStorage::open(db, Some(compression))
+                .unwrap()
+                .into();
+            assert_eq!(storage.payload(100).unwrap(), payload);
+
+            let partial_payload: Payload = serde_json::from_str(r#"{ "page": 2 }"#).unwrap();
+            storage.assign(100, &partial_payload).unwrap();
+
+            let res = storage.payload(100).unwrap();
+            assert_eq!(res.0.get("text"), payload.0.get("text"));
+            assert_eq!(res.0.get("page"), partial_payload.0.get("page"));
+        }
+    }
 }
@@ -0,0 +1,158 @@
+use std::any::TypeId;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use issues::{Code, Issue, Solution};
+use itertools::Itertools;
+
+use crate::telemetry::HnswGraphTelemetry;
+
+/// Max percentage of deleted points in a graph. Searches waste time on traversing them.
+const MAX_DELETED_PERCENT: f64 = 30.0;
+
+/// Min average number of links on the bottom level of a graph, in percents of the configured max
+const MIN_LINKS_PERCENT: f64 = 25.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HnswGraphProblem {
+    Disconnected,
+    FewLinks,
+    ManyDeleted,
+}
+
+impl HnswGraphProblem {
+    /// Problems of the graph, which degrade the quality or speed of searches
+    pub fn of(graph: &HnswGraphTelemetry) -> Vec<Self> {
+        let mut problems = Vec::new();
+
+        // Without the main graph, the links only come from payload subgraphs
+        let main_graph = graph
+            .levels
+            .first()
+            .filter(|bottom| bottom.max_out_degree > 0);
+
+        if let Some(bottom) = main_graph {
+            if graph.components > 1 {
+                problems.push(Self::Disconnected);
+            }
+            if bottom.avg_out_degree * 100.0 < bottom.max_out_degree as f64 * MIN_LINKS_PERCENT {
+                problems.push(Self::FewLinks);
+            }
+        }
+
+        if graph.deleted_fraction * 100.0 > MAX_DELETED_PERCENT {
+            problems.push(Self::ManyDeleted);
+        }
+
+        problems
+    }
+}
+
+impl fmt::Display for HnswGraphProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disconnected => {
+                write!(f, "the graph is disconnected, some points can't be found")
+            }
+            Self::FewLinks => write!(
+                f,
+                "points have less than {MIN_LINKS_PERCENT}% of the configured links on average",
+            ),
+            Self::ManyDeleted => write!(
+                f,
+                "more than {MAX_DELETED_PERCENT}% of the points are deleted",
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DegradedHnswGraph {
+    collection_name: String,
+    vector_name: String,
+    problems: BTreeSet<HnswGraphProblem>,
+    instance_id: String,
+}
+
+impl DegradedHnswGraph {
+    pub fn new(
+        collection_name: String,
+        vector_name: String,
+        problems: BTreeSet<HnswGraphProblem>,
+    ) -> Self {
+        let instance_id = Self::get_instance_id(&collection_name, &vector_name);
+        Self {
+            collection_name,
+            vector_name,
+            problems,
+            instance_id,
+        }
+    }
+
+    pub fn get_instance_id(collection_name: &str, vector_name: &str) -> String {
+        format!("{collection_name}/{vector_name}")
+    }
+
+    pub fn get_collection_name(code: &Code) -> &str {
+        debug_assert!(code.issue_type == TypeId::of::<Self>());
+        code.instance_id.split('/').next().unwrap_or("") // Code format is always the same
+    }
+}
+
+impl Issue for DegradedHnswGraph {
+    fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn name() -> &'static str {
+        "DEGRADED_HNSW_GRAPH"
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "HNSW graph of vector '{}' in collection '{}' is degraded: {}",
+            self.vector_name,
+            self.collection_name,
+            self.problems.iter().join("; "),
+        )
+    }
+
+    fn solution(&self) -> Solution {
+        Solution::Refactor(
+            "Rebuild the index by updating the HNSW config of the vector, or lower the `deleted_threshold` of the optimizers to vacuum deleted points sooner".to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::HnswLevelTelemetry;
+
+    fn graph(components: usize, avg_out_degree: f64, deleted_fraction: f64) -> HnswGraphTelemetry {
+        HnswGraphTelemetry {
+            vector_name: None,
+            levels: vec![HnswLevelTelemetry {
+                points: 1000,
+                avg_out_degree,
+                max_out_degree: 32,
+            }],
+            entry_points: 1,
+            components,
+            deleted_fraction,
+        }
+    }
+
+    #[test]
+    fn test_hnsw_graph_problems() {
+        assert!(HnswGraphProblem::of(&graph(1, 20.0, 0.1)).is_empty());
+        assert_eq!(
+            HnswGraphProblem::of(&graph(3, 20.0, 0.1)),
+            vec![HnswGraphProblem::Disconnected],
+        );
+        assert_eq!(
+            HnswGraphProblem::of(&graph(1, 4.0, 0.5)),
+            vec![HnswGraphProblem::FewLinks, HnswGraphProblem::ManyDeleted],
+        );
+    }
+}
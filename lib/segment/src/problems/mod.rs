@@ -1,3 +1,5 @@
+pub mod degraded_hnsw_graph;
 pub mod unindexed_field;
 
+pub use degraded_hnsw_graph::DegradedHnswGraph;
 pub use unindexed_field::UnindexedField;
@@ -1,12 +1,12 @@
 use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
-use std::sync::OnceLock;
 use std::time::Duration;
 
 use http::header::CONTENT_TYPE;
 use http::{HeaderMap, HeaderValue, Method, Uri};
 use issues::{Action, Code, ImmediateSolution, Issue, Solution};
 use itertools::Itertools;
+use parking_lot::RwLock;
 use strum::IntoEnumIterator as _;
 
 use crate::common::operation_error::OperationError;
@@ -25,14 +25,22 @@ pub struct UnindexedField {
     instance_id: String,
 }
 
-/// Don't use this directly, use `UnindexedField::slow_query_threshold()` instead
-pub static SLOW_QUERY_THRESHOLD: OnceLock<Duration> = OnceLock::new();
+/// Don't use this directly, use `UnindexedField::slow_query_threshold()` instead.
+/// Can be changed at runtime, when the configuration is reloaded.
+static SLOW_QUERY_THRESHOLD: RwLock<Option<Duration>> = RwLock::new(None);
 
 impl UnindexedField {
     const DEFAULT_SLOW_QUERY_SECS: f32 = 1.2;
 
     pub fn slow_query_threshold() -> Duration {
-        *SLOW_QUERY_THRESHOLD.get_or_init(|| Duration::from_secs_f32(Self::DEFAULT_SLOW_QUERY_SECS))
+        SLOW_QUERY_THRESHOLD
+            .read()
+            .unwrap_or_else(|| Duration::from_secs_f32(Self::DEFAULT_SLOW_QUERY_SECS))
+    }
+
+    /// Set the threshold of slow queries, `None` restores the default one
+    pub fn set_slow_query_threshold(threshold: Option<Duration>) {
+        *SLOW_QUERY_THRESHOLD.write() = threshold;
     }
 
     pub fn get_instance_id(collection_name: &str, field_name: &JsonPathV2) -> String {
@@ -99,6 +107,17 @@ impl UnindexedField {
             issue.submit();
         }
     }
+
+    /// Keys of the fields used in the filter, which have no index suitable for their conditions
+    pub fn unindexed_keys(
+        filter: &Filter,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> Vec<PayloadKeyType> {
+        Extractor::new(filter, payload_schema, String::new())
+            .unindexed_schema
+            .into_keys()
+            .collect()
+    }
 }
 
 impl Issue for UnindexedField {
@@ -208,6 +227,7 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
                     min_token_len: None,
                     max_token_len: None,
                     lowercase: None,
+                    custom_tokenizer: None,
                 }))
             }
             Match::Any(match_any) => infer_schema_from_any_variants(&match_any.any),
@@ -7,27 +7,31 @@ use std::thread::{self, JoinHandle};
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitVec;
-use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset, TelemetryDetail};
 use io::file_operations::{atomic_save_json, read_json};
 use io::storage_version::{StorageVersion, VERSION_FILE};
 use itertools::Either;
 use memory::mmap_ops;
 use parking_lot::{Mutex, RwLock};
 use rocksdb::DB;
+use semver::Version;
 use tar::Builder;
 use uuid::Uuid;
 
+use crate::common::hyperloglog::HyperLogLog;
 use crate::common::operation_error::OperationError::TypeInferenceError;
 use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
 };
+use crate::common::sampling::sample_key;
 use crate::common::validate_snapshot_archive::open_snapshot_archive_with_validation;
 use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
-use crate::data_types::vectors::{QueryVector, Vector};
+use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::entry::entry_point::SegmentEntry;
+use crate::id_tracker::point_mappings::PointMappingsHandle;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::numeric_index::StreamRange;
 use crate::index::field_index::CardinalityEstimation;
@@ -35,7 +39,7 @@ use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
-use crate::telemetry::SegmentTelemetry;
+use crate::telemetry::{HnswGraphTelemetry, SegmentTelemetry};
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
     PayloadSchemaType, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
@@ -44,10 +48,20 @@ use crate::types::{
 use crate::utils;
 use crate::utils::fs::find_symlink;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::vector_norms::{dense_vector_norm, query_vector_norm, VectorNorms};
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 pub const SEGMENT_STATE_FILE: &str = "segment.json";
 
+/// Marks a segment with data written by an older version, and holds that version
+pub const OUTDATED_FORMAT_FILE: &str = "outdated_format.info";
+
+/// Segment version, which last changed the format of the data files
+///
+/// Segments of later versions are migrated without marking them as outdated, their data files
+/// are the same as written by the current version.
+pub const SEGMENT_FORMAT_VERSION: Version = Version::new(0, 6, 0);
+
 const SNAPSHOT_PATH: &str = "snapshot";
 
 // Sub-directories of `SNAPSHOT_PATH`:
@@ -79,7 +93,12 @@ pub struct Segment {
     pub current_path: PathBuf,
     /// Component for mapping external ids to internal and also keeping track of point versions
     pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    /// Mapping snapshots published by the ID tracker, to look up internal ids without borrowing
+    /// the tracker. None if internal ids are looked up in the tracker itself.
+    pub point_mappings: Option<PointMappingsHandle>,
     pub vector_data: HashMap<String, VectorData>,
+    /// Norms of the vectors which are normalized on insertion
+    pub vector_norms: VectorNorms,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
@@ -194,6 +213,46 @@ impl Segment {
         Ok(new_index)
     }
 
+    /// Norms of the given vectors which are normalized on insertion, taken before preprocessing
+    fn tracked_vector_norms(&self, vectors: &NamedVectors) -> HashMap<String, ScoreType> {
+        vectors
+            .iter()
+            .filter_map(|(vector_name, vector)| {
+                let config = self.segment_config.vector_data.get(vector_name)?;
+                match vector {
+                    VectorRef::Dense(dense) if VectorNorms::is_tracked(config) => {
+                        Some((vector_name.to_owned(), dense_vector_norm(dense)))
+                    }
+                    VectorRef::Dense(_) | VectorRef::Sparse(_) | VectorRef::MultiDense(_) => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Store the norms of the point's vectors
+    ///
+    /// If `replace_all`, the norms of tracked vectors not given are removed, as their vectors are.
+    fn store_vector_norms(
+        &self,
+        internal_id: PointOffsetType,
+        norms: &HashMap<String, ScoreType>,
+        replace_all: bool,
+    ) -> OperationResult<()> {
+        for (vector_name, config) in &self.segment_config.vector_data {
+            if !VectorNorms::is_tracked(config) {
+                continue;
+            }
+            match norms.get(vector_name) {
+                Some(&norm) => self
+                    .vector_norms
+                    .set(vector_name, internal_id, Some(norm))?,
+                None if replace_all => self.vector_norms.set(vector_name, internal_id, None)?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
     /// Operation wrapped, which handles previous and new errors in the segment, automatically
     /// updates versions and skips operations if the segment version is too old
     ///
@@ -385,7 +444,29 @@ impl Segment {
     }
 
     pub fn get_internal_id(&self, point_id: PointIdType) -> Option<PointOffsetType> {
-        self.id_tracker.borrow().internal_id(point_id)
+        self.with_internal_ids(|internal_id| internal_id(point_id))
+    }
+
+    /// Look up internal ids of points with the given function.
+    ///
+    /// If the segment uses mapping snapshots, ids are looked up in the published snapshot, which
+    /// concurrent readers share without contention. Without a published snapshot, a new one is
+    /// published while the ID tracker is borrowed, so that no change of the mapping is missed.
+    fn with_internal_ids<T>(
+        &self,
+        lookup: impl FnOnce(&dyn Fn(PointIdType) -> Option<PointOffsetType>) -> T,
+    ) -> T {
+        if let Some(point_mappings) = &self.point_mappings {
+            if let Some(snapshot) = point_mappings.load().as_ref() {
+                return lookup(&|point_id| snapshot.internal_id(point_id));
+            }
+            if let Some(snapshot) = self.id_tracker.borrow().publish_point_mappings() {
+                return lookup(&|point_id| snapshot.internal_id(point_id));
+            }
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        lookup(&|point_id| id_tracker.internal_id(point_id))
     }
 
     pub fn get_deleted_points_bitvec(&self) -> BitVec {
@@ -393,7 +474,7 @@ impl Segment {
     }
 
     fn lookup_internal_id(&self, point_id: PointIdType) -> OperationResult<PointOffsetType> {
-        let internal_id_opt = self.id_tracker.borrow().internal_id(point_id);
+        let internal_id_opt = self.get_internal_id(point_id);
         match internal_id_opt {
             Some(internal_id) => Ok(internal_id),
             None => Err(OperationError::PointIdError {
@@ -425,6 +506,41 @@ impl Segment {
         })
     }
 
+    /// Whether segments of the given stored version have data files in an outdated format
+    pub fn is_outdated_format(stored_version: &Version) -> bool {
+        stored_version < &SEGMENT_FORMAT_VERSION
+    }
+
+    /// Mark the data of the segment as written by the given, older version
+    ///
+    /// The oldest version is kept, if the segment was already marked by an earlier migration.
+    pub fn mark_outdated_format(current_path: &Path, version: &Version) -> OperationResult<()> {
+        let marker_path = current_path.join(OUTDATED_FORMAT_FILE);
+        if !marker_path.exists() {
+            fs::write(marker_path, version.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Version which wrote the data of the segment, if it is older than the current version
+    ///
+    /// Segments of older versions are migrated when loaded, but their data stays in the format of
+    /// that version until the segment is rewritten, e.g. by an optimizer.
+    pub fn outdated_format(&self) -> Option<Version> {
+        let marker_path = self.current_path.join(OUTDATED_FORMAT_FILE);
+        let version = fs::read_to_string(marker_path).ok()?;
+        match version.trim().parse() {
+            Ok(version) => Some(version),
+            Err(err) => {
+                log::warn!(
+                    "Can't parse outdated format version of segment {}: {err}",
+                    self.current_path.display(),
+                );
+                Some(Version::new(0, 0, 0))
+            }
+        }
+    }
+
     /// Retrieve vector by internal ID
     ///
     /// Returns None if the vector does not exists or deleted
@@ -583,11 +699,15 @@ impl Segment {
     }
 
     /// Converts raw ScoredPointOffset search result into ScoredPoint result
+    ///
+    /// With `raw_score_of` - the vector name and the norm of the query - points get the raw inner
+    /// product with the query, if the norm of their vector is known.
     fn process_search_result(
         &self,
         internal_result: &[ScoredPointOffset],
         with_payload: &WithPayload,
         with_vector: &WithVector,
+        raw_score_of: Option<(&str, ScoreType)>,
     ) -> OperationResult<Vec<ScoredPoint>> {
         let id_tracker = self.id_tracker.borrow();
         internal_result
@@ -641,6 +761,13 @@ impl Segment {
                         Some(result.into())
                     }
                 };
+                let raw_score = match raw_score_of {
+                    Some((vector_name, query_norm)) => self
+                        .vector_norms
+                        .get(vector_name, point_offset)?
+                        .map(|norm| scored_point_offset.score * query_norm * norm),
+                    None => None,
+                };
 
                 Ok(ScoredPoint {
                     id: point_id,
@@ -650,6 +777,9 @@ impl Segment {
                     vector,
                     shard_key: None,
                     order_value: None,
+                    prefetch_matches: None,
+                    routing: None,
+                    raw_score,
                 })
             })
             .collect()
@@ -1124,10 +1254,23 @@ impl SegmentEntry for Segment {
 
         check_stopped(&vector_query_context.is_stopped())?;
 
+        let with_raw_score = params.map_or(false, |params| params.with_raw_score)
+            && self
+                .segment_config
+                .vector_data
+                .get(vector_name)
+                .map_or(false, VectorNorms::is_tracked);
+
         let res = internal_results
             .iter()
-            .map(|internal_result| {
-                self.process_search_result(internal_result, with_payload, with_vector)
+            .zip(query_vectors)
+            .map(|(internal_result, query_vector)| {
+                let raw_score_of = if with_raw_score {
+                    query_vector_norm(query_vector).map(|norm| (vector_name, norm))
+                } else {
+                    None
+                };
+                self.process_search_result(internal_result, with_payload, with_vector, raw_score_of)
             })
             .collect();
 
@@ -1142,14 +1285,17 @@ impl SegmentEntry for Segment {
     ) -> OperationResult<bool> {
         debug_assert!(self.is_appendable());
         check_named_vectors(&vectors, &self.segment_config)?;
+        let norms = self.tracked_vector_norms(&vectors);
         vectors.preprocess(|name| self.config().vector_data.get(name).unwrap());
         let stored_internal_point = self.id_tracker.borrow().internal_id(point_id);
         self.handle_point_version_and_failure(op_num, stored_internal_point, |segment| {
             if let Some(existing_internal_id) = stored_internal_point {
                 segment.replace_all_vectors(existing_internal_id, vectors)?;
+                segment.store_vector_norms(existing_internal_id, &norms, true)?;
                 Ok((true, Some(existing_internal_id)))
             } else {
                 let new_index = segment.insert_new_vectors(point_id, vectors)?;
+                segment.store_vector_norms(new_index, &norms, true)?;
                 Ok((false, Some(new_index)))
             }
         })
@@ -1195,6 +1341,7 @@ impl SegmentEntry for Segment {
         mut vectors: NamedVectors,
     ) -> OperationResult<bool> {
         check_named_vectors(&vectors, &self.segment_config)?;
+        let norms = self.tracked_vector_norms(&vectors);
         vectors.preprocess(|name| self.config().vector_data.get(name).unwrap());
         let internal_id = self.id_tracker.borrow().internal_id(point_id);
         match internal_id {
@@ -1204,6 +1351,7 @@ impl SegmentEntry for Segment {
             Some(internal_id) => {
                 self.handle_point_version_and_failure(op_num, Some(internal_id), |segment| {
                     segment.update_vectors(internal_id, vectors)?;
+                    segment.store_vector_norms(internal_id, &norms, false)?;
                     Ok((true, Some(internal_id)))
                 })
             }
@@ -1231,6 +1379,10 @@ impl SegmentEntry for Segment {
                     )?;
                     let mut vector_storage = vector_data.vector_storage.borrow_mut();
                     let is_deleted = vector_storage.delete_vector(internal_id)?;
+                    let config = segment.segment_config.vector_data.get(vector_name);
+                    if config.map_or(false, VectorNorms::is_tracked) {
+                        segment.vector_norms.set(vector_name, internal_id, None)?;
+                    }
                     Ok((is_deleted, Some(internal_id)))
                 })
             }
@@ -1384,6 +1536,28 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
+    ) -> Vec<(u64, PointIdType)> {
+        let payload_index = self.payload_index.borrow();
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        let id_tracker = self.id_tracker.borrow();
+
+        let keys = id_tracker
+            .iter_from(None)
+            .filter(|(_, internal_id)| {
+                filter_context
+                    .as_ref()
+                    .map_or(true, |context| context.check(*internal_id))
+            })
+            .map(|(external_id, _)| (sample_key(external_id, seed), external_id));
+
+        peek_top_largest_iterable(keys, limit)
+    }
+
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let id_tracker = self.id_tracker.borrow();
         let iterator = id_tracker.iter_from(from).map(|x| x.0);
@@ -1394,7 +1568,17 @@ impl SegmentEntry for Segment {
     }
 
     fn has_point(&self, point_id: PointIdType) -> bool {
-        self.id_tracker.borrow().internal_id(point_id).is_some()
+        self.get_internal_id(point_id).is_some()
+    }
+
+    fn existing_points(&self, point_ids: &[PointIdType]) -> Vec<PointIdType> {
+        self.with_internal_ids(|internal_id| {
+            point_ids
+                .iter()
+                .copied()
+                .filter(|&point_id| internal_id(point_id).is_some())
+                .collect()
+        })
     }
 
     fn available_point_count(&self) -> usize {
@@ -1535,6 +1719,7 @@ impl SegmentEntry for Segment {
         let id_tracker_mapping_flusher = self.id_tracker.borrow().mapping_flusher();
         let payload_index_flusher = self.payload_index.borrow().flusher();
         let id_tracker_versions_flusher = self.id_tracker.borrow().versions_flusher();
+        let vector_norms_flusher = self.vector_norms.flusher();
         let persisted_version = self.persisted_version.clone();
 
         // Flush order is important:
@@ -1594,6 +1779,9 @@ impl SegmentEntry for Segment {
                     OperationError::service_error(format!("Failed to flush vector_storage: {err}"))
                 })?;
             }
+            vector_norms_flusher().map_err(|err| {
+                OperationError::service_error(format!("Failed to flush vector norms: {err}"))
+            })?;
             payload_index_flusher().map_err(|err| {
                 OperationError::service_error(format!("Failed to flush payload_index: {err}"))
             })?;
@@ -1687,6 +1875,46 @@ impl SegmentEntry for Segment {
         self.payload_index.borrow().indexed_fields()
     }
 
+    fn keyword_values(&self, key: PayloadKeyTypeRef, limit: usize) -> Option<HashSet<String>> {
+        self.payload_index.borrow().keyword_values(key, limit)
+    }
+
+    fn keyword_values_sketch(&self, key: PayloadKeyTypeRef) -> Option<HyperLogLog> {
+        self.payload_index.borrow().keyword_values_sketch(key)
+    }
+
+    fn hnsw_neighbors(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> OperationResult<Option<Vec<Vec<PointIdType>>>> {
+        check_vector_name(vector_name, &self.segment_config)?;
+        let Some(internal_id) = self.get_internal_id(point_id) else {
+            return Ok(None);
+        };
+        let vector_data = &self.vector_data[vector_name];
+        let Some(links) = vector_data
+            .vector_index
+            .borrow()
+            .hnsw_point_links(internal_id)
+        else {
+            return Ok(None);
+        };
+
+        // Neighbors deleted since the graph was built are skipped
+        let id_tracker = self.id_tracker.borrow();
+        let neighbors = links
+            .into_iter()
+            .map(|level_links| {
+                level_links
+                    .into_iter()
+                    .filter_map(|link| id_tracker.external_id(link))
+                    .collect()
+            })
+            .collect();
+        Ok(Some(neighbors))
+    }
+
     fn check_error(&self) -> Option<SegmentFailedState> {
         self.error_status.clone()
     }
@@ -1822,6 +2050,15 @@ impl SegmentEntry for Segment {
             &files.join(VERSION_FILE),
         )?;
 
+        let outdated_format_path = self.current_path.join(OUTDATED_FORMAT_FILE);
+        if outdated_format_path.exists() {
+            utils::tar::append_file(
+                &mut builder,
+                &outdated_format_path,
+                &files.join(OUTDATED_FORMAT_FILE),
+            )?;
+        }
+
         builder.finish()?;
 
         // remove tmp directory in background
@@ -1854,9 +2091,27 @@ impl SegmentEntry for Segment {
             config: self.config().clone(),
             vector_index_searches,
             payload_field_indices: self.payload_index.borrow().get_telemetry_data(),
+            hnsw_graphs: self.hnsw_graphs_telemetry(),
+        }
+    }
+
+    fn compute_hnsw_graph_stats(&self) {
+        for vector_data in self.vector_data.values() {
+            vector_data.vector_index.borrow().compute_hnsw_graph_stats();
         }
     }
 
+    fn hnsw_graphs_telemetry(&self) -> Vec<HnswGraphTelemetry> {
+        self.vector_data
+            .iter()
+            .filter_map(|(k, v)| {
+                let mut telemetry = v.vector_index.borrow().hnsw_graph_telemetry()?;
+                telemetry.vector_name = Some(k.clone());
+                Some(telemetry)
+            })
+            .collect()
+    }
+
     fn fill_query_context(&self, query_context: &mut QueryContext) {
         query_context.add_available_point_count(self.available_point_count());
 
@@ -1942,6 +2197,7 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -1987,6 +2243,74 @@ mod tests {
         assert_eq!(search_result, search_batch_result[0].clone())
     }
 
+    #[test]
+    fn test_raw_score() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Cosine,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+            payload_compression: None,
+        };
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+        segment
+            .upsert_point(100, 1.into(), only_default_vector(&[3.0, 4.0]))
+            .unwrap();
+        segment
+            .upsert_point(101, 2.into(), only_default_vector(&[0.0, 2.0]))
+            .unwrap();
+
+        let query_vector = [2.0, 0.0].into();
+        let search = |segment: &Segment, with_raw_score| {
+            let params = SearchParams {
+                with_raw_score,
+                ..Default::default()
+            };
+            segment
+                .search(
+                    DEFAULT_VECTOR_NAME,
+                    &query_vector,
+                    &WithPayload::default(),
+                    &false.into(),
+                    None,
+                    10,
+                    Some(&params),
+                )
+                .unwrap()
+        };
+
+        let result = search(&segment, false);
+        assert!(result.iter().all(|point| point.raw_score.is_none()));
+
+        let result = search(&segment, true);
+        assert_eq!(result[0].id, 1.into());
+        assert!((result[0].score - 0.6).abs() < 1e-5);
+        assert!((result[0].raw_score.unwrap() - 6.0).abs() < 1e-4);
+        assert_eq!(result[1].id, 2.into());
+        assert!(result[1].raw_score.unwrap().abs() < 1e-4);
+
+        // Norms are persisted with the segment
+        segment.flush(true).unwrap();
+        let segment_path = segment.current_path.clone();
+        drop(segment);
+        let segment = load_segment(&segment_path, &AtomicBool::new(false))
+            .unwrap()
+            .unwrap();
+        let result = search(&segment, true);
+        assert!((result[0].raw_score.unwrap() - 6.0).abs() < 1e-4);
+    }
+
     #[test]
     fn test_from_filter_attributes() {
         let data = r#"
@@ -2016,6 +2340,7 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
 
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -2080,6 +2405,56 @@ mod tests {
         assert!(results_with_invalid_filter.is_empty());
     }
 
+    #[test]
+    fn test_outdated_format() {
+        assert!(Segment::is_outdated_format(&Version::new(0, 5, 0)));
+        assert!(!Segment::is_outdated_format(&SEGMENT_FORMAT_VERSION));
+        assert!(!Segment::is_outdated_format(&Version::new(
+            SEGMENT_FORMAT_VERSION.major,
+            SEGMENT_FORMAT_VERSION.minor,
+            SEGMENT_FORMAT_VERSION.patch + 1,
+        )));
+
+        let segment_base_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+            payload_compression: None,
+        };
+
+        let segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
+        let segment_path = segment.current_path.clone();
+        assert_eq!(segment.outdated_format(), None);
+        drop(segment);
+
+        // Segment of the current format is not marked when loaded
+        let segment = load_segment(&segment_path, &AtomicBool::new(false))
+            .unwrap()
+            .unwrap();
+        assert_eq!(segment.outdated_format(), None);
+        drop(segment);
+
+        // Mark of an earlier migration stays until the segment is rewritten
+        Segment::mark_outdated_format(&segment_path, &Version::new(0, 5, 0)).unwrap();
+        Segment::mark_outdated_format(&segment_path, &Version::new(0, 5, 1)).unwrap();
+        let segment = load_segment(&segment_path, &AtomicBool::new(false))
+            .unwrap()
+            .unwrap();
+        assert_eq!(segment.outdated_format(), Some(Version::new(0, 5, 0)));
+    }
+
     #[test]
     fn test_snapshot() {
         let data = r#"
@@ -2108,6 +2483,7 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
 
         let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -2204,6 +2580,7 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
 
         let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -2238,6 +2615,7 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2333,6 +2711,7 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2401,6 +2780,7 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2518,6 +2898,7 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2679,6 +3060,7 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
         segment
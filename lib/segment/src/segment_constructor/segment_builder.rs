@@ -29,6 +29,7 @@ use crate::types::{
     PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState, SeqNumberType,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::vector_norms::VectorNorms;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 /// Structure for constructing segment out of several other segments
@@ -36,6 +37,7 @@ pub struct SegmentBuilder {
     version: SeqNumberType,
     id_tracker: IdTrackerEnum,
     payload_storage: PayloadStorageEnum,
+    vector_norms: VectorNorms,
     vector_storages: HashMap<String, VectorStorageEnum>,
     segment_config: SegmentConfig,
 
@@ -44,6 +46,8 @@ pub struct SegmentBuilder {
     // Path to the temporary segment directory
     temp_path: PathBuf,
     indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    // Directory to keep checkpoints of HNSW index builds in, to resume interrupted builds
+    hnsw_checkpoints_path: Option<PathBuf>,
 }
 
 impl SegmentBuilder {
@@ -64,6 +68,8 @@ impl SegmentBuilder {
 
         let payload_storage = create_payload_storage(database.clone(), segment_config)?;
 
+        let vector_norms = VectorNorms::open(database.clone());
+
         let mut vector_storages = HashMap::new();
 
         for (vector_name, vector_config) in &segment_config.vector_data {
@@ -95,15 +101,23 @@ impl SegmentBuilder {
             version: Default::default(), // default version is 0
             id_tracker,
             payload_storage,
+            vector_norms,
             vector_storages,
             segment_config: segment_config.clone(),
 
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            hnsw_checkpoints_path: None,
         })
     }
 
+    /// Keep checkpoints of the HNSW index builds in the given directory, so interrupted builds of
+    /// the same points can resume from them
+    pub fn set_hnsw_checkpoints_path(&mut self, path: PathBuf) {
+        self.hnsw_checkpoints_path = Some(path);
+    }
+
     pub fn remove_indexed_field(&mut self, field: &PayloadKeyType) {
         self.indexed_fields.remove(field);
     }
@@ -169,12 +183,27 @@ impl SegmentBuilder {
             }
         }
 
+        let tracked_norms: Vec<_> = self
+            .segment_config
+            .vector_data
+            .iter()
+            .filter(|(_, config)| VectorNorms::is_tracked(config))
+            .map(|(vector_name, _)| vector_name)
+            .collect();
+
         if let Some(new_internal_range) = new_internal_range {
             let internal_id_iter = new_internal_range.zip(other_id_tracker.iter_ids());
 
             for (new_internal_id, old_internal_id) in internal_id_iter {
                 check_process_stopped(stopped)?;
 
+                for vector_name in &tracked_norms {
+                    let norm = other.vector_norms.get(vector_name, old_internal_id)?;
+                    if norm.is_some() {
+                        self.vector_norms.set(vector_name, new_internal_id, norm)?;
+                    }
+                }
+
                 let external_id =
                     if let Some(external_id) = other_id_tracker.external_id(old_internal_id) {
                         external_id
@@ -253,16 +282,19 @@ impl SegmentBuilder {
                 version,
                 id_tracker,
                 payload_storage,
+                vector_norms,
                 mut vector_storages,
                 segment_config,
                 destination_path,
                 temp_path,
                 indexed_fields,
+                hnsw_checkpoints_path,
             } = self;
 
             let appendable_flag = segment_config.is_appendable();
 
             payload_storage.flusher()()?;
+            vector_norms.flusher()()?;
             let payload_storage_arc = Arc::new(AtomicRefCell::new(payload_storage));
 
             id_tracker.mapping_flusher()()?;
@@ -321,6 +353,7 @@ impl SegmentBuilder {
                     payload_index_arc.clone(),
                     quantized_vectors_arc,
                     Some(permit.clone()),
+                    hnsw_checkpoints_path.as_deref(),
                     stopped,
                 )?;
             }
@@ -56,6 +56,7 @@ use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::{
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
+use crate::vector_storage::vector_norms::VectorNorms;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 pub const PAYLOAD_INDEX_PATH: &str = "payload_index";
@@ -274,12 +275,14 @@ pub(crate) fn create_payload_storage(
     config: &SegmentConfig,
 ) -> OperationResult<PayloadStorageEnum> {
     let payload_storage = match config.payload_storage_type {
-        PayloadStorageType::InMemory => {
-            PayloadStorageEnum::from(SimplePayloadStorage::open(database)?)
-        }
-        PayloadStorageType::OnDisk => {
-            PayloadStorageEnum::from(OnDiskPayloadStorage::open(database)?)
-        }
+        PayloadStorageType::InMemory => PayloadStorageEnum::from(SimplePayloadStorage::open(
+            database,
+            config.payload_compression.clone(),
+        )?),
+        PayloadStorageType::OnDisk => PayloadStorageEnum::from(OnDiskPayloadStorage::open(
+            database,
+            config.payload_compression.clone(),
+        )?),
     };
     Ok(payload_storage)
 }
@@ -303,6 +306,7 @@ pub(crate) fn create_vector_index(
     payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
     permit: Option<Arc<CpuPermit>>,
+    hnsw_checkpoints_path: Option<&Path>,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorIndexEnum> {
     let vector_index = match &vector_config.index {
@@ -321,6 +325,7 @@ pub(crate) fn create_vector_index(
                 hnsw_config: vector_hnsw_config.clone(),
                 permit,
                 stopped,
+                checkpoints_path: hnsw_checkpoints_path,
             };
             if vector_hnsw_config.on_disk == Some(true) {
                 VectorIndexEnum::HnswMmap(HNSWIndex::open(args)?)
@@ -411,6 +416,12 @@ fn create_segment(
     let appendable_flag = config.is_appendable();
 
     let id_tracker = sp(create_id_tracker(database.clone())?);
+    // The mapping of non-appendable segments rarely changes, read it from published snapshots
+    let point_mappings = if appendable_flag {
+        None
+    } else {
+        id_tracker.borrow().point_mappings()
+    };
 
     let payload_index_path = get_payload_index_path(segment_path);
     let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
@@ -465,6 +476,7 @@ fn create_segment(
             payload_index.clone(),
             quantized_vectors.clone(),
             None,
+            None,
             stopped,
         )?);
 
@@ -533,7 +545,9 @@ fn create_segment(
         persisted_version: Arc::new(Mutex::new(version)),
         current_path: segment_path.to_owned(),
         id_tracker,
+        point_mappings,
         vector_data,
+        vector_norms: VectorNorms::open(database.clone()),
         segment_type,
         appendable_flag,
         payload_index,
@@ -592,6 +606,11 @@ pub fn load_segment(path: &Path, stopped: &AtomicBool) -> OperationResult<Option
             Segment::save_state(&segment_state, path)?;
         }
 
+        // Data files stay in the format of the stored version, until the segment is rewritten
+        if Segment::is_outdated_format(&stored_version) {
+            Segment::mark_outdated_format(path, &stored_version)?;
+        }
+
         SegmentVersion::save(path)?
     }
 
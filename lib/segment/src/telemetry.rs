@@ -4,8 +4,8 @@ use serde::Serialize;
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_time_statistics::OperationDurationStatistics;
 use crate::types::{
-    PayloadIndexInfo, SegmentConfig, SegmentInfo, SparseVectorDataConfig, VectorDataConfig,
-    VectorDataInfo,
+    PayloadCompressionConfig, PayloadIndexInfo, SegmentConfig, SegmentInfo, SparseVectorDataConfig,
+    VectorDataConfig, VectorDataInfo,
 };
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -14,6 +14,9 @@ pub struct SegmentTelemetry {
     pub config: SegmentConfig,
     pub vector_index_searches: Vec<VectorIndexSearchesTelemetry>,
     pub payload_field_indices: Vec<PayloadIndexTelemetry>,
+    /// Structure of the HNSW graphs, once they were inspected by the periodic check of graphs
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hnsw_graphs: Vec<HnswGraphTelemetry>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -64,6 +67,38 @@ pub struct VectorIndexSearchesTelemetry {
 
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub unfiltered_exact: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_auto_quantized: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_auto_original: OperationDurationStatistics,
+}
+
+/// Structure of the HNSW graph of a vector, to assess its quality
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct HnswGraphTelemetry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_name: Option<String>,
+    /// Statistics of each level of the graph, starting from the bottom one
+    pub levels: Vec<HnswLevelTelemetry>,
+    /// Number of the highest entry points of the graph
+    pub entry_points: usize,
+    /// Number of connected components of the bottom level. If there is more than one, some
+    /// points can't be reached by searches
+    pub components: usize,
+    /// Fraction of the points in the graph, which are deleted since the graph was built
+    pub deleted_fraction: f64,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct HnswLevelTelemetry {
+    /// Number of points on the level
+    pub points: usize,
+    /// Average number of links of a point on the level
+    pub avg_out_degree: f64,
+    /// Max number of links of a point on the level, as configured
+    pub max_out_degree: usize,
 }
 
 impl Anonymize for SegmentTelemetry {
@@ -73,6 +108,29 @@ impl Anonymize for SegmentTelemetry {
             config: self.config.anonymize(),
             vector_index_searches: self.vector_index_searches.anonymize(),
             payload_field_indices: self.payload_field_indices.anonymize(),
+            hnsw_graphs: self.hnsw_graphs.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for HnswGraphTelemetry {
+    fn anonymize(&self) -> Self {
+        Self {
+            vector_name: None,
+            levels: self.levels.anonymize(),
+            entry_points: self.entry_points,
+            components: self.components,
+            deleted_fraction: self.deleted_fraction,
+        }
+    }
+}
+
+impl Anonymize for HnswLevelTelemetry {
+    fn anonymize(&self) -> Self {
+        Self {
+            points: self.points.anonymize(),
+            avg_out_degree: self.avg_out_degree,
+            max_out_degree: self.max_out_degree,
         }
     }
 }
@@ -120,6 +178,17 @@ impl Anonymize for SegmentConfig {
             vector_data: self.vector_data.anonymize(),
             sparse_vector_data: self.sparse_vector_data.anonymize(),
             payload_storage_type: self.payload_storage_type,
+            payload_compression: self.payload_compression.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for PayloadCompressionConfig {
+    fn anonymize(&self) -> Self {
+        PayloadCompressionConfig {
+            fields: self.fields.anonymize(),
+            level: self.level,
+            min_size: self.min_size,
         }
     }
 }
@@ -159,6 +228,8 @@ impl Anonymize for VectorIndexSearchesTelemetry {
             filtered_exact: self.filtered_exact.anonymize(),
             filtered_sparse: self.filtered_sparse.anonymize(),
             unfiltered_exact: self.filtered_exact.anonymize(),
+            filtered_auto_quantized: self.filtered_auto_quantized.anonymize(),
+            filtered_auto_original: self.filtered_auto_original.anonymize(),
         }
     }
 }
@@ -28,11 +28,11 @@ use crate::common::utils::{self, MaybeOneOrMany, MultiValue};
 use crate::data_types::integer_index::IntegerIndexParams;
 use crate::data_types::order_by::OrderValue;
 use crate::data_types::text_index::TextIndexParams;
-use crate::data_types::vectors::VectorStructInternal;
+use crate::data_types::vectors::{VectorElementType, VectorStructInternal};
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::sparse_index::sparse_index_config::SparseIndexConfig;
 use crate::json_path::{JsonPath, JsonPathInterface};
-use crate::spaces::metric::MetricPostProcessing;
+use crate::spaces::metric::{Metric, MetricPostProcessing};
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::vector_storage::simple_sparse_vector_storage::SPARSE_VECTOR_DISTANCE;
 
@@ -176,6 +176,24 @@ impl Distance {
         }
     }
 
+    /// Similarity of the vectors with this metric, as scored in the vector storage, before
+    /// [`Self::postprocess_score`]. The vectors are preprocessed first, e.g. normalized for cosine.
+    pub fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        fn similarity<M: Metric<VectorElementType>>(
+            v1: &[VectorElementType],
+            v2: &[VectorElementType],
+        ) -> ScoreType {
+            M::similarity(&M::preprocess(v1.to_vec()), &M::preprocess(v2.to_vec()))
+        }
+
+        match self {
+            Distance::Cosine => similarity::<CosineMetric>(v1, v2),
+            Distance::Euclid => similarity::<EuclidMetric>(v1, v2),
+            Distance::Dot => similarity::<DotProductMetric>(v1, v2),
+            Distance::Manhattan => similarity::<ManhattanMetric>(v1, v2),
+        }
+    }
+
     pub fn distance_order(&self) -> Order {
         match self {
             Distance::Cosine | Distance::Dot => Order::LargeBetter,
@@ -215,6 +233,78 @@ pub struct ScoredPoint {
     pub shard_key: Option<ShardKey>,
     /// Order-by value
     pub order_value: Option<OrderValue>,
+    /// Results of the fused prefetches this point was found in
+    pub prefetch_matches: Option<Vec<PrefetchMatch>>,
+    /// Where the point was found, if requested
+    pub routing: Option<PointRouting>,
+    /// Inner product with the query before normalization, if requested for a cosine vector
+    pub raw_score: Option<ScoreType>,
+}
+
+/// Position of a point in the results of one of the fused prefetches
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+pub struct PrefetchMatch {
+    /// Index of the prefetch in the request
+    pub prefetch: usize,
+    /// Position of the point in the results of the prefetch, starting from 0
+    pub rank: usize,
+    /// Score of the point in the results of the prefetch
+    pub score: ScoreType,
+}
+
+/// Shard and replica a point was found in
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct PointRouting {
+    /// Shard key of the shard, if the collection uses custom sharding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Id of the shard
+    pub shard_id: u32,
+    /// Id of the peer of the replica which returned the point
+    pub peer_id: u64,
+}
+
+/// Work done to execute a query, summed over the searched segments of all queried replicas
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct QueryStats {
+    /// Number of vector scores computed, including rescoring with original vectors
+    pub vectors_scored: usize,
+    /// Number of points checked against the filter one by one
+    pub filter_checks: usize,
+    /// Number of segment searches, which scored the points without the HNSW graph
+    pub plain_searches: usize,
+    /// Number of segment searches, which traversed the HNSW graph
+    pub hnsw_searches: usize,
+    /// Number of segment searches of sparse vectors
+    pub sparse_searches: usize,
+    /// How each of the searched segments was searched
+    #[serde(default)]
+    pub segments: Vec<SegmentSearchStats>,
+}
+
+/// Searches of a segment done in the same mode
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct SegmentSearchStats {
+    /// Id of the segment
+    pub segment: String,
+    /// How the segment was searched
+    pub mode: SegmentSearchMode,
+    /// Number of searches of the segment in this mode
+    pub searches: usize,
+}
+
+/// How a segment was searched
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentSearchMode {
+    /// Points were scored one by one, without the HNSW graph
+    Plain,
+    /// HNSW graph was traversed
+    Hnsw,
+    /// Inverted index of sparse vectors was used
+    Sparse,
 }
 
 impl Eq for ScoredPoint {}
@@ -360,7 +450,9 @@ pub struct SearchParams {
     #[serde(default)]
     pub exact: bool,
 
-    /// Quantization params
+    /// Quantization params.
+    /// If not set, qdrant decides whether to use quantized vectors, depending on the number of
+    /// points matching the filter.
     #[serde(default)]
     #[validate]
     pub quantization: Option<QuantizationSearchParams>,
@@ -370,6 +462,12 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
     pub indexed_only: bool,
+
+    /// If enabled, points found by a nearest search on a cosine vector also get a `raw_score`:
+    /// the inner product with the query before both vectors were normalized.
+    /// Points inserted before raw scores were supported get none.
+    #[serde(default)]
+    pub with_raw_score: bool,
 }
 
 /// Collection default values
@@ -497,6 +595,21 @@ const fn default_max_indexing_threads() -> usize {
     0
 }
 
+/// Reuse of memory between searches on the segments of a collection.
+/// Defaults fit most workloads, tuning may help collections with very high request rates.
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchPoolsConfig {
+    /// Maximal number of visited lists kept for reuse by the HNSW index of each segment.
+    /// Each concurrent search on an index needs its own list, more are allocated and dropped afterwards.
+    /// If 0 - lists are not reused. Default: number of CPUs, from 16 to 128.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visited_pool_size: Option<usize>,
+    /// Reuse the score buffers of sparse vector search contexts between searches. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reuse_search_contexts: Option<bool>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum CompressionRatio {
@@ -696,6 +809,22 @@ impl PayloadStorageType {
     }
 }
 
+/// Compression of large payload fields in storage, e.g. the text of document chunks.
+/// Trades CPU time on reading and writing payload for smaller disk and page cache footprint.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadCompressionConfig {
+    /// Top-level payload fields to compress. Fields of a point are compressed together.
+    pub fields: Vec<String>,
+    /// Zstd compression level, from 1 (fastest) to 22 (smallest). Default: 3
+    #[validate(range(min = 1, max = 22))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<i32>,
+    /// Store the fields uncompressed if they take fewer bytes than this. Default: 128
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size: Option<usize>,
+}
+
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct SegmentConfig {
@@ -706,6 +835,9 @@ pub struct SegmentConfig {
     pub sparse_vector_data: HashMap<String, SparseVectorDataConfig>,
     /// Defines payload storage type
     pub payload_storage_type: PayloadStorageType,
+    /// Compression of payload fields in storage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_compression: Option<PayloadCompressionConfig>,
 }
 
 impl SegmentConfig {
@@ -2284,6 +2416,24 @@ impl Filter {
         self.clone().merge_owned(other.clone())
     }
 
+    /// The only condition of the filter, if it is a single `must` or `should` condition
+    pub fn single_required_condition(&self) -> Option<&Condition> {
+        let has_must_not = self
+            .must_not
+            .as_ref()
+            .is_some_and(|must_not| !must_not.is_empty());
+        if has_must_not || self.min_should.is_some() {
+            return None;
+        }
+
+        let must = self.must.as_deref().unwrap_or_default();
+        let should = self.should.as_deref().unwrap_or_default();
+        match (must, should) {
+            ([condition], []) | ([], [condition]) => Some(condition),
+            _ => None,
+        }
+    }
+
     pub fn merge_owned(self, other: Filter) -> Filter {
         let merge_component = |this, other| -> Option<Vec<Condition>> {
             match (this, other) {
@@ -3622,6 +3772,19 @@ mod tests {
         });
         assert_eq!(payload, expected.into());
     }
+
+    #[test]
+    fn test_distance_similarity() {
+        let score = |distance: Distance, v1: &[f32], v2: &[f32]| {
+            distance.postprocess_score(distance.similarity(v1, v2))
+        };
+
+        assert!((score(Distance::Cosine, &[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(score(Distance::Cosine, &[1.0, 0.0], &[0.0, 3.0]).abs() < 1e-6);
+        assert!((score(Distance::Dot, &[1.0, 2.0], &[3.0, 4.0]) - 11.0).abs() < 1e-6);
+        assert!((score(Distance::Euclid, &[0.0, 0.0], &[3.0, 4.0]) - 5.0).abs() < 1e-6);
+        assert!((score(Distance::Manhattan, &[0.0, 0.0], &[3.0, 4.0]) - 7.0).abs() < 1e-6);
+    }
 }
 
 pub type TheMap<K, V> = BTreeMap<K, V>;
@@ -5,6 +5,7 @@ mod chunked_utils;
 pub mod chunked_vectors;
 pub mod quantized;
 pub mod raw_scorer;
+pub mod vector_norms;
 mod vector_storage_base;
 
 #[cfg(test)]
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use common::types::{PointOffsetType, ScoreType};
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_VECTOR_NORMS_CF};
+use crate::common::Flusher;
+use crate::data_types::vectors::{QueryVector, Vector, VectorElementType};
+use crate::types::{Distance, VectorDataConfig};
+
+/// Lengths of dense vectors, as they were before normalization
+///
+/// Vectors of cosine configured storages are normalized on insertion, which discards their
+/// magnitude. The length is kept here, so the raw inner product with a query can be recovered
+/// from the cosine score: `raw = cosine * |query| * |vector|`.
+///
+/// Only points inserted or updated since norms are kept have one.
+pub struct VectorNorms {
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+impl VectorNorms {
+    pub fn open(database: Arc<RwLock<DB>>) -> Self {
+        Self {
+            db_wrapper: DatabaseColumnWrapper::new(database, DB_VECTOR_NORMS_CF),
+        }
+    }
+
+    /// Whether vectors with this config are normalized, so their norm has to be kept
+    pub fn is_tracked(config: &VectorDataConfig) -> bool {
+        config.distance == Distance::Cosine && config.multivector_config.is_none()
+    }
+
+    fn key(vector_name: &str, point_offset: PointOffsetType) -> Vec<u8> {
+        let mut key = Vec::with_capacity(vector_name.len() + 4);
+        key.extend_from_slice(vector_name.as_bytes());
+        key.extend_from_slice(&point_offset.to_be_bytes());
+        key
+    }
+
+    /// Store the norm of a vector of the point, or remove it if `None`
+    pub fn set(
+        &self,
+        vector_name: &str,
+        point_offset: PointOffsetType,
+        norm: Option<ScoreType>,
+    ) -> OperationResult<()> {
+        let key = Self::key(vector_name, point_offset);
+        match norm {
+            Some(norm) => self.db_wrapper.put(key, norm.to_le_bytes()),
+            None => self.db_wrapper.remove(key),
+        }
+    }
+
+    pub fn get(
+        &self,
+        vector_name: &str,
+        point_offset: PointOffsetType,
+    ) -> OperationResult<Option<ScoreType>> {
+        let key = Self::key(vector_name, point_offset);
+        self.db_wrapper
+            .get_pinned(&key, |value| value.try_into().map(ScoreType::from_le_bytes))?
+            .transpose()
+            .map_err(|_| OperationError::service_error("cannot deserialize vector norm"))
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+}
+
+/// Euclidean length of a dense vector
+pub fn dense_vector_norm(vector: &[VectorElementType]) -> ScoreType {
+    vector.iter().map(|x| x * x).sum::<ScoreType>().sqrt()
+}
+
+/// Norm of a nearest neighbour query with a dense vector, the only query with a raw score
+pub fn query_vector_norm(query: &QueryVector) -> Option<ScoreType> {
+    match query {
+        QueryVector::Nearest(Vector::Dense(vector)) => Some(dense_vector_norm(vector)),
+        QueryVector::Nearest(Vector::Sparse(_) | Vector::MultiDense(_))
+        | QueryVector::Recommend(_)
+        | QueryVector::Discovery(_)
+        | QueryVector::Context(_) => None,
+    }
+}
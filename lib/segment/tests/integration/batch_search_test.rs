@@ -48,6 +48,7 @@ fn test_batch_and_single_request_equivalency() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -158,6 +159,7 @@ fn test_batch_and_single_request_equivalency() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
@@ -134,6 +134,7 @@ fn test_byte_storage_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let config_byte = SegmentConfig {
         vector_data: HashMap::from([(
@@ -150,6 +151,7 @@ fn test_byte_storage_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -224,6 +226,7 @@ fn test_byte_storage_hnsw(
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
@@ -257,6 +257,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -350,6 +351,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
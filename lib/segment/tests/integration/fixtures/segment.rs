@@ -165,6 +165,7 @@ pub fn build_segment_3(path: &Path) -> Segment {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
@@ -258,6 +259,7 @@ pub fn build_segment_sparse_1(path: &Path) -> Segment {
                 },
             )]),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
@@ -343,6 +345,7 @@ pub fn build_segment_sparse_2(path: &Path) -> Segment {
                 },
             )]),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
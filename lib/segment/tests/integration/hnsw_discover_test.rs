@@ -80,6 +80,7 @@ fn hnsw_discover_precision() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
         sparse_vector_data: Default::default(),
     };
 
@@ -119,6 +120,7 @@ fn hnsw_discover_precision() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
@@ -192,6 +194,7 @@ fn filtered_hnsw_discover_precision() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
         sparse_vector_data: Default::default(),
     };
 
@@ -242,6 +245,7 @@ fn filtered_hnsw_discover_precision() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
@@ -1,11 +1,11 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::ops::Deref;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
-use common::types::{ScoreType, ScoredPointOffset};
+use common::types::{ScoreType, ScoredPointOffset, TelemetryDetail};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use segment::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
@@ -22,7 +22,7 @@ use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::PayloadSchemaType::Keyword;
 use segment::types::{
     CompressionRatio, Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, Payload,
-    ProductQuantizationConfig, QuantizationConfig, QuantizationSearchParams,
+    PointIdType, ProductQuantizationConfig, QuantizationConfig, QuantizationSearchParams,
     ScalarQuantizationConfig, SearchParams, SegmentConfig, VectorDataConfig, VectorStorageType,
 };
 use segment::vector_storage::quantized::quantized_vectors::QuantizedVectors;
@@ -77,6 +77,7 @@ fn hnsw_quantized_search_test(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -142,6 +143,7 @@ fn hnsw_quantized_search_test(
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
@@ -170,6 +172,9 @@ fn hnsw_quantized_search_test(
     check_oversampling(&query_vectors, &hnsw_index, None, ef, top);
     check_oversampling(&query_vectors, &hnsw_index, Some(&filter), ef, top);
 
+    // check that filtered searches without quantization params choose the vectors to score
+    check_auto_quantization(&query_vectors, &hnsw_index, &filter, top);
+
     // check that rescoring is working
     // to check it, set all vectors to zero and expect zero scores
     let zero_vector = vec![0.0; dim];
@@ -284,6 +289,64 @@ fn check_oversampling(
     }
 }
 
+fn check_auto_quantization(
+    query_vectors: &[QueryVector],
+    hnsw_index: &HNSWIndex<GraphLinksRam>,
+    filter: &Filter,
+    top: usize,
+) {
+    let few_points_filter = Filter::new_must(Condition::HasId(
+        (0..top as u64 / 2)
+            .map(PointIdType::from)
+            .collect::<HashSet<_>>()
+            .into(),
+    ));
+
+    let telemetry = hnsw_index.get_telemetry_data(TelemetryDetail::default());
+    let auto_quantized = telemetry.filtered_auto_quantized.count;
+    let auto_original = telemetry.filtered_auto_original.count;
+
+    for query in query_vectors {
+        // Few candidates are scored with the original vectors
+        hnsw_index
+            .search(
+                &[query],
+                Some(&few_points_filter),
+                top,
+                None,
+                &Default::default(),
+            )
+            .unwrap();
+        // Many more candidates than requested results are scored with the quantized vectors
+        hnsw_index
+            .search(&[query], Some(filter), 1, None, &Default::default())
+            .unwrap();
+        // Quantization params of the request are respected
+        hnsw_index
+            .search(
+                &[query],
+                Some(&few_points_filter),
+                top,
+                Some(&SearchParams {
+                    quantization: Some(QuantizationSearchParams::default()),
+                    ..Default::default()
+                }),
+                &Default::default(),
+            )
+            .unwrap();
+    }
+
+    let telemetry = hnsw_index.get_telemetry_data(TelemetryDetail::default());
+    assert_eq!(
+        telemetry.filtered_auto_quantized.count,
+        auto_quantized + query_vectors.len(),
+    );
+    assert_eq!(
+        telemetry.filtered_auto_original.count,
+        auto_original + query_vectors.len(),
+    );
+}
+
 fn check_rescoring(
     query_vectors: &[QueryVector],
     hnsw_index: &HNSWIndex<GraphLinksRam>,
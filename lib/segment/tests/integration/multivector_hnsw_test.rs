@@ -56,6 +56,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -147,6 +148,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         hnsw_config: hnsw_config.clone(),
         permit: Some(permit.clone()),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
@@ -161,6 +163,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        checkpoints_path: None,
     })
     .unwrap();
 
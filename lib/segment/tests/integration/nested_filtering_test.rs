@@ -245,3 +245,116 @@ fn test_filtering_context_consistency() {
         assert!(!res3.is_empty());
     }
 }
+
+fn nested_payload_index(dir: &std::path::Path, indexed_fields: &[&str]) -> StructPayloadIndex {
+    let mut payload_storage = InMemoryPayloadStorage::default();
+    for (idx, payload) in nested_payloads().into_iter().enumerate() {
+        payload_storage
+            .assign(idx as PointOffsetType, &payload)
+            .unwrap();
+    }
+
+    let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
+    let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(NUM_POINTS)));
+
+    let mut index =
+        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir, true).unwrap();
+    for field in indexed_fields {
+        let schema = if field.ends_with("text") {
+            PayloadSchemaType::Text
+        } else {
+            PayloadSchemaType::Integer
+        };
+        index.set_indexed(&path(field), schema.into()).unwrap();
+    }
+    index
+}
+
+#[test]
+fn test_nested_index_checks() {
+    let indexed_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let plain_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+    let indexed = nested_payload_index(
+        indexed_dir.path(),
+        &["arr1[].a", "arr1[].b", "arr1[].c", "arr1[].text"],
+    );
+    let plain = nested_payload_index(plain_dir.path(), &[]);
+
+    let nested_filters = [
+        // Single condition, checked on the index only
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            path("b"),
+            3.into(),
+        ))),
+        Filter::new_should(Condition::Field(FieldCondition::new_range(
+            path("b"),
+            Range {
+                gte: Some(12.0),
+                ..Default::default()
+            },
+        ))),
+        // Conditions of the same object, checked on the payload after the index
+        Filter {
+            must: Some(vec![
+                Condition::Field(FieldCondition::new_match(path("a"), 1.into())),
+                Condition::Field(FieldCondition::new_match(path("b"), 3.into())),
+            ]),
+            should: None,
+            min_should: None,
+            must_not: None,
+        },
+        Filter {
+            must: Some(vec![
+                Condition::Field(FieldCondition::new_match(path("c"), 1.into())),
+                Condition::Field(FieldCondition::new_match(
+                    path("text"),
+                    Match::Text("a3".to_string().into()),
+                )),
+            ]),
+            should: None,
+            min_should: None,
+            must_not: None,
+        },
+        // Condition on a field without index
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            path("d"),
+            1.into(),
+        ))),
+    ];
+
+    for nested_filter in nested_filters {
+        let filter = Filter::new_must(Condition::new_nested(path("arr1"), nested_filter));
+
+        let indexed_context = indexed.filter_context(&filter);
+        let plain_context = plain.filter_context(&filter);
+        let matches: Vec<_> = (0..NUM_POINTS as PointOffsetType)
+            .filter(|&point_id| indexed_context.check(point_id))
+            .collect();
+        let expected: Vec<_> = (0..NUM_POINTS as PointOffsetType)
+            .filter(|&point_id| plain_context.check(point_id))
+            .collect();
+        assert_eq!(matches, expected, "{filter:?}");
+
+        let estimation = indexed.estimate_cardinality(&filter);
+        assert!(estimation.min <= matches.len(), "{filter:?}");
+        assert!(estimation.max >= matches.len(), "{filter:?}");
+    }
+
+    // Objects with `a = 1` have `b` from 1 to 10, but the index of `b` holds values of all objects
+    let filter = Filter::new_must(Condition::new_nested(
+        path("arr1"),
+        Filter {
+            must: Some(vec![
+                Condition::Field(FieldCondition::new_match(path("a"), 1.into())),
+                Condition::Field(FieldCondition::new_match(path("b"), 12.into())),
+            ]),
+            should: None,
+            min_should: None,
+            must_not: None,
+        },
+    ));
+    let filter_context = indexed.filter_context(&filter);
+    assert!((0..NUM_POINTS as PointOffsetType).all(|point_id| !filter_context.check(point_id)));
+    assert_eq!(indexed.estimate_cardinality(&filter).min, 0);
+}
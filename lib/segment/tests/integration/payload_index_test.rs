@@ -59,6 +59,7 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut plain_segment = build_segment(path_plain, &config, true).unwrap();
@@ -196,6 +197,7 @@ fn build_test_segments_nested_payload(path_struct: &Path, path_plain: &Path) ->
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut plain_segment = build_segment(path_plain, &config, true).unwrap();
@@ -261,6 +261,7 @@ fn test_update_named_vector() {
         exact: true,
         quantization: None,
         indexed_only: false,
+        with_raw_score: false,
     };
     let nearest_upsert = segment
         .search(
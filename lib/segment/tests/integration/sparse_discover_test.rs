@@ -124,6 +124,7 @@ fn sparse_index_discover_test() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let dense_config = SegmentConfig {
         vector_data: HashMap::from([(
@@ -139,6 +140,7 @@ fn sparse_index_discover_test() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
         sparse_vector_data: Default::default(),
     };
 
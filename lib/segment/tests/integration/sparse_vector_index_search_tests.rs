@@ -585,6 +585,7 @@ fn sparse_vector_index_persistence_test() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -753,6 +754,7 @@ fn sparse_vector_test_large_index() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -13,7 +13,9 @@ use rand::rngs::StdRng;
 use rand::SeedableRng as _;
 use sparse::common::scores_memory_pool::ScoresMemoryPool;
 use sparse::common::sparse_vector::{RemappedSparseVector, SparseVector};
-use sparse::common::sparse_vector_fixture::{random_positive_sparse_vector, random_sparse_vector};
+use sparse::common::sparse_vector_fixture::{
+    random_positive_sparse_vector, random_sparse_vector, random_splade_like_vector,
+};
 use sparse::common::types::QuantizedU8;
 use sparse::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
 use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
@@ -32,6 +34,7 @@ const TOP: usize = 10;
 pub fn bench_search(c: &mut Criterion) {
     bench_uniform_random(c, "random-50k", 50_000);
     bench_uniform_random(c, "random-500k", 500_000);
+    bench_splade_like(c, "splade-like-500k", 500_000);
 
     {
         let query_vectors =
@@ -65,6 +68,28 @@ fn bench_uniform_random(c: &mut Criterion, name: &str, num_vectors: usize) {
     run_bench(c, name, index, &query_vectors);
 }
 
+/// Documents and queries with the term distribution of SPLADE embeddings, without downloading a
+/// dataset. Long postings of common terms are where skipping non-competitive postings pays off.
+fn bench_splade_like(c: &mut Criterion, name: &str, num_vectors: usize) {
+    const DOCUMENT_TERMS: usize = 150;
+    const QUERY_TERMS: usize = 30;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let index = InvertedIndexBuilder::build_from_iterator((0..num_vectors).map(|idx| {
+        (
+            idx as PointOffsetType,
+            random_splade_like_vector(&mut rnd, DOCUMENT_TERMS).into_remapped(),
+        )
+    }));
+
+    let query_vectors = (0..NUM_QUERIES)
+        .map(|_| random_splade_like_vector(&mut rnd, QUERY_TERMS))
+        .collect::<Vec<_>>();
+
+    run_bench(c, name, index, &query_vectors);
+}
+
 pub fn bench_movies(c: &mut Criterion) {
     let mut iter =
         loaders::JsonReader::open(Dataset::SpladeWikiMovies.download().unwrap()).unwrap();
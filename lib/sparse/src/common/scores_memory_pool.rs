@@ -8,17 +8,24 @@ type PooledScores = Vec<ScoreType>;
 pub struct PooledScoresHandle<'a> {
     pool: &'a ScoresMemoryPool,
     pub scores: PooledScores,
+    /// Maximal number of buffers in the pool, for this buffer to be returned to it
+    keep_limit: usize,
 }
 
 impl<'a> PooledScoresHandle<'a> {
-    fn new(pool: &'a ScoresMemoryPool, scores: PooledScores) -> Self {
-        PooledScoresHandle { pool, scores }
+    fn new(pool: &'a ScoresMemoryPool, scores: PooledScores, keep_limit: usize) -> Self {
+        PooledScoresHandle {
+            pool,
+            scores,
+            keep_limit,
+        }
     }
 }
 
 impl<'a> Drop for PooledScoresHandle<'a> {
     fn drop(&mut self) {
-        self.pool.return_back(std::mem::take(&mut self.scores));
+        self.pool
+            .return_back(std::mem::take(&mut self.scores), self.keep_limit);
     }
 }
 
@@ -35,15 +42,21 @@ impl ScoresMemoryPool {
     }
 
     pub fn get(&self) -> PooledScoresHandle {
+        self.get_with_limit(*POOL_KEEP_LIMIT).0
+    }
+
+    /// Get a buffer, which is returned to the pool once dropped, if the pool keeps less than
+    /// `keep_limit` buffers. Also returns whether a pooled buffer was reused.
+    pub fn get_with_limit(&self, keep_limit: usize) -> (PooledScoresHandle, bool) {
         match self.pool.lock().pop() {
-            None => PooledScoresHandle::new(self, vec![]),
-            Some(data) => PooledScoresHandle::new(self, data),
+            None => (PooledScoresHandle::new(self, vec![], keep_limit), false),
+            Some(data) => (PooledScoresHandle::new(self, data, keep_limit), true),
         }
     }
 
-    fn return_back(&self, data: PooledScores) {
+    fn return_back(&self, data: PooledScores, keep_limit: usize) {
         let mut pool = self.pool.lock();
-        if pool.len() < *POOL_KEEP_LIMIT {
+        if pool.len() < keep_limit {
             pool.push(data);
         }
     }
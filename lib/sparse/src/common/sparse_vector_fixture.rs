@@ -61,3 +61,28 @@ pub fn random_positive_sparse_vector<R: Rng + ?Sized>(
     }
     vec
 }
+
+/// Vocabulary size of the BERT tokenizer used by SPLADE models
+pub const SPLADE_VOCAB_SIZE: usize = 30_522;
+
+/// Generates a sparse vector shaped like a SPLADE embedding, with up to `num_terms` positive
+/// weights over the SPLADE vocabulary.
+///
+/// Terms are skewed towards the low ids, so a few postings are long and most are short, as in
+/// real SPLADE indexes. Weights are mostly small, with a few large ones.
+pub fn random_splade_like_vector<R: Rng + ?Sized>(
+    rnd_gen: &mut R,
+    num_terms: usize,
+) -> SparseVector {
+    let mut tuples: Vec<(u32, f32)> = (0..num_terms.max(1))
+        .map(|_| {
+            let term = SPLADE_VOCAB_SIZE as f64 * rnd_gen.gen::<f64>().powi(3);
+            let weight = rnd_gen.gen::<f32>().powi(4) * 3.0 + 0.01;
+            (term as u32, weight)
+        })
+        .collect();
+    tuples.sort_unstable_by_key(|(term, _)| *term);
+    tuples.dedup_by_key(|(term, _)| *term);
+
+    SparseVector::try_from(tuples).unwrap()
+}
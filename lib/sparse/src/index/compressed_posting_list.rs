@@ -1,4 +1,3 @@
-use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::mem::size_of;
 
@@ -9,6 +8,7 @@ use itertools::Itertools as _;
 
 use super::posting_list_common::{
     GenericPostingElement, PostingElement, PostingElementEx, PostingListIter,
+    DEFAULT_MAX_NEXT_WEIGHT,
 };
 use crate::common::types::{DimWeight, Weight};
 type BitPackerImpl = bitpacking::BitPacker4x;
@@ -29,6 +29,9 @@ pub struct CompressedPostingList<W: Weight> {
 
     /// Quantization parameters.
     quantization_params: W::QuantizationParams,
+
+    /// Max weight of the elements in the list. Used to skip elements that can't make the top.
+    max_weight: DimWeight,
 }
 
 /// A non-owning view of [`GenericCompressedPostingList`].
@@ -39,6 +42,8 @@ pub struct CompressedPostingListView<'a, W: Weight> {
     remainders: &'a [GenericPostingElement<W>],
     last_id: Option<PointOffsetType>,
     multiplier: W::QuantizationParams,
+    /// `None` for lists stored without the max weight.
+    max_weight: Option<DimWeight>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -62,9 +67,14 @@ impl<W: Weight> CompressedPostingList<W> {
             remainders: &self.remainders,
             last_id: self.last_id,
             multiplier: self.quantization_params,
+            max_weight: Some(self.max_weight),
         }
     }
 
+    pub fn max_weight(&self) -> DimWeight {
+        self.max_weight
+    }
+
     pub fn iter(&self) -> CompressedPostingListIterator<W> {
         self.view().iter()
     }
@@ -104,6 +114,7 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
         remainders: &'a [GenericPostingElement<W>],
         last_id: Option<PointOffsetType>,
         multiplier: W::QuantizationParams,
+        max_weight: Option<DimWeight>,
     ) -> Self {
         CompressedPostingListView {
             id_data,
@@ -111,6 +122,7 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
             remainders,
             last_id,
             multiplier,
+            max_weight,
         }
     }
 
@@ -132,6 +144,10 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
         self.multiplier
     }
 
+    pub fn max_weight(&self) -> Option<DimWeight> {
+        self.max_weight
+    }
+
     pub(super) fn store_size(&self) -> CompressedPostingListStoreSize {
         CompressedPostingListStoreSize::new::<W>(
             self.id_data.len(),
@@ -147,6 +163,9 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
             remainders: self.remainders.to_vec(),
             last_id: self.last_id,
             quantization_params: self.multiplier,
+            max_weight: self
+                .max_weight
+                .unwrap_or_else(|| max_weight(self.chunks, self.remainders, self.multiplier)),
         }
     }
 
@@ -277,16 +296,32 @@ impl CompressedPostingBuilder {
             );
         }
 
+        let max_weight = max_weight(&chunks, &remainders, quantization_params);
         CompressedPostingList {
             id_data,
             chunks,
             remainders,
             last_id: self.elements.last().map(|e| e.record_id),
             quantization_params,
+            max_weight,
         }
     }
 }
 
+/// Max weight of the stored elements, as read back after quantization
+fn max_weight<W: Weight>(
+    chunks: &[CompressedPostingChunk<W>],
+    remainders: &[GenericPostingElement<W>],
+    quantization_params: W::QuantizationParams,
+) -> DimWeight {
+    chunks
+        .iter()
+        .flat_map(|chunk| chunk.weights.iter())
+        .chain(remainders.iter().map(|e| &e.weight))
+        .map(|weight| weight.to_f32(quantization_params))
+        .fold(DEFAULT_MAX_NEXT_WEIGHT, DimWeight::max)
+}
+
 #[derive(Clone)]
 pub struct CompressedPostingListIterator<'a, W: Weight> {
     list: CompressedPostingListView<'a, W>,
@@ -363,17 +398,58 @@ impl<'a, W: Weight> PostingListIter for CompressedPostingListIterator<'a, W> {
 
     #[inline]
     fn skip_to(&mut self, record_id: PointOffsetType) -> Option<PostingElementEx> {
-        // TODO: optimize
-        while let Some(e) = self.peek() {
-            match e.record_id.cmp(&record_id) {
-                Ordering::Equal => return Some(e),
-                Ordering::Greater => return None,
-                Ordering::Less => {
-                    self.next();
-                }
+        if self
+            .list
+            .last_id
+            .map_or(true, |last_id| last_id < record_id)
+        {
+            self.skip_to_end();
+            return None;
+        }
+
+        let chunks = self.list.chunks;
+        let chunk_index = self.pos / BitPackerImpl::BLOCK_LEN;
+        if chunk_index < chunks.len() {
+            // Jump to the last chunk starting at or before the id, without unpacking the skipped ones
+            let skipped =
+                chunks[chunk_index + 1..].partition_point(|chunk| chunk.initial <= record_id);
+            if skipped > 0 {
+                self.pos = (chunk_index + skipped) * BitPackerImpl::BLOCK_LEN;
+                self.unpacked = false;
+            }
+
+            // Remainders follow the last chunk
+            let is_last_chunk = self.pos / BitPackerImpl::BLOCK_LEN + 1 == chunks.len();
+            let in_remainders = self
+                .list
+                .remainders
+                .first()
+                .is_some_and(|e| e.record_id <= record_id);
+            if is_last_chunk && in_remainders {
+                self.pos = chunks.len() * BitPackerImpl::BLOCK_LEN;
+                self.unpacked = false;
             }
         }
-        None
+
+        let chunk_index = self.pos / BitPackerImpl::BLOCK_LEN;
+        if chunk_index < chunks.len() {
+            if !self.unpacked {
+                self.list
+                    .decompress_chunk(chunk_index, &mut self.decompressed_chunk);
+                self.unpacked = true;
+            }
+            let start = self.pos % BitPackerImpl::BLOCK_LEN;
+            let count = self.decompressed_chunk[start..].partition_point(|&id| id < record_id);
+            self.pos += count;
+            if start + count == BitPackerImpl::BLOCK_LEN {
+                self.unpacked = false;
+            }
+        } else {
+            let start = self.pos - chunks.len() * BitPackerImpl::BLOCK_LEN;
+            self.pos += self.list.remainders[start..].partition_point(|e| e.record_id < record_id);
+        }
+
+        self.peek().filter(|e| e.record_id == record_id)
     }
 
     #[inline]
@@ -446,8 +522,9 @@ impl<'a, W: Weight> PostingListIter for CompressedPostingListIterator<'a, W> {
         self.pos = pos;
     }
 
-    fn reliable_max_next_weight() -> bool {
-        false
+    #[inline]
+    fn max_weight_to_end(&mut self) -> Option<DimWeight> {
+        self.list.max_weight
     }
 
     fn into_std_iter(self) -> impl Iterator<Item = PostingElement> {
@@ -558,6 +635,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_skip_to() {
+        for case in cases() {
+            let list = CompressedPostingList::<f32>::from(case.clone());
+            let mut iter = list.iter();
+
+            // Ids of the case are consecutive, starting at 10000
+            for (record_id, weight) in case.iter().copied().step_by(50) {
+                let element = iter.skip_to(record_id).unwrap();
+                assert_eq!((element.record_id, element.weight), (record_id, weight));
+                assert_eq!(iter.len_to_end(), case.len() - (record_id - 10000) as usize);
+            }
+
+            // Missing ids position the iterator on the next element
+            assert!(iter.skip_to(PointOffsetType::MAX).is_none());
+            assert_eq!(iter.len_to_end(), 0);
+        }
+    }
+
+    #[test]
+    fn test_skip_to_missing() {
+        for count in CASES {
+            // Only even ids, across chunks and remainders
+            let case: Vec<_> = (0..count)
+                .map(|i| (i as PointOffsetType * 2, i as DimWeight))
+                .collect();
+            let list = CompressedPostingList::<f32>::from(case.clone());
+            let mut iter = list.iter();
+
+            for i in (0..count).step_by(37) {
+                let record_id = i as PointOffsetType * 2;
+                assert!(iter.skip_to(record_id + 1).is_none());
+                assert_eq!(iter.current_index(), i + 1);
+                assert_eq!(
+                    iter.peek().map(|e| e.record_id),
+                    case.get(i + 1).map(|e| e.0)
+                );
+
+                // Ids behind the current position are not found again
+                assert!(iter.skip_to(record_id).is_none());
+                assert_eq!(iter.current_index(), i + 1);
+            }
+
+            // Stays consistent with iterating the rest of the list
+            let rest: Vec<_> = std::iter::from_fn(|| iter.next())
+                .map(|e| e.record_id)
+                .collect();
+            let start = iter.current_index() - rest.len();
+            let expected: Vec<_> = case[start..].iter().map(|e| e.0).collect();
+            assert_eq!(rest, expected);
+        }
+    }
+
+    #[test]
+    fn test_max_weight() {
+        for case in cases() {
+            let list = CompressedPostingList::<f32>::from(case.clone());
+            let expected = case
+                .iter()
+                .map(|(_, weight)| *weight)
+                .fold(DEFAULT_MAX_NEXT_WEIGHT, DimWeight::max);
+            assert_eq!(list.max_weight(), expected);
+            assert_eq!(list.iter().max_weight_to_end(), Some(expected));
+            assert_eq!(list.view().to_owned(), list);
+        }
+    }
+
     #[test]
     fn test_count_le_sorted() {
         let data = [1, 2, 4, 5];
@@ -19,7 +19,7 @@ use serde::{Deserialize, Serialize};
 use super::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
 use super::INDEX_FILE_NAME;
 use crate::common::sparse_vector::RemappedSparseVector;
-use crate::common::types::{DimId, DimOffset, Weight};
+use crate::common::types::{DimId, DimOffset, DimWeight, Weight};
 use crate::index::compressed_posting_list::{
     CompressedPostingChunk, CompressedPostingListIterator, CompressedPostingListView,
 };
@@ -29,6 +29,10 @@ use crate::index::posting_list_common::GenericPostingElement;
 
 const INDEX_CONFIG_FILE_NAME: &str = "inverted_index_config.json";
 
+/// Max weight of each posting list, as little-endian `f32` values.
+/// Indexes created by older versions don't have this file.
+const INDEX_MAX_WEIGHTS_FILE_NAME: &str = "inverted_index_max_weights.dat";
+
 pub struct Version;
 
 impl StorageVersion for Version {
@@ -49,6 +53,8 @@ pub struct InvertedIndexCompressedMmap<W> {
     path: PathBuf,
     mmap: Arc<Mmap>,
     pub file_header: InvertedIndexFileHeader,
+    /// Max weight of each posting list, if stored
+    max_weights: Option<Vec<DimWeight>>,
     _phantom: PhantomData<W>,
 }
 
@@ -100,10 +106,15 @@ impl<W: Weight> InvertedIndex for InvertedIndexCompressedMmap<W> {
     }
 
     fn files(path: &Path) -> Vec<PathBuf> {
-        vec![
+        let mut files = vec![
             Self::index_file_path(path),
             Self::index_config_file_path(path),
-        ]
+        ];
+        let max_weights_file_path = Self::max_weights_file_path(path);
+        if max_weights_file_path.exists() {
+            files.push(max_weights_file_path);
+        }
+        files
     }
 
     fn remove(&mut self, _id: PointOffsetType, _old_vector: RemappedSparseVector) {
@@ -148,6 +159,10 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
         path.join(INDEX_CONFIG_FILE_NAME)
     }
 
+    pub fn max_weights_file_path(path: &Path) -> PathBuf {
+        path.join(INDEX_MAX_WEIGHTS_FILE_NAME)
+    }
+
     pub fn get<'a>(&'a self, id: &DimId) -> Option<CompressedPostingListView<'a, W>> {
         // check that the id is not out of bounds (posting_count includes the empty zeroth entry)
         if *id >= self.file_header.posting_count as DimId {
@@ -194,6 +209,9 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
             ),
             header.last_id.checked_sub(1),
             header.quantization_params,
+            self.max_weights
+                .as_ref()
+                .and_then(|max_weights| max_weights.get(*id as usize).copied()),
         ))
     }
 
@@ -256,10 +274,24 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
         };
         atomic_save_json(&Self::index_config_file_path(path.as_ref()), &file_header)?;
 
+        // save max weights of posting lists
+        let max_weights: Vec<DimWeight> = index
+            .postings
+            .as_slice()
+            .iter()
+            .map(|posting| posting.max_weight())
+            .collect();
+        let max_weights_data: Vec<u8> = max_weights
+            .iter()
+            .flat_map(|weight| weight.to_le_bytes())
+            .collect();
+        std::fs::write(Self::max_weights_file_path(path.as_ref()), max_weights_data)?;
+
         Ok(Self {
             path: path.as_ref().to_owned(),
             mmap: Arc::new(open_read_mmap(file_path.as_ref())?),
             file_header,
+            max_weights: Some(max_weights),
             _phantom: PhantomData,
         })
     }
@@ -273,10 +305,32 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
         let file_path = Self::index_file_path(path.as_ref());
         let mmap = open_read_mmap(file_path.as_ref())?;
         madvise::madvise(&mmap, madvise::Advice::Normal)?;
+        // read max weights of posting lists, if stored
+        let max_weights_file_path = Self::max_weights_file_path(path.as_ref());
+        let max_weights = if max_weights_file_path.exists() {
+            let data = std::fs::read(&max_weights_file_path)?;
+            if data.len() != file_header.posting_count * size_of::<DimWeight>() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "Max weights file {} does not match the posting count",
+                        max_weights_file_path.display(),
+                    ),
+                ));
+            }
+            let max_weights = data
+                .chunks_exact(size_of::<DimWeight>())
+                .map(|bytes| DimWeight::from_le_bytes(bytes.try_into().unwrap()))
+                .collect();
+            Some(max_weights)
+        } else {
+            None
+        };
         Ok(Self {
             path: path.as_ref().to_owned(),
             mmap: Arc::new(mmap),
             file_header,
+            max_weights,
             _phantom: PhantomData,
         })
     }
@@ -357,5 +411,18 @@ mod tests {
         assert!(inverted_index_mmap.get(&6).is_none());
         assert!(inverted_index_mmap.get(&7).is_none());
         assert!(inverted_index_mmap.get(&100).is_none());
+
+        // indexes stored without max weights are still readable
+        std::fs::remove_file(InvertedIndexCompressedMmap::<W>::max_weights_file_path(
+            tmp_dir_path.path(),
+        ))
+        .unwrap();
+        let inverted_index_mmap = InvertedIndexCompressedMmap::<W>::load(&tmp_dir_path).unwrap();
+        assert_eq!(inverted_index_mmap.get(&1).unwrap().max_weight(), None);
+        assert_eq!(inverted_index_mmap.get(&1).unwrap().len(), 9);
+        assert_eq!(
+            inverted_index_mmap.get(&1).unwrap().to_owned(),
+            inverted_index_ram.postings[1],
+        );
     }
 }
@@ -221,8 +221,10 @@ impl<'a> PostingListIter for PostingListIterator<'a> {
         self.current_index = current_index;
     }
 
-    fn reliable_max_next_weight() -> bool {
-        true
+    #[inline]
+    fn max_weight_to_end(&mut self) -> Option<DimWeight> {
+        self.peek()
+            .map(|element| element.weight.max(element.max_next_weight))
     }
 
     fn into_std_iter(self) -> impl Iterator<Item = PostingElement> {
@@ -72,8 +72,10 @@ pub trait PostingListIter {
         f: impl FnMut(&mut Ctx, PointOffsetType, DimWeight),
     );
 
-    /// Whether the max_next_weight is reliable.
-    fn reliable_max_next_weight() -> bool;
+    /// Upper bound of the weights from the current position to the end of the list.
+    ///
+    /// Returns `None` if the bound is unknown.
+    fn max_weight_to_end(&mut self) -> Option<DimWeight>;
 
     fn into_std_iter(self) -> impl Iterator<Item = PostingElement>;
 }
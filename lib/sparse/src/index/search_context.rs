@@ -1,4 +1,4 @@
-use std::cmp::{max, min, Ordering};
+use std::cmp::{max, min};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
 
@@ -17,6 +17,8 @@ pub struct IndexedPostingListIterator<T: PostingListIter> {
     posting_list_iterator: T,
     query_index: DimId,
     query_weight: DimWeight,
+    /// Upper bound of the score contribution of the remaining elements, updated for pruning
+    max_score: f32,
 }
 
 /// Making this larger makes the search faster but uses more (pooled) memory
@@ -32,6 +34,10 @@ pub struct SearchContext<'a, 'b, T: PostingListIter = PostingListIterator<'a>> {
     max_record_id: PointOffsetType,         // max_record_id ids across all posting lists
     pooled: PooledScoresHandle<'b>,         // handle to pooled scores
     use_pruning: bool,
+    // MaxScore pruning: the first `non_essential_count` posting lists can't make the top results
+    // on their own, they are only checked for ids found in the other (essential) posting lists
+    non_essential_count: usize,
+    non_essential_max_score: f32, // sum of the max scores of the non-essential posting lists
 }
 
 impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
@@ -66,15 +72,16 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                         posting_list_iterator: it,
                         query_index,
                         query_weight,
+                        max_score: f32::INFINITY,
                     });
                 }
             }
         }
         let top_results = TopK::new(top);
-        // Query vectors with negative values can NOT use the pruning mechanism which relies on the pre-computed `max_next_weight`.
+        // Query vectors with negative values can NOT use the pruning mechanism which relies on the max weights of posting lists.
         // The max contribution per posting list that we calculate is not made to compute the max value of two negative numbers.
         // This is a limitation of the current pruning implementation.
-        let use_pruning = T::reliable_max_next_weight() && query.values.iter().all(|v| *v >= 0.0);
+        let use_pruning = query.values.iter().all(|v| *v >= 0.0);
         let min_record_id = Some(min_record_id);
         SearchContext {
             postings_iterators,
@@ -86,6 +93,8 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
             max_record_id,
             pooled,
             use_pruning,
+            non_essential_count: 0,
+            non_essential_max_score: 0.0,
         }
     }
 
@@ -138,7 +147,11 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         self.pooled.scores.clear(); // keep underlying allocated memory
         self.pooled.scores.resize(batch_len as usize, 0.0);
 
-        for posting in self.postings_iterators.iter_mut() {
+        let (non_essential, essential) = self
+            .postings_iterators
+            .split_at_mut(self.non_essential_count);
+
+        for posting in essential.iter_mut() {
             posting.posting_list_iterator.for_each_till_id(
                 batch_last_id,
                 self.pooled.scores.as_mut_slice(),
@@ -155,17 +168,44 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
 
         for (local_index, &score) in self.pooled.scores.iter().enumerate() {
             // publish only the non-zero scores above the current min to beat
-            if score != 0.0 && score > self.top_results.threshold() {
-                let real_id = batch_start_id + local_index as PointOffsetType;
-                // do not score if filter condition is not satisfied
-                if !filter_condition(real_id) {
-                    continue;
+            let min_score = self.top_results.threshold();
+            if score == 0.0 || score + self.non_essential_max_score <= min_score {
+                continue;
+            }
+            let real_id = batch_start_id + local_index as PointOffsetType;
+            // complete the score from the non-essential posting lists, largest contributions first
+            let mut score = score;
+            let mut remaining_max_score = self.non_essential_max_score;
+            for posting in non_essential.iter_mut().rev() {
+                if score + remaining_max_score <= min_score {
+                    break;
+                }
+                remaining_max_score -= posting.max_score;
+                if let Some(element) = posting.posting_list_iterator.skip_to(real_id) {
+                    score += element.weight * posting.query_weight;
+                }
+            }
+            if score <= min_score {
+                continue;
+            }
+            // do not score if filter condition is not satisfied
+            if !filter_condition(real_id) {
+                continue;
+            }
+            let score_point_offset = ScoredPointOffset {
+                score,
+                idx: real_id,
+            };
+            self.top_results.push(score_point_offset);
+        }
+
+        // the ids of the batch left in non-essential posting lists can't make the top results
+        for posting in non_essential.iter_mut() {
+            match batch_last_id.checked_add(1) {
+                Some(next_id) => {
+                    posting.posting_list_iterator.skip_to(next_id);
                 }
-                let score_point_offset = ScoredPointOffset {
-                    score,
-                    idx: real_id,
-                };
-                self.top_results.push(score_point_offset);
+                None => posting.posting_list_iterator.skip_to_end(),
             }
         }
     }
@@ -212,26 +252,33 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         min_record_id
     }
 
-    /// Make sure the longest posting list is at the head of the posting list iterators
-    fn promote_longest_posting_lists_to_the_front(&mut self) {
-        // find index of longest posting list
-        let posting_index = self
-            .postings_iterators
-            .iter()
-            .enumerate()
-            .max_by(|(_, a), (_, b)| {
-                a.posting_list_iterator
-                    .len_to_end()
-                    .cmp(&b.posting_list_iterator.len_to_end())
-            })
-            .map(|(index, _)| index);
-
-        if let Some(posting_index) = posting_index {
-            // make sure it is not already at the head
-            if posting_index != 0 {
-                // swap longest posting list to the head
-                self.postings_iterators.swap(0, posting_index);
+    /// Split posting lists into non-essential and essential ones (MaxScore)
+    ///
+    /// Posting lists are sorted by their max score contribution. The ones at the head, which can't
+    /// beat `min_score` together, are non-essential: the ids found only in them can't make the top
+    /// results, so they are never iterated but only checked for ids found in the essential ones.
+    fn partition_posting_lists(&mut self, min_score: f32) {
+        for posting in self.postings_iterators.iter_mut() {
+            // missing ids contribute nothing, so the max contribution is never below 0
+            posting.max_score = posting
+                .posting_list_iterator
+                .max_weight_to_end()
+                .map_or(f32::INFINITY, |max_weight| {
+                    max_weight.max(0.0) * posting.query_weight
+                });
+        }
+        self.postings_iterators
+            .sort_by(|a, b| a.max_score.total_cmp(&b.max_score));
+
+        self.non_essential_count = 0;
+        self.non_essential_max_score = 0.0;
+        for posting in &self.postings_iterators {
+            let max_score = self.non_essential_max_score + posting.max_score;
+            if max_score > min_score {
+                break;
             }
+            self.non_essential_count += 1;
+            self.non_essential_max_score = max_score;
         }
     }
 
@@ -243,7 +290,6 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         if self.postings_iterators.is_empty() {
             return Vec::new();
         }
-        let mut best_min_score = f32::MIN;
         loop {
             // check for cancellation (atomic amortized by batch)
             if self.is_stopped.load(Relaxed) {
@@ -269,112 +315,33 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
             self.postings_iterators.retain(|posting_iterator| {
                 posting_iterator.posting_list_iterator.len_to_end() != 0
             });
-
-            // update min_record_id
-            self.min_record_id = Self::next_min_id(&mut self.postings_iterators);
+            self.non_essential_count = 0;
+            self.non_essential_max_score = 0.0;
 
             // check if all posting lists are exhausted
             if self.postings_iterators.is_empty() {
                 break;
             }
 
+            // we potentially have enough results to prune low performing posting lists
+            if self.use_pruning && self.top_results.len() >= self.top {
+                self.partition_posting_lists(self.top_results.threshold());
+            }
+
+            // update min_record_id, only essential posting lists can start new results
+            self.min_record_id =
+                Self::next_min_id(&mut self.postings_iterators[self.non_essential_count..]);
+
             // if only one posting list left, we can score it quickly
-            if self.postings_iterators.len() == 1 {
+            if self.postings_iterators.len() == 1 && self.non_essential_count == 0 {
                 self.process_last_posting_list(filter_condition);
                 break;
             }
-
-            // we potentially have enough results to prune low performing posting lists
-            if self.use_pruning && self.top_results.len() >= self.top {
-                // current min score
-                let new_min_score = self.top_results.threshold();
-                if new_min_score == best_min_score {
-                    // no improvement in lowest best score since last pruning - skip pruning
-                    continue;
-                } else {
-                    best_min_score = new_min_score;
-                }
-                // make sure the first posting list is the longest for pruning
-                self.promote_longest_posting_lists_to_the_front();
-
-                // prune posting list that cannot possibly contribute to the top results
-                let pruned = self.prune_longest_posting_list(new_min_score);
-                if pruned {
-                    // update min_record_id
-                    self.min_record_id = Self::next_min_id(&mut self.postings_iterators);
-                }
-            }
         }
         // posting iterators exhausted, return result queue
         let queue = std::mem::take(&mut self.top_results);
         queue.into_vec()
     }
-
-    /// Prune posting lists that cannot possibly contribute to the top results
-    /// Assumes longest posting list is at the head of the posting list iterators
-    /// Returns true if the longest posting list was pruned
-    pub fn prune_longest_posting_list(&mut self, min_score: f32) -> bool {
-        if self.postings_iterators.is_empty() {
-            return false;
-        }
-        // peek first element of longest posting list
-        let (longest_posting_iterator, rest_iterators) = self.postings_iterators.split_at_mut(1);
-        let longest_posting_iterator = &mut longest_posting_iterator[0];
-        if let Some(element) = longest_posting_iterator.posting_list_iterator.peek() {
-            let next_min_id_in_others = Self::next_min_id(rest_iterators);
-            match next_min_id_in_others {
-                Some(next_min_id) => {
-                    match next_min_id.cmp(&element.record_id) {
-                        Ordering::Equal => {
-                            // if the next min id in the other posting lists is the same as the current one,
-                            // we can't prune the current element as it needs to be scored properly across posting lists
-                            return false;
-                        }
-                        Ordering::Less => {
-                            // we can't prune as there the other posting lists contains smaller smaller ids that need to scored first
-                            return false;
-                        }
-                        Ordering::Greater => {
-                            // next_min_id is > element.record_id there is a chance to prune up to `next_min_id`
-                            // check against the max possible score using the `max_next_weight`
-                            // we can under prune as we should actually check the best score up to `next_min_id` - 1 only
-                            // instead of the max possible score but it is not possible to know the best score up to `next_min_id` - 1
-                            let max_weight_from_list = element.weight.max(element.max_next_weight);
-                            let max_score_contribution =
-                                max_weight_from_list * longest_posting_iterator.query_weight;
-                            if max_score_contribution <= min_score {
-                                // prune to next_min_id
-                                let longest_posting_iterator =
-                                    &mut self.postings_iterators[0].posting_list_iterator;
-                                let position_before_pruning =
-                                    longest_posting_iterator.current_index();
-                                longest_posting_iterator.skip_to(next_min_id);
-                                let position_after_pruning =
-                                    longest_posting_iterator.current_index();
-                                // check if pruning took place
-                                return position_before_pruning != position_after_pruning;
-                            }
-                        }
-                    }
-                }
-                None => {
-                    // the current posting list is the only one left, we can potentially skip it to the end
-                    // check against the max possible score using the `max_next_weight`
-                    let max_weight_from_list = element.weight.max(element.max_next_weight);
-                    let max_score_contribution =
-                        max_weight_from_list * longest_posting_iterator.query_weight;
-                    if max_score_contribution <= min_score {
-                        // prune to the end!
-                        let longest_posting_iterator = &mut self.postings_iterators[0];
-                        longest_posting_iterator.posting_list_iterator.skip_to_end();
-                        return true;
-                    }
-                }
-            }
-        }
-        // no pruning took place
-        false
-    }
 }
 
 #[cfg(test)]
@@ -384,7 +351,8 @@ mod tests {
     use std::borrow::Cow;
     use std::sync::OnceLock;
 
-    use rand::Rng;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use tempfile::TempDir;
 
     use super::*;
@@ -708,12 +676,12 @@ mod tests {
     }
 
     #[test]
-    fn pruning_single_to_end_test<I: InvertedIndex>() {
+    fn partition_posting_lists_test<I: InvertedIndex>() {
         let index = TestIndex::<I>::from_ram({
             let mut builder = InvertedIndexBuilder::new();
-            builder.add(1, [(1, 10.0)].into());
-            builder.add(2, [(1, 20.0)].into());
-            builder.add(3, [(1, 30.0)].into());
+            builder.add(1, [(1, 10.0), (2, 1.0)].into());
+            builder.add(2, [(1, 20.0), (3, 2.0)].into());
+            builder.add(3, [(1, 30.0), (2, 3.0), (3, 4.0)].into());
             builder.build()
         });
 
@@ -729,95 +697,70 @@ mod tests {
             &is_stopped,
         );
 
-        // assuming we have gathered enough results and want to prune the longest posting list
-        assert!(search_context.prune_longest_posting_list(30.0));
-        // the longest posting list was pruned to the end
-        assert_eq!(
-            search_context.postings_iterators[0]
-                .posting_list_iterator
-                .len_to_end(),
-            0
-        );
+        // posting lists are sorted by their max score contribution: 3.0, 4.0 and 30.0
+        search_context.partition_posting_lists(0.5);
+        assert_eq!(search_context.non_essential_count, 0);
+        let query_indices: Vec<_> = search_context
+            .postings_iterators
+            .iter()
+            .map(|posting| posting.query_index)
+            .collect();
+        assert_eq!(query_indices, vec![2, 3, 1]);
+
+        search_context.partition_posting_lists(5.0);
+        assert_eq!(search_context.non_essential_count, 1);
+
+        search_context.partition_posting_lists(8.0);
+        assert_eq!(search_context.non_essential_count, 2);
+        assert!((search_context.non_essential_max_score - 7.0).abs() < 0.1);
+
+        // non-essential posting lists still contribute to the scores of the essential ids
+        search_context.advance_batch(1, 3, &match_all);
+        let results = search_context.top_results.into_vec();
+        assert_eq!(results[0].idx, 3);
+        assert!((results[0].score - 37.0).abs() < 0.5);
     }
 
     #[test]
-    fn pruning_multi_to_end_test<I: InvertedIndex>() {
-        let index = TestIndex::<I>::from_ram({
-            let mut builder = InvertedIndexBuilder::new();
-            builder.add(1, [(1, 10.0)].into());
-            builder.add(2, [(1, 20.0)].into());
-            builder.add(3, [(1, 30.0)].into());
-            builder.add(5, [(3, 10.0)].into());
-            builder.add(6, [(2, 20.0), (3, 20.0)].into());
-            builder.add(7, [(2, 30.0), (3, 30.0)].into());
-            builder.build()
-        });
+    fn max_score_pruning_random_test<I: InvertedIndex>() {
+        let mut rnd = StdRng::seed_from_u64(42);
+        // ids spread over several batches, for the pruning to kick in
+        let index = TestIndex::<I>::from_ram(random_inverted_index(&mut rnd, 30_000, 30));
+        let query = RemappedSparseVector {
+            indices: (1..30).collect(),
+            values: (1..30).map(|_| rnd.gen_range(1..5) as DimWeight).collect(),
+        };
 
         let is_stopped = AtomicBool::new(false);
-        let mut search_context = SearchContext::new(
-            RemappedSparseVector {
-                indices: vec![1, 2, 3],
-                values: vec![1.0, 1.0, 1.0],
-            },
-            1,
-            &index.index,
-            get_pooled_scores(),
-            &is_stopped,
-        );
-
-        // assuming we have gathered enough results and want to prune the longest posting list
-        assert!(search_context.prune_longest_posting_list(30.0));
-        // the longest posting list was pruned to the end
-        assert_eq!(
-            search_context.postings_iterators[0]
-                .posting_list_iterator
-                .len_to_end(),
-            0
-        );
-    }
-
-    #[test]
-    fn pruning_multi_under_prune_test<I: InvertedIndex>() {
-        if !I::Iter::reliable_max_next_weight() {
-            return;
+        for top in [1, 10, 100] {
+            let mut search_context = SearchContext::new(
+                query.clone(),
+                top,
+                &index.index,
+                get_pooled_scores(),
+                &is_stopped,
+            );
+            let mut pruned = search_context.search(&match_all);
+
+            let mut search_context = SearchContext::new(
+                query.clone(),
+                top,
+                &index.index,
+                get_pooled_scores(),
+                &is_stopped,
+            );
+            search_context.use_pruning = false;
+            let mut exhaustive = search_context.search(&match_all);
+
+            // scores may differ slightly, as they are summed in a different order
+            pruned.sort_unstable_by_key(|point| point.idx);
+            exhaustive.sort_unstable_by_key(|point| point.idx);
+            assert_eq!(pruned.len(), exhaustive.len());
+            for (pruned, exhaustive) in pruned.iter().zip(&exhaustive) {
+                assert_eq!(pruned.idx, exhaustive.idx);
+                assert!((pruned.score - exhaustive.score).abs() < 1e-3);
+            }
         }
-
-        let index = TestIndex::<I>::from_ram({
-            let mut builder = InvertedIndexBuilder::new();
-            builder.add(1, [(1, 10.0)].into());
-            builder.add(2, [(1, 20.0)].into());
-            builder.add(3, [(1, 20.0)].into());
-            builder.add(4, [(1, 10.0)].into());
-            builder.add(5, [(3, 10.0)].into());
-            builder.add(6, [(1, 20.0), (2, 20.0), (3, 20.0)].into());
-            builder.add(7, [(1, 40.0), (2, 30.0), (3, 30.0)].into());
-            builder.build()
-        });
-
-        let is_stopped = AtomicBool::new(false);
-        let mut search_context = SearchContext::new(
-            RemappedSparseVector {
-                indices: vec![1, 2, 3],
-                values: vec![1.0, 1.0, 1.0],
-            },
-            1,
-            &index.index,
-            get_pooled_scores(),
-            &is_stopped,
-        );
-
-        // one would expect this to prune up to `6` but it does not happen it practice because we are under pruning by design
-        // we should actually check the best score up to `6` - 1 only instead of the max possible score (40.0)
-        assert!(!search_context.prune_longest_posting_list(30.0));
-
-        assert!(search_context.prune_longest_posting_list(40.0));
-        // the longest posting list was pruned to the end
-        assert_eq!(
-            search_context.postings_iterators[0]
-                .posting_list_iterator
-                .len_to_end(),
-            2 // 6, 7
-        );
     }
 
     /// Generates a random inverted index with `num_vectors` vectors
@@ -837,45 +780,6 @@ mod tests {
         inverted_index_ram
     }
 
-    #[test]
-    fn promote_longest_test<I: InvertedIndex>() {
-        let index = TestIndex::<I>::from_ram({
-            let mut builder = InvertedIndexBuilder::new();
-            builder.add(1, [(1, 10.0), (2, 10.0), (3, 10.0)].into());
-            builder.add(2, [(1, 20.0), (3, 20.0)].into());
-            builder.add(3, [(2, 30.0), (3, 30.0)].into());
-            builder.build()
-        });
-
-        let is_stopped = AtomicBool::new(false);
-        let mut search_context = SearchContext::new(
-            RemappedSparseVector {
-                indices: vec![1, 2, 3],
-                values: vec![1.0, 1.0, 1.0],
-            },
-            3,
-            &index.index,
-            get_pooled_scores(),
-            &is_stopped,
-        );
-
-        assert_eq!(
-            search_context.postings_iterators[0]
-                .posting_list_iterator
-                .len_to_end(),
-            2
-        );
-
-        search_context.promote_longest_posting_lists_to_the_front();
-
-        assert_eq!(
-            search_context.postings_iterators[0]
-                .posting_list_iterator
-                .len_to_end(),
-            3
-        );
-    }
-
     #[test]
     fn plain_search_all_test<I: InvertedIndex>() {
         let index = TestIndex::<I>::from_ram({
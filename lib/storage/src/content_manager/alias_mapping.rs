@@ -3,6 +3,8 @@ use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Utc};
+use collection::operations::types::AliasChange;
 use collection::shards::CollectionId;
 use io::file_operations::{atomic_save_json, read_json};
 use serde::{Deserialize, Serialize};
@@ -11,6 +13,11 @@ use crate::content_manager::errors::StorageError;
 
 pub const ALIAS_MAPPING_CONFIG_FILE: &str = "data.json";
 
+pub const ALIAS_HISTORY_FILE: &str = "history.json";
+
+/// Max number of changes kept in the history of an alias
+pub const MAX_ALIAS_HISTORY_LEN: usize = 100;
+
 type Alias = String;
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
@@ -26,13 +33,44 @@ impl AliasMapping {
     }
 }
 
+/// Changes of the collections aliases point to, oldest first.
+/// Part of the consensus state, so all peers keep the same history.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct AliasHistory(HashMap<Alias, Vec<AliasChange>>);
+
+impl AliasHistory {
+    pub fn load(path: &Path) -> Result<Self, StorageError> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(path, self)?)
+    }
+
+    pub fn get(&self, alias: &str) -> &[AliasChange] {
+        self.0.get(alias).map_or(&[], Vec::as_slice)
+    }
+
+    fn record(&mut self, alias: &str, change: AliasChange) {
+        let changes = self.0.entry(alias.to_string()).or_default();
+        changes.push(change);
+        if changes.len() > MAX_ALIAS_HISTORY_LEN {
+            changes.drain(..changes.len() - MAX_ALIAS_HISTORY_LEN);
+        }
+    }
+}
+
 /// Persists mapping between alias and collection name. The data is assumed to be relatively small.
 /// - Reads are served from memory.
 /// - Writes are durably saved.
+///
+/// Changes of the mapping are recorded in the alias history, saved next to the mapping.
 #[derive(Debug)]
 pub struct AliasPersistence {
     data_path: PathBuf,
     alias_mapping: AliasMapping,
+    history_path: PathBuf,
+    alias_history: AliasHistory,
 }
 
 impl AliasPersistence {
@@ -56,9 +94,18 @@ impl AliasPersistence {
         }
         let data_path = Self::init_file(&dir_path)?;
         let alias_mapping = AliasMapping::load(&data_path)?;
+        // Aliases created by older versions have no history
+        let history_path = dir_path.join(ALIAS_HISTORY_FILE);
+        let alias_history = if history_path.exists() {
+            AliasHistory::load(&history_path)?
+        } else {
+            AliasHistory::default()
+        };
         Ok(AliasPersistence {
             data_path,
             alias_mapping,
+            history_path,
+            alias_history,
         })
     }
 
@@ -66,17 +113,42 @@ impl AliasPersistence {
         self.alias_mapping.0.get(alias).cloned()
     }
 
-    pub fn insert(&mut self, alias: String, collection_name: String) -> Result<(), StorageError> {
+    pub fn insert(
+        &mut self,
+        alias: String,
+        collection_name: String,
+        changed_at: Option<DateTime<Utc>>,
+    ) -> Result<(), StorageError> {
+        self.alias_history.record(
+            &alias,
+            AliasChange {
+                collection_name: Some(collection_name.clone()),
+                changed_at,
+            },
+        );
         self.alias_mapping.0.insert(alias, collection_name);
         self.alias_mapping.save(&self.data_path)?;
+        self.alias_history.save(&self.history_path)?;
         Ok(())
     }
 
-    pub fn remove(&mut self, alias: &str) -> Result<Option<String>, StorageError> {
+    pub fn remove(
+        &mut self,
+        alias: &str,
+        changed_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<String>, StorageError> {
         let output = self.alias_mapping.0.remove(alias);
 
         if output.is_some() {
+            self.alias_history.record(
+                alias,
+                AliasChange {
+                    collection_name: None,
+                    changed_at,
+                },
+            );
             self.alias_mapping.save(&self.data_path)?;
+            self.alias_history.save(&self.history_path)?;
         }
 
         Ok(output)
@@ -84,13 +156,24 @@ impl AliasPersistence {
 
     /// Removes all aliases for a given collection.
     pub fn remove_collection(&mut self, collection_name: &str) -> Result<(), StorageError> {
-        let prev_len = self.alias_mapping.0.len();
+        let removed_aliases = self.collection_aliases(collection_name);
 
-        self.alias_mapping.0.retain(|_, v| v != collection_name);
+        if removed_aliases.is_empty() {
+            return Ok(());
+        }
 
-        if prev_len != self.alias_mapping.0.len() {
-            self.alias_mapping.save(&self.data_path)?;
+        for alias in &removed_aliases {
+            self.alias_mapping.0.remove(alias);
+            self.alias_history.record(
+                alias,
+                AliasChange {
+                    collection_name: None,
+                    changed_at: None,
+                },
+            );
         }
+        self.alias_mapping.save(&self.data_path)?;
+        self.alias_history.save(&self.history_path)?;
 
         Ok(())
     }
@@ -99,21 +182,44 @@ impl AliasPersistence {
         &mut self,
         old_alias_name: &str,
         new_alias_name: String,
+        changed_at: Option<DateTime<Utc>>,
     ) -> Result<(), StorageError> {
         match self.get(old_alias_name) {
             None => Err(StorageError::NotFound {
                 description: format!("Alias {old_alias_name} does not exists!"),
             }),
             Some(collection_name) => {
+                // The history is kept per alias name: the old name is removed, the new one points
+                // to the collection from now on
+                self.alias_history.record(
+                    old_alias_name,
+                    AliasChange {
+                        collection_name: None,
+                        changed_at,
+                    },
+                );
+                self.alias_history.record(
+                    &new_alias_name,
+                    AliasChange {
+                        collection_name: Some(collection_name.clone()),
+                        changed_at,
+                    },
+                );
                 self.alias_mapping.0.remove(old_alias_name);
                 self.alias_mapping.0.insert(new_alias_name, collection_name);
                 // 'remove' & 'insert' saved atomically
                 self.alias_mapping.save(&self.data_path)?;
+                self.alias_history.save(&self.history_path)?;
                 Ok(())
             }
         }
     }
 
+    /// Changes of the collection the alias points to, oldest first
+    pub fn alias_history(&self, alias: &str) -> &[AliasChange] {
+        self.alias_history.get(alias)
+    }
+
     pub fn collection_aliases(&self, collection_name: &str) -> Vec<String> {
         let mut result = vec![];
         for (alias, target_collection) in self.alias_mapping.0.iter() {
@@ -134,6 +240,16 @@ impl AliasPersistence {
         Ok(())
     }
 
+    pub fn history_state(&self) -> &AliasHistory {
+        &self.alias_history
+    }
+
+    pub fn apply_history_state(&mut self, alias_history: AliasHistory) -> Result<(), StorageError> {
+        self.alias_history = alias_history;
+        self.alias_history.save(&self.history_path)?;
+        Ok(())
+    }
+
     pub fn check_alias_exists(&self, alias: &str) -> bool {
         self.alias_mapping.0.contains_key(alias)
     }
@@ -1,12 +1,14 @@
 use std::collections::BTreeMap;
 
+use chrono::{DateTime, Utc};
 use collection::config::{CollectionConfig, ShardingMethod};
 use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use collection::operations::hybrid_vectors::hybrid_vectors_params;
 use collection::operations::types::{
-    SparseVectorParams, SparseVectorsConfig, VectorsConfig, VectorsConfigDiff,
+    HybridVectorParams, SparseVectorParams, SparseVectorsConfig, VectorsConfig, VectorsConfigDiff,
 };
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::resharding::ReshardKey;
@@ -165,6 +167,11 @@ pub struct CreateCollection {
     /// Sparse vector data config.
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Hybrid vectors: pairs of a dense and a sparse vector, which queries target by one name.
+    /// Each adds a named vector `<name>-dense` and a sparse vector `<name>-sparse`.
+    #[serde(default)]
+    #[validate]
+    pub hybrid_vectors: Option<BTreeMap<String, HybridVectorParams>>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -211,6 +218,7 @@ pub struct UpdateCollection {
     #[serde(alias = "optimizer_config")]
     pub optimizers_config: Option<OptimizersConfigDiff>, // TODO: Allow updates for other configuration params as well
     /// Collection base params. If none - it is left unchanged.
+    #[validate]
     pub params: Option<CollectionParamsDiff>,
     /// HNSW parameters to update for the collection index. If none - it is left unchanged.
     #[validate]
@@ -285,6 +293,10 @@ impl UpdateCollectionOperation {
 #[serde(rename_all = "snake_case")]
 pub struct ChangeAliasesOperation {
     pub actions: Vec<AliasOperations>,
+    /// Time of the change, set by the peer proposing the operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub changed_at: Option<DateTime<Utc>>,
 }
 
 /// Operation for deleting collection with given name
@@ -370,6 +382,43 @@ pub struct DropPayloadIndex {
     pub field_name: PayloadKeyType,
 }
 
+/// Primary cluster to keep a collection as a read-only warm standby of
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct StandbyConfig {
+    /// REST URL of the primary cluster, e.g. `http://primary:6333`
+    #[validate(length(min = 1))]
+    pub url: String,
+    /// Name of the collection on the primary cluster. Default: the name of the standby collection
+    #[validate(length(min = 1, max = 255))]
+    pub collection: Option<String>,
+    /// API key of the primary cluster
+    pub api_key: Option<String>,
+    /// Seconds between synchronizations with the primary collection. Default: 60
+    ///
+    /// Each synchronization reads the changes of the primary since the previous one. If the
+    /// change feed can't be used, all points of both collections are scrolled and compared
+    /// instead, which is expensive for large collections.
+    #[validate(range(min = 1))]
+    pub interval_sec: Option<u64>,
+}
+
+/// Change of a standby collection, applied by all peers
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum StandbyUpdate {
+    /// Follow the primary collection, and reject writes from users
+    Follow(StandbyConfig),
+    /// Stop following the primary collection for good, and accept writes
+    Promote,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct UpdateStandby {
+    pub collection_name: String,
+    pub update: StandbyUpdate,
+}
+
 /// Enumeration of all possible collection update operations
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -385,6 +434,7 @@ pub enum CollectionMetaOperations {
     DropShardKey(DropShardKey),
     CreatePayloadIndex(CreatePayloadIndex),
     DropPayloadIndex(DropPayloadIndex),
+    UpdateStandby(UpdateStandby),
     Nop { token: usize }, // Empty operation
 }
 
@@ -392,6 +442,13 @@ pub enum CollectionMetaOperations {
 /// for the new collection
 impl From<CollectionConfig> for CreateCollection {
     fn from(value: CollectionConfig) -> Self {
+        let hybrid_vectors = value.params.hybrid_vectors.as_ref().map(|hybrid_vectors| {
+            hybrid_vectors_params(
+                hybrid_vectors,
+                &value.params.vectors,
+                value.params.sparse_vectors.as_ref(),
+            )
+        });
         Self {
             vectors: value.params.vectors,
             shard_number: Some(value.params.shard_number.get()),
@@ -405,6 +462,7 @@ impl From<CollectionConfig> for CreateCollection {
             init_from: None,
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
+            hybrid_vectors,
         }
     }
 }
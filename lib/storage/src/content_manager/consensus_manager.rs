@@ -24,9 +24,10 @@ use tokio::sync::broadcast::Receiver;
 use tokio::time::error::Elapsed;
 use tonic::transport::Uri;
 
-use super::alias_mapping::AliasMapping;
+use super::alias_mapping::{AliasHistory, AliasMapping};
 use super::consensus_ops::{ConsensusOperations, SnapshotStatus};
 use super::errors::StorageError;
+use super::toc::standby::Standbys;
 use super::CollectionContainer;
 use crate::content_manager::consensus::consensus_wal::ConsensusOpWal;
 use crate::content_manager::consensus::entry_queue::EntryId;
@@ -59,6 +60,12 @@ pub struct SnapshotData {
 pub struct CollectionsSnapshot {
     pub collections: HashMap<CollectionId, collection_state::State>,
     pub aliases: AliasMapping,
+    /// Missing in snapshots of older versions
+    #[serde(default)]
+    pub alias_history: AliasHistory,
+    /// Missing in snapshots of older versions
+    #[serde(default)]
+    pub standbys: Standbys,
 }
 
 impl TryFrom<&[u8]> for SnapshotData {
@@ -193,6 +200,19 @@ impl<C: CollectionContainer> ConsensusManager<C> {
             .collect()
     }
 
+    /// Leader of the consensus, if known to this peer
+    pub fn leader_id(&self) -> Option<PeerId> {
+        self.soft_state
+            .read()
+            .as_ref()
+            .map(|state| state.leader_id)
+            .filter(|leader_id| *leader_id != raft::INVALID_ID)
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.leader_id() == Some(self.this_peer_id())
+    }
+
     pub fn first_voter(&self) -> PeerId {
         match self.first_voter.read().as_ref() {
             Some(id) => *id,
@@ -1,5 +1,7 @@
+use api::grpc::ERROR_CODE_METADATA_KEY;
 use collection::operations::conversions::sharding_method_from_proto;
-use collection::operations::types::SparseVectorsConfig;
+use collection::operations::types::{HybridVectorParams, SparseVectorsConfig};
+use tonic::metadata::MetadataValue;
 use tonic::Status;
 
 use crate::content_manager::collection_meta_ops::{
@@ -22,8 +24,16 @@ pub fn error_to_status(error: StorageError) -> tonic::Status {
         StorageError::ChecksumMismatch { .. } => tonic::Code::DataLoss,
         StorageError::Forbidden { .. } => tonic::Code::PermissionDenied,
         StorageError::PreconditionFailed { .. } => tonic::Code::FailedPrecondition,
+        StorageError::VectorDimensionMismatch { .. } => tonic::Code::InvalidArgument,
+        StorageError::PayloadIndexMissing { .. } => tonic::Code::InvalidArgument,
+        StorageError::ShardUnavailable { .. } => tonic::Code::Unavailable,
     };
-    tonic::Status::new(error_code, format!("{error}"))
+    let mut status = tonic::Status::new(error_code, format!("{error}"));
+    status.metadata_mut().insert(
+        ERROR_CODE_METADATA_KEY,
+        MetadataValue::from_static(error.code().as_str()),
+    );
+    status
 }
 
 impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
@@ -42,6 +52,16 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .sparse_vectors_config
                     .map(|v| SparseVectorsConfig::try_from(v).map(|SparseVectorsConfig(x)| x))
                     .transpose()?,
+                hybrid_vectors: value
+                    .hybrid_vectors_config
+                    .map(|config| {
+                        config
+                            .map
+                            .into_iter()
+                            .map(|(name, params)| Ok((name, HybridVectorParams::try_from(params)?)))
+                            .collect::<Result<_, Status>>()
+                    })
+                    .transpose()?,
                 hnsw_config: value.hnsw_config.map(|v| v.into()),
                 wal_config: value.wal_config.map(|v| v.into()),
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
@@ -163,6 +183,9 @@ impl TryFrom<api::grpc::qdrant::ChangeAliases> for CollectionMetaOperations {
             .into_iter()
             .map(|a| a.try_into())
             .collect::<Result<_, _>>()?;
-        Ok(Self::ChangeAliases(ChangeAliasesOperation { actions }))
+        Ok(Self::ChangeAliases(ChangeAliasesOperation {
+            actions,
+            changed_at: None,
+        }))
     }
 }
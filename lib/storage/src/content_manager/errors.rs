@@ -1,6 +1,7 @@
 use std::backtrace::Backtrace;
 use std::io::Error as IoError;
 
+use api::grpc::models::ErrorCode;
 use collection::operations::types::CollectionError;
 use io::file_operations::FileStorageError;
 use tempfile::PersistError;
@@ -32,6 +33,12 @@ pub enum StorageError {
     Forbidden { description: String },
     #[error("Pre-condition failure: {description}")]
     PreconditionFailed { description: String }, // system is not in the state to perform the operation
+    #[error("Wrong input: {description}")]
+    VectorDimensionMismatch { description: String },
+    #[error("Wrong input: {description}")]
+    PayloadIndexMissing { description: String },
+    #[error("Service internal error: {description}")]
+    ShardUnavailable { description: String },
 }
 
 impl StorageError {
@@ -79,6 +86,25 @@ impl StorageError {
         }
     }
 
+    /// Machine-readable code of the error, returned by the APIs
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            StorageError::BadInput { .. } => ErrorCode::BadRequest,
+            StorageError::AlreadyExists { .. } => ErrorCode::AlreadyExists,
+            StorageError::NotFound { .. } => ErrorCode::NotFound,
+            StorageError::ServiceError { .. } => ErrorCode::InternalError,
+            StorageError::BadRequest { .. } => ErrorCode::BadRequest,
+            StorageError::Locked { .. } => ErrorCode::Locked,
+            StorageError::Timeout { .. } => ErrorCode::Timeout,
+            StorageError::ChecksumMismatch { .. } => ErrorCode::ChecksumMismatch,
+            StorageError::Forbidden { .. } => ErrorCode::Forbidden,
+            StorageError::PreconditionFailed { .. } => ErrorCode::PreconditionFailed,
+            StorageError::VectorDimensionMismatch { .. } => ErrorCode::VectorDimMismatch,
+            StorageError::PayloadIndexMissing { .. } => ErrorCode::PayloadIndexMissing,
+            StorageError::ShardUnavailable { .. } => ErrorCode::ShardUnavailable,
+        }
+    }
+
     /// Used to override the `description` field of the resulting `StorageError`
     pub fn from_inconsistent_shard_failure(
         err: CollectionError,
@@ -94,6 +120,9 @@ impl StorageError {
             CollectionError::PointNotFound { .. } => StorageError::NotFound {
                 description: overriding_description,
             },
+            CollectionError::ReferencedPointsNotFound { .. } => StorageError::NotFound {
+                description: overriding_description,
+            },
             CollectionError::ServiceError { backtrace, .. } => StorageError::ServiceError {
                 description: overriding_description,
                 backtrace,
@@ -131,6 +160,17 @@ impl StorageError {
                 description: overriding_description,
                 backtrace: None,
             },
+            CollectionError::VectorDimensionMismatch { .. } => {
+                StorageError::VectorDimensionMismatch {
+                    description: overriding_description,
+                }
+            }
+            CollectionError::PayloadIndexMissing { .. } => StorageError::PayloadIndexMissing {
+                description: overriding_description,
+            },
+            CollectionError::ShardUnavailable { .. } => StorageError::ShardUnavailable {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -145,6 +185,9 @@ impl From<CollectionError> for StorageError {
             CollectionError::PointNotFound { .. } => StorageError::NotFound {
                 description: format!("{err}"),
             },
+            CollectionError::ReferencedPointsNotFound { .. } => StorageError::NotFound {
+                description: format!("{err}"),
+            },
             CollectionError::ServiceError { error, backtrace } => StorageError::ServiceError {
                 description: error,
                 backtrace,
@@ -179,6 +222,15 @@ impl From<CollectionError> for StorageError {
                 description: format!("{err}"),
                 backtrace: None,
             },
+            CollectionError::VectorDimensionMismatch { description } => {
+                StorageError::VectorDimensionMismatch { description }
+            }
+            CollectionError::PayloadIndexMissing { description } => {
+                StorageError::PayloadIndexMissing { description }
+            }
+            CollectionError::ShardUnavailable { description } => {
+                StorageError::ShardUnavailable { description }
+            }
         }
     }
 }
@@ -64,6 +64,44 @@ pub async fn do_delete_collection_snapshot(
     }))
 }
 
+pub fn do_mount_collection_snapshot(
+    dispatcher: &Dispatcher,
+    access: Access,
+    collection_name: &str,
+    snapshot_name: &str,
+    lazy: bool,
+) -> Result<JoinHandle<Result<bool, StorageError>>, StorageError> {
+    let collection_pass = access
+        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?
+        .into_static();
+    let toc = dispatcher.toc(&access).clone();
+    let snapshot_name = snapshot_name.to_string();
+
+    log::info!("Mounting collection snapshot {collection_pass}:{snapshot_name}");
+    Ok(tokio::spawn(async move {
+        toc.mount_snapshot(&collection_pass, &snapshot_name, lazy)
+            .await
+    }))
+}
+
+pub fn do_unmount_collection_snapshot(
+    dispatcher: &Dispatcher,
+    access: Access,
+    collection_name: &str,
+    snapshot_name: &str,
+) -> Result<JoinHandle<Result<bool, StorageError>>, StorageError> {
+    let collection_pass = access
+        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?
+        .into_static();
+    let toc = dispatcher.toc(&access).clone();
+    let snapshot_name = snapshot_name.to_string();
+
+    log::info!("Unmounting collection snapshot {collection_pass}:{snapshot_name}");
+    Ok(tokio::spawn(async move {
+        toc.unmount_snapshot(&collection_pass, &snapshot_name).await
+    }))
+}
+
 pub async fn do_list_full_snapshots(
     toc: &TableOfContent,
     access: Access,
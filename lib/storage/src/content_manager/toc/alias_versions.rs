@@ -0,0 +1,103 @@
+//! Versioned collections behind an alias, for blue/green deployments.
+//!
+//! Versions of an alias are collections named `<alias>-v<N>`. A new version is created with the
+//! configuration of the collection the alias points to, or with a new one for a rebuild, and
+//! optionally filled with its points for a clone. The alias is switched to the new version at
+//! once, so the alias history records which version the alias pointed to over time.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::TableOfContent;
+use crate::content_manager::collection_meta_ops::CreateCollection;
+
+/// Create the next version of the collection an alias points to, and switch the alias to it.
+///
+/// Writes to the alias during a copy of the points are lost, so they should be stopped before.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct CreateAliasVersion {
+    /// Configuration of the new version.
+    /// Default: the configuration of the collection the alias points to
+    #[validate]
+    pub config: Option<CreateCollection>,
+    /// Copy the payload indexes and points of the collection the alias points to into the new
+    /// version, before the alias is switched. Default: false
+    pub copy_points: Option<bool>,
+}
+
+/// New version of the collection an alias points to
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AliasVersion {
+    pub alias_name: String,
+    /// Collection the alias points to now
+    pub collection_name: String,
+    /// Collection the alias pointed to before, if any
+    pub previous_collection_name: Option<String>,
+    pub version: u64,
+}
+
+/// Name of the collection of a version of an alias
+pub fn alias_version_name(alias_name: &str, version: u64) -> String {
+    format!("{alias_name}-v{version}")
+}
+
+/// Version of an alias the collection is named after, if any
+pub fn parse_alias_version(alias_name: &str, collection_name: &str) -> Option<u64> {
+    let version = collection_name
+        .strip_prefix(alias_name)?
+        .strip_prefix("-v")?;
+    // Reject signs and leading zeros, so that names and versions map one to one
+    if version.starts_with(['+', '0']) {
+        return None;
+    }
+    version.parse().ok()
+}
+
+impl TableOfContent {
+    /// Collection the alias points to, if the alias exists
+    pub async fn alias_collection(&self, alias_name: &str) -> Option<String> {
+        self.alias_persistence.read().await.get(alias_name)
+    }
+
+    /// Next version of the alias: one above the versions of existing collections, and of the
+    /// collections the alias pointed to before, so versions of deleted collections are not reused
+    pub async fn next_alias_version(&self, alias_name: &str) -> u64 {
+        let collections = self.collections.read().await;
+        let alias_persistence = self.alias_persistence.read().await;
+
+        let history = alias_persistence
+            .alias_history(alias_name)
+            .iter()
+            .filter_map(|change| change.collection_name.as_deref());
+
+        collections
+            .keys()
+            .map(String::as_str)
+            .chain(history)
+            .filter_map(|collection_name| parse_alias_version(alias_name, collection_name))
+            .max()
+            .map_or(1, |version| version + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alias_version_name() {
+        assert_eq!(alias_version_name("products", 3), "products-v3");
+        assert_eq!(parse_alias_version("products", "products-v3"), Some(3));
+        assert_eq!(parse_alias_version("products", "products-v12"), Some(12));
+
+        assert_eq!(parse_alias_version("products", "products"), None);
+        assert_eq!(parse_alias_version("products", "products-v"), None);
+        assert_eq!(parse_alias_version("products", "products-v03"), None);
+        assert_eq!(parse_alias_version("products", "products-v+3"), None);
+        assert_eq!(parse_alias_version("products", "products-v3a"), None);
+        assert_eq!(parse_alias_version("products", "products-x-v3"), None);
+        assert_eq!(parse_alias_version("products-x", "products-v3"), None);
+    }
+}
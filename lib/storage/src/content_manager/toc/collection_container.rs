@@ -119,9 +119,12 @@ impl TableOfContent {
         for (id, collection) in self.collections.read().await.iter() {
             collections.insert(id.clone(), collection.state().await);
         }
+        let alias_persistence = self.alias_persistence.read().await;
         consensus_manager::CollectionsSnapshot {
             collections,
-            aliases: self.alias_persistence.read().await.state().clone(),
+            aliases: alias_persistence.state().clone(),
+            alias_history: alias_persistence.history_state().clone(),
+            standbys: self.standbys_state(),
         }
     }
 
@@ -235,11 +238,12 @@ impl TableOfContent {
                 }
             }
 
-            // Apply alias mapping
-            self.alias_persistence
-                .write()
-                .await
-                .apply_state(data.aliases)?;
+            // Apply alias mapping and its history
+            let mut alias_persistence = self.alias_persistence.write().await;
+            alias_persistence.apply_state(data.aliases)?;
+            alias_persistence.apply_history_state(data.alias_history)?;
+
+            self.apply_standbys_state(data.standbys)?;
 
             Ok(())
         })
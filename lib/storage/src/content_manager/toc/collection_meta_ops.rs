@@ -10,6 +10,7 @@ use collection::shards::transfer::ShardTransfer;
 use collection::shards::{transfer, CollectionId};
 use uuid::Uuid;
 
+use super::events::CollectionEventKind;
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
 use crate::content_manager::collections_ops::Checker as _;
@@ -111,6 +112,10 @@ impl TableOfContent {
                     .await
                     .map(|()| true)
             }
+            CollectionMetaOperations::UpdateStandby(update_standby) => {
+                log::debug!("Update standby {:?}", update_standby);
+                self.update_standby(update_standby).map(|()| true)
+            }
         }
     }
 
@@ -197,6 +202,7 @@ impl TableOfContent {
             issues::publish(CollectionDeletedEvent {
                 collection_id: collection_name.to_string(),
             });
+            self.publish_collection_event(collection_name, CollectionEventKind::Deleted);
 
             // At this point collection is removed from memory and moved to ".deleted" folder.
             // Next time we load service the collection will not appear in the list of collections.
@@ -235,6 +241,7 @@ impl TableOfContent {
         // Prevent search on partially switched collections
         let collection_lock = self.collections.write().await;
         let mut alias_lock = self.alias_persistence.write().await;
+        let changed_at = operation.changed_at;
         for action in operation.actions {
             match action {
                 AliasOperations::CreateAlias(CreateAliasOperation {
@@ -251,12 +258,12 @@ impl TableOfContent {
                         .validate_collection_not_exists(&alias_name)
                         .await?;
 
-                    alias_lock.insert(alias_name, collection_name)?;
+                    alias_lock.insert(alias_name, collection_name, changed_at)?;
                 }
                 AliasOperations::DeleteAlias(DeleteAliasOperation {
                     delete_alias: DeleteAlias { alias_name },
                 }) => {
-                    alias_lock.remove(&alias_name)?;
+                    alias_lock.remove(&alias_name, changed_at)?;
                 }
                 AliasOperations::RenameAlias(RenameAliasOperation {
                     rename_alias:
@@ -265,7 +272,7 @@ impl TableOfContent {
                             new_alias_name,
                         },
                 }) => {
-                    alias_lock.rename_alias(&old_alias_name, new_alias_name)?;
+                    alias_lock.rename_alias(&old_alias_name, new_alias_name, changed_at)?;
                 }
             };
         }
@@ -502,7 +509,13 @@ impl TableOfContent {
                     &transfer.key(),
                     &collection.state().await.transfers,
                 )?;
+                let kind = CollectionEventKind::TransferCompleted {
+                    shard_id: transfer.shard_id,
+                    from: transfer.from,
+                    to: transfer.to,
+                };
                 collection.finish_shard_transfer(transfer, None).await?;
+                self.publish_collection_event(&collection_id, kind);
             }
             ShardTransferOperations::RecoveryToPartial(transfer)
             | ShardTransferOperations::SnapshotRecovered(transfer) => {
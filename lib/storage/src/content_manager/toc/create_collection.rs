@@ -4,6 +4,7 @@ use std::num::NonZeroU32;
 use collection::collection::Collection;
 use collection::config::{self, CollectionConfig, CollectionParams, ShardingMethod};
 use collection::operations::config_diff::DiffConfig as _;
+use collection::operations::hybrid_vectors::add_hybrid_vectors;
 use collection::operations::types::{
     check_sparse_compatible, CollectionResult, SparseVectorParams, VectorsConfig,
 };
@@ -12,6 +13,7 @@ use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
 
+use super::events::CollectionEventKind;
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
 use crate::content_manager::collections_ops::Checker as _;
@@ -43,7 +45,8 @@ impl TableOfContent {
             write_consistency_factor,
             init_from,
             quantization_config,
-            sparse_vectors,
+            mut sparse_vectors,
+            hybrid_vectors,
         } = operation;
 
         self.collections
@@ -63,6 +66,13 @@ impl TableOfContent {
             )));
         }
 
+        // Hybrid vectors are stored as a named and a sparse vector each
+        let hybrid_vectors = hybrid_vectors
+            .map(|hybrid_vectors| {
+                add_hybrid_vectors(hybrid_vectors, &mut vectors, &mut sparse_vectors)
+            })
+            .transpose()?;
+
         if let Some(init_from) = &init_from {
             self.check_collections_compatibility(&vectors, &sparse_vectors, &init_from.collection)
                 .await?;
@@ -150,6 +160,13 @@ impl TableOfContent {
                 },
             )?,
             read_fan_out_factor: None,
+            point_history_size: None,
+            vector_routing: None,
+            frozen: false,
+            filter_templates: None,
+            hybrid_vectors,
+            search_pools: None,
+            payload_compression: None,
         };
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
@@ -229,6 +246,8 @@ impl TableOfContent {
 
         drop(collection_create_guard);
 
+        self.publish_collection_event(collection_name, CollectionEventKind::Created);
+
         // Notify the collection is created and ready to use
         for shard_id in local_shards {
             self.on_peer_created(collection_name.to_string(), self.this_peer_id, shard_id)
@@ -0,0 +1,313 @@
+//! Detection of points with identical vectors.
+//!
+//! Buggy ingestion runs may write the same data several times under different ids. The collection
+//! is scanned once, and points are grouped by a hash of their vectors. Groups are verified by
+//! comparing the vectors of their points exactly, before they are reported or deleted.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use api::rest::{Vector, VectorStruct};
+use collection::operations::point_ops::{PointOperations, WriteOrdering};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{PointRequestInternal, ScrollRequestInternal};
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use schemars::JsonSchema;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{PointIdType, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::rbac::{Access, AccessRequirements};
+
+/// Default number of points read or deleted per request
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// Find points with identical vectors in a collection.
+///
+/// Writes to the collection during the scan may be missed, so they should be stopped before.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct FindDuplicates {
+    /// Compare only this vector of the points. Default: all vectors
+    pub using: Option<String>,
+    /// Delete all points of each group of duplicates, except the first one. Default: false
+    pub delete: Option<bool>,
+    /// Number of points read or deleted per request. Default: 1000
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+}
+
+impl FindDuplicates {
+    /// Scan reads all points of the collection, and deleting them requires write access
+    pub fn access_requirements(&self) -> AccessRequirements {
+        if self.delete.unwrap_or(false) {
+            AccessRequirements::new().whole().write()
+        } else {
+            AccessRequirements::new().whole()
+        }
+    }
+}
+
+/// Points with identical vectors found in a collection
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DuplicatesReport {
+    /// Number of scanned points with vectors
+    pub points_count: usize,
+    /// Ids of the points with identical vectors, ordered by id. The first point of each group is
+    /// the one kept on deletion.
+    pub duplicates: Vec<Vec<PointIdType>>,
+    /// Number of deleted points
+    pub deleted: usize,
+}
+
+/// Hash of the vectors of a point, independent of the order of named vectors
+fn hash_vectors(vectors: &VectorStruct) -> u64 {
+    fn hash_dense(vector: &[f32], hasher: &mut impl Hasher) {
+        vector.len().hash(hasher);
+        vector.iter().for_each(|value| value.to_bits().hash(hasher));
+    }
+
+    fn hash_multi_dense(vectors: &[Vec<f32>], hasher: &mut impl Hasher) {
+        vectors.len().hash(hasher);
+        vectors.iter().for_each(|vector| hash_dense(vector, hasher));
+    }
+
+    let mut hasher = DefaultHasher::new();
+    match vectors {
+        VectorStruct::Single(vector) => {
+            0u8.hash(&mut hasher);
+            hash_dense(vector, &mut hasher);
+        }
+        VectorStruct::MultiDense(vectors) => {
+            1u8.hash(&mut hasher);
+            hash_multi_dense(vectors, &mut hasher);
+        }
+        VectorStruct::Named(named) => {
+            2u8.hash(&mut hasher);
+            let mut entries: Vec<_> = named.iter().collect();
+            entries.sort_unstable_by_key(|(name, _)| *name);
+
+            entries.len().hash(&mut hasher);
+            for (name, vector) in entries {
+                name.hash(&mut hasher);
+                match vector {
+                    Vector::Dense(vector) => {
+                        0u8.hash(&mut hasher);
+                        hash_dense(vector, &mut hasher);
+                    }
+                    Vector::Sparse(vector) => {
+                        1u8.hash(&mut hasher);
+                        vector.indices.hash(&mut hasher);
+                        hash_dense(&vector.values, &mut hasher);
+                    }
+                    Vector::MultiDense(vectors) => {
+                        2u8.hash(&mut hasher);
+                        hash_multi_dense(vectors, &mut hasher);
+                    }
+                }
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// Split points with equal hashes into groups of points with exactly equal vectors
+fn split_equal(points: Vec<(PointIdType, VectorStructInternal)>) -> Vec<Vec<PointIdType>> {
+    let mut groups: Vec<(VectorStructInternal, Vec<PointIdType>)> = Vec::new();
+    for (point_id, vectors) in points {
+        match groups.iter_mut().find(|(group, _)| *group == vectors) {
+            Some((_, ids)) => ids.push(point_id),
+            None => groups.push((vectors, vec![point_id])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, mut ids)| {
+            ids.sort_unstable();
+            ids
+        })
+        .filter(|ids| ids.len() > 1)
+        .collect()
+}
+
+impl TableOfContent {
+    /// Scan the collection for points with identical vectors, and delete all but one point of
+    /// each group if requested
+    pub async fn find_duplicate_points(
+        &self,
+        collection_name: &str,
+        request: FindDuplicates,
+        access: Access,
+    ) -> Result<DuplicatesReport, StorageError> {
+        let collection_pass =
+            access.check_collection_access(collection_name, request.access_requirements())?;
+
+        let batch_size = request.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+        let with_vector = match &request.using {
+            Some(vector_name) => WithVector::Selector(vec![vector_name.clone()]),
+            None => WithVector::Bool(true),
+        };
+
+        let mut points_count = 0;
+        let mut first_points: HashMap<u64, PointIdType> = HashMap::new();
+        let mut candidates: HashMap<u64, Vec<PointIdType>> = HashMap::new();
+
+        let mut offset = None;
+        loop {
+            let request = ScrollRequestInternal {
+                offset,
+                limit: Some(batch_size),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: with_vector.clone(),
+                order_by: None,
+            };
+
+            // Don't hold the collection between batches
+            let page = self
+                .get_collection(&collection_pass)
+                .await?
+                .scroll_by(request, None, &ShardSelectorInternal::All)
+                .await?;
+
+            for record in page.points {
+                let Some(vectors) = record.vector.filter(|vectors| !vectors.is_empty()) else {
+                    continue;
+                };
+                points_count += 1;
+
+                let hash = hash_vectors(&vectors);
+                match first_points.get(&hash) {
+                    None => {
+                        first_points.insert(hash, record.id);
+                    }
+                    Some(first_point) => candidates
+                        .entry(hash)
+                        .or_insert_with(|| vec![*first_point])
+                        .push(record.id),
+                }
+            }
+
+            offset = page.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+        drop(first_points);
+
+        // Verify the candidates, their hashes may collide
+        let mut duplicates = Vec::new();
+        for ids in candidates.into_values() {
+            let records = self
+                .get_collection(&collection_pass)
+                .await?
+                .retrieve(
+                    PointRequestInternal {
+                        ids,
+                        with_payload: Some(WithPayloadInterface::Bool(false)),
+                        with_vector: with_vector.clone(),
+                        as_of_version: None,
+                    },
+                    None,
+                    &ShardSelectorInternal::All,
+                )
+                .await?;
+            let points = records
+                .into_iter()
+                .filter_map(|record| Some((record.id, record.vector?)))
+                .collect();
+            duplicates.extend(split_equal(points));
+        }
+        duplicates.sort_unstable();
+
+        let mut deleted = 0;
+        if request.delete.unwrap_or(false) {
+            let redundant: Vec<_> = duplicates
+                .iter()
+                .flat_map(|ids| ids.iter().skip(1).copied())
+                .collect();
+
+            for ids in redundant.chunks(batch_size) {
+                let operation =
+                    CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                        ids: ids.to_vec(),
+                    });
+                self.update(
+                    collection_name,
+                    OperationWithClockTag::new(operation, None),
+                    true,
+                    WriteOrdering::default(),
+                    ShardSelectorInternal::All,
+                    access.clone(),
+                )
+                .await?;
+                deleted += ids.len();
+            }
+        }
+
+        log::info!(
+            "Found {} groups of points with identical vectors in collection {collection_name}, \
+             deleted {deleted} points",
+            duplicates.len(),
+        );
+
+        Ok(DuplicatesReport {
+            points_count,
+            duplicates,
+            deleted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_vectors() {
+        let named = |vectors: Vec<(&str, Vector)>| {
+            VectorStruct::Named(
+                vectors
+                    .into_iter()
+                    .map(|(name, vector)| (name.to_string(), vector))
+                    .collect(),
+            )
+        };
+
+        let a = named(vec![
+            ("image", Vector::Dense(vec![1.0, 2.0])),
+            ("text", Vector::Dense(vec![3.0])),
+        ]);
+        let b = named(vec![
+            ("text", Vector::Dense(vec![3.0])),
+            ("image", Vector::Dense(vec![1.0, 2.0])),
+        ]);
+        let c = named(vec![
+            ("image", Vector::Dense(vec![1.0, 2.0])),
+            ("text", Vector::Dense(vec![4.0])),
+        ]);
+
+        assert_eq!(hash_vectors(&a), hash_vectors(&b));
+        assert_ne!(hash_vectors(&a), hash_vectors(&c));
+        assert_ne!(
+            hash_vectors(&VectorStruct::Single(vec![1.0, 2.0])),
+            hash_vectors(&VectorStruct::MultiDense(vec![vec![1.0, 2.0]])),
+        );
+    }
+
+    #[test]
+    fn test_split_equal() {
+        let points = vec![
+            (3.into(), VectorStructInternal::from(vec![1.0, 2.0])),
+            (1.into(), VectorStructInternal::from(vec![1.0, 2.0])),
+            (2.into(), VectorStructInternal::from(vec![5.0, 6.0])),
+        ];
+
+        assert_eq!(split_equal(points), vec![vec![1.into(), 3.into()]]);
+    }
+}
@@ -0,0 +1,341 @@
+//! Lifecycle events of collections.
+//!
+//! Creation, deletion and completed shard transfers are published as they are applied on this
+//! peer. Status changes and finished optimizations are detected by periodically checking the
+//! local shards of each collection, if configured. Events are delivered to internal subscribers
+//! and to the configured webhooks, so that external automation doesn't have to poll the cluster
+//! API.
+//!
+//! Cluster-wide events are applied through consensus on every peer, so only the consensus leader
+//! sends them to webhooks. Around a change of the leader, such an event may be sent twice or not
+//! at all. Status changes and finished optimizations are about the local shards of a peer, so
+//! every peer sends its own.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use collection::collection_manager::optimizers::OptimizationsProgress;
+use collection::operations::types::CollectionStatus;
+use collection::shards::shard::{PeerId, ShardId};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::types::WebhookConfig;
+
+/// Max number of events not yet received by the slowest subscriber, older ones are dropped
+const EVENTS_CAPACITY: usize = 1024;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CollectionEventKind {
+    Created,
+    Deleted,
+    /// All optimizations of the local shards are finished, after at least one finished since
+    /// the previous check
+    OptimizationFinished,
+    /// Worst status of the local shards has changed
+    StatusChanged {
+        from: CollectionStatus,
+        to: CollectionStatus,
+    },
+    TransferCompleted {
+        shard_id: ShardId,
+        from: PeerId,
+        to: PeerId,
+    },
+}
+
+impl CollectionEventKind {
+    /// Whether the event is applied through consensus, and observed by all peers alike
+    pub fn is_cluster_wide(&self) -> bool {
+        match self {
+            CollectionEventKind::Created
+            | CollectionEventKind::Deleted
+            | CollectionEventKind::TransferCompleted { .. } => true,
+            CollectionEventKind::OptimizationFinished
+            | CollectionEventKind::StatusChanged { .. } => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CollectionEvent {
+    pub collection_name: String,
+    /// Peer which observed the event
+    pub peer_id: PeerId,
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: CollectionEventKind,
+}
+
+pub struct CollectionEvents {
+    sender: broadcast::Sender<CollectionEvent>,
+}
+
+impl CollectionEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENTS_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<CollectionEvent> {
+        self.sender.subscribe()
+    }
+
+    fn publish(&self, collection_name: &str, peer_id: PeerId, kind: CollectionEventKind) {
+        let event = CollectionEvent {
+            collection_name: collection_name.to_string(),
+            peer_id,
+            timestamp: Utc::now(),
+            kind,
+        };
+        // Fails only if there are no subscribers, the event is irrelevant then
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for CollectionEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of the local shards of a collection, checked periodically
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LocalState {
+    status: CollectionStatus,
+    optimizations: OptimizationsProgress,
+}
+
+/// Events derived from the change of the state of the local shards of a collection
+fn state_events(from: LocalState, to: LocalState) -> Vec<CollectionEventKind> {
+    let mut events = Vec::new();
+
+    if from.status != to.status {
+        events.push(CollectionEventKind::StatusChanged {
+            from: from.status,
+            to: to.status,
+        });
+    }
+
+    // Counters start over if shards are reloaded, e.g. recovered from a snapshot
+    if to.optimizations.running == 0 && to.optimizations.finished > from.optimizations.finished {
+        events.push(CollectionEventKind::OptimizationFinished);
+    }
+
+    events
+}
+
+struct Webhook {
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl Webhook {
+    fn new(config: &WebhookConfig) -> Result<Self, StorageError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            api_key: config.api_key.clone(),
+        })
+    }
+
+    async fn notify(&self, event: &CollectionEvent) -> Result<(), StorageError> {
+        let mut request = self.client.post(&self.url).json(event);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        request.send().await?.error_for_status()?;
+        Ok(())
+    }
+
+    /// Sends the events to the webhook in order, until the events are closed. Cluster-wide events
+    /// are only sent while `is_leader` returns true.
+    async fn run(
+        self,
+        mut events: broadcast::Receiver<CollectionEvent>,
+        is_leader: Arc<dyn Fn() -> bool + Send + Sync>,
+    ) {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::warn!("Webhook {} missed {skipped} collection events", self.url);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            };
+
+            if event.kind.is_cluster_wide() && !is_leader() {
+                continue;
+            }
+
+            if let Err(err) = self.notify(&event).await {
+                log::warn!(
+                    "Failed to notify webhook {} about event of collection {}: {err}",
+                    self.url,
+                    event.collection_name,
+                );
+            }
+        }
+    }
+}
+
+impl TableOfContent {
+    /// Subscribe to lifecycle events of all collections, starting from now
+    pub fn subscribe_collection_events(&self) -> broadcast::Receiver<CollectionEvent> {
+        self.collection_events.subscribe()
+    }
+
+    pub(super) fn publish_collection_event(
+        &self,
+        collection_name: &str,
+        kind: CollectionEventKind,
+    ) {
+        self.collection_events
+            .publish(collection_name, self.this_peer_id, kind);
+    }
+
+    /// Deliver collection events to the configured webhooks, and watch the local shards of
+    /// collections periodically. Does nothing, if collection events are not configured.
+    ///
+    /// `is_leader` tells if this peer is the consensus leader, which sends cluster-wide events.
+    pub async fn watch_collection_events(
+        self: Arc<Self>,
+        is_leader: impl Fn() -> bool + Send + Sync + 'static,
+    ) -> Result<(), StorageError> {
+        let Some(config) = self.storage_config.collection_events.clone() else {
+            return Ok(());
+        };
+
+        let is_leader: Arc<dyn Fn() -> bool + Send + Sync> = Arc::new(is_leader);
+        for webhook_config in &config.webhooks {
+            let webhook = Webhook::new(webhook_config)?;
+            tokio::spawn(webhook.run(self.subscribe_collection_events(), is_leader.clone()));
+        }
+
+        let interval = Duration::from_secs(config.check_interval_sec.max(1));
+        let mut states: HashMap<String, LocalState> = HashMap::new();
+
+        loop {
+            tokio::time::sleep(interval).await;
+
+            // Don't block changes of the collections while checking all of them
+            let collection_names: Vec<_> = self.collections.read().await.keys().cloned().collect();
+
+            let mut current = HashMap::new();
+            for collection_name in collection_names {
+                let Some(collection) = self.get_collection_opt(collection_name.clone()).await
+                else {
+                    continue;
+                };
+                let state = LocalState {
+                    status: collection.local_status().await,
+                    optimizations: collection.local_optimizations_progress().await,
+                };
+                drop(collection);
+                current.insert(collection_name, state);
+            }
+
+            // Collections created since the last check are reported once they change
+            for (collection_name, state) in &current {
+                let Some(previous) = states.get(collection_name) else {
+                    continue;
+                };
+                for kind in state_events(*previous, *state) {
+                    self.publish_collection_event(collection_name, kind);
+                }
+            }
+
+            states = current;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_events() {
+        use CollectionStatus::*;
+
+        let state = |status, running, finished| LocalState {
+            status,
+            optimizations: OptimizationsProgress { running, finished },
+        };
+
+        assert!(state_events(state(Green, 0, 0), state(Green, 0, 0)).is_empty());
+        assert_eq!(
+            state_events(state(Green, 0, 0), state(Yellow, 1, 0)),
+            vec![CollectionEventKind::StatusChanged {
+                from: Green,
+                to: Yellow,
+            }],
+        );
+
+        // Still optimizing, even though one of the optimizations finished
+        assert!(state_events(state(Yellow, 2, 0), state(Yellow, 1, 1)).is_empty());
+        assert_eq!(
+            state_events(state(Yellow, 1, 1), state(Green, 0, 2)),
+            vec![
+                CollectionEventKind::StatusChanged {
+                    from: Yellow,
+                    to: Green,
+                },
+                CollectionEventKind::OptimizationFinished,
+            ],
+        );
+
+        // Started and finished between checks, without a change of the status
+        assert_eq!(
+            state_events(state(Green, 0, 2), state(Green, 0, 3)),
+            vec![CollectionEventKind::OptimizationFinished],
+        );
+
+        // Yellow for another reason than optimizations
+        assert_eq!(
+            state_events(state(Yellow, 0, 0), state(Green, 0, 0)),
+            vec![CollectionEventKind::StatusChanged {
+                from: Yellow,
+                to: Green,
+            }],
+        );
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = CollectionEvent {
+            collection_name: "test".to_string(),
+            peer_id: 1,
+            timestamp: DateTime::from_timestamp(0, 0).unwrap(),
+            kind: CollectionEventKind::TransferCompleted {
+                shard_id: 2,
+                from: 1,
+                to: 3,
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "collection_name": "test",
+                "peer_id": 1,
+                "timestamp": "1970-01-01T00:00:00Z",
+                "event": "transfer_completed",
+                "shard_id": 2,
+                "from": 1,
+                "to": 3,
+            }),
+        );
+    }
+}
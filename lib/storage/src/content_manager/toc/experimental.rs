@@ -0,0 +1,159 @@
+//! Experimental query features.
+//!
+//! Features which are not stable yet are enabled per collection in the storage config, rather
+//! than for the whole build. This allows trialing them on one collection in production, without
+//! exposing the rest of the collections to them.
+
+use collection::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest, HybridQuery, Query,
+};
+use collection::operations::universal_query::shard_query::Fusion;
+use itertools::Itertools;
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::types::ExperimentalFeature;
+
+impl ExperimentalFeature {
+    /// Experimental feature the query depends on, if any
+    fn of_query(query: &Query) -> Option<Self> {
+        match query {
+            Query::Fusion(Fusion::Dbsf)
+            | Query::Hybrid(HybridQuery {
+                fusion: Some(Fusion::Dbsf),
+                ..
+            }) => Some(Self::DbsfFusion),
+            Query::Fusion(Fusion::Rrf)
+            | Query::Hybrid(_)
+            | Query::Vector(_)
+            | Query::OrderBy(_)
+            | Query::Sample(_) => None,
+        }
+    }
+}
+
+fn add_prefetch_features(prefetch: &CollectionPrefetch, features: &mut Vec<ExperimentalFeature>) {
+    features.extend(
+        prefetch
+            .query
+            .as_ref()
+            .and_then(ExperimentalFeature::of_query),
+    );
+    for nested in &prefetch.prefetch {
+        add_prefetch_features(nested, features);
+    }
+}
+
+/// Experimental features used by the request, or any of its prefetches
+fn used_features(request: &CollectionQueryRequest) -> Vec<ExperimentalFeature> {
+    let mut features: Vec<_> = request
+        .query
+        .as_ref()
+        .and_then(ExperimentalFeature::of_query)
+        .into_iter()
+        .collect();
+    for prefetch in &request.prefetch {
+        add_prefetch_features(prefetch, &mut features);
+    }
+    features.into_iter().unique().collect()
+}
+
+impl TableOfContent {
+    /// Check that the queries only use experimental features enabled for the collection
+    pub(super) fn check_experimental_features<'a>(
+        &self,
+        collection_name: &str,
+        requests: impl IntoIterator<Item = &'a CollectionQueryRequest>,
+    ) -> Result<(), StorageError> {
+        let enabled = self
+            .storage_config
+            .experimental_features
+            .get(collection_name);
+
+        for request in requests {
+            for feature in used_features(request) {
+                if !enabled.is_some_and(|enabled| enabled.contains(&feature)) {
+                    return Err(StorageError::bad_request(format!(
+                        "Experimental feature `{feature}` is not enabled for collection \
+                         {collection_name}, it can be enabled in `storage.experimental_features`",
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{WithPayloadInterface, WithVector};
+
+    use super::*;
+
+    fn prefetch(query: Option<Query>, prefetch: Vec<CollectionPrefetch>) -> CollectionPrefetch {
+        CollectionPrefetch {
+            prefetch,
+            query,
+            using: String::new(),
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            params: None,
+            lookup_from: None,
+            shard_selection: None,
+        }
+    }
+
+    fn request(query: Option<Query>, prefetch: Vec<CollectionPrefetch>) -> CollectionQueryRequest {
+        CollectionQueryRequest {
+            prefetch,
+            query,
+            using: String::new(),
+            filter: None,
+            propagate_filter: CollectionQueryRequest::DEFAULT_PROPAGATE_FILTER,
+            ids: None,
+            filter_template: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+            with_routing: false,
+            with_stats: false,
+            lookup_from: None,
+            budget: None,
+            rerank: None,
+            skip_missing: false,
+        }
+    }
+
+    #[test]
+    fn test_used_features() {
+        let rrf = request(
+            Some(Query::Fusion(Fusion::Rrf)),
+            vec![prefetch(None, vec![]), prefetch(None, vec![])],
+        );
+        assert!(used_features(&rrf).is_empty());
+
+        let dbsf = request(
+            Some(Query::Fusion(Fusion::Dbsf)),
+            vec![prefetch(None, vec![]), prefetch(None, vec![])],
+        );
+        assert_eq!(used_features(&dbsf), vec![ExperimentalFeature::DbsfFusion]);
+
+        // Features of nested prefetches are found, and reported once
+        let nested = request(
+            Some(Query::Fusion(Fusion::Dbsf)),
+            vec![prefetch(
+                Some(Query::Fusion(Fusion::Dbsf)),
+                vec![prefetch(None, vec![]), prefetch(None, vec![])],
+            )],
+        );
+        assert_eq!(
+            used_features(&nested),
+            vec![ExperimentalFeature::DbsfFusion],
+        );
+    }
+}
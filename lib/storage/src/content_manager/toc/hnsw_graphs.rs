@@ -0,0 +1,38 @@
+//! Periodic check of the HNSW graphs of all collections.
+//!
+//! Statistics of a graph are computed by walking all of its links, once per segment. This is too
+//! expensive for requests like collection info, so graphs are inspected in the background, and the
+//! issues about degraded graphs are raised and solved from here.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::TableOfContent;
+
+/// How often to check the HNSW graphs of all collections
+const HNSW_GRAPHS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+impl TableOfContent {
+    /// Check the HNSW graphs of the local shards of all collections periodically, raising issues
+    /// about degraded graphs.
+    pub async fn check_hnsw_graphs(self: Arc<Self>) {
+        loop {
+            tokio::time::sleep(HNSW_GRAPHS_CHECK_INTERVAL).await;
+
+            let collection_names: Vec<_> = self.collections.read().await.keys().cloned().collect();
+
+            for collection_name in collection_names {
+                let collections = self.collections.read().await;
+                let Some(collection) = collections.get(&collection_name) else {
+                    continue;
+                };
+
+                if let Err(err) = collection.check_hnsw_graphs().await {
+                    log::warn!(
+                        "Failed to check HNSW graphs of collection {collection_name}: {err}"
+                    );
+                }
+            }
+        }
+    }
+}
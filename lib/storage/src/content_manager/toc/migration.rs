@@ -0,0 +1,584 @@
+//! Migration of a collection to another Qdrant cluster.
+//!
+//! The collection is created on the target cluster with the same configuration, payload indexes
+//! and shard keys, and all points are copied into it through the public gRPC API of the target.
+//! The copy is verified by comparing the number of points and a checksum of their ids, payloads
+//! and vectors on both clusters, before an alias on the target cluster is pointed to it.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use api::grpc::conversions::{convert_shard_key_to_grpc, proto_to_payloads};
+use api::grpc::qdrant::{
+    alias_operations, read_consistency, vectors_config, AliasOperations, ChangeAliases,
+    CollectionParams, CreateAlias, CreateCollection, CreateFieldIndexCollection, CreateShardKey,
+    CreateShardKeyRequest, HealthCheckRequest, HybridVectorParams, HybridVectorParamsMap,
+    ReadConsistency, ReadConsistencyType, ScrollPoints,
+};
+use api::rest::{Vector, VectorStruct};
+use collection::collection::Collection;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CollectionError, CollectionInfo};
+use schemars::JsonSchema;
+use segment::data_types::vectors::{VectorStructInternal, DEFAULT_VECTOR_NAME};
+use segment::types::{Payload, PointIdType, ShardKey, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use validator::Validate;
+
+use super::remote_cluster::RemoteCluster;
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::rbac::CollectionPass;
+
+/// Default number of points copied to the target cluster per request
+const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// Timeout of a single request to the target cluster
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Migrate a collection to another Qdrant cluster.
+///
+/// Writes to the collection during the migration make the verification fail, so they should be
+/// stopped before.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrateCollection {
+    /// gRPC URI of the target cluster, e.g. `http://qdrant.example.com:6334`
+    #[validate(length(min = 1))]
+    pub uri: String,
+    /// Name of the collection on the target cluster. Default: the name of the migrated collection
+    #[validate(length(min = 1, max = 255))]
+    pub collection: Option<String>,
+    /// API key of the target cluster
+    pub api_key: Option<String>,
+    /// Create the collection on the target cluster, with the same configuration, payload indexes
+    /// and shard keys. Default: true
+    pub create_collection: Option<bool>,
+    /// Alias on the target cluster to point to the migrated collection, once the copy is verified
+    #[validate(length(min = 1, max = 255))]
+    pub alias: Option<String>,
+    /// Number of points copied per request. Default: 100
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+}
+
+/// Result of a verified migration of a collection
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MigrationReport {
+    /// Name of the collection on the target cluster
+    pub collection: String,
+    /// Version of Qdrant on the target cluster
+    pub target_version: String,
+    /// Number of points copied to the target cluster
+    pub points_count: usize,
+    /// Checksum of the ids, payloads and vectors of all points, equal on both clusters
+    pub checksum: String,
+    /// Alias on the target cluster, which was pointed to the migrated collection
+    pub alias: Option<String>,
+}
+
+/// Checksum of a set of points, independent of their order
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PointsChecksum {
+    count: usize,
+    hash: u64,
+}
+
+impl PointsChecksum {
+    fn add(&mut self, id: PointIdType, payload: Option<&Payload>, vectors: Option<&VectorStruct>) {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        match payload {
+            Some(payload) => hash_object(&payload.0, &mut hasher),
+            None => hash_object(&Map::new(), &mut hasher),
+        }
+        hash_vectors(vectors, &mut hasher);
+
+        self.count += 1;
+        self.hash = self.hash.wrapping_add(hasher.finish());
+    }
+}
+
+/// Hash of a JSON object, independent of the order of its keys
+fn hash_object(object: &Map<String, Value>, hasher: &mut impl Hasher) {
+    let mut entries: Vec<_> = object.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+
+    entries.len().hash(hasher);
+    for (key, value) in entries {
+        key.hash(hasher);
+        hash_value(value, hasher);
+    }
+}
+
+/// Hash of all vectors of a point, independent of the order of the named vectors
+///
+/// A single unnamed vector hashes the same as a named vector with the default name.
+fn hash_vectors(vectors: Option<&VectorStruct>, hasher: &mut impl Hasher) {
+    match vectors {
+        None => 0usize.hash(hasher),
+        Some(VectorStruct::Single(dense)) => {
+            1usize.hash(hasher);
+            DEFAULT_VECTOR_NAME.hash(hasher);
+            hash_dense_vector(dense, hasher);
+        }
+        Some(VectorStruct::MultiDense(multi_dense)) => {
+            1usize.hash(hasher);
+            DEFAULT_VECTOR_NAME.hash(hasher);
+            hash_multi_dense_vector(multi_dense, hasher);
+        }
+        Some(VectorStruct::Named(named)) => {
+            let mut entries: Vec<_> = named.iter().collect();
+            entries.sort_unstable_by_key(|(name, _)| *name);
+
+            entries.len().hash(hasher);
+            for (name, vector) in entries {
+                name.hash(hasher);
+                match vector {
+                    Vector::Dense(dense) => hash_dense_vector(dense, hasher),
+                    Vector::Sparse(sparse) => {
+                        hash_sparse_vector(&sparse.indices, &sparse.values, hasher)
+                    }
+                    Vector::MultiDense(multi_dense) => hash_multi_dense_vector(multi_dense, hasher),
+                }
+            }
+        }
+    }
+}
+
+fn hash_dense_vector(dense: &[f32], hasher: &mut impl Hasher) {
+    (0u8, dense.len()).hash(hasher);
+    dense.iter().for_each(|value| value.to_bits().hash(hasher));
+}
+
+fn hash_multi_dense_vector(multi_dense: &[Vec<f32>], hasher: &mut impl Hasher) {
+    (1u8, multi_dense.len()).hash(hasher);
+    multi_dense
+        .iter()
+        .for_each(|dense| hash_dense_vector(dense, hasher));
+}
+
+/// Hash of a sparse vector, independent of the order of its indices
+fn hash_sparse_vector(indices: &[u32], values: &[f32], hasher: &mut impl Hasher) {
+    let mut entries: Vec<_> = indices.iter().zip(values).collect();
+    entries.sort_unstable_by_key(|(index, _)| **index);
+
+    (2u8, entries.len()).hash(hasher);
+    for (index, value) in entries {
+        (index, value.to_bits()).hash(hasher);
+    }
+}
+
+fn hash_value(value: &Value, hasher: &mut impl Hasher) {
+    match value {
+        Value::Null => 0u8.hash(hasher),
+        Value::Bool(bool) => (1u8, bool).hash(hasher),
+        // gRPC transfers numbers as either `i64` or `f64`
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => (2u8, integer).hash(hasher),
+            None => (3u8, number.as_f64().map(f64::to_bits)).hash(hasher),
+        },
+        Value::String(string) => (4u8, string).hash(hasher),
+        Value::Array(array) => {
+            (5u8, array.len()).hash(hasher);
+            array.iter().for_each(|value| hash_value(value, hasher));
+        }
+        Value::Object(object) => {
+            6u8.hash(hasher);
+            hash_object(object, hasher);
+        }
+    }
+}
+
+/// Whether a cluster of the given version can receive collections from this one
+fn is_compatible_version(target_version: &str) -> bool {
+    fn major_minor(version: &str) -> Option<(u32, u32)> {
+        let mut parts = version.split('.');
+        Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+    }
+
+    let (Some((major, minor)), Some((target_major, target_minor))) = (
+        major_minor(api::grpc::api_crate_version()),
+        major_minor(target_version),
+    ) else {
+        return false;
+    };
+
+    // Same as for upgrades of a cluster, the target may be at most one minor version ahead
+    major == target_major && (minor == target_minor || minor + 1 == target_minor)
+}
+
+impl TableOfContent {
+    /// Copy the collection to another Qdrant cluster, and verify the copy
+    pub async fn migrate_collection(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+        request: MigrateCollection,
+    ) -> Result<MigrationReport, StorageError> {
+        let (collection_name, info, shard_keys) = {
+            let collection = self.get_collection(collection_pass).await?;
+            let (info, shard_keys) = collection_layout(&collection).await?;
+            (collection.name(), info, shard_keys)
+        };
+
+        let target_collection = request
+            .collection
+            .clone()
+            .unwrap_or_else(|| collection_name.clone());
+        let batch_size = request.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+
+        let remote = RemoteCluster::new(&request.uri, request.api_key.as_deref(), REQUEST_TIMEOUT)?;
+
+        let target_version = remote
+            .qdrant()
+            .health_check(remote.request(HealthCheckRequest {}))
+            .await
+            .map_err(CollectionError::from)?
+            .into_inner()
+            .version;
+        if !is_compatible_version(&target_version) {
+            return Err(StorageError::bad_request(format!(
+                "Target cluster runs Qdrant {target_version}, \
+                 which can't receive collections from Qdrant {}",
+                api::grpc::api_crate_version(),
+            )));
+        }
+
+        log::info!(
+            "Migrating collection {collection_name} to collection {target_collection} of {}",
+            request.uri,
+        );
+
+        if request.create_collection.unwrap_or(true) {
+            create_target_collection(&remote, &target_collection, info.into(), shard_keys).await?;
+        }
+
+        let mut source_checksum = PointsChecksum::default();
+        remote
+            .copy_points(
+                &self.collections,
+                &collection_name,
+                &target_collection,
+                batch_size,
+                |record| {
+                    source_checksum.add(record.id, record.payload.as_ref(), record.vector.as_ref())
+                },
+            )
+            .await?;
+
+        let target_checksum = remote_checksum(&remote, &target_collection, batch_size).await?;
+        if source_checksum != target_checksum {
+            return Err(StorageError::service_error(format!(
+                "Migration of collection {collection_name} can't be verified: \
+                 {} points with checksum {:016x} on this cluster, \
+                 {} points with checksum {:016x} on the target cluster",
+                source_checksum.count,
+                source_checksum.hash,
+                target_checksum.count,
+                target_checksum.hash,
+            )));
+        }
+
+        if let Some(alias) = &request.alias {
+            // Creating an existing alias points it to the new collection atomically
+            let change_aliases = ChangeAliases {
+                actions: vec![AliasOperations {
+                    action: Some(alias_operations::Action::CreateAlias(CreateAlias {
+                        collection_name: target_collection.clone(),
+                        alias_name: alias.clone(),
+                    })),
+                }],
+                timeout: None,
+            };
+            remote
+                .collections()
+                .update_aliases(remote.request(change_aliases))
+                .await
+                .map_err(CollectionError::from)?;
+        }
+
+        log::info!(
+            "Migrated {} points of collection {collection_name} to collection {target_collection} \
+             of {}",
+            source_checksum.count,
+            request.uri,
+        );
+
+        Ok(MigrationReport {
+            collection: target_collection,
+            target_version,
+            points_count: source_checksum.count,
+            checksum: format!("{:016x}", source_checksum.hash),
+            alias: request.alias,
+        })
+    }
+}
+
+/// Params of the hybrid vectors of the collection, to create them again on the target cluster
+fn hybrid_vectors_config(params: &CollectionParams) -> Option<HybridVectorParamsMap> {
+    let hybrid_vectors = params.hybrid_vectors.as_ref()?;
+    let Some(vectors_config::Config::ParamsMap(vectors)) = params
+        .vectors_config
+        .as_ref()
+        .and_then(|config| config.config.as_ref())
+    else {
+        return None;
+    };
+
+    let map = hybrid_vectors
+        .map
+        .iter()
+        .map(|(name, pair)| {
+            let params = HybridVectorParams {
+                dense: vectors.map.get(&pair.dense).cloned(),
+                sparse: params
+                    .sparse_vectors_config
+                    .as_ref()
+                    .and_then(|sparse| sparse.map.get(&pair.sparse))
+                    .cloned(),
+            };
+            (name.clone(), params)
+        })
+        .collect();
+    Some(HybridVectorParamsMap { map })
+}
+
+/// Info of the collection, and its shard keys with their numbers of shards
+pub(super) async fn collection_layout(
+    collection: &Collection,
+) -> Result<(CollectionInfo, Vec<(ShardKey, usize)>), StorageError> {
+    let info = collection.info(&ShardSelectorInternal::All).await?;
+
+    let mut shard_keys = Vec::new();
+    for shard_key in collection.get_shard_keys().await {
+        let shards_number = collection.shard_key_shard_ids(&shard_key).await?.len();
+        shard_keys.push((shard_key, shards_number));
+    }
+
+    Ok((info, shard_keys))
+}
+
+/// Create the collection on the target cluster, with the configuration of the migrated one
+pub(super) async fn create_target_collection(
+    remote: &RemoteCluster,
+    collection_name: &str,
+    info: api::grpc::qdrant::CollectionInfo,
+    shard_keys: Vec<(ShardKey, usize)>,
+) -> Result<(), StorageError> {
+    let config = info.config.unwrap_or_default();
+    let params = config.params.unwrap_or_default();
+    let hybrid_vectors_config = hybrid_vectors_config(&params);
+
+    let create_collection = CreateCollection {
+        collection_name: collection_name.to_string(),
+        hnsw_config: config.hnsw_config,
+        wal_config: config.wal_config,
+        optimizers_config: config.optimizer_config,
+        shard_number: Some(params.shard_number),
+        on_disk_payload: Some(params.on_disk_payload),
+        timeout: None,
+        vectors_config: params.vectors_config,
+        replication_factor: params.replication_factor,
+        write_consistency_factor: params.write_consistency_factor,
+        init_from_collection: None,
+        quantization_config: config.quantization_config,
+        sharding_method: params.sharding_method,
+        sparse_vectors_config: params.sparse_vectors_config,
+        hybrid_vectors_config,
+    };
+
+    let mut collections = remote.collections();
+    collections
+        .create(remote.request(create_collection))
+        .await
+        .map_err(CollectionError::from)?;
+
+    for (shard_key, shards_number) in shard_keys {
+        let create_shard_key = CreateShardKeyRequest {
+            collection_name: collection_name.to_string(),
+            request: Some(CreateShardKey {
+                shard_key: Some(convert_shard_key_to_grpc(shard_key)),
+                shards_number: Some(shards_number as u32),
+                replication_factor: None,
+                placement: vec![],
+            }),
+            timeout: None,
+        };
+        collections
+            .create_shard_key(remote.request(create_shard_key))
+            .await
+            .map_err(CollectionError::from)?;
+    }
+
+    // Create payload indexes before copying points, so they don't need to be rebuilt
+    let mut points = remote.points();
+    for (field_name, schema) in info.payload_schema {
+        let create_index = CreateFieldIndexCollection {
+            collection_name: collection_name.to_string(),
+            wait: Some(true),
+            field_name,
+            field_type: Some(schema.data_type),
+            field_index_params: schema.params,
+            ordering: None,
+        };
+        points
+            .create_field_index(remote.request(create_index))
+            .await
+            .map_err(CollectionError::from)?;
+    }
+
+    Ok(())
+}
+
+/// Checksum of all points of a collection on the target cluster
+async fn remote_checksum(
+    remote: &RemoteCluster,
+    collection_name: &str,
+    batch_size: usize,
+) -> Result<PointsChecksum, StorageError> {
+    let mut points = remote.points();
+    let mut checksum = PointsChecksum::default();
+
+    let mut offset = None;
+    loop {
+        let scroll = ScrollPoints {
+            collection_name: collection_name.to_string(),
+            filter: None,
+            offset,
+            limit: Some(batch_size as u32),
+            with_payload: Some(WithPayloadInterface::Bool(true).into()),
+            with_vectors: Some(WithVector::Bool(true).into()),
+            // Replicas of the target cluster may not have applied all copied points yet
+            read_consistency: Some(ReadConsistency {
+                value: Some(read_consistency::Value::Type(
+                    ReadConsistencyType::All as i32,
+                )),
+            }),
+            shard_key_selector: None,
+            order_by: None,
+            session_token: None,
+        };
+        let page = points
+            .scroll(remote.request(scroll))
+            .await
+            .map_err(CollectionError::from)?
+            .into_inner();
+
+        for point in page.result {
+            let id = point.id.ok_or_else(|| {
+                StorageError::service_error("Target cluster returned a point without id")
+            })?;
+            let id = PointIdType::try_from(id).map_err(CollectionError::from)?;
+            let payload = proto_to_payloads(point.payload).map_err(CollectionError::from)?;
+            let vectors = point
+                .vectors
+                .map(VectorStructInternal::try_from)
+                .transpose()
+                .map_err(CollectionError::from)?
+                .map(VectorStruct::from);
+            checksum.add(id, Some(&payload), vectors.as_ref());
+        }
+
+        offset = page.next_page_offset;
+        if offset.is_none() {
+            break;
+        }
+    }
+
+    Ok(checksum)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_points_checksum_is_order_independent() {
+        let first: Payload = serde_json::from_value(json!({"a": 1, "b": [1.5, "x"]})).unwrap();
+        let second: Payload = serde_json::from_value(json!({"b": [1.5, "x"], "a": 1})).unwrap();
+
+        let mut checksum = PointsChecksum::default();
+        checksum.add(PointIdType::NumId(1), Some(&first), None);
+        checksum.add(PointIdType::NumId(2), None, None);
+
+        let mut reordered = PointsChecksum::default();
+        reordered.add(PointIdType::NumId(2), Some(&Payload(Map::new())), None);
+        reordered.add(PointIdType::NumId(1), Some(&second), None);
+        assert_eq!(checksum, reordered);
+
+        let changed: Payload = serde_json::from_value(json!({"a": 2, "b": [1.5, "x"]})).unwrap();
+        let mut different = PointsChecksum::default();
+        different.add(PointIdType::NumId(2), None, None);
+        different.add(PointIdType::NumId(1), Some(&changed), None);
+        assert_ne!(checksum, different);
+    }
+
+    #[test]
+    fn test_points_checksum_covers_vectors() {
+        let checksum_of = |vectors: VectorStruct| {
+            let mut checksum = PointsChecksum::default();
+            checksum.add(PointIdType::NumId(1), None, Some(&vectors));
+            checksum
+        };
+        let sparse = |indices: Vec<u32>, values: Vec<f32>| -> Vector {
+            serde_json::from_value(json!({"indices": indices, "values": values})).unwrap()
+        };
+        let vectors = || {
+            HashMap::from([
+                ("dense".to_string(), Vector::Dense(vec![1.0, 2.0])),
+                ("sparse".to_string(), sparse(vec![1, 5], vec![0.5, 0.7])),
+                (
+                    "multi".to_string(),
+                    Vector::MultiDense(vec![vec![1.0, 2.0], vec![3.0, 4.0]]),
+                ),
+            ])
+        };
+        let checksum = checksum_of(VectorStruct::Named(vectors()));
+
+        let mut reordered = vectors();
+        reordered.insert("sparse".to_string(), sparse(vec![5, 1], vec![0.7, 0.5]));
+        assert_eq!(checksum, checksum_of(VectorStruct::Named(reordered)));
+
+        let changes = [
+            ("dense", Vector::Dense(vec![1.0, 2.5])),
+            ("sparse", sparse(vec![1, 6], vec![0.5, 0.7])),
+            ("multi", Vector::MultiDense(vec![vec![1.0, 2.0]])),
+        ];
+        for (name, vector) in changes {
+            let mut changed = vectors();
+            changed.insert(name.to_string(), vector);
+            assert_ne!(
+                checksum,
+                checksum_of(VectorStruct::Named(changed)),
+                "{name}"
+            );
+        }
+
+        let mut missing = vectors();
+        missing.remove("multi");
+        assert_ne!(checksum, checksum_of(VectorStruct::Named(missing)));
+
+        // The unnamed vector is the vector with the default name
+        assert_eq!(
+            checksum_of(VectorStruct::Single(vec![1.0, 2.0])),
+            checksum_of(VectorStruct::Named(HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_string(),
+                Vector::Dense(vec![1.0, 2.0]),
+            )]))),
+        );
+    }
+
+    #[test]
+    fn test_is_compatible_version() {
+        let version = api::grpc::api_crate_version();
+        assert!(is_compatible_version(version));
+        assert!(!is_compatible_version("0.1.0"));
+        assert!(!is_compatible_version("invalid"));
+    }
+}
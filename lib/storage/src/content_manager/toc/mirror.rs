@@ -0,0 +1,839 @@
+//! Asynchronous mirroring of the writes of a collection to a secondary cluster.
+//!
+//! Write operations received from users are applied locally one at a time, and queued in the same
+//! order, to be sent to the public gRPC API of the secondary cluster by a background worker. So
+//! writes to a mirrored collection don't run concurrently with each other, but they don't wait for
+//! the secondary cluster either. If the queue overflows or the secondary rejects an operation, the
+//! mirror is marked as out of sync until a resync copies all points of the collection again.
+//!
+//! A resync copies the points into a new collection of the secondary cluster, and then points an
+//! alias with the name of the mirror collection to it, so the secondary keeps serving the previous
+//! copy until the new one is complete. If the mirror collection is still a collection and not an
+//! alias, it can't be replaced without a period in which it does not exist, so the points are
+//! copied into it in place instead, and points missing in the mirrored collection are deleted.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use api::grpc::qdrant::points_client::PointsClient;
+use api::grpc::qdrant::{
+    alias_operations, AliasOperations, ChangeAliases, CollectionExistsRequest, CreateAlias,
+    DeleteCollection, ListAliasesRequest, ScrollPoints, ShardKeySelector,
+};
+use chrono::{DateTime, Utc};
+use collection::operations::payload_ops::PayloadOps;
+use collection::operations::point_ops::PointOperations;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CollectionError, PointRequestInternal};
+use collection::operations::vector_ops::VectorOperations;
+use collection::operations::{CollectionUpdateOperations, FieldIndexOperations};
+use collection::shards::conversions::{
+    internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
+    internal_delete_index, internal_delete_payload, internal_delete_points,
+    internal_delete_points_by_filter, internal_delete_vectors, internal_delete_vectors_by_filter,
+    internal_set_payload, internal_update_vectors, internal_upsert_points,
+};
+use schemars::JsonSchema;
+use segment::types::{PointIdType, WithPayloadInterface, WithVector};
+use serde::Serialize;
+use tokio::runtime::Handle;
+use tokio::sync::{mpsc, oneshot, Mutex, MutexGuard, RwLock};
+use tonic::transport::Channel;
+use tonic::{Code, Status};
+
+use super::migration::{collection_layout, create_target_collection};
+use super::remote_cluster::{shard_key_selector, RemoteCluster};
+use super::TableOfContent;
+use crate::content_manager::collections_ops::Collections;
+use crate::content_manager::errors::StorageError;
+use crate::rbac::CollectionPass;
+use crate::types::MirrorConfig;
+
+/// Number of points copied to the secondary cluster per request during a resync
+const RESYNC_BATCH_SIZE: usize = 100;
+
+/// Max delay between retries of an operation the secondary cluster is temporarily unable to accept
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Current state of the mirroring of a collection to a secondary cluster
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MirrorStatus {
+    /// gRPC URI of the secondary cluster
+    pub uri: String,
+    /// Name of the collection on the secondary cluster
+    pub collection: String,
+    /// False if operations were lost, and the secondary cluster needs a resync
+    pub in_sync: bool,
+    /// If a resync of all points to the secondary cluster is in progress
+    pub resync_in_progress: bool,
+    /// Number of operations waiting to be mirrored
+    pub pending_operations: usize,
+    /// Number of operations applied on the secondary cluster
+    pub mirrored_operations: usize,
+    /// Number of operations rejected by the secondary cluster, or dropped because the queue was full
+    pub failed_operations: usize,
+    /// Time in milliseconds the oldest pending operation is waiting to be mirrored
+    pub lag_ms: u64,
+    /// Time when the last operation was applied on the secondary cluster
+    pub last_mirrored_at: Option<DateTime<Utc>>,
+    /// Last error of the secondary cluster
+    pub last_error: Option<String>,
+}
+
+#[derive(Default)]
+struct MirrorState {
+    out_of_sync: bool,
+    resync_in_progress: bool,
+    /// Number of operations dropped since the last resync started, which the resync may miss
+    dropped_since_resync: usize,
+    pending_operations: usize,
+    mirrored_operations: usize,
+    failed_operations: usize,
+    /// Time when the operation being mirrored right now was queued
+    sending_since: Option<Instant>,
+    last_mirrored_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+struct MirroredOperation {
+    operation: CollectionUpdateOperations,
+    shard_key_selector: Option<ShardKeySelector>,
+    queued_at: Instant,
+}
+
+type ResyncRequest = oneshot::Sender<Result<(), StorageError>>;
+
+pub(super) struct CollectionMirror {
+    uri: String,
+    collection: String,
+    operations: mpsc::Sender<MirroredOperation>,
+    resyncs: mpsc::UnboundedSender<ResyncRequest>,
+    state: Arc<parking_lot::Mutex<MirrorState>>,
+    /// Held while an operation is applied locally and queued, to queue operations in apply order.
+    ///
+    /// It serializes all writes to the collection, each one waits for the local apply of the
+    /// previous one. The secondary cluster is not waited for, the worker sends queued operations.
+    apply_lock: Mutex<()>,
+}
+
+impl CollectionMirror {
+    /// Start mirroring the collection, with a background worker on the given runtime
+    pub fn new(
+        collection_name: &str,
+        config: &MirrorConfig,
+        collections: Arc<RwLock<Collections>>,
+        runtime: &Handle,
+    ) -> Result<Self, StorageError> {
+        let remote = {
+            // The client spawns its connection task on the runtime
+            let _runtime = runtime.enter();
+            RemoteCluster::new(
+                &config.uri,
+                config.api_key.as_deref(),
+                Duration::from_millis(config.timeout_ms),
+            )?
+        };
+
+        let collection = config
+            .collection
+            .clone()
+            .unwrap_or_else(|| collection_name.to_string());
+
+        let (operations, operations_receiver) = mpsc::channel(config.queue_size.max(1));
+        let (resyncs, resyncs_receiver) = mpsc::unbounded_channel();
+        let state = Arc::new(parking_lot::Mutex::new(MirrorState::default()));
+
+        let worker = MirrorWorker {
+            source_collection: collection_name.to_string(),
+            target_collection: collection.clone(),
+            remote,
+            collections,
+            state: state.clone(),
+        };
+
+        runtime.spawn(worker.run(operations_receiver, resyncs_receiver));
+
+        Ok(Self {
+            uri: config.uri.clone(),
+            collection,
+            operations,
+            resyncs,
+            state,
+            apply_lock: Mutex::new(()),
+        })
+    }
+
+    /// Lock to hold while an operation is applied locally, until it is pushed to the queue
+    pub async fn lock_apply(&self) -> MutexGuard<'_, ()> {
+        self.apply_lock.lock().await
+    }
+
+    /// Queue an operation, which was applied locally, to be mirrored
+    pub fn push(
+        &self,
+        operation: CollectionUpdateOperations,
+        shard_selector: &ShardSelectorInternal,
+    ) {
+        let shard_keys = match shard_selector {
+            ShardSelectorInternal::ShardKey(shard_key) => vec![shard_key.clone()],
+            ShardSelectorInternal::ShardKeys(shard_keys) => shard_keys.clone(),
+            ShardSelectorInternal::Empty
+            | ShardSelectorInternal::All
+            | ShardSelectorInternal::ShardId(_) => vec![],
+        };
+
+        let operation = MirroredOperation {
+            operation,
+            shard_key_selector: shard_key_selector(shard_keys),
+            queued_at: Instant::now(),
+        };
+
+        // Count the operation before queueing it, so the worker never sees a negative count
+        self.state.lock().pending_operations += 1;
+
+        if self.operations.try_send(operation).is_err() {
+            let mut state = self.state.lock();
+            state.pending_operations -= 1;
+            state.failed_operations += 1;
+            state.dropped_since_resync += 1;
+            if !state.out_of_sync {
+                log::warn!(
+                    "Mirroring queue of collection {} is full, the mirror needs a resync",
+                    self.collection,
+                );
+            }
+            state.out_of_sync = true;
+        }
+    }
+
+    pub fn status(&self) -> MirrorStatus {
+        let state = self.state.lock();
+        MirrorStatus {
+            uri: self.uri.clone(),
+            collection: self.collection.clone(),
+            in_sync: !state.out_of_sync,
+            resync_in_progress: state.resync_in_progress,
+            pending_operations: state.pending_operations,
+            mirrored_operations: state.mirrored_operations,
+            failed_operations: state.failed_operations,
+            lag_ms: state
+                .sending_since
+                .map_or(0, |since| since.elapsed().as_millis() as u64),
+            last_mirrored_at: state.last_mirrored_at,
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    /// Replace all points on the secondary cluster with the current points of the collection.
+    ///
+    /// Pending operations are discarded, as the copied points already include them.
+    /// Operations queued during the resync are applied to the new copy afterwards.
+    pub async fn resync(&self) -> Result<MirrorStatus, StorageError> {
+        let (done, done_receiver) = oneshot::channel();
+        self.resyncs
+            .send(done)
+            .map_err(|_| StorageError::service_error("Mirroring worker is stopped"))?;
+        done_receiver.await??;
+        Ok(self.status())
+    }
+}
+
+struct MirrorWorker {
+    source_collection: String,
+    target_collection: String,
+    remote: RemoteCluster,
+    collections: Arc<RwLock<Collections>>,
+    state: Arc<parking_lot::Mutex<MirrorState>>,
+}
+
+impl MirrorWorker {
+    async fn run(
+        self,
+        mut operations: mpsc::Receiver<MirroredOperation>,
+        mut resyncs: mpsc::UnboundedReceiver<ResyncRequest>,
+    ) {
+        let mut client = self.remote.points();
+        loop {
+            tokio::select! {
+                biased;
+
+                Some(done) = resyncs.recv() => {
+                    let discarded = std::iter::from_fn(|| operations.try_recv().ok()).count();
+                    {
+                        let mut state = self.state.lock();
+                        state.pending_operations -= discarded;
+                        state.resync_in_progress = true;
+                        // Operations dropped before were applied locally, so they are copied
+                        state.dropped_since_resync = 0;
+                    }
+
+                    let result = self.resync().await;
+
+                    let mut state = self.state.lock();
+                    state.resync_in_progress = false;
+                    match &result {
+                        // Operations dropped during the resync may have missed the copy
+                        Ok(()) if state.dropped_since_resync > 0 => log::warn!(
+                            "{} operations of collection {} were dropped during the resync, \
+                             the mirror needs another resync",
+                            state.dropped_since_resync,
+                            self.source_collection,
+                        ),
+                        Ok(()) => state.out_of_sync = false,
+                        Err(err) => state.last_error = Some(err.to_string()),
+                    }
+                    drop(state);
+
+                    let _ = done.send(result);
+                }
+
+                Some(operation) = operations.recv() => {
+                    self.state.lock().sending_since = Some(operation.queued_at);
+                    let result = self
+                        .send_with_retries(
+                            &mut client,
+                            operation.operation,
+                            operation.shard_key_selector,
+                        )
+                        .await;
+
+                    let mut state = self.state.lock();
+                    state.pending_operations -= 1;
+                    state.sending_since = None;
+                    match result {
+                        Ok(()) => {
+                            state.mirrored_operations += 1;
+                            state.last_mirrored_at = Some(Utc::now());
+                        }
+                        Err(err) => {
+                            log::warn!(
+                                "Secondary cluster rejected mirrored operation of collection {}, \
+                                 the mirror needs a resync: {err}",
+                                self.source_collection,
+                            );
+                            state.failed_operations += 1;
+                            state.out_of_sync = true;
+                            state.last_error = Some(err.to_string());
+                        }
+                    }
+                }
+
+                else => break,
+            }
+        }
+    }
+
+    /// Send the operation, retrying for as long as the secondary cluster is unavailable
+    async fn send_with_retries(
+        &self,
+        client: &mut PointsClient<Channel>,
+        operation: CollectionUpdateOperations,
+        shard_key_selector: Option<ShardKeySelector>,
+    ) -> Result<(), Status> {
+        let mut delay = Duration::from_millis(100);
+        loop {
+            let result = self
+                .send(client, operation.clone(), shard_key_selector.clone())
+                .await;
+
+            match result {
+                Err(err) if is_transient(&err) => {
+                    log::debug!(
+                        "Failed to mirror operation of collection {}, retrying in {delay:?}: {err}",
+                        self.source_collection,
+                    );
+                    self.state.lock().last_error = Some(err.to_string());
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(MAX_RETRY_DELAY);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        client: &mut PointsClient<Channel>,
+        operation: CollectionUpdateOperations,
+        shard_key_selector: Option<ShardKeySelector>,
+    ) -> Result<(), Status> {
+        let collection_name = self.target_collection.clone();
+        // Apply operations one by one, so they are applied in the same order as on this cluster
+        let wait = true;
+        let ordering = None;
+
+        match operation {
+            CollectionUpdateOperations::PointOperation(operation) => match operation {
+                PointOperations::UpsertPoints(operation) => {
+                    let mut request = internal_upsert_points(
+                        None,
+                        None,
+                        collection_name,
+                        operation,
+                        wait,
+                        ordering,
+                    )
+                    .map_err(|err| Status::invalid_argument(err.to_string()))?
+                    .upsert_points
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.upsert(self.remote.request(request)).await?;
+                }
+                PointOperations::DeletePoints { ids } => {
+                    let mut request =
+                        internal_delete_points(None, None, collection_name, ids, wait, ordering)
+                            .delete_points
+                            .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.delete(self.remote.request(request)).await?;
+                }
+                PointOperations::DeletePointsByFilter(filter) => {
+                    let mut request = internal_delete_points_by_filter(
+                        None,
+                        None,
+                        collection_name,
+                        filter,
+                        wait,
+                        ordering,
+                    )
+                    .delete_points
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.delete(self.remote.request(request)).await?;
+                }
+                // Only used internally, to transfer shards
+                PointOperations::SyncPoints(_) => {}
+            },
+            CollectionUpdateOperations::VectorOperation(operation) => match operation {
+                VectorOperations::UpdateVectors(operation) => {
+                    let mut request = internal_update_vectors(
+                        None,
+                        None,
+                        collection_name,
+                        operation,
+                        wait,
+                        ordering,
+                    )
+                    .update_vectors
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.update_vectors(self.remote.request(request)).await?;
+                }
+                VectorOperations::DeleteVectors(ids, vector_names) => {
+                    let mut request = internal_delete_vectors(
+                        None,
+                        None,
+                        collection_name,
+                        ids.points,
+                        vector_names,
+                        wait,
+                        ordering,
+                    )
+                    .delete_vectors
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.delete_vectors(self.remote.request(request)).await?;
+                }
+                VectorOperations::DeleteVectorsByFilter(filter, vector_names) => {
+                    let mut request = internal_delete_vectors_by_filter(
+                        None,
+                        None,
+                        collection_name,
+                        filter,
+                        vector_names,
+                        wait,
+                        ordering,
+                    )
+                    .delete_vectors
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.delete_vectors(self.remote.request(request)).await?;
+                }
+            },
+            CollectionUpdateOperations::PayloadOperation(operation) => match operation {
+                PayloadOps::SetPayload(operation) => {
+                    let mut request = internal_set_payload(
+                        None,
+                        None,
+                        collection_name,
+                        operation,
+                        wait,
+                        ordering,
+                    )
+                    .set_payload_points
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.set_payload(self.remote.request(request)).await?;
+                }
+                PayloadOps::OverwritePayload(operation) => {
+                    let mut request = internal_set_payload(
+                        None,
+                        None,
+                        collection_name,
+                        operation,
+                        wait,
+                        ordering,
+                    )
+                    .set_payload_points
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client
+                        .overwrite_payload(self.remote.request(request))
+                        .await?;
+                }
+                PayloadOps::DeletePayload(operation) => {
+                    let mut request = internal_delete_payload(
+                        None,
+                        None,
+                        collection_name,
+                        operation,
+                        wait,
+                        ordering,
+                    )
+                    .delete_payload_points
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.delete_payload(self.remote.request(request)).await?;
+                }
+                PayloadOps::ClearPayload { points } => {
+                    let mut request =
+                        internal_clear_payload(None, None, collection_name, points, wait, ordering)
+                            .clear_payload_points
+                            .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.clear_payload(self.remote.request(request)).await?;
+                }
+                PayloadOps::ClearPayloadByFilter(filter) => {
+                    let mut request = internal_clear_payload_by_filter(
+                        None,
+                        None,
+                        collection_name,
+                        filter,
+                        wait,
+                        ordering,
+                    )
+                    .clear_payload_points
+                    .unwrap_or_default();
+                    request.shard_key_selector = shard_key_selector;
+                    client.clear_payload(self.remote.request(request)).await?;
+                }
+            },
+            CollectionUpdateOperations::FieldIndexOperation(operation) => match operation {
+                FieldIndexOperations::CreateIndex(operation) => {
+                    let request = internal_create_index(
+                        None,
+                        None,
+                        collection_name,
+                        operation,
+                        wait,
+                        ordering,
+                    )
+                    .create_field_index_collection
+                    .unwrap_or_default();
+                    client
+                        .create_field_index(self.remote.request(request))
+                        .await?;
+                }
+                FieldIndexOperations::DeleteIndex(field_name) => {
+                    let request = internal_delete_index(
+                        None,
+                        None,
+                        collection_name,
+                        field_name,
+                        wait,
+                        ordering,
+                    )
+                    .delete_field_index_collection
+                    .unwrap_or_default();
+                    client
+                        .delete_field_index(self.remote.request(request))
+                        .await?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Copy all points of the collection into a new collection on the secondary cluster, and
+    /// replace the previous copy with it
+    async fn resync(&self) -> Result<(), StorageError> {
+        log::info!(
+            "Resyncing mirror of collection {} to {}",
+            self.source_collection,
+            self.target_collection,
+        );
+
+        let previous_collection = self.aliased_target_collection().await?;
+
+        if previous_collection.is_none() && self.remote_collection_exists().await? {
+            self.resync_in_place().await?;
+        } else {
+            self.resync_into_new_collection(previous_collection).await?;
+        }
+
+        log::info!(
+            "Resynced mirror of collection {} to {}",
+            self.source_collection,
+            self.target_collection,
+        );
+
+        Ok(())
+    }
+
+    async fn resync_into_new_collection(
+        &self,
+        previous_collection: Option<String>,
+    ) -> Result<(), StorageError> {
+        let (info, shard_keys) = {
+            let collections = self.collections.read().await;
+            let collection = collections.get(&self.source_collection).ok_or_else(|| {
+                StorageError::not_found(format!("Collection {} not found", self.source_collection))
+            })?;
+            collection_layout(collection).await?
+        };
+
+        let staging_collection = format!(
+            "{}-resync-{}",
+            self.target_collection,
+            Utc::now().timestamp_millis(),
+        );
+        create_target_collection(&self.remote, &staging_collection, info.into(), shard_keys)
+            .await?;
+
+        let copied = self
+            .remote
+            .copy_points(
+                &self.collections,
+                &self.source_collection,
+                &staging_collection,
+                RESYNC_BATCH_SIZE,
+                |_| {},
+            )
+            .await;
+        if let Err(err) = copied {
+            // Keep serving the previous copy, and don't leave the incomplete one behind
+            self.delete_remote_collection(&staging_collection).await?;
+            return Err(err);
+        }
+
+        self.replace_target(&staging_collection, previous_collection)
+            .await
+    }
+
+    /// Copy all points of the collection into the mirror collection, and delete the points which
+    /// don't exist in the collection anymore
+    async fn resync_in_place(&self) -> Result<(), StorageError> {
+        self.remote
+            .copy_points(
+                &self.collections,
+                &self.source_collection,
+                &self.target_collection,
+                RESYNC_BATCH_SIZE,
+                |_| {},
+            )
+            .await?;
+
+        let mut client = self.remote.points();
+        let mut offset = None;
+        loop {
+            let scroll_points = ScrollPoints {
+                collection_name: self.target_collection.clone(),
+                offset,
+                limit: Some(RESYNC_BATCH_SIZE as u32),
+                with_payload: Some(WithPayloadInterface::Bool(false).into()),
+                with_vectors: Some(WithVector::Bool(false).into()),
+                ..Default::default()
+            };
+            let page = client
+                .scroll(self.remote.request(scroll_points))
+                .await
+                .map_err(CollectionError::from)?
+                .into_inner();
+
+            let ids = page
+                .result
+                .into_iter()
+                .filter_map(|point| point.id)
+                .map(PointIdType::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(CollectionError::from)?;
+
+            let missing_ids = self.missing_points(ids).await?;
+            if !missing_ids.is_empty() {
+                let request = internal_delete_points(
+                    None,
+                    None,
+                    self.target_collection.clone(),
+                    missing_ids,
+                    true,
+                    None,
+                )
+                .delete_points
+                .unwrap_or_default();
+                client
+                    .delete(self.remote.request(request))
+                    .await
+                    .map_err(CollectionError::from)?;
+            }
+
+            offset = page.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Points of the given ones, which don't exist in the mirrored collection
+    async fn missing_points(
+        &self,
+        ids: Vec<PointIdType>,
+    ) -> Result<Vec<PointIdType>, StorageError> {
+        let request = PointRequestInternal {
+            ids: ids.clone(),
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: WithVector::Bool(false),
+            as_of_version: None,
+        };
+
+        let existing: HashSet<_> = {
+            let collections = self.collections.read().await;
+            let collection = collections.get(&self.source_collection).ok_or_else(|| {
+                StorageError::not_found(format!("Collection {} not found", self.source_collection))
+            })?;
+            collection
+                .retrieve(request, None, &ShardSelectorInternal::All)
+                .await?
+                .into_iter()
+                .map(|record| record.id)
+                .collect()
+        };
+
+        Ok(ids
+            .into_iter()
+            .filter(|id| !existing.contains(id))
+            .collect())
+    }
+
+    /// Collection the alias with the name of the mirror collection points to, if it is an alias
+    async fn aliased_target_collection(&self) -> Result<Option<String>, StorageError> {
+        let aliased_collection = self
+            .remote
+            .collections()
+            .list_aliases(self.remote.request(ListAliasesRequest {}))
+            .await
+            .map_err(CollectionError::from)?
+            .into_inner()
+            .aliases
+            .into_iter()
+            .find(|alias| alias.alias_name == self.target_collection)
+            .map(|alias| alias.collection_name);
+        Ok(aliased_collection)
+    }
+
+    async fn remote_collection_exists(&self) -> Result<bool, StorageError> {
+        let collection_exists = CollectionExistsRequest {
+            collection_name: self.target_collection.clone(),
+        };
+        let exists = self
+            .remote
+            .collections()
+            .collection_exists(self.remote.request(collection_exists))
+            .await
+            .map_err(CollectionError::from)?
+            .into_inner()
+            .result
+            .map_or(false, |result| result.exists);
+        Ok(exists)
+    }
+
+    /// Point the alias with the name of the mirror collection to the new copy, and then delete
+    /// the previous copy, so the mirror collection exists all the time
+    async fn replace_target(
+        &self,
+        staging_collection: &str,
+        previous_collection: Option<String>,
+    ) -> Result<(), StorageError> {
+        // Creating an existing alias points it to the new collection atomically
+        let change_aliases = ChangeAliases {
+            actions: vec![AliasOperations {
+                action: Some(alias_operations::Action::CreateAlias(CreateAlias {
+                    collection_name: staging_collection.to_string(),
+                    alias_name: self.target_collection.clone(),
+                })),
+            }],
+            timeout: None,
+        };
+        self.remote
+            .collections()
+            .update_aliases(self.remote.request(change_aliases))
+            .await
+            .map_err(CollectionError::from)?;
+
+        if let Some(previous_collection) = previous_collection {
+            self.delete_remote_collection(&previous_collection).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn delete_remote_collection(&self, collection_name: &str) -> Result<(), StorageError> {
+        let delete_collection = DeleteCollection {
+            collection_name: collection_name.to_string(),
+            timeout: None,
+        };
+        self.remote
+            .collections()
+            .delete(self.remote.request(delete_collection))
+            .await
+            .map_err(CollectionError::from)?;
+        Ok(())
+    }
+}
+
+/// Whether the secondary cluster may accept the operation when retried later
+fn is_transient(status: &Status) -> bool {
+    matches!(
+        status.code(),
+        Code::Unavailable
+            | Code::DeadlineExceeded
+            | Code::ResourceExhausted
+            | Code::Aborted
+            | Code::Cancelled
+            | Code::Unknown,
+    )
+}
+
+impl TableOfContent {
+    /// Status of the mirroring of the collection to a secondary cluster
+    pub async fn mirror_status(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+    ) -> Result<MirrorStatus, StorageError> {
+        Ok(self.get_mirror(collection_pass).await?.status())
+    }
+
+    /// Replace all points on the secondary cluster with the current points of the collection
+    pub async fn resync_mirror(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+    ) -> Result<MirrorStatus, StorageError> {
+        self.get_mirror(collection_pass).await?.resync().await
+    }
+
+    async fn get_mirror(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+    ) -> Result<&CollectionMirror, StorageError> {
+        // Resolve aliases
+        let collection_name = self.get_collection(collection_pass).await?.name();
+        self.mirrors.get(&collection_name).ok_or_else(|| {
+            StorageError::bad_request(format!(
+                "Collection {collection_name} is not mirrored to a secondary cluster",
+            ))
+        })
+    }
+}
@@ -0,0 +1,167 @@
+//! Budget of memory-mapped files across collections.
+//!
+//! Vectors and indices of collections are memory-mapped, and their pages stay resident until the
+//! kernel reclaims them. Under memory pressure the kernel doesn't know which collections are
+//! queried, so it may evict pages of hot collections as well and thrash. Instead, resident bytes of
+//! the memory-mapped files of each collection are tracked once the node approaches memory pressure,
+//! and the pages of the collections, which were not accessed for the longest time, are evicted
+//! first, until the memory usage is back within the budget.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use memory::resident::{self, FileMapping};
+use segment::utils::mem::Mem;
+
+use super::{TableOfContent, COLLECTIONS_DIR};
+use crate::types::MmapBudgetConfig;
+
+pub struct MmapBudget {
+    config: MmapBudgetConfig,
+    mem: parking_lot::Mutex<Mem>,
+    /// Last time each collection was accessed, by collection name
+    last_access: parking_lot::Mutex<HashMap<String, Instant>>,
+}
+
+impl MmapBudget {
+    pub fn new(config: &MmapBudgetConfig) -> Self {
+        Self {
+            config: config.clone(),
+            mem: parking_lot::Mutex::new(Mem::new()),
+            last_access: Default::default(),
+        }
+    }
+
+    /// Mark the collection as accessed
+    pub fn touch(&self, collection_name: &str) {
+        let now = Instant::now();
+        let mut last_access = self.last_access.lock();
+        match last_access.get_mut(collection_name) {
+            Some(accessed_at) => *accessed_at = now,
+            None => {
+                last_access.insert(collection_name.to_string(), now);
+            }
+        }
+    }
+
+    /// Number of bytes to free, to get the memory usage below the pressure threshold
+    fn excess_bytes(&self) -> u64 {
+        let mut mem = self.mem.lock();
+        mem.refresh();
+
+        let total = mem.total_memory_bytes();
+        let used = total.saturating_sub(mem.available_memory_bytes());
+        let budget = (total as f64 * self.config.memory_pressure_ratio) as u64;
+        used.saturating_sub(budget)
+    }
+}
+
+impl TableOfContent {
+    /// Evict memory-mapped pages of cold collections periodically, whenever the node approaches
+    /// memory pressure. Does nothing, if no budget is configured.
+    pub async fn enforce_mmap_budget(self: Arc<Self>) {
+        let Some(budget) = &self.mmap_budget else {
+            return;
+        };
+        let interval = Duration::from_secs(budget.config.check_interval_sec.max(1));
+
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let toc = self.clone();
+            let result = tokio::task::spawn_blocking(move || toc.evict_cold_collections()).await;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => log::warn!("Failed to evict pages of cold collections: {err}"),
+                Err(err) => log::error!("Eviction of pages of cold collections panicked: {err}"),
+            }
+        }
+    }
+
+    fn evict_cold_collections(&self) -> io::Result<()> {
+        let Some(budget) = &self.mmap_budget else {
+            return Ok(());
+        };
+
+        let mut excess_bytes = budget.excess_bytes();
+        if excess_bytes == 0 {
+            return Ok(());
+        }
+
+        // Mapped paths are canonical
+        let collections_path = Path::new(self.storage_path())
+            .join(COLLECTIONS_DIR)
+            .canonicalize()?;
+        let mut mappings_by_collection: HashMap<String, Vec<FileMapping>> = HashMap::new();
+        for mapping in resident::file_mappings()? {
+            if let Some(collection_name) = collection_of(&collections_path, &mapping.path) {
+                mappings_by_collection
+                    .entry(collection_name)
+                    .or_default()
+                    .push(mapping);
+            }
+        }
+
+        // Coldest first, collections never accessed since the start are the coldest
+        let cold_after = Duration::from_secs(budget.config.cold_after_sec);
+        let mut cold_collections: Vec<_> = {
+            let last_access = budget.last_access.lock();
+            mappings_by_collection
+                .into_iter()
+                .map(|(name, mappings)| (last_access.get(&name).copied(), name, mappings))
+                .filter(|(accessed_at, _, _)| {
+                    accessed_at.map_or(true, |accessed_at| accessed_at.elapsed() >= cold_after)
+                })
+                .collect()
+        };
+        cold_collections.sort_unstable_by_key(|(accessed_at, _, _)| *accessed_at);
+
+        for (_, collection_name, mappings) in cold_collections {
+            if excess_bytes == 0 {
+                break;
+            }
+
+            let resident_bytes: u64 = mappings.iter().map(|mapping| mapping.resident_bytes).sum();
+            if resident_bytes == 0 {
+                continue;
+            }
+
+            for mapping in &mappings {
+                if let Err(err) = resident::evict(mapping) {
+                    log::warn!("Failed to evict pages of {}: {err}", mapping.path.display());
+                }
+            }
+
+            log::info!(
+                "Memory pressure: evicted {resident_bytes} resident bytes of memory-mapped files \
+                 of cold collection {collection_name}",
+            );
+            excess_bytes = excess_bytes.saturating_sub(resident_bytes);
+        }
+
+        if excess_bytes > 0 {
+            log::debug!(
+                "Memory pressure: no cold collections left to evict, {excess_bytes} bytes over \
+                 budget",
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Name of the collection, the file at the path belongs to
+fn collection_of(collections_path: &Path, path: &Path) -> Option<String> {
+    match path
+        .strip_prefix(collections_path)
+        .ok()?
+        .components()
+        .next()?
+    {
+        Component::Normal(name) => name.to_str().map(str::to_string),
+        _ => None,
+    }
+}
@@ -1,11 +1,26 @@
+pub mod alias_versions;
 mod collection_container;
 use common::types::TelemetryDetail;
 mod collection_meta_ops;
 mod create_collection;
+pub mod duplicates;
+pub mod events;
+mod experimental;
+mod hnsw_graphs;
 mod locks;
+pub mod migration;
+pub mod mirror;
+mod mmap_budget;
+pub mod mounted_snapshots;
 mod point_ops;
 mod point_ops_internal;
+pub mod query_limits;
+mod remote_cluster;
+mod replication_lags;
+mod rerank;
+mod rewrite;
 mod snapshots;
+pub mod standby;
 mod temp_directories;
 pub mod transfer;
 
@@ -38,6 +53,12 @@ use tonic::codegen::InterceptedService;
 use tonic::transport::Channel;
 use tonic::Status;
 
+use self::events::CollectionEvents;
+use self::mirror::CollectionMirror;
+use self::mmap_budget::MmapBudget;
+use self::mounted_snapshots::MountedSnapshots;
+use self::rerank::Reranker;
+use self::standby::{CollectionStandby, Standbys};
 use self::transfer::ShardTransferDispatcher;
 use crate::content_manager::alias_mapping::AliasPersistence;
 use crate::content_manager::collection_meta_ops::CreateCollectionOperation;
@@ -77,12 +98,27 @@ pub struct TableOfContent {
     /// timings. For example, the health check timing and consensus timing.
     ///
     /// If not defined - no rate limiting is applied.
-    update_rate_limiter: Option<Semaphore>,
+    /// Replaced when the configuration is reloaded, updates in progress keep their permits.
+    update_rate_limiter: parking_lot::RwLock<Option<Arc<Semaphore>>>,
     /// A lock to prevent concurrent collection creation.
     /// Effectively, this lock ensures that `create_collection` is called sequentially.
     collection_create_lock: Mutex<()>,
     /// Dispatcher for shard transfer to access consensus.
     shard_transfer_dispatcher: parking_lot::Mutex<Option<ShardTransferDispatcher>>,
+    /// Collection snapshots mounted read-only on this peer, by `collection:snapshot_name`
+    mounted_snapshots: Arc<RwLock<MountedSnapshots>>,
+    /// External service to rerank query results with, if configured
+    reranker: Option<Reranker>,
+    /// Mirrors of collections to secondary clusters, by collection name
+    mirrors: HashMap<String, CollectionMirror>,
+    /// Standbys of collections of a primary cluster, part of the consensus state
+    standbys: parking_lot::RwLock<Standbys>,
+    /// Followers of the primaries of standby collections, run by this peer while it is the leader
+    standby_followers: parking_lot::Mutex<HashMap<String, Arc<CollectionStandby>>>,
+    /// Eviction of memory-mapped files of cold collections, if configured
+    mmap_budget: Option<MmapBudget>,
+    /// Lifecycle events of collections, for internal subscribers and webhooks
+    collection_events: CollectionEvents,
 }
 
 impl TableOfContent {
@@ -97,7 +133,7 @@ impl TableOfContent {
         channel_service: ChannelService,
         this_peer_id: PeerId,
         consensus_proposal_sender: Option<OperationSender>,
-    ) -> Self {
+    ) -> Result<Self, StorageError> {
         let snapshots_path = Path::new(&storage_config.snapshots_path.clone()).to_owned();
         create_dir_all(&snapshots_path).expect("Can't create Snapshots directory");
         let collections_path = Path::new(&storage_config.storage_path).join(COLLECTIONS_DIR);
@@ -170,25 +206,41 @@ impl TableOfContent {
         let alias_persistence =
             AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
 
-        let rate_limiter = match storage_config.performance.update_rate_limit {
-            Some(limit) => Some(Semaphore::new(limit)),
-            None => {
-                if consensus_proposal_sender.is_some() {
-                    // Auto adjust the rate limit in distributed mode.
-                    // Select number of working threads as a guess.
-                    let limit = max(get_num_cpus(), 2);
-                    log::debug!(
-                        "Auto adjusting update rate limit to {limit} parallel update requests"
-                    );
-                    Some(Semaphore::new(limit))
-                } else {
-                    None
-                }
-            }
-        };
+        let rate_limiter = new_update_rate_limiter(
+            storage_config.performance.update_rate_limit,
+            consensus_proposal_sender.is_some(),
+        );
+
+        let reranker = storage_config.reranker.as_ref().map(|config| {
+            Reranker::new(config).expect("Can't create HTTP client for the reranker")
+        });
 
-        TableOfContent {
-            collections: Arc::new(RwLock::new(collections)),
+        let collections = Arc::new(RwLock::new(collections));
+
+        let mirrors = storage_config
+            .mirrors
+            .iter()
+            .map(|(collection_name, config)| {
+                let mirror = CollectionMirror::new(
+                    collection_name,
+                    config,
+                    collections.clone(),
+                    general_runtime.handle(),
+                )
+                .map_err(|err| {
+                    StorageError::bad_input(format!(
+                        "Can't configure mirror of collection {collection_name}: {err}",
+                    ))
+                })?;
+                Ok((collection_name.clone(), mirror))
+            })
+            .collect::<Result<_, StorageError>>()?;
+
+        let standbys = Standbys::load(Path::new(&storage_config.storage_path))?;
+        let mmap_budget = storage_config.mmap_budget.as_ref().map(MmapBudget::new);
+
+        Ok(TableOfContent {
+            collections,
             storage_config: Arc::new(storage_config.clone()),
             search_runtime,
             update_runtime,
@@ -200,10 +252,17 @@ impl TableOfContent {
             consensus_proposal_sender,
             is_write_locked: AtomicBool::new(false),
             lock_error_message: parking_lot::Mutex::new(None),
-            update_rate_limiter: rate_limiter,
+            update_rate_limiter: parking_lot::RwLock::new(rate_limiter),
             collection_create_lock: Default::default(),
             shard_transfer_dispatcher: Default::default(),
-        }
+            mounted_snapshots: Default::default(),
+            reranker,
+            mirrors,
+            standbys: parking_lot::RwLock::new(standbys),
+            standby_followers: Default::default(),
+            mmap_budget,
+            collection_events: CollectionEvents::new(),
+        })
     }
 
     /// Return `true` if service is working in distributed mode.
@@ -253,6 +312,9 @@ impl TableOfContent {
             let alias_persistence = self.alias_persistence.read().await;
             Self::resolve_name(collection_name, &read_collection, &alias_persistence).await?
         };
+        if let Some(mmap_budget) = &self.mmap_budget {
+            mmap_budget.touch(&real_collection_name);
+        }
         // resolve_name already checked collection existence, unwrap is safe here
         Ok(RwLockReadGuard::map(read_collection, |collection| {
             collection.get(&real_collection_name).unwrap() // TODO: WTF!?
@@ -270,7 +332,9 @@ impl TableOfContent {
         &self,
         collection_name: String,
     ) -> Option<RwLockReadGuard<Collection>> {
-        self.get_collection_unchecked(&collection_name).await.ok()
+        self.get_collection_for_read_unchecked(&collection_name)
+            .await
+            .ok()
     }
 
     /// Finds the original name of the collection
@@ -341,6 +405,26 @@ impl TableOfContent {
         Ok(aliases)
     }
 
+    /// Changes of the collection the alias points to, oldest first
+    pub async fn alias_history(
+        &self,
+        alias_name: &str,
+        access: &Access,
+    ) -> Result<AliasHistoryResponse, StorageError> {
+        access.check_collection_access(alias_name, AccessRequirements::new())?;
+        let alias_persistence = self.alias_persistence.read().await;
+        let changes = alias_persistence.alias_history(alias_name);
+        if changes.is_empty() {
+            return Err(StorageError::NotFound {
+                description: format!("Alias {alias_name} has no history"),
+            });
+        }
+        Ok(AliasHistoryResponse {
+            alias_name: alias_name.to_string(),
+            changes: changes.to_vec(),
+        })
+    }
+
     pub async fn suggest_shard_distribution(
         &self,
         op: &CreateCollectionOperation,
@@ -718,4 +802,30 @@ impl TableOfContent {
     pub fn get_channel_service(&self) -> &ChannelService {
         &self.channel_service
     }
+
+    /// Change the limit of parallel update requests, without waiting for running updates
+    pub fn set_update_rate_limit(&self, update_rate_limit: Option<usize>) {
+        *self.update_rate_limiter.write() =
+            new_update_rate_limiter(update_rate_limit, self.is_distributed());
+    }
+}
+
+fn new_update_rate_limiter(
+    update_rate_limit: Option<usize>,
+    is_distributed: bool,
+) -> Option<Arc<Semaphore>> {
+    match update_rate_limit {
+        Some(limit) => Some(Arc::new(Semaphore::new(limit))),
+        None => {
+            if is_distributed {
+                // Auto adjust the rate limit in distributed mode.
+                // Select number of working threads as a guess.
+                let limit = max(get_num_cpus(), 2);
+                log::debug!("Auto adjusting update rate limit to {limit} parallel update requests");
+                Some(Arc::new(Semaphore::new(limit)))
+            } else {
+                None
+            }
+        }
+    }
 }
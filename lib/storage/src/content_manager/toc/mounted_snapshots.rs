@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use collection::collection::Collection;
+use collection::common::snapshots_manager::SnapshotStorageManager;
+use collection::operations::config_diff::OptimizersConfigDiff;
+use collection::shards::replica_set::ReplicaState;
+use parking_lot::Mutex;
+use tempfile::TempDir;
+use tokio::sync::RwLockReadGuard;
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::rbac::CollectionPass;
+
+/// Separates collection and snapshot names when referring to a mounted snapshot,
+/// e.g. `my_collection:my_collection-2024-01-01-00-00-00.snapshot`
+///
+/// This char is not allowed in collection names, so it can't collide with a live collection.
+pub const SNAPSHOT_NAME_SEPARATOR: char = ':';
+
+/// Lower bound of the interval to check lazily mounted snapshots for idleness
+const MIN_IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Collection snapshot loaded read-only alongside the live collection
+pub struct MountedSnapshot {
+    /// Location of the snapshot in the snapshot storage
+    snapshot_path: PathBuf,
+    /// Unpacked snapshot, unless it is mounted lazily and was not queried recently
+    loaded: Option<LoadedSnapshot>,
+    /// Last time a lazily mounted snapshot was queried
+    last_access: Option<Arc<Mutex<Instant>>>,
+    /// Prevents concurrent queries from loading a lazily mounted snapshot twice
+    loading: Arc<tokio::sync::Mutex<()>>,
+}
+
+struct LoadedSnapshot {
+    collection: Collection,
+    /// Directory the snapshot is unpacked into, removed on unload
+    _directory: TempDir,
+}
+
+pub type MountedSnapshots = HashMap<String, MountedSnapshot>;
+
+/// Name under which a mounted snapshot of a collection can be queried
+pub fn mounted_snapshot_name(collection_name: &str, snapshot_name: &str) -> String {
+    format!("{collection_name}{SNAPSHOT_NAME_SEPARATOR}{snapshot_name}")
+}
+
+/// Name of the collection a mounted snapshot was taken of, or the name itself if it doesn't refer
+/// to a mounted snapshot
+pub fn source_collection_name(name: &str) -> &str {
+    name.split_once(SNAPSHOT_NAME_SEPARATOR)
+        .map_or(name, |(collection_name, _)| collection_name)
+}
+
+impl TableOfContent {
+    /// Mount a collection snapshot read-only, so it can be queried as `collection:snapshot_name`
+    /// without restoring over the live collection. The live collection doesn't have to exist, so
+    /// snapshots of deleted collections can be queried as archives.
+    ///
+    /// If `lazy`, the snapshot stays in the snapshot storage until it is queried. It is then
+    /// downloaded and unpacked, and unloaded again once it was not queried for
+    /// `lazy_mount_idle_timeout_sec`, so rarely queried snapshots don't hold local disk space.
+    ///
+    /// Mounts are local to this peer and do not survive a restart.
+    /// Returns `false` if the snapshot is already mounted.
+    pub async fn mount_snapshot(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+        snapshot_name: &str,
+        lazy: bool,
+    ) -> Result<bool, StorageError> {
+        let mount_name = mounted_snapshot_name(collection_pass.name(), snapshot_name);
+
+        if self
+            .mounted_snapshots
+            .read()
+            .await
+            .contains_key(&mount_name)
+        {
+            return Ok(false);
+        }
+
+        let snapshot_path = self
+            .get_snapshots_storage_manager()?
+            .get_snapshot_path(
+                &self.snapshots_path_for_collection(collection_pass.name()),
+                snapshot_name,
+            )
+            .await?;
+
+        let loaded = if lazy {
+            None
+        } else {
+            Some(self.load_snapshot(&mount_name, &snapshot_path).await?)
+        };
+        let last_access = lazy.then(|| Arc::new(Mutex::new(Instant::now())));
+
+        let mut mounted_snapshots = self.mounted_snapshots.write().await;
+        if mounted_snapshots.contains_key(&mount_name) {
+            // Mounted concurrently
+            return Ok(false);
+        }
+        mounted_snapshots.insert(
+            mount_name.clone(),
+            MountedSnapshot {
+                snapshot_path,
+                loaded,
+                last_access: last_access.clone(),
+                loading: Default::default(),
+            },
+        );
+        drop(mounted_snapshots);
+
+        if let Some(last_access) = last_access {
+            self.unload_when_idle(mount_name, last_access);
+        }
+
+        Ok(true)
+    }
+
+    /// Download and unpack a collection snapshot, and load it as a local-only collection
+    async fn load_snapshot(
+        &self,
+        mount_name: &str,
+        snapshot_path: &Path,
+    ) -> Result<LoadedSnapshot, StorageError> {
+        let snapshot_manager = self.get_snapshots_storage_manager()?;
+
+        // Snapshots in object storage have to be downloaded first
+        let download_dir = self.snapshots_download_tempdir()?;
+        let snapshot_path = match &snapshot_manager {
+            SnapshotStorageManager::LocalFS(_) => snapshot_path.to_path_buf(),
+            SnapshotStorageManager::S3(_) => {
+                let file_name = snapshot_path.file_name().ok_or_else(|| {
+                    StorageError::service_error(format!(
+                        "Invalid snapshot path {}",
+                        snapshot_path.display(),
+                    ))
+                })?;
+                let local_path = download_dir.path().join(file_name);
+                snapshot_manager
+                    .get_stored_file(snapshot_path, &local_path)
+                    .await?;
+                local_path
+            }
+        };
+
+        let mount_dir = tempfile::Builder::new()
+            .prefix(&format!("col-{mount_name}-mount-"))
+            .tempdir_in(self.optional_temp_or_storage_temp_path()?)?;
+
+        log::debug!(
+            "Mounting snapshot {} as {mount_name} at {}",
+            snapshot_path.display(),
+            mount_dir.path().display(),
+        );
+
+        let this_peer_id = self.this_peer_id;
+        let collection_path = mount_dir.path().to_path_buf();
+        let restoring = tokio::task::spawn_blocking(move || {
+            // Mounted snapshot is always local-only, it must not take part in the cluster
+            Collection::restore_snapshot(&snapshot_path, &collection_path, this_peer_id, false)
+        });
+        restoring.await??;
+        drop(download_dir);
+
+        let snapshots_path = mount_dir.path().join("snapshots");
+        tokio::fs::create_dir_all(&snapshots_path).await?;
+
+        // Mounted snapshot is read-only, its segments must not be optimized
+        let optimizers_overwrite = match self.storage_config.optimizers_overwrite.clone() {
+            Some(overwrite) => OptimizersConfigDiff {
+                max_optimization_threads: Some(0),
+                ..overwrite
+            },
+            None => OptimizersConfigDiff {
+                deleted_threshold: None,
+                vacuum_min_vector_number: None,
+                default_segment_number: None,
+                max_segment_size: None,
+                memmap_threshold: None,
+                indexing_threshold: None,
+                flush_interval_sec: None,
+                max_optimization_threads: Some(0),
+            },
+        };
+
+        let collection = Collection::load(
+            mount_name.to_string(),
+            this_peer_id,
+            mount_dir.path(),
+            &snapshots_path,
+            self.storage_config.to_shared_storage_config(false).into(),
+            self.channel_service.clone(),
+            Self::change_peer_state_callback(
+                None,
+                mount_name.to_string(),
+                ReplicaState::Dead,
+                None,
+            ),
+            Self::request_shard_transfer_callback(None, mount_name.to_string()),
+            Self::abort_shard_transfer_callback(None, mount_name.to_string()),
+            Some(self.search_runtime.handle().clone()),
+            Some(self.update_runtime.handle().clone()),
+            self.optimizer_cpu_budget.clone(),
+            Some(optimizers_overwrite),
+        )
+        .await;
+
+        Ok(LoadedSnapshot {
+            collection,
+            _directory: mount_dir,
+        })
+    }
+
+    /// Periodically unload a lazily mounted snapshot if it was not queried recently.
+    /// Stops once the snapshot is unmounted.
+    fn unload_when_idle(&self, mount_name: String, last_access: Arc<Mutex<Instant>>) {
+        let mounted_snapshots = self.mounted_snapshots.clone();
+        let idle_timeout = Duration::from_secs(self.storage_config.lazy_mount_idle_timeout_sec);
+
+        self.general_runtime.spawn(async move {
+            loop {
+                tokio::time::sleep(idle_timeout.max(MIN_IDLE_CHECK_INTERVAL)).await;
+
+                let mut mounted_snapshots = mounted_snapshots.write().await;
+                let Some(mounted) = mounted_snapshots.get_mut(&mount_name) else {
+                    break;
+                };
+                // Unmounted, then mounted again
+                if !mounted
+                    .last_access
+                    .as_ref()
+                    .is_some_and(|access| Arc::ptr_eq(access, &last_access))
+                {
+                    break;
+                }
+
+                if mounted.loaded.is_some() && last_access.lock().elapsed() >= idle_timeout {
+                    log::debug!("Unloading idle mounted snapshot {mount_name}");
+                    let unloaded = mounted.loaded.take();
+                    drop(mounted_snapshots);
+                    drop(unloaded);
+                }
+            }
+        });
+    }
+
+    /// Unload a mounted snapshot and remove its unpacked files
+    ///
+    /// Returns `false` if the snapshot is not mounted.
+    pub async fn unmount_snapshot(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+        snapshot_name: &str,
+    ) -> Result<bool, StorageError> {
+        let mount_name = mounted_snapshot_name(collection_pass.name(), snapshot_name);
+
+        let Some(mounted) = self.mounted_snapshots.write().await.remove(&mount_name) else {
+            return Ok(false);
+        };
+
+        log::debug!("Unmounting snapshot {snapshot_name} of collection {collection_pass}");
+        drop(mounted);
+
+        Ok(true)
+    }
+
+    /// Same as `get_collection`, but also resolves `collection:snapshot_name` to a mounted
+    /// snapshot. Must only be used for read operations.
+    pub(super) async fn get_collection_for_read(
+        &self,
+        collection: &CollectionPass<'_>,
+    ) -> Result<RwLockReadGuard<Collection>, StorageError> {
+        self.get_collection_for_read_unchecked(collection.name())
+            .await
+    }
+
+    /// Same as `get_collection_for_read`, but does not check access rights.
+    pub(super) async fn get_collection_for_read_unchecked(
+        &self,
+        collection_name: &str,
+    ) -> Result<RwLockReadGuard<Collection>, StorageError> {
+        if collection_name.contains(SNAPSHOT_NAME_SEPARATOR)
+            && self.collections.read().await.get(collection_name).is_none()
+        {
+            self.load_lazy_mount(collection_name).await?;
+
+            let mounted_snapshots = self.mounted_snapshots.read().await;
+            let collection = RwLockReadGuard::try_map(mounted_snapshots, |mounted| {
+                let loaded = mounted.get(collection_name)?.loaded.as_ref()?;
+                Some(&loaded.collection)
+            });
+            if let Ok(collection) = collection {
+                return Ok(collection);
+            }
+        }
+
+        self.get_collection_unchecked(collection_name).await
+    }
+
+    /// Load a lazily mounted snapshot if it is not loaded yet, and mark it as accessed
+    async fn load_lazy_mount(&self, mount_name: &str) -> Result<(), StorageError> {
+        let loading = {
+            let mounted_snapshots = self.mounted_snapshots.read().await;
+            let Some(mounted) = mounted_snapshots.get(mount_name) else {
+                return Ok(());
+            };
+            if let Some(last_access) = &mounted.last_access {
+                *last_access.lock() = Instant::now();
+            }
+            if mounted.loaded.is_some() {
+                return Ok(());
+            }
+            mounted.loading.clone()
+        };
+
+        let _loading = loading.lock().await;
+
+        let snapshot_path = match self.mounted_snapshots.read().await.get(mount_name) {
+            Some(mounted) if mounted.loaded.is_none() => mounted.snapshot_path.clone(),
+            // Loaded concurrently, or unmounted
+            _ => return Ok(()),
+        };
+
+        log::info!("Loading lazily mounted snapshot {mount_name}");
+        let loaded = self.load_snapshot(mount_name, &snapshot_path).await?;
+
+        if let Some(mounted) = self.mounted_snapshots.write().await.get_mut(mount_name) {
+            if let Some(last_access) = &mounted.last_access {
+                *last_access.lock() = Instant::now();
+            }
+            mounted.loaded = Some(loaded);
+        }
+
+        Ok(())
+    }
+}
@@ -1,18 +1,21 @@
 use std::time::Duration;
 
 use collection::collection::Collection;
+use collection::common::resource_usage;
 use collection::grouping::group_by::GroupRequest;
 use collection::grouping::GroupBy;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::point_ops::WriteOrdering;
+use collection::operations::session_token::{self, SessionToken};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use collection::{discovery, recommendations};
+use futures::future;
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
-use segment::types::{ScoredPoint, ShardKey};
+use segment::types::{QueryStats, ScoredPoint, ShardKey};
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageError;
@@ -29,6 +32,7 @@ impl TableOfContent {
     /// # Result
     ///
     /// Points with recommendation score
+    #[allow(clippy::too_many_arguments)]
     pub async fn recommend(
         &self,
         collection_name: &str,
@@ -37,17 +41,27 @@ impl TableOfContent {
         shard_selector: ShardSelectorInternal,
         access: Access,
         timeout: Option<Duration>,
+        session_token: Option<&SessionToken>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
-        let collection = self.get_collection(&collection_pass).await?;
-        recommendations::recommend_by(
-            request,
-            &collection,
-            |name| self.get_collection_opt(name),
-            read_consistency,
-            shard_selector,
-            timeout,
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        self.rewrite_request(&collection.name(), &mut request);
+        self.check_query_limits(&collection, [(&request, &shard_selector)])?;
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
+                timeout,
+                recommendations::recommend_by(
+                    request,
+                    &collection,
+                    |name| self.get_collection_opt(name),
+                    read_consistency,
+                    shard_selector,
+                    timeout,
+                ),
+            ),
         )
         .await
         .map_err(|err| err.into())
@@ -70,6 +84,7 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         access: Access,
         timeout: Option<Duration>,
+        session_token: Option<&SessionToken>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
@@ -79,13 +94,30 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
-        let collection = self.get_collection(&collection_pass).await?;
-        recommendations::recommend_batch_by(
-            requests,
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        let collection_name = collection.name();
+        for (request, _shard_selector) in &mut requests {
+            self.rewrite_request(&collection_name, request);
+        }
+        self.check_query_limits(
             &collection,
-            |name| self.get_collection_opt(name),
-            read_consistency,
-            timeout,
+            requests
+                .iter()
+                .map(|(request, shard_selector)| (request, shard_selector)),
+        )?;
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
+                timeout,
+                recommendations::recommend_batch_by(
+                    requests,
+                    &collection,
+                    |name| self.get_collection_opt(name),
+                    read_consistency,
+                    timeout,
+                ),
+            ),
         )
         .await
         .map_err(|err| err.into())
@@ -105,6 +137,7 @@ impl TableOfContent {
     /// # Result
     ///
     /// Points with search score
+    #[allow(clippy::too_many_arguments)]
     pub async fn core_search_batch(
         &self,
         collection_name: &str,
@@ -113,6 +146,7 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
         timeout: Option<Duration>,
+        session_token: Option<&SessionToken>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
         let mut collection_pass = None;
         for request in &mut request.searches {
@@ -122,11 +156,28 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
-        let collection = self.get_collection(&collection_pass).await?;
-        collection
-            .core_search_batch(request, read_consistency, shard_selection, timeout)
-            .await
-            .map_err(|err| err.into())
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        let collection_name = collection.name();
+        for request in &mut request.searches {
+            self.rewrite_request(&collection_name, request);
+        }
+        self.check_query_limits(
+            &collection,
+            request
+                .searches
+                .iter()
+                .map(|request| (request, &shard_selection)),
+        )?;
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
+                timeout,
+                collection.core_search_batch(request, read_consistency, shard_selection, timeout),
+            ),
+        )
+        .await
+        .map_err(|err| err.into())
     }
 
     /// Count points in the collection.
@@ -148,14 +199,22 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         access: Access,
+        session_token: Option<&SessionToken>,
     ) -> Result<CountResult, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
-        let collection = self.get_collection(&collection_pass).await?;
-        collection
-            .count(request, read_consistency, &shard_selection)
-            .await
-            .map_err(|err| err.into())
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        self.rewrite_request(&collection.name(), &mut request);
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
+                None,
+                collection.count(request, read_consistency, &shard_selection),
+            ),
+        )
+        .await
+        .map_err(|err| err.into())
     }
 
     /// Return specific points by IDs
@@ -176,16 +235,24 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         access: Access,
+        session_token: Option<&SessionToken>,
     ) -> Result<Vec<Record>, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
-        let collection = self.get_collection(&collection_pass).await?;
-        collection
-            .retrieve(request, read_consistency, &shard_selection)
-            .await
-            .map_err(|err| err.into())
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
+                None,
+                collection.retrieve(request, read_consistency, &shard_selection),
+            ),
+        )
+        .await
+        .map_err(|err| err.into())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn group(
         &self,
         collection_name: &str,
@@ -194,10 +261,13 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
         timeout: Option<Duration>,
+        session_token: Option<&SessionToken>,
     ) -> Result<GroupsResult, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
-        let collection = self.get_collection(&collection_pass).await?;
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        self.rewrite_request(&collection.name(), &mut request);
+        self.check_query_limits(&collection, [(&request, &shard_selection)])?;
 
         let collection_by_name = |name| self.get_collection_opt(name);
 
@@ -206,13 +276,16 @@ impl TableOfContent {
             .set_shard_selection(shard_selection)
             .set_timeout(timeout);
 
-        group_by
-            .execute()
-            .await
-            .map(|groups| GroupsResult { groups })
-            .map_err(|err| err.into())
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(session_token, timeout, group_by.execute()),
+        )
+        .await
+        .map(|groups| GroupsResult { groups })
+        .map_err(|err| err.into())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn discover(
         &self,
         collection_name: &str,
@@ -221,17 +294,27 @@ impl TableOfContent {
         shard_selector: ShardSelectorInternal,
         access: Access,
         timeout: Option<Duration>,
+        session_token: Option<&SessionToken>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
-        let collection = self.get_collection(&collection_pass).await?;
-        discovery::discover(
-            request,
-            &collection,
-            |name| self.get_collection_opt(name),
-            read_consistency,
-            shard_selector,
-            timeout,
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        self.rewrite_request(&collection.name(), &mut request);
+        self.check_query_limits(&collection, [(&request, &shard_selector)])?;
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
+                timeout,
+                discovery::discover(
+                    request,
+                    &collection,
+                    |name| self.get_collection_opt(name),
+                    read_consistency,
+                    shard_selector,
+                    timeout,
+                ),
+            ),
         )
         .await
         .map_err(|err| err.into())
@@ -244,6 +327,7 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         access: Access,
         timeout: Option<Duration>,
+        session_token: Option<&SessionToken>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
@@ -253,14 +337,31 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
-        let collection = self.get_collection(&collection_pass).await?;
-
-        discovery::discover_batch(
-            requests,
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        let collection_name = collection.name();
+        for (request, _shard_selector) in &mut requests {
+            self.rewrite_request(&collection_name, request);
+        }
+        self.check_query_limits(
             &collection,
-            |name| self.get_collection_opt(name),
-            read_consistency,
-            timeout,
+            requests
+                .iter()
+                .map(|(request, shard_selector)| (request, shard_selector)),
+        )?;
+
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
+                timeout,
+                discovery::discover_batch(
+                    requests,
+                    &collection,
+                    |name| self.get_collection_opt(name),
+                    read_consistency,
+                    timeout,
+                ),
+            ),
         )
         .await
         .map_err(|err| err.into())
@@ -284,43 +385,171 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         access: Access,
+        session_token: Option<&SessionToken>,
     ) -> Result<ScrollResult, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
-        let collection = self.get_collection(&collection_pass).await?;
-        collection
-            .scroll_by(request, read_consistency, &shard_selection)
-            .await
-            .map_err(|err| err.into())
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        self.rewrite_request(&collection.name(), &mut request);
+        resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
+                None,
+                collection.scroll_by(request, read_consistency, &shard_selection),
+            ),
+        )
+        .await
+        .map_err(|err| err.into())
+    }
+
+    /// Read points changed since the given cursor in shards stored on this peer
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - which collection to use
+    /// * `request` - [`PointChangesRequest`]
+    ///
+    /// # Result
+    ///
+    /// Changed points and the cursor to read next changes from
+    pub async fn point_changes(
+        &self,
+        collection_name: &str,
+        mut request: PointChangesRequest,
+        access: Access,
+    ) -> Result<PointChangesResult, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        resource_usage::scope(
+            collection.resource_usage(),
+            collection.point_changes(request),
+        )
+        .await
+        .map_err(|err| err.into())
     }
 
     pub async fn query_batch(
         &self,
         collection_name: &str,
-        mut requests: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        requests: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
         read_consistency: Option<ReadConsistency>,
         access: Access,
         timeout: Option<Duration>,
+        session_token: Option<&SessionToken>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        self.query_batch_with_stats(
+            collection_name,
+            requests,
+            read_consistency,
+            access,
+            timeout,
+            session_token,
+        )
+        .await
+        .map(|(results, _stats)| results)
+    }
+
+    /// Same as `query_batch`, but also returns the work done to execute each query requested
+    /// `with_stats`
+    pub async fn query_batch_with_stats(
+        &self,
+        collection_name: &str,
+        mut requests: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+        session_token: Option<&SessionToken>,
+    ) -> Result<(Vec<Vec<ScoredPoint>>, Vec<Option<QueryStats>>), StorageError> {
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
             collection_pass = Some(access.check_point_op(collection_name, request)?);
         }
         let Some(collection_pass) = collection_pass else {
-            return Ok(vec![]);
+            return Ok((vec![], vec![]));
         };
 
-        let collection = self.get_collection(&collection_pass).await?;
+        // Reranking is done on the final results, with the external reranker
+        let reranks: Vec<_> = requests
+            .iter_mut()
+            .map(|(request, shard_selector)| {
+                request
+                    .rerank
+                    .take()
+                    .map(|rerank| (rerank, shard_selector.clone()))
+            })
+            .collect();
+        let reranker = match &self.reranker {
+            Some(reranker) => Some(reranker),
+            None if reranks.iter().any(Option::is_some) => {
+                return Err(StorageError::bad_input(
+                    "Reranking is not available, no reranker is configured",
+                ));
+            }
+            None => None,
+        };
+
+        let collection = self.get_collection_for_read(&collection_pass).await?;
+        let collection_name = collection.name();
+        for (request, _shard_selector) in &mut requests {
+            self.rewrite_request(&collection_name, request);
+        }
 
-        collection
-            .query_batch(
-                requests,
-                |name| self.get_collection_opt(name),
-                read_consistency,
+        self.check_experimental_features(
+            &collection_name,
+            requests.iter().map(|(request, _)| request),
+        )?;
+
+        self.check_query_limits(
+            &collection,
+            requests
+                .iter()
+                .map(|(request, shard_selector)| (request, shard_selector)),
+        )?;
+
+        let (results, stats) = resource_usage::scope(
+            collection.resource_usage(),
+            session_token::scope(
+                session_token,
                 timeout,
-            )
-            .await
-            .map_err(|err| err.into())
+                collection.query_batch_with_stats(
+                    requests,
+                    |name| self.get_collection_opt(name),
+                    read_consistency,
+                    timeout,
+                ),
+            ),
+        )
+        .await?;
+
+        let Some(reranker) = reranker else {
+            return Ok((results, stats));
+        };
+
+        let collection: &Collection = &collection;
+        let reranked = results
+            .into_iter()
+            .zip(reranks)
+            .map(|(points, rerank)| async move {
+                match rerank {
+                    Some((rerank, shard_selector)) => {
+                        reranker
+                            .rerank(
+                                collection,
+                                points,
+                                &rerank,
+                                read_consistency,
+                                &shard_selector,
+                            )
+                            .await
+                    }
+                    None => Ok(points),
+                }
+            });
+
+        let results = future::try_join_all(reranked).await?;
+        Ok((results, stats))
     }
 
     /// # Cancel safety
@@ -346,12 +575,24 @@ impl TableOfContent {
             .collect();
 
         // `Collection::update_from_client` is cancel safe, so it's safe to use `TryStreamExt::try_collect`
-        let results: Vec<_> = updates.try_collect().await?;
+        let results: Vec<UpdateResult> = updates.try_collect().await?;
 
-        results
+        // The token covers the writes to all shard keys
+        let mut session_token = SessionToken::default();
+        for result in &results {
+            if let Some(token) = &result.session_token {
+                session_token.merge(token);
+            }
+        }
+
+        let mut result = results
             .into_iter()
             .next()
-            .ok_or_else(|| StorageError::bad_input("Empty shard keys selection"))
+            .ok_or_else(|| StorageError::bad_input("Empty shard keys selection"))?;
+        if !session_token.is_empty() {
+            result.session_token = Some(session_token);
+        }
+        Ok(result)
     }
 
     /// # Cancel safety
@@ -400,11 +641,12 @@ impl TableOfContent {
         // │ Updating node     │ <- update_from_peer
         // └───────────────────┘
 
-        let _update_rate_limiter = match &self.update_rate_limiter {
+        let update_rate_limiter = self.update_rate_limiter.read().clone();
+        let _update_rate_limiter = match update_rate_limiter {
             Some(update_rate_limiter) => {
                 // We only want to rate limit the first node in the chain
                 if !shard_selector.is_shard_id() {
-                    Some(update_rate_limiter.acquire().await)
+                    Some(update_rate_limiter.acquire_owned().await)
                 } else {
                     None
                 }
@@ -419,6 +661,28 @@ impl TableOfContent {
 
         // TODO: `debug_assert(operation.clock_tag.is_none())` for `_update_shard_keys`/`update_from_client`!?
 
+        // Operations received from users, and not their parts forwarded by other peers
+        let is_from_user = operation.clock_tag.is_none() && !shard_selector.is_shard_id();
+
+        if is_from_user && operation.operation.is_write_operation() {
+            self.check_standby_write(&collection.name())?;
+        }
+
+        let mirror = self
+            .mirrors
+            .get(&collection.name())
+            .filter(|_| is_from_user);
+
+        // Writes to a mirrored collection are applied one at a time, and queued before the next
+        // one is applied, so the secondary cluster receives them in the same order
+        let _mirror_apply_lock = match mirror {
+            Some(mirror) => Some(mirror.lock_apply().await),
+            None => None,
+        };
+
+        let mirrored =
+            mirror.map(|mirror| (mirror, operation.operation.clone(), shard_selector.clone()));
+
         let res = match shard_selector {
             ShardSelectorInternal::Empty => {
                 collection
@@ -468,6 +732,10 @@ impl TableOfContent {
             }
         };
 
+        if let Some((mirror, operation, shard_selector)) = mirrored {
+            mirror.push(operation, &shard_selector);
+        }
+
         Ok(res)
     }
 }
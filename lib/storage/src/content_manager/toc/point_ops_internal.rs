@@ -1,9 +1,12 @@
 //! Methods here are for distributed internal use only.
 
+use std::sync::Arc;
 use std::time::Duration;
 
+use collection::common::resource_usage;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use segment::index::search_stats::SearchStats;
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageError;
@@ -15,12 +18,15 @@ impl TableOfContent {
         requests: Vec<ShardQueryRequest>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        search_stats: Option<Arc<SearchStats>>,
     ) -> Result<Vec<ShardQueryResponse>, StorageError> {
         let collection = self.get_collection_unchecked(collection_name).await?;
 
-        let res = collection
-            .query_batch_internal(requests, &shard_selection, timeout)
-            .await?;
+        let res = resource_usage::scope(
+            collection.resource_usage(),
+            collection.query_batch_internal(requests, &shard_selection, timeout, search_stats),
+        )
+        .await?;
 
         Ok(res)
     }
@@ -0,0 +1,406 @@
+//! Limits of the queries to collections.
+//!
+//! Queries exceeding the configured limits are either rejected, or executed with the exceeded
+//! limits returned as warnings. The latter gives clients a migration period, before the limits
+//! are enforced.
+//!
+//! Limits are checked by the table of contents for all kinds of searches, whichever API they come
+//! from. Warnings are collected in the scope of the API request, see [`collect_warnings`], and
+//! returned in the body of its response.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+
+use collection::collection::Collection;
+use collection::grouping::group_by::{GroupRequest, SourceRequest};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{
+    CoreSearchRequest, DiscoverRequestInternal, RecommendRequestInternal,
+};
+use collection::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest,
+};
+use itertools::Itertools;
+use segment::types::{Filter, PayloadKeyType};
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::types::{QueryLimitsConfig, QueryLimitsMode};
+
+tokio::task_local! {
+    /// Warnings of the current API request
+    static WARNINGS: RefCell<Vec<String>>;
+}
+
+/// Run the API request future, collecting the warnings about the query limits its queries exceed
+pub async fn collect_warnings<F: Future>(future: F) -> (F::Output, Vec<String>) {
+    WARNINGS
+        .scope(RefCell::new(Vec::new()), async {
+            let output = future.await;
+            let warnings = WARNINGS.with(|warnings| warnings.take());
+            (output, warnings)
+        })
+        .await
+}
+
+/// Add warnings to the current API request. Ignored, if the request doesn't collect warnings.
+fn add_warnings(new_warnings: impl IntoIterator<Item = String>) {
+    let _ = WARNINGS.try_with(|warnings| {
+        let mut warnings = warnings.borrow_mut();
+        for warning in new_warnings {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+    });
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryLimitViolation {
+    Limit { limit: usize, max_limit: usize },
+    PrefetchDepth { depth: usize, max_depth: usize },
+    UnindexedFilter { key: PayloadKeyType },
+}
+
+impl fmt::Display for QueryLimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Limit { limit, max_limit } => {
+                write!(f, "limit {limit} exceeds the max limit of {max_limit}")
+            }
+            Self::PrefetchDepth { depth, max_depth } => {
+                write!(
+                    f,
+                    "prefetch depth {depth} exceeds the max depth of {max_depth}",
+                )
+            }
+            Self::UnindexedFilter { key } => {
+                write!(f, "filter by field '{key}' without a suitable index")
+            }
+        }
+    }
+}
+
+/// Limits of a query, and of its prefetches
+pub(super) struct QueryShape<'a> {
+    limit: usize,
+    depth: usize,
+    filters: Vec<&'a Filter>,
+}
+
+impl<'a> QueryShape<'a> {
+    fn flat(limit: usize, filter: Option<&'a Filter>) -> Self {
+        Self {
+            limit,
+            depth: 0,
+            filters: filter.into_iter().collect(),
+        }
+    }
+
+    fn add_prefetch(&mut self, prefetch: &'a CollectionPrefetch, depth: usize) {
+        self.limit = self.limit.max(prefetch.limit);
+        self.depth = self.depth.max(depth);
+        self.filters.extend(&prefetch.filter);
+        for nested in &prefetch.prefetch {
+            self.add_prefetch(nested, depth + 1);
+        }
+    }
+}
+
+impl<'a> From<&'a CollectionQueryRequest> for QueryShape<'a> {
+    fn from(request: &'a CollectionQueryRequest) -> Self {
+        let mut shape = Self::flat(request.limit, request.filter.as_ref());
+        for prefetch in &request.prefetch {
+            shape.add_prefetch(prefetch, 1);
+        }
+        shape
+    }
+}
+
+impl<'a> From<&'a CoreSearchRequest> for QueryShape<'a> {
+    fn from(request: &'a CoreSearchRequest) -> Self {
+        Self::flat(request.limit, request.filter.as_ref())
+    }
+}
+
+impl<'a> From<&'a RecommendRequestInternal> for QueryShape<'a> {
+    fn from(request: &'a RecommendRequestInternal) -> Self {
+        Self::flat(request.limit, request.filter.as_ref())
+    }
+}
+
+impl<'a> From<&'a DiscoverRequestInternal> for QueryShape<'a> {
+    fn from(request: &'a DiscoverRequestInternal) -> Self {
+        Self::flat(request.limit, request.filter.as_ref())
+    }
+}
+
+impl<'a> From<&'a GroupRequest> for QueryShape<'a> {
+    fn from(request: &'a GroupRequest) -> Self {
+        let filter = match &request.source {
+            SourceRequest::Search(request) => request.filter.as_ref(),
+            SourceRequest::Recommend(request) => request.filter.as_ref(),
+        };
+        // Each of the groups holds up to `group_size` hits
+        Self::flat(request.limit.saturating_mul(request.group_size), filter)
+    }
+}
+
+impl QueryLimitsConfig {
+    /// All limits the query exceeds
+    fn violations(
+        &self,
+        shape: &QueryShape,
+        unindexed_keys: impl Fn(&Filter) -> Vec<PayloadKeyType>,
+    ) -> Vec<QueryLimitViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(max_limit) = self.max_limit {
+            if shape.limit > max_limit {
+                violations.push(QueryLimitViolation::Limit {
+                    limit: shape.limit,
+                    max_limit,
+                });
+            }
+        }
+
+        if let Some(max_depth) = self.max_prefetch_depth {
+            if shape.depth > max_depth {
+                violations.push(QueryLimitViolation::PrefetchDepth {
+                    depth: shape.depth,
+                    max_depth,
+                });
+            }
+        }
+
+        if self.forbid_unindexed_filter {
+            let keys = shape
+                .filters
+                .iter()
+                .flat_map(|filter| unindexed_keys(filter))
+                .unique();
+            violations.extend(keys.map(|key| QueryLimitViolation::UnindexedFilter { key }));
+        }
+
+        violations
+    }
+}
+
+impl TableOfContent {
+    /// Check the queries to the collection against the configured query limits.
+    ///
+    /// Returns an error in `enforce` mode. In `warn` mode, the exceeded limits are added to the
+    /// warnings of the current API request instead.
+    pub(super) fn check_query_limits<'a, R>(
+        &self,
+        collection: &Collection,
+        requests: impl IntoIterator<Item = (&'a R, &'a ShardSelectorInternal)>,
+    ) -> Result<(), StorageError>
+    where
+        R: 'a,
+        &'a R: Into<QueryShape<'a>>,
+    {
+        let Some(query_limits) = &self.storage_config.query_limits else {
+            return Ok(());
+        };
+
+        for (request, shard_selector) in requests {
+            // Requests to a specific shard come from other peers, which checked the limits already
+            if shard_selector.is_shard_id() {
+                continue;
+            }
+
+            let violations = query_limits.violations(&request.into(), |filter| {
+                collection.unindexed_filter_keys(filter)
+            });
+            if violations.is_empty() {
+                continue;
+            }
+
+            match query_limits.mode {
+                QueryLimitsMode::Enforce => {
+                    return Err(StorageError::bad_request(format!(
+                        "Query exceeds the limits: {}",
+                        violations.iter().join(", "),
+                    )));
+                }
+                QueryLimitsMode::Warn => {
+                    collection.record_query_over_soft_limits();
+                    add_warnings(violations.iter().map(ToString::to_string));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::rest::SearchRequestInternal;
+    use segment::types::{Condition, FieldCondition, WithPayloadInterface, WithVector};
+
+    use super::*;
+
+    fn prefetch(limit: usize, prefetch: Vec<CollectionPrefetch>) -> CollectionPrefetch {
+        CollectionPrefetch {
+            prefetch,
+            query: None,
+            using: String::new(),
+            filter: None,
+            score_threshold: None,
+            limit,
+            params: None,
+            lookup_from: None,
+            shard_selection: None,
+        }
+    }
+
+    fn request(limit: usize, prefetch: Vec<CollectionPrefetch>) -> CollectionQueryRequest {
+        CollectionQueryRequest {
+            prefetch,
+            query: None,
+            using: String::new(),
+            filter: None,
+            propagate_filter: CollectionQueryRequest::DEFAULT_PROPAGATE_FILTER,
+            ids: None,
+            filter_template: None,
+            score_threshold: None,
+            limit,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+            with_routing: false,
+            with_stats: false,
+            lookup_from: None,
+            budget: None,
+            rerank: None,
+            skip_missing: false,
+        }
+    }
+
+    fn filter_by(key: &str) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            key.parse().unwrap(),
+            "value".to_string().into(),
+        )))
+    }
+
+    /// Fields starting with `unindexed` have no index
+    fn unindexed_keys(filter: &Filter) -> Vec<PayloadKeyType> {
+        filter
+            .must
+            .iter()
+            .flatten()
+            .filter_map(|condition| match condition {
+                Condition::Field(field) => Some(field.key.clone()),
+                _ => None,
+            })
+            .filter(|key| key.to_string().starts_with("unindexed"))
+            .collect()
+    }
+
+    fn limits() -> QueryLimitsConfig {
+        QueryLimitsConfig {
+            mode: QueryLimitsMode::Warn,
+            max_limit: Some(100),
+            max_prefetch_depth: Some(1),
+            forbid_unindexed_filter: true,
+        }
+    }
+
+    #[test]
+    fn test_query_within_limits() {
+        let mut request = request(100, vec![prefetch(100, vec![])]);
+        request.filter = Some(filter_by("indexed"));
+
+        let shape = QueryShape::from(&request);
+        assert!(limits().violations(&shape, unindexed_keys).is_empty());
+
+        // Nothing is limited by default
+        let request = self::request(10_000, vec![prefetch(1, vec![prefetch(1, vec![])])]);
+        let shape = QueryShape::from(&request);
+        assert!(QueryLimitsConfig::default()
+            .violations(&shape, unindexed_keys)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_query_over_limits() {
+        let mut nested = prefetch(1000, vec![]);
+        nested.filter = Some(filter_by("unindexed_a"));
+        let mut request = request(10, vec![prefetch(10, vec![nested])]);
+        request.filter = Some(filter_by("unindexed_a"));
+
+        let shape = QueryShape::from(&request);
+        let violations = limits().violations(&shape, unindexed_keys);
+        assert_eq!(
+            violations,
+            vec![
+                QueryLimitViolation::Limit {
+                    limit: 1000,
+                    max_limit: 100,
+                },
+                QueryLimitViolation::PrefetchDepth {
+                    depth: 2,
+                    max_depth: 1,
+                },
+                // Reported once, although used in two filters
+                QueryLimitViolation::UnindexedFilter {
+                    key: "unindexed_a".parse().unwrap(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_group_request_limit() {
+        let search = SearchRequestInternal {
+            vector: vec![1.0, 0.0].into(),
+            filter: None,
+            params: None,
+            limit: 20,
+            offset: None,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+        };
+        let mut request = GroupRequest::with_limit_from_request(
+            SourceRequest::Search(search),
+            "docId".parse().unwrap(),
+            1,
+        );
+
+        let shape = QueryShape::from(&request);
+        assert!(limits().violations(&shape, unindexed_keys).is_empty());
+
+        // Hits of all groups count towards the limit
+        request.group_size = 10;
+        let shape = QueryShape::from(&request);
+        assert_eq!(
+            limits().violations(&shape, unindexed_keys),
+            vec![QueryLimitViolation::Limit {
+                limit: 200,
+                max_limit: 100,
+            }],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_warnings() {
+        let ((), warnings) = collect_warnings(async {
+            add_warnings(["first".to_string(), "second".to_string()]);
+            // Same warning of another query in the batch is reported once
+            add_warnings(["first".to_string()]);
+        })
+        .await;
+        assert_eq!(warnings, vec!["first".to_string(), "second".to_string()]);
+
+        // Outside of a request, warnings are dropped
+        add_warnings(["ignored".to_string()]);
+        let ((), warnings) = collect_warnings(async {}).await;
+        assert!(warnings.is_empty());
+    }
+}
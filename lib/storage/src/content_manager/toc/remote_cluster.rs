@@ -0,0 +1,166 @@
+//! Client of the public gRPC API of another Qdrant cluster, used to copy collections into it.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use api::grpc::conversions::convert_shard_key_to_grpc;
+use api::grpc::qdrant::collections_client::CollectionsClient;
+use api::grpc::qdrant::points_client::PointsClient;
+use api::grpc::qdrant::qdrant_client::QdrantClient;
+use api::grpc::qdrant::ShardKeySelector;
+use api::rest::Record;
+use collection::operations::point_ops::{PointInsertOperationsInternal, PointStruct};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CollectionError, ScrollRequestInternal};
+use collection::shards::conversions::internal_upsert_points;
+use segment::types::{ShardKey, WithPayloadInterface, WithVector};
+use tokio::sync::RwLock;
+use tonic::metadata::AsciiMetadataValue;
+use tonic::transport::{Channel, Endpoint};
+
+use crate::content_manager::collections_ops::Collections;
+use crate::content_manager::errors::StorageError;
+
+#[derive(Clone)]
+pub(super) struct RemoteCluster {
+    channel: Channel,
+    api_key: Option<AsciiMetadataValue>,
+}
+
+impl RemoteCluster {
+    /// Client of the cluster with the given gRPC URI, which connects on the first request.
+    ///
+    /// Must be called within the context of a Tokio runtime.
+    pub fn new(uri: &str, api_key: Option<&str>, timeout: Duration) -> Result<Self, StorageError> {
+        let endpoint = Endpoint::from_shared(uri.to_string())
+            .map_err(|err| StorageError::bad_input(format!("Invalid URI {uri}: {err}")))?
+            .timeout(timeout);
+
+        let api_key = api_key
+            .map(|api_key| {
+                api_key
+                    .parse::<AsciiMetadataValue>()
+                    .map_err(|_| StorageError::bad_input("API key is not a valid header value"))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            channel: endpoint.connect_lazy(),
+            api_key,
+        })
+    }
+
+    pub fn points(&self) -> PointsClient<Channel> {
+        PointsClient::new(self.channel.clone())
+    }
+
+    pub fn collections(&self) -> CollectionsClient<Channel> {
+        CollectionsClient::new(self.channel.clone())
+    }
+
+    pub fn qdrant(&self) -> QdrantClient<Channel> {
+        QdrantClient::new(self.channel.clone())
+    }
+
+    /// Request to the remote cluster, authorized with the API key
+    pub fn request<T>(&self, message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        if let Some(api_key) = &self.api_key {
+            request.metadata_mut().insert("api-key", api_key.clone());
+        }
+        request
+    }
+
+    /// Copy all points of a local collection to a collection of the remote cluster.
+    ///
+    /// Each point is passed to `inspect` before it is copied.
+    pub async fn copy_points(
+        &self,
+        collections: &RwLock<Collections>,
+        source_collection: &str,
+        target_collection: &str,
+        batch_size: usize,
+        mut inspect: impl FnMut(&Record),
+    ) -> Result<(), StorageError> {
+        let mut client = self.points();
+
+        let mut offset = None;
+        loop {
+            let request = ScrollRequestInternal {
+                offset,
+                limit: Some(batch_size),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: WithVector::Bool(true),
+                order_by: None,
+            };
+
+            // Don't hold the lock of the collections between batches
+            let page = {
+                let collections = collections.read().await;
+                let collection = collections.get(source_collection).ok_or_else(|| {
+                    StorageError::not_found(format!("Collection {source_collection} not found"))
+                })?;
+                collection
+                    .scroll_by(request, None, &ShardSelectorInternal::All)
+                    .await?
+            };
+
+            // Points of a custom sharded collection must be placed into the same shard key
+            let mut points_by_shard_key: HashMap<Option<ShardKey>, Vec<PointStruct>> =
+                HashMap::new();
+            for record in page.points {
+                inspect(&record);
+                let point = PointStruct {
+                    id: record.id,
+                    vector: record
+                        .vector
+                        .unwrap_or_else(|| api::rest::VectorStruct::Named(HashMap::new())),
+                    payload: record.payload,
+                };
+                points_by_shard_key
+                    .entry(record.shard_key)
+                    .or_default()
+                    .push(point);
+            }
+
+            for (shard_key, points) in points_by_shard_key {
+                let mut request = internal_upsert_points(
+                    None,
+                    None,
+                    target_collection.to_string(),
+                    PointInsertOperationsInternal::PointsList(points),
+                    true,
+                    None,
+                )?
+                .upsert_points
+                .unwrap_or_default();
+                request.shard_key_selector = shard_key_selector(shard_key.into_iter().collect());
+                client
+                    .upsert(self.request(request))
+                    .await
+                    .map_err(CollectionError::from)?;
+            }
+
+            offset = page.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(super) fn shard_key_selector(shard_keys: Vec<ShardKey>) -> Option<ShardKeySelector> {
+    if shard_keys.is_empty() {
+        return None;
+    }
+
+    Some(ShardKeySelector {
+        shard_keys: shard_keys
+            .into_iter()
+            .map(convert_shard_key_to_grpc)
+            .collect(),
+    })
+}
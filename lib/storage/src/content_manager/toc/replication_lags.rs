@@ -0,0 +1,30 @@
+//! Periodic refresh of the replication lags of all collections.
+//!
+//! Lags are measured by asking every remote replica for the clocks of its WAL, which is too slow
+//! for cluster info, telemetry and metrics. They read the lags cached by the last refresh instead.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::TableOfContent;
+
+/// How often to measure the replication lags of all collections
+const REPLICATION_LAGS_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+impl TableOfContent {
+    /// Measure the replication lags of the remote replicas of all collections periodically
+    pub async fn refresh_replication_lags(self: Arc<Self>) {
+        loop {
+            tokio::time::sleep(REPLICATION_LAGS_REFRESH_INTERVAL).await;
+
+            let collection_names: Vec<_> = self.collections.read().await.keys().cloned().collect();
+
+            for collection_name in collection_names {
+                let collections = self.collections.read().await;
+                if let Some(collection) = collections.get(&collection_name) {
+                    collection.refresh_replication_lags().await;
+                }
+            }
+        }
+    }
+}
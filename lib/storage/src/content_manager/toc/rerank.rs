@@ -0,0 +1,216 @@
+//! Reranking of query results by an external service, e.g. a cross-encoder.
+//!
+//! The reranker receives the query text and the texts of the top results, and responds with a
+//! score for each text. Reranking is best effort: if the reranker fails or doesn't respond in
+//! time, results are returned in their original order.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use api::rest::Rerank;
+use collection::collection::Collection;
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::PointRequestInternal;
+use common::types::ScoreType;
+use reqwest::header::CONTENT_TYPE;
+use segment::json_path::JsonPath;
+use segment::types::{
+    Payload, PayloadContainer as _, PointIdType, ScoredPoint, WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::content_manager::errors::StorageError;
+use crate::types::RerankerConfig;
+
+#[derive(Serialize)]
+struct RerankerRequest<'a> {
+    query: &'a str,
+    documents: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct RerankerResponse {
+    scores: Vec<ScoreType>,
+}
+
+pub(super) struct Reranker {
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl Reranker {
+    pub fn new(config: &RerankerConfig) -> Result<Self, StorageError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            api_key: config.api_key.clone(),
+        })
+    }
+
+    /// Reorders the top points by the scores of the reranker, which replace their scores.
+    ///
+    /// Texts are retrieved separately, as they may not be part of the requested payload.
+    pub async fn rerank(
+        &self,
+        collection: &Collection,
+        points: Vec<ScoredPoint>,
+        rerank: &Rerank,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        let top = rerank.top.unwrap_or(points.len()).min(points.len());
+        if top == 0 {
+            return Ok(points);
+        }
+
+        let request = PointRequestInternal {
+            ids: points[..top].iter().map(|point| point.id).collect(),
+            with_payload: Some(WithPayloadInterface::Fields(rerank.fields.clone())),
+            with_vector: WithVector::Bool(false),
+            as_of_version: None,
+        };
+
+        let payloads: HashMap<PointIdType, Payload> = collection
+            .retrieve(request, read_consistency, shard_selection)
+            .await?
+            .into_iter()
+            .filter_map(|record| Some((record.id, record.payload?)))
+            .collect();
+
+        let texts: Vec<String> = points[..top]
+            .iter()
+            .map(|point| {
+                payloads
+                    .get(&point.id)
+                    .map(|payload| document_text(payload, &rerank.fields))
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        match self.score(&rerank.query, &texts).await {
+            Ok(scores) => Ok(rerank_top(points, scores)),
+            Err(err) => {
+                log::warn!("Failed to rerank query results, keeping the original order: {err}");
+                Ok(points)
+            }
+        }
+    }
+
+    /// Scores of the texts against the query, in the order of the texts
+    async fn score(&self, query: &str, texts: &[String]) -> Result<Vec<ScoreType>, StorageError> {
+        let body = serde_json::to_vec(&RerankerRequest {
+            query,
+            documents: texts,
+        })?;
+
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await?.error_for_status()?.bytes().await?;
+        let RerankerResponse { scores } = serde_json::from_slice(&response)?;
+
+        if scores.len() != texts.len() {
+            return Err(StorageError::service_error(format!(
+                "Reranker returned {} scores for {} texts",
+                scores.len(),
+                texts.len(),
+            )));
+        }
+
+        Ok(scores)
+    }
+}
+
+/// Text of the given payload fields, joined with new lines. Values of arrays are joined as well.
+fn document_text(payload: &Payload, fields: &[JsonPath]) -> String {
+    fields
+        .iter()
+        .flat_map(|field| payload.get_value(field))
+        .flat_map(|value| match value {
+            Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        })
+        .filter_map(|value| value.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Gives the first points the given scores and sorts them by it, the other points keep their
+/// order after them
+fn rerank_top(mut points: Vec<ScoredPoint>, scores: Vec<ScoreType>) -> Vec<ScoredPoint> {
+    let top = scores.len();
+    for (point, score) in points.iter_mut().zip(scores) {
+        point.score = score;
+    }
+    points[..top].sort_by(|a, b| b.score.total_cmp(&a.score));
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn path(path: &str) -> JsonPath {
+        path.parse().unwrap()
+    }
+
+    fn point(id: u64, score: ScoreType) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            prefetch_matches: None,
+            routing: None,
+            raw_score: None,
+        }
+    }
+
+    #[test]
+    fn test_document_text() {
+        let payload = Payload::from(json!({
+            "title": "Qdrant",
+            "text": ["Vector", "database"],
+            "year": 2021,
+        }));
+
+        assert_eq!(
+            document_text(&payload, &[path("title"), path("text"), path("year")]),
+            "Qdrant\nVector\ndatabase",
+        );
+        assert_eq!(document_text(&payload, &[path("missing")]), "");
+    }
+
+    #[test]
+    fn test_rerank_top() {
+        let points = vec![point(1, 0.9), point(2, 0.8), point(3, 0.7), point(4, 0.6)];
+
+        let reranked = rerank_top(points, vec![0.1, 0.5, 0.3]);
+
+        let ids: Vec<_> = reranked.iter().map(|point| point.id).collect();
+        assert_eq!(
+            ids,
+            vec![2u64.into(), 3u64.into(), 1u64.into(), 4u64.into()]
+        );
+        let scores: Vec<_> = reranked.iter().map(|point| point.score).collect();
+        assert_eq!(scores, vec![0.5, 0.3, 0.1, 0.6]);
+    }
+}
@@ -0,0 +1,248 @@
+//! Server-side rewriting of read requests to a collection.
+//!
+//! Operators configure rules per collection: a filter combined with the filter of each request,
+//! a cap on the number of results and renamed vectors. This allows rolling out schema changes
+//! without coordinating every client. Rules apply to the requested collection only, not to
+//! collections used for lookups.
+
+use std::mem::take;
+
+use api::rest::NamedVectorStruct as RestNamedVectorStruct;
+use collection::grouping::group_by::{GroupRequest, SourceRequest};
+use collection::operations::query_enum::QueryEnum;
+use collection::operations::types::{
+    CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal, RecommendRequestInternal,
+    ScrollRequestInternal, UsingVector,
+};
+use collection::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest,
+};
+use segment::data_types::vectors::NamedVectorStruct;
+use segment::types::Filter;
+
+use super::TableOfContent;
+use crate::types::QueryRewriteRules;
+
+pub(super) trait RewritableRequest {
+    fn rewrite(&mut self, rules: &QueryRewriteRules);
+}
+
+impl QueryRewriteRules {
+    fn apply_filter(&self, filter: &mut Option<Filter>) {
+        if let Some(rules_filter) = &self.filter {
+            let f = filter.get_or_insert_with(Default::default);
+            *f = take(f).merge_owned(rules_filter.clone());
+        }
+    }
+
+    fn cap_limit(&self, limit: &mut usize) {
+        if let Some(max_limit) = self.max_limit {
+            *limit = (*limit).min(max_limit);
+        }
+    }
+
+    fn rename_vector(&self, name: &mut String) {
+        if let Some(new_name) = self.vector_names.get(name.as_str()) {
+            *name = new_name.clone();
+        }
+    }
+
+    fn rename_optional_vector(&self, name: &mut Option<String>) {
+        if let Some(name) = name {
+            self.rename_vector(name);
+        }
+    }
+
+    fn rename_using(&self, using: &mut Option<UsingVector>) {
+        if let Some(UsingVector::Name(name)) = using {
+            self.rename_vector(name);
+        }
+    }
+
+    fn rename_named_vector(&self, vector: &mut NamedVectorStruct) {
+        match vector {
+            NamedVectorStruct::Default(_) => {}
+            NamedVectorStruct::Dense(vector) => self.rename_vector(&mut vector.name),
+            NamedVectorStruct::Sparse(vector) => self.rename_vector(&mut vector.name),
+            NamedVectorStruct::MultiDense(vector) => self.rename_vector(&mut vector.name),
+        }
+    }
+
+    fn rename_query(&self, query: &mut QueryEnum) {
+        match query {
+            QueryEnum::Nearest(vector) => self.rename_named_vector(vector),
+            QueryEnum::RecommendBestScore(query) => self.rename_optional_vector(&mut query.using),
+            QueryEnum::Discover(query) => self.rename_optional_vector(&mut query.using),
+            QueryEnum::Context(query) => self.rename_optional_vector(&mut query.using),
+        }
+    }
+
+    fn rewrite_prefetch(&self, prefetch: &mut CollectionPrefetch) {
+        self.apply_filter(&mut prefetch.filter);
+        self.cap_limit(&mut prefetch.limit);
+        self.rename_vector(&mut prefetch.using);
+
+        for prefetch in prefetch.prefetch.iter_mut() {
+            self.rewrite_prefetch(prefetch);
+        }
+    }
+
+    /// Rewrite the source of a group request, whose limit is managed by the grouping itself
+    fn rewrite_group_source(&self, source: &mut SourceRequest) {
+        match source {
+            SourceRequest::Search(request) => {
+                self.apply_filter(&mut request.filter);
+                match &mut request.vector {
+                    RestNamedVectorStruct::Default(_) => {}
+                    RestNamedVectorStruct::Dense(vector) => self.rename_vector(&mut vector.name),
+                    RestNamedVectorStruct::Sparse(vector) => self.rename_vector(&mut vector.name),
+                }
+            }
+            SourceRequest::Recommend(request) => {
+                self.apply_filter(&mut request.filter);
+                self.rename_using(&mut request.using);
+            }
+        }
+    }
+}
+
+impl RewritableRequest for RecommendRequestInternal {
+    fn rewrite(&mut self, rules: &QueryRewriteRules) {
+        rules.apply_filter(&mut self.filter);
+        rules.cap_limit(&mut self.limit);
+        rules.rename_using(&mut self.using);
+    }
+}
+
+impl RewritableRequest for CoreSearchRequest {
+    fn rewrite(&mut self, rules: &QueryRewriteRules) {
+        rules.apply_filter(&mut self.filter);
+        rules.cap_limit(&mut self.limit);
+        rules.rename_query(&mut self.query);
+    }
+}
+
+impl RewritableRequest for CountRequestInternal {
+    fn rewrite(&mut self, rules: &QueryRewriteRules) {
+        rules.apply_filter(&mut self.filter);
+    }
+}
+
+impl RewritableRequest for GroupRequest {
+    fn rewrite(&mut self, rules: &QueryRewriteRules) {
+        rules.rewrite_group_source(&mut self.source);
+        rules.cap_limit(&mut self.limit);
+    }
+}
+
+impl RewritableRequest for DiscoverRequestInternal {
+    fn rewrite(&mut self, rules: &QueryRewriteRules) {
+        rules.apply_filter(&mut self.filter);
+        rules.cap_limit(&mut self.limit);
+        rules.rename_using(&mut self.using);
+    }
+}
+
+impl RewritableRequest for ScrollRequestInternal {
+    fn rewrite(&mut self, rules: &QueryRewriteRules) {
+        rules.apply_filter(&mut self.filter);
+        if let Some(limit) = &mut self.limit {
+            rules.cap_limit(limit);
+        }
+    }
+}
+
+impl RewritableRequest for CollectionQueryRequest {
+    fn rewrite(&mut self, rules: &QueryRewriteRules) {
+        rules.apply_filter(&mut self.filter);
+        rules.cap_limit(&mut self.limit);
+        rules.rename_vector(&mut self.using);
+
+        for prefetch in self.prefetch.iter_mut() {
+            rules.rewrite_prefetch(prefetch);
+        }
+    }
+}
+
+impl TableOfContent {
+    /// Apply the rewrite rules configured for the collection to the request, if any
+    pub(super) fn rewrite_request(
+        &self,
+        collection_name: &str,
+        request: &mut impl RewritableRequest,
+    ) {
+        if let Some(rules) = self.storage_config.query_rewrites.get(collection_name) {
+            request.rewrite(rules);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use segment::types::{Condition, FieldCondition, Match, ValueVariants};
+
+    use super::*;
+
+    fn match_filter(key: &str, value: ValueVariants) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            key.parse().unwrap(),
+            Match::new_value(value),
+        )))
+    }
+
+    fn rules() -> QueryRewriteRules {
+        QueryRewriteRules {
+            filter: Some(match_filter("archived", ValueVariants::Bool(false))),
+            max_limit: Some(200),
+            vector_names: HashMap::from([("image".to_string(), "image_v2".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_rewrite_recommend_request() {
+        let mut request = RecommendRequestInternal {
+            using: Some(UsingVector::Name("image".to_string())),
+            limit: 1000,
+            ..Default::default()
+        };
+
+        request.rewrite(&rules());
+
+        assert_eq!(
+            request.using,
+            Some(UsingVector::Name("image_v2".to_string()))
+        );
+        assert_eq!(request.limit, 200);
+        assert_eq!(request.filter, rules().filter);
+
+        // Other vectors and smaller limits are kept
+        let mut request = RecommendRequestInternal {
+            using: Some(UsingVector::Name("text".to_string())),
+            limit: 10,
+            ..Default::default()
+        };
+
+        request.rewrite(&rules());
+
+        assert_eq!(request.using, Some(UsingVector::Name("text".to_string())));
+        assert_eq!(request.limit, 10);
+    }
+
+    #[test]
+    fn test_rewrite_keeps_request_filter() {
+        let request_filter = match_filter("color", ValueVariants::Keyword("red".to_string()));
+        let mut request = CountRequestInternal {
+            filter: Some(request_filter.clone()),
+            exact: true,
+        };
+
+        request.rewrite(&rules());
+
+        assert_eq!(
+            request.filter,
+            Some(request_filter.merge_owned(rules().filter.unwrap())),
+        );
+    }
+}
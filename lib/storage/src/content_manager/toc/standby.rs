@@ -0,0 +1,1054 @@
+//! Warm standby collections, following a collection of a primary cluster.
+//!
+//! Standbys are part of the consensus state, so all peers reject writes of users to a standby
+//! until it is promoted during a failover. Promotion stops following the primary for good.
+//!
+//! A standby is synchronized by the consensus leader alone. If the standby collection doesn't
+//! exist yet, or its vectors or shards differ from the primary, a snapshot of the primary
+//! collection is created through its REST API and restored. Otherwise the points of both
+//! collections are compared page by page, and only the points which differ are written. Payload
+//! indexes are created and dropped to match the primary either way.
+//!
+//! Comparing scrolls both collections in full, so it is only a fallback. After a snapshot restore
+//! or comparison, the standby continues from the change feed of the primary
+//! (`POST /collections/{name}/points/changes`), which only returns points changed since the last
+//! synchronization. Shards without changes cost nothing then. The feed is read from a single peer
+//! of the primary, so it is only used if the peer answering the request hosts all shards, and the
+//! same peer keeps answering. Points are compared again whenever the feed can't be used, e.g. if
+//! the primary truncated its WAL since the last synchronization.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use collection::collection::Collection;
+use collection::config::CollectionParams;
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::snapshot_ops::{
+    SnapshotDescription, SnapshotPriority, SnapshotRecover,
+};
+use collection::operations::types::{ChangesCursor, ScrollRequestInternal};
+use collection::operations::CollectionUpdateOperations;
+use collection::shards::shard::PeerId;
+use io::file_operations::{atomic_save_json, read_json};
+use schemars::JsonSchema;
+use segment::types::{
+    Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadSchemaParams,
+    PayloadSchemaType, PointIdType, ShardKey, WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::TableOfContent;
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreatePayloadIndex, DropPayloadIndex, StandbyConfig, StandbyUpdate,
+    UpdateStandby,
+};
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::snapshots::recover::do_recover_from_snapshot;
+use crate::dispatcher::Dispatcher;
+use crate::rbac::{Access, AccessRequirements, CollectionPass};
+
+/// File in the storage directory, with the standby collections of the consensus state
+pub const STANDBYS_FILE: &str = "standbys.json";
+
+const STANDBY_ACCESS: Access = Access::full("Warm standby");
+
+/// How often to check, if standbys are added, promoted or reconfigured
+const FOLLOWERS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+const DEFAULT_SYNC_INTERVAL_SEC: u64 = 60;
+
+/// Number of points of each collection, compared at once, and changed points read per shard
+const SYNC_PAGE_SIZE: usize = 256;
+
+/// Standby collections by collection name, part of the consensus state
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Standbys(HashMap<String, StandbyEntry>);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandbyEntry {
+    pub config: StandbyConfig,
+    /// If the standby was promoted, and accepts writes instead of following the primary
+    pub promoted: bool,
+}
+
+impl Standbys {
+    pub fn load(storage_path: &Path) -> Result<Self, StorageError> {
+        let path = Self::path(storage_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(read_json(&path)?)
+    }
+
+    fn save(&self, storage_path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(&Self::path(storage_path), self)?)
+    }
+
+    fn path(storage_path: &Path) -> PathBuf {
+        storage_path.join(STANDBYS_FILE)
+    }
+
+    pub fn get(&self, collection_name: &str) -> Option<&StandbyEntry> {
+        self.0.get(collection_name)
+    }
+}
+
+/// Current state of a standby collection
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StandbyStatus {
+    /// REST URL of the primary cluster
+    pub url: String,
+    /// Name of the collection on the primary cluster
+    pub collection: String,
+    /// If the standby was promoted, and accepts writes instead of following the primary
+    pub promoted: bool,
+    /// Peer which follows the primary and reports the following fields, the consensus leader.
+    /// Null if this peer doesn't know the leader.
+    pub follower_peer_id: Option<PeerId>,
+    /// If the standby is being synchronized with the primary right now
+    pub sync_in_progress: bool,
+    /// Number of snapshots of the primary collection restored so far
+    pub restored_snapshots: usize,
+    /// Name of the last restored snapshot
+    pub last_snapshot: Option<String>,
+    /// Number of synchronizations, which compared the points of both collections instead of
+    /// restoring a snapshot
+    pub incremental_syncs: usize,
+    /// Number of synchronizations, which applied the change feed of the primary
+    pub change_feed_syncs: usize,
+    /// Number of points written by incremental synchronizations so far
+    pub updated_points: usize,
+    /// Number of points deleted by incremental synchronizations so far
+    pub deleted_points: usize,
+    /// Time when the standby was last synchronized with the primary
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// Last error of following the primary collection
+    pub last_error: Option<String>,
+}
+
+#[derive(Default)]
+struct StandbyState {
+    sync_in_progress: bool,
+    restored_snapshots: usize,
+    last_snapshot: Option<String>,
+    incremental_syncs: usize,
+    change_feed_syncs: usize,
+    updated_points: usize,
+    deleted_points: usize,
+    last_synced_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    /// Where to continue reading the change feed of the primary, if it can be used
+    changes_position: Option<ChangesPosition>,
+}
+
+/// Position in the change feed of the primary collection
+#[derive(Clone)]
+struct ChangesPosition {
+    /// Peer of the primary cluster, which read the changes. Cursors are only valid for it.
+    peer_id: PeerId,
+    cursor: ChangesCursor,
+}
+
+/// Outcome of a single synchronization with the primary
+enum Synced {
+    Snapshot(String),
+    Points { updated: usize, deleted: usize },
+    Changes { updated: usize, deleted: usize },
+}
+
+/// Response of the REST API of the primary cluster
+#[derive(Deserialize)]
+struct PrimaryResponse<T> {
+    result: T,
+}
+
+#[derive(Deserialize)]
+struct PrimaryCollectionInfo {
+    config: PrimaryCollectionConfig,
+    payload_schema: HashMap<PayloadKeyType, PrimaryPayloadIndex>,
+}
+
+#[derive(Deserialize)]
+struct PrimaryPayloadIndex {
+    data_type: PayloadSchemaType,
+    #[serde(default)]
+    params: Option<PayloadSchemaParams>,
+}
+
+#[derive(Deserialize)]
+struct PrimaryCollectionConfig {
+    params: CollectionParams,
+}
+
+#[derive(Deserialize)]
+struct PrimaryClusterInfo {
+    shard_count: usize,
+}
+
+#[derive(Deserialize)]
+struct PrimaryChanges {
+    changes: Vec<PrimaryChange>,
+    next_cursor: ChangesCursor,
+    peer_id: PeerId,
+}
+
+#[derive(Deserialize)]
+struct PrimaryChange {
+    id: PointIdType,
+    #[serde(default)]
+    shard_key: Option<ShardKey>,
+    /// Current state of the point, none if it was deleted
+    #[serde(default)]
+    point: Option<PrimaryPoint>,
+}
+
+#[derive(Deserialize)]
+struct PrimaryPage {
+    points: Vec<PrimaryPoint>,
+    next_page_offset: Option<PointIdType>,
+}
+
+#[derive(Deserialize)]
+struct PrimaryPoint {
+    id: PointIdType,
+    #[serde(default)]
+    payload: Option<Payload>,
+    #[serde(default)]
+    vector: Option<api::rest::VectorStruct>,
+    #[serde(default)]
+    shard_key: Option<ShardKey>,
+}
+
+/// Follower of the primary collection of a standby, run by the consensus leader
+pub struct CollectionStandby {
+    collection_name: String,
+    primary_collection: String,
+    config: StandbyConfig,
+    /// Held while the standby is synchronized, so a promotion on this peer waits for it
+    sync_lock: tokio::sync::Mutex<()>,
+    state: parking_lot::Mutex<StandbyState>,
+}
+
+impl CollectionStandby {
+    pub fn new(collection_name: &str, config: &StandbyConfig) -> Result<Self, StorageError> {
+        let standby = Self {
+            collection_name: collection_name.to_string(),
+            primary_collection: config
+                .collection
+                .clone()
+                .unwrap_or_else(|| collection_name.to_string()),
+            config: config.clone(),
+            sync_lock: Default::default(),
+            state: Default::default(),
+        };
+
+        // Validate the URL upfront
+        standby.url(&[])?;
+
+        Ok(standby)
+    }
+
+    /// URL of the given path within the primary collection
+    fn url(&self, path: &[&str]) -> Result<Url, StorageError> {
+        let invalid_url =
+            || StorageError::bad_input(format!("Invalid primary URL {}", self.config.url));
+
+        let mut url = Url::parse(&self.config.url).map_err(|_| invalid_url())?;
+        url.path_segments_mut()
+            .map_err(|_| invalid_url())?
+            .pop_if_empty()
+            .extend(["collections", self.primary_collection.as_str()])
+            .extend(path);
+        Ok(url)
+    }
+
+    fn status(&self, entry: &StandbyEntry, follower_peer_id: Option<PeerId>) -> StandbyStatus {
+        let state = self.state.lock();
+        StandbyStatus {
+            url: entry.config.url.clone(),
+            collection: self.primary_collection.clone(),
+            promoted: entry.promoted,
+            follower_peer_id,
+            sync_in_progress: state.sync_in_progress,
+            restored_snapshots: state.restored_snapshots,
+            last_snapshot: state.last_snapshot.clone(),
+            incremental_syncs: state.incremental_syncs,
+            change_feed_syncs: state.change_feed_syncs,
+            updated_points: state.updated_points,
+            deleted_points: state.deleted_points,
+            last_synced_at: state.last_synced_at,
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    fn collection_pass(&self) -> Result<CollectionPass<'_>, StorageError> {
+        Ok(STANDBY_ACCESS
+            .check_global_access(AccessRequirements::new().manage())?
+            .issue_pass(&self.collection_name))
+    }
+
+    /// If this follower is still the one of the standby on this peer
+    fn is_following(self: &Arc<Self>, toc: &TableOfContent) -> bool {
+        toc.standby_followers
+            .lock()
+            .get(&self.collection_name)
+            .is_some_and(|follower| Arc::ptr_eq(follower, self))
+    }
+
+    /// Synchronize the standby with the primary periodically, until it is no longer followed by
+    /// this peer.
+    ///
+    /// The client must send the API key of the primary cluster, if it has one.
+    async fn follow(self: Arc<Self>, dispatcher: Dispatcher, client: reqwest::Client) {
+        let toc = dispatcher.toc(&STANDBY_ACCESS).clone();
+        let interval = Duration::from_secs(
+            self.config
+                .interval_sec
+                .unwrap_or(DEFAULT_SYNC_INTERVAL_SEC)
+                .max(1),
+        );
+
+        while self.is_following(&toc) {
+            {
+                let _sync = self.sync_lock.lock().await;
+                self.state.lock().sync_in_progress = true;
+                let result = self.sync(&dispatcher, &client).await;
+
+                let mut state = self.state.lock();
+                state.sync_in_progress = false;
+                match result {
+                    Ok(synced) => {
+                        match synced {
+                            Synced::Snapshot(snapshot_name) => {
+                                state.restored_snapshots += 1;
+                                state.last_snapshot = Some(snapshot_name);
+                            }
+                            Synced::Points { updated, deleted } => {
+                                state.incremental_syncs += 1;
+                                state.updated_points += updated;
+                                state.deleted_points += deleted;
+                            }
+                            Synced::Changes { updated, deleted } => {
+                                state.change_feed_syncs += 1;
+                                state.updated_points += updated;
+                                state.deleted_points += deleted;
+                            }
+                        }
+                        state.last_synced_at = Some(Utc::now());
+                        state.last_error = None;
+                    }
+                    Err(err) => {
+                        log::warn!(
+                            "Failed to synchronize standby collection {} with the primary: {err}",
+                            self.collection_name,
+                        );
+                        state.last_error = Some(err.to_string());
+                    }
+                }
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+
+        log::info!(
+            "Stopped following the primary of standby collection {}",
+            self.collection_name,
+        );
+    }
+
+    /// Restore a snapshot of the primary, if the standby is not compatible with it. Otherwise
+    /// apply the change feed of the primary, or write the points which differ if it can't be used.
+    async fn sync(
+        self: &Arc<Self>,
+        dispatcher: &Dispatcher,
+        client: &reqwest::Client,
+    ) -> Result<Synced, StorageError> {
+        let toc = dispatcher.toc(&STANDBY_ACCESS);
+
+        let primary = self.primary_info(client).await?;
+        let is_compatible = match toc.get_collection(&self.collection_pass()?).await {
+            Ok(collection) => Self::is_compatible(&collection, &primary).await,
+            Err(_) => false,
+        };
+
+        let position = self.state.lock().changes_position.take();
+        let changes = match position {
+            Some(position) if is_compatible => {
+                self.sync_changes(toc, client, position).await.transpose()
+            }
+            _ => None,
+        };
+
+        let synced = match changes {
+            Some(Ok(synced)) => synced,
+            changes => {
+                if let Some(Err(err)) = changes {
+                    log::warn!(
+                        "Failed to apply the change feed of the primary to standby collection {}, \
+                         comparing all points instead: {err}",
+                        self.collection_name,
+                    );
+                }
+                self.state.lock().changes_position = None;
+
+                // Changes made during the full synchronization are applied again by the next one
+                let position = self.primary_changes_position(client).await;
+
+                let synced = if is_compatible {
+                    let (updated, deleted) = self.sync_points(toc, client).await?;
+                    Synced::Points { updated, deleted }
+                } else {
+                    let snapshot_name = self.restore_snapshot(dispatcher, client).await?;
+                    Synced::Snapshot(snapshot_name)
+                };
+
+                match position {
+                    Ok(position) => self.state.lock().changes_position = position,
+                    Err(err) => log::debug!(
+                        "Can't read the change feed of the primary of standby collection {}: {err}",
+                        self.collection_name,
+                    ),
+                }
+
+                synced
+            }
+        };
+
+        // Indexes of a restored collection are not part of the restored shards
+        self.sync_payload_indexes(dispatcher, &primary).await?;
+
+        Ok(synced)
+    }
+
+    /// If the points of the primary can be written into the standby collection as they are
+    async fn is_compatible(collection: &Collection, primary: &PrimaryCollectionInfo) -> bool {
+        let state = collection.state().await;
+        let local = &state.config.params;
+        let remote = &primary.config.params;
+
+        local.vectors == remote.vectors
+            && local.sparse_vectors == remote.sparse_vectors
+            && local.shard_number == remote.shard_number
+            && local.sharding_method == remote.sharding_method
+    }
+
+    /// Create and drop payload indexes of the standby collection, to match the primary
+    async fn sync_payload_indexes(
+        &self,
+        dispatcher: &Dispatcher,
+        primary: &PrimaryCollectionInfo,
+    ) -> Result<(), StorageError> {
+        let local_schema = dispatcher
+            .toc(&STANDBY_ACCESS)
+            .get_collection(&self.collection_pass()?)
+            .await?
+            .state()
+            .await
+            .payload_index_schema
+            .schema;
+
+        let mut operations = Vec::new();
+
+        for (field_name, index) in &primary.payload_schema {
+            let index = PayloadIndexInfo {
+                data_type: index.data_type,
+                params: index.params.clone(),
+                points: 0,
+            };
+            let is_same = local_schema
+                .get(field_name)
+                .is_some_and(|local| PayloadIndexInfo::new(local.clone(), 0) == index);
+            if !is_same {
+                operations.push(CollectionMetaOperations::CreatePayloadIndex(
+                    CreatePayloadIndex {
+                        collection_name: self.collection_name.clone(),
+                        field_name: field_name.clone(),
+                        field_schema: PayloadFieldSchema::try_from(index)
+                            .map_err(StorageError::service_error)?,
+                    },
+                ));
+            }
+        }
+
+        for field_name in local_schema.keys() {
+            if !primary.payload_schema.contains_key(field_name) {
+                operations.push(CollectionMetaOperations::DropPayloadIndex(
+                    DropPayloadIndex {
+                        collection_name: self.collection_name.clone(),
+                        field_name: field_name.clone(),
+                    },
+                ));
+            }
+        }
+
+        for operation in operations {
+            dispatcher
+                .submit_collection_meta_op(operation, STANDBY_ACCESS, None)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Current position in the change feed of the primary, if the feed covers all of its shards
+    async fn primary_changes_position(
+        &self,
+        client: &reqwest::Client,
+    ) -> Result<Option<ChangesPosition>, StorageError> {
+        let changes = self.primary_changes(client, None).await?;
+
+        let response = client.get(self.url(&["cluster"])?).send().await?;
+        if !response.status().is_success() {
+            return Err(StorageError::service_error(format!(
+                "Failed to get cluster info of collection {} of the primary cluster: status - {}",
+                self.primary_collection,
+                response.status(),
+            )));
+        }
+        let cluster = response
+            .json::<PrimaryResponse<PrimaryClusterInfo>>()
+            .await?
+            .result;
+
+        // The feed only contains shards of the peer which answered
+        if changes.next_cursor.len() != cluster.shard_count {
+            return Ok(None);
+        }
+
+        Ok(Some(ChangesPosition {
+            peer_id: changes.peer_id,
+            cursor: changes.next_cursor,
+        }))
+    }
+
+    /// Apply the changes of the primary since the given position
+    ///
+    /// Returns none, if the changes were read by another peer of the primary, so the position
+    /// is not valid anymore.
+    async fn sync_changes(
+        self: &Arc<Self>,
+        toc: &TableOfContent,
+        client: &reqwest::Client,
+        mut position: ChangesPosition,
+    ) -> Result<Option<Synced>, StorageError> {
+        let (mut updated, mut deleted) = (0, 0);
+
+        // Stop early, if promoted or no longer the leader
+        while self.is_following(toc) {
+            let page = self.primary_changes(client, Some(&position.cursor)).await?;
+            if page.peer_id != position.peer_id
+                || page.next_cursor.keys().ne(position.cursor.keys())
+            {
+                return Ok(None);
+            }
+
+            let is_last_page = page.changes.is_empty();
+
+            let mut upserts: HashMap<Option<ShardKey>, Vec<PointStruct>> = HashMap::new();
+            let mut deletes: HashMap<Option<ShardKey>, Vec<PointIdType>> = HashMap::new();
+
+            for change in page.changes {
+                match change.point {
+                    Some(point) => upserts
+                        .entry(point.shard_key)
+                        .or_default()
+                        .push(point_struct(point.id, point.vector, point.payload)),
+                    None => deletes.entry(change.shard_key).or_default().push(change.id),
+                }
+            }
+
+            let (page_updated, page_deleted) = self.write_points(toc, upserts, deletes).await?;
+            updated += page_updated;
+            deleted += page_deleted;
+
+            position.cursor = page.next_cursor;
+            self.state.lock().changes_position = Some(position.clone());
+
+            if is_last_page {
+                break;
+            }
+        }
+
+        Ok(Some(Synced::Changes { updated, deleted }))
+    }
+
+    /// Changes of the primary collection since the given cursor, or only the current cursor
+    async fn primary_changes(
+        &self,
+        client: &reqwest::Client,
+        cursor: Option<&ChangesCursor>,
+    ) -> Result<PrimaryChanges, StorageError> {
+        let request = serde_json::json!({
+            "cursor": cursor,
+            "limit": SYNC_PAGE_SIZE,
+            "with_payload": true,
+            "with_vector": true,
+        });
+        let response = client
+            .post(self.url(&["points", "changes"])?)
+            .json(&request)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(StorageError::service_error(format!(
+                "Primary cluster failed to read changes of collection {}: status - {}",
+                self.primary_collection,
+                response.status(),
+            )));
+        }
+        Ok(response
+            .json::<PrimaryResponse<PrimaryChanges>>()
+            .await?
+            .result)
+    }
+
+    /// Compare the points of both collections page by page, and write the points which differ
+    ///
+    /// Returns the number of written and deleted points.
+    async fn sync_points(
+        self: &Arc<Self>,
+        toc: &TableOfContent,
+        client: &reqwest::Client,
+    ) -> Result<(usize, usize), StorageError> {
+        let (mut updated, mut deleted) = (0, 0);
+
+        let mut offset = None;
+        loop {
+            // Stop early, if promoted or no longer the leader
+            if !self.is_following(toc) {
+                break;
+            }
+
+            let page = self.primary_page(client, offset).await?;
+            let mut local_points = self
+                .local_points(toc, offset, page.next_page_offset)
+                .await?;
+
+            let mut upserts: HashMap<Option<ShardKey>, Vec<PointStruct>> = HashMap::new();
+            let mut deletes: HashMap<Option<ShardKey>, Vec<PointIdType>> = HashMap::new();
+
+            for point in page.points {
+                match local_points.remove(&point.id) {
+                    Some(local)
+                        if local.payload == point.payload
+                            && local.vector == point.vector
+                            && local.shard_key == point.shard_key =>
+                    {
+                        continue;
+                    }
+                    // Moved to another shard key, remove the old copy
+                    Some(local) if local.shard_key != point.shard_key => {
+                        deletes.entry(local.shard_key).or_default().push(local.id);
+                    }
+                    _ => {}
+                }
+
+                upserts
+                    .entry(point.shard_key)
+                    .or_default()
+                    .push(point_struct(point.id, point.vector, point.payload));
+            }
+
+            // Points, which don't exist on the primary anymore
+            for (id, local) in local_points {
+                deletes.entry(local.shard_key).or_default().push(id);
+            }
+
+            let (page_updated, page_deleted) = self.write_points(toc, upserts, deletes).await?;
+            updated += page_updated;
+            deleted += page_deleted;
+
+            offset = page.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+
+        Ok((updated, deleted))
+    }
+
+    /// Delete and upsert points of the standby collection, grouped by shard key
+    ///
+    /// Returns the number of written and deleted points.
+    async fn write_points(
+        &self,
+        toc: &TableOfContent,
+        upserts: HashMap<Option<ShardKey>, Vec<PointStruct>>,
+        deletes: HashMap<Option<ShardKey>, Vec<PointIdType>>,
+    ) -> Result<(usize, usize), StorageError> {
+        let (mut updated, mut deleted) = (0, 0);
+
+        let collection = toc.get_collection(&self.collection_pass()?).await?;
+        for (shard_key, ids) in deletes {
+            deleted += ids.len();
+            let operation =
+                CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints { ids });
+            collection
+                .update_from_client(operation, true, WriteOrdering::default(), shard_key)
+                .await?;
+        }
+        for (shard_key, points) in upserts {
+            updated += points.len();
+            let operation = CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
+            );
+            collection
+                .update_from_client(operation, true, WriteOrdering::default(), shard_key)
+                .await?;
+        }
+
+        Ok((updated, deleted))
+    }
+
+    /// Page of points of the primary collection, starting at the given offset
+    async fn primary_page(
+        &self,
+        client: &reqwest::Client,
+        offset: Option<PointIdType>,
+    ) -> Result<PrimaryPage, StorageError> {
+        let request = serde_json::json!({
+            "offset": offset,
+            "limit": SYNC_PAGE_SIZE,
+            "with_payload": true,
+            "with_vector": true,
+        });
+        let response = client
+            .post(self.url(&["points", "scroll"])?)
+            .json(&request)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(StorageError::service_error(format!(
+                "Primary cluster failed to scroll collection {}: status - {}",
+                self.primary_collection,
+                response.status(),
+            )));
+        }
+        Ok(response
+            .json::<PrimaryResponse<PrimaryPage>>()
+            .await?
+            .result)
+    }
+
+    /// Points of the standby collection with IDs in the range from `offset` up to `end`
+    async fn local_points(
+        &self,
+        toc: &TableOfContent,
+        mut offset: Option<PointIdType>,
+        end: Option<PointIdType>,
+    ) -> Result<HashMap<PointIdType, api::rest::Record>, StorageError> {
+        let mut points = HashMap::new();
+
+        loop {
+            let request = ScrollRequestInternal {
+                offset,
+                limit: Some(SYNC_PAGE_SIZE),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: WithVector::Bool(true),
+                order_by: None,
+            };
+
+            // Don't hold the lock of the collections between pages
+            let page = toc
+                .get_collection(&self.collection_pass()?)
+                .await?
+                .scroll_by(request, None, &ShardSelectorInternal::All)
+                .await?;
+
+            for record in page.points {
+                if end.is_some_and(|end| record.id >= end) {
+                    return Ok(points);
+                }
+                points.insert(record.id, record);
+            }
+
+            offset = page.next_page_offset;
+            if offset.is_none() {
+                return Ok(points);
+            }
+        }
+    }
+
+    async fn primary_info(
+        &self,
+        client: &reqwest::Client,
+    ) -> Result<PrimaryCollectionInfo, StorageError> {
+        let response = client.get(self.url(&[])?).send().await?;
+        if !response.status().is_success() {
+            return Err(StorageError::service_error(format!(
+                "Failed to get collection {} of the primary cluster: status - {}",
+                self.primary_collection,
+                response.status(),
+            )));
+        }
+        Ok(response
+            .json::<PrimaryResponse<PrimaryCollectionInfo>>()
+            .await?
+            .result)
+    }
+
+    /// Create a snapshot of the primary collection, and restore the standby collection from it
+    async fn restore_snapshot(
+        &self,
+        dispatcher: &Dispatcher,
+        client: &reqwest::Client,
+    ) -> Result<String, StorageError> {
+        let response = client
+            .post(self.url(&["snapshots"])?)
+            .query(&[("wait", "true")])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(StorageError::service_error(format!(
+                "Primary cluster failed to create a snapshot of collection {}: status - {}",
+                self.primary_collection,
+                response.status(),
+            )));
+        }
+        let snapshot = response
+            .json::<PrimaryResponse<SnapshotDescription>>()
+            .await?
+            .result;
+        let snapshot_url = self.url(&["snapshots", &snapshot.name])?;
+
+        log::debug!(
+            "Restoring standby collection {} from {snapshot_url}",
+            self.collection_name,
+        );
+
+        let recover = SnapshotRecover {
+            location: snapshot_url.clone(),
+            // The primary is the source of truth
+            priority: Some(SnapshotPriority::Snapshot),
+            checksum: snapshot.checksum,
+            api_key: None,
+        };
+        let recovered = do_recover_from_snapshot(
+            dispatcher,
+            &self.collection_name,
+            recover,
+            STANDBY_ACCESS,
+            client.clone(),
+        )?
+        .await;
+
+        // Don't pile up snapshots on the primary, even if the restore failed
+        let deleted = client
+            .delete(snapshot_url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(err) = deleted {
+            log::warn!(
+                "Failed to delete snapshot {} of collection {} on the primary: {err}",
+                snapshot.name,
+                self.primary_collection,
+            );
+        }
+
+        recovered??;
+        Ok(snapshot.name)
+    }
+}
+
+fn point_struct(
+    id: PointIdType,
+    vector: Option<api::rest::VectorStruct>,
+    payload: Option<Payload>,
+) -> PointStruct {
+    PointStruct {
+        id,
+        vector: vector.unwrap_or_else(|| api::rest::VectorStruct::Named(HashMap::new())),
+        payload,
+    }
+}
+
+/// Follow the primaries of standby collections, while this peer is the consensus leader.
+///
+/// `client` creates an HTTP client, which sends the given API key of a primary cluster.
+pub async fn follow_standbys(
+    dispatcher: Dispatcher,
+    client: impl Fn(Option<&str>) -> Result<reqwest::Client, StorageError>,
+) {
+    let toc = dispatcher.toc(&STANDBY_ACCESS).clone();
+    let mut interval = tokio::time::interval(FOLLOWERS_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let is_leader = dispatcher
+            .consensus_state()
+            .map_or(true, |state| state.is_leader());
+        let standbys = toc.standbys.read().clone();
+
+        let mut followers = toc.standby_followers.lock();
+
+        // Followers stop on their own, once they are removed from here
+        followers.retain(|collection_name, follower| {
+            is_leader
+                && standbys
+                    .get(collection_name)
+                    .is_some_and(|entry| !entry.promoted && entry.config == follower.config)
+        });
+
+        if !is_leader {
+            continue;
+        }
+
+        for (collection_name, entry) in &standbys.0 {
+            if entry.promoted || followers.contains_key(collection_name) {
+                continue;
+            }
+
+            let follower = match CollectionStandby::new(collection_name, &entry.config) {
+                Ok(follower) => Arc::new(follower),
+                Err(err) => {
+                    log::error!("Can't follow the primary of standby {collection_name}: {err}");
+                    continue;
+                }
+            };
+
+            match client(entry.config.api_key.as_deref()) {
+                Ok(client) => {
+                    log::info!("Following the primary of standby collection {collection_name}");
+                    tokio::spawn(follower.clone().follow(dispatcher.clone(), client));
+                }
+                Err(err) => {
+                    follower.state.lock().last_error = Some(err.to_string());
+                }
+            }
+
+            // Keep the follower even if its client failed, so the error is reported once
+            followers.insert(collection_name.clone(), follower);
+        }
+    }
+}
+
+impl TableOfContent {
+    pub fn is_promoted_standby(&self, collection_name: &str) -> bool {
+        self.standbys
+            .read()
+            .get(collection_name)
+            .is_some_and(|entry| entry.promoted)
+    }
+
+    /// Reject writes to a standby collection, which was not promoted yet
+    pub(super) fn check_standby_write(&self, collection_name: &str) -> Result<(), StorageError> {
+        let is_following = self
+            .standbys
+            .read()
+            .get(collection_name)
+            .is_some_and(|entry| !entry.promoted);
+        if is_following {
+            return Err(StorageError::Locked {
+                description: format!(
+                    "Collection {collection_name} is a standby of a primary cluster and is \
+                     read-only, promote it to accept writes",
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Apply a change of a standby collection, agreed on by the consensus
+    pub(super) fn update_standby(&self, operation: UpdateStandby) -> Result<(), StorageError> {
+        let UpdateStandby {
+            collection_name,
+            update,
+        } = operation;
+
+        let mut standbys = self.standbys.write();
+        let mut updated = standbys.clone();
+        match update {
+            StandbyUpdate::Follow(config) => {
+                log::info!("Collection {collection_name} is a standby of a primary cluster");
+                updated.0.insert(
+                    collection_name,
+                    StandbyEntry {
+                        config,
+                        promoted: false,
+                    },
+                );
+            }
+            StandbyUpdate::Promote => {
+                let entry = updated.0.get_mut(&collection_name).ok_or_else(|| {
+                    StorageError::bad_request(format!(
+                        "Collection {collection_name} is not a standby of a primary cluster",
+                    ))
+                })?;
+                entry.promoted = true;
+                log::info!("Standby collection {collection_name} is promoted, accepting writes");
+            }
+        }
+
+        updated.save(Path::new(self.storage_path()))?;
+        *standbys = updated;
+        Ok(())
+    }
+
+    pub(super) fn standbys_state(&self) -> Standbys {
+        self.standbys.read().clone()
+    }
+
+    pub(super) fn apply_standbys_state(&self, state: Standbys) -> Result<(), StorageError> {
+        let mut standbys = self.standbys.write();
+        if *standbys != state {
+            state.save(Path::new(self.storage_path()))?;
+            *standbys = state;
+        }
+        Ok(())
+    }
+
+    /// Status of the standby collection
+    pub async fn standby_status(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+        follower_peer_id: Option<PeerId>,
+    ) -> Result<StandbyStatus, StorageError> {
+        let collection_name = self.resolve_standby_name(collection_pass).await;
+        let entry = self
+            .standbys
+            .read()
+            .get(&collection_name)
+            .cloned()
+            .ok_or_else(|| {
+                StorageError::bad_request(format!(
+                    "Collection {collection_name} is not a standby of a primary cluster",
+                ))
+            })?;
+
+        // Followed by another peer, or not at all
+        let follower = self.standby_followers.lock().get(&collection_name).cloned();
+        let follower = match follower {
+            Some(follower) => follower,
+            None => Arc::new(CollectionStandby::new(&collection_name, &entry.config)?),
+        };
+
+        Ok(follower.status(&entry, follower_peer_id))
+    }
+
+    /// Wait for a running synchronization of the standby on this peer to finish
+    pub async fn await_standby_sync(&self, collection_pass: &CollectionPass<'_>) {
+        let collection_name = self.resolve_standby_name(collection_pass).await;
+        let follower = self.standby_followers.lock().get(&collection_name).cloned();
+        if let Some(follower) = follower {
+            let _sync = follower.sync_lock.lock().await;
+        }
+    }
+
+    /// Resolve aliases. Before the first restore, the standby collection doesn't exist yet.
+    pub async fn resolve_standby_name(&self, collection_pass: &CollectionPass<'_>) -> String {
+        match self.get_collection(collection_pass).await {
+            Ok(collection) => collection.name(),
+            Err(_) => collection_pass.name().to_string(),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use chrono::Utc;
 use collection::config::ShardingMethod;
 use common::defaults::CONSENSUS_META_OP_WAIT;
 use segment::types::default_shard_number_per_node_const;
@@ -50,12 +51,17 @@ impl Dispatcher {
     /// This function needs to be called from a runtime with timers enabled.
     pub async fn submit_collection_meta_op(
         &self,
-        operation: CollectionMetaOperations,
+        mut operation: CollectionMetaOperations,
         access: Access,
         wait_timeout: Option<Duration>,
     ) -> Result<bool, StorageError> {
         access.check_collection_meta_operation(&operation)?;
 
+        // Time of alias changes is decided by the proposing peer, so all peers record the same history
+        if let CollectionMetaOperations::ChangeAliases(changes) = &mut operation {
+            changes.changed_at = Some(Utc::now());
+        }
+
         // if distributed deployment is enabled
         if let Some(state) = self.consensus_state.as_ref() {
             let start = Instant::now();
@@ -138,6 +144,9 @@ impl Dispatcher {
                 CollectionMetaOperations::CreateCollection(_)
                 | CollectionMetaOperations::CreateShardKey(_) => true,
 
+                // Sync nodes, so all of them accept or reject writes to the standby from now on
+                CollectionMetaOperations::UpdateStandby(_) => true,
+
                 // Sync nodes when creating or renaming collection aliases
                 CollectionMetaOperations::ChangeAliases(changes) => {
                     changes.actions.iter().any(|change| match change {
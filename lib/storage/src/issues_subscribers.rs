@@ -3,7 +3,7 @@ use std::sync::Arc;
 use collection::events::{CollectionDeletedEvent, IndexCreatedEvent, SlowQueryEvent};
 use issues::broker::Subscriber;
 use issues::Code;
-use segment::problems::UnindexedField;
+use segment::problems::{DegradedHnswGraph, UnindexedField};
 
 #[derive(Clone, Copy)]
 pub struct UnindexedFieldSubscriber;
@@ -39,3 +39,14 @@ impl Subscriber<IndexCreatedEvent> for UnindexedFieldSubscriber {
         ));
     }
 }
+
+#[derive(Clone, Copy)]
+pub struct DegradedHnswGraphSubscriber;
+
+impl Subscriber<CollectionDeletedEvent> for DegradedHnswGraphSubscriber {
+    fn notify(&self, event: Arc<CollectionDeletedEvent>) {
+        issues::solve_by_filter::<DegradedHnswGraph, _>(|code| {
+            DegradedHnswGraph::get_collection_name(code) == event.collection_id
+        });
+    }
+}
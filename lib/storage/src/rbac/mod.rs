@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidateArgs, ValidationError, ValidationErrors};
 
 use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::mounted_snapshots::source_collection_name;
 
 mod ops_checks;
 
@@ -111,10 +112,12 @@ impl CollectionAccessList {
         &'a self,
         collection_name: &'a str,
     ) -> Result<CollectionAccessView<'a>, StorageError> {
+        // Mounted snapshots are accessed with the rights on the collection they were taken of
+        let source_name = source_collection_name(collection_name);
         let access = self
             .0
             .iter()
-            .find(|collections| collections.collection == collection_name)
+            .find(|collections| collections.collection == source_name)
             .ok_or_else(|| {
                 StorageError::forbidden(format!(
                     "Access to collection {collection_name} is required"
@@ -9,7 +9,8 @@ use collection::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloa
 use collection::operations::point_ops::{PointIdsList, PointOperations};
 use collection::operations::types::{
     ContextExamplePair, CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal,
-    PointRequestInternal, RecommendExample, RecommendRequestInternal, ScrollRequestInternal,
+    PointChangesRequest, PointRequestInternal, RecommendExample, RecommendRequestInternal,
+    ScrollRequestInternal,
 };
 use collection::operations::universal_query::collection_query::{
     CollectionPrefetch, CollectionQueryRequest, Query, VectorInput, VectorQuery,
@@ -57,7 +58,8 @@ impl Access {
             | CollectionMetaOperations::TransferShard(_, _)
             | CollectionMetaOperations::SetShardReplicaState(_)
             | CollectionMetaOperations::CreateShardKey(_)
-            | CollectionMetaOperations::DropShardKey(_) => {
+            | CollectionMetaOperations::DropShardKey(_)
+            | CollectionMetaOperations::UpdateStandby(_) => {
                 self.check_global_access(AccessRequirements::new().manage())?;
             }
             CollectionMetaOperations::CreatePayloadIndex(op) => {
@@ -125,6 +127,17 @@ impl<'a> CollectionAccessView<'a> {
         }
     }
 
+    fn check_query(&self, query: &Query) -> Result<(), StorageError> {
+        match query {
+            Query::Vector(vector_query) => self.check_vector_query(vector_query),
+            Query::Hybrid(hybrid) => {
+                self.check_vector_input(&hybrid.dense)?;
+                self.check_vector_input(&hybrid.sparse)
+            }
+            Query::Fusion(_) | Query::OrderBy(_) | Query::Sample(_) => Ok(()),
+        }
+    }
+
     fn check_vector_query(
         &self,
         vector_query: &VectorQuery<VectorInput>,
@@ -155,6 +168,9 @@ impl<'a> CollectionAccessView<'a> {
         match vector_input {
             VectorInput::Vector(_) => Ok(()),
             VectorInput::Id(_) => self.check_whole_access(),
+            VectorInput::Sum(terms) => terms
+                .iter()
+                .try_for_each(|term| self.check_vector_input(&term.vector)),
         }
     }
 }
@@ -203,6 +219,24 @@ impl CheckableCollectionOperation for PointRequestInternal {
     }
 }
 
+impl CheckableCollectionOperation for PointChangesRequest {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: true,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        _view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for CoreSearchRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -329,8 +363,8 @@ impl CheckableCollectionOperation for CollectionQueryRequest {
     ) -> Result<(), StorageError> {
         view.apply_filter(&mut self.filter);
 
-        if let Some(Query::Vector(vector_query)) = &self.query {
-            view.check_vector_query(vector_query)?
+        if let Some(query) = &self.query {
+            view.check_query(query)?
         }
 
         access.check_lookup_from(&self.lookup_from)?;
@@ -350,8 +384,8 @@ fn check_access_for_prefetch(
 ) -> Result<(), StorageError> {
     view.apply_filter(&mut prefetch.filter);
 
-    if let Some(Query::Vector(vector_query)) = &prefetch.query {
-        view.check_vector_query(vector_query)?
+    if let Some(query) = &prefetch.query {
+        view.check_query(query)?
     }
 
     access.check_lookup_from(&prefetch.lookup_from)?;
@@ -551,6 +585,35 @@ mod tests {
     use super::*;
     use crate::rbac::{CollectionAccess, CollectionAccessMode};
 
+    #[test]
+    fn test_mounted_snapshot_access() {
+        let access = Access::Collection(CollectionAccessList(vec![CollectionAccess {
+            collection: "col".to_string(),
+            access: CollectionAccessMode::Read,
+            payload: None,
+        }]));
+
+        assert!(access
+            .check_collection_access("col:col-snapshot", AccessRequirements::new())
+            .is_ok());
+        assert!(access
+            .check_collection_access("col:col-snapshot", AccessRequirements::new().write())
+            .is_err());
+        assert!(access
+            .check_collection_access("other:other-snapshot", AccessRequirements::new())
+            .is_err());
+
+        // Rights on the mounted snapshot itself are not rights on the collection
+        let access = Access::Collection(CollectionAccessList(vec![CollectionAccess {
+            collection: "col:col-snapshot".to_string(),
+            access: CollectionAccessMode::ReadWrite,
+            payload: None,
+        }]));
+        assert!(access
+            .check_collection_access("col:col-snapshot", AccessRequirements::new())
+            .is_err());
+    }
+
     #[test]
     fn test_apply_filter() {
         let list = CollectionAccessList(vec![CollectionAccess {
@@ -720,6 +783,7 @@ mod tests_ops {
                 vector: Some("vector".to_string()),
                 shard_key: None,
             }),
+            skip_missing: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -781,6 +845,7 @@ mod tests_ops {
             ids: vec![PointIdType::NumId(12345)],
             with_payload: None,
             with_vector: WithVector::Bool(true),
+            as_of_version: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -885,6 +950,8 @@ mod tests_ops {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vectors: Some(WithVector::Bool(true)),
             }),
+            with_neighbors: None,
+            with_aggregates: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
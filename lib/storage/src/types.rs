@@ -1,5 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
@@ -7,7 +9,8 @@ use collection::common::snapshots_manager::SnapShotsConfig;
 use collection::config::WalConfig;
 use collection::operations::config_diff::OptimizersConfigDiff;
 use collection::operations::shared_storage_config::{
-    SharedStorageConfig, DEFAULT_IO_SHARD_TRANSFER_LIMIT, DEFAULT_SNAPSHOTS_PATH,
+    SharedStorageConfig, DEFAULT_DEGRADED_REPLICATION_LAG, DEFAULT_IO_SHARD_TRANSFER_LIMIT,
+    DEFAULT_SNAPSHOTS_PATH,
 };
 use collection::operations::types::{NodeType, PeerMetadata};
 use collection::optimizers_builder::OptimizersConfig;
@@ -16,11 +19,14 @@ use collection::shards::transfer::ShardTransferMethod;
 use memory::madvise;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
-use segment::types::{CollectionConfigDefaults, HnswConfig};
+use segment::types::{CollectionConfigDefaults, Filter, HnswConfig};
 use serde::{Deserialize, Serialize};
 use tonic::transport::Uri;
 use validator::Validate;
 
+/// Directory for the spilled results of queries, inside the temp or storage directory
+const QUERY_SPILL_SUBDIR_NAME: &str = "tmp";
+
 pub type PeerAddressById = HashMap<PeerId, Uri>;
 pub type PeerMetadataById = HashMap<PeerId, PeerMetadata>;
 
@@ -43,12 +49,36 @@ pub struct PerformanceConfig {
     pub incoming_shard_transfers_limit: Option<usize>,
     #[serde(default = "default_io_shard_transfers_limit")]
     pub outgoing_shard_transfers_limit: Option<usize>,
+    /// Memory budget for merging the results of a query, in megabytes.
+    /// Points exceeding it are spilled to temporary files.
+    /// If null - results are always kept in memory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_merge_memory_budget_mb: Option<usize>,
+    /// Percentile of recent read latencies of a shard, e.g. 95. If a replica doesn't respond
+    /// within it, the read is also sent to another replica and the first response is used.
+    /// If null - reads are not hedged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hedged_reads_percentile: Option<f64>,
+    /// Active replicas lagging behind the replica of this peer by more than this number of
+    /// operations are reported as degraded in the cluster info of collections, and reads are
+    /// routed to other replicas first.
+    /// If null - replicas are never reported as degraded.
+    #[serde(default = "default_degraded_replication_lag")]
+    pub degraded_replication_lag: Option<u64>,
+    /// Attribute bytes read from disk to collections in telemetry and metrics.
+    /// Costs a read of `/proc/thread-self/io` before and after each measured task, Linux only.
+    #[serde(default)]
+    pub measure_io_usage: bool,
 }
 
 const fn default_io_shard_transfers_limit() -> Option<usize> {
     DEFAULT_IO_SHARD_TRANSFER_LIMIT
 }
 
+const fn default_degraded_replication_lag() -> Option<u64> {
+    DEFAULT_DEGRADED_REPLICATION_LAG
+}
+
 /// Global configuration of the storage, loaded on the service launch, default stored in ./config
 #[derive(Clone, Debug, Deserialize, Validate)]
 pub struct StorageConfig {
@@ -97,6 +127,172 @@ pub struct StorageConfig {
     /// Default values for collections.
     #[serde(default)]
     pub collection: Option<CollectionConfigDefaults>,
+    /// Lazily mounted snapshots are unloaded, and their unpacked files removed, once they were not
+    /// queried for this many seconds. They are loaded again from the snapshot storage when queried.
+    #[serde(default = "default_lazy_mount_idle_timeout_sec")]
+    pub lazy_mount_idle_timeout_sec: u64,
+    /// External service to rerank the results of queries with, e.g. a cross-encoder.
+    /// If null - reranking is not available.
+    #[validate]
+    #[serde(default)]
+    pub reranker: Option<RerankerConfig>,
+    /// Secondary clusters to asynchronously mirror the writes of collections to, by collection name.
+    #[serde(default)]
+    pub mirrors: HashMap<String, MirrorConfig>,
+    /// Evict memory-mapped pages of cold collections, when the node approaches memory pressure.
+    /// If null - the kernel alone decides which pages to evict.
+    #[validate]
+    #[serde(default)]
+    pub mmap_budget: Option<MmapBudgetConfig>,
+    /// Watch collections for status changes and finished optimizations, and notify webhooks about
+    /// lifecycle events of collections. If null - only creation, deletion and completed transfers
+    /// are published to internal subscribers.
+    #[validate]
+    #[serde(default)]
+    pub collection_events: Option<CollectionEventsConfig>,
+    /// Rules to rewrite read requests to collections with, by collection name.
+    #[serde(default)]
+    pub query_rewrites: HashMap<String, QueryRewriteRules>,
+    /// Limits of the queries to collections. If null - queries are not limited.
+    #[serde(default)]
+    pub query_limits: Option<QueryLimitsConfig>,
+    /// Experimental query features allowed for each collection, by collection name.
+    #[serde(default)]
+    pub experimental_features: HashMap<String, HashSet<ExperimentalFeature>>,
+}
+
+/// Configuration of the external reranking service
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct RerankerConfig {
+    /// Endpoint to send the query and the texts of the results to
+    #[validate(length(min = 1))]
+    pub url: String,
+    /// API key, sent as bearer token in the `Authorization` header
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// If the reranker doesn't respond within this time, the original order is kept
+    #[serde(default = "default_reranker_timeout_ms")]
+    #[validate(range(min = 1))]
+    pub timeout_ms: u64,
+}
+
+/// Configuration of the mirroring of a collection to a secondary cluster
+#[derive(Clone, Debug, Deserialize)]
+pub struct MirrorConfig {
+    /// gRPC URI of the secondary cluster, e.g. `http://secondary:6334`
+    pub uri: String,
+    /// Name of the collection on the secondary cluster. Same as the mirrored collection by default.
+    #[serde(default)]
+    pub collection: Option<String>,
+    /// API key of the secondary cluster
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Max number of operations waiting to be mirrored.
+    /// If exceeded, operations are dropped and the secondary needs a resync.
+    #[serde(default = "default_mirror_queue_size")]
+    pub queue_size: usize,
+    /// Timeout of a single request to the secondary cluster
+    #[serde(default = "default_mirror_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+/// Configuration of the eviction of memory-mapped files of cold collections
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct MmapBudgetConfig {
+    /// Start evicting once this fraction of the memory available to the node is in use,
+    /// and evict until usage is below it again
+    #[serde(default = "default_mmap_budget_memory_pressure_ratio")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub memory_pressure_ratio: f64,
+    /// Collections, which were not accessed for this many seconds, are cold and may be evicted
+    #[serde(default = "default_mmap_budget_cold_after_sec")]
+    pub cold_after_sec: u64,
+    /// Interval to check memory usage at
+    #[serde(default = "default_mmap_budget_check_interval_sec")]
+    #[validate(range(min = 1))]
+    pub check_interval_sec: u64,
+}
+
+/// Rules applied to read requests to a collection, before they are executed
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct QueryRewriteRules {
+    /// Filter, which is always combined with the filter of the request
+    #[serde(default)]
+    pub filter: Option<Filter>,
+    /// Max number of results a request may return, larger limits are capped
+    #[serde(default)]
+    pub max_limit: Option<usize>,
+    /// Vectors renamed in requests, from the old name to the new one
+    #[serde(default)]
+    pub vector_names: HashMap<String, String>,
+}
+
+/// Limits of the queries to collections
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct QueryLimitsConfig {
+    #[serde(default)]
+    pub mode: QueryLimitsMode,
+    /// Max number of results of a query or a prefetch
+    #[serde(default)]
+    pub max_limit: Option<usize>,
+    /// Max nesting of prefetches in a query
+    #[serde(default)]
+    pub max_prefetch_depth: Option<usize>,
+    /// Forbid filtering by fields without a suitable payload index
+    #[serde(default)]
+    pub forbid_unindexed_filter: bool,
+}
+
+/// How queries exceeding the limits are handled
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryLimitsMode {
+    /// Reject queries exceeding the limits
+    #[default]
+    Enforce,
+    /// Execute queries exceeding the limits, and return the exceeded limits as warnings
+    Warn,
+}
+
+/// Query features which are not stable yet. Their behavior may change between releases, so they
+/// are only allowed for collections which enable them.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ExperimentalFeature {
+    /// Distribution-based score fusion of prefetches
+    DbsfFusion,
+}
+
+impl fmt::Display for ExperimentalFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DbsfFusion => write!(f, "dbsf_fusion"),
+        }
+    }
+}
+
+/// Configuration of the lifecycle events of collections
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct CollectionEventsConfig {
+    /// Interval to check the status of collections at
+    #[serde(default = "default_collection_events_check_interval_sec")]
+    #[validate(range(min = 1))]
+    pub check_interval_sec: u64,
+    /// Endpoints to send each event to, as JSON in a POST request
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+/// Configuration of an endpoint, which is notified about collection events
+#[derive(Clone, Debug, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// API key, sent as bearer token in the `Authorization` header
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Timeout of a single notification. Notifications are not retried.
+    #[serde(default = "default_webhook_timeout_ms")]
+    pub timeout_ms: u64,
 }
 
 impl StorageConfig {
@@ -116,6 +312,16 @@ impl StorageConfig {
             self.performance.outgoing_shard_transfers_limit,
             self.snapshots_path.clone(),
             self.snapshots_config.clone(),
+            self.performance
+                .query_merge_memory_budget_mb
+                .map(|mb| mb * 1024 * 1024),
+            Path::new(self.temp_path.as_deref().unwrap_or(&self.storage_path))
+                .join(QUERY_SPILL_SUBDIR_NAME),
+            self.performance
+                .hedged_reads_percentile
+                .map(|percentile| percentile.clamp(0.0, 100.0)),
+            self.performance.degraded_replication_lag,
+            self.performance.measure_io_usage,
         )
     }
 }
@@ -124,6 +330,42 @@ fn default_snapshots_path() -> String {
     DEFAULT_SNAPSHOTS_PATH.to_string()
 }
 
+const fn default_lazy_mount_idle_timeout_sec() -> u64 {
+    600
+}
+
+const fn default_reranker_timeout_ms() -> u64 {
+    1000
+}
+
+const fn default_mirror_queue_size() -> usize {
+    10_000
+}
+
+const fn default_mirror_timeout_ms() -> u64 {
+    10_000
+}
+
+const fn default_mmap_budget_memory_pressure_ratio() -> f64 {
+    0.9
+}
+
+const fn default_mmap_budget_cold_after_sec() -> u64 {
+    300
+}
+
+const fn default_mmap_budget_check_interval_sec() -> u64 {
+    10
+}
+
+const fn default_collection_events_check_interval_sec() -> u64 {
+    5
+}
+
+const fn default_webhook_timeout_ms() -> u64 {
+    5_000
+}
+
 const fn default_on_disk_payload() -> bool {
     false
 }
@@ -56,6 +56,10 @@ fn test_alias_operation() {
             search_timeout_sec: None,
             incoming_shard_transfers_limit: Some(1),
             outgoing_shard_transfers_limit: Some(1),
+            query_merge_memory_budget_mb: None,
+            hedged_reads_percentile: None,
+            degraded_replication_lag: None,
+            measure_io_usage: false,
         },
         hnsw_index: Default::default(),
         mmap_advice: madvise::Advice::Random,
@@ -68,6 +72,14 @@ fn test_alias_operation() {
         // update_concurrency: None,
         shard_transfer_method: None,
         collection: None,
+        lazy_mount_idle_timeout_sec: 600,
+        reranker: None,
+        mirrors: Default::default(),
+        mmap_budget: None,
+        collection_events: None,
+        query_rewrites: Default::default(),
+        query_limits: None,
+        experimental_features: Default::default(),
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -80,16 +92,19 @@ fn test_alias_operation() {
     let (propose_sender, _propose_receiver) = std::sync::mpsc::channel();
     let propose_operation_sender = OperationSender::new(propose_sender);
 
-    let toc = Arc::new(TableOfContent::new(
-        &config,
-        search_runtime,
-        update_runtime,
-        general_runtime,
-        CpuBudget::default(),
-        ChannelService::new(6333, None),
-        0,
-        Some(propose_operation_sender),
-    ));
+    let toc = Arc::new(
+        TableOfContent::new(
+            &config,
+            search_runtime,
+            update_runtime,
+            general_runtime,
+            CpuBudget::default(),
+            ChannelService::new(6333, None),
+            0,
+            Some(propose_operation_sender),
+        )
+        .unwrap(),
+    );
     let dispatcher = Dispatcher::new(toc);
 
     handle
@@ -112,6 +127,7 @@ fn test_alias_operation() {
                         init_from: None,
                         quantization_config: None,
                         sharding_method: None,
+                        hybrid_vectors: None,
                     },
                 )),
                 FULL_ACCESS.clone(),
@@ -128,6 +144,7 @@ fn test_alias_operation() {
                         alias_name: "test_alias".to_string(),
                     }
                     .into()],
+                changed_at: None,
             }),
             FULL_ACCESS.clone(),
             None,
@@ -153,6 +170,7 @@ fn test_alias_operation() {
                         }
                         .into(),
                     ],
+                changed_at: None,
             }),
             FULL_ACCESS.clone(),
             None,
@@ -168,4 +186,44 @@ fn test_alias_operation() {
             ),
         )
         .unwrap();
+
+    let history = |alias_name: &str| {
+        handle
+            .block_on(
+                dispatcher
+                    .toc(&FULL_ACCESS)
+                    .alias_history(alias_name, &FULL_ACCESS),
+            )
+            .unwrap()
+            .changes
+    };
+
+    // Every change is timestamped by the dispatcher
+    let test_alias = history("test_alias");
+    assert_eq!(
+        test_alias
+            .iter()
+            .map(|change| change.collection_name.as_deref())
+            .collect::<Vec<_>>(),
+        vec![Some("test"), None],
+    );
+    assert!(test_alias.iter().all(|change| change.changed_at.is_some()));
+    assert!(test_alias[0].changed_at <= test_alias[1].changed_at);
+
+    // Renamed alias ends under the old name, and starts under the new one
+    let test_alias2 = history("test_alias2");
+    assert_eq!(test_alias2.len(), 2);
+    assert_eq!(test_alias2[1].collection_name, None);
+    let test_alias3 = history("test_alias3");
+    assert_eq!(test_alias3.len(), 1);
+    assert_eq!(test_alias3[0].collection_name.as_deref(), Some("test"));
+    assert_eq!(test_alias3[0].changed_at, test_alias2[1].changed_at);
+
+    assert!(handle
+        .block_on(
+            dispatcher
+                .toc(&FULL_ACCESS)
+                .alias_history("test_alias4", &FULL_ACCESS),
+        )
+        .is_err());
 }
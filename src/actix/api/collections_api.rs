@@ -5,11 +5,15 @@ use actix_web::rt::time::Instant;
 use actix_web::{delete, get, patch, post, put, web, HttpResponse, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::config_diff::CollectionParamsDiff;
+use collection::operations::types::SegmentUpgradeRequest;
 use serde::Deserialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
-    DeleteCollectionOperation, UpdateCollection, UpdateCollectionOperation,
+    DeleteCollectionOperation, StandbyConfig, UpdateCollection, UpdateCollectionOperation,
 };
+use storage::content_manager::toc::alias_versions::CreateAliasVersion;
+use storage::content_manager::toc::migration::MigrateCollection;
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
@@ -31,6 +35,12 @@ impl WaitTimeout {
     }
 }
 
+#[derive(Debug, Deserialize, Validate)]
+struct AliasPath {
+    #[validate(length(min = 1, max = 255))]
+    alias_name: String,
+}
+
 #[get("/collections")]
 fn get_collections(
     dispatcher: web::Data<Dispatcher>,
@@ -49,6 +59,39 @@ async fn get_aliases(
     process_response(response, timing)
 }
 
+#[get("/aliases/{alias_name}/history")]
+async fn get_alias_history(
+    dispatcher: web::Data<Dispatcher>,
+    alias: Path<AliasPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_alias_history(dispatcher.toc(&access), access, &alias.alias_name).await;
+    process_response(response, timing)
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct CreateAliasVersionParam {
+    wait: Option<bool>,
+}
+
+#[post("/aliases/{alias_name}/versions")]
+async fn create_alias_version(
+    dispatcher: web::Data<Dispatcher>,
+    alias: Path<AliasPath>,
+    Query(params): Query<CreateAliasVersionParam>,
+    Json(request): Json<CreateAliasVersion>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let dispatcher = dispatcher.into_inner();
+    let alias_name = alias.into_inner().alias_name;
+    helpers::time_or_accept(
+        do_create_alias_version(dispatcher, access, alias_name, request),
+        params.wait.unwrap_or(true),
+    )
+    .await
+}
+
 #[get("/collections/{name}")]
 async fn get_collection(
     dispatcher: web::Data<Dispatcher>,
@@ -128,6 +171,37 @@ async fn update_collection(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/freeze")]
+async fn freeze_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let mut operation = UpdateCollectionOperation::new_empty(collection.name.clone());
+    operation.update_collection.params = Some(CollectionParamsDiff {
+        replication_factor: None,
+        write_consistency_factor: None,
+        read_fan_out_factor: None,
+        point_history_size: None,
+        vector_routing: None,
+        on_disk_payload: None,
+        frozen: Some(true),
+        filter_templates: None,
+        search_pools: None,
+        payload_compression: None,
+    });
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::UpdateCollection(operation),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
 #[delete("/collections/{name}")]
 async fn delete_collection(
     dispatcher: web::Data<Dispatcher>,
@@ -199,6 +273,163 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[derive(Debug, Deserialize, Validate)]
+struct CompactWalParam {
+    wait: Option<bool>,
+}
+
+#[post("/collections/{name}/wal/compact")]
+async fn compact_collection_wal(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    Query(params): Query<CompactWalParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
+        do_compact_collection_wal(dispatcher.toc(&access).clone(), access, &collection.name)
+    })
+    .await
+}
+
+#[post("/collections/{name}/segments/upgrade")]
+async fn start_segment_upgrade(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    Json(request): Json<SegmentUpgradeRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_start_segment_upgrade(dispatcher.toc(&access), access, &collection.name, request).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/segments/upgrade")]
+async fn get_segment_upgrade(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_segment_upgrade(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[delete("/collections/{name}/segments/upgrade")]
+async fn stop_segment_upgrade(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_stop_segment_upgrade(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct ResyncMirrorParam {
+    wait: Option<bool>,
+}
+
+#[get("/collections/{name}/mirror")]
+async fn get_collection_mirror(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_mirror(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/mirror/resync")]
+async fn resync_collection_mirror(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    Query(params): Query<ResyncMirrorParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
+        do_resync_collection_mirror(dispatcher.toc(&access).clone(), access, &collection.name)
+    })
+    .await
+}
+
+#[get("/collections/{name}/standby")]
+async fn get_collection_standby(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_standby(&dispatcher.into_inner(), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{name}/standby")]
+async fn update_collection_standby(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    config: Json<StandbyConfig>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_update_collection_standby(
+        &dispatcher.into_inner(),
+        access,
+        &collection.name,
+        config.into_inner(),
+        query.timeout(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/standby/promote")]
+async fn promote_collection_standby(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_promote_collection_standby(
+        &dispatcher.into_inner(),
+        access,
+        &collection.name,
+        query.timeout(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct MigrateCollectionParam {
+    wait: Option<bool>,
+}
+
+#[post("/collections/{name}/migrate")]
+async fn migrate_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    Query(params): Query<MigrateCollectionParam>,
+    Json(request): Json<MigrateCollection>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
+        do_migrate_collection(
+            dispatcher.toc(&access).clone(),
+            access,
+            &collection.name,
+            request,
+        )
+    })
+    .await
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     // Ordering of services is important for correct path pattern matching
@@ -209,11 +440,24 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_collection_existence)
         .service(create_collection)
         .service(update_collection)
+        .service(freeze_collection)
         .service(delete_collection)
         .service(get_aliases)
+        .service(get_alias_history)
+        .service(create_alias_version)
         .service(get_collection_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(compact_collection_wal)
+        .service(start_segment_upgrade)
+        .service(get_segment_upgrade)
+        .service(stop_segment_upgrade)
+        .service(get_collection_mirror)
+        .service(resync_collection_mirror)
+        .service(get_collection_standby)
+        .service(update_collection_standby)
+        .service(promote_collection_standby)
+        .service(migrate_collection);
 }
 
 #[cfg(test)]
@@ -39,6 +39,7 @@ async fn count_points(
         shard_selector,
         access,
         // ToDo: use timeout from params
+        params.session_token.as_ref(),
     )
     .await;
 
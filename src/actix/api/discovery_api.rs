@@ -1,15 +1,17 @@
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::common::search_stats;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{DiscoverRequest, DiscoverRequestBatch};
 use itertools::Itertools;
+use storage::content_manager::toc::query_limits;
 use storage::dispatcher::Dispatcher;
 use tokio::time::Instant;
 
 use crate::actix::api::read_params::ReadParams;
 use crate::actix::api::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::process_response_with_stats;
 use crate::common::points::do_discover_batch_points;
 
 #[post("/collections/{name}/points/discover")]
@@ -25,6 +27,7 @@ async fn discover_points(
     let DiscoverRequest {
         discover_request,
         shard_key,
+        with_stats,
     } = request.into_inner();
 
     let shard_selection = match shard_key {
@@ -32,25 +35,27 @@ async fn discover_points(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = dispatcher
-        .toc(&access)
-        .discover(
+    let ((response, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats.unwrap_or_default(),
+        dispatcher.toc(&access).discover(
             &collection.name,
             discover_request,
             params.consistency,
             shard_selection,
             access,
             params.timeout(),
-        )
-        .await
-        .map(|scored_points| {
-            scored_points
-                .into_iter()
-                .map(api::rest::ScoredPoint::from)
-                .collect_vec()
-        });
+            params.session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let response = response.map(|scored_points| {
+        scored_points
+            .into_iter()
+            .map(api::rest::ScoredPoint::from)
+            .collect_vec()
+    });
 
-    process_response(response, timing)
+    process_response_with_stats(response, timing, warnings, stats)
 }
 
 #[post("/collections/{name}/points/discover/batch")]
@@ -63,16 +68,26 @@ async fn discover_batch_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = do_discover_batch_points(
-        dispatcher.toc(&access),
-        &collection.name,
-        request.into_inner(),
-        params.consistency,
-        access,
-        params.timeout(),
-    )
-    .await
-    .map(|batch_scored_points| {
+    let request = request.into_inner();
+    let with_stats = request
+        .searches
+        .iter()
+        .any(|req| req.with_stats.unwrap_or_default());
+
+    let ((response, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats,
+        do_discover_batch_points(
+            dispatcher.toc(&access),
+            &collection.name,
+            request,
+            params.consistency,
+            access,
+            params.timeout(),
+            params.session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let response = response.map(|batch_scored_points| {
         batch_scored_points
             .into_iter()
             .map(|scored_points| {
@@ -84,7 +99,7 @@ async fn discover_batch_points(
             .collect_vec()
     });
 
-    process_response(response, timing)
+    process_response_with_stats(response, timing, warnings, stats)
 }
 
 pub fn config_discovery_api(cfg: &mut web::ServiceConfig) {
@@ -1,13 +1,14 @@
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::{QueryRequest, QueryRequestBatch, QueryResponse};
+use api::rest::{QueryDslRequest, QueryRequest, QueryRequestBatch, QueryResponse};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
+use collection::operations::universal_query::query_dsl::parse_query_dsl;
 use itertools::Itertools;
 use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
 
-use super::read_params::ReadParams;
+use super::read_params::{ClientTimeout, ReadParams};
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers;
@@ -18,6 +19,7 @@ async fn query_points(
     collection: Path<CollectionPath>,
     request: Json<QueryRequest>,
     params: Query<ReadParams>,
+    client_timeout: ClientTimeout,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     helpers::time(async move {
@@ -31,16 +33,19 @@ async fn query_points(
             Some(shard_keys) => shard_keys.into(),
         };
 
-        let points = dispatcher
+        let (mut results, mut stats) = dispatcher
             .toc(&access)
-            .query_batch(
+            .query_batch_with_stats(
                 &collection.name,
                 vec![(query_request.into(), shard_selection)],
                 params.consistency,
                 access,
-                params.timeout(),
+                params.timeout_within(&client_timeout),
+                params.session_token.as_ref(),
             )
-            .await?
+            .await?;
+
+        let points = results
             .pop()
             .ok_or_else(|| {
                 StorageError::service_error("Expected at least one response for one query")
@@ -48,8 +53,9 @@ async fn query_points(
             .into_iter()
             .map(api::rest::ScoredPoint::from)
             .collect_vec();
+        let stats = stats.pop().flatten();
 
-        Ok(QueryResponse { points })
+        Ok(QueryResponse { points, stats })
     })
     .await
 }
@@ -60,6 +66,7 @@ async fn query_points_batch(
     collection: Path<CollectionPath>,
     request: Json<QueryRequestBatch>,
     params: Query<ReadParams>,
+    client_timeout: ClientTimeout,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     helpers::time(async move {
@@ -83,22 +90,27 @@ async fn query_points_batch(
             })
             .collect();
 
-        let res = dispatcher
+        let (results, stats) = dispatcher
             .toc(&access)
-            .query_batch(
+            .query_batch_with_stats(
                 &collection.name,
                 batch,
                 params.consistency,
                 access,
-                params.timeout(),
+                params.timeout_within(&client_timeout),
+                params.session_token.as_ref(),
             )
-            .await?
+            .await?;
+
+        let res = results
             .into_iter()
-            .map(|response| QueryResponse {
+            .zip(stats)
+            .map(|(response, stats)| QueryResponse {
                 points: response
                     .into_iter()
                     .map(api::rest::ScoredPoint::from)
                     .collect_vec(),
+                stats,
             })
             .collect_vec();
 
@@ -107,7 +119,53 @@ async fn query_points_batch(
     .await
 }
 
+#[post("/collections/{name}/points/query/dsl")]
+async fn query_points_dsl(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryDslRequest>,
+    params: Query<ReadParams>,
+    client_timeout: ClientTimeout,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        let QueryDslRequest { query, shard_key } = request.into_inner();
+
+        let query_request = parse_query_dsl(&query)?;
+        let shard_selection = match shard_key {
+            None => ShardSelectorInternal::All,
+            Some(shard_keys) => shard_keys.into(),
+        };
+
+        let (mut results, mut stats) = dispatcher
+            .toc(&access)
+            .query_batch_with_stats(
+                &collection.name,
+                vec![(query_request, shard_selection)],
+                params.consistency,
+                access,
+                params.timeout_within(&client_timeout),
+                params.session_token.as_ref(),
+            )
+            .await?;
+
+        let points = results
+            .pop()
+            .ok_or_else(|| {
+                StorageError::service_error("Expected at least one response for one query")
+            })?
+            .into_iter()
+            .map(api::rest::ScoredPoint::from)
+            .collect_vec();
+        let stats = stats.pop().flatten();
+
+        Ok(QueryResponse { points, stats })
+    })
+    .await
+}
+
 pub fn config_query_api(cfg: &mut web::ServiceConfig) {
     cfg.service(query_points);
     cfg.service(query_points_batch);
+    cfg.service(query_points_dsl);
 }
@@ -1,24 +1,65 @@
+use std::future::{ready, Ready};
 use std::num::NonZeroU64;
 use std::time::Duration;
 
+use actix_web::FromRequest;
 use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::session_token::SessionToken;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
 use validator::Validate;
 
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
+use crate::actix::helpers::HttpError;
+use crate::common::request_timeout::{min_timeout, parse_request_timeout, REQUEST_TIMEOUT_HEADER};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
 pub struct ReadParams {
     #[serde(default, deserialize_with = "deserialize_read_consistency")]
     #[validate]
     pub consistency: Option<ReadConsistency>,
     /// If set, overrides global timeout for this request. Unit is seconds.
     pub timeout: Option<NonZeroU64>,
+    /// Token returned by writes which the request must observe.
+    #[serde(default)]
+    pub session_token: Option<SessionToken>,
 }
 
 impl ReadParams {
     pub fn timeout(&self) -> Option<Duration> {
         self.timeout.map(|num| Duration::from_secs(num.get()))
     }
+
+    /// Timeout of the request, limited by the timeout of the client
+    pub fn timeout_within(&self, client_timeout: &ClientTimeout) -> Option<Duration> {
+        min_timeout(self.timeout(), client_timeout.0)
+    }
+}
+
+/// Timeout of the client, given in the `X-Request-Timeout` header
+pub struct ClientTimeout(pub Option<Duration>);
+
+impl FromRequest for ClientTimeout {
+    type Error = HttpError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(
+        req: &actix_web::HttpRequest,
+        _payload: &mut actix_web::dev::Payload,
+    ) -> Self::Future {
+        let Some(value) = req.headers().get(REQUEST_TIMEOUT_HEADER) else {
+            return ready(Ok(ClientTimeout(None)));
+        };
+
+        let timeout = value.to_str().ok().and_then(parse_request_timeout);
+        ready(match timeout {
+            Some(timeout) => Ok(ClientTimeout(Some(timeout))),
+            None => Err(StorageError::bad_input(
+                "Invalid X-Request-Timeout header, expected a positive number of seconds",
+            )
+            .into()),
+        })
+    }
 }
 
 fn deserialize_read_consistency<'de, D>(
@@ -78,6 +119,17 @@ mod test {
         assert!(try_deserialize(&str("0")).is_err());
     }
 
+    #[test]
+    fn deserialize_session_token() {
+        let params = deserialize("session_token=0:1:0:10:2,1:1:0:3:1");
+        assert_eq!(
+            params.session_token.unwrap().to_string(),
+            "0:1:0:10:2,1:1:0:3:1",
+        );
+
+        assert!(try_deserialize("session_token=0:1:0").is_err());
+    }
+
     fn test(value: &str, params: ReadParams) {
         test_str(&str(value), params);
     }
@@ -3,7 +3,9 @@ use std::time::Duration;
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::common::search_stats;
 use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::session_token::SessionToken;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch,
@@ -11,14 +13,14 @@ use collection::operations::types::{
 use itertools::Itertools;
 use segment::types::ScoredPoint;
 use storage::content_manager::errors::StorageError;
-use storage::content_manager::toc::TableOfContent;
+use storage::content_manager::toc::{query_limits, TableOfContent};
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::process_response_with_stats;
 
 #[post("/collections/{name}/points/recommend")]
 async fn recommend_points(
@@ -33,6 +35,7 @@ async fn recommend_points(
     let RecommendRequest {
         recommend_request,
         shard_key,
+        with_stats,
     } = request.into_inner();
 
     let shard_selection = match shard_key {
@@ -40,25 +43,27 @@ async fn recommend_points(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = dispatcher
-        .toc(&access)
-        .recommend(
+    let ((response, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats.unwrap_or_default(),
+        dispatcher.toc(&access).recommend(
             &collection.name,
             recommend_request,
             params.consistency,
             shard_selection,
             access,
             params.timeout(),
-        )
-        .await
-        .map(|scored_points| {
-            scored_points
-                .into_iter()
-                .map(api::rest::ScoredPoint::from)
-                .collect_vec()
-        });
-
-    process_response(response, timing)
+            params.session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let response = response.map(|scored_points| {
+        scored_points
+            .into_iter()
+            .map(api::rest::ScoredPoint::from)
+            .collect_vec()
+    });
+
+    process_response_with_stats(response, timing, warnings, stats)
 }
 
 async fn do_recommend_batch_points(
@@ -68,6 +73,7 @@ async fn do_recommend_batch_points(
     read_consistency: Option<ReadConsistency>,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = request
         .searches
@@ -82,8 +88,15 @@ async fn do_recommend_batch_points(
         })
         .collect();
 
-    toc.recommend_batch(collection_name, requests, read_consistency, access, timeout)
-        .await
+    toc.recommend_batch(
+        collection_name,
+        requests,
+        read_consistency,
+        access,
+        timeout,
+        session_token,
+    )
+    .await
 }
 
 #[post("/collections/{name}/points/recommend/batch")]
@@ -96,16 +109,26 @@ async fn recommend_batch_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = do_recommend_batch_points(
-        dispatcher.toc(&access),
-        &collection.name,
-        request.into_inner(),
-        params.consistency,
-        access,
-        params.timeout(),
-    )
-    .await
-    .map(|batch_scored_points| {
+    let request = request.into_inner();
+    let with_stats = request
+        .searches
+        .iter()
+        .any(|req| req.with_stats.unwrap_or_default());
+
+    let ((response, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats,
+        do_recommend_batch_points(
+            dispatcher.toc(&access),
+            &collection.name,
+            request,
+            params.consistency,
+            access,
+            params.timeout(),
+            params.session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let response = response.map(|batch_scored_points| {
         batch_scored_points
             .into_iter()
             .map(|scored_points| {
@@ -117,7 +140,7 @@ async fn recommend_batch_points(
             .collect_vec()
     });
 
-    process_response(response, timing)
+    process_response_with_stats(response, timing, warnings, stats)
 }
 
 #[post("/collections/{name}/points/recommend/groups")]
@@ -133,6 +156,7 @@ async fn recommend_point_groups(
     let RecommendGroupsRequest {
         recommend_group_request,
         shard_key,
+        with_stats,
     } = request.into_inner();
 
     let shard_selection = match shard_key {
@@ -140,18 +164,22 @@ async fn recommend_point_groups(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = crate::common::points::do_recommend_point_groups(
-        dispatcher.toc(&access),
-        &collection.name,
-        recommend_group_request,
-        params.consistency,
-        shard_selection,
-        access,
-        params.timeout(),
-    )
+    let ((response, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats.unwrap_or_default(),
+        crate::common::points::do_recommend_point_groups(
+            dispatcher.toc(&access),
+            &collection.name,
+            recommend_group_request,
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+            params.session_token.as_ref(),
+        ),
+    ))
     .await;
 
-    process_response(response, timing)
+    process_response_with_stats(response, timing, warnings, stats)
 }
 // Configure services
 pub fn config_recommend_api(cfg: &mut web::ServiceConfig) {
@@ -2,8 +2,12 @@ use actix_web::rt::time::Instant;
 use actix_web::{get, post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::session_token::SessionToken;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::types::{PointRequest, PointRequestInternal, Record, ScrollRequest};
+use collection::operations::types::{
+    PointChangesRequest, PointRequest, PointRequestInternal, Record, SampleRequest, ScoreRequest,
+    ScrollRequest,
+};
 use itertools::Itertools;
 use segment::types::{PointIdType, WithPayloadInterface};
 use serde::Deserialize;
@@ -17,7 +21,9 @@ use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response};
-use crate::common::points::do_get_points;
+use crate::common::points::{
+    do_get_hnsw_neighborhood, do_get_points, do_sample_points, do_score_points,
+};
 
 #[derive(Deserialize, Validate)]
 struct PointPath {
@@ -26,17 +32,25 @@ struct PointPath {
     id: String,
 }
 
+#[derive(Deserialize, Validate)]
+struct HnswNeighborhoodParams {
+    /// Name of the vector, the default one if not specified
+    vector: Option<String>,
+}
+
 async fn do_get_point(
     toc: &TableOfContent,
     collection_name: &str,
     point_id: PointIdType,
     read_consistency: Option<ReadConsistency>,
     access: Access,
+    session_token: Option<&SessionToken>,
 ) -> Result<Option<Record>, StorageError> {
     let request = PointRequestInternal {
         ids: vec![point_id],
         with_payload: Some(WithPayloadInterface::Bool(true)),
         with_vector: true.into(),
+        as_of_version: None,
     };
 
     let shard_selection = ShardSelectorInternal::All;
@@ -47,6 +61,7 @@ async fn do_get_point(
         read_consistency,
         shard_selection,
         access,
+        session_token,
     )
     .await
     .map(|points| points.into_iter().next())
@@ -71,6 +86,7 @@ async fn get_point(
             point_id,
             params.consistency,
             access,
+            params.session_token.as_ref(),
         )
         .await?
         else {
@@ -84,6 +100,31 @@ async fn get_point(
     .await
 }
 
+#[get("/collections/{name}/points/{id}/hnsw")]
+async fn get_point_hnsw_neighborhood(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    point: Path<PointPath>,
+    params: Query<HnswNeighborhoodParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        let point_id: PointIdType = point.id.parse().map_err(|_| StorageError::BadInput {
+            description: format!("Can not recognize \"{}\" as point id", point.id),
+        })?;
+
+        do_get_hnsw_neighborhood(
+            dispatcher.toc(&access),
+            &collection.name,
+            point_id,
+            params.into_inner().vector,
+            access,
+        )
+        .await
+    })
+    .await
+}
+
 #[post("/collections/{name}/points")]
 async fn get_points(
     dispatcher: web::Data<Dispatcher>,
@@ -111,6 +152,7 @@ async fn get_points(
         params.consistency,
         shard_selection,
         access,
+        params.session_token.as_ref(),
     )
     .await;
     let response = response.map(|v| v.into_iter().map(api::rest::Record::from).collect_vec());
@@ -146,8 +188,95 @@ async fn scroll_points(
             // TODO: handle params.timeout
             shard_selection,
             access,
+            params.session_token.as_ref(),
         )
         .await;
 
     process_response(response, timing)
 }
+
+#[post("/collections/{name}/points/changes")]
+async fn point_changes(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<PointChangesRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = dispatcher
+        .toc(&access)
+        .point_changes(&collection.name, request.into_inner(), access)
+        .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/sample")]
+async fn sample_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SampleRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SampleRequest {
+        sample_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let response = do_sample_points(
+        dispatcher.toc(&access),
+        &collection.name,
+        sample_request,
+        params.consistency,
+        shard_selection,
+        access,
+        params.timeout(),
+        params.session_token.as_ref(),
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/score")]
+async fn score_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ScoreRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let ScoreRequest {
+        score_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let response = do_score_points(
+        dispatcher.toc(&access),
+        &collection.name,
+        score_request,
+        params.consistency,
+        shard_selection,
+        access,
+        params.session_token.as_ref(),
+    )
+    .await;
+
+    process_response(response, timing)
+}
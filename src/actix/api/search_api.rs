@@ -1,17 +1,19 @@
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::common::search_stats;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
 };
 use itertools::Itertools;
+use storage::content_manager::toc::query_limits;
 use storage::dispatcher::Dispatcher;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::process_response_with_stats;
 use crate::common::points::{
     do_core_search_points, do_search_batch_points, do_search_point_groups,
 };
@@ -29,6 +31,7 @@ async fn search_points(
     let SearchRequest {
         search_request,
         shard_key,
+        with_stats,
     } = request.into_inner();
 
     let shard_selection = match shard_key {
@@ -36,24 +39,28 @@ async fn search_points(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = do_core_search_points(
-        dispatcher.toc(&access),
-        &collection.name,
-        search_request.into(),
-        params.consistency,
-        shard_selection,
-        access,
-        params.timeout(),
-    )
-    .await
-    .map(|scored_points| {
+    let ((response, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats.unwrap_or_default(),
+        do_core_search_points(
+            dispatcher.toc(&access),
+            &collection.name,
+            search_request.into(),
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+            params.session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let response = response.map(|scored_points| {
         scored_points
             .into_iter()
             .map(api::rest::ScoredPoint::from)
             .collect_vec()
     });
 
-    process_response(response, timing)
+    process_response_with_stats(response, timing, warnings, stats)
 }
 
 #[post("/collections/{name}/points/search/batch")]
@@ -67,6 +74,10 @@ async fn batch_search_points(
     let timing = Instant::now();
 
     let request = request.into_inner();
+    let with_stats = request
+        .searches
+        .iter()
+        .any(|req| req.with_stats.unwrap_or_default());
     let requests = request
         .searches
         .into_iter()
@@ -74,6 +85,7 @@ async fn batch_search_points(
             let SearchRequest {
                 search_request,
                 shard_key,
+                with_stats: _,
             } = req;
             let shard_selection = match shard_key {
                 None => ShardSelectorInternal::All,
@@ -85,16 +97,20 @@ async fn batch_search_points(
         })
         .collect();
 
-    let response = do_search_batch_points(
-        dispatcher.toc(&access),
-        &collection.name,
-        requests,
-        params.consistency,
-        access,
-        params.timeout(),
-    )
-    .await
-    .map(|batch_scored_points| {
+    let ((response, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats,
+        do_search_batch_points(
+            dispatcher.toc(&access),
+            &collection.name,
+            requests,
+            params.consistency,
+            access,
+            params.timeout(),
+            params.session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let response = response.map(|batch_scored_points| {
         batch_scored_points
             .into_iter()
             .map(|scored_points| {
@@ -106,7 +122,7 @@ async fn batch_search_points(
             .collect_vec()
     });
 
-    process_response(response, timing)
+    process_response_with_stats(response, timing, warnings, stats)
 }
 
 #[post("/collections/{name}/points/search/groups")]
@@ -122,6 +138,7 @@ async fn search_point_groups(
     let SearchGroupsRequest {
         search_group_request,
         shard_key,
+        with_stats,
     } = request.into_inner();
 
     let shard_selection = match shard_key {
@@ -129,18 +146,22 @@ async fn search_point_groups(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = do_search_point_groups(
-        dispatcher.toc(&access),
-        &collection.name,
-        search_group_request,
-        params.consistency,
-        shard_selection,
-        access,
-        params.timeout(),
-    )
+    let ((response, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats.unwrap_or_default(),
+        do_search_point_groups(
+            dispatcher.toc(&access),
+            &collection.name,
+            search_group_request,
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+            params.session_token.as_ref(),
+        ),
+    ))
     .await;
 
-    process_response(response, timing)
+    process_response_with_stats(response, timing, warnings, stats)
 }
 
 // Configure services
@@ -1,11 +1,11 @@
 use std::future::Future;
 use std::sync::Arc;
 
-use actix_web::http::header::ContentType;
+use actix_web::http::header::{ContentDisposition, ContentType, DispositionParam, DispositionType};
 use actix_web::http::StatusCode;
 use actix_web::rt::time::Instant;
 use actix_web::web::Query;
-use actix_web::{get, post, web, HttpResponse, Responder};
+use actix_web::{delete, get, post, web, HttpResponse, Responder};
 use actix_web_validator::Json;
 use common::types::{DetailsLevel, TelemetryDetail};
 use schemars::JsonSchema;
@@ -18,6 +18,9 @@ use tokio::sync::Mutex;
 
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response_error};
+use crate::common::allocator;
+use crate::common::config_reload::ConfigReloader;
+use crate::common::diagnostics::collect_diagnostics;
 use crate::common::health;
 use crate::common::helpers::LocksOption;
 use crate::common::metrics::MetricsData;
@@ -95,6 +98,42 @@ async fn metrics(
         .body(MetricsData::from(telemetry_data).format_metrics())
 }
 
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct DiagnosticsParam {
+    pub anonymize: Option<bool>,
+}
+
+#[get("/diagnostics")]
+async fn diagnostics(
+    telemetry_collector: web::Data<Mutex<TelemetryCollector>>,
+    config_reloader: web::Data<ConfigReloader>,
+    params: Query<DiagnosticsParam>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    // Configuration and logs are only available to managers
+    if let Err(err) = access.check_global_access(AccessRequirements::new().manage()) {
+        return process_response_error(err, Instant::now());
+    }
+
+    let anonymize = params.anonymize.unwrap_or(false);
+    let bundle = {
+        let telemetry_collector = telemetry_collector.lock().await;
+        collect_diagnostics(&config_reloader, &telemetry_collector, &access, anonymize).await
+    };
+
+    let file_name = format!(
+        "qdrant-diagnostics-{}.json",
+        bundle.created_at.format("%Y-%m-%d-%H-%M-%S"),
+    );
+
+    HttpResponse::Ok()
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(file_name)],
+        })
+        .json(bundle)
+}
+
 #[post("/locks")]
 fn put_locks(
     dispatcher: web::Data<Dispatcher>,
@@ -137,6 +176,19 @@ fn get_stacktrace(ActixAccess(access): ActixAccess) -> impl Future<Output = Http
     })
 }
 
+#[post("/memory/purge")]
+fn purge_memory(ActixAccess(access): ActixAccess) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        tokio::task::spawn_blocking(allocator::purge)
+            .await?
+            .map_err(|err| {
+                StorageError::service_error(format!("Failed to purge allocator: {err}"))
+            })?;
+        Ok(allocator::stats())
+    })
+}
+
 #[get("/healthz")]
 async fn healthz() -> impl Responder {
     kubernetes_healthz().await
@@ -195,16 +247,71 @@ async fn update_logger_config(
     helpers::process_response(result, timing)
 }
 
+#[post("/logger/modules/{module}")]
+fn update_logger_module_config(
+    handle: web::Data<tracing::LoggerHandle>,
+    module: web::Path<String>,
+    config: web::Json<tracing::default::ModuleConfig>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        let module = module.into_inner();
+        let config = config.into_inner();
+        config
+            .validate(&module)
+            .map_err(StorageError::bad_request)?;
+
+        handle
+            .update_module_config(module, Some(config))
+            .await
+            .map_err(|err| StorageError::service_error(err.to_string()))?;
+        Ok(handle.get_config().await)
+    })
+}
+
+#[delete("/logger/modules/{module}")]
+fn delete_logger_module_config(
+    handle: web::Data<tracing::LoggerHandle>,
+    module: web::Path<String>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        handle
+            .update_module_config(module.into_inner(), None)
+            .await
+            .map_err(|err| StorageError::service_error(err.to_string()))?;
+        Ok(handle.get_config().await)
+    })
+}
+
+#[post("/config/reload")]
+fn reload_config(
+    config_reloader: web::Data<ConfigReloader>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        config_reloader.reload().await
+    })
+}
+
 // Configure services
 pub fn config_service_api(cfg: &mut web::ServiceConfig) {
     cfg.service(telemetry)
         .service(metrics)
+        .service(diagnostics)
         .service(put_locks)
         .service(get_locks)
         .service(get_stacktrace)
+        .service(purge_memory)
         .service(healthz)
         .service(livez)
         .service(readyz)
         .service(get_logger_config)
-        .service(update_logger_config);
+        .service(update_logger_config)
+        .service(update_logger_module_config)
+        .service(delete_logger_module_config)
+        .service(reload_config);
 }
@@ -9,7 +9,8 @@ use collection::common::file_utils::move_file;
 use collection::common::sha_256::{hash_file, hashes_equal};
 use collection::common::snapshot_stream::SnapshotStream;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
+    CreateShardKeySnapshot, ShardKeySnapshotRecover, ShardSnapshotRecover, SnapshotPriority,
+    SnapshotRecover,
 };
 use collection::shards::shard::ShardId;
 use futures::{FutureExt as _, TryFutureExt as _};
@@ -20,7 +21,7 @@ use storage::content_manager::errors::StorageError;
 use storage::content_manager::snapshots::recover::do_recover_from_snapshot;
 use storage::content_manager::snapshots::{
     do_create_full_snapshot, do_delete_collection_snapshot, do_delete_full_snapshot,
-    do_list_full_snapshots,
+    do_list_full_snapshots, do_mount_collection_snapshot, do_unmount_collection_snapshot,
 };
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
@@ -58,6 +59,13 @@ pub struct SnapshottingParam {
     pub wait: Option<bool>,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct SnapshotMountingParam {
+    pub wait: Option<bool>,
+    /// If true, only load the snapshot while it is queried
+    pub lazy: Option<bool>,
+}
+
 #[derive(MultipartForm)]
 pub struct SnapshottingForm {
     snapshot: TempFile,
@@ -325,6 +333,45 @@ async fn delete_collection_snapshot(
     .await
 }
 
+#[put("/collections/{name}/snapshots/{snapshot_name}/mount")]
+async fn mount_collection_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, String)>,
+    params: valid::Query<SnapshotMountingParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
+        let (collection_name, snapshot_name) = path.into_inner();
+        do_mount_collection_snapshot(
+            dispatcher.get_ref(),
+            access,
+            &collection_name,
+            &snapshot_name,
+            params.lazy.unwrap_or(false),
+        )
+    })
+    .await
+}
+
+#[delete("/collections/{name}/snapshots/{snapshot_name}/mount")]
+async fn unmount_collection_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, String)>,
+    params: valid::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
+        let (collection_name, snapshot_name) = path.into_inner();
+        do_unmount_collection_snapshot(
+            dispatcher.get_ref(),
+            access,
+            &collection_name,
+            &snapshot_name,
+        )
+    })
+    .await
+}
+
 #[get("/collections/{collection}/shards/{shard}/snapshots")]
 async fn list_shard_snapshots(
     dispatcher: web::Data<Dispatcher>,
@@ -505,6 +552,52 @@ async fn delete_shard_snapshot(
     helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
 }
 
+#[post("/collections/{collection}/shards/snapshots")]
+async fn create_shard_key_snapshots(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<String>,
+    query: web::Query<SnapshottingParam>,
+    request: valid::Json<CreateShardKeySnapshot>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let collection = path.into_inner();
+    let future = common::snapshots::create_shard_key_snapshots(
+        dispatcher.toc(&access).clone(),
+        access,
+        collection,
+        request.into_inner(),
+    );
+
+    helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
+}
+
+#[put("/collections/{collection}/shards/snapshots/recover")]
+async fn recover_shard_key_snapshots(
+    dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
+    path: web::Path<String>,
+    query: web::Query<SnapshottingParam>,
+    request: valid::Json<ShardKeySnapshotRecover>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let future = async move {
+        let collection = path.into_inner();
+
+        common::snapshots::recover_shard_key_snapshots(
+            dispatcher.toc(&access).clone(),
+            access,
+            collection,
+            request.into_inner(),
+            http_client.as_ref().clone(),
+        )
+        .await?;
+
+        Ok(true)
+    };
+
+    helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
+}
+
 // Configure services
 pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
     cfg.service(list_snapshots)
@@ -517,6 +610,10 @@ pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
         .service(get_full_snapshot)
         .service(delete_full_snapshot)
         .service(delete_collection_snapshot)
+        .service(mount_collection_snapshot)
+        .service(unmount_collection_snapshot)
+        .service(create_shard_key_snapshots)
+        .service(recover_shard_key_snapshots)
         .service(list_shard_snapshots)
         .service(create_shard_snapshot)
         .service(recover_shard_snapshot)
@@ -1,21 +1,26 @@
+use actix_multipart::form::tempfile::TempFile;
+use actix_multipart::form::MultipartForm;
 use actix_web::rt::time::Instant;
-use actix_web::{delete, post, put, web, Responder};
+use actix_web::{delete, get, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::common::external_index::ExternalIndexFormat;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
 use segment::json_path::{JsonPath, JsonPathInterface};
 use serde::{Deserialize, Serialize};
+use storage::content_manager::toc::duplicates::FindDuplicates;
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::{self, process_response};
 use crate::common::points::{
     do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
+    do_delete_points_with_report, do_delete_vectors, do_find_duplicate_points, do_get_index_stats,
+    do_get_index_status, do_import_points, do_overwrite_payload, do_set_payload, do_update_vectors,
     do_upsert_points, CreateFieldIndex, UpdateOperations,
 };
 
@@ -59,25 +64,77 @@ async fn upsert_points(
     process_response(response, timing)
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ImportParam {
+    pub format: ExternalIndexFormat,
+    /// Name of the vector to import the vectors of the index into. Default vector if not set.
+    pub vector_name: Option<String>,
+    pub wait: Option<bool>,
+    pub ordering: Option<WriteOrdering>,
+}
+
+#[derive(MultipartForm)]
+pub struct ImportForm {
+    index: TempFile,
+}
+
+#[post("/collections/{name}/points/import")]
+async fn import_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    MultipartForm(form): MultipartForm<ImportForm>,
+    params: Query<ImportParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let ImportParam {
+        format,
+        vector_name,
+        wait,
+        ordering,
+    } = params.into_inner();
+
+    let response = do_import_points(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        form.index.file.path(),
+        format,
+        vector_name,
+        wait.unwrap_or(false),
+        ordering.unwrap_or_default(),
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct DeleteParam {
+    pub wait: Option<bool>,
+    pub ordering: Option<WriteOrdering>,
+    /// Report the outcome of the deletion of each point. Requires `wait`.
+    pub report: Option<bool>,
+}
+
 #[post("/collections/{name}/points/delete")]
 async fn delete_points(
     dispatcher: web::Data<Dispatcher>,
     collection: Path<CollectionPath>,
     operation: Json<PointsSelector>,
-    params: Query<UpdateParam>,
+    params: Query<DeleteParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let report = params.report.unwrap_or(false);
 
-    let response = do_delete_points(
+    let response = do_delete_points_with_report(
         dispatcher.toc(&access).clone(),
         collection.into_inner().name,
         operation,
-        None,
-        None,
+        report,
         wait,
         ordering,
         access,
@@ -327,9 +384,72 @@ async fn delete_field_index(
     process_response(response, timing)
 }
 
+#[get("/collections/{name}/index/{field_name}")]
+async fn get_field_index(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    field: Path<FieldPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = do_get_index_status(
+        dispatcher.toc(&access),
+        &collection.name,
+        &field.name,
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/index/{field_name}/stats")]
+async fn get_field_index_stats(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    field: Path<FieldPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = do_get_index_stats(
+        dispatcher.toc(&access),
+        &collection.name,
+        &field.name,
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[derive(Deserialize, Validate)]
+struct FindDuplicatesParam {
+    wait: Option<bool>,
+}
+
+#[post("/collections/{name}/points/duplicates")]
+async fn find_duplicate_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    Query(params): Query<FindDuplicatesParam>,
+    Json(request): Json<FindDuplicates>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
+        do_find_duplicate_points(
+            dispatcher.toc(&access).clone(),
+            collection.into_inner().name,
+            request,
+            access,
+        )
+    })
+    .await
+}
+
 // Configure services
 pub fn config_update_api(cfg: &mut web::ServiceConfig) {
     cfg.service(upsert_points)
+        .service(import_points)
         .service(delete_points)
         .service(update_vectors)
         .service(delete_vectors)
@@ -339,5 +459,8 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(clear_payload)
         .service(create_field_index)
         .service(delete_field_index)
+        .service(get_field_index)
+        .service(get_field_index_stats)
+        .service(find_duplicate_points)
         .service(update_batch);
 }
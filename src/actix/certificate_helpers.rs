@@ -12,6 +12,8 @@ use rustls::sign::CertifiedKey;
 use rustls::{crypto, RootCertStore, ServerConfig};
 use rustls_pemfile::Item;
 
+#[cfg(feature = "grpc-http3")]
+use crate::settings::GrpcHttp3Config;
 use crate::settings::{Settings, TlsConfig};
 
 type Result<T> = std::result::Result<T, Error>;
@@ -141,13 +143,48 @@ fn load_certified_key(tls_config: &TlsConfig) -> Result<Arc<CertifiedKey>> {
 ///
 /// Uses TLS settings as configured in configuration by user.
 pub fn actix_tls_server_config(settings: &Settings) -> Result<ServerConfig> {
-    let config = ServerConfig::builder();
     let tls_config = settings
         .tls
         .clone()
         .ok_or_else(Settings::tls_config_is_undefined_error)
         .map_err(Error::Io)?;
 
+    tls_server_config(settings, tls_config)
+}
+
+/// Generate a gRPC over HTTP/3 server configuration with TLS
+///
+/// Uses TLS settings as configured in configuration by user, with the certificate and key of the
+/// HTTP/3 configuration if set.
+#[cfg(feature = "grpc-http3")]
+pub fn grpc_http3_tls_server_config(
+    settings: &Settings,
+    http3_config: &GrpcHttp3Config,
+) -> Result<ServerConfig> {
+    let tls_config = match (&settings.tls, &http3_config.cert, &http3_config.key) {
+        (Some(tls_config), cert, key) => TlsConfig {
+            cert: cert.clone().unwrap_or_else(|| tls_config.cert.clone()),
+            key: key.clone().unwrap_or_else(|| tls_config.key.clone()),
+            ..tls_config.clone()
+        },
+        // Without a TLS config, there is no CA to verify client certificates against
+        (None, Some(cert), Some(key)) if !settings.service.verify_https_client_certificate => {
+            TlsConfig {
+                cert: cert.clone(),
+                key: key.clone(),
+                ca_cert: String::new(),
+                cert_ttl: None,
+            }
+        }
+        (None, _, _) => return Err(Error::Io(Settings::tls_config_is_undefined_error())),
+    };
+
+    tls_server_config(settings, tls_config)
+}
+
+fn tls_server_config(settings: &Settings, tls_config: TlsConfig) -> Result<ServerConfig> {
+    let config = ServerConfig::builder();
+
     // Verify client CA or not
     let config = if settings.service.verify_https_client_certificate {
         let mut root_cert_store = RootCertStore::empty();
@@ -5,8 +5,10 @@ use actix_web::rt::time::Instant;
 use actix_web::{http, HttpResponse, ResponseError};
 use api::grpc::models::{ApiResponse, ApiStatus};
 use collection::operations::types::CollectionError;
+use segment::types::QueryStats;
 use serde::Serialize;
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::query_limits;
 use tokio::task::JoinHandle;
 
 pub fn accepted_response(timing: Instant) -> HttpResponse {
@@ -14,10 +16,37 @@ pub fn accepted_response(timing: Instant) -> HttpResponse {
         result: None,
         status: ApiStatus::Accepted,
         time: timing.elapsed().as_secs_f64(),
+        warnings: vec![],
+        stats: None,
     })
 }
 
 pub fn process_response<D>(response: Result<D, StorageError>, timing: Instant) -> HttpResponse
+where
+    D: Serialize,
+{
+    process_response_with_warnings(response, timing, vec![])
+}
+
+/// Same as `process_response`, but also returns the warnings in the body of a successful response
+pub fn process_response_with_warnings<D>(
+    response: Result<D, StorageError>,
+    timing: Instant,
+    warnings: Vec<String>,
+) -> HttpResponse
+where
+    D: Serialize,
+{
+    process_response_with_stats(response, timing, warnings, None)
+}
+
+/// Same as `process_response_with_warnings`, but also returns the work done to execute the request
+pub fn process_response_with_stats<D>(
+    response: Result<D, StorageError>,
+    timing: Instant,
+    warnings: Vec<String>,
+    stats: Option<QueryStats>,
+) -> HttpResponse
 where
     D: Serialize,
 {
@@ -26,6 +55,8 @@ where
             result: Some(res),
             status: ApiStatus::Ok,
             time: timing.elapsed().as_secs_f64(),
+            warnings,
+            stats,
         }),
         Err(err) => process_response_error(err, timing),
     }
@@ -43,12 +74,15 @@ pub fn process_response_error(err: StorageError, timing: Instant) -> HttpRespons
         }
     }
 
+    let code = err.code();
     let error: HttpError = err.into();
 
     HttpResponse::build(error.status_code()).json(ApiResponse::<()> {
         result: None,
-        status: ApiStatus::Error(error.to_string()),
+        status: ApiStatus::error(error.to_string(), code),
         time: timing.elapsed().as_secs_f64(),
+        warnings: vec![],
+        stats: None,
     })
 }
 
@@ -133,8 +167,9 @@ where
     T: serde::Serialize,
 {
     let instant = Instant::now();
-    match future.await.transpose() {
-        Some(v) => process_response(v, instant),
+    let (result, warnings) = query_limits::collect_warnings(future).await;
+    match result.transpose() {
+        Some(v) => process_response_with_warnings(v, instant, warnings),
         None => accepted_response(instant),
     }
 }
@@ -158,6 +193,9 @@ impl ResponseError for HttpError {
             StorageError::ChecksumMismatch { .. } => http::StatusCode::BAD_REQUEST,
             StorageError::Forbidden { .. } => http::StatusCode::FORBIDDEN,
             StorageError::PreconditionFailed { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
+            StorageError::VectorDimensionMismatch { .. } => http::StatusCode::BAD_REQUEST,
+            StorageError::PayloadIndexMissing { .. } => http::StatusCode::BAD_REQUEST,
+            StorageError::ShardUnavailable { .. } => http::StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 }
@@ -2,7 +2,7 @@
 pub mod actix_telemetry;
 pub mod api;
 mod auth;
-mod certificate_helpers;
+pub mod certificate_helpers;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod helpers;
 pub mod web_ui;
@@ -10,7 +10,7 @@ pub mod web_ui;
 use std::io;
 use std::sync::Arc;
 
-use ::api::grpc::models::{ApiResponse, ApiStatus, VersionInfo};
+use ::api::grpc::models::{ApiResponse, ApiStatus, ErrorCode, VersionInfo};
 use actix_cors::Cors;
 use actix_multipart::form::tempfile::TempFileConfig;
 use actix_multipart::form::MultipartFormConfig;
@@ -29,7 +29,10 @@ use crate::actix::api::discovery_api::config_discovery_api;
 use crate::actix::api::issues_api::config_issues_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
-use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::retrieve_api::{
+    get_point, get_point_hnsw_neighborhood, get_points, point_changes, sample_points, score_points,
+    scroll_points,
+};
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
 use crate::actix::api::shards_api::config_shards_api;
@@ -38,6 +41,7 @@ use crate::actix::api::update_api::config_update_api;
 use crate::actix::auth::{Auth, WhitelistItem};
 use crate::actix::web_ui::{web_ui_factory, web_ui_folder, WEB_UI_PATH};
 use crate::common::auth::AuthKeys;
+use crate::common::config_reload::ConfigReloader;
 use crate::common::debugger::DebuggerState;
 use crate::common::health;
 use crate::common::http_client::HttpClient;
@@ -57,6 +61,7 @@ pub fn init(
     health_checker: Option<Arc<health::HealthChecker>>,
     settings: Settings,
     logger_handle: LoggerHandle,
+    config_reloader: Arc<ConfigReloader>,
 ) -> io::Result<()> {
     actix_web::rt::System::new().block_on(async {
         let auth_keys = AuthKeys::try_create(
@@ -76,6 +81,7 @@ pub fn init(
         let debugger_state = web::Data::new(DebuggerState::from_settings(&settings));
         let telemetry_collector_data = web::Data::from(telemetry_collector);
         let logger_handle_data = web::Data::new(logger_handle);
+        let config_reloader = web::Data::from(config_reloader);
         let http_client = web::Data::new(HttpClient::from_settings(&settings)?);
         let health_checker = web::Data::new(health_checker);
         let web_ui_available = web_ui_folder(&settings);
@@ -127,6 +133,7 @@ pub fn init(
                 .app_data(dispatcher_data.clone())
                 .app_data(telemetry_collector_data.clone())
                 .app_data(logger_handle_data.clone())
+                .app_data(config_reloader.clone())
                 .app_data(http_client.clone())
                 .app_data(debugger_state.clone())
                 .app_data(health_checker.clone())
@@ -151,7 +158,11 @@ pub fn init(
                 // Ordering of services is important for correct path pattern matching
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
+                .service(point_changes)
+                .service(sample_points)
+                .service(score_points)
                 .service(count_points)
+                .service(get_point_hnsw_neighborhood)
                 .service(get_point)
                 .service(get_points);
 
@@ -188,6 +199,25 @@ pub fn init(
         };
 
         log::info!("Qdrant HTTP listening on {}", port);
+
+        if let Some(path) = &settings.service.http_unix_socket {
+            #[cfg(unix)]
+            {
+                let listener = crate::common::helpers::bind_unix_socket(
+                    path,
+                    settings.service.unix_socket_permissions.as_deref(),
+                )?;
+                server = server.listen_uds(listener)?;
+                log::info!("Qdrant HTTP listening on Unix socket {}", path.display());
+            }
+
+            #[cfg(not(unix))]
+            log::warn!(
+                "Unix sockets are not supported on this platform, not listening on {}",
+                path.display(),
+            );
+        }
+
         server.run().await
     })
 }
@@ -229,8 +259,10 @@ fn validation_error_handler(
     }
     .json(ApiResponse::<()> {
         result: None,
-        status: ApiStatus::Error(msg),
+        status: ApiStatus::error(msg, ErrorCode::BadRequest),
         time: 0.0,
+        warnings: vec![],
+        stats: None,
     });
     error::InternalError::from_response(err, response).into()
 }
@@ -0,0 +1,170 @@
+//! Statistics and tuning of the jemalloc memory allocator.
+//!
+//! On platforms where jemalloc is not used, statistics are not available, and tuning is a no-op.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Trade-off between memory returned to the OS, and throughput of allocations
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocatorProfile {
+    /// Defaults of the allocator, unused memory is returned to the OS within about 10 seconds
+    #[default]
+    Balanced,
+    /// Return unused memory to the OS right away, at the cost of slower allocations
+    LowMemory,
+    /// Keep unused memory for reuse for up to a minute, at the cost of higher resident memory
+    Throughput,
+}
+
+impl AllocatorProfile {
+    /// Time in milliseconds, after which unused pages are returned to the OS, if not the default
+    fn decay_ms(self) -> Option<isize> {
+        match self {
+            AllocatorProfile::Balanced => None,
+            AllocatorProfile::LowMemory => Some(0),
+            AllocatorProfile::Throughput => Some(60_000),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct AllocatorConfig {
+    #[serde(default)]
+    pub profile: AllocatorProfile,
+}
+
+/// Memory statistics of the allocator
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AllocatorStats {
+    /// Total number of bytes allocated by the application
+    pub allocated_bytes: usize,
+    /// Total number of bytes in active pages allocated by the application
+    pub active_bytes: usize,
+    /// Total number of bytes dedicated to allocator metadata
+    pub metadata_bytes: usize,
+    /// Number of bytes in physically resident pages mapped by the allocator
+    pub resident_bytes: usize,
+    /// Total number of bytes in active extents mapped by the allocator
+    pub mapped_bytes: usize,
+    /// Total number of bytes in virtual memory mappings, which were retained instead of returned
+    /// to the OS
+    pub retained_bytes: usize,
+}
+
+#[cfg(all(
+    not(target_env = "msvc"),
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod jemalloc {
+    use std::ffi::CString;
+    use std::{io, ptr};
+
+    use tikv_jemalloc_ctl::{arenas, background_thread, epoch, raw, stats};
+
+    use super::{AllocatorProfile, AllocatorStats};
+
+    /// Arena index, which addresses all arenas in `arena.<i>.*` controls
+    const MALLCTL_ARENAS_ALL: usize = 4096;
+
+    pub fn apply_profile(profile: AllocatorProfile) -> anyhow::Result<()> {
+        let Some(decay_ms) = profile.decay_ms() else {
+            return Ok(());
+        };
+
+        // Defaults of arenas created later
+        unsafe {
+            raw::write(b"arenas.dirty_decay_ms\0", decay_ms)?;
+            raw::write(b"arenas.muzzy_decay_ms\0", decay_ms)?;
+        }
+
+        for arena in 0..arenas::narenas::read()? {
+            // Arenas which are not initialized yet fail, and will use the defaults above
+            let dirty_decay_ms = format!("arena.{arena}.dirty_decay_ms\0");
+            let muzzy_decay_ms = format!("arena.{arena}.muzzy_decay_ms\0");
+            unsafe {
+                let _ = raw::write(dirty_decay_ms.as_bytes(), decay_ms);
+                let _ = raw::write(muzzy_decay_ms.as_bytes(), decay_ms);
+            }
+        }
+
+        // Return memory to the OS in background threads, instead of on allocations
+        background_thread::write(true)?;
+
+        Ok(())
+    }
+
+    pub fn stats() -> anyhow::Result<AllocatorStats> {
+        // Statistics are cached, until the epoch is advanced
+        epoch::advance()?;
+
+        Ok(AllocatorStats {
+            allocated_bytes: stats::allocated::read()?,
+            active_bytes: stats::active::read()?,
+            metadata_bytes: stats::metadata::read()?,
+            resident_bytes: stats::resident::read()?,
+            mapped_bytes: stats::mapped::read()?,
+            retained_bytes: stats::retained::read()?,
+        })
+    }
+
+    pub fn purge() -> anyhow::Result<()> {
+        let name = CString::new(format!("arena.{MALLCTL_ARENAS_ALL}.purge"))?;
+
+        // Purging neither reads nor writes a value, which the wrappers of `mallctl` always do
+        let code = unsafe {
+            tikv_jemalloc_sys::mallctl(
+                name.as_ptr(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                0,
+            )
+        };
+
+        if code != 0 {
+            return Err(io::Error::from_raw_os_error(code).into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(all(
+    not(target_env = "msvc"),
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+mod jemalloc {
+    use super::{AllocatorProfile, AllocatorStats};
+
+    pub fn apply_profile(_profile: AllocatorProfile) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    pub fn stats() -> anyhow::Result<AllocatorStats> {
+        anyhow::bail!("Allocator statistics are not available on this platform")
+    }
+
+    pub fn purge() -> anyhow::Result<()> {
+        anyhow::bail!("Purging the allocator is not available on this platform")
+    }
+}
+
+/// Tune the allocator according to the profile
+pub fn apply_profile(profile: AllocatorProfile) {
+    if let Err(err) = jemalloc::apply_profile(profile) {
+        log::warn!("Failed to apply allocator profile {profile:?}: {err}");
+    }
+}
+
+/// Current memory statistics of the allocator, if available
+pub fn stats() -> Option<AllocatorStats> {
+    jemalloc::stats()
+        .map_err(|err| log::debug!("Failed to read allocator statistics: {err}"))
+        .ok()
+}
+
+/// Return all unused memory of the allocator to the OS, e.g. after large deletions
+pub fn purge() -> anyhow::Result<()> {
+    jemalloc::purge()
+}
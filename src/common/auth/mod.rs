@@ -128,6 +128,7 @@ impl AuthKeys {
                 None,
                 ShardSelectorInternal::All,
                 Access::full("JWT stateful validation"),
+                None,
             )
             .await
             .map_err(|e| match e {
@@ -8,10 +8,12 @@ use collection::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
     ReplicateShardOperation, RestartTransfer, RestartTransferOperation, StartResharding,
 };
+use collection::operations::point_ops::WriteOrdering;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
+    AliasDescription, AliasHistoryResponse, CollectionClusterInfo, CollectionInfo,
+    CollectionsAliasesResponse, SegmentUpgradeProgress, SegmentUpgradeRequest, ShardWalCompaction,
 };
 use collection::shards::replica_set;
 use collection::shards::resharding::ReshardKey;
@@ -21,15 +23,25 @@ use itertools::Itertools;
 use rand::prelude::SliceRandom;
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
-    CollectionMetaOperations, CreateShardKey, DropShardKey, ReshardingOperation,
-    ShardTransferOperations, UpdateCollectionOperation,
+    AliasOperations, ChangeAliasesOperation, CollectionMetaOperations, CreateAlias,
+    CreateCollection, CreateCollectionOperation, CreateShardKey, DeleteAlias,
+    DeleteCollectionOperation, DropShardKey, ReshardingOperation, ShardTransferOperations,
+    StandbyConfig, StandbyUpdate, UpdateCollectionOperation, UpdateStandby,
 };
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::alias_versions::{
+    alias_version_name, AliasVersion, CreateAliasVersion,
+};
+use storage::content_manager::toc::migration::{MigrateCollection, MigrationReport};
+use storage::content_manager::toc::mirror::MirrorStatus;
+use storage::content_manager::toc::standby::{CollectionStandby, StandbyStatus};
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::{Access, AccessRequirements};
 use tokio::task::JoinHandle;
 
+use crate::common::points::copy_collection_points;
+
 pub async fn do_collection_exists(
     toc: &TableOfContent,
     access: Access,
@@ -149,6 +161,176 @@ pub async fn do_list_aliases(
     Ok(CollectionsAliasesResponse { aliases })
 }
 
+pub async fn do_get_alias_history(
+    toc: &TableOfContent,
+    access: Access,
+    alias_name: &str,
+) -> Result<AliasHistoryResponse, StorageError> {
+    toc.alias_history(alias_name, &access).await
+}
+
+/// Create the next version of the collection an alias points to, named `<alias>-v<N>`, and
+/// switch the alias to it. The new version is deleted if it can't be filled or the alias can't be
+/// switched.
+pub async fn do_create_alias_version(
+    dispatcher: Arc<Dispatcher>,
+    access: Access,
+    alias_name: String,
+    request: CreateAliasVersion,
+) -> Result<AliasVersion, StorageError> {
+    // Versions are new collections, which the alias is switched to
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    let toc = dispatcher.toc(&access).clone();
+    let CreateAliasVersion {
+        config,
+        copy_points,
+    } = request;
+    let copy_points = copy_points.unwrap_or(false);
+
+    if config
+        .as_ref()
+        .is_some_and(|config| config.init_from.is_some())
+    {
+        return Err(StorageError::bad_request(
+            "`init_from` is not supported for versions of an alias, use `copy_points` instead",
+        ));
+    }
+
+    let previous_collection_name = toc.alias_collection(&alias_name).await;
+    let previous_state = match &previous_collection_name {
+        Some(previous_name) => {
+            let previous_pass =
+                access.check_collection_access(previous_name, AccessRequirements::new())?;
+            Some(toc.get_collection(&previous_pass).await?.state().await)
+        }
+        None => {
+            let alias_pass =
+                access.check_collection_access(&alias_name, AccessRequirements::new())?;
+            if toc.get_collection(&alias_pass).await.is_ok() {
+                return Err(StorageError::bad_request(format!(
+                    "{alias_name} is a collection, not an alias",
+                )));
+            }
+            None
+        }
+    };
+
+    let payload_index_schema = match (&previous_state, copy_points) {
+        (Some(state), true) => {
+            if state.config.params.sharding_method.unwrap_or_default() == ShardingMethod::Custom
+                || config.as_ref().and_then(|config| config.sharding_method)
+                    == Some(ShardingMethod::Custom)
+            {
+                return Err(StorageError::bad_request(
+                    "Copying points of collections with custom sharding is not supported",
+                ));
+            }
+            Some(state.payload_index_schema.schema.clone())
+        }
+        (None, true) => {
+            return Err(StorageError::bad_request(format!(
+                "Alias {alias_name} doesn't exist, there are no points to copy",
+            )));
+        }
+        (_, false) => None,
+    };
+
+    let config = match (config, previous_state) {
+        (Some(config), _) => config,
+        (None, Some(state)) => CreateCollection::from(state.config),
+        (None, None) => {
+            return Err(StorageError::bad_request(format!(
+                "Alias {alias_name} doesn't exist, the configuration of its first version is required",
+            )));
+        }
+    };
+
+    let version = toc.next_alias_version(&alias_name).await;
+    let collection_name = alias_version_name(&alias_name, version);
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                collection_name.clone(),
+                config,
+            )),
+            access.clone(),
+            None,
+        )
+        .await?;
+
+    let switched = async {
+        if let (Some(previous_name), Some(payload_index_schema)) =
+            (&previous_collection_name, payload_index_schema)
+        {
+            copy_collection_points(
+                &dispatcher,
+                previous_name,
+                &collection_name,
+                payload_index_schema,
+                WriteOrdering::default(),
+                &access,
+            )
+            .await?;
+        }
+
+        // Switch the alias at once
+        let mut actions: Vec<AliasOperations> = Vec::new();
+        if previous_collection_name.is_some() {
+            actions.push(
+                DeleteAlias {
+                    alias_name: alias_name.clone(),
+                }
+                .into(),
+            );
+        }
+        actions.push(
+            CreateAlias {
+                collection_name: collection_name.clone(),
+                alias_name: alias_name.clone(),
+            }
+            .into(),
+        );
+        dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::ChangeAliases(ChangeAliasesOperation {
+                    actions,
+                    changed_at: None,
+                }),
+                access.clone(),
+                None,
+            )
+            .await
+    }
+    .await;
+
+    if let Err(err) = switched {
+        if let Err(drop_err) = dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation(
+                    collection_name.clone(),
+                )),
+                access.clone(),
+                None,
+            )
+            .await
+        {
+            log::warn!(
+                "Failed to delete collection {collection_name} of a failed version of alias \
+                 {alias_name}: {drop_err}"
+            );
+        }
+        return Err(err);
+    }
+
+    Ok(AliasVersion {
+        alias_name,
+        collection_name,
+        previous_collection_name,
+        version,
+    })
+}
+
 pub async fn do_list_snapshots(
     toc: &TableOfContent,
     access: Access,
@@ -176,6 +358,167 @@ pub fn do_create_snapshot(
     }))
 }
 
+pub fn do_compact_collection_wal(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: &str,
+) -> Result<JoinHandle<Result<Vec<ShardWalCompaction>, StorageError>>, StorageError> {
+    let collection_pass = access
+        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?
+        .into_static();
+    Ok(tokio::spawn(async move {
+        let collection = toc.get_collection(&collection_pass).await?;
+        Ok(collection.compact_wal().await?)
+    }))
+}
+
+pub async fn do_start_segment_upgrade(
+    toc: &TableOfContent,
+    access: Access,
+    collection_name: &str,
+    request: SegmentUpgradeRequest,
+) -> Result<SegmentUpgradeProgress, StorageError> {
+    let collection_pass = access
+        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.start_segment_upgrade(request).await?)
+}
+
+pub async fn do_get_segment_upgrade(
+    toc: &TableOfContent,
+    access: Access,
+    collection_name: &str,
+) -> Result<SegmentUpgradeProgress, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.segment_upgrade_progress())
+}
+
+pub async fn do_stop_segment_upgrade(
+    toc: &TableOfContent,
+    access: Access,
+    collection_name: &str,
+) -> Result<SegmentUpgradeProgress, StorageError> {
+    let collection_pass = access
+        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.stop_segment_upgrade())
+}
+
+pub async fn do_get_collection_mirror(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<MirrorStatus, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    toc.mirror_status(&collection_pass).await
+}
+
+pub fn do_resync_collection_mirror(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    name: &str,
+) -> Result<JoinHandle<Result<MirrorStatus, StorageError>>, StorageError> {
+    let collection_pass = access
+        .check_collection_access(name, AccessRequirements::new().write().whole())?
+        .into_static();
+    Ok(tokio::spawn(async move {
+        toc.resync_mirror(&collection_pass).await
+    }))
+}
+
+pub async fn do_get_collection_standby(
+    dispatcher: &Dispatcher,
+    access: Access,
+    name: &str,
+) -> Result<StandbyStatus, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let toc = dispatcher.toc(&access);
+    toc.standby_status(&collection_pass, standby_follower(dispatcher, toc))
+        .await
+}
+
+pub async fn do_update_collection_standby(
+    dispatcher: &Dispatcher,
+    access: Access,
+    name: &str,
+    config: StandbyConfig,
+    wait_timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    let collection_pass = access
+        .check_global_access(AccessRequirements::new().manage())?
+        .issue_pass(name);
+
+    // Fail early on an invalid URL, instead of on the leader
+    CollectionStandby::new(name, &config)?;
+    let collection_name = dispatcher
+        .toc(&access)
+        .resolve_standby_name(&collection_pass)
+        .await;
+
+    let operation = CollectionMetaOperations::UpdateStandby(UpdateStandby {
+        collection_name,
+        update: StandbyUpdate::Follow(config),
+    });
+    dispatcher
+        .submit_collection_meta_op(operation, access, wait_timeout)
+        .await
+}
+
+pub async fn do_promote_collection_standby(
+    dispatcher: &Dispatcher,
+    access: Access,
+    name: &str,
+    wait_timeout: Option<Duration>,
+) -> Result<StandbyStatus, StorageError> {
+    // Promotion is a failover of the cluster, not a regular write
+    let collection_pass = access
+        .check_global_access(AccessRequirements::new().manage())?
+        .issue_pass(name);
+    let toc = dispatcher.toc(&access);
+    let collection_name = toc.resolve_standby_name(&collection_pass).await;
+
+    let operation = CollectionMetaOperations::UpdateStandby(UpdateStandby {
+        collection_name,
+        update: StandbyUpdate::Promote,
+    });
+    dispatcher
+        .submit_collection_meta_op(operation, access.clone(), wait_timeout)
+        .await?;
+
+    // A synchronization running on this peer stops after the current page
+    toc.await_standby_sync(&collection_pass).await;
+    toc.standby_status(&collection_pass, standby_follower(dispatcher, toc))
+        .await
+}
+
+/// Peer, which follows the primaries of standby collections
+fn standby_follower(dispatcher: &Dispatcher, toc: &TableOfContent) -> Option<PeerId> {
+    match dispatcher.consensus_state() {
+        Some(state) => state.leader_id(),
+        None => Some(toc.this_peer_id),
+    }
+}
+
+pub fn do_migrate_collection(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    name: &str,
+    request: MigrateCollection,
+) -> Result<JoinHandle<Result<MigrationReport, StorageError>>, StorageError> {
+    // Migration copies all data of the collection out of this cluster
+    let collection_pass = access
+        .check_global_access(AccessRequirements::new().manage())?
+        .issue_pass(name)
+        .into_static();
+    Ok(tokio::spawn(async move {
+        toc.migrate_collection(&collection_pass, request).await
+    }))
+}
+
 pub async fn do_get_collection_cluster(
     toc: &TableOfContent,
     access: Access,
@@ -0,0 +1,279 @@
+//! Reload of the configuration without restarting the peer.
+//!
+//! The configuration is read again from the same sources as on startup, on SIGHUP or by the API.
+//! Changes of a small set of runtime settings are applied right away, changes of all other
+//! settings are reported as requiring a restart. This includes the snapshot paths, as collections
+//! keep the paths they were loaded with.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use segment::problems::UnindexedField;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use tokio::sync::Mutex;
+use validator::Validate;
+
+use crate::settings::Settings;
+use crate::tracing::LoggerHandle;
+
+/// Settings applied on reload, including all settings nested in them
+const RELOADABLE_SETTINGS: &[&str] = &[
+    "log_level",
+    "logger",
+    "storage.performance.update_rate_limit",
+    "service.slow_query_secs",
+];
+
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct ConfigReloadReport {
+    /// Changed settings, which were applied
+    pub applied: Vec<String>,
+    /// Changed settings, which only take effect after a restart of the peer
+    pub requires_restart: Vec<String>,
+}
+
+pub struct ConfigReloader {
+    config_path: Option<String>,
+    /// Running configuration, by path of each setting
+    running: Mutex<BTreeMap<String, Value>>,
+    logger_handle: LoggerHandle,
+    toc: Arc<TableOfContent>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        config_path: Option<String>,
+        settings: &Settings,
+        logger_handle: LoggerHandle,
+        toc: Arc<TableOfContent>,
+    ) -> Self {
+        Self {
+            config_path,
+            running: Mutex::new(flatten(&settings.raw_config)),
+            logger_handle,
+            toc,
+        }
+    }
+
+    /// Configuration the peer is running with, as read on startup and changed by reloads
+    pub async fn running_config(&self) -> Value {
+        unflatten(&*self.running.lock().await)
+    }
+
+    /// Read the configuration again, and apply the changed settings which don't require a restart
+    pub async fn reload(&self) -> Result<ConfigReloadReport, StorageError> {
+        let mut running = self.running.lock().await;
+
+        let settings = Settings::new(self.config_path.clone()).map_err(|err| {
+            StorageError::bad_input(format!("Failed to load configuration: {err}"))
+        })?;
+        settings
+            .validate()
+            .map_err(|err| StorageError::bad_input(format!("Invalid configuration: {err}")))?;
+
+        let config = flatten(&settings.raw_config);
+        let (applied, requires_restart): (Vec<_>, Vec<_>) = changed_settings(&running, &config)
+            .into_iter()
+            .partition(|path| is_reloadable(path));
+
+        let is_changed = |setting: &str| applied.iter().any(|path| is_nested(path, setting));
+
+        // Apply the only fallible change first, to not apply the configuration partially
+        if is_changed("log_level") || is_changed("logger") {
+            let logger_config = settings
+                .logger
+                .with_top_level_directive(settings.log_level.clone());
+            self.logger_handle
+                .update_config(logger_config)
+                .await
+                .map_err(|err| {
+                    StorageError::service_error(format!("Failed to reconfigure logger: {err}"))
+                })?;
+        }
+
+        if is_changed("storage.performance.update_rate_limit") {
+            self.toc
+                .set_update_rate_limit(settings.storage.performance.update_rate_limit);
+        }
+
+        if is_changed("service.slow_query_secs") {
+            UnindexedField::set_slow_query_threshold(
+                settings
+                    .service
+                    .slow_query_secs
+                    .map(Duration::from_secs_f32),
+            );
+        }
+
+        // Changes which require a restart are reported again on the next reload
+        for path in &applied {
+            match config.get(path) {
+                Some(value) => running.insert(path.clone(), value.clone()),
+                None => running.remove(path),
+            };
+        }
+
+        if !applied.is_empty() {
+            log::info!("Applied reloaded settings: {}", applied.join(", "));
+        }
+        if !requires_restart.is_empty() {
+            log::warn!(
+                "Reloaded settings require a restart to take effect: {}",
+                requires_restart.join(", "),
+            );
+        }
+
+        Ok(ConfigReloadReport {
+            applied,
+            requires_restart,
+        })
+    }
+}
+
+/// Whether the setting at `path` is `setting`, or nested in it
+fn is_nested(path: &str, setting: &str) -> bool {
+    path.strip_prefix(setting)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+fn is_reloadable(path: &str) -> bool {
+    RELOADABLE_SETTINGS
+        .iter()
+        .any(|setting| is_nested(path, setting))
+}
+
+/// Values of the configuration by path of the setting, such as `storage.snapshots_path`
+fn flatten(config: &Value) -> BTreeMap<String, Value> {
+    fn flatten_into(path: String, value: &Value, settings: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(object) if !object.is_empty() => {
+                for (key, value) in object {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    flatten_into(path, value, settings);
+                }
+            }
+            value => {
+                settings.insert(path, value.clone());
+            }
+        }
+    }
+
+    let mut settings = BTreeMap::new();
+    flatten_into(String::new(), config, &mut settings);
+    settings
+}
+
+/// Nest the values of the configuration back, reverting [`flatten`]
+fn unflatten(settings: &BTreeMap<String, Value>) -> Value {
+    let mut config = Value::Object(Map::new());
+    for (path, value) in settings {
+        let mut node = &mut config;
+        for key in path.split('.').filter(|key| !key.is_empty()) {
+            if !node.is_object() {
+                *node = Value::Object(Map::new());
+            }
+            node = node
+                .as_object_mut()
+                .unwrap()
+                .entry(key)
+                .or_insert(Value::Null);
+        }
+        *node = value.clone();
+    }
+    config
+}
+
+/// Paths of the settings which were added, removed or changed, in order
+fn changed_settings(
+    running: &BTreeMap<String, Value>,
+    config: &BTreeMap<String, Value>,
+) -> Vec<String> {
+    let mut changed: Vec<_> = running
+        .keys()
+        .chain(config.keys())
+        .filter(|path| running.get(*path) != config.get(*path))
+        .cloned()
+        .collect();
+    changed.sort_unstable();
+    changed.dedup();
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_changed_settings() {
+        let running = flatten(&json!({
+            "log_level": "INFO",
+            "storage": {
+                "snapshots_path": "./snapshots",
+                "performance": { "update_rate_limit": null },
+            },
+        }));
+        let config = flatten(&json!({
+            "log_level": "DEBUG",
+            "storage": {
+                "snapshots_path": "./backups",
+                "performance": { "update_rate_limit": 8 },
+            },
+            "service": { "slow_query_secs": 2.0 },
+        }));
+
+        let changed = changed_settings(&running, &config);
+        assert_eq!(
+            changed,
+            vec![
+                "log_level",
+                "service.slow_query_secs",
+                "storage.performance.update_rate_limit",
+                "storage.snapshots_path",
+            ],
+        );
+
+        let (applied, requires_restart): (Vec<_>, Vec<_>) =
+            changed.into_iter().partition(|path| is_reloadable(path));
+        assert_eq!(
+            applied,
+            vec![
+                "log_level",
+                "service.slow_query_secs",
+                "storage.performance.update_rate_limit",
+            ],
+        );
+        assert_eq!(requires_restart, vec!["storage.snapshots_path"]);
+    }
+
+    #[test]
+    fn test_unflatten() {
+        let config = json!({
+            "log_level": "INFO",
+            "storage": {
+                "snapshots_path": "./snapshots",
+                "performance": { "update_rate_limit": null, "optimizer_cpu_budget": 0 },
+                "optimizers": {},
+            },
+            "cluster": { "peers": ["a", "b"] },
+        });
+        assert_eq!(unflatten(&flatten(&config)), config);
+    }
+
+    #[test]
+    fn test_is_reloadable() {
+        assert!(is_reloadable("logger.on_disk.enabled"));
+        assert!(!is_reloadable("logger_extra"));
+        assert!(!is_reloadable("storage.performance.max_search_threads"));
+    }
+}
@@ -121,6 +121,49 @@ pub fn tonic_error_to_io_error(err: tonic::transport::Error) -> io::Error {
     io::Error::new(io::ErrorKind::Other, err)
 }
 
+/// Bind a Unix domain socket, replacing a stale socket file left by a previous run
+///
+/// `permissions` is an octal file mode, applied to the socket file after binding.
+#[cfg(unix)]
+pub fn bind_unix_socket(
+    path: &std::path::Path,
+    permissions: Option<&str>,
+) -> io::Result<std::os::unix::net::UnixListener> {
+    use std::os::unix::fs::{FileTypeExt as _, PermissionsExt as _};
+    use std::os::unix::net::UnixListener;
+
+    let mode = permissions
+        .map(|permissions| {
+            u32::from_str_radix(permissions, 8).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid Unix socket permissions {permissions:?}: {err}"),
+                )
+            })
+        })
+        .transpose()?;
+
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(path)?,
+        Ok(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    let listener = UnixListener::bind(path)?;
+
+    if let Some(mode) = mode {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    }
+
+    Ok(listener)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -148,4 +191,32 @@ mod tests {
         sleep(Duration::from_millis(500));
         join.join().unwrap()
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_bind_unix_socket() {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt as _;
+
+        use super::bind_unix_socket;
+
+        let dir = tempfile::Builder::new().tempdir().unwrap();
+        let path = dir.path().join("qdrant.sock");
+
+        let listener = bind_unix_socket(&path, Some("600")).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // Socket file left by a previous run is replaced
+        drop(listener);
+        bind_unix_socket(&path, None).unwrap();
+
+        // Other files are never removed
+        let file_path = dir.path().join("regular");
+        fs::write(&file_path, "data").unwrap();
+        assert!(bind_unix_socket(&file_path, None).is_err());
+        assert!(file_path.exists());
+
+        assert!(bind_unix_socket(&dir.path().join("other.sock"), Some("abc")).is_err());
+    }
 }
@@ -1,7 +1,12 @@
+use collection::common::query_memory::QueryMemoryStatistics;
+use collection::common::query_usage::QueryUsageStatistics;
+use collection::common::resource_usage::ResourceUsageStatistics;
+use collection::shards::telemetry::SearchPoolsTelemetry;
 use prometheus::proto::{Counter, Gauge, LabelPair, Metric, MetricFamily, MetricType};
 use prometheus::TextEncoder;
 use segment::common::operation_time_statistics::OperationDurationStatistics;
 
+use crate::common::allocator::AllocatorStats;
 use crate::common::telemetry::TelemetryData;
 use crate::common::telemetry_ops::app_telemetry::{AppBuildTelemetry, AppFeaturesTelemetry};
 use crate::common::telemetry_ops::cluster_telemetry::{ClusterStatusTelemetry, ClusterTelemetry};
@@ -83,6 +88,9 @@ impl MetricsProvider for TelemetryData {
         self.collections.add_metrics(metrics);
         self.cluster.add_metrics(metrics);
         self.requests.add_metrics(metrics);
+        if let Some(memory) = &self.memory {
+            memory.add_metrics(metrics);
+        }
     }
 }
 
@@ -112,6 +120,52 @@ impl MetricsProvider for AppFeaturesTelemetry {
     }
 }
 
+impl MetricsProvider for AllocatorStats {
+    fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
+        let memory_metrics = [
+            (
+                "memory_allocated_bytes",
+                "total number of bytes allocated by the application",
+                self.allocated_bytes,
+            ),
+            (
+                "memory_active_bytes",
+                "total number of bytes in active pages allocated by the application",
+                self.active_bytes,
+            ),
+            (
+                "memory_metadata_bytes",
+                "total number of bytes dedicated to allocator metadata",
+                self.metadata_bytes,
+            ),
+            (
+                "memory_resident_bytes",
+                "number of bytes in physically resident pages mapped by the allocator",
+                self.resident_bytes,
+            ),
+            (
+                "memory_mapped_bytes",
+                "total number of bytes in active extents mapped by the allocator",
+                self.mapped_bytes,
+            ),
+            (
+                "memory_retained_bytes",
+                "total number of bytes in virtual memory mappings retained by the allocator",
+                self.retained_bytes,
+            ),
+        ];
+
+        for (name, help, bytes) in memory_metrics {
+            metrics.push(metric_family(
+                name,
+                help,
+                MetricType::GAUGE,
+                vec![gauge(bytes as f64, &[])],
+            ));
+        }
+    }
+}
+
 impl MetricsProvider for CollectionsTelemetry {
     fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
         let vector_count = self
@@ -135,6 +189,174 @@ impl MetricsProvider for CollectionsTelemetry {
             MetricType::GAUGE,
             vec![gauge(vector_count as f64, &[])],
         ));
+
+        let query_memory = self
+            .collections
+            .iter()
+            .flatten()
+            .map(|p| match p {
+                CollectionTelemetryEnum::Aggregated(a) => a.query_memory.clone(),
+                CollectionTelemetryEnum::Full(c) => c.query_memory.clone(),
+            })
+            .fold(QueryMemoryStatistics::default(), |acc, x| acc + x);
+        metrics.push(metric_family(
+            "collections_query_memory_bytes",
+            "estimated memory held by query results",
+            MetricType::HISTOGRAM,
+            vec![histogram(
+                query_memory.count as u64,
+                query_memory.total_bytes as f64,
+                &query_memory
+                    .bytes_histogram
+                    .iter()
+                    .map(|&(b, c)| (b as f64, c as u64))
+                    .collect::<Vec<_>>(),
+                &[],
+            )],
+        ));
+
+        let query_usage = self
+            .collections
+            .iter()
+            .flatten()
+            .map(|p| match p {
+                CollectionTelemetryEnum::Aggregated(a) => a.query_usage.clone(),
+                CollectionTelemetryEnum::Full(c) => c.query_usage.clone(),
+            })
+            .fold(QueryUsageStatistics::default(), |acc, x| acc + x);
+        metrics.push(metric_family(
+            "collections_queries_total",
+            "number of queries and prefetches by the kind of their query",
+            MetricType::COUNTER,
+            query_usage
+                .query_types
+                .iter()
+                .map(|(query_type, count)| counter(count as f64, &[("type", query_type)]))
+                .collect(),
+        ));
+        metrics.push(metric_family(
+            "collections_queries_with_filter_total",
+            "number of queries with a filter",
+            MetricType::COUNTER,
+            vec![counter(query_usage.with_filter as f64, &[])],
+        ));
+        metrics.push(metric_family(
+            "collections_queries_with_prefetch_total",
+            "number of queries with prefetches",
+            MetricType::COUNTER,
+            vec![counter(query_usage.with_prefetch as f64, &[])],
+        ));
+        metrics.push(metric_family(
+            "collections_queries_over_soft_limits_total",
+            "number of queries exceeding the query limits, executed with warnings",
+            MetricType::COUNTER,
+            self.collections
+                .iter()
+                .flatten()
+                .map(|collection| {
+                    let over_soft_limits = match collection {
+                        CollectionTelemetryEnum::Aggregated(a) => a.query_usage.over_soft_limits,
+                        CollectionTelemetryEnum::Full(c) => c.query_usage.over_soft_limits,
+                    };
+                    counter(over_soft_limits as f64, &[("collection", collection.id())])
+                })
+                .collect(),
+        ));
+
+        let resource_usage = self
+            .collections
+            .iter()
+            .flatten()
+            .map(|p| match p {
+                CollectionTelemetryEnum::Aggregated(a) => a.resource_usage.clone(),
+                CollectionTelemetryEnum::Full(c) => c.resource_usage.clone(),
+            })
+            .fold(ResourceUsageStatistics::default(), |acc, x| acc + x);
+        metrics.push(metric_family(
+            "collections_cpu_seconds_total",
+            "CPU time spent on requests to collections",
+            MetricType::COUNTER,
+            vec![counter(resource_usage.cpu_time_ms as f64 / 1000.0, &[])],
+        ));
+        metrics.push(metric_family(
+            "collections_io_read_bytes_total",
+            "number of bytes read from storage for requests to collections",
+            MetricType::COUNTER,
+            vec![counter(resource_usage.io_read_bytes as f64, &[])],
+        ));
+
+        let mut max_replication_lags = Vec::new();
+        let mut degraded_replicas = Vec::new();
+        for collection in self.collections.iter().flatten() {
+            let (max_replication_lag, degraded) = match collection {
+                CollectionTelemetryEnum::Aggregated(a) => {
+                    (a.max_replication_lag, a.degraded_replicas)
+                }
+                CollectionTelemetryEnum::Full(c) => {
+                    (c.max_replication_lag(), c.count_degraded_replicas())
+                }
+            };
+            let labels = [("collection", collection.id())];
+            max_replication_lags.push(gauge(max_replication_lag as f64, &labels));
+            degraded_replicas.push(gauge(degraded as f64, &labels));
+        }
+        metrics.push(metric_family(
+            "collections_max_replication_lag",
+            "maximum number of operations a replica lags behind the replica of this peer",
+            MetricType::GAUGE,
+            max_replication_lags,
+        ));
+        metrics.push(metric_family(
+            "collections_degraded_replicas",
+            "number of replicas lagging more than the configured threshold",
+            MetricType::GAUGE,
+            degraded_replicas,
+        ));
+
+        let search_pools = self
+            .collections
+            .iter()
+            .flatten()
+            .map(|p| match p {
+                CollectionTelemetryEnum::Aggregated(a) => a.search_pools,
+                CollectionTelemetryEnum::Full(c) => c.search_pools(),
+            })
+            .fold(SearchPoolsTelemetry::default(), |acc, x| acc + x);
+        let pools = [
+            ("visited_lists", search_pools.visited_lists),
+            ("search_contexts", search_pools.search_contexts),
+        ];
+        metrics.push(metric_family(
+            "collections_search_pool_reused_total",
+            "number of searches which reused pooled memory",
+            MetricType::COUNTER,
+            pools
+                .iter()
+                .map(|&(pool, stats)| counter(stats.reused as f64, &[("pool", pool)]))
+                .collect(),
+        ));
+        metrics.push(metric_family(
+            "collections_search_pool_allocated_total",
+            "number of searches which allocated memory, because the pool was empty",
+            MetricType::COUNTER,
+            pools
+                .iter()
+                .map(|&(pool, stats)| counter(stats.allocated as f64, &[("pool", pool)]))
+                .collect(),
+        ));
+        metrics.push(metric_family(
+            "collections_search_pool_hit_rate",
+            "share of searches which reused pooled memory",
+            MetricType::GAUGE,
+            pools
+                .iter()
+                .filter_map(|&(pool, stats)| {
+                    stats
+                        .hit_rate()
+                        .map(|hit_rate| gauge(hit_rate, &[("pool", pool)]))
+                })
+                .collect(),
+        ));
     }
 }
 
@@ -194,6 +416,20 @@ impl MetricsProvider for ClusterStatusTelemetry {
                 vec![gauge(if self.is_voter { 1.0 } else { 0.0 }, &[])],
             ));
         }
+
+        if !self.read_retries.is_empty() {
+            metrics.push(metric_family(
+                "cluster_read_retries_total",
+                "total number of retried search and query requests to shards on other peers",
+                MetricType::COUNTER,
+                self.read_retries
+                    .iter()
+                    .map(|(peer_id, retries)| {
+                        counter(*retries as f64, &[("peer_id", &peer_id.to_string())])
+                    })
+                    .collect(),
+            ));
+        }
     }
 }
 
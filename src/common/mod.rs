@@ -1,5 +1,8 @@
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod allocator;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod collections;
+pub mod config_reload;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod error_reporting;
 #[allow(dead_code)]
@@ -10,6 +13,8 @@ pub mod http_client;
 pub mod metrics;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod points;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod request_timeout;
 pub mod snapshots;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod stacktrace;
@@ -25,4 +30,6 @@ pub mod strings;
 
 pub mod debugger;
 
+pub mod diagnostics;
+
 pub mod pyroscope_state;
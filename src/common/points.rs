@@ -1,23 +1,32 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
-use api::rest::{SearchGroupsRequestInternal, ShardKeySelector};
+use api::rest::{SearchGroupsRequestInternal, ShardKeySelector, Vector, VectorStruct};
 use collection::common::batching::batch_requests;
+use collection::common::external_index::{read_external_index, ExternalIndexFormat, ExternalPoint};
+use collection::common::projection::pca_2d;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{
     DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
 };
 use collection::operations::point_ops::{
-    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
-    WriteOrdering,
+    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointStruct,
+    PointsSelector, WriteOrdering,
 };
+use collection::operations::session_token::{self, SessionToken};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    DiscoverRequestBatch, DiscoverRequestInternal, GroupsResult, PointRequestInternal,
-    RecommendGroupsRequestInternal, Record, ScrollRequestInternal, ScrollResult, UpdateResult,
+    DiscoverRequestBatch, DiscoverRequestInternal, GroupsResult, HnswNeighborhood,
+    PayloadIndexStats, PayloadIndexStatus, PointRequestInternal, RecommendGroupsRequestInternal,
+    Record, ReplicaPayloadIndexState, SampleRequestInternal, SampleResult, SampledPoint,
+    ScoreRequestInternal, ScoreResult, ScrollRequestInternal, ScrollResult, UpdateResult,
+    UpdateStatus,
 };
-use collection::operations::universal_query::collection_query::CollectionQueryRequest;
+use collection::operations::universal_query::collection_query::{CollectionQueryRequest, Query};
+use collection::operations::universal_query::shard_query::Sample;
 use collection::operations::vector_ops::{
     DeleteVectors, UpdateVectors, UpdateVectorsOp, VectorOperations,
 };
@@ -26,16 +35,22 @@ use collection::operations::{
 };
 use collection::shards::shard::ShardId;
 use schemars::JsonSchema;
+use segment::data_types::vectors::{VectorRef, DEFAULT_VECTOR_NAME};
 use segment::json_path::JsonPath;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
+use segment::types::{
+    PayloadFieldSchema, PayloadKeyType, PayloadSchemaParams, PointIdType, QueryStats, ScoredPoint,
+    WithPayloadInterface, WithVector,
+};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreatePayloadIndex, DropPayloadIndex,
 };
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::duplicates::{DuplicatesReport, FindDuplicates};
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
-use storage::rbac::Access;
+use storage::rbac::{Access, AccessRequirements};
+use tokio::task::JoinHandle;
 use validator::Validate;
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
@@ -112,6 +127,31 @@ pub struct UpdateOperations {
     pub operations: Vec<UpdateOperation>,
 }
 
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DeletePointsResult {
+    #[serde(flatten)]
+    pub result: UpdateResult,
+    /// Outcome of the deletion of each point, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<DeletePointsReport>,
+}
+
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct DeletePointsReport {
+    /// Points which were deleted
+    pub deleted: Vec<PointIdType>,
+    /// Points which didn't exist
+    pub not_found: Vec<PointIdType>,
+    /// Points which could not be deleted, e.g. because a shard failed, grouped by the error
+    pub failed: Vec<FailedPointsDeletion>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FailedPointsDeletion {
+    pub points: Vec<PointIdType>,
+    pub error: String,
+}
+
 impl Validate for UpdateOperation {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -219,6 +259,158 @@ pub async fn do_delete_points(
     .await
 }
 
+/// Number of points deleted by a single operation, when deleting a list of points
+const DELETE_CHUNK_SIZE: usize = 10_000;
+
+/// Deletes points, a list of points in chunks of [`DELETE_CHUNK_SIZE`].
+///
+/// With `report`, the outcome of the deletion of each point is reported, and chunks failing to
+/// be deleted don't fail the whole request. Requires `wait`, to only report applied deletions.
+pub async fn do_delete_points_with_report(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    points: PointsSelector,
+    report: bool,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<DeletePointsResult, StorageError> {
+    let PointsSelector::PointIdsSelector(PointIdsList {
+        points: ids,
+        shard_key,
+    }) = points
+    else {
+        if report {
+            return Err(StorageError::bad_input(
+                "Report of deleted points is only available when deleting points by IDs",
+            ));
+        }
+        let result = do_delete_points(
+            toc,
+            collection_name,
+            points,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await?;
+        return Ok(DeletePointsResult {
+            result,
+            report: None,
+        });
+    };
+
+    if report && !wait {
+        return Err(StorageError::bad_input(
+            "Report of deleted points requires `wait=true`",
+        ));
+    }
+
+    let mut delete_report = report.then(DeletePointsReport::default);
+    let mut result = UpdateResult {
+        operation_id: None,
+        status: if wait {
+            UpdateStatus::Completed
+        } else {
+            UpdateStatus::Acknowledged
+        },
+        clock_tag: None,
+        session_token: None,
+    };
+    let mut session_token = SessionToken::default();
+
+    for chunk in ids.chunks(DELETE_CHUNK_SIZE) {
+        let delete_chunk = || {
+            do_delete_points(
+                toc.clone(),
+                collection_name.clone(),
+                PointsSelector::PointIdsSelector(PointIdsList {
+                    points: chunk.to_vec(),
+                    shard_key: shard_key.clone(),
+                }),
+                None,
+                None,
+                wait,
+                ordering,
+                access.clone(),
+            )
+        };
+
+        let chunk_result = match &mut delete_report {
+            None => delete_chunk().await?,
+            Some(delete_report) => {
+                let existing = existing_point_ids(
+                    &toc,
+                    &collection_name,
+                    chunk,
+                    shard_key.clone(),
+                    access.clone(),
+                );
+                let outcome = match existing.await {
+                    Ok(existing) => delete_chunk().await.map(|result| (existing, result)),
+                    Err(err) => Err(err),
+                };
+                match outcome {
+                    Ok((existing, chunk_result)) => {
+                        let (deleted, not_found): (Vec<_>, Vec<_>) =
+                            chunk.iter().copied().partition(|id| existing.contains(id));
+                        delete_report.deleted.extend(deleted);
+                        delete_report.not_found.extend(not_found);
+                        chunk_result
+                    }
+                    Err(err) => {
+                        delete_report.failed.push(FailedPointsDeletion {
+                            points: chunk.to_vec(),
+                            error: err.to_string(),
+                        });
+                        continue;
+                    }
+                }
+            }
+        };
+
+        if let Some(chunk_token) = &chunk_result.session_token {
+            session_token.merge(chunk_token);
+        }
+        result = chunk_result;
+    }
+
+    result.session_token = (!session_token.is_empty()).then_some(session_token);
+    Ok(DeletePointsResult {
+        result,
+        report: delete_report,
+    })
+}
+
+/// IDs of the given points which exist in the collection
+async fn existing_point_ids(
+    toc: &TableOfContent,
+    collection_name: &str,
+    ids: &[PointIdType],
+    shard_key: Option<ShardKeySelector>,
+    access: Access,
+) -> Result<HashSet<PointIdType>, StorageError> {
+    let request = PointRequestInternal {
+        ids: ids.to_vec(),
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: WithVector::Bool(false),
+        as_of_version: None,
+    };
+    let records = toc
+        .retrieve(
+            collection_name,
+            request,
+            None,
+            get_shard_selector_for_update(None, shard_key),
+            access,
+            None,
+        )
+        .await?;
+    Ok(records.into_iter().map(|record| record.id).collect())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_update_vectors(
     toc: Arc<TableOfContent>,
@@ -590,6 +782,181 @@ pub async fn do_batch_update_points(
     Ok(results)
 }
 
+/// Number of points read from an external index, or copied between collections, and upserted at
+/// once
+const IMPORT_BATCH_SIZE: usize = 1000;
+
+/// Upserts the vectors of an external index file into the collection, keeping their IDs.
+///
+/// The index file is read completely before anything is written, so a malformed file changes
+/// nothing. Points are then upserted in batches through the regular update path, a failure
+/// in the middle keeps the batches written before.
+///
+/// Returns the result of the last upserted batch.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_import_points(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    index_path: &Path,
+    format: ExternalIndexFormat,
+    vector_name: Option<String>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    // Check access before reading the index, which might be large or malformed
+    access.check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
+
+    let index_path_buf = index_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        read_external_index(&index_path_buf, format)?.try_for_each(|point| point.map(|_| ()))
+    })
+    .await??;
+
+    let mut result = UpdateResult {
+        operation_id: None,
+        status: UpdateStatus::Completed,
+        clock_tag: None,
+        session_token: None,
+    };
+
+    let mut points_iter = read_external_index(index_path, format)?;
+    loop {
+        // Reading the index file is blocking
+        let (iter, batch) = tokio::task::spawn_blocking(move || {
+            let batch = points_iter
+                .by_ref()
+                .take(IMPORT_BATCH_SIZE)
+                .collect::<Result<Vec<_>, _>>();
+            (points_iter, batch)
+        })
+        .await?;
+        points_iter = iter;
+
+        let batch = batch?;
+        if batch.is_empty() {
+            break;
+        }
+
+        let points: Vec<_> = batch
+            .into_iter()
+            .map(|ExternalPoint { id, vector }| PointStruct {
+                id: PointIdType::NumId(id),
+                vector: match &vector_name {
+                    Some(name) => {
+                        VectorStruct::Named(HashMap::from([(name.clone(), Vector::Dense(vector))]))
+                    }
+                    None => VectorStruct::Single(vector),
+                },
+                payload: None,
+            })
+            .collect();
+
+        result = do_upsert_points(
+            toc.clone(),
+            collection_name.clone(),
+            PointInsertOperations::from(points),
+            None,
+            None,
+            wait,
+            ordering,
+            access.clone(),
+        )
+        .await?;
+    }
+
+    Ok(result)
+}
+
+/// Create the payload indexes of a collection in another collection, and copy all its points
+/// into it.
+///
+/// Returns the result of the last upserted batch.
+pub async fn copy_collection_points(
+    dispatcher: &Arc<Dispatcher>,
+    source_name: &str,
+    target_name: &str,
+    payload_index_schema: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ordering: WriteOrdering,
+    access: &Access,
+) -> Result<UpdateResult, StorageError> {
+    let toc = dispatcher.toc(access).clone();
+
+    for (field_name, field_schema) in payload_index_schema {
+        dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::CreatePayloadIndex(CreatePayloadIndex {
+                    collection_name: target_name.to_string(),
+                    field_name,
+                    field_schema,
+                }),
+                access.clone(),
+                None,
+            )
+            .await?;
+    }
+
+    let mut result = UpdateResult {
+        operation_id: None,
+        status: UpdateStatus::Completed,
+        clock_tag: None,
+        session_token: None,
+    };
+
+    let source_pass = access.check_collection_access(source_name, AccessRequirements::new())?;
+    let mut offset = None;
+    loop {
+        let request = ScrollRequestInternal {
+            offset,
+            limit: Some(IMPORT_BATCH_SIZE),
+            filter: None,
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: WithVector::Bool(true),
+            order_by: None,
+        };
+
+        // Don't hold the collection between batches
+        let page = toc
+            .get_collection(&source_pass)
+            .await?
+            .scroll_by(request, None, &ShardSelectorInternal::All)
+            .await?;
+
+        let points: Vec<_> = page
+            .points
+            .into_iter()
+            .map(|record| PointStruct {
+                id: record.id,
+                vector: record
+                    .vector
+                    .map(VectorStruct::from)
+                    .unwrap_or_else(|| VectorStruct::Named(HashMap::new())),
+                payload: record.payload,
+            })
+            .collect();
+        if !points.is_empty() {
+            result = do_upsert_points(
+                toc.clone(),
+                target_name.to_string(),
+                PointInsertOperations::from(points),
+                None,
+                None,
+                true,
+                ordering,
+                access.clone(),
+            )
+            .await?;
+        }
+
+        offset = page.next_page_offset;
+        if offset.is_none() {
+            break;
+        }
+    }
+
+    Ok(result)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_create_index_internal(
     toc: Arc<TableOfContent>,
@@ -644,6 +1011,14 @@ pub async fn do_create_index(
         ));
     };
 
+    if let PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(text_index_params)) =
+        &field_schema
+    {
+        text_index_params
+            .validate_tokenizer()
+            .map_err(StorageError::bad_request)?;
+    }
+
     let consensus_op = CollectionMetaOperations::CreatePayloadIndex(CreatePayloadIndex {
         collection_name: collection_name.to_string(),
         field_name: operation.field_name.clone(),
@@ -664,17 +1039,100 @@ pub async fn do_create_index(
     // for `wait` parameter and return type.
     // The idea is to migrate from the point-like interface to consensus-like interface in the next few versions
 
-    do_create_index_internal(
-        toc,
-        collection_name,
-        operation.field_name,
+    let result = do_create_index_internal(
+        toc.clone(),
+        collection_name.clone(),
+        operation.field_name.clone(),
         Some(field_schema),
         clock_tag,
         shard_selection,
         wait,
         ordering,
     )
-    .await
+    .await?;
+
+    // Once the index is built, check that no replica missed it
+    if wait {
+        check_index_consistency(&toc, &collection_name, &operation.field_name).await;
+    }
+
+    Ok(result)
+}
+
+/// Warn about replicas which don't have the payload index
+async fn check_index_consistency(
+    toc: &TableOfContent,
+    collection_name: &str,
+    field_name: &JsonPath,
+) {
+    let status = do_get_index_status(
+        toc,
+        collection_name,
+        field_name,
+        Access::full("Index consistency check"),
+    )
+    .await;
+
+    match status {
+        Ok(status) => {
+            for replica in status.replicas {
+                if replica.state != ReplicaPayloadIndexState::Built {
+                    log::warn!(
+                        "Payload index for field {field_name} of collection {collection_name} \
+                         is {:?} on replica of shard {} on peer {}",
+                        replica.state,
+                        replica.shard_id,
+                        replica.peer_id,
+                    );
+                }
+            }
+        }
+        Err(err) => log::warn!(
+            "Failed to check payload index for field {field_name} of collection {collection_name}: {err}"
+        ),
+    }
+}
+
+/// State of the payload index on every active replica of the collection
+pub async fn do_get_index_status(
+    toc: &TableOfContent,
+    collection_name: &str,
+    field_name: &JsonPath,
+    access: Access,
+) -> Result<PayloadIndexStatus, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.payload_index_status(field_name).await?)
+}
+
+/// Statistics of the payload index, estimated from the replicas on this peer
+pub async fn do_get_index_stats(
+    toc: &TableOfContent,
+    collection_name: &str,
+    field_name: &JsonPath,
+    access: Access,
+) -> Result<PayloadIndexStats, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.payload_index_stats(field_name).await?)
+}
+
+/// Neighbors of the point in the HNSW graphs of the vector, in the segments of this peer.
+/// Intended for investigation of search recall.
+pub async fn do_get_hnsw_neighborhood(
+    toc: &TableOfContent,
+    collection_name: &str,
+    point_id: PointIdType,
+    vector_name: Option<String>,
+    access: Access,
+) -> Result<HnswNeighborhood, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    let vector_name = vector_name.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string());
+    Ok(collection.hnsw_neighborhood(point_id, &vector_name).await?)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -748,6 +1206,7 @@ pub async fn do_delete_index(
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_core_search_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -756,6 +1215,7 @@ pub async fn do_core_search_points(
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
     let batch_res = do_core_search_batch_points(
         toc,
@@ -767,6 +1227,7 @@ pub async fn do_core_search_points(
         shard_selection,
         access,
         timeout,
+        session_token,
     )
     .await?;
     batch_res
@@ -782,6 +1243,7 @@ pub async fn do_search_batch_points(
     read_consistency: Option<ReadConsistency>,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = batch_requests::<
         (CoreSearchRequest, ShardSelectorInternal),
@@ -811,6 +1273,7 @@ pub async fn do_search_batch_points(
                 shard_selector,
                 access.clone(),
                 timeout,
+                session_token,
             );
             res.push(req);
             Ok(())
@@ -822,6 +1285,7 @@ pub async fn do_search_batch_points(
     Ok(flatten_results)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_core_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -830,6 +1294,7 @@ pub async fn do_core_search_batch_points(
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     toc.core_search_batch(
         collection_name,
@@ -838,10 +1303,12 @@ pub async fn do_core_search_batch_points(
         shard_selection,
         access,
         timeout,
+        session_token,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_search_point_groups(
     toc: &TableOfContent,
     collection_name: &str,
@@ -850,6 +1317,7 @@ pub async fn do_search_point_groups(
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
 ) -> Result<GroupsResult, StorageError> {
     toc.group(
         collection_name,
@@ -858,10 +1326,12 @@ pub async fn do_search_point_groups(
         shard_selection,
         access,
         timeout,
+        session_token,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_recommend_point_groups(
     toc: &TableOfContent,
     collection_name: &str,
@@ -870,6 +1340,7 @@ pub async fn do_recommend_point_groups(
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
 ) -> Result<GroupsResult, StorageError> {
     toc.group(
         collection_name,
@@ -878,10 +1349,12 @@ pub async fn do_recommend_point_groups(
         shard_selection,
         access,
         timeout,
+        session_token,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_discover_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -890,6 +1363,7 @@ pub async fn do_discover_points(
     shard_selector: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
     toc.discover(
         collection_name,
@@ -898,6 +1372,7 @@ pub async fn do_discover_points(
         shard_selector,
         access,
         timeout,
+        session_token,
     )
     .await
 }
@@ -909,6 +1384,7 @@ pub async fn do_discover_batch_points(
     read_consistency: Option<ReadConsistency>,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = request
         .searches
@@ -923,8 +1399,15 @@ pub async fn do_discover_batch_points(
         })
         .collect();
 
-    toc.discover_batch(collection_name, requests, read_consistency, access, timeout)
-        .await
+    toc.discover_batch(
+        collection_name,
+        requests,
+        read_consistency,
+        access,
+        timeout,
+        session_token,
+    )
+    .await
 }
 
 pub async fn do_count_points(
@@ -934,6 +1417,7 @@ pub async fn do_count_points(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
+    session_token: Option<&SessionToken>,
 ) -> Result<CountResult, StorageError> {
     toc.count(
         collection_name,
@@ -941,6 +1425,7 @@ pub async fn do_count_points(
         read_consistency,
         shard_selection,
         access,
+        session_token,
     )
     .await
 }
@@ -952,6 +1437,7 @@ pub async fn do_get_points(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
+    session_token: Option<&SessionToken>,
 ) -> Result<Vec<Record>, StorageError> {
     toc.retrieve(
         collection_name,
@@ -959,6 +1445,7 @@ pub async fn do_get_points(
         read_consistency,
         shard_selection,
         access,
+        session_token,
     )
     .await
 }
@@ -970,6 +1457,7 @@ pub async fn do_scroll_points(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
+    session_token: Option<&SessionToken>,
 ) -> Result<ScrollResult, StorageError> {
     toc.scroll(
         collection_name,
@@ -977,10 +1465,12 @@ pub async fn do_scroll_points(
         read_consistency,
         shard_selection,
         access,
+        session_token,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_query_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -989,15 +1479,24 @@ pub async fn do_query_points(
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<ScoredPoint>, StorageError> {
+    session_token: Option<&SessionToken>,
+) -> Result<(Vec<ScoredPoint>, Option<QueryStats>), StorageError> {
     let requests = vec![(request, shard_selection)];
-    let batch_res = toc
-        .query_batch(collection_name, requests, read_consistency, access, timeout)
+    let (batch_res, batch_stats) = toc
+        .query_batch_with_stats(
+            collection_name,
+            requests,
+            read_consistency,
+            access,
+            timeout,
+            session_token,
+        )
         .await?;
-    batch_res
+    let points = batch_res
         .into_iter()
         .next()
-        .ok_or_else(|| StorageError::service_error("Empty query result"))
+        .ok_or_else(|| StorageError::service_error("Empty query result"))?;
+    Ok((points, batch_stats.into_iter().next().flatten()))
 }
 
 pub async fn do_query_batch_points(
@@ -1007,7 +1506,159 @@ pub async fn do_query_batch_points(
     read_consistency: Option<ReadConsistency>,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-    toc.query_batch(collection_name, requests, read_consistency, access, timeout)
-        .await
+    session_token: Option<&SessionToken>,
+) -> Result<(Vec<Vec<ScoredPoint>>, Vec<Option<QueryStats>>), StorageError> {
+    toc.query_batch_with_stats(
+        collection_name,
+        requests,
+        read_consistency,
+        access,
+        timeout,
+        session_token,
+    )
+    .await
+}
+
+/// Pick random points of the collection, along with the 2D projection of their vectors if
+/// requested. The sample is different on each call.
+pub async fn do_sample_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SampleRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+    session_token: Option<&SessionToken>,
+) -> Result<SampleResult, StorageError> {
+    let SampleRequestInternal {
+        limit,
+        filter,
+        with_payload,
+        projection,
+    } = request;
+
+    let projected_vector = projection.map(|projection| {
+        projection
+            .using
+            .unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string())
+    });
+
+    let query_request = CollectionQueryRequest {
+        prefetch: vec![],
+        query: Some(Query::Sample(Sample {
+            seed: rand::random(),
+        })),
+        using: DEFAULT_VECTOR_NAME.to_string(),
+        filter,
+        propagate_filter: CollectionQueryRequest::DEFAULT_PROPAGATE_FILTER,
+        ids: None,
+        filter_template: None,
+        score_threshold: None,
+        limit: limit.unwrap_or(SampleRequestInternal::DEFAULT_LIMIT),
+        offset: 0,
+        params: None,
+        // Vectors are only needed to compute the projection, they are not returned
+        with_vector: match &projected_vector {
+            Some(vector_name) => WithVector::Selector(vec![vector_name.clone()]),
+            None => WithVector::Bool(false),
+        },
+        with_payload: with_payload.unwrap_or(WithPayloadInterface::Bool(true)),
+        with_routing: false,
+        with_stats: false,
+        lookup_from: None,
+        budget: None,
+        rerank: None,
+        skip_missing: false,
+    };
+
+    let (points, _stats) = do_query_points(
+        toc,
+        collection_name,
+        query_request,
+        read_consistency,
+        shard_selection,
+        access,
+        timeout,
+        session_token,
+    )
+    .await?;
+
+    let mut projections = vec![None; points.len()];
+    if let Some(vector_name) = &projected_vector {
+        let mut projected = Vec::with_capacity(points.len());
+        let mut vectors = Vec::with_capacity(points.len());
+        for (idx, point) in points.iter().enumerate() {
+            match point
+                .vector
+                .as_ref()
+                .and_then(|vector| vector.get(vector_name))
+            {
+                Some(VectorRef::Dense(vector)) => {
+                    projected.push(idx);
+                    vectors.push(vector);
+                }
+                Some(VectorRef::Sparse(_) | VectorRef::MultiDense(_)) => {
+                    return Err(StorageError::bad_input(format!(
+                        "Only dense vectors can be projected, vector `{vector_name}` is not dense",
+                    )));
+                }
+                None => {}
+            }
+        }
+
+        for (idx, coordinates) in projected.into_iter().zip(pca_2d(&vectors)) {
+            projections[idx] = Some(coordinates);
+        }
+    }
+
+    let points = points
+        .into_iter()
+        .zip(projections)
+        .map(|(point, projection)| SampledPoint {
+            id: point.id,
+            payload: point.payload,
+            shard_key: point.shard_key,
+            projection,
+        })
+        .collect();
+
+    Ok(SampleResult { points })
+}
+
+/// Score the query against the given vectors or points exactly, with the distance of the vector
+pub async fn do_score_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: ScoreRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    session_token: Option<&SessionToken>,
+) -> Result<ScoreResult, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, AccessRequirements::new())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    let scores = session_token::scope(
+        session_token,
+        None,
+        collection.score_vectors(request, read_consistency, &shard_selection),
+    )
+    .await?;
+    Ok(ScoreResult { scores })
+}
+
+/// Start the scan of the collection for points with identical vectors
+pub fn do_find_duplicate_points(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: FindDuplicates,
+    access: Access,
+) -> Result<JoinHandle<Result<DuplicatesReport, StorageError>>, StorageError> {
+    // Fail before starting the scan, in case the result is not awaited
+    access.check_collection_access(&collection_name, request.access_requirements())?;
+    Ok(tokio::spawn(async move {
+        toc.find_duplicate_points(&collection_name, request, access)
+            .await
+    }))
 }
@@ -0,0 +1,106 @@
+//! Timeouts which clients set in headers of their requests.
+//!
+//! A client waits for a response only until its timeout, so work on the request is limited to it,
+//! in addition to the timeout given in the parameters of the request.
+
+use std::time::Duration;
+
+/// Header of REST requests with the timeout of the client in seconds, e.g. `X-Request-Timeout: 2.5`
+pub const REQUEST_TIMEOUT_HEADER: &str = "x-request-timeout";
+
+/// Header of gRPC requests with the timeout of the client, as defined by the gRPC protocol
+pub const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+
+/// Parse the value of the `X-Request-Timeout` header, in seconds
+pub fn parse_request_timeout(value: &str) -> Option<Duration> {
+    let seconds: f64 = value.trim().parse().ok()?;
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return None;
+    }
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Parse the value of the `grpc-timeout` header, such as `100m` for 100 milliseconds.
+///
+/// See <https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md>
+pub fn parse_grpc_timeout(value: &str) -> Option<Duration> {
+    // At most 8 digits, followed by a single unit
+    if value.len() < 2 || value.len() > 9 || !value.is_ascii() {
+        return None;
+    }
+
+    let (amount, unit) = value.split_at(value.len() - 1);
+    if !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let amount: u64 = amount.parse().ok()?;
+
+    let timeout = match unit {
+        "H" => Duration::from_secs(amount * 60 * 60),
+        "M" => Duration::from_secs(amount * 60),
+        "S" => Duration::from_secs(amount),
+        "m" => Duration::from_millis(amount),
+        "u" => Duration::from_micros(amount),
+        "n" => Duration::from_nanos(amount),
+        _ => return None,
+    };
+    Some(timeout)
+}
+
+/// The earlier of the timeout of the request parameters and the timeout of the client
+pub fn min_timeout(
+    timeout: Option<Duration>,
+    client_timeout: Option<Duration>,
+) -> Option<Duration> {
+    match (timeout, client_timeout) {
+        (Some(timeout), Some(client_timeout)) => Some(timeout.min(client_timeout)),
+        (timeout, client_timeout) => timeout.or(client_timeout),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_timeout() {
+        assert_eq!(parse_request_timeout("5"), Some(Duration::from_secs(5)));
+        assert_eq!(
+            parse_request_timeout(" 0.25 "),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(parse_request_timeout("0"), None);
+        assert_eq!(parse_request_timeout("-1"), None);
+        assert_eq!(parse_request_timeout("inf"), None);
+        assert_eq!(parse_request_timeout("5s"), None);
+    }
+
+    #[test]
+    fn test_parse_grpc_timeout() {
+        assert_eq!(parse_grpc_timeout("1H"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_grpc_timeout("2M"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_grpc_timeout("30S"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_grpc_timeout("100m"), Some(Duration::from_millis(100)));
+        assert_eq!(
+            parse_grpc_timeout("99999999u"),
+            Some(Duration::from_micros(99999999))
+        );
+        assert_eq!(parse_grpc_timeout("5n"), Some(Duration::from_nanos(5)));
+        assert_eq!(parse_grpc_timeout("100"), None);
+        assert_eq!(parse_grpc_timeout("m"), None);
+        assert_eq!(parse_grpc_timeout("+5S"), None);
+        assert_eq!(parse_grpc_timeout("123456789S"), None);
+        assert_eq!(parse_grpc_timeout("5s"), None);
+    }
+
+    #[test]
+    fn test_min_timeout() {
+        let short = Some(Duration::from_secs(1));
+        let long = Some(Duration::from_secs(10));
+        assert_eq!(min_timeout(short, long), short);
+        assert_eq!(min_timeout(long, short), short);
+        assert_eq!(min_timeout(None, long), long);
+        assert_eq!(min_timeout(long, None), long);
+        assert_eq!(min_timeout(None, None), None);
+    }
+}
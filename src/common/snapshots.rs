@@ -5,6 +5,7 @@ use std::sync::Arc;
 use collection::collection::Collection;
 use collection::common::sha_256::hash_file;
 use collection::operations::snapshot_ops::{
+    CreateShardKeySnapshot, ShardKeySnapshotDescription, ShardKeySnapshotRecover,
     ShardSnapshotLocation, SnapshotDescription, SnapshotPriority,
 };
 use collection::shards::replica_set::ReplicaState;
@@ -194,6 +195,100 @@ pub async fn recover_shard_snapshot(
     Ok(())
 }
 
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn create_shard_key_snapshots(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: String,
+    request: CreateShardKeySnapshot,
+) -> Result<Vec<ShardKeySnapshotDescription>, StorageError> {
+    let collection_pass = access
+        .check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+
+    let snapshots = collection
+        .create_shard_key_snapshots(
+            &request.shard_key,
+            &toc.optional_temp_or_snapshot_temp_path()?,
+        )
+        .await?;
+
+    Ok(snapshots)
+}
+
+/// Recover the shards of a shard key, each from its own snapshot
+///
+/// Shards are recovered one after another. If recovering a shard fails, the shards before it
+/// stay recovered.
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn recover_shard_key_snapshots(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: String,
+    request: ShardKeySnapshotRecover,
+    client: HttpClient,
+) -> Result<(), StorageError> {
+    let ShardKeySnapshotRecover {
+        shard_key,
+        snapshots,
+        priority,
+        api_key,
+    } = request;
+
+    if snapshots.is_empty() {
+        return Err(StorageError::bad_input(
+            "No shard snapshots to recover from",
+        ));
+    }
+
+    {
+        let collection_pass = access
+            .check_global_access(AccessRequirements::new().manage())?
+            .issue_pass(&collection_name);
+        let collection = toc.get_collection(&collection_pass).await?;
+        let shard_ids = collection.shard_key_shard_ids(&shard_key).await?;
+
+        let mut recovered = Vec::with_capacity(snapshots.len());
+        for snapshot in &snapshots {
+            if !shard_ids.contains(&snapshot.shard_id) {
+                return Err(StorageError::bad_input(format!(
+                    "Shard {} does not belong to shard key {shard_key}",
+                    snapshot.shard_id,
+                )));
+            }
+            if recovered.contains(&snapshot.shard_id) {
+                return Err(StorageError::bad_input(format!(
+                    "Shard {} is listed more than once",
+                    snapshot.shard_id,
+                )));
+            }
+            recovered.push(snapshot.shard_id);
+        }
+    }
+
+    for snapshot in snapshots {
+        recover_shard_snapshot(
+            toc.clone(),
+            access.clone(),
+            collection_name.clone(),
+            snapshot.shard_id,
+            snapshot.location,
+            priority.unwrap_or_default(),
+            snapshot.checksum,
+            client.clone(),
+            api_key.clone(),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 /// # Cancel safety
 ///
 /// This function is *not* cancel safe.
@@ -9,6 +9,7 @@ use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 use uuid::Uuid;
 
+use crate::common::allocator::{self, AllocatorStats};
 use crate::common::telemetry_ops::app_telemetry::{AppBuildTelemetry, AppBuildTelemetryCollector};
 use crate::common::telemetry_ops::cluster_telemetry::ClusterTelemetry;
 use crate::common::telemetry_ops::collections_telemetry::CollectionsTelemetry;
@@ -34,6 +35,9 @@ pub struct TelemetryData {
     pub(crate) collections: CollectionsTelemetry,
     pub(crate) cluster: ClusterTelemetry,
     pub(crate) requests: RequestsTelemetry,
+    /// Memory statistics of the allocator, if available on this platform
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) memory: Option<AllocatorStats>,
 }
 
 impl Anonymize for TelemetryData {
@@ -44,6 +48,7 @@ impl Anonymize for TelemetryData {
             collections: self.collections.anonymize(),
             cluster: self.cluster.anonymize(),
             requests: self.requests.anonymize(),
+            memory: self.memory.clone(),
         }
     }
 }
@@ -78,12 +83,13 @@ impl TelemetryCollector {
             collections: CollectionsTelemetry::collect(detail, access, self.dispatcher.toc(access))
                 .await,
             app: AppBuildTelemetry::collect(detail, &self.app_telemetry_collector, &self.settings),
-            cluster: ClusterTelemetry::collect(detail, &self.dispatcher, &self.settings),
+            cluster: ClusterTelemetry::collect(detail, access, &self.dispatcher, &self.settings),
             requests: RequestsTelemetry::collect(
                 &self.actix_telemetry_collector.lock(),
                 &self.tonic_telemetry_collector.lock(),
                 detail,
             ),
+            memory: allocator::stats(),
         }
     }
 }
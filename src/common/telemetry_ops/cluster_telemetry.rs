@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
+
 use collection::shards::shard::PeerId;
 use common::types::{DetailsLevel, TelemetryDetail};
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use serde::Serialize;
 use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
 use storage::types::{ClusterStatus, ConsensusThreadStatus, StateRole};
 
 use crate::settings::Settings;
@@ -53,6 +56,8 @@ pub struct ClusterStatusTelemetry {
     pub is_voter: bool,
     pub peer_id: Option<PeerId>,
     pub consensus_thread_status: ConsensusThreadStatus,
+    /// Number of retried search and query requests to shards on other peers, by peer
+    pub read_retries: BTreeMap<PeerId, usize>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -67,6 +72,7 @@ pub struct ClusterTelemetry {
 impl ClusterTelemetry {
     pub fn collect(
         detail: TelemetryDetail,
+        access: &Access,
         dispatcher: &Dispatcher,
         settings: &Settings,
     ) -> ClusterTelemetry {
@@ -84,6 +90,12 @@ impl ClusterTelemetry {
                         is_voter: cluster_info.raft_info.is_voter,
                         peer_id: Some(cluster_info.peer_id),
                         consensus_thread_status: cluster_info.consensus_thread_status,
+                        read_retries: dispatcher
+                            .toc(access)
+                            .get_channel_service()
+                            .read_retries()
+                            .into_iter()
+                            .collect(),
                     }),
                 })
                 .flatten(),
@@ -114,6 +126,7 @@ impl Anonymize for ClusterStatusTelemetry {
             is_voter: self.is_voter,
             peer_id: None,
             consensus_thread_status: self.consensus_thread_status.clone(),
+            read_retries: BTreeMap::new(),
         }
     }
 }
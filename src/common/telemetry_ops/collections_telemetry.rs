@@ -1,5 +1,9 @@
+use collection::common::query_memory::QueryMemoryStatistics;
+use collection::common::query_usage::QueryUsageStatistics;
+use collection::common::resource_usage::ResourceUsageStatistics;
 use collection::config::CollectionParams;
 use collection::operations::types::OptimizersStatus;
+use collection::shards::telemetry::SearchPoolsTelemetry;
 use collection::telemetry::CollectionTelemetry;
 use common::types::{DetailsLevel, TelemetryDetail};
 use schemars::JsonSchema;
@@ -10,9 +14,20 @@ use storage::rbac::Access;
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
 pub struct CollectionsAggregatedTelemetry {
+    /// Name of the collection
+    pub id: String,
     pub vectors: usize,
     pub optimizers_status: OptimizersStatus,
     pub params: CollectionParams,
+    pub query_memory: QueryMemoryStatistics,
+    pub query_usage: QueryUsageStatistics,
+    pub resource_usage: ResourceUsageStatistics,
+    /// Maximum number of operations a remote replica lags behind the replica of this peer
+    pub max_replication_lag: u64,
+    /// Number of remote replicas lagging more than the configured threshold
+    pub degraded_replicas: usize,
+    /// Reuse of pooled memory by searches on the local shards
+    pub search_pools: SearchPoolsTelemetry,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -40,8 +55,25 @@ impl From<CollectionTelemetry> for CollectionsAggregatedTelemetry {
 
         CollectionsAggregatedTelemetry {
             vectors: telemetry.count_vectors(),
+            max_replication_lag: telemetry.max_replication_lag(),
+            degraded_replicas: telemetry.count_degraded_replicas(),
+            search_pools: telemetry.search_pools(),
             optimizers_status,
             params: telemetry.config.params,
+            query_memory: telemetry.query_memory,
+            query_usage: telemetry.query_usage,
+            resource_usage: telemetry.resource_usage,
+            id: telemetry.id,
+        }
+    }
+}
+
+impl CollectionTelemetryEnum {
+    /// Name of the collection
+    pub fn id(&self) -> &str {
+        match self {
+            CollectionTelemetryEnum::Full(telemetry) => &telemetry.id,
+            CollectionTelemetryEnum::Aggregated(telemetry) => &telemetry.id,
         }
     }
 }
@@ -100,9 +132,16 @@ impl Anonymize for CollectionTelemetryEnum {
 impl Anonymize for CollectionsAggregatedTelemetry {
     fn anonymize(&self) -> Self {
         CollectionsAggregatedTelemetry {
+            id: self.id.anonymize(),
             optimizers_status: self.optimizers_status.clone(),
             vectors: self.vectors.anonymize(),
             params: self.params.anonymize(),
+            query_memory: self.query_memory.anonymize(),
+            query_usage: self.query_usage.anonymize(),
+            resource_usage: self.resource_usage.anonymize(),
+            max_replication_lag: self.max_replication_lag,
+            degraded_replicas: self.degraded_replicas,
+            search_pools: self.search_pools,
         }
     }
 }
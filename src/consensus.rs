@@ -1277,7 +1277,8 @@ mod tests {
             ChannelService::new(settings.service.http_port, None),
             persistent_state.this_peer_id(),
             Some(operation_sender.clone()),
-        );
+        )
+        .unwrap();
         let toc_arc = Arc::new(toc);
         let storage_path = toc_arc.storage_path();
         let consensus_state: ConsensusStateRef = ConsensusManager::new(
@@ -1345,6 +1346,7 @@ mod tests {
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
+                            hybrid_vectors: None,
                         },
                     )),
                     Access::full("For test"),
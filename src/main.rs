@@ -28,6 +28,7 @@ use startup::setup_panic_hook;
 use storage::content_manager::consensus::operation_sender::OperationSender;
 use storage::content_manager::consensus::persistent::Persistent;
 use storage::content_manager::consensus_manager::{ConsensusManager, ConsensusStateRef};
+use storage::content_manager::toc::standby;
 use storage::content_manager::toc::transfer::ShardTransferDispatcher;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
@@ -38,10 +39,13 @@ use storage::rbac::Access;
 ))]
 use tikv_jemallocator::Jemalloc;
 
+use crate::common::allocator;
+use crate::common::config_reload::ConfigReloader;
 use crate::common::helpers::{
     create_general_purpose_runtime, create_search_runtime, create_update_runtime,
     load_tls_client_config,
 };
+use crate::common::http_client::HttpClient;
 use crate::common::telemetry::TelemetryCollector;
 use crate::common::telemetry_reporting::TelemetryReporter;
 use crate::greeting::welcome;
@@ -134,7 +138,7 @@ fn main() -> anyhow::Result<()> {
 
     remove_started_file_indicator();
 
-    let settings = Settings::new(args.config_path)?;
+    let settings = Settings::new(args.config_path.clone())?;
 
     let reporting_enabled = !settings.telemetry_disabled && !args.disable_telemetry;
 
@@ -148,6 +152,7 @@ fn main() -> anyhow::Result<()> {
 
     setup_panic_hook(reporting_enabled, reporting_id.to_string());
 
+    allocator::apply_profile(settings.allocator.profile);
     memory::madvise::set_global(settings.storage.mmap_advice);
     segment::vector_storage::common::set_async_scorer(settings.storage.async_scorer);
 
@@ -242,6 +247,7 @@ fn main() -> anyhow::Result<()> {
             settings.cluster.p2p.connection_pool_size,
             tls_config,
         ));
+        channel_service.read_retry_policy = settings.cluster.read_retries.to_retry_policy();
         channel_service.id_to_address = persistent_consensus_state.peer_address_by_id.clone();
         channel_service.id_to_metadata = persistent_consensus_state.peer_metadata_by_id.clone();
     }
@@ -257,7 +263,7 @@ fn main() -> anyhow::Result<()> {
         channel_service.clone(),
         persistent_consensus_state.this_peer_id(),
         propose_operation_sender.clone(),
-    );
+    )?;
 
     toc.clear_all_tmp_directories()?;
 
@@ -397,9 +403,105 @@ fn main() -> anyhow::Result<()> {
         log::info!("Telemetry reporting disabled");
     }
 
+    //
+    // Warm standby collections
+    //
+
+    let http_client = HttpClient::from_settings(&settings)?;
+    runtime_handle.spawn(standby::follow_standbys(
+        dispatcher_arc.as_ref().clone(),
+        move |api_key| Ok(http_client.client(api_key)?),
+    ));
+
+    //
+    // Eviction of memory-mapped files of cold collections
+    //
+
+    if settings.storage.mmap_budget.is_some() {
+        log::info!("Evicting memory-mapped files of cold collections under memory pressure");
+        runtime_handle.spawn(
+            dispatcher_arc
+                .toc(&FULL_ACCESS)
+                .clone()
+                .enforce_mmap_budget(),
+        );
+    }
+
+    //
+    // Background check of HNSW graphs
+    //
+
+    runtime_handle.spawn(dispatcher_arc.toc(&FULL_ACCESS).clone().check_hnsw_graphs());
+
+    //
+    // Background refresh of replication lags
+    //
+
+    runtime_handle.spawn(
+        dispatcher_arc
+            .toc(&FULL_ACCESS)
+            .clone()
+            .refresh_replication_lags(),
+    );
+
+    //
+    // Lifecycle events of collections
+    //
+
+    if settings.storage.collection_events.is_some() {
+        log::info!("Watching collection events");
+        let toc = dispatcher_arc.toc(&FULL_ACCESS).clone();
+        let dispatcher = dispatcher_arc.clone();
+        let is_leader = move || {
+            dispatcher
+                .consensus_state()
+                .map_or(true, |state| state.is_leader())
+        };
+        runtime_handle.spawn(async move {
+            if let Err(err) = toc.watch_collection_events(is_leader).await {
+                log::error!("Failed to watch collection events: {err}");
+            }
+        });
+    }
+
     // Setup subscribers to listen for issue-able events
     issues_setup::setup_subscribers(&settings);
 
+    //
+    // Reload of the configuration
+    //
+
+    let config_reloader = Arc::new(ConfigReloader::new(
+        args.config_path,
+        &settings,
+        logger_handle.clone(),
+        dispatcher_arc.toc(&FULL_ACCESS).clone(),
+    ));
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let config_reloader = config_reloader.clone();
+        runtime_handle.spawn(async move {
+            let mut hangups = match signal(SignalKind::hangup()) {
+                Ok(hangups) => hangups,
+                Err(err) => {
+                    log::error!(
+                        "Failed to listen for SIGHUP, configuration reload is disabled: {err}"
+                    );
+                    return;
+                }
+            };
+            while hangups.recv().await.is_some() {
+                log::info!("Received SIGHUP, reloading configuration");
+                if let Err(err) = config_reloader.reload().await {
+                    log::error!("Failed to reload configuration: {err}");
+                }
+            }
+        });
+    }
+
     // Helper to better log start errors
     let log_err_if_any = |server_name, result| match result {
         Err(err) => {
@@ -428,6 +530,7 @@ fn main() -> anyhow::Result<()> {
                         health_checker,
                         settings,
                         logger_handle,
+                        config_reloader,
                     ),
                 )
             })
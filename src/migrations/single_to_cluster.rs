@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use collection::config::ShardingMethod;
+use collection::operations::hybrid_vectors::hybrid_vectors_params;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::PeerId;
 use storage::content_manager::collection_meta_ops::{
@@ -41,6 +42,16 @@ pub async fn handle_existing_collections(
         let collection_state = collection_obj.state().await;
         let shards_number = collection_state.config.params.shard_number.get();
         let sharding_method = collection_state.config.params.sharding_method;
+        let hybrid_vectors = {
+            let params = &collection_state.config.params;
+            params.hybrid_vectors.as_ref().map(|hybrid_vectors| {
+                hybrid_vectors_params(
+                    hybrid_vectors,
+                    &params.vectors,
+                    params.sparse_vectors.as_ref(),
+                )
+            })
+        };
 
         let mut collection_create_operation = CreateCollectionOperation::new(
             collection_name.to_string(),
@@ -63,6 +74,7 @@ pub async fn handle_existing_collections(
                 optimizers_config: Some(collection_state.config.optimizer_config.into()),
                 init_from: None,
                 quantization_config: collection_state.config.quantization_config,
+                hybrid_vectors,
             },
         );
 
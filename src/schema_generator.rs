@@ -1,30 +1,43 @@
-use api::grpc::models::{CollectionsResponse, VersionInfo};
-use api::rest::{QueryRequest, QueryRequestBatch, QueryResponse, Record, ScoredPoint};
+use api::grpc::models::{CollectionsResponse, ErrorCode, VersionInfo};
+use api::rest::{
+    QueryDslRequest, QueryRequest, QueryRequestBatch, QueryResponse, Record, ScoredPoint,
+};
+use collection::common::external_index::ExternalIndexFormat;
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
 use collection::operations::snapshot_ops::{
+    CreateShardKeySnapshot, ShardKeySnapshotDescription, ShardKeySnapshotRecover,
     ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
 };
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
-    CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    AliasDescription, AliasHistoryResponse, CollectionClusterInfo, CollectionExistence,
+    CollectionInfo, CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest,
+    DiscoverRequestBatch, GroupsResult, HnswNeighborhood, PayloadIndexStats, PayloadIndexStatus,
+    PointChangesRequest, PointChangesResult, PointGroup, PointRequest, RecommendGroupsRequest,
+    RecommendRequest, RecommendRequestBatch, SampleRequest, SampleResult, ScoreRequest,
+    ScoreResult, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
+    SearchRequestBatch, SegmentUpgradeProgress, SegmentUpgradeRequest, ShardWalCompaction,
+    UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
 use serde::Serialize;
 use storage::content_manager::collection_meta_ops::{
-    ChangeAliasesOperation, CreateCollection, UpdateCollection,
+    ChangeAliasesOperation, CreateCollection, StandbyConfig, UpdateCollection,
 };
+use storage::content_manager::toc::alias_versions::{AliasVersion, CreateAliasVersion};
+use storage::content_manager::toc::duplicates::{DuplicatesReport, FindDuplicates};
+use storage::content_manager::toc::migration::{MigrateCollection, MigrationReport};
+use storage::content_manager::toc::mirror::MirrorStatus;
+use storage::content_manager::toc::standby::StandbyStatus;
 use storage::types::ClusterStatus;
 
+use crate::common::config_reload::ConfigReloadReport;
 use crate::common::helpers::LocksOption;
-use crate::common::points::{CreateFieldIndex, UpdateOperations};
+use crate::common::points::{CreateFieldIndex, DeletePointsResult, UpdateOperations};
 use crate::common::telemetry::TelemetryData;
 
 mod actix;
@@ -84,6 +97,36 @@ struct AllDefinitions {
     be: QueryRequest,
     bf: QueryRequestBatch,
     bg: QueryResponse,
+    bh: PointChangesRequest,
+    bi: PointChangesResult,
+    bj: QueryDslRequest,
+    bk: ExternalIndexFormat,
+    bl: SampleRequest,
+    bm: SampleResult,
+    bn: CreateShardKeySnapshot,
+    bo: ShardKeySnapshotRecover,
+    bp: ShardKeySnapshotDescription,
+    bq: MirrorStatus,
+    br: MigrateCollection,
+    bs: MigrationReport,
+    bt: ErrorCode,
+    bu: PayloadIndexStatus,
+    bv: StandbyStatus,
+    bw: HnswNeighborhood,
+    bx: ScoreRequest,
+    by: ScoreResult,
+    bz: FindDuplicates,
+    ca: DuplicatesReport,
+    cb: ConfigReloadReport,
+    cc: PayloadIndexStats,
+    cd: DeletePointsResult,
+    ce: ShardWalCompaction,
+    cf: SegmentUpgradeRequest,
+    cg: SegmentUpgradeProgress,
+    ch: AliasHistoryResponse,
+    ci: StandbyConfig,
+    cj: CreateAliasVersion,
+    ck: AliasVersion,
 }
 
 fn save_schema<T: JsonSchema>() {
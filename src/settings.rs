@@ -1,14 +1,18 @@
+use std::path::PathBuf;
+use std::time::Duration;
 use std::{env, io};
 
 use api::grpc::transport_channel_pool::{
-    DEFAULT_CONNECT_TIMEOUT, DEFAULT_GRPC_TIMEOUT, DEFAULT_POOL_SIZE,
+    RetryPolicy, DEFAULT_CONNECT_TIMEOUT, DEFAULT_GRPC_TIMEOUT, DEFAULT_POOL_SIZE, DEFAULT_RETRIES,
 };
 use collection::operations::validation;
 use config::{Config, ConfigError, Environment, File, FileFormat, Source};
 use serde::Deserialize;
 use storage::types::StorageConfig;
+use tonic::Code;
 use validator::Validate;
 
+use crate::common::allocator::AllocatorConfig;
 use crate::common::debugger::DebuggerConfig;
 use crate::tracing;
 
@@ -46,6 +50,38 @@ pub struct ServiceConfig {
 
     /// How much time is considered too long for a query to execute.
     pub slow_query_secs: Option<f32>,
+
+    /// Path of a Unix domain socket to additionally serve the REST API on.
+    #[serde(default)]
+    pub http_unix_socket: Option<PathBuf>,
+
+    /// Path of a Unix domain socket to additionally serve the gRPC API on.
+    /// Only used if gRPC is enabled.
+    #[serde(default)]
+    pub grpc_unix_socket: Option<PathBuf>,
+
+    /// Permissions of the created socket files, as an octal mode, e.g. `660`.
+    #[serde(default)]
+    pub unix_socket_permissions: Option<String>,
+
+    /// Additionally serve the gRPC API over HTTP/3 (QUIC).
+    /// Only used if gRPC is enabled and Qdrant is built with the `grpc-http3` feature.
+    #[serde(default)]
+    pub grpc_http3: Option<GrpcHttp3Config>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)] // necessary because fields are only used with the `grpc-http3` feature
+pub struct GrpcHttp3Config {
+    /// UDP port to bind HTTP/3 on. Defaults to the gRPC port.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Server certificate chain file. Defaults to the certificate of the TLS config.
+    #[serde(default)]
+    pub cert: Option<String>,
+    /// Server private key file. Defaults to the key of the TLS config.
+    #[serde(default)]
+    pub key: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default, Validate)]
@@ -63,6 +99,9 @@ pub struct ClusterConfig {
     #[serde(default)]
     #[validate]
     pub consensus: ConsensusConfig,
+    /// Retries of search and query requests to shards on other peers
+    #[serde(default)]
+    pub read_retries: ReadRetriesConfig,
 }
 
 #[derive(Debug, Deserialize, Clone, Validate)]
@@ -113,6 +152,68 @@ impl Default for ConsensusConfig {
     }
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReadRetriesConfig {
+    /// Number of retries after the first attempt
+    #[serde(default = "default_read_retries")]
+    pub max_retries: usize,
+    /// Backoff before the first retry, doubled for each next retry
+    #[serde(default = "default_read_retry_backoff_ms")]
+    pub backoff_ms: u64,
+    /// Errors returned by the peer to retry. Failures to connect to the peer are always retried.
+    #[serde(default = "default_read_retriable_errors")]
+    pub retriable_errors: Vec<RetriableError>,
+}
+
+impl Default for ReadRetriesConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_read_retries(),
+            backoff_ms: default_read_retry_backoff_ms(),
+            retriable_errors: default_read_retriable_errors(),
+        }
+    }
+}
+
+impl ReadRetriesConfig {
+    pub fn to_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            retries: self.max_retries,
+            backoff: Duration::from_millis(self.backoff_ms),
+            retriable_codes: self
+                .retriable_errors
+                .iter()
+                .map(|&error| Code::from(error))
+                .collect(),
+        }
+    }
+}
+
+/// gRPC status codes, which may be transient
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetriableError {
+    Cancelled,
+    DeadlineExceeded,
+    ResourceExhausted,
+    Aborted,
+    Internal,
+    Unavailable,
+}
+
+impl From<RetriableError> for Code {
+    fn from(error: RetriableError) -> Self {
+        match error {
+            RetriableError::Cancelled => Code::Cancelled,
+            RetriableError::DeadlineExceeded => Code::DeadlineExceeded,
+            RetriableError::ResourceExhausted => Code::ResourceExhausted,
+            RetriableError::Aborted => Code::Aborted,
+            RetriableError::Internal => Code::Internal,
+            RetriableError::Unavailable => Code::Unavailable,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Validate)]
 pub struct TlsConfig {
     pub cert: String,
@@ -143,11 +244,16 @@ pub struct Settings {
     pub tls: Option<TlsConfig>,
     #[serde(default)]
     pub debugger: DebuggerConfig,
+    #[serde(default)]
+    pub allocator: AllocatorConfig,
     /// A list of messages for errors that happened during loading the configuration. We collect
     /// them and store them here while loading because then our logger is not configured yet.
     /// We therefore need to log these messages later, after the logger is ready.
     #[serde(default, skip)]
     pub load_errors: Vec<LogMsg>,
+    /// The configuration as it was merged from all sources, to report it for diagnostics.
+    #[serde(default, skip)]
+    pub raw_config: serde_json::Value,
 }
 
 impl Settings {
@@ -199,8 +305,11 @@ impl Settings {
         config = config.add_source(Environment::with_prefix("QDRANT").separator("__"));
 
         // Build and merge config and deserialize into Settings, attach any load errors we had
-        let mut settings: Settings = config.build()?.try_deserialize()?;
+        let config = config.build()?;
+        let raw_config = config.clone().try_deserialize()?;
+        let mut settings: Settings = config.try_deserialize()?;
         settings.load_errors.extend(load_errors);
+        settings.raw_config = raw_config;
         Ok(settings)
     }
 
@@ -292,6 +401,22 @@ const fn default_message_timeout_tics() -> u64 {
     10
 }
 
+const fn default_read_retries() -> usize {
+    DEFAULT_RETRIES
+}
+
+const fn default_read_retry_backoff_ms() -> u64 {
+    100
+}
+
+fn default_read_retriable_errors() -> Vec<RetriableError> {
+    vec![
+        RetriableError::Cancelled,
+        RetriableError::Unavailable,
+        RetriableError::Internal,
+    ]
+}
+
 const fn default_tls_cert_ttl() -> Option<u64> {
     // Default one hour
     Some(3600)
@@ -319,6 +444,28 @@ mod tests {
             .expect("failed to validate default config");
     }
 
+    #[test]
+    fn test_read_retries_config() {
+        let config: ReadRetriesConfig = serde_json::from_value(serde_json::json!({
+            "max_retries": 5,
+            "retriable_errors": ["unavailable", "resource_exhausted"],
+        }))
+        .unwrap();
+
+        let policy = config.to_retry_policy();
+        assert_eq!(policy.retries, 5);
+        assert_eq!(policy.backoff, Duration::from_millis(100));
+        assert_eq!(
+            policy.retriable_codes,
+            vec![Code::Unavailable, Code::ResourceExhausted],
+        );
+
+        assert_eq!(
+            ReadRetriesConfig::default().to_retry_policy(),
+            RetryPolicy::default(),
+        );
+    }
+
     #[sealed_test(files = ["config/config.yaml", "config/development.yaml"])]
     fn test_runtime_development_config() {
         env::set_var("RUN_MODE", "development");
@@ -132,7 +132,9 @@ pub fn recover_full_snapshot(
         if alias_persistence.get(&alias).is_some() && !force {
             panic!("Alias {alias} already exists. Use --force-snapshot to overwrite it.");
         }
-        alias_persistence.insert(alias, collection_name).unwrap();
+        alias_persistence
+            .insert(alias, collection_name, None)
+            .unwrap();
     }
 
     // Remove temporary directory
@@ -6,15 +6,15 @@ use api::grpc::qdrant::{
     ChangeAliases, CollectionClusterInfoRequest, CollectionClusterInfoResponse,
     CollectionExistsRequest, CollectionExistsResponse, CollectionOperationResponse,
     CreateCollection, CreateShardKeyRequest, CreateShardKeyResponse, DeleteCollection,
-    DeleteShardKeyRequest, DeleteShardKeyResponse, GetCollectionInfoRequest,
-    GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
+    DeleteShardKeyRequest, DeleteShardKeyResponse, GetAliasHistoryRequest, GetAliasHistoryResponse,
+    GetCollectionInfoRequest, GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
     ListCollectionAliasesRequest, ListCollectionsRequest, ListCollectionsResponse,
     UpdateCollection, UpdateCollectionClusterSetupRequest, UpdateCollectionClusterSetupResponse,
 };
 use collection::operations::cluster_ops::{
     ClusterOperations, CreateShardingKeyOperation, DropShardingKeyOperation,
 };
-use collection::operations::types::CollectionsAliasesResponse;
+use collection::operations::types::{AliasHistoryResponse, CollectionsAliasesResponse};
 use storage::content_manager::conversions::error_to_status;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
@@ -160,6 +160,28 @@ impl Collections for CollectionsService {
         Ok(Response::new(response))
     }
 
+    async fn get_alias_history(
+        &self,
+        mut request: Request<GetAliasHistoryRequest>,
+    ) -> Result<Response<GetAliasHistoryResponse>, Status> {
+        validate(request.get_ref())?;
+        let timing = Instant::now();
+        let access = extract_access(&mut request);
+        let GetAliasHistoryRequest { alias_name } = request.into_inner();
+        let AliasHistoryResponse {
+            alias_name,
+            changes,
+        } = do_get_alias_history(self.dispatcher.toc(&access), access, &alias_name)
+            .await
+            .map_err(error_to_status)?;
+        let response = GetAliasHistoryResponse {
+            alias_name,
+            changes: changes.into_iter().map(|change| change.into()).collect(),
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
+
     async fn collection_exists(
         &self,
         mut request: Request<CollectionExistsRequest>,
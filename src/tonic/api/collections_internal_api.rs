@@ -5,8 +5,9 @@ use api::grpc::qdrant::collections_internal_server::CollectionsInternal;
 use api::grpc::qdrant::{
     CollectionOperationResponse, GetCollectionInfoRequestInternal, GetCollectionInfoResponse,
     GetShardRecoveryPointRequest, GetShardRecoveryPointResponse, InitiateShardTransferRequest,
-    UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    UpdateShardCutoffPointRequest, WaitForSessionClocksRequest, WaitForShardStateRequest,
 };
+use collection::operations::session_token::ShardSessionClock;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::{Access, AccessRequirements, CollectionPass};
@@ -207,4 +208,55 @@ impl CollectionsInternal for CollectionsInternalService {
         };
         Ok(Response::new(response))
     }
+
+    async fn wait_for_session_clocks(
+        &self,
+        request: Request<WaitForSessionClocksRequest>,
+    ) -> Result<Response<CollectionOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+        let WaitForSessionClocksRequest {
+            collection_name,
+            shard_id,
+            clocks,
+            timeout_ms,
+        } = request.into_inner();
+
+        let session_clocks: Vec<_> = clocks
+            .into_iter()
+            .map(|clock| ShardSessionClock {
+                shard_id,
+                peer_id: clock.peer_id,
+                clock_id: clock.clock_id,
+                clock_tick: clock.clock_tick,
+                replica_peer_id: self.toc.this_peer_id,
+            })
+            .collect();
+
+        let collection_read = self
+            .toc
+            .get_collection(&full_access_pass(&collection_name)?)
+            .await
+            .map_err(|err| {
+                Status::not_found(format!(
+                    "Collection {collection_name} could not be found: {err}"
+                ))
+            })?;
+
+        let reached = collection_read
+            .wait_for_local_session_clocks(
+                shard_id,
+                &session_clocks,
+                Duration::from_millis(timeout_ms),
+            )
+            .await
+            .map_err(error_to_status)?;
+
+        let response = CollectionOperationResponse {
+            result: reached,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
 }
@@ -6,11 +6,16 @@ mod points_common;
 pub mod points_internal_api;
 pub mod raft_api;
 pub mod snapshots_api;
+mod update_deduplication;
+
+use std::time::Duration;
 
 use collection::operations::validation;
-use tonic::Status;
+use tonic::{Request, Status};
 use validator::Validate;
 
+use crate::common::request_timeout::{parse_grpc_timeout, GRPC_TIMEOUT_HEADER};
+
 /// Validate the given request and fail on error.
 ///
 /// Returns validation error on failure.
@@ -20,6 +25,12 @@ fn validate(request: &impl Validate) -> Result<(), Status> {
     })
 }
 
+/// Timeout of the client, given in the `grpc-timeout` header of the request
+fn client_timeout<T>(request: &Request<T>) -> Option<Duration> {
+    let value = request.metadata().get(GRPC_TIMEOUT_HEADER)?;
+    parse_grpc_timeout(value.to_str().ok()?)
+}
+
 /// Validate the given request. Returns validation error on failure.
 fn validate_and_log(request: &impl Validate) {
     if let Err(ref err) = request.validate() {
@@ -21,7 +21,8 @@ use super::points_common::{
     delete_vectors, discover, discover_batch, query, query_batch, recommend_groups, search_groups,
     update_batch, update_vectors,
 };
-use super::validate;
+use super::{client_timeout, validate};
+use crate::common::request_timeout::min_timeout;
 use crate::tonic::api::points_common::{
     clear_payload, convert_shard_selector_for_read, core_search_batch, count, create_field_index,
     delete, delete_field_index, delete_payload, get, overwrite_payload, recommend, recommend_batch,
@@ -291,9 +292,13 @@ impl Points for PointsService {
             search_points,
             read_consistency,
             timeout,
+            session_token,
         } = request.into_inner();
 
         let timeout = timeout.map(Duration::from_secs);
+        let with_stats = search_points
+            .iter()
+            .any(|search_point| search_point.with_stats.unwrap_or_default());
 
         let mut requests = Vec::new();
 
@@ -313,6 +318,8 @@ impl Points for PointsService {
             read_consistency,
             access,
             timeout,
+            session_token,
+            with_stats,
         )
         .await
     }
@@ -369,6 +376,7 @@ impl Points for PointsService {
             recommend_points,
             read_consistency,
             timeout,
+            session_token,
         } = request.into_inner();
         recommend_batch(
             self.dispatcher.toc(&access),
@@ -377,6 +385,7 @@ impl Points for PointsService {
             read_consistency,
             access,
             timeout.map(Duration::from_secs),
+            session_token,
         )
         .await
     }
@@ -416,6 +425,7 @@ impl Points for PointsService {
             discover_points,
             read_consistency,
             timeout,
+            session_token,
         } = request.into_inner();
 
         discover_batch(
@@ -425,6 +435,7 @@ impl Points for PointsService {
             read_consistency,
             access,
             timeout.map(Duration::from_secs),
+            session_token,
         )
         .await
     }
@@ -452,11 +463,13 @@ impl Points for PointsService {
     ) -> Result<Response<QueryResponse>, Status> {
         validate(request.get_ref())?;
         let access = extract_access(&mut request);
+        let client_timeout = client_timeout(&request);
         query(
             self.dispatcher.toc(&access),
             request.into_inner(),
             None,
             access,
+            client_timeout,
         )
         .await
     }
@@ -467,14 +480,16 @@ impl Points for PointsService {
     ) -> Result<Response<QueryBatchResponse>, Status> {
         validate(request.get_ref())?;
         let access = extract_access(&mut request);
+        let client_timeout = client_timeout(&request);
         let request = request.into_inner();
         let QueryBatchPoints {
             collection_name,
             query_points,
             read_consistency,
             timeout,
+            session_token,
         } = request;
-        let timeout = timeout.map(Duration::from_secs);
+        let timeout = min_timeout(timeout.map(Duration::from_secs), client_timeout);
         query_batch(
             self.dispatcher.toc(&access),
             collection_name,
@@ -482,6 +497,7 @@ impl Points for PointsService {
             read_consistency,
             access,
             timeout,
+            session_token,
         )
         .await
     }
@@ -1,3 +1,4 @@
+use std::str::FromStr as _;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -17,6 +18,7 @@ use api::grpc::qdrant::{
     UpsertPoints,
 };
 use api::rest::{OrderByInterface, ShardKeySelector};
+use collection::common::search_stats;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
     try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
@@ -26,6 +28,7 @@ use collection::operations::point_ops::{
     self, PointInsertOperations, PointOperations, PointSyncOperation, PointsList,
 };
 use collection::operations::query_enum::QueryEnum;
+use collection::operations::session_token::SessionToken;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PointRequestInternal,
@@ -42,7 +45,7 @@ use segment::types::{
     ExtendedPointId, Filter, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
 };
 use storage::content_manager::conversions::error_to_status;
-use storage::content_manager::toc::TableOfContent;
+use storage::content_manager::toc::{query_limits, TableOfContent};
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 use tonic::{Response, Status};
@@ -54,6 +57,7 @@ use crate::common::points::{
     do_query_batch_points, do_query_points, do_scroll_points, do_search_batch_points,
     do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
 };
+use crate::common::request_timeout::min_timeout;
 
 fn extract_points_selector(
     points_selector: Option<PointsSelector>,
@@ -98,6 +102,13 @@ pub(crate) fn convert_shard_selector_for_read(
     }
 }
 
+fn parse_session_token(session_token: Option<&str>) -> Result<Option<SessionToken>, Status> {
+    session_token
+        .map(SessionToken::from_str)
+        .transpose()
+        .map_err(Status::invalid_argument)
+}
+
 pub async fn upsert(
     toc: Arc<TableOfContent>,
     upsert_points: UpsertPoints,
@@ -965,6 +976,8 @@ pub async fn search(
         timeout,
         shard_key_selector,
         sparse_indices,
+        session_token,
+        with_stats,
     } = search_points;
 
     let vector_struct =
@@ -988,19 +1001,24 @@ pub async fn search(
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let timing = Instant::now();
-    let scored_points = do_core_search_points(
-        toc,
-        &collection_name,
-        search_request,
-        read_consistency,
-        shard_selector,
-        access,
-        timeout.map(Duration::from_secs),
-    )
-    .await
-    .map_err(error_to_status)?;
+    let ((scored_points, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats.unwrap_or_default(),
+        do_core_search_points(
+            toc,
+            &collection_name,
+            search_request,
+            read_consistency,
+            shard_selector,
+            access,
+            timeout.map(Duration::from_secs),
+            session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let scored_points = scored_points.map_err(error_to_status)?;
 
     let response = SearchResponse {
         result: scored_points
@@ -1008,6 +1026,8 @@ pub async fn search(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1020,30 +1040,40 @@ pub async fn core_search_batch(
     read_consistency: Option<ReadConsistencyGrpc>,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<String>,
+    with_stats: bool,
 ) -> Result<Response<SearchBatchResponse>, Status> {
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let timing = Instant::now();
 
-    let scored_points = do_search_batch_points(
-        toc,
-        &collection_name,
-        requests,
-        read_consistency,
-        access,
-        timeout,
-    )
-    .await
-    .map_err(error_to_status)?;
+    let ((scored_points, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats,
+        do_search_batch_points(
+            toc,
+            &collection_name,
+            requests,
+            read_consistency,
+            access,
+            timeout,
+            session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let scored_points = scored_points.map_err(error_to_status)?;
 
     let response = SearchBatchResponse {
         result: scored_points
             .into_iter()
             .map(|points| BatchResult {
                 result: points.into_iter().map(|p| p.into()).collect(),
+                stats: None,
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1057,6 +1087,7 @@ pub async fn core_search_list(
     shard_selection: Option<ShardId>,
     access: Access,
     timeout: Option<Duration>,
+    with_stats: bool,
 ) -> Result<Response<SearchBatchResponse>, Status> {
     let searches: Result<Vec<_>, Status> =
         search_points.into_iter().map(TryInto::try_into).collect();
@@ -1079,26 +1110,32 @@ pub async fn core_search_list(
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
-    let scored_points = toc
-        .core_search_batch(
+    let (scored_points, stats) = search_stats::collect(
+        with_stats,
+        toc.core_search_batch(
             &collection_name,
             request,
             read_consistency,
             shard_selection,
             access,
             timeout,
-        )
-        .await
-        .map_err(error_to_status)?;
+            None,
+        ),
+    )
+    .await;
+    let scored_points = scored_points.map_err(error_to_status)?;
 
     let response = SearchBatchResponse {
         result: scored_points
             .into_iter()
             .map(|points| BatchResult {
                 result: points.into_iter().map(|p| p.into()).collect(),
+                stats: None,
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings: vec![],
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1117,29 +1154,38 @@ pub async fn search_groups(
         read_consistency,
         timeout,
         shard_key_selector,
+        session_token,
+        with_stats,
         ..
     } = search_point_groups;
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
 
     let timing = Instant::now();
-    let groups_result = crate::common::points::do_search_point_groups(
-        toc,
-        &collection_name,
-        search_groups_request,
-        read_consistency,
-        shard_selector,
-        access,
-        timeout.map(Duration::from_secs),
-    )
-    .await
-    .map_err(error_to_status)?;
+    let ((groups_result, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats.unwrap_or_default(),
+        crate::common::points::do_search_point_groups(
+            toc,
+            &collection_name,
+            search_groups_request,
+            read_consistency,
+            shard_selector,
+            access,
+            timeout.map(Duration::from_secs),
+            session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let groups_result = groups_result.map_err(error_to_status)?;
 
     let response = SearchGroupsResponse {
         result: Some(groups_result.into()),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1170,6 +1216,9 @@ pub async fn recommend(
         read_consistency,
         timeout,
         shard_key_selector,
+        skip_missing,
+        session_token,
+        with_stats,
     } = recommend_points;
 
     let timeout = timeout.map(Duration::from_secs);
@@ -1211,24 +1260,30 @@ pub async fn recommend(
         score_threshold,
         using: using.map(|u| u.into()),
         lookup_from: lookup_from.map(|l| l.into()),
+        skip_missing,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
 
     let timing = Instant::now();
-    let recommended_points = toc
-        .recommend(
-            &collection_name,
-            request,
-            read_consistency,
-            shard_selector,
-            access,
-            timeout,
-        )
-        .await
-        .map_err(error_to_status)?;
+    let ((recommended_points, stats), warnings) =
+        query_limits::collect_warnings(search_stats::collect(
+            with_stats.unwrap_or_default(),
+            toc.recommend(
+                &collection_name,
+                request,
+                read_consistency,
+                shard_selector,
+                access,
+                timeout,
+                session_token.as_ref(),
+            ),
+        ))
+        .await;
+    let recommended_points = recommended_points.map_err(error_to_status)?;
 
     let response = RecommendResponse {
         result: recommended_points
@@ -1236,6 +1291,8 @@ pub async fn recommend(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1248,7 +1305,11 @@ pub async fn recommend_batch(
     read_consistency: Option<ReadConsistencyGrpc>,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<String>,
 ) -> Result<Response<RecommendBatchResponse>, Status> {
+    let with_stats = recommend_points
+        .iter()
+        .any(|request| request.with_stats.unwrap_or_default());
     let mut requests = Vec::with_capacity(recommend_points.len());
 
     for mut request in recommend_points {
@@ -1260,27 +1321,34 @@ pub async fn recommend_batch(
     }
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let timing = Instant::now();
-    let scored_points = toc
-        .recommend_batch(
+    let ((scored_points, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats,
+        toc.recommend_batch(
             &collection_name,
             requests,
             read_consistency,
             access,
             timeout,
-        )
-        .await
-        .map_err(error_to_status)?;
+            session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let scored_points = scored_points.map_err(error_to_status)?;
 
     let response = RecommendBatchResponse {
         result: scored_points
             .into_iter()
             .map(|points| BatchResult {
                 result: points.into_iter().map(|p| p.into()).collect(),
+                stats: None,
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1298,29 +1366,38 @@ pub async fn recommend_groups(
         read_consistency,
         timeout,
         shard_key_selector,
+        session_token,
+        with_stats,
         ..
     } = recommend_point_groups;
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
 
     let timing = Instant::now();
-    let groups_result = crate::common::points::do_recommend_point_groups(
-        toc,
-        &collection_name,
-        recommend_groups_request,
-        read_consistency,
-        shard_selector,
-        access,
-        timeout.map(Duration::from_secs),
-    )
-    .await
-    .map_err(error_to_status)?;
+    let ((groups_result, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats.unwrap_or_default(),
+        crate::common::points::do_recommend_point_groups(
+            toc,
+            &collection_name,
+            recommend_groups_request,
+            read_consistency,
+            shard_selector,
+            access,
+            timeout.map(Duration::from_secs),
+            session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let groups_result = groups_result.map_err(error_to_status)?;
 
     let response = RecommendGroupsResponse {
         result: Some(groups_result.into()),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1331,6 +1408,8 @@ pub async fn discover(
     discover_points: DiscoverPoints,
     access: Access,
 ) -> Result<Response<DiscoverResponse>, Status> {
+    let session_token = parse_session_token(discover_points.session_token.as_deref())?;
+    let with_stats = discover_points.with_stats.unwrap_or_default();
     let (request, collection_name, read_consistency, timeout, shard_key_selector) =
         try_discover_request_from_grpc(discover_points)?;
 
@@ -1338,17 +1417,21 @@ pub async fn discover(
 
     let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
 
-    let discovered_points = toc
-        .discover(
-            &collection_name,
-            request,
-            read_consistency,
-            shard_selector,
-            access,
-            timeout,
-        )
-        .await
-        .map_err(error_to_status)?;
+    let ((discovered_points, stats), warnings) =
+        query_limits::collect_warnings(search_stats::collect(
+            with_stats,
+            toc.discover(
+                &collection_name,
+                request,
+                read_consistency,
+                shard_selector,
+                access,
+                timeout,
+                session_token.as_ref(),
+            ),
+        ))
+        .await;
+    let discovered_points = discovered_points.map_err(error_to_status)?;
 
     let response = DiscoverResponse {
         result: discovered_points
@@ -1356,6 +1439,8 @@ pub async fn discover(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1368,7 +1453,11 @@ pub async fn discover_batch(
     read_consistency: Option<ReadConsistencyGrpc>,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<String>,
 ) -> Result<Response<DiscoverBatchResponse>, Status> {
+    let with_stats = discover_points
+        .iter()
+        .any(|request| request.with_stats.unwrap_or_default());
     let mut requests = Vec::with_capacity(discover_points.len());
 
     for discovery_request in discover_points {
@@ -1379,27 +1468,34 @@ pub async fn discover_batch(
     }
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let timing = Instant::now();
-    let scored_points = toc
-        .discover_batch(
+    let ((scored_points, stats), warnings) = query_limits::collect_warnings(search_stats::collect(
+        with_stats,
+        toc.discover_batch(
             &collection_name,
             requests,
             read_consistency,
             access,
             timeout,
-        )
-        .await
-        .map_err(error_to_status)?;
+            session_token.as_ref(),
+        ),
+    ))
+    .await;
+    let scored_points = scored_points.map_err(error_to_status)?;
 
     let response = DiscoverBatchResponse {
         result: scored_points
             .into_iter()
             .map(|points| BatchResult {
                 result: points.into_iter().map(|p| p.into()).collect(),
+                stats: None,
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1420,6 +1516,7 @@ pub async fn scroll(
         read_consistency,
         shard_key_selector,
         order_by,
+        session_token,
     } = scroll_points;
 
     let scroll_request = ScrollRequestInternal {
@@ -1437,6 +1534,7 @@ pub async fn scroll(
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
 
@@ -1448,6 +1546,7 @@ pub async fn scroll(
         read_consistency,
         shard_selector,
         access,
+        session_token.as_ref(),
     )
     .await
     .map_err(error_to_status)?;
@@ -1478,6 +1577,7 @@ pub async fn count(
         exact,
         read_consistency,
         shard_key_selector,
+        session_token,
     } = count_points;
 
     let count_request = collection::operations::types::CountRequestInternal {
@@ -1486,6 +1586,7 @@ pub async fn count(
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
 
@@ -1497,6 +1598,7 @@ pub async fn count(
         read_consistency,
         shard_selector,
         access,
+        session_token.as_ref(),
     )
     .await
     .map_err(error_to_status)?;
@@ -1522,6 +1624,8 @@ pub async fn get(
         with_vectors,
         read_consistency,
         shard_key_selector,
+        as_of_version,
+        session_token,
     } = get_points;
 
     let point_request = PointRequestInternal {
@@ -1533,9 +1637,11 @@ pub async fn get(
         with_vector: with_vectors
             .map(|selector| selector.into())
             .unwrap_or_default(),
+        as_of_version,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
 
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
 
@@ -1548,6 +1654,7 @@ pub async fn get(
         read_consistency,
         shard_selector,
         access,
+        session_token.as_ref(),
     )
     .await
     .map_err(error_to_status)?;
@@ -1565,6 +1672,7 @@ pub async fn query(
     query_points: QueryPoints,
     shard_selection: Option<ShardId>,
     access: Access,
+    client_timeout: Option<Duration>,
 ) -> Result<Response<QueryResponse>, Status> {
     let shard_key_selector = query_points.shard_key_selector.clone();
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
@@ -1573,11 +1681,15 @@ pub async fn query(
         .clone()
         .map(TryFrom::try_from)
         .transpose()?;
-    let timeout = query_points.timeout.map(Duration::from_secs);
+    let timeout = min_timeout(
+        query_points.timeout.map(Duration::from_secs),
+        client_timeout,
+    );
+    let session_token = parse_session_token(query_points.session_token.as_deref())?;
     let collection_name = query_points.collection_name.clone();
     let request = CollectionQueryRequest::try_from(query_points)?;
     let timing = Instant::now();
-    let scored_points = do_query_points(
+    let (scored_points, warnings) = query_limits::collect_warnings(do_query_points(
         toc,
         &collection_name,
         request,
@@ -1585,9 +1697,10 @@ pub async fn query(
         shard_selector,
         access,
         timeout,
-    )
-    .await
-    .map_err(error_to_status)?;
+        session_token.as_ref(),
+    ))
+    .await;
+    let (scored_points, stats) = scored_points.map_err(error_to_status)?;
 
     let response = QueryResponse {
         result: scored_points
@@ -1595,6 +1708,8 @@ pub async fn query(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: stats.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -1607,8 +1722,10 @@ pub async fn query_batch(
     read_consistency: Option<ReadConsistencyGrpc>,
     access: Access,
     timeout: Option<Duration>,
+    session_token: Option<String>,
 ) -> Result<Response<QueryBatchResponse>, Status> {
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let session_token = parse_session_token(session_token.as_deref())?;
     let mut requests = Vec::with_capacity(points.len());
     for query_points in points {
         let shard_key_selector = query_points.shard_key_selector.clone();
@@ -1617,25 +1734,30 @@ pub async fn query_batch(
         requests.push((request, shard_selector));
     }
     let timing = Instant::now();
-    let scored_points = do_query_batch_points(
+    let (scored_points, warnings) = query_limits::collect_warnings(do_query_batch_points(
         toc,
         &collection_name,
         requests,
         read_consistency,
         access,
         timeout,
-    )
-    .await
-    .map_err(error_to_status)?;
+        session_token.as_ref(),
+    ))
+    .await;
+    let (scored_points, stats) = scored_points.map_err(error_to_status)?;
 
     let response = QueryBatchResponse {
         result: scored_points
             .into_iter()
-            .map(|points| BatchResult {
+            .zip(stats)
+            .map(|(points, stats)| BatchResult {
                 result: points.into_iter().map(|p| p.into()).collect(),
+                stats: stats.map(From::from),
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        warnings,
+        stats: None,
     };
 
     Ok(Response::new(response))
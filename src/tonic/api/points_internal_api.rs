@@ -6,21 +6,27 @@ use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
     DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, IntermediateResult, PointsOperationResponseInternal, QueryBatchPointsInternal,
-    QueryBatchResponseInternal, QueryResultInternal, QueryShardPoints, RecommendPointsInternal,
-    RecommendResponse, ScrollPointsInternal, ScrollResponse, SearchBatchResponse,
-    SetPayloadPointsInternal, SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    GetResponse, GroupAggregatesInternal, GroupAggregatesResponseInternal, IntermediateResult,
+    PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
+    QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
+    ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
+    SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
+use collection::shards::conversions::{
+    group_aggregates_to_grpc, try_group_aggregates_request_from_grpc,
+};
 use collection::shards::shard::ShardId;
 use itertools::Itertools;
+use segment::index::search_stats::SearchStats;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
-use storage::rbac::Access;
+use storage::rbac::{Access, AccessRequirements};
 use tonic::{Request, Response, Status};
 
 use super::points_common::core_search_list;
+use super::update_deduplication::UpdateDeduplication;
 use super::validate_and_log;
 use crate::tonic::api::points_common::{
     clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
@@ -33,11 +39,15 @@ const FULL_ACCESS: Access = Access::full("Internal API");
 /// This API is intended for P2P communication within a distributed deployment.
 pub struct PointsInternalService {
     toc: Arc<TableOfContent>,
+    updates: UpdateDeduplication,
 }
 
 impl PointsInternalService {
     pub fn new(toc: Arc<TableOfContent>) -> Self {
-        Self { toc }
+        Self {
+            toc,
+            updates: UpdateDeduplication::default(),
+        }
     }
 }
 
@@ -47,6 +57,7 @@ pub async fn query_batch_internal(
     query_points: Vec<QueryShardPoints>,
     shard_selection: Option<ShardId>,
     timeout: Option<Duration>,
+    with_stats: Option<bool>,
 ) -> Result<Response<QueryBatchResponseInternal>, Status> {
     let batch_requests: Vec<_> = query_points
         .into_iter()
@@ -65,8 +76,18 @@ pub async fn query_batch_internal(
         Some(shard_id) => ShardSelectorInternal::ShardId(shard_id),
     };
 
+    let search_stats = with_stats
+        .unwrap_or_default()
+        .then(|| Arc::new(SearchStats::default()));
+
     let batch_response = toc
-        .query_batch_internal(&collection_name, batch_requests, shard_selection, timeout)
+        .query_batch_internal(
+            &collection_name,
+            batch_requests,
+            shard_selection,
+            timeout,
+            search_stats.clone(),
+        )
         .await
         .map_err(error_to_status)?;
 
@@ -83,6 +104,7 @@ pub async fn query_batch_internal(
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        stats: search_stats.map(|search_stats| search_stats.stats().into()),
     };
 
     Ok(Response::new(response))
@@ -100,19 +122,24 @@ impl PointsInternal for PointsInternalService {
             upsert_points,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let upsert_points =
             upsert_points.ok_or_else(|| Status::invalid_argument("UpsertPoints is missing"))?;
 
-        upsert(
-            self.toc.clone(),
-            upsert_points,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                upsert(
+                    self.toc.clone(),
+                    upsert_points,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn delete(
@@ -125,19 +152,24 @@ impl PointsInternal for PointsInternalService {
             delete_points,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let delete_points =
             delete_points.ok_or_else(|| Status::invalid_argument("DeletePoints is missing"))?;
 
-        delete(
-            self.toc.clone(),
-            delete_points,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                delete(
+                    self.toc.clone(),
+                    delete_points,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn update_vectors(
@@ -150,19 +182,24 @@ impl PointsInternal for PointsInternalService {
 
         let shard_id = request.shard_id;
         let clock_tag = request.clock_tag;
+        let update_id = request.update_id;
 
         let update_point_vectors = request
             .update_vectors
             .ok_or_else(|| Status::invalid_argument("UpdateVectors is missing"))?;
 
-        update_vectors(
-            self.toc.clone(),
-            update_point_vectors,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                update_vectors(
+                    self.toc.clone(),
+                    update_point_vectors,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn delete_vectors(
@@ -175,19 +212,24 @@ impl PointsInternal for PointsInternalService {
 
         let shard_id = request.shard_id;
         let clock_tag = request.clock_tag;
+        let update_id = request.update_id;
 
         let delete_point_vectors = request
             .delete_vectors
             .ok_or_else(|| Status::invalid_argument("DeleteVectors is missing"))?;
 
-        delete_vectors(
-            self.toc.clone(),
-            delete_point_vectors,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                delete_vectors(
+                    self.toc.clone(),
+                    delete_point_vectors,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn set_payload(
@@ -200,19 +242,24 @@ impl PointsInternal for PointsInternalService {
             set_payload_points,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let set_payload_points = set_payload_points
             .ok_or_else(|| Status::invalid_argument("SetPayloadPoints is missing"))?;
 
-        set_payload(
-            self.toc.clone(),
-            set_payload_points,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                set_payload(
+                    self.toc.clone(),
+                    set_payload_points,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn overwrite_payload(
@@ -225,19 +272,24 @@ impl PointsInternal for PointsInternalService {
             set_payload_points,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let set_payload_points = set_payload_points
             .ok_or_else(|| Status::invalid_argument("SetPayloadPoints is missing"))?;
 
-        overwrite_payload(
-            self.toc.clone(),
-            set_payload_points,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                overwrite_payload(
+                    self.toc.clone(),
+                    set_payload_points,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn delete_payload(
@@ -250,19 +302,24 @@ impl PointsInternal for PointsInternalService {
             delete_payload_points,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let delete_payload_points = delete_payload_points
             .ok_or_else(|| Status::invalid_argument("DeletePayloadPoints is missing"))?;
 
-        delete_payload(
-            self.toc.clone(),
-            delete_payload_points,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                delete_payload(
+                    self.toc.clone(),
+                    delete_payload_points,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn clear_payload(
@@ -275,19 +332,24 @@ impl PointsInternal for PointsInternalService {
             clear_payload_points,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let clear_payload_points = clear_payload_points
             .ok_or_else(|| Status::invalid_argument("ClearPayloadPoints is missing"))?;
 
-        clear_payload(
-            self.toc.clone(),
-            clear_payload_points,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                clear_payload(
+                    self.toc.clone(),
+                    clear_payload_points,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn create_field_index(
@@ -300,18 +362,23 @@ impl PointsInternal for PointsInternalService {
             create_field_index_collection,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let create_field_index_collection = create_field_index_collection
             .ok_or_else(|| Status::invalid_argument("CreateFieldIndexCollection is missing"))?;
 
-        create_field_index_internal(
-            self.toc.clone(),
-            create_field_index_collection,
-            clock_tag.map(Into::into),
-            shard_id,
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                create_field_index_internal(
+                    self.toc.clone(),
+                    create_field_index_collection,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                ),
+            )
+            .await
     }
 
     async fn delete_field_index(
@@ -324,18 +391,23 @@ impl PointsInternal for PointsInternalService {
             delete_field_index_collection,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let delete_field_index_collection = delete_field_index_collection
             .ok_or_else(|| Status::invalid_argument("DeleteFieldIndexCollection is missing"))?;
 
-        delete_field_index_internal(
-            self.toc.clone(),
-            delete_field_index_collection,
-            clock_tag.map(Into::into),
-            shard_id,
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                delete_field_index_internal(
+                    self.toc.clone(),
+                    delete_field_index_collection,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                ),
+            )
+            .await
     }
 
     async fn core_search_batch(
@@ -349,6 +421,7 @@ impl PointsInternal for PointsInternalService {
             search_points,
             shard_id,
             timeout,
+            with_stats,
         } = request.into_inner();
 
         let timeout = timeout.map(Duration::from_secs);
@@ -367,6 +440,7 @@ impl PointsInternal for PointsInternalService {
             shard_id,
             FULL_ACCESS.clone(),
             timeout,
+            with_stats.unwrap_or_default(),
         )
         .await
     }
@@ -467,18 +541,23 @@ impl PointsInternal for PointsInternalService {
             sync_points,
             shard_id,
             clock_tag,
+            update_id,
         } = request.into_inner();
 
         let sync_points =
             sync_points.ok_or_else(|| Status::invalid_argument("SyncPoints is missing"))?;
-        sync(
-            self.toc.clone(),
-            sync_points,
-            clock_tag.map(Into::into),
-            shard_id,
-            FULL_ACCESS.clone(),
-        )
-        .await
+        self.updates
+            .apply(
+                update_id,
+                sync(
+                    self.toc.clone(),
+                    sync_points,
+                    clock_tag.map(Into::into),
+                    shard_id,
+                    FULL_ACCESS.clone(),
+                ),
+            )
+            .await
     }
 
     async fn query_batch(
@@ -492,6 +571,7 @@ impl PointsInternal for PointsInternalService {
             shard_id,
             query_points,
             timeout,
+            with_stats,
         } = request.into_inner();
 
         let timeout = timeout.map(Duration::from_secs);
@@ -502,7 +582,44 @@ impl PointsInternal for PointsInternalService {
             query_points,
             shard_id,
             timeout,
+            with_stats,
         )
         .await
     }
+
+    async fn group_aggregates(
+        &self,
+        request: Request<GroupAggregatesInternal>,
+    ) -> Result<Response<GroupAggregatesResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+        let request = request.into_inner();
+        let collection_name = request.collection_name.clone();
+        let shard_selection = match request.shard_id {
+            Some(shard_id) => ShardSelectorInternal::ShardId(shard_id),
+            None => ShardSelectorInternal::All,
+        };
+        let request = try_group_aggregates_request_from_grpc(request)?;
+
+        let collection_pass = FULL_ACCESS
+            .check_collection_access(&collection_name, AccessRequirements::new())
+            .map_err(error_to_status)?;
+        let collection = self
+            .toc
+            .get_collection(&collection_pass)
+            .await
+            .map_err(error_to_status)?;
+
+        let groups = collection
+            .group_aggregates(request, &shard_selection)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = GroupAggregatesResponseInternal {
+            groups: group_aggregates_to_grpc(groups),
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
 }
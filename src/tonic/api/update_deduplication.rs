@@ -0,0 +1,211 @@
+//! Deduplication of internal updates.
+//!
+//! A peer retries an internal update on transient network errors, even if the update was already
+//! received, for example when only its response got lost. Each internal update carries a unique
+//! id, so a retried update is not applied again, but waits for the result of the first attempt.
+//!
+//! Only updates in flight or applied successfully are deduplicated. A failed update is forgotten,
+//! so a retry of it is applied again.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use api::grpc::qdrant::PointsOperationResponseInternal;
+use futures::future::{BoxFuture, Shared};
+use futures::FutureExt as _;
+use parking_lot::Mutex;
+use tonic::{Response, Status};
+
+/// How long the result of a successful update is kept to answer retries of it
+const DEDUPLICATION_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Maximum number of updates kept for deduplication, oldest ones are forgotten first
+const MAX_DEDUPLICATED_UPDATES: usize = 100_000;
+
+type UpdateResult = Result<PointsOperationResponseInternal, Status>;
+
+type SharedUpdate = Shared<BoxFuture<'static, UpdateResult>>;
+
+#[derive(Default)]
+pub struct UpdateDeduplication {
+    updates: Mutex<Updates>,
+}
+
+#[derive(Default)]
+struct Updates {
+    results: HashMap<u64, (Instant, SharedUpdate)>,
+    received: VecDeque<(Instant, u64)>,
+}
+
+impl Updates {
+    fn forget_expired(&mut self, now: Instant) {
+        while let Some(&(received, update_id)) = self.received.front() {
+            let expired = now.saturating_duration_since(received) > DEDUPLICATION_WINDOW;
+            if !expired && self.received.len() <= MAX_DEDUPLICATED_UPDATES {
+                break;
+            }
+
+            self.received.pop_front();
+
+            // The update may have failed and been received again since
+            if self
+                .results
+                .get(&update_id)
+                .is_some_and(|(result_received, _)| *result_received == received)
+            {
+                self.results.remove(&update_id);
+            }
+        }
+    }
+
+    /// Forget a failed update, unless it was received again already
+    fn forget_failed(&mut self, update_id: u64, failed: &SharedUpdate) {
+        if self
+            .results
+            .get(&update_id)
+            .is_some_and(|(_, result)| result.ptr_eq(failed))
+        {
+            self.results.remove(&update_id);
+        }
+    }
+}
+
+impl UpdateDeduplication {
+    /// Apply the update, unless an update with the same id is in flight or was applied already.
+    ///
+    /// The update runs in a separate task, so it completes even if the request which started it
+    /// is cancelled, and its result is returned to all requests with the same id. If the update
+    /// fails, a later request with the same id applies it again.
+    pub async fn apply(
+        &self,
+        update_id: Option<u64>,
+        update: impl Future<Output = Result<Response<PointsOperationResponseInternal>, Status>>
+            + Send
+            + 'static,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        // Updates of peers without update ids are not deduplicated
+        let Some(update_id) = update_id else {
+            return update.await;
+        };
+
+        let result = {
+            let mut updates = self.updates.lock();
+            let now = Instant::now();
+            updates.forget_expired(now);
+
+            match updates.results.get(&update_id) {
+                Some((_, result)) => {
+                    log::debug!("Update {update_id} was already received, not applying it again");
+                    result.clone()
+                }
+                None => {
+                    let task = tokio::spawn(update);
+                    let result = async move {
+                        match task.await {
+                            Ok(result) => result.map(Response::into_inner),
+                            Err(err) => Err(Status::internal(format!("Update failed: {err}"))),
+                        }
+                    }
+                    .boxed()
+                    .shared();
+
+                    updates.results.insert(update_id, (now, result.clone()));
+                    updates.received.push_back((now, update_id));
+                    result
+                }
+            }
+        };
+
+        let response = result.clone().await;
+        if response.is_err() {
+            self.updates.lock().forget_failed(update_id, &result);
+        }
+
+        response.map(Response::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn update(
+        applied: &Arc<AtomicUsize>,
+    ) -> BoxFuture<'static, Result<Response<PointsOperationResponseInternal>, Status>> {
+        let applied = applied.clone();
+        async move {
+            let operation_id = applied.fetch_add(1, Ordering::SeqCst) as f64;
+            Ok(Response::new(PointsOperationResponseInternal {
+                result: None,
+                time: operation_id,
+            }))
+        }
+        .boxed()
+    }
+
+    #[tokio::test]
+    async fn test_retried_update_is_applied_once() {
+        let deduplication = UpdateDeduplication::default();
+        let applied = Arc::new(AtomicUsize::new(0));
+
+        let first = deduplication
+            .apply(Some(1), update(&applied))
+            .await
+            .unwrap();
+        let retry = deduplication
+            .apply(Some(1), update(&applied))
+            .await
+            .unwrap();
+        assert_eq!(applied.load(Ordering::SeqCst), 1);
+        assert_eq!(first.into_inner(), retry.into_inner());
+
+        deduplication
+            .apply(Some(2), update(&applied))
+            .await
+            .unwrap();
+        assert_eq!(applied.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_update_is_applied_again() {
+        let deduplication = UpdateDeduplication::default();
+        let applied = Arc::new(AtomicUsize::new(0));
+
+        let failing = {
+            let applied = applied.clone();
+            async move {
+                applied.fetch_add(1, Ordering::SeqCst);
+                Err::<Response<PointsOperationResponseInternal>, _>(Status::unavailable(
+                    "temporary failure",
+                ))
+            }
+        };
+        deduplication.apply(Some(1), failing).await.unwrap_err();
+
+        deduplication
+            .apply(Some(1), update(&applied))
+            .await
+            .unwrap();
+        assert_eq!(applied.load(Ordering::SeqCst), 2);
+
+        deduplication
+            .apply(Some(1), update(&applied))
+            .await
+            .unwrap();
+        assert_eq!(applied.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_updates_without_id_are_always_applied() {
+        let deduplication = UpdateDeduplication::default();
+        let applied = Arc::new(AtomicUsize::new(0));
+
+        deduplication.apply(None, update(&applied)).await.unwrap();
+        deduplication.apply(None, update(&applied)).await.unwrap();
+        assert_eq!(applied.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,309 @@
+//! Public gRPC API over HTTP/3 (QUIC).
+//!
+//! tonic only speaks HTTP/2, so requests received over HTTP/3 are converted and passed to the same
+//! tower service as on the other listeners. Responses are streamed back, including the trailers
+//! carrying the gRPC status.
+
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::{Buf, Bytes};
+use h3::server::RequestResolver;
+use quinn::crypto::rustls::QuicServerConfig;
+use tonic::codegen::{http as tonic_http, Body as HttpBody, StdError};
+use tonic::transport::Body;
+use tower::{Service, ServiceExt};
+
+type H3Connection = h3_quinn::Connection;
+
+/// HTTP/3 error code for a graceful close
+const H3_NO_ERROR: u32 = 0x100;
+
+/// Serve `service` over HTTP/3 on the UDP `socket` until `shutdown` completes
+pub async fn serve<S, B>(
+    socket: SocketAddr,
+    mut tls_config: rustls::ServerConfig,
+    service: S,
+    shutdown: impl Future<Output = ()>,
+) -> io::Result<()>
+where
+    S: Service<tonic_http::Request<Body>, Response = tonic_http::Response<B>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    S::Error: Into<StdError>,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<StdError> + Send,
+{
+    tls_config.alpn_protocols = vec![b"h3".to_vec()];
+    let crypto = QuicServerConfig::try_from(tls_config)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let server_config = quinn::ServerConfig::with_crypto(Arc::new(crypto));
+    let endpoint = quinn::Endpoint::server(server_config, socket)?;
+
+    tokio::pin!(shutdown);
+
+    loop {
+        let incoming = tokio::select! {
+            incoming = endpoint.accept() => incoming,
+            () = &mut shutdown => break,
+        };
+        let Some(incoming) = incoming else {
+            break;
+        };
+
+        let service = service.clone();
+        tokio::spawn(async move {
+            if let Err(err) = serve_connection(incoming, service).await {
+                log::debug!("HTTP/3 connection failed: {err}");
+            }
+        });
+    }
+
+    endpoint.close(quinn::VarInt::from_u32(H3_NO_ERROR), b"shutdown");
+    endpoint.wait_idle().await;
+
+    Ok(())
+}
+
+async fn serve_connection<S, B>(incoming: quinn::Incoming, service: S) -> Result<(), StdError>
+where
+    S: Service<tonic_http::Request<Body>, Response = tonic_http::Response<B>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    S::Error: Into<StdError>,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<StdError> + Send,
+{
+    let connection = incoming.await?;
+    let mut connection =
+        h3::server::Connection::<_, Bytes>::new(H3Connection::new(connection)).await?;
+
+    loop {
+        match connection.accept().await {
+            Ok(Some(resolver)) => {
+                let service = service.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = serve_request(resolver, service).await {
+                        log::debug!("HTTP/3 request failed: {err}");
+                    }
+                });
+            }
+            Ok(None) => return Ok(()),
+            Err(err) if err.is_h3_no_error() => return Ok(()),
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+async fn serve_request<S, B>(
+    resolver: RequestResolver<H3Connection, Bytes>,
+    service: S,
+) -> Result<(), StdError>
+where
+    S: Service<tonic_http::Request<Body>, Response = tonic_http::Response<B>>,
+    S::Error: Into<StdError>,
+    B: HttpBody<Data = Bytes>,
+    B::Error: Into<StdError>,
+{
+    let (request, stream) = resolver.resolve_request().await?;
+    let (mut send, mut recv) = stream.split();
+
+    // Stream the request body to the service while it handles the request
+    let (mut body_sender, body) = Body::channel();
+    tokio::spawn(async move {
+        let result: Result<(), StdError> = async {
+            while let Some(mut data) = recv.recv_data().await? {
+                body_sender
+                    .send_data(data.copy_to_bytes(data.remaining()))
+                    .await?;
+            }
+            if let Some(trailers) = recv.recv_trailers().await? {
+                body_sender
+                    .send_trailers(headers_from_h3(&trailers)?)
+                    .await?;
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            log::debug!("Failed to receive HTTP/3 request body: {err}");
+            body_sender.abort();
+        }
+    });
+
+    let (parts, ()) = request.into_parts();
+    let mut request = tonic_http::Request::builder()
+        .method(parts.method.as_str())
+        .uri(parts.uri.to_string())
+        .version(tonic_http::Version::HTTP_2)
+        .body(body)?;
+    *request.headers_mut() = headers_from_h3(&parts.headers)?;
+
+    let response = service.oneshot(request).await.map_err(Into::into)?;
+
+    let (parts, body) = response.into_parts();
+    let mut response = http::Response::builder()
+        .status(parts.status.as_u16())
+        .body(())?;
+    *response.headers_mut() = headers_into_h3(&parts.headers)?;
+    send.send_response(response).await?;
+
+    let mut body = std::pin::pin!(body);
+    while let Some(data) = HttpBody::data(&mut body).await {
+        let data = data.map_err(Into::into)?;
+        send.send_data(data).await?;
+    }
+    let trailers = HttpBody::trailers(&mut body).await.map_err(Into::into)?;
+    if let Some(trailers) = trailers {
+        send.send_trailers(headers_into_h3(&trailers)?).await?;
+    }
+    send.finish().await?;
+
+    Ok(())
+}
+
+/// Convert headers of an HTTP/3 request for tonic
+fn headers_from_h3(headers: &http::HeaderMap) -> Result<tonic_http::HeaderMap, StdError> {
+    let mut converted = tonic_http::HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        converted.append(
+            tonic_http::HeaderName::from_bytes(name.as_str().as_bytes())?,
+            tonic_http::HeaderValue::from_bytes(value.as_bytes())?,
+        );
+    }
+    Ok(converted)
+}
+
+/// Convert headers of a tonic response for HTTP/3
+fn headers_into_h3(headers: &tonic_http::HeaderMap) -> Result<http::HeaderMap, StdError> {
+    let mut converted = http::HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        converted.append(
+            http::HeaderName::from_bytes(name.as_str().as_bytes())?,
+            http::HeaderValue::from_bytes(value.as_bytes())?,
+        );
+    }
+    Ok(converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    use quinn::crypto::rustls::QuicClientConfig;
+
+    use super::*;
+    use crate::actix::certificate_helpers;
+    use crate::settings::{GrpcHttp3Config, Settings};
+
+    const CA_CERT: &str = "tests/tls/cert/cacert.pem";
+    const CERT: &str = "tests/tls/cert/cert.pem";
+    const KEY: &str = "tests/tls/cert/key.pem";
+    const SERVER_NAME: &str = "node1.qdrant";
+
+    /// Echo the request body, and end the response with a gRPC status trailer
+    async fn echo(
+        request: tonic_http::Request<Body>,
+    ) -> Result<tonic_http::Response<Body>, Infallible> {
+        let mut request_body = request.into_body();
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            while let Some(Ok(data)) = HttpBody::data(&mut request_body).await {
+                sender.send_data(data).await.unwrap();
+            }
+            let mut trailers = tonic_http::HeaderMap::new();
+            trailers.insert("grpc-status", tonic_http::HeaderValue::from_static("0"));
+            sender.send_trailers(trailers).await.unwrap();
+        });
+        Ok(tonic_http::Response::new(body))
+    }
+
+    #[tokio::test]
+    async fn test_serve_over_http3() {
+        let settings = Settings::new(None).unwrap();
+        let http3_config = GrpcHttp3Config {
+            port: None,
+            cert: Some(CERT.into()),
+            key: Some(KEY.into()),
+        };
+        let tls_config =
+            certificate_helpers::grpc_http3_tls_server_config(&settings, &http3_config).unwrap();
+
+        // Reserve a free UDP port
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let (stop_sender, stop_receiver) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(socket, tls_config, tower::service_fn(echo), async {
+            let _ = stop_receiver.await;
+        }));
+
+        let mut roots = rustls::RootCertStore::empty();
+        let ca_cert = std::fs::read(CA_CERT).unwrap();
+        for cert in rustls_pemfile::certs(&mut ca_cert.as_slice()) {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let mut client_tls = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        client_tls.alpn_protocols = vec![b"h3".to_vec()];
+        let client_crypto = QuicClientConfig::try_from(client_tls).unwrap();
+
+        let mut endpoint = quinn::Endpoint::client("127.0.0.1:0".parse().unwrap()).unwrap();
+        endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(client_crypto)));
+        let connection = endpoint
+            .connect(socket, SERVER_NAME)
+            .unwrap()
+            .await
+            .unwrap();
+        let (mut driver, mut send_request) = h3::client::new(h3_quinn::Connection::new(connection))
+            .await
+            .unwrap();
+        let driver = tokio::spawn(async move {
+            let _ = futures::future::poll_fn(|cx| driver.poll_close(cx)).await;
+        });
+
+        let request =
+            http::Request::post(format!("https://{SERVER_NAME}/qdrant.Qdrant/HealthCheck"))
+                .header("content-type", "application/grpc")
+                .body(())
+                .unwrap();
+        let mut stream = send_request.send_request(request).await.unwrap();
+        stream
+            .send_data(Bytes::from_static(b"\0\0\0\0\0"))
+            .await
+            .unwrap();
+        stream.finish().await.unwrap();
+
+        let response = stream.recv_response().await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let mut body = Vec::new();
+        while let Some(mut data) = stream.recv_data().await.unwrap() {
+            body.extend_from_slice(&data.copy_to_bytes(data.remaining()));
+        }
+        assert_eq!(body, b"\0\0\0\0\0");
+
+        let trailers = stream.recv_trailers().await.unwrap().unwrap();
+        assert_eq!(trailers["grpc-status"], "0");
+
+        drop(send_request);
+        stop_sender.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        driver.abort();
+    }
+}
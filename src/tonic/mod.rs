@@ -1,5 +1,7 @@
 mod api;
 mod auth;
+#[cfg(feature = "grpc-http3")]
+mod http3;
 mod logging;
 mod tonic_telemetry;
 
@@ -38,6 +40,8 @@ use tonic::codec::CompressionEncoding;
 use tonic::transport::{Server, ServerTlsConfig};
 use tonic::{Request, Response, Status};
 
+#[cfg(feature = "grpc-http3")]
+use crate::actix::certificate_helpers;
 use crate::common::auth::AuthKeys;
 use crate::common::helpers;
 use crate::common::http_client::HttpClient;
@@ -154,98 +158,217 @@ pub fn init(
         let socket =
             SocketAddr::from((settings.service.host.parse::<IpAddr>().unwrap(), grpc_port));
 
-        let qdrant_service = QdrantService::default();
-        let health_service = HealthService::default();
-        let collections_service = CollectionsService::new(dispatcher.clone());
-        let points_service = PointsService::new(dispatcher.clone());
-        let snapshot_service = SnapshotsService::new(dispatcher.clone());
-
-        // Only advertise the public services. By default, all services in QDRANT_DESCRIPTOR_SET
-        // will be advertised, so explicitly list the services to be included.
-        let reflection_service = tonic_reflection::server::Builder::configure()
-            .register_encoded_file_descriptor_set(QDRANT_DESCRIPTOR_SET)
-            .with_service_name("qdrant.Collections")
-            .with_service_name("qdrant.Points")
-            .with_service_name("qdrant.Snapshots")
-            .with_service_name("qdrant.Qdrant")
-            .with_service_name("grpc.health.v1.Health")
-            .build()
-            .unwrap();
-
         log::info!("Qdrant gRPC listening on {}", grpc_port);
+        let tcp_server = serve(
+            dispatcher.clone(),
+            telemetry_collector.clone(),
+            &settings,
+            Listener::Tcp(socket),
+        );
+
+        let unix_server = async {
+            let Some(path) = &settings.service.grpc_unix_socket else {
+                return Ok(());
+            };
 
-        let mut server = Server::builder();
+            #[cfg(unix)]
+            {
+                let listener = helpers::bind_unix_socket(
+                    path,
+                    settings.service.unix_socket_permissions.as_deref(),
+                )?;
+                listener.set_nonblocking(true)?;
+                let listener = tokio::net::UnixListener::from_std(listener)?;
+
+                log::info!("Qdrant gRPC listening on Unix socket {}", path.display());
+                serve(
+                    dispatcher.clone(),
+                    telemetry_collector.clone(),
+                    &settings,
+                    Listener::Unix(listener),
+                )
+                .await
+            }
+
+            #[cfg(not(unix))]
+            {
+                log::warn!(
+                    "Unix sockets are not supported on this platform, not listening on {}",
+                    path.display(),
+                );
+                Ok(())
+            }
+        };
 
-        if settings.service.enable_tls {
-            log::info!("TLS enabled for gRPC API (TTL not supported)");
+        let http3_server = async {
+            let Some(http3_config) = &settings.service.grpc_http3 else {
+                return Ok(());
+            };
 
-            let tls_server_config = helpers::load_tls_external_server_config(settings.tls()?)?;
+            #[cfg(feature = "grpc-http3")]
+            {
+                let port = http3_config.port.unwrap_or(grpc_port);
+                let socket = SocketAddr::new(socket.ip(), port);
+                let tls_config =
+                    certificate_helpers::grpc_http3_tls_server_config(&settings, http3_config)
+                        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+                log::info!("Qdrant gRPC over HTTP/3 listening on UDP port {}", port);
+                serve(
+                    dispatcher.clone(),
+                    telemetry_collector.clone(),
+                    &settings,
+                    Listener::Quic(socket, Box::new(tls_config)),
+                )
+                .await
+            }
+
+            #[cfg(not(feature = "grpc-http3"))]
+            {
+                let _ = http3_config;
+                log::warn!("Qdrant is built without HTTP/3 support, not serving gRPC over HTTP/3");
+                Ok(())
+            }
+        };
 
-            server = server
-                .tls_config(tls_server_config)
-                .map_err(helpers::tonic_error_to_io_error)?;
-        } else {
-            log::info!("TLS disabled for gRPC API");
-        }
+        tokio::try_join!(tcp_server, unix_server, http3_server)?;
+        Ok(())
+    })
+}
 
-        // The stack of middleware that our service will be wrapped in
-        let middleware_layer = tower::ServiceBuilder::new()
-            .layer(logging::LoggingMiddlewareLayer::new())
-            .layer(tonic_telemetry::TonicTelemetryLayer::new(
-                telemetry_collector,
-            ))
-            .option_layer({
-                AuthKeys::try_create(
-                    &settings.service,
-                    dispatcher
-                        .toc(&Access::full("For tonic auth middleware"))
-                        .clone(),
-                )
-                .map(auth::AuthLayer::new)
-            })
-            .into_inner();
-
-        server
-            .layer(middleware_layer)
-            .add_service(reflection_service)
-            .add_service(
-                QdrantServer::new(qdrant_service)
-                    .send_compressed(CompressionEncoding::Gzip)
-                    .accept_compressed(CompressionEncoding::Gzip)
-                    .max_decoding_message_size(usize::MAX),
-            )
-            .add_service(
-                CollectionsServer::new(collections_service)
-                    .send_compressed(CompressionEncoding::Gzip)
-                    .accept_compressed(CompressionEncoding::Gzip)
-                    .max_decoding_message_size(usize::MAX),
-            )
-            .add_service(
-                PointsServer::new(points_service)
-                    .send_compressed(CompressionEncoding::Gzip)
-                    .accept_compressed(CompressionEncoding::Gzip)
-                    .max_decoding_message_size(usize::MAX),
-            )
-            .add_service(
-                SnapshotsServer::new(snapshot_service)
-                    .send_compressed(CompressionEncoding::Gzip)
-                    .accept_compressed(CompressionEncoding::Gzip)
-                    .max_decoding_message_size(usize::MAX),
-            )
-            .add_service(
-                HealthServer::new(health_service)
-                    .send_compressed(CompressionEncoding::Gzip)
-                    .accept_compressed(CompressionEncoding::Gzip)
-                    .max_decoding_message_size(usize::MAX),
+/// Listener to serve the public gRPC API on
+enum Listener {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener),
+    /// UDP socket to serve HTTP/3 on, with its TLS config
+    #[cfg(feature = "grpc-http3")]
+    Quic(SocketAddr, Box<rustls::ServerConfig>),
+}
+
+async fn serve(
+    dispatcher: Arc<Dispatcher>,
+    telemetry_collector: Arc<parking_lot::Mutex<TonicTelemetryCollector>>,
+    settings: &Settings,
+    listener: Listener,
+) -> io::Result<()> {
+    let qdrant_service = QdrantService::default();
+    let health_service = HealthService::default();
+    let collections_service = CollectionsService::new(dispatcher.clone());
+    let points_service = PointsService::new(dispatcher.clone());
+    let snapshot_service = SnapshotsService::new(dispatcher.clone());
+
+    // Only advertise the public services. By default, all services in QDRANT_DESCRIPTOR_SET
+    // will be advertised, so explicitly list the services to be included.
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(QDRANT_DESCRIPTOR_SET)
+        .with_service_name("qdrant.Collections")
+        .with_service_name("qdrant.Points")
+        .with_service_name("qdrant.Snapshots")
+        .with_service_name("qdrant.Qdrant")
+        .with_service_name("grpc.health.v1.Health")
+        .build()
+        .unwrap();
+
+    let mut server = Server::builder();
+
+    // Unix sockets are local to the host, they are served without TLS.
+    // QUIC connections are encrypted by the listener itself.
+    let is_tcp = matches!(listener, Listener::Tcp(_));
+
+    if settings.service.enable_tls && is_tcp {
+        log::info!("TLS enabled for gRPC API (TTL not supported)");
+
+        let tls_server_config = helpers::load_tls_external_server_config(settings.tls()?)?;
+
+        server = server
+            .tls_config(tls_server_config)
+            .map_err(helpers::tonic_error_to_io_error)?;
+    } else if is_tcp {
+        log::info!("TLS disabled for gRPC API");
+    }
+
+    // The stack of middleware that our service will be wrapped in
+    let middleware_layer = tower::ServiceBuilder::new()
+        .layer(logging::LoggingMiddlewareLayer::new())
+        .layer(tonic_telemetry::TonicTelemetryLayer::new(
+            telemetry_collector,
+        ))
+        .option_layer({
+            AuthKeys::try_create(
+                &settings.service,
+                dispatcher
+                    .toc(&Access::full("For tonic auth middleware"))
+                    .clone(),
             )
-            .serve_with_shutdown(socket, async {
-                wait_stop_signal("gRPC service").await;
+            .map(auth::AuthLayer::new)
+        })
+        .into_inner();
+
+    let router = server
+        .layer(middleware_layer)
+        .add_service(reflection_service)
+        .add_service(
+            QdrantServer::new(qdrant_service)
+                .send_compressed(CompressionEncoding::Gzip)
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        )
+        .add_service(
+            CollectionsServer::new(collections_service)
+                .send_compressed(CompressionEncoding::Gzip)
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        )
+        .add_service(
+            PointsServer::new(points_service)
+                .send_compressed(CompressionEncoding::Gzip)
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        )
+        .add_service(
+            SnapshotsServer::new(snapshot_service)
+                .send_compressed(CompressionEncoding::Gzip)
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        )
+        .add_service(
+            HealthServer::new(health_service)
+                .send_compressed(CompressionEncoding::Gzip)
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        );
+
+    let result = match listener {
+        Listener::Tcp(socket) => {
+            router
+                .serve_with_shutdown(socket, async {
+                    wait_stop_signal("gRPC service").await;
+                })
+                .await
+        }
+        #[cfg(unix)]
+        Listener::Unix(listener) => {
+            let incoming = futures_util::stream::unfold(listener, |listener| async move {
+                let stream = listener.accept().await.map(|(stream, _addr)| stream);
+                Some((stream, listener))
+            });
+            router
+                .serve_with_incoming_shutdown(incoming, async {
+                    wait_stop_signal("gRPC service on Unix socket").await;
+                })
+                .await
+        }
+        #[cfg(feature = "grpc-http3")]
+        Listener::Quic(socket, tls_config) => {
+            return http3::serve(socket, *tls_config, router.into_service(), async {
+                wait_stop_signal("gRPC service over HTTP/3").await;
             })
-            .await
-            .map_err(helpers::tonic_error_to_io_error)
-    })?;
+            .await;
+        }
+    };
 
-    Ok(())
+    result.map_err(helpers::tonic_error_to_io_error)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -1,6 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::num::NonZeroU32;
+use std::str::FromStr as _;
 
 use serde::{Deserialize, Serialize};
+use tracing_subscriber::filter::FilterExt as _;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{filter, fmt, registry};
 
@@ -12,6 +16,30 @@ pub struct Config {
     pub log_level: Option<String>,
     pub span_events: Option<HashSet<config::SpanEvent>>,
     pub color: Option<config::Color>,
+    /// Overrides of the log level and sampling of modules, by module path
+    pub modules: Option<BTreeMap<String, ModuleConfig>>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ModuleConfig {
+    /// Log level of the module, overriding the top-level log level
+    pub log_level: Option<String>,
+    /// Log only one of every `sample_rate` events of the module
+    pub sample_rate: Option<NonZeroU32>,
+}
+
+impl ModuleConfig {
+    pub fn validate(&self, module: &str) -> Result<(), String> {
+        if module.is_empty() || module.contains([',', '=', '[', ' ']) {
+            return Err(format!("Invalid module path `{module}`"));
+        }
+        if let Some(log_level) = &self.log_level {
+            log::LevelFilter::from_str(log_level)
+                .map_err(|_| format!("Invalid log level `{log_level}` of module `{module}`"))?;
+        }
+        Ok(())
+    }
 }
 
 impl Config {
@@ -19,16 +47,19 @@ impl Config {
         self.log_level = other.log_level.or(self.log_level.take());
         self.span_events = other.span_events.or(self.span_events.take());
         self.color = other.color.or(self.color.take());
+        self.modules = other.modules.or(self.modules.take());
     }
 }
 
 #[rustfmt::skip] // `rustfmt` formats this into unreadable single line
 pub type Logger<S> = filter::Filtered<
     Option<fmt::Layer<S>>,
-    filter::EnvFilter,
+    Filter<S>,
     S,
 >;
 
+pub type Filter<S> = filter::combinator::And<filter::EnvFilter, sampling::Sampler, S>;
+
 pub fn new_logger<S>(config: &Config) -> Logger<S>
 where
     S: tracing::Subscriber + for<'span> registry::LookupSpan<'span>,
@@ -49,6 +80,22 @@ where
         .with_ansi(config.color.unwrap_or_default().to_bool())
 }
 
-pub fn new_filter(config: &Config) -> filter::EnvFilter {
-    filter(config.log_level.as_deref().unwrap_or(""))
+pub fn new_filter<S>(config: &Config) -> Filter<S>
+where
+    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span>,
+{
+    let mut directives = config.log_level.clone().unwrap_or_default();
+    let modules = config.modules.iter().flatten();
+
+    for (module, module_config) in modules.clone() {
+        if let Some(log_level) = &module_config.log_level {
+            let comma = if directives.is_empty() { "" } else { "," };
+            write!(&mut directives, "{comma}{module}={log_level}").unwrap(); // Writing into `String` never fails
+        }
+    }
+
+    let sample_rates = modules
+        .filter_map(|(module, module_config)| Some((module.clone(), module_config.sample_rate?)));
+
+    filter(&directives).and(sampling::Sampler::new(sample_rates))
 }
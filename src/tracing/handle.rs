@@ -77,16 +77,43 @@ impl LoggerHandle {
             config.on_disk = merged_config.on_disk;
         }
 
-        if merged_config.default != config.default {
-            let new_layer = default::new_layer(&merged_config.default);
-            let new_filter = default::new_filter(&merged_config.default);
+        self.update_default_config(&mut config, merged_config.default)
+    }
+
+    /// Change the log level or sampling of a single module, `None` removes its overrides
+    pub async fn update_module_config(
+        &self,
+        module: String,
+        module_config: Option<default::ModuleConfig>,
+    ) -> anyhow::Result<()> {
+        let mut config = self.config.write().await;
+
+        let mut new_default = config.default.clone();
+        let modules = new_default.modules.get_or_insert_with(Default::default);
+        match module_config {
+            Some(module_config) => modules.insert(module, module_config),
+            None => modules.remove(&module),
+        };
+
+        self.update_default_config(&mut config, new_default)
+    }
+
+    /// See `update_config` on why the logger is changed this way
+    fn update_default_config(
+        &self,
+        config: &mut config::LoggerConfig,
+        new_default: default::Config,
+    ) -> anyhow::Result<()> {
+        if new_default != config.default {
+            let new_layer = default::new_layer(&new_default);
+            let new_filter = default::new_filter(&new_default);
 
             self.default.modify(move |logger| {
                 *logger.inner_mut() = Some(new_layer);
                 *logger.filter_mut() = new_filter;
             })?;
 
-            config.default = merged_config.default;
+            config.default = new_default;
         }
 
         Ok(())
@@ -4,6 +4,8 @@ pub mod config;
 pub mod default;
 pub mod handle;
 pub mod on_disk;
+pub mod recent_errors;
+pub mod sampling;
 
 #[cfg(test)]
 mod test;
@@ -50,6 +52,9 @@ pub fn setup(mut config: config::LoggerConfig) -> anyhow::Result<LoggerHandle> {
     let (default_logger, default_logger_handle) = reload::Layer::new(default_logger);
     let reg = reg.with(default_logger);
 
+    // Keeps recent warnings and errors for diagnostics, regardless of the configured log level
+    let reg = reg.with(recent_errors::new_logger());
+
     let logger_handle = LoggerHandle::new(config, default_logger_handle, on_disk_logger_handle);
 
     // Use `console` or `console-subscriber` feature to enable `console-subscriber`
@@ -0,0 +1,94 @@
+//! In-memory buffer of the most recently logged warnings and errors.
+//!
+//! Keeps them available for the diagnostics bundle, even if the log output of the peer is not
+//! accessible.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_log::NormalizeEvent as _;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::{filter, registry};
+
+/// Number of the most recent records to keep
+const CAPACITY: usize = 100;
+
+static RECENT_ERRORS: Mutex<VecDeque<LogRecord>> = Mutex::new(VecDeque::new());
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Utc>,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Most recently logged warnings and errors, from the oldest to the newest
+pub fn recent_errors() -> Vec<LogRecord> {
+    RECENT_ERRORS.lock().iter().cloned().collect()
+}
+
+pub type Logger<S> = filter::Filtered<RecentErrorsLayer, filter::LevelFilter, S>;
+
+pub fn new_logger<S>() -> Logger<S>
+where
+    S: Subscriber + for<'span> registry::LookupSpan<'span>,
+{
+    RecentErrorsLayer.with_filter(filter::LevelFilter::WARN)
+}
+
+pub struct RecentErrorsLayer;
+
+impl<S: Subscriber> Layer<S> for RecentErrorsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        // Events emitted through the `log` crate carry their actual target in their fields
+        let normalized_metadata = event.normalized_metadata();
+        let metadata = normalized_metadata
+            .as_ref()
+            .unwrap_or_else(|| event.metadata());
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let record = LogRecord {
+            timestamp: Utc::now(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+        };
+
+        let mut records = RECENT_ERRORS.lock();
+        if records.len() >= CAPACITY {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+}
+
+/// Formats the message of the event, followed by its other fields
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        // Fields of the `log` record are already part of the normalized metadata
+        if field.name().starts_with("log.") {
+            return;
+        }
+
+        let separator = if self.message.is_empty() { "" } else { " " };
+        if field.name() == "message" {
+            self.message.insert_str(0, &format!("{value:?}{separator}"));
+        } else {
+            // Writing into `String` never fails
+            write!(&mut self.message, "{separator}{}={value:?}", field.name()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Sampling of noisy log events.
+//!
+//! Keeps only one of every N events of a module, e.g. to log a fraction of slow requests, without
+//! losing the rest of the logs of the module at the same level.
+
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tracing::Metadata;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Filter};
+
+pub struct Sampler {
+    /// Modules with their sample rate and number of seen events, the most specific modules first
+    modules: Vec<(String, u64, AtomicU64)>,
+}
+
+impl Sampler {
+    pub fn new(sample_rates: impl IntoIterator<Item = (String, NonZeroU32)>) -> Self {
+        let mut modules: Vec<_> = sample_rates
+            .into_iter()
+            .map(|(module, rate)| (module, u64::from(rate.get()), AtomicU64::new(0)))
+            .collect();
+        modules.sort_unstable_by(|(a, ..), (b, ..)| b.len().cmp(&a.len()));
+        Self { modules }
+    }
+
+    /// Whether to keep the next event of the target
+    pub fn sample(&self, target: &str) -> bool {
+        let module = self
+            .modules
+            .iter()
+            .find(|(module, ..)| is_in_module(target, module));
+
+        match module {
+            Some((_, rate, seen)) => seen.fetch_add(1, Ordering::Relaxed) % rate == 0,
+            None => true,
+        }
+    }
+}
+
+impl<S> Filter<S> for Sampler {
+    fn enabled(&self, metadata: &Metadata<'_>, _: &Context<'_, S>) -> bool {
+        !metadata.is_event() || self.sample(metadata.target())
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        // Sampling doesn't restrict the levels, don't hide the hint of the level filter
+        Some(LevelFilter::TRACE)
+    }
+}
+
+fn is_in_module(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::num::NonZeroU32;
 
 use serde_json::json;
 
@@ -29,6 +30,7 @@ fn deseriailze_logger_config() {
                 config::SpanEvent::Close,
             ])),
             color: Some(config::Color::Explicit(true)),
+            modules: None,
         },
 
         on_disk: on_disk::Config {
@@ -76,6 +78,55 @@ fn deseriailze_config_with_explicit_nulls() {
     assert_eq!(config, LoggerConfig::default());
 }
 
+#[test]
+fn deserialize_module_config() {
+    let json = json!({
+        "modules": {
+            "collection::shards::transfer": { "log_level": "debug" },
+            "actix_web": { "sample_rate": 10 },
+        }
+    });
+
+    let config = deserialize_config(json);
+    let modules = config.default.modules.unwrap();
+
+    assert_eq!(
+        modules["collection::shards::transfer"].log_level.as_deref(),
+        Some("debug"),
+    );
+    assert_eq!(
+        modules["actix_web"].sample_rate.map(|rate| rate.get()),
+        Some(10)
+    );
+
+    assert!(modules["collection::shards::transfer"]
+        .validate("collection::shards::transfer")
+        .is_ok());
+    assert!(modules["actix_web"].validate("actix_web=trace").is_err());
+}
+
+#[test]
+fn sample_module_events() {
+    let sampler = sampling::Sampler::new([
+        ("actix_web".to_string(), NonZeroU32::new(3).unwrap()),
+        (
+            "actix_web::middleware".to_string(),
+            NonZeroU32::new(2).unwrap(),
+        ),
+    ]);
+
+    let sampled = |target: &str| (0..6).filter(|_| sampler.sample(target)).count();
+
+    // The most specific module is sampled
+    assert_eq!(sampled("actix_web::middleware::logger"), 3);
+    assert_eq!(sampled("actix_web::server"), 2);
+    assert_eq!(sampled("actix_web"), 2);
+
+    // Other modules are not sampled, even with the same prefix
+    assert_eq!(sampled("actix_web_validator"), 6);
+    assert_eq!(sampled("collection"), 6);
+}
+
 fn deserialize_config(json: serde_json::Value) -> LoggerConfig {
     serde_json::from_value(json).unwrap()
 }
@@ -121,6 +121,9 @@ ACTION_ACCESS = {
     "update_collection_params": EndpointAccess(
         False, False, True, "PATCH /collections/{collection_name}", "qdrant.Collections/Update"
     ),
+    "freeze_collection": EndpointAccess(
+        False, False, True, "POST /collections/{collection_name}/freeze"
+    ),
     "get_collection_cluster_info": EndpointAccess(
         True,
         True,
@@ -136,6 +139,73 @@ ACTION_ACCESS = {
         "GET /collections/{collection_name}/exists",
         "qdrant.Collections/CollectionExists",
     ),
+    "get_collection_mirror": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/mirror",
+        coll_rw_payload=False,
+    ),
+    "compact_collection_wal": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/wal/compact",
+        coll_rw_payload=False,
+    ),
+    "start_segment_upgrade": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/segments/upgrade",
+        coll_rw_payload=False,
+    ),
+    "get_segment_upgrade": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/segments/upgrade",
+        coll_rw_payload=False,
+    ),
+    "stop_segment_upgrade": EndpointAccess(
+        False,
+        True,
+        True,
+        "DELETE /collections/{collection_name}/segments/upgrade",
+        coll_rw_payload=False,
+    ),
+    "resync_collection_mirror": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/mirror/resync",
+        coll_rw_payload=False,
+    ),
+    "get_collection_standby": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/standby",
+        coll_rw_payload=False,
+    ),
+    "update_collection_standby": EndpointAccess(
+        False,
+        False,
+        True,
+        "PUT /collections/{collection_name}/standby",
+    ),
+    "promote_collection_standby": EndpointAccess(
+        False,
+        False,
+        True,
+        "POST /collections/{collection_name}/standby/promote",
+    ),
+    "migrate_collection": EndpointAccess(
+        False,
+        False,
+        True,
+        "POST /collections/{collection_name}/migrate",
+    ),
     "replicate_shard_operation": EndpointAccess(
         False,
         False,
@@ -224,6 +294,19 @@ ACTION_ACCESS = {
     "list_aliases": EndpointAccess(
         True, True, True, "GET /aliases", "qdrant.Collections/ListAliases"
     ),
+    "get_alias_history": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /aliases/{alias_name}/history",
+        "qdrant.Collections/GetAliasHistory",
+    ),
+    "create_alias_version": EndpointAccess(
+        False,
+        False,
+        True,
+        "POST /aliases/{alias_name}/versions",
+    ),
     ### Shard Keys ###
     "create_default_shard_key": EndpointAccess(
         False,
@@ -263,6 +346,20 @@ ACTION_ACCESS = {
         "qdrant.Points/DeleteFieldIndex",
         coll_rw_payload=False,
     ),
+    "get_index": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/index/{field_name}",
+        coll_rw_payload=False,
+    ),
+    "get_index_stats": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/index/{field_name}/stats",
+        coll_rw_payload=False,
+    ),
     ### Collection Snapshots ###
     "list_collection_snapshots": EndpointAccess(
         True,
@@ -295,6 +392,20 @@ ACTION_ACCESS = {
         "GET /collections/{collection_name}/snapshots/{snapshot_name}",
         coll_rw_payload=False,
     ),
+    "mount_collection_snapshot": EndpointAccess(
+        False,
+        True,
+        True,
+        "PUT /collections/{collection_name}/snapshots/{snapshot_name}/mount",
+        coll_rw_payload=False,
+    ),
+    "unmount_collection_snapshot": EndpointAccess(
+        False,
+        True,
+        True,
+        "DELETE /collections/{collection_name}/snapshots/{snapshot_name}/mount",
+        coll_rw_payload=False,
+    ),
     "upload_collection_snapshot": EndpointAccess(
         False, False, True, "POST /collections/{collection_name}/snapshots/upload"
     ),
@@ -345,6 +456,19 @@ ACTION_ACCESS = {
         "GET /collections/{collection_name}/shards/{shard_id}/snapshots/{snapshot_name}",
         coll_rw_payload=False,
     ),
+    "create_shard_key_snapshots": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/shards/snapshots",
+        coll_rw_payload=False,
+    ),
+    "recover_shard_key_snapshots": EndpointAccess(
+        False,
+        False,
+        True,
+        "PUT /collections/{collection_name}/shards/snapshots/recover",
+    ),
     ### Full Snapshots ###
     "list_full_snapshots": EndpointAccess(
         True,
@@ -375,6 +499,13 @@ ACTION_ACCESS = {
         "GET /collections/{collection_name}/points/{id}",
         coll_rw_payload=False,
     ),
+    "get_point_hnsw_neighborhood": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/points/{id}/hnsw",
+        coll_rw_payload=False,
+    ),
     "get_points": EndpointAccess(
         True,
         True,
@@ -391,6 +522,13 @@ ACTION_ACCESS = {
         "qdrant.Points/Upsert",
         coll_rw_payload=False,
     ),
+    "import_points": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/import",
+        coll_rw_payload=False,
+    ),
     "update_points_batch": EndpointAccess(
         False,
         True,
@@ -406,6 +544,13 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/delete",
         "qdrant.Points/Delete",
     ),
+    "find_duplicate_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/duplicates",
+        coll_rw_payload=False,
+    ),
     "update_vectors": EndpointAccess(
         False,
         True,
@@ -460,6 +605,25 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/scroll",
         "qdrant.Points/Scroll",
     ),
+    "point_changes": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/changes",
+        coll_rw_payload=False,
+    ),
+    "sample_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/sample",
+    ),
+    "score_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/score",
+    ),
     "search_points": EndpointAccess(
         True,
         True,
@@ -530,6 +694,9 @@ ACTION_ACCESS = {
     "query_batch_points": EndpointAccess(
         True, True, True, "POST /collections/{collection_name}/points/query/batch", "qdrant.Points/QueryBatch"
     ),
+    "query_points_dsl": EndpointAccess(
+        True, True, True, "POST /collections/{collection_name}/points/query/dsl"
+    ),
     ### Service ###
     "root": EndpointAccess(True, True, True, "GET /", "qdrant.Qdrant/HealthCheck"),
     "readyz": EndpointAccess(True, True, True, "GET /readyz", "grpc.health.v1.Health/Check"),
@@ -537,8 +704,11 @@ ACTION_ACCESS = {
     "livez": EndpointAccess(True, True, True, "GET /livez", "grpc.health.v1.Health/Check"),
     "telemetry": EndpointAccess(True, False, True, "GET /telemetry", coll_r=False),
     "metrics": EndpointAccess(True, False, True, "GET /metrics", coll_r=False),
+    "diagnostics": EndpointAccess(False, False, True, "GET /diagnostics"),
     "post_locks": EndpointAccess(False, False, True, "POST /locks"),
     "get_locks": EndpointAccess(True, False, True, "GET /locks", coll_r=False),
+    "purge_memory": EndpointAccess(False, False, True, "POST /memory/purge"),
+    "reload_config": EndpointAccess(False, False, True, "POST /config/reload"),
     "get_issues": EndpointAccess(False, False, True, "GET /issues"),
     "clear_issues": EndpointAccess(False, False, True, "DELETE /issues"),
 }
@@ -863,6 +1033,14 @@ def test_update_collection_params():
     )
 
 
+def test_freeze_collection():
+    fake_name = random_str()
+    check_access(
+        "freeze_collection",
+        path_params={"collection_name": fake_name},
+    )
+
+
 def test_create_alias():
     def req():
         return {
@@ -929,6 +1107,23 @@ def test_list_aliases():
     check_access("list_aliases")
 
 
+def test_get_alias_history():
+    check_access(
+        "get_alias_history",
+        grpc_request={"alias_name": COLL_NAME},
+        path_params={"alias_name": COLL_NAME},
+    )
+
+
+def test_create_alias_version():
+    check_access(
+        "create_alias_version",
+        # Alias without collection nor configuration, to fail after the access check
+        rest_request={},
+        path_params={"alias_name": "missing_alias"},
+    )
+
+
 def test_get_collection_cluster_info():
     check_access(
         "get_collection_cluster_info",
@@ -945,6 +1140,81 @@ def test_collection_exists():
     )
 
 
+def test_get_collection_mirror():
+    check_access(
+        "get_collection_mirror",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_compact_collection_wal():
+    check_access(
+        "compact_collection_wal",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_start_segment_upgrade():
+    check_access(
+        "start_segment_upgrade",
+        rest_request={},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_segment_upgrade():
+    check_access(
+        "get_segment_upgrade",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_stop_segment_upgrade():
+    check_access(
+        "stop_segment_upgrade",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_resync_collection_mirror():
+    check_access(
+        "resync_collection_mirror",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_collection_standby():
+    check_access(
+        "get_collection_standby",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_update_collection_standby():
+    check_access(
+        "update_collection_standby",
+        # Invalid URL, to fail after the access check without following anything
+        rest_request={"url": "invalid url"},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_promote_collection_standby():
+    check_access(
+        "promote_collection_standby",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_migrate_collection():
+    check_access(
+        "migrate_collection",
+        # Invalid URI, to fail after the access check without contacting another cluster
+        rest_request={"uri": "invalid uri"},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_replicate_shard_operation():
     peer_ids = [PEER_ID + 5, PEER_ID + 3]
     replicate_shard = {
@@ -1185,6 +1455,20 @@ def test_delete_index():
     )
 
 
+def test_get_index():
+    check_access(
+        "get_index",
+        path_params={"collection_name": COLL_NAME, "field_name": FIELD_NAME},
+    )
+
+
+def test_get_index_stats():
+    check_access(
+        "get_index_stats",
+        path_params={"collection_name": COLL_NAME, "field_name": FIELD_NAME},
+    )
+
+
 def test_list_collection_snapshots():
     check_access(
         "list_collection_snapshots",
@@ -1227,6 +1511,20 @@ def test_download_collection_snapshot():
     )
 
 
+def test_mount_collection_snapshot():
+    check_access(
+        "mount_collection_snapshot",
+        path_params={"collection_name": COLL_NAME, "snapshot_name": random_str()},
+    )
+
+
+def test_unmount_collection_snapshot():
+    check_access(
+        "unmount_collection_snapshot",
+        path_params={"collection_name": COLL_NAME, "snapshot_name": random_str()},
+    )
+
+
 @pytest.fixture(scope="module")
 def collection_snapshot():
     res = requests.post(
@@ -1333,6 +1631,25 @@ def test_delete_shard_snapshot():
     )
 
 
+def test_create_shard_key_snapshots():
+    check_access(
+        "create_shard_key_snapshots",
+        rest_request={"shard_key": SHARD_KEY},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_recover_shard_key_snapshots(shard_snapshot_name: str):
+    check_access(
+        "recover_shard_key_snapshots",
+        rest_request={
+            "shard_key": SHARD_KEY,
+            "snapshots": [{"shard_id": SHARD_ID, "location": shard_snapshot_name}],
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_download_shard_snapshot(shard_snapshot_name: str):
     check_access(
         "download_shard_snapshot",
@@ -1386,6 +1703,13 @@ def test_get_point():
     )
 
 
+def test_get_point_hnsw_neighborhood():
+    check_access(
+        "get_point_hnsw_neighborhood",
+        path_params={"collection_name": COLL_NAME, "id": 1},
+    )
+
+
 def test_get_points():
     check_access(
         "get_points",
@@ -1408,6 +1732,18 @@ def test_upsert_points():
     )
 
 
+def test_import_points():
+    check_access(
+        "import_points",
+        rest_req_kwargs={
+            "files": {"index": b"not an index"},
+            "params": {"format": "faiss"},
+            "timeout": 1,
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_update_points_batch():
     rest_operations = [
         {"upsert": {"shard_key": SHARD_KEY, "points": [{"id": 1, "vector": [1, 2, 3, 4]}]}},
@@ -1507,6 +1843,14 @@ def test_delete_points():
     )
 
 
+def test_find_duplicate_points():
+    check_access(
+        "find_duplicate_points",
+        rest_request={},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_update_vectors():
     check_access(
         "update_vectors",
@@ -1598,6 +1942,30 @@ def test_scroll_points():
     )
 
 
+def test_point_changes():
+    check_access(
+        "point_changes",
+        rest_request={"limit": 10},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_sample_points():
+    check_access(
+        "sample_points",
+        rest_request={"limit": 10},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_score_points():
+    check_access(
+        "score_points",
+        rest_request={"query": [1, 2, 3, 4], "targets": [[4, 3, 2, 1]]},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_search_points():
     check_access(
         "search_points",
@@ -1752,6 +2120,14 @@ def test_query_batch_points():
     )
     
 
+def test_query_points_dsl():
+    check_access(
+        "query_points_dsl",
+        rest_request={"query": "nearest([0.1, 0.2, 0.3, 0.4]) limit 10"},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_root():
     check_access("root")
 
@@ -1776,6 +2152,10 @@ def test_metrics():
     check_access("metrics")
 
 
+def test_diagnostics():
+    check_access("diagnostics")
+
+
 def test_post_locks():
     check_access("post_locks", rest_request={"write": False})
 
@@ -1784,6 +2164,14 @@ def test_get_locks():
     check_access("get_locks")
 
 
+def test_purge_memory():
+    check_access("purge_memory")
+
+
+def test_reload_config():
+    check_access("reload_config")
+
+
 def test_get_issues():
     check_access("get_issues")
 
@@ -0,0 +1,89 @@
+import pathlib
+
+from .fixtures import create_collection, create_field_index, upsert_random_points
+from .utils import *
+
+COLLECTION_NAME = "test_collection"
+
+
+def test_collection_migration(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    target_path = tmp_path / "target"
+    target_path.mkdir()
+    target_uris, _, _ = start_cluster(target_path, 1)
+    target_uri = target_uris[0]
+    target_grpc_uri = f"http://127.0.0.1:{processes[-1].grpc_port}"
+
+    source_path = tmp_path / "source"
+    source_path.mkdir()
+    source_uris, _, _ = start_cluster(source_path, 1)
+    source_uri = source_uris[0]
+
+    create_collection(source_uri, COLLECTION_NAME, shard_number=2)
+    create_field_index(source_uri, COLLECTION_NAME)
+    upsert_random_points(source_uri, 250, collection_name=COLLECTION_NAME, batch_size=100)
+
+    r = requests.post(
+        f"{source_uri}/collections/{COLLECTION_NAME}/migrate?wait=true",
+        json={
+            "uri": target_grpc_uri,
+            "collection": "migrated",
+            "alias": "production",
+            "batch_size": 64,
+        },
+    )
+    assert_http_ok(r)
+    report = r.json()["result"]
+    assert report["collection"] == "migrated"
+    assert report["points_count"] == 250
+    assert report["alias"] == "production"
+
+    # The target collection has the configuration, payload indexes and points of the source
+    r = requests.get(f"{target_uri}/collections/migrated")
+    assert_http_ok(r)
+    info = r.json()["result"]
+    assert info["config"]["params"]["shard_number"] == 2
+    assert info["payload_schema"]["city"]["data_type"] == "keyword"
+
+    # The alias redirects to the migrated collection
+    r = requests.post(
+        f"{target_uri}/collections/production/points/count", json={"exact": True}
+    )
+    assert_http_ok(r)
+    assert r.json()["result"]["count"] == 250
+
+    r = requests.post(
+        f"{source_uri}/collections/{COLLECTION_NAME}/points/scroll",
+        json={"limit": 10, "with_payload": True, "with_vector": True},
+    )
+    assert_http_ok(r)
+    source_points = r.json()["result"]["points"]
+
+    r = requests.post(
+        f"{target_uri}/collections/production/points",
+        json={
+            "ids": [point["id"] for point in source_points],
+            "with_payload": True,
+            "with_vector": True,
+        },
+    )
+    assert_http_ok(r)
+    target_points = {point["id"]: point for point in r.json()["result"]}
+    for point in source_points:
+        assert target_points[point["id"]]["payload"] == point["payload"]
+        assert target_points[point["id"]]["vector"] == point["vector"]
+
+    # Migrating into an existing collection fails, unless it is not created again
+    r = requests.post(
+        f"{source_uri}/collections/{COLLECTION_NAME}/migrate?wait=true",
+        json={"uri": target_grpc_uri, "collection": "migrated"},
+    )
+    assert not r.ok
+
+    r = requests.post(
+        f"{source_uri}/collections/{COLLECTION_NAME}/migrate?wait=true",
+        json={"uri": target_grpc_uri, "collection": "migrated", "create_collection": False},
+    )
+    assert_http_ok(r)
+    assert r.json()["result"]["points_count"] == 250
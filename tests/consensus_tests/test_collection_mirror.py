@@ -0,0 +1,105 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+
+COLLECTION_NAME = "test_collection"
+
+
+def count_points(peer_api_uri: str) -> int:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/points/count", json={"exact": True}
+    )
+    assert_http_ok(r)
+    return r.json()["result"]["count"]
+
+
+def get_mirror_status(peer_api_uri: str) -> dict:
+    r = requests.get(f"{peer_api_uri}/collections/{COLLECTION_NAME}/mirror")
+    assert_http_ok(r)
+    return r.json()["result"]
+
+
+def mirror_caught_up(primary_uri: str, secondary_uri: str) -> bool:
+    status = get_mirror_status(primary_uri)
+    return status["pending_operations"] == 0 and count_points(secondary_uri) == count_points(
+        primary_uri
+    )
+
+
+def test_collection_mirror(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    secondary_path = tmp_path / "secondary"
+    secondary_path.mkdir()
+    secondary_uris, _, _ = start_cluster(secondary_path, 1)
+    secondary_uri = secondary_uris[0]
+    secondary_grpc_port = processes[-1].grpc_port
+
+    create_collection(secondary_uri, COLLECTION_NAME)
+
+    primary_path = tmp_path / "primary"
+    primary_path.mkdir()
+    extra_env = {
+        "QDRANT__STORAGE__MIRRORS__TEST_COLLECTION__URI": f"http://127.0.0.1:{secondary_grpc_port}",
+    }
+    primary_uris, _, _ = start_cluster(primary_path, 1, extra_env=extra_env)
+    primary_uri = primary_uris[0]
+
+    create_collection(primary_uri, COLLECTION_NAME)
+
+    # Writes are mirrored to the secondary cluster
+    upsert_random_points(primary_uri, 100, collection_name=COLLECTION_NAME)
+    wait_for(mirror_caught_up, primary_uri, secondary_uri)
+    assert count_points(secondary_uri) == 100
+
+    r = requests.post(
+        f"{primary_uri}/collections/{COLLECTION_NAME}/points/delete?wait=true",
+        json={"points": list(range(10))},
+    )
+    assert_http_ok(r)
+    wait_for(mirror_caught_up, primary_uri, secondary_uri)
+    assert count_points(secondary_uri) == 90
+
+    status = get_mirror_status(primary_uri)
+    assert status["in_sync"]
+    assert status["mirrored_operations"] >= 2
+    assert status["failed_operations"] == 0
+
+    # Points written only to the secondary are removed by a resync
+    upsert_random_points(secondary_uri, 20, collection_name=COLLECTION_NAME, offset=1000)
+    assert count_points(secondary_uri) == 110
+
+    r = requests.post(f"{primary_uri}/collections/{COLLECTION_NAME}/mirror/resync?wait=true")
+    assert_http_ok(r)
+    assert r.json()["result"]["in_sync"]
+    assert count_points(secondary_uri) == 90
+
+    # The mirror collection on the secondary is now an alias of the resynced copy
+    r = requests.get(f"{secondary_uri}/aliases")
+    assert_http_ok(r)
+    aliases = {
+        alias["alias_name"]: alias["collection_name"] for alias in r.json()["result"]["aliases"]
+    }
+    first_copy = aliases[COLLECTION_NAME]
+    assert first_copy.startswith(f"{COLLECTION_NAME}-resync-")
+
+    # Writes after the resync are mirrored into the new copy
+    upsert_random_points(primary_uri, 10, collection_name=COLLECTION_NAME, offset=2000)
+    wait_for(mirror_caught_up, primary_uri, secondary_uri)
+    assert count_points(secondary_uri) == 100
+
+    # Another resync replaces the copy, and deletes the previous one
+    r = requests.post(f"{primary_uri}/collections/{COLLECTION_NAME}/mirror/resync?wait=true")
+    assert_http_ok(r)
+    assert count_points(secondary_uri) == 100
+
+    r = requests.get(f"{secondary_uri}/collections")
+    assert_http_ok(r)
+    collections = [collection["name"] for collection in r.json()["result"]["collections"]]
+    assert first_copy not in collections
+    assert len(collections) == 1
+
+    # Collections without a configured mirror have no status
+    r = requests.get(f"{secondary_uri}/collections/{COLLECTION_NAME}/mirror")
+    assert r.status_code == 400
@@ -0,0 +1,114 @@
+import pathlib
+import time
+
+from .fixtures import create_collection, create_field_index, upsert_random_points
+from .utils import *
+
+COLLECTION_NAME = "test_collection"
+
+
+def get_standby_status(peer_api_uri: str) -> dict:
+    r = requests.get(f"{peer_api_uri}/collections/{COLLECTION_NAME}/standby")
+    assert_http_ok(r)
+    return r.json()["result"]
+
+
+def get_follower_status(peer_api_uris: [str]) -> dict:
+    """Status reported by the peer, which follows the primary"""
+    for peer_api_uri in peer_api_uris:
+        status = get_standby_status(peer_api_uri)
+        if status["follower_peer_id"] == get_cluster_info(peer_api_uri)["peer_id"]:
+            return status
+    raise Exception("No peer follows the primary")
+
+
+def get_points(peer_api_uri: str) -> dict:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/points/scroll",
+        json={"limit": 1000, "with_payload": True, "with_vector": True},
+    )
+    assert_http_ok(r)
+    return {point["id"]: point for point in r.json()["result"]["points"]}
+
+
+def standby_caught_up(primary_uri: str, standby_uri: str) -> bool:
+    r = requests.get(f"{standby_uri}/collections/{COLLECTION_NAME}")
+    if not r.ok:
+        return False
+    return get_points(standby_uri) == get_points(primary_uri)
+
+
+def test_collection_standby(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    primary_path = tmp_path / "primary"
+    primary_path.mkdir()
+    primary_uris, _, _ = start_cluster(primary_path, 1)
+    primary_uri = primary_uris[0]
+
+    create_collection(primary_uri, COLLECTION_NAME)
+    create_field_index(primary_uri, COLLECTION_NAME)
+    upsert_random_points(primary_uri, 100, collection_name=COLLECTION_NAME)
+
+    standby_path = tmp_path / "standby"
+    standby_path.mkdir()
+    standby_uris, _, _ = start_cluster(standby_path, 2)
+
+    r = requests.put(
+        f"{standby_uris[1]}/collections/{COLLECTION_NAME}/standby",
+        json={"url": primary_uri, "interval_sec": 1},
+    )
+    assert_http_ok(r)
+
+    # The standby is part of the cluster state, known to all peers
+    for standby_uri in standby_uris:
+        status = get_standby_status(standby_uri)
+        assert status["url"] == primary_uri
+        assert not status["promoted"]
+
+    # The first synchronization restores a snapshot, including payload indexes
+    for standby_uri in standby_uris:
+        wait_for(standby_caught_up, primary_uri, standby_uri)
+    assert get_follower_status(standby_uris)["restored_snapshots"] == 1
+    info = get_collection_info(standby_uris[0], COLLECTION_NAME)
+    assert info["payload_schema"]["city"]["data_type"] == "keyword"
+
+    # All peers reject writes to the standby
+    for standby_uri in standby_uris:
+        r = requests.put(
+            f"{standby_uri}/collections/{COLLECTION_NAME}/points?wait=true",
+            json={"points": [{"id": 1000, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {}}]},
+        )
+        assert r.status_code == 403
+
+    # Changes of the primary are synchronized without restoring another snapshot, from its change
+    # feed, as the single peer of the primary hosts all shards
+    upsert_random_points(primary_uri, 10, collection_name=COLLECTION_NAME, offset=95)
+    r = requests.post(
+        f"{primary_uri}/collections/{COLLECTION_NAME}/points/delete?wait=true",
+        json={"points": list(range(10))},
+    )
+    assert_http_ok(r)
+
+    for standby_uri in standby_uris:
+        wait_for(standby_caught_up, primary_uri, standby_uri)
+    status = get_follower_status(standby_uris)
+    assert status["restored_snapshots"] == 1
+    assert status["change_feed_syncs"] >= 1
+    assert status["updated_points"] >= 10
+    assert status["deleted_points"] >= 10
+    assert status["last_error"] is None
+
+    # Promotion on any peer makes all of them accept writes
+    r = requests.post(f"{standby_uris[0]}/collections/{COLLECTION_NAME}/standby/promote")
+    assert_http_ok(r)
+    assert r.json()["result"]["promoted"]
+
+    for standby_uri in standby_uris:
+        assert get_standby_status(standby_uri)["promoted"]
+        upsert_random_points(standby_uri, 5, collection_name=COLLECTION_NAME, offset=1000)
+
+    # The promoted standby doesn't follow the primary anymore
+    upsert_random_points(primary_uri, 5, collection_name=COLLECTION_NAME, offset=2000)
+    time.sleep(3)
+    assert 2000 not in get_points(standby_uris[0])
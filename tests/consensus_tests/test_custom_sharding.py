@@ -216,3 +216,171 @@ def test_shard_consistency(tmp_path: pathlib.Path):
     assert len(result) == 6
     for point in result:
         assert point["shard_key"] in ["dogs", "birds"]
+
+
+def test_prefetch_shard_key(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection_with_custom_sharding(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICAS)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+
+    create_shard(peer_api_uris[0], COLLECTION_NAME, shard_key="cats")
+    create_shard(peer_api_uris[0], COLLECTION_NAME, shard_key="dogs")
+
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points?wait=true", json={
+            "shard_key": "cats",
+            "points": [
+                {"id": 1, "vector": [0.29, 0.81, 0.75, 0.11], "payload": {"name": "Barsik"}},
+                {"id": 2, "vector": [0.19, 0.11, 0.15, 0.21], "payload": {"name": "Murzik"}},
+            ]
+        })
+    assert_http_ok(r)
+
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points?wait=true", json={
+            "shard_key": "dogs",
+            "points": [
+                {"id": 5, "vector": [0.29, 0.81, 0.75, 0.11], "payload": {"name": "Sharik"}},
+                {"id": 6, "vector": [0.19, 0.11, 0.15, 0.21], "payload": {"name": "Tuzik"}},
+            ]
+        })
+    assert_http_ok(r)
+
+    # Scoped leg on `cats`, global leg on every shard
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points/query",
+        json={
+            "prefetch": [
+                {"query": [0.29, 0.81, 0.75, 0.11], "shard_key": "cats", "limit": 10},
+                {"query": [0.19, 0.11, 0.15, 0.21], "limit": 10},
+            ],
+            "query": {"fusion": "rrf"},
+            "limit": 10,
+        }
+    )
+    assert_http_ok(r)
+    result = r.json()["result"]["points"]
+    assert {point["id"] for point in result} == {1, 2, 5, 6}
+    for point in result:
+        assert point["shard_key"] == ("cats" if point["id"] < 5 else "dogs")
+
+    # Both legs scoped to different shards, while the request itself targets one of them
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points/query",
+        json={
+            "prefetch": [
+                {"query": [0.29, 0.81, 0.75, 0.11], "limit": 10},
+                {"query": [0.29, 0.81, 0.75, 0.11], "shard_key": "dogs", "limit": 10},
+            ],
+            "query": {"fusion": "rrf"},
+            "shard_key": "cats",
+            "limit": 10,
+        }
+    )
+    assert_http_ok(r)
+    result = r.json()["result"]["points"]
+    assert {point["id"] for point in result} == {1, 2, 5, 6}
+
+    # Prefetches are rescored within each shard, so they can't have their own shard key
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points/query",
+        json={
+            "prefetch": [
+                {"query": [0.29, 0.81, 0.75, 0.11], "shard_key": "cats", "limit": 10},
+            ],
+            "query": [0.29, 0.81, 0.75, 0.11],
+            "limit": 10,
+        }
+    )
+    assert r.status_code == 400
+
+
+def test_shard_key_snapshot(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection_with_custom_sharding(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICAS)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+
+    # Keep all shards of the tenant on the first peer
+    peer_id = get_cluster_info(peer_api_uris[0])["peer_id"]
+    create_shard(peer_api_uris[0], COLLECTION_NAME, shard_key="cats", shard_number=2, placement=[peer_id])
+    create_shard(peer_api_uris[0], COLLECTION_NAME, shard_key="dogs")
+
+    cats = [
+        {"id": 1, "vector": [0.29, 0.81, 0.75, 0.11], "payload": {"name": "Barsik"}},
+        {"id": 2, "vector": [0.19, 0.11, 0.15, 0.21], "payload": {"name": "Murzik"}},
+        {"id": 3, "vector": [0.99, 0.81, 0.75, 0.31], "payload": {"name": "Vaska"}},
+    ]
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points?wait=true",
+        json={"shard_key": "cats", "points": cats},
+    )
+    assert_http_ok(r)
+
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points?wait=true",
+        json={"shard_key": "dogs", "points": [{"id": 5, "vector": [0.29, 0.81, 0.75, 0.11]}]},
+    )
+    assert_http_ok(r)
+
+    # Snapshot every shard of the tenant at once
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/shards/snapshots?wait=true",
+        json={"shard_key": "cats"},
+    )
+    assert_http_ok(r)
+    snapshots = r.json()["result"]
+    assert len(snapshots) == 2
+
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points/delete?wait=true",
+        json={"shard_key": "cats", "points": [1, 2, 3]},
+    )
+    assert_http_ok(r)
+
+    # Shards of another tenant are rejected
+    cluster_info = get_collection_cluster_info(peer_api_uris[0], COLLECTION_NAME)
+    dogs_shard_ids = {
+        shard["shard_id"]
+        for shard in cluster_info["local_shards"] + cluster_info["remote_shards"]
+        if shard.get("shard_key") == "dogs"
+    }
+    assert dogs_shard_ids
+    for shard_id in dogs_shard_ids:
+        r = requests.put(
+            f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/shards/snapshots/recover?wait=true",
+            json={
+                "shard_key": "cats",
+                "snapshots": [{"shard_id": shard_id, "location": snapshots[0]["snapshot"]["name"]}],
+            },
+        )
+        assert r.status_code == 400
+
+    # Restore the tenant, without touching the other one
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/shards/snapshots/recover?wait=true",
+        json={
+            "shard_key": "cats",
+            "snapshots": [
+                {
+                    "shard_id": snapshot["shard_id"],
+                    "location": snapshot["snapshot"]["name"],
+                    "checksum": snapshot["snapshot"]["checksum"],
+                }
+                for snapshot in snapshots
+            ],
+        },
+    )
+    assert_http_ok(r)
+
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points/scroll",
+        json={"limit": 10, "with_payload": False},
+    )
+    assert_http_ok(r)
+    assert sorted(point["id"] for point in r.json()["result"]["points"]) == [1, 2, 3, 5]
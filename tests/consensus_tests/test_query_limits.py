@@ -0,0 +1,102 @@
+import pathlib
+
+from .fixtures import create_collection, create_field_index, random_dense_vector, upsert_random_points
+from .utils import *
+
+N_PEERS = 2
+COLLECTION_NAME = "test_collection"
+
+LIMITS_ENV = {
+    "QDRANT__STORAGE__QUERY_LIMITS__MAX_LIMIT": "10",
+    "QDRANT__STORAGE__QUERY_LIMITS__MAX_PREFETCH_DEPTH": "1",
+    "QDRANT__STORAGE__QUERY_LIMITS__FORBID_UNINDEXED_FILTER": "true",
+}
+
+# Exceeds all of the limits
+QUERY_OVER_LIMITS = {
+    "prefetch": {
+        "prefetch": {"query": random_dense_vector(), "limit": 10},
+        "query": random_dense_vector(),
+        "limit": 10,
+    },
+    "query": random_dense_vector(),
+    "filter": {"must": [{"key": "country", "match": {"value": "Germany"}}]},
+    "limit": 20,
+}
+
+
+def setup_collection(tmp_path: pathlib.Path, mode: str) -> str:
+    peer_urls, _, _ = start_cluster(
+        tmp_path, N_PEERS, extra_env={**LIMITS_ENV, "QDRANT__STORAGE__QUERY_LIMITS__MODE": mode}
+    )
+
+    create_collection(peer_urls[0], shard_number=N_PEERS)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME, peer_api_uris=peer_urls
+    )
+    create_field_index(peer_urls[0])
+    upsert_random_points(peer_urls[0], 100)
+
+    return peer_urls[0]
+
+
+def test_query_limits_warn(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_url = setup_collection(tmp_path, "warn")
+
+    # Queries within the limits have no warnings
+    r = requests.post(
+        f"{peer_url}/collections/{COLLECTION_NAME}/points/query",
+        json={
+            "query": random_dense_vector(),
+            "filter": {"must": [{"key": "city", "match": {"value": "London"}}]},
+            "limit": 10,
+        },
+    )
+    assert_http_ok(r)
+    assert "warnings" not in r.json()
+
+    # Queries over the limits are executed, and the exceeded limits are returned in the body
+    r = requests.post(
+        f"{peer_url}/collections/{COLLECTION_NAME}/points/query", json=QUERY_OVER_LIMITS
+    )
+    assert_http_ok(r)
+    assert r.json()["warnings"] == [
+        "limit 20 exceeds the max limit of 10",
+        "prefetch depth 2 exceeds the max depth of 1",
+        "filter by field 'country' without a suitable index",
+    ]
+
+    # Limits apply to other kinds of searches as well
+    r = requests.post(
+        f"{peer_url}/collections/{COLLECTION_NAME}/points/search",
+        json={"vector": random_dense_vector(), "limit": 20},
+    )
+    assert_http_ok(r)
+    assert len(r.json()["result"]) == 20
+    assert r.json()["warnings"] == ["limit 20 exceeds the max limit of 10"]
+
+    # Shard requests to the other peer are not counted again
+    r = requests.get(f"{peer_url}/metrics")
+    assert_http_ok(r)
+    assert f'collections_queries_over_soft_limits_total{{collection="{COLLECTION_NAME}"}} 2' in r.text
+
+
+def test_query_limits_enforce(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_url = setup_collection(tmp_path, "enforce")
+
+    r = requests.post(
+        f"{peer_url}/collections/{COLLECTION_NAME}/points/query", json=QUERY_OVER_LIMITS
+    )
+    assert r.status_code == 400
+    assert "Query exceeds the limits: limit 20 exceeds the max limit of 10" in r.json()["status"]["error"]
+
+    r = requests.post(
+        f"{peer_url}/collections/{COLLECTION_NAME}/points/recommend/groups",
+        json={"positive": [1], "group_by": "city", "limit": 5, "group_size": 3},
+    )
+    assert r.status_code == 400
+    assert "limit 15 exceeds the max limit of 10" in r.json()["status"]["error"]
@@ -0,0 +1,42 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+
+N_PEERS = 3
+COLLECTION_NAME = "test_collection"
+
+
+def replicas_caught_up(peer_url: str) -> bool:
+    info = get_collection_cluster_info(peer_url, COLLECTION_NAME)
+    return all(
+        remote_shard.get("replication_lag") == 0 for remote_shard in info["remote_shards"]
+    )
+
+
+def test_replication_lag_in_cluster_info(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_urls, _peer_dirs, _bootstrap_url = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_urls[0], shard_number=1, replication_factor=N_PEERS)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME, peer_api_uris=peer_urls
+    )
+    upsert_random_points(peer_urls[0], 100)
+
+    # Every peer reports the lag of the other replicas, which catch up with the updates
+    for peer_url in peer_urls:
+        wait_for(replicas_caught_up, peer_url)
+
+        info = get_collection_cluster_info(peer_url, COLLECTION_NAME)
+        assert len(info["remote_shards"]) == N_PEERS - 1
+        for remote_shard in info["remote_shards"]:
+            assert remote_shard["state"] == "Active"
+            assert remote_shard["degraded"] is False
+
+        # Metrics are reported per collection
+        r = requests.get(f"{peer_url}/metrics")
+        assert_http_ok(r)
+        assert f'collections_max_replication_lag{{collection="{COLLECTION_NAME}"}} 0' in r.text
+        assert f'collections_degraded_replicas{{collection="{COLLECTION_NAME}"}} 0' in r.text
@@ -0,0 +1,61 @@
+import http.client
+import json
+import os
+import pathlib
+import socket
+import stat
+
+import grpc
+
+from .utils import *
+
+
+class UnixHTTPConnection(http.client.HTTPConnection):
+    def __init__(self, path: str):
+        super().__init__("localhost")
+        self.path = path
+
+    def connect(self):
+        self.sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        self.sock.connect(self.path)
+
+
+def test_unix_socket(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    http_socket = tmp_path / "http.sock"
+    grpc_socket = tmp_path / "grpc.sock"
+
+    extra_env = {
+        "QDRANT__SERVICE__HTTP_UNIX_SOCKET": str(http_socket),
+        "QDRANT__SERVICE__GRPC_UNIX_SOCKET": str(grpc_socket),
+        "QDRANT__SERVICE__UNIX_SOCKET_PERMISSIONS": "600",
+    }
+
+    peer_api_uris, _peer_dirs, _bootstrap_uri = start_cluster(tmp_path, 1, extra_env=extra_env)
+
+    # TCP listeners are still served
+    assert_http_ok(requests.get(f"{peer_api_uris[0]}/readyz"))
+
+    for path in (http_socket, grpc_socket):
+        mode = os.stat(path).st_mode
+        assert stat.S_ISSOCK(mode)
+        assert stat.S_IMODE(mode) == 0o600
+
+    # REST over the Unix socket
+    connection = UnixHTTPConnection(str(http_socket))
+    connection.request("GET", "/collections")
+    response = connection.getresponse()
+    assert response.status == 200
+    assert json.loads(response.read())["status"] == "ok"
+    connection.close()
+
+    # gRPC over the Unix socket, with an empty `HealthCheckRequest`
+    with grpc.insecure_channel(f"unix:{grpc_socket}") as channel:
+        health_check = channel.unary_unary(
+            "/qdrant.Qdrant/HealthCheck",
+            request_serializer=lambda request: request,
+            response_deserializer=lambda response: response,
+        )
+        reply = health_check(b"", timeout=10)
+        assert b"qdrant" in reply
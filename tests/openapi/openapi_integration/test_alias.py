@@ -181,3 +181,126 @@ def test_alias_operations():
         }
     )
     assert response.status_code == 404
+
+
+def test_alias_history(on_disk_vectors):
+    second_collection_name = f'{collection_name}_2'
+    alias_name = 'test_alias_history'
+
+    basic_collection_setup(collection_name=second_collection_name, on_disk_vectors=on_disk_vectors)
+
+    for actions in [
+        [{"create_alias": {"alias_name": alias_name, "collection_name": collection_name}}],
+        [
+            {"delete_alias": {"alias_name": alias_name}},
+            {"create_alias": {"alias_name": alias_name, "collection_name": second_collection_name}},
+        ],
+        [{"delete_alias": {"alias_name": alias_name}}],
+    ]:
+        response = request_with_validation(
+            api='/collections/aliases',
+            method="POST",
+            body={"actions": actions}
+        )
+        assert response.ok
+
+    response = request_with_validation(
+        api='/aliases/{alias_name}/history',
+        path_params={'alias_name': alias_name},
+        method="GET"
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert result['alias_name'] == alias_name
+
+    # History outlives the alias, keep only changes of this run
+    changes = result['changes'][-4:]
+    assert [change['collection_name'] for change in changes] == [
+        collection_name,
+        None,
+        second_collection_name,
+        None,
+    ]
+    assert all(change['changed_at'] is not None for change in changes)
+
+
+def test_alias_history_not_found():
+    response = request_with_validation(
+        api='/aliases/{alias_name}/history',
+        path_params={'alias_name': 'test_alias_never_created'},
+        method="GET"
+    )
+    assert response.status_code == 404
+
+
+def test_alias_versions(on_disk_vectors):
+    alias_name = 'test_alias_versions'
+
+    response = request_with_validation(
+        api='/collections/aliases',
+        method="POST",
+        body={
+            "actions": [
+                {"create_alias": {"alias_name": alias_name, "collection_name": collection_name}}
+            ]
+        }
+    )
+    assert response.ok
+
+    # Clone: same configuration and points
+    response = request_with_validation(
+        api='/aliases/{alias_name}/versions',
+        path_params={'alias_name': alias_name},
+        method="POST",
+        body={"copy_points": True}
+    )
+    assert response.ok
+    clone = response.json()['result']
+    assert clone['alias_name'] == alias_name
+    assert clone['previous_collection_name'] == collection_name
+    assert clone['collection_name'] == f"{alias_name}-v{clone['version']}"
+
+    # Rebuild: new configuration, no points
+    response = request_with_validation(
+        api='/aliases/{alias_name}/versions',
+        path_params={'alias_name': alias_name},
+        method="POST",
+        body={"config": {"vectors": {"size": 4, "distance": "Dot"}}}
+    )
+    assert response.ok
+    rebuild = response.json()['result']
+    assert rebuild['previous_collection_name'] == clone['collection_name']
+    assert rebuild['version'] == clone['version'] + 1
+
+    try:
+        counts = {}
+        for name in [collection_name, clone['collection_name'], alias_name]:
+            response = request_with_validation(
+                api='/collections/{collection_name}/points/count',
+                path_params={'collection_name': name},
+                method="POST",
+                body={"exact": True}
+            )
+            assert response.ok
+            counts[name] = response.json()['result']['count']
+        assert counts[clone['collection_name']] == counts[collection_name] > 0
+        assert counts[alias_name] == 0
+
+        response = request_with_validation(
+            api='/aliases/{alias_name}/history',
+            path_params={'alias_name': alias_name},
+            method="GET"
+        )
+        assert response.ok
+        # History outlives the alias, keep only changes of this run
+        changes = response.json()['result']['changes'][-5:]
+        assert [change['collection_name'] for change in changes] == [
+            collection_name,
+            None,
+            clone['collection_name'],
+            None,
+            rebuild['collection_name'],
+        ]
+    finally:
+        drop_collection(collection_name=clone['collection_name'])
+        drop_collection(collection_name=rebuild['collection_name'])
@@ -191,3 +191,77 @@ def test_edit_collection_params(on_disk_vectors, on_disk_payload):
     assert config["quantization_config"]["scalar"]["type"] == "int8"
     assert config["quantization_config"]["scalar"]["quantile"] == 0.99
     assert config["quantization_config"]["scalar"]["always_ram"]
+
+
+def test_collection_freeze():
+    response = request_with_validation(
+        api='/collections/{collection_name}/freeze',
+        method="POST",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()["result"]["config"]["params"]["frozen"]
+
+    # Points of frozen collections can't be updated
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 7,
+                    "vector": [0.15, 0.31, 0.76, 0.74],
+                    "payload": {"city": "Rome"}
+                }
+            ]
+        }
+    )
+    assert response.status_code == 400
+    assert "frozen" in response.json()["status"]["error"]
+
+    # Reads are not affected
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 2},
+    )
+    assert response.ok
+
+    # Unfreezing allows updates again
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "params": {
+                "frozen": False,
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 7,
+                    "vector": [0.15, 0.31, 0.76, 0.74],
+                    "payload": {"city": "Rome"}
+                }
+            ]
+        }
+    )
+    assert response.ok
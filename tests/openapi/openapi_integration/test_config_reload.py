@@ -0,0 +1,13 @@
+from .helpers.helpers import request_with_validation
+
+
+def test_reload_unchanged_config():
+    response = request_with_validation(
+        api="/config/reload",
+        method="POST",
+    )
+    assert response.ok, response.text
+
+    # Configuration files were not changed since startup
+    report = response.json()["result"]
+    assert report == {"applied": [], "requires_restart": []}
@@ -58,3 +58,79 @@ def test_delete_points():
     )
     assert response.ok
     assert response.json()['result']['points_count'] == 5
+
+
+def test_delete_points_report():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'report': 'true'},
+        body={
+            "points": [1, 2, 100, 3, 200]
+        }
+    )
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert result['status'] == 'completed'
+    assert sorted(result['report']['deleted']) == [1, 2, 3]
+    assert sorted(result['report']['not_found']) == [100, 200]
+    assert result['report']['failed'] == []
+
+    # deleted points are not found anymore
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'report': 'true'},
+        body={
+            "points": [1, 4]
+        }
+    )
+    assert response.ok, response.text
+    report = response.json()['result']['report']
+    assert report['deleted'] == [4]
+    assert report['not_found'] == [1]
+
+    # no report without asking for it
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [5]
+        }
+    )
+    assert response.ok, response.text
+    assert 'report' not in response.json()['result']
+
+
+def test_delete_points_report_invalid():
+    # report of deletion by filter
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'report': 'true'},
+        body={
+            "filter": {
+                "must": [
+                    {"has_id": [5]}
+                ]
+            }
+        }
+    )
+    assert response.status_code == 400, response.text
+
+    # report without waiting for the deletion
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'report': 'true'},
+        body={
+            "points": [5]
+        }
+    )
+    assert response.status_code == 400, response.text
@@ -0,0 +1,25 @@
+from .helpers.helpers import request_with_validation
+
+
+def test_diagnostics_bundle():
+    response = request_with_validation(
+        api="/diagnostics",
+        method="GET",
+    )
+    assert response.ok, response.text
+    assert "attachment" in response.headers["content-disposition"]
+
+    bundle = response.json()
+    assert set(bundle) == {"created_at", "config", "telemetry", "issues", "recent_errors"}
+    assert "http_port" in bundle["config"]["service"]
+    assert "collections" in bundle["telemetry"]
+    assert "cluster" in bundle["telemetry"]
+
+
+def test_diagnostics_bundle_anonymized():
+    response = request_with_validation(
+        api="/diagnostics",
+        method="GET",
+        query_params={"anonymize": "true"},
+    )
+    assert response.ok, response.text
@@ -0,0 +1,58 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_error_codes"
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def assert_error_code(response, status_code, code):
+    assert response.status_code == status_code, response.text
+    assert response.json()["status"]["code"] == code
+
+
+def test_vector_dimension_mismatch():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={"points": [{"id": 1, "vector": [0.1, 0.2, 0.3]}]},
+    )
+    assert_error_code(response, 400, "VECTOR_DIM_MISMATCH")
+
+
+def test_payload_index_missing():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/scroll",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"order_by": "count", "limit": 10},
+    )
+    assert_error_code(response, 400, "PAYLOAD_INDEX_MISSING")
+
+
+def test_not_found():
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": "non-existing-collection"},
+    )
+    assert_error_code(response, 404, "NOT_FOUND")
+
+
+def test_bad_request():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"query": {"fusion": "rrf"}, "using": "missing"},
+    )
+    assert_error_code(response, 400, "BAD_REQUEST")
@@ -0,0 +1,132 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_filter_templates"
+
+TENANT_TEMPLATE = {
+    "filter": {
+        "must": [
+            {"key": "tenant", "match": {"value": {"$param": "tenant"}}},
+            {"key": "level", "range": {"lte": {"$param": "level"}}},
+        ]
+    }
+}
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={"vectors": {"size": 2, "distance": "Dot"}},
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={
+            "points": [
+                {"id": 1, "vector": [1.0, 0.0], "payload": {"tenant": "acme", "level": 1}},
+                {"id": 2, "vector": [0.9, 0.1], "payload": {"tenant": "acme", "level": 5}},
+                {"id": 3, "vector": [0.8, 0.2], "payload": {"tenant": "globex", "level": 1}},
+                {"id": 4, "vector": [0.7, 0.3], "payload": {"tenant": "acme", "level": 2}},
+            ]
+        },
+    )
+    assert response.ok, response.text
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_filter_templates(filter_templates):
+    return request_with_validation(
+        api="/collections/{collection_name}",
+        method="PATCH",
+        path_params={"collection_name": collection_name},
+        body={"params": {"filter_templates": filter_templates}},
+    )
+
+
+def query(**body):
+    return request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"query": [1.0, 0.0], **body},
+    )
+
+
+def test_filter_templates():
+    response = set_filter_templates({"tenant": TENANT_TEMPLATE})
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok, response.text
+    assert response.json()["result"]["config"]["params"]["filter_templates"] == {
+        "tenant": TENANT_TEMPLATE
+    }
+
+    template = {"name": "tenant", "params": {"tenant": "acme", "level": 2}}
+    response = query(filter_template=template)
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [1, 4]
+
+    # the template is applied together with the filter of the request
+    response = query(
+        filter_template=template,
+        filter={"must_not": [{"has_id": [1]}]},
+    )
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [4]
+
+    # the template also restricts prefetches which don't get the filter of the request
+    response = query(
+        prefetch=[{"query": [0.0, 1.0]}],
+        query={"fusion": "rrf"},
+        filter_template=template,
+        propagate_filter=False,
+    )
+    assert response.ok, response.text
+    assert sorted(point["id"] for point in response.json()["result"]["points"]) == [1, 4]
+
+
+def test_filter_templates_invalid_params():
+    response = set_filter_templates({"tenant": TENANT_TEMPLATE})
+    assert response.ok, response.text
+
+    # missing parameter
+    response = query(filter_template={"name": "tenant", "params": {"tenant": "acme"}})
+    assert response.status_code == 400, response.text
+
+    # unknown parameter
+    response = query(
+        filter_template={
+            "name": "tenant",
+            "params": {"tenant": "acme", "level": 2, "other": 1},
+        }
+    )
+    assert response.status_code == 400, response.text
+
+    # unknown template
+    response = query(filter_template={"name": "other"})
+    assert response.status_code == 400, response.text
+
+
+def test_filter_templates_malformed_placeholder():
+    response = set_filter_templates(
+        {"tenant": {"filter": {"must": [{"key": "tenant", "match": {"value": {"$param": 1}}}]}}}
+    )
+    assert response.status_code == 400, response.text
@@ -274,3 +274,71 @@ def test_scroll_with_prefix():
 
     assert response.ok
     assert len(response.json()['result']) == 3
+
+
+def test_custom_tokenizer():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 1000 + idx,
+                    "vector": [random.random() for _ in range(4)],
+                    "payload": {"code": code}
+                } for idx, code in enumerate(["parseHttpRequest", "parse_json_body", "HTTPServer"])
+            ]
+        }
+    )
+    assert response.ok
+
+    # Unknown tokenizers are rejected
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "code",
+            "field_schema": {
+                "type": "text",
+                "tokenizer": "custom",
+                "custom_tokenizer": "unknown",
+            }
+        }
+    )
+    assert response.status_code == 400
+    assert "code_identifier" in response.json()["status"]["error"]
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "code",
+            "field_schema": {
+                "type": "text",
+                "tokenizer": "custom",
+                "custom_tokenizer": "code_identifier",
+            }
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "limit": 10,
+            "filter": {
+                "must": [{"key": "code", "match": {"text": "http"}}]
+            }
+        }
+    )
+    assert response.ok
+    ids = sorted(point['id'] for point in response.json()['result']['points'])
+    assert ids == [1000, 1002]
@@ -17,7 +17,7 @@ def upsert_chunked_docs(collection_name, docs=50, chunks=5):
         for chunk in range(chunks):
             doc_id = doc
             i = doc * chunks + chunk
-            p = {"id": i, "vector": [1.0, 0.0, 0.0, 0.0], "payload": {"docId": doc_id}}
+            p = {"id": i, "vector": [1.0, 0.0, 0.0, 0.0], "payload": {"docId": doc_id, "chunk": chunk}}
             points.append(p)
 
     response = request_with_validation(
@@ -175,6 +175,74 @@ def test_search():
             assert h["payload"]["docId"] == g["id"]
 
 
+def test_search_with_neighbors():
+    chunks = 5
+
+    response = request_with_validation(
+        api=SEARCH_GROUPS_API,
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "vector": [1.0, 0.0, 0.0, 0.0],
+            "limit": 10,
+            "with_payload": ["docId"],
+            "group_by": "docId",
+            "group_size": 1,
+            "with_neighbors": {"ordinal_by": "chunk", "neighbors": 1},
+        },
+    )
+    assert response.ok, response.text
+
+    groups = response.json()["result"]["groups"]
+
+    assert len(groups) == 10
+    for group in groups:
+        best_id = group["hits"][0]["id"]
+        doc, chunk = divmod(best_id, chunks)
+
+        expected_ids = [
+            doc * chunks + neighbor
+            for neighbor in range(max(chunk - 1, 0), min(chunk + 1, chunks - 1) + 1)
+        ]
+        neighbors = group["neighbors"]
+        assert [point["id"] for point in neighbors] == expected_ids
+        for point in neighbors:
+            # payload is selected as for the hits
+            assert point["payload"] == {"docId": group["id"]}
+
+
+def test_search_with_aggregates():
+    response = request_with_validation(
+        api=SEARCH_GROUPS_API,
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "vector": [1.0, 0.0, 0.0, 0.0],
+            "limit": 10,
+            "with_payload": ["docId"],
+            "group_by": "docId",
+            "group_size": 2,
+            "with_aggregates": {"mean": ["chunk", "missing"]},
+        },
+    )
+    assert response.ok, response.text
+
+    groups = response.json()["result"]["groups"]
+
+    assert len(groups) == 10
+    for group in groups:
+        aggregates = group["aggregates"]
+        # aggregates cover all points of the group, not only the returned hits
+        assert len(group["hits"]) == 2
+        assert aggregates["count"] == 5
+        assert aggregates["best_score"] == group["hits"][0]["score"]
+        # mean of chunks 0..4, fields without numeric values are omitted
+        assert aggregates["mean"] == {"chunk": 2.0}
+        for hit in group["hits"]:
+            # aggregated fields are not returned, unless requested
+            assert hit["payload"] == {"docId": group["id"]}
+
+
 def test_recommend():
     response = request_with_validation(
         api=RECO_GROUPS_API,
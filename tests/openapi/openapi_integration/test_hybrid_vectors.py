@@ -0,0 +1,157 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_hybrid_vectors"
+
+
+def create_collection(**body):
+    return request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body=body,
+    )
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = create_collection(
+        hybrid_vectors={"text": {"dense": {"size": 2, "distance": "Dot"}}},
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={
+            "points": [
+                {
+                    "id": 1,
+                    "vector": {
+                        "text-dense": [1.0, 0.0],
+                        "text-sparse": {"indices": [1], "values": [0.1]},
+                    },
+                },
+                {
+                    "id": 2,
+                    "vector": {
+                        "text-dense": [0.5, 0.5],
+                        "text-sparse": {"indices": [1], "values": [1.0]},
+                    },
+                },
+                {
+                    "id": 3,
+                    "vector": {
+                        "text-dense": [0.0, 1.0],
+                        "text-sparse": {"indices": [2], "values": [1.0]},
+                    },
+                },
+            ]
+        },
+    )
+    assert response.ok, response.text
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def query(**body):
+    return request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body=body,
+    )
+
+
+HYBRID_QUERY = {
+    "hybrid": {
+        "dense": [1.0, 0.2],
+        "sparse": {"indices": [1], "values": [1.0]},
+    }
+}
+
+
+def test_hybrid_vectors_config():
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok, response.text
+    params = response.json()["result"]["config"]["params"]
+    assert params["vectors"]["text-dense"]["size"] == 2
+    assert "text-sparse" in params["sparse_vectors"]
+    assert params["hybrid_vectors"] == {
+        "text": {"dense": "text-dense", "sparse": "text-sparse"}
+    }
+
+
+def test_hybrid_query():
+    response = query(query=HYBRID_QUERY, using="text", limit=3)
+    assert response.ok, response.text
+    points = response.json()["result"]["points"]
+    # Point 3 is only found by the dense vector, and ranked last by it
+    assert [point["id"] for point in points][:2] in ([1, 2], [2, 1])
+    assert points[-1]["id"] == 3
+
+    # Same as fusing the prefetches of both vectors
+    response = query(
+        prefetch=[
+            {"query": HYBRID_QUERY["hybrid"]["dense"], "using": "text-dense", "limit": 3},
+            {"query": HYBRID_QUERY["hybrid"]["sparse"], "using": "text-sparse", "limit": 3},
+        ],
+        query={"fusion": "rrf"},
+        limit=3,
+    )
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [
+        point["id"] for point in points
+    ]
+
+    # Hybrid queries can be used in prefetches as well
+    response = query(
+        prefetch=[{"query": HYBRID_QUERY, "using": "text"}],
+        query=[1.0, 0.0],
+        using="text-dense",
+        limit=1,
+    )
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [1]
+
+
+def test_hybrid_query_errors():
+    # Unknown hybrid vector
+    response = query(query=HYBRID_QUERY, using="other")
+    assert response.status_code == 400, response.text
+
+    # A hybrid query can't have prefetches of its own
+    response = query(
+        prefetch=[{"query": [1.0, 0.0], "using": "text-dense"}],
+        query=HYBRID_QUERY,
+        using="text",
+    )
+    assert response.status_code == 400, response.text
+
+
+def test_hybrid_vectors_conflict():
+    drop_collection(collection_name=collection_name)
+
+    response = create_collection(
+        vectors={"text-dense": {"size": 4, "distance": "Dot"}},
+        hybrid_vectors={"text": {"dense": {"size": 2, "distance": "Dot"}}},
+    )
+    assert response.status_code == 400, response.text
+
+    # Hybrid vectors need named vectors
+    response = create_collection(
+        vectors={"size": 2, "distance": "Dot"},
+        hybrid_vectors={"text": {"dense": {"size": 2, "distance": "Dot"}}},
+    )
+    assert response.status_code == 400, response.text
@@ -0,0 +1,184 @@
+import struct
+
+import pytest
+import requests
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+from .helpers.settings import QDRANT_HOST
+
+collection_name = 'test_collection_import'
+
+VECTORS = {
+    11: [1.0, 0.0, 0.0, 0.0],
+    12: [0.0, 1.0, 0.0, 0.0],
+    13: [0.0, 0.0, 1.0, 0.0],
+}
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+            },
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def faiss_flat_with_ids(vectors: dict) -> bytes:
+    """Same layout as `faiss.write_index(faiss.IndexIDMap(faiss.IndexFlatL2(4)))`"""
+
+    def header(fourcc: bytes, dim: int, ntotal: int) -> bytes:
+        # dim, ntotal, two unused values, is_trained, METRIC_L2
+        return fourcc + struct.pack('<iqqqBi', dim, ntotal, 1 << 20, 1 << 20, 1, 1)
+
+    floats = [value for vector in vectors.values() for value in vector]
+    return (
+            header(b'IxMp', 4, len(vectors))
+            + header(b'IxF2', 4, len(vectors))
+            + struct.pack(f'<Q{len(floats)}f', len(floats), *floats)
+            + struct.pack(f'<Q{len(vectors)}q', len(vectors), *vectors.keys())
+    )
+
+
+def hnswlib_index(vectors: dict) -> bytes:
+    """Same layout as `hnswlib.Index.save_index`, with up to 2 links per element"""
+    links_size = 4 + 2 * 4
+    label_offset = links_size + 4 * 4
+    element_size = label_offset + 8
+
+    data = struct.pack(
+        '<QQQQQQiIQQQdQ',
+        0,  # offsetLevel0_
+        len(vectors),  # max_elements_
+        len(vectors),  # cur_element_count
+        element_size,  # size_data_per_element_
+        label_offset,  # label_offset_
+        links_size,  # offsetData_
+        0, 0, 2, 4, 2, 1.0, 100,  # graph parameters
+    )
+    for label, vector in vectors.items():
+        data += bytes(links_size) + struct.pack('<4fQ', *vector, label)
+    return data
+
+
+def import_index(index: bytes, index_format: str):
+    return requests.post(
+        f"{QDRANT_HOST}/collections/{collection_name}/points/import",
+        params={'format': index_format, 'wait': 'true'},
+        files={'index': ('index', index)},
+    )
+
+
+def assert_imported():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"ids": list(VECTORS.keys()), "with_vector": True},
+    )
+    assert response.ok
+
+    points = {point['id']: point['vector'] for point in response.json()['result']}
+    assert points == VECTORS
+
+
+@pytest.mark.parametrize("index_format, index", [
+    ("faiss", faiss_flat_with_ids(VECTORS)),
+    ("hnswlib", hnswlib_index(VECTORS)),
+])
+def test_import(index_format, index):
+    response = import_index(index, index_format)
+    assert response.ok, response.text
+    assert response.json()['result']['status'] == 'completed'
+
+    assert_imported()
+
+
+def test_import_invalid_index():
+    response = import_index(b'IHNf' + bytes(64), "faiss")
+    assert response.status_code == 400, response.text
+
+
+def test_import_keeps_points_and_indexes():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.0, 0.0, 0.0, 1.0], "payload": {"city": "Berlin"}},
+                {"id": 11, "vector": [0.0, 0.0, 0.0, 1.0], "payload": {"city": "London"}},
+            ]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"field_name": "city", "field_schema": "keyword"}
+    )
+    assert response.ok
+
+    response = import_index(faiss_flat_with_ids(VECTORS), "faiss")
+    assert response.ok, response.text
+
+    assert_imported()
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"ids": [1, 11], "with_payload": True},
+    )
+    assert response.ok
+    payloads = {point['id']: point['payload'] for point in response.json()['result']}
+    # Imported points overwrite the existing ones
+    assert payloads == {1: {"city": "Berlin"}, 11: {}}
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert 'city' in response.json()['result']['payload_schema']
+
+
+def test_failed_import_changes_nothing():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [{"id": 1, "vector": [0.0, 0.0, 0.0, 1.0]}]}
+    )
+    assert response.ok
+
+    # Valid header and first elements, truncated in the middle of the last one
+    index = hnswlib_index(VECTORS)
+    response = import_index(index[:-10], "hnswlib")
+    assert response.status_code == 400, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={},
+    )
+    assert response.ok
+    assert [point['id'] for point in response.json()['result']['points']] == [1]
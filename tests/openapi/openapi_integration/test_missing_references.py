@@ -0,0 +1,63 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_missing_references"
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def recommend(positive, negative, **extra):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/recommend",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"positive": positive, "negative": negative, "limit": 3, **extra},
+    )
+    return response, lambda: [point["id"] for point in response.json()["result"]]
+
+
+def query(positive, negative, **extra):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": {"recommend": {"positive": positive, "negative": negative}},
+            "limit": 3,
+            **extra,
+        },
+    )
+    return response, lambda: [point["id"] for point in response.json()["result"]["points"]]
+
+
+@pytest.mark.parametrize("request_fn", [recommend, query])
+def test_missing_references_are_listed(request_fn):
+    response, _ = request_fn(positive=[1, 100], negative=[2, 200])
+    assert response.status_code == 404, response.text
+    assert (
+        response.json()["status"]["error"]
+        == f"Not found: No points with ids [100, 200] found in collection `{collection_name}`"
+    )
+
+
+@pytest.mark.parametrize("request_fn", [recommend, query])
+def test_skip_missing_references(request_fn):
+    response, ids = request_fn(positive=[1, 100], negative=[2, 200], skip_missing=True)
+    assert response.ok, response.text
+
+    expected_response, expected_ids = request_fn(positive=[1], negative=[2])
+    assert expected_response.ok, expected_response.text
+
+    assert ids() == expected_ids()
+
+
+def test_skip_all_positive_references():
+    response, _ = recommend(positive=[100], negative=[2], skip_missing=True)
+    assert response.status_code == 400, response.text
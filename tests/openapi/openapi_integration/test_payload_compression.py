@@ -0,0 +1,101 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_payload_compression"
+
+text = "Qdrant is a vector similarity search engine. " * 50
+
+
+@pytest.fixture(autouse=True, params=[False, True])
+def setup(request):
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={
+            "vectors": {"size": 4, "distance": "Dot"},
+            "on_disk_payload": request.param,
+        },
+    )
+    assert response.ok, response.text
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(ids):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={
+            "points": [
+                {
+                    "id": point_id,
+                    "vector": [0.1, 0.2, 0.3, 0.4],
+                    "payload": {"text": text, "chunk": point_id},
+                }
+                for point_id in ids
+            ]
+        },
+    )
+    assert response.ok, response.text
+
+
+def retrieve_payloads():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/scroll",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"limit": 10, "with_payload": True},
+    )
+    assert response.ok, response.text
+    return {point["id"]: point["payload"] for point in response.json()["result"]["points"]}
+
+
+def test_payload_compression():
+    # Points stored before compression is enabled stay readable
+    upsert_points([1, 2])
+
+    payload_compression = {"fields": ["text"], "level": 5}
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PATCH",
+        path_params={"collection_name": collection_name},
+        body={"params": {"payload_compression": payload_compression}},
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok, response.text
+    params = response.json()["result"]["config"]["params"]
+    assert params["payload_compression"] == payload_compression
+
+    upsert_points([3, 4])
+
+    payloads = retrieve_payloads()
+    assert len(payloads) == 4
+    for point_id, payload in payloads.items():
+        assert payload == {"text": text, "chunk": point_id}
+
+    # Filtering on compressed fields reads them as usual
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/count",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "filter": {"must": [{"key": "text", "match": {"text": "similarity"}}]},
+            "exact": True,
+        },
+    )
+    assert response.ok, response.text
+    assert response.json()["result"]["count"] == 4
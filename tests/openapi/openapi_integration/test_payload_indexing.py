@@ -57,6 +57,20 @@ def test_payload_indexing_operations():
     assert response.json()[
         'result']['payload_schema']['test_payload']['data_type'] == "keyword"
 
+    # All replicas have built the index
+    response = request_with_validation(
+        api='/collections/{collection_name}/index/{field_name}',
+        method="GET",
+        path_params={'collection_name': collection_name,
+                     'field_name': 'test_payload'},
+    )
+    assert response.ok
+    status = response.json()['result']
+    assert status['field_schema'] == "keyword"
+    assert status['consistent']
+    assert len(status['replicas']) > 0
+    assert all(replica['state'] == "built" for replica in status['replicas'])
+
     # Delete index
     response = request_with_validation(
         api='/collections/{collection_name}/index/{field_name}',
@@ -75,6 +89,14 @@ def test_payload_indexing_operations():
     assert response.ok
     assert len(response.json()['result']['payload_schema']) == 0
 
+    response = request_with_validation(
+        api='/collections/{collection_name}/index/{field_name}',
+        method="GET",
+        path_params={'collection_name': collection_name,
+                     'field_name': 'test_payload'},
+    )
+    assert response.status_code == 404
+
 
 def set_payload(payload, points):
     response = request_with_validation(
@@ -292,3 +314,52 @@ def test_update_payload_on_indexed_field():
     )
     assert response.ok
     assert [p['id'] for p in response.json()['result']['points']] == [1, 2, 3]
+
+
+def test_payload_index_stats():
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "city",
+            "field_schema": "keyword"
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 100, "with_payload": ["city"]}
+    )
+    assert response.ok
+    cities = set()
+    for point in response.json()['result']['points']:
+        city = point['payload'].get('city')
+        if isinstance(city, list):
+            cities.update(city)
+        elif city is not None:
+            cities.add(city)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index/{field_name}/stats',
+        method="GET",
+        path_params={'collection_name': collection_name,
+                     'field_name': 'city'},
+    )
+    assert response.ok
+    stats = response.json()['result']
+    assert stats['field_schema'] == "keyword"
+    # Small numbers of values are estimated exactly
+    assert stats['approximate_unique_values'] == len(cities)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index/{field_name}/stats',
+        method="GET",
+        path_params={'collection_name': collection_name,
+                     'field_name': 'price'},
+    )
+    assert response.status_code == 404
@@ -0,0 +1,94 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_point_changes'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_changes(cursor, limit=None):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/changes',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "cursor": cursor,
+            "limit": limit,
+            "with_payload": True,
+        }
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_point_changes():
+    # No cursor - only current position is returned
+    result = get_changes(None)
+    assert result['changes'] == []
+    cursor = result['next_cursor']
+
+    result = get_changes(cursor)
+    assert result['changes'] == []
+    assert result['next_cursor'] == cursor
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [1]}
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"changed": True},
+            "points": [2]
+        }
+    )
+    assert response.ok
+
+    # Changes are read operation by operation
+    result = get_changes(cursor, limit=1)
+    changes = result['changes']
+    assert len(changes) == 1
+    assert changes[0]['id'] == 1
+    assert changes[0]['point'] is None
+
+    result = get_changes(result['next_cursor'])
+    changes = result['changes']
+    assert len(changes) == 1
+    assert changes[0]['id'] == 2
+    assert changes[0]['point']['payload']['changed'] is True
+
+    result = get_changes(result['next_cursor'])
+    assert result['changes'] == []
+
+    # Points deleted by filter can't be tracked
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"filter": {"must": [{"has_id": [3]}]}}
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/changes',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"cursor": result['next_cursor']}
+    )
+    assert response.status_code == 400
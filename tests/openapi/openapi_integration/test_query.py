@@ -168,6 +168,55 @@ def test_basic_scroll():
         assert record.get("payload") == scored_point.get("payload")
 
 
+def test_search_within_ids():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "vector": [0.1, 0.2, 0.3, 0.4],
+            "filter": {"must": [{"has_id": [2, 3, 5]}]},
+            "limit": 10,
+        },
+    )
+    assert response.ok
+    search_result = response.json()["result"]
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": [0.1, 0.2, 0.3, 0.4],
+            "ids": [2, 3, 5],
+            "limit": 10,
+        },
+    )
+    assert response.ok, response.text
+    query_result = response.json()["result"]["points"]
+
+    assert len(query_result) == 3
+    assert search_result == query_result
+
+    # Prefetches only score the given points too, also if the filter is not propagated to them
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": [{"query": [0.1, 0.2, 0.3, 0.4], "limit": 10}],
+            "query": {"fusion": "rrf"},
+            "ids": [2, 3, 5],
+            "propagate_filter": False,
+            "limit": 10,
+        },
+    )
+    assert response.ok, response.text
+    fusion_result = response.json()["result"]["points"]
+
+    assert sorted(point["id"] for point in fusion_result) == [2, 3, 5]
+
+
 def test_basic_recommend_avg():
     response = request_with_validation(
         api="/collections/{collection_name}/points/recommend",
@@ -351,3 +400,359 @@ def test_basic_rrf():
         assert expected["id"] == result["id"]
         assert expected.get("payload") == result.get("payload")
         assert isclose(expected["score"], result["score"], rel_tol=1e-5)
+
+
+def test_rrf_prefetch_matches():
+    prefetches = [
+        {"query": [0.1, 0.2, 0.3, 0.4], "limit": 3},
+        {"query": [0.5, 0.6, 0.7, 0.8], "limit": 3},
+    ]
+
+    prefetch_results = []
+    for prefetch in prefetches:
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body=prefetch,
+        )
+        assert response.ok, response.text
+        prefetch_results.append(response.json()["result"]["points"])
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": prefetches,
+            "query": {"fusion": "rrf"},
+        },
+    )
+    assert response.ok, response.text
+    rrf_result = response.json()["result"]["points"]
+
+    for point in rrf_result:
+        matches = point["prefetch_matches"]
+        assert len(matches) > 0
+        for match in matches:
+            expected = prefetch_results[match["prefetch"]][match["rank"]]
+            assert expected["id"] == point["id"]
+            assert isclose(expected["score"], match["score"], rel_tol=1e-5)
+
+
+def test_rrf_offset():
+    def rrf_query(prefetch_limit, offset):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "prefetch": [
+                    {"query": [0.1, 0.2, 0.3, 0.4], "limit": prefetch_limit},
+                    {"query": [0.5, 0.6, 0.7, 0.8], "limit": prefetch_limit},
+                ],
+                "query": {"fusion": "rrf"},
+                "offset": offset,
+            },
+        )
+        assert response.ok, response.text
+        return response.json()["result"]["points"]
+
+    # Each prefetch fetches `offset` extra points, so the offset doesn't cut into the fused results
+    full_result = rrf_query(prefetch_limit=5, offset=0)
+    offset_result = rrf_query(prefetch_limit=3, offset=2)
+
+    assert len(offset_result) == len(full_result) - 2
+    for expected, result in zip(full_result[2:], offset_result):
+        assert isclose(expected["score"], result["score"], rel_tol=1e-5)
+
+
+def test_rrf_with_payload_and_vector():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": [
+                {"query": [0.1, 0.2, 0.3, 0.4], "limit": 5},
+                {"query": [0.5, 0.6, 0.7, 0.8], "limit": 5},
+            ],
+            "query": {"fusion": "rrf"},
+            "limit": 3,
+            "with_payload": True,
+            "with_vector": True,
+        },
+    )
+    assert response.ok, response.text
+    rrf_result = response.json()["result"]["points"]
+    assert len(rrf_result) == 3
+
+    # Payload and vector are fetched for the fused points
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "ids": [point["id"] for point in rrf_result],
+            "with_payload": True,
+            "with_vector": True,
+        },
+    )
+    assert response.ok, response.text
+    records = {record["id"]: record for record in response.json()["result"]}
+
+    for point in rrf_result:
+        assert point["payload"] == records[point["id"]]["payload"]
+        assert point["vector"] == records[point["id"]]["vector"]
+
+
+def test_vector_sum():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"ids": [1, 2], "with_vector": True},
+    )
+    assert response.ok
+    vectors = {point["id"]: point["vector"] for point in response.json()["result"]}
+
+    # vec(1) - vec(2) + 0.5 * [0.1, 0.2, 0.3, 0.4]
+    expected_vector = [
+        a - b + 0.5 * c
+        for a, b, c in zip(vectors[1], vectors[2], [0.1, 0.2, 0.3, 0.4])
+    ]
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "vector": expected_vector,
+            # referenced points are excluded from the results
+            "filter": {"must_not": [{"has_id": [1, 2]}]},
+            "limit": 10,
+        },
+    )
+    assert response.ok
+    search_result = response.json()["result"]
+
+    query_result = root_and_rescored_query({
+        "nearest": {
+            "sum": [
+                {"vector": 1},
+                {"vector": 2, "weight": -1},
+                {"vector": [0.1, 0.2, 0.3, 0.4], "weight": 0.5},
+            ]
+        }
+    })
+
+    assert [point["id"] for point in query_result] == [point["id"] for point in search_result]
+    for expected, point in zip(search_result, query_result):
+        assert isclose(expected["score"], point["score"], rel_tol=1e-5)
+
+    # vectors of different dimensions can't be summed
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": {"sum": [{"vector": 1}, {"vector": [0.1, 0.2]}]},
+        },
+    )
+    assert response.status_code == 400, response.text
+
+
+def test_text_budget():
+    def query(budget=None):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "query": [0.1, 0.2, 0.3, 0.4],
+                "limit": 10,
+                "with_payload": ["city"],
+                "budget": budget,
+            },
+        )
+        assert response.ok, response.text
+        return response.json()["result"]["points"]
+
+    def city_size(point):
+        city = point["payload"].get("city", [])
+        cities = city if isinstance(city, list) else [city]
+        return sum(len(city) for city in cities if isinstance(city, str))
+
+    full_result = query()
+
+    expected_result = []
+    total = 0
+    for point in full_result:
+        total += city_size(point)
+        if total > 15:
+            break
+        expected_result.append(point)
+
+    assert 0 < len(expected_result) < len(full_result)
+    assert query({"key": "city", "limit": 15}) == expected_result
+
+    # the text field doesn't need to be part of the returned payload
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": [0.1, 0.2, 0.3, 0.4],
+            "limit": 10,
+            "budget": {"key": "city", "limit": 15, "unit": "tokens"},
+        },
+    )
+    assert response.ok, response.text
+    assert len(response.json()["result"]["points"]) > 0
+
+
+def test_rerank_without_reranker():
+    # the test server has no reranker configured
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": [0.1, 0.2, 0.3, 0.4],
+            "limit": 10,
+            "rerank": {"query": "capital city", "fields": ["city"], "top": 5},
+        },
+    )
+    assert response.status_code == 400, response.text
+    assert "no reranker is configured" in response.json()["status"]["error"]
+
+
+def test_experimental_fusion_not_enabled():
+    # the test server enables no experimental features
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": [
+                {"query": [0.1, 0.2, 0.3, 0.4]},
+                {"query": [0.5, 0.6, 0.7, 0.8]},
+            ],
+            "query": {"fusion": "dbsf"},
+            "limit": 10,
+        },
+    )
+    assert response.status_code == 400, response.text
+    assert "`dbsf_fusion` is not enabled" in response.json()["status"]["error"]
+
+
+def test_query_with_routing():
+    def query(**body):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={"query": [0.1, 0.2, 0.3, 0.4], "limit": 3, **body},
+        )
+        assert response.ok, response.text
+        return response.json()["result"]["points"]
+
+    points = query(with_routing=True)
+    assert len(points) == 3
+    for point in points:
+        # the collection has a single shard without shard keys
+        assert point["routing"]["shard_id"] == 0
+        assert isinstance(point["routing"]["peer_id"], int)
+        assert "shard_key" not in point["routing"]
+
+    # only returned if requested
+    points = query()
+    assert all("routing" not in point for point in points)
+
+
+def test_query_with_stats():
+    def query(**body):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={"query": [0.1, 0.2, 0.3, 0.4], "limit": 3, **body},
+        )
+        assert response.ok, response.text
+        return response.json()["result"]
+
+    result = query(with_stats=True)
+    assert len(result["points"]) == 3
+    stats = result["stats"]
+    assert stats["plain_searches"] + stats["hnsw_searches"] >= 1
+    assert stats["vectors_scored"] >= 3
+
+    # small segments are searched without the HNSW graph
+    assert len(stats["segments"]) >= 1
+    assert all(segment["mode"] == "plain" for segment in stats["segments"])
+    assert sum(segment["searches"] for segment in stats["segments"]) == stats["plain_searches"]
+
+    result = query(
+        with_stats=True,
+        filter={"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+    )
+    assert result["stats"]["plain_searches"] + result["stats"]["hnsw_searches"] >= 1
+
+    # only returned if requested
+    result = query()
+    assert "stats" not in result
+
+
+@pytest.mark.parametrize(
+    "api,body",
+    [
+        ("/collections/{collection_name}/points/search", {"vector": [0.1, 0.2, 0.3, 0.4], "limit": 3}),
+        ("/collections/{collection_name}/points/recommend", {"positive": [1], "limit": 3}),
+        ("/collections/{collection_name}/points/discover", {"target": 1, "limit": 3}),
+        (
+            "/collections/{collection_name}/points/search/groups",
+            {"vector": [0.1, 0.2, 0.3, 0.4], "group_by": "city", "limit": 2, "group_size": 2},
+        ),
+    ],
+)
+def test_search_with_stats(api, body):
+    def search(**extra):
+        response = request_with_validation(
+            api=api,
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={**body, **extra},
+        )
+        assert response.ok, response.text
+        return response.json()
+
+    stats = search(with_stats=True)["stats"]
+    assert stats["plain_searches"] >= 1
+    assert stats["vectors_scored"] >= 1
+    assert {segment["mode"] for segment in stats["segments"]} == {"plain"}
+
+    # only returned if requested
+    assert "stats" not in search()
+
+
+def test_search_batch_with_stats():
+    def search_batch(*with_stats):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/search/batch",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "searches": [
+                    {"vector": [0.1, 0.2, 0.3, 0.4], "limit": 3, "with_stats": value}
+                    for value in with_stats
+                ]
+            },
+        )
+        assert response.ok, response.text
+        return response.json()
+
+    # stats of the whole batch, if any of the searches requests them
+    response = search_batch(False, True)
+    assert len(response["result"]) == 2
+    assert response["stats"]["plain_searches"] >= 1
+
+    assert "stats" not in search_batch(False, False)
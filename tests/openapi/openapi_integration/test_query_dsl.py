@@ -0,0 +1,81 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_query_dsl"
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def query(body):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body=body,
+    )
+    assert response.ok, response.text
+    return response.json()["result"]["points"]
+
+
+def query_dsl(text):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query/dsl",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"query": text},
+    )
+    assert response.ok, response.text
+    return response.json()["result"]["points"]
+
+
+def test_query_dsl_matches_json_query():
+    dsl_points = query_dsl(
+        "nearest([0.1, 0.2, 0.3, 0.4]) where city = 'Berlin' or city = 'London' limit 3 with payload"
+    )
+    json_points = query({
+        "query": [0.1, 0.2, 0.3, 0.4],
+        "filter": {
+            "should": [
+                {"key": "city", "match": {"value": "Berlin"}},
+                {"key": "city", "match": {"value": "London"}},
+            ]
+        },
+        "limit": 3,
+        "with_payload": True,
+    })
+    assert dsl_points == json_points
+
+
+def test_query_dsl_hybrid():
+    dsl_points = query_dsl(
+        "prefetch nearest([0.1, 0.2, 0.3, 0.4]) "
+        "prefetch sparse-text({63: 0.5, 65: 0.25}) "
+        "fuse rrf limit 5"
+    )
+    json_points = query({
+        "prefetch": [
+            {"query": [0.1, 0.2, 0.3, 0.4]},
+            {"query": {"indices": [63, 65], "values": [0.5, 0.25]}, "using": "sparse-text"},
+        ],
+        "query": {"fusion": "rrf"},
+        "limit": 5,
+    })
+    assert dsl_points == json_points
+
+
+def test_query_dsl_syntax_error():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query/dsl",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"query": "nearest([0.1, 0.2, 0.3, 0.4] limit 3"},
+    )
+    assert response.status_code == 400
+    assert "Query syntax error at position" in response.json()["status"]["error"]
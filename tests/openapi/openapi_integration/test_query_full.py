@@ -326,7 +326,7 @@ def test_recommend_lookup_validations():
         },
     )
     assert not response.ok, response.text
-    assert response.json()["status"]["error"] == "Not found: No point with id 3 found"
+    assert response.json()["status"]["error"] == f"Not found: No points with ids [3] found in collection `{lookup_collection_name}`"
 
     # check query + lookup_from non-existing collection
     response = request_with_validation(
@@ -401,7 +401,7 @@ def test_recommend_lookup_validations():
         },
     )
     assert not response.ok, response.text
-    assert response.json()["status"]["error"] == "Not found: No point with id 3 found"
+    assert response.json()["status"]["error"] == f"Not found: No points with ids [3] found in collection `{lookup_collection_name}`"
 
     # check nested query + lookup_from non-existing collection
     response = request_with_validation(
@@ -0,0 +1,71 @@
+from math import isclose
+
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_raw_score'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Cosine",
+                "on_disk": on_disk_vectors,
+            }
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [3.0, 4.0]},
+                {"id": 2, "vector": [0.0, 2.0]},
+            ]
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(**params):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [2.0, 0.0],
+            "limit": 2,
+            "params": params,
+        }
+    )
+    assert response.ok, response.text
+    return response.json()['result']
+
+
+def test_search_with_raw_score():
+    points = search(with_raw_score=True)
+    assert [point['id'] for point in points] == [1, 2]
+    assert isclose(points[0]['score'], 0.6, abs_tol=1e-5)
+    # cosine of 0.6 scaled by the query norm of 2 and the vector norm of 5
+    assert isclose(points[0]['raw_score'], 6.0, abs_tol=1e-4)
+    assert isclose(points[1]['raw_score'], 0.0, abs_tol=1e-4)
+
+    # only returned if requested
+    points = search()
+    assert all('raw_score' not in point for point in points)
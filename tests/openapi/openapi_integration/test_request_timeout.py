@@ -0,0 +1,41 @@
+import pytest
+import requests
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.settings import QDRANT_HOST
+
+collection_name = "test_request_timeout"
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def query(headers, **params):
+    return requests.post(
+        f"{QDRANT_HOST}/collections/{collection_name}/points/query",
+        params=params,
+        headers=headers,
+        json={"query": [0.1, 0.2, 0.3, 0.4], "limit": 3},
+    )
+
+
+@pytest.mark.parametrize("timeout", ["10", "0.5"])
+def test_request_timeout_header(timeout):
+    response = query({"X-Request-Timeout": timeout})
+    assert response.ok, response.text
+    assert len(response.json()["result"]["points"]) == 3
+
+    # Together with the timeout parameter, the earlier one applies
+    response = query({"X-Request-Timeout": timeout}, timeout=1)
+    assert response.ok, response.text
+
+
+@pytest.mark.parametrize("timeout", ["0", "-1", "5s", "soon"])
+def test_invalid_request_timeout_header(timeout):
+    response = query({"X-Request-Timeout": timeout})
+    assert response.status_code == 400, response.text
+    assert "X-Request-Timeout" in response.text
@@ -0,0 +1,79 @@
+import pytest
+import requests
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+from .helpers.settings import QDRANT_HOST
+
+collection_name = 'test_collection_sample'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def sample(body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/sample',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok, response.text
+    return response.json()['result']['points']
+
+
+def test_sample():
+    points = sample({"limit": 3})
+    assert len(points) == 3
+    assert len({point['id'] for point in points}) == 3
+    for point in points:
+        assert 'payload' in point
+        assert 'vector' not in point
+        assert 'projection' not in point
+
+    # Sample of the whole collection
+    points = sample({"limit": 100})
+    assert sorted(point['id'] for point in points) == list(range(1, 11))
+
+
+def test_sample_filter():
+    points = sample({
+        "limit": 100,
+        "filter": {"must": [{"key": "city", "match": {"value": "London"}}]},
+        "with_payload": False,
+    })
+    assert sorted(point['id'] for point in points) == [2, 4]
+    for point in points:
+        assert point.get('payload') is None
+
+
+def test_sample_projection():
+    points = sample({"limit": 100, "projection": {}})
+    assert len(points) == 10
+
+    # Points 9 and 10 only have a sparse vector
+    projected = {point['id']: point.get('projection') for point in points}
+    assert projected[9] is None
+    assert projected[10] is None
+    for point_id in range(1, 9):
+        assert len(projected[point_id]) == 2
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/sample',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"projection": {"using": "sparse-text"}},
+    )
+    assert response.status_code == 400, response.text
+
+
+def test_sample_invalid_limit():
+    response = requests.post(
+        f"{QDRANT_HOST}/collections/{collection_name}/points/sample",
+        json={"limit": 0},
+    )
+    assert response.status_code == 422
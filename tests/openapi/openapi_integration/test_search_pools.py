@@ -0,0 +1,106 @@
+import re
+
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_search_pools"
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={"sparse_vectors": {"text": {}}},
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={
+            "points": [
+                {"id": 1, "vector": {"text": {"indices": [1, 2], "values": [0.5, 1.0]}}},
+                {"id": 2, "vector": {"text": {"indices": [2, 3], "values": [1.0, 0.5]}}},
+            ]
+        },
+    )
+    assert response.ok, response.text
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_search_pools(search_pools):
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PATCH",
+        path_params={"collection_name": collection_name},
+        body={"params": {"search_pools": search_pools}},
+    )
+    assert response.ok, response.text
+
+
+def query():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"query": {"indices": [2], "values": [1.0]}, "using": "text"},
+    )
+    assert response.ok, response.text
+
+
+def search_context_counters():
+    response = request_with_validation(api="/metrics", method="GET")
+    assert response.ok, response.text
+
+    def counter(name):
+        match = re.search(
+            rf'^collections_search_pool_{name}_total{{pool="search_contexts"}} (\S+)$',
+            response.text,
+            re.MULTILINE,
+        )
+        assert match, response.text
+        return float(match.group(1))
+
+    return counter("reused"), counter("allocated")
+
+
+def test_search_pools_config():
+    search_pools = {"visited_pool_size": 4, "reuse_search_contexts": False}
+    set_search_pools(search_pools)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok, response.text
+    assert response.json()["result"]["config"]["params"]["search_pools"] == search_pools
+
+
+def test_search_context_reuse():
+    # Buffers are not returned to the pools, so every search allocates one
+    set_search_pools({"reuse_search_contexts": False})
+    reused, allocated = search_context_counters()
+    query()
+    query()
+    reused_after, allocated_after = search_context_counters()
+    assert reused_after == reused
+    assert allocated_after >= allocated + 2
+
+    # Buffers of the first search are reused by the second one
+    set_search_pools({"reuse_search_contexts": True})
+    reused, _ = search_context_counters()
+    query()
+    query()
+    reused_after, _ = search_context_counters()
+    assert reused_after > reused
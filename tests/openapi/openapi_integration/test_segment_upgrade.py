@@ -0,0 +1,48 @@
+import time
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_segment_upgrade"
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def segment_upgrade(method, body=None):
+    response = request_with_validation(
+        api="/collections/{collection_name}/segments/upgrade",
+        method=method,
+        path_params={"collection_name": collection_name},
+        body=body,
+    )
+    assert response.ok, response.text
+    return response.json()["result"]
+
+
+def test_segment_upgrade():
+    progress = segment_upgrade("GET")
+    assert progress["status"] == "idle"
+
+    # Segments of a new collection are already in the current format
+    progress = segment_upgrade("POST", {"throttle_ms": 10})
+    assert progress["total"] == 0
+    assert progress["remaining"] == 0
+
+    for _ in range(50):
+        progress = segment_upgrade("GET")
+        if progress["status"] != "running":
+            break
+        time.sleep(0.1)
+    assert progress["status"] == "done"
+    assert progress["upgraded"] == 0
+
+    # Stopping a finished upgrade keeps its status
+    progress = segment_upgrade("DELETE")
+    assert progress["status"] == "done"
@@ -0,0 +1,121 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_session_token"
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert(point_id, wait):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true" if wait else "false"},
+        body={"points": [{"id": point_id, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {}}]},
+    )
+    assert response.ok, response.text
+    return response.json()["result"]["session_token"]
+
+
+def query_ids(point_ids, **query_params):
+    return request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        query_params=query_params,
+        body={"filter": {"must": [{"has_id": point_ids}]}},
+    )
+
+
+def test_read_your_writes():
+    # Without waiting, the write is only acknowledged
+    token = upsert(100, wait=False)
+    assert token
+
+    response = query_ids([100], session_token=token)
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [100]
+
+
+def test_joined_session_tokens():
+    first = upsert(100, wait=False)
+    second = upsert(101, wait=False)
+
+    response = query_ids([100, 101], session_token=f"{first},{second}")
+    assert response.ok, response.text
+    assert sorted(point["id"] for point in response.json()["result"]["points"]) == [100, 101]
+
+
+def test_invalid_session_token():
+    response = query_ids([1], session_token="0:1:0")
+    assert response.status_code == 400, response.text
+
+
+def test_session_token_in_other_reads():
+    token = upsert(100, wait=False)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/{id}",
+        method="GET",
+        path_params={"collection_name": collection_name, "id": 100},
+        query_params={"session_token": token},
+    )
+    assert response.ok, response.text
+    assert response.json()["result"]["id"] == 100
+
+    token = upsert(101, wait=False)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/count",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        query_params={"session_token": token},
+        body={"filter": {"must": [{"has_id": [100, 101]}]}, "exact": True},
+    )
+    assert response.ok, response.text
+    assert response.json()["result"]["count"] == 2
+
+    token = upsert(102, wait=False)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/scroll",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        query_params={"session_token": token},
+        body={"filter": {"must": [{"has_id": [102]}]}},
+    )
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [102]
+
+    token = upsert(103, wait=False)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        query_params={"session_token": token},
+        body={
+            "vector": [0.1, 0.2, 0.3, 0.4],
+            "filter": {"must": [{"has_id": [103]}]},
+            "limit": 10,
+        },
+    )
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]] == [103]
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        query_params={"session_token": "0:1:0"},
+        body={"vector": [0.1, 0.2, 0.3, 0.4], "limit": 10},
+    )
+    assert response.status_code == 400, response.text
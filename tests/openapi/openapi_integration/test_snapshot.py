@@ -424,3 +424,51 @@ def test_collection_snapshot_security():
     )
     assert not response.ok
     assert response.status_code == 404
+
+
+def test_lazy_mount_snapshot():
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+    snapshot_name = response.json()['result']['name']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    points_count = response.json()['result']['count']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/{snapshot_name}/mount',
+        method="PUT",
+        path_params={'collection_name': collection_name, 'snapshot_name': snapshot_name},
+        query_params={'wait': 'true', 'lazy': 'true'},
+    )
+    assert response.ok
+    assert response.json()['result']
+
+    # Snapshot is loaded by the first query
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': f"{collection_name}:{snapshot_name}"},
+        body={"exact": True},
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == points_count
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/{snapshot_name}/mount',
+        method="DELETE",
+        path_params={'collection_name': collection_name, 'snapshot_name': snapshot_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+    assert response.json()['result']
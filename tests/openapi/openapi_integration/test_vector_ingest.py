@@ -0,0 +1,85 @@
+import math
+
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_vector_ingest"
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={
+            "vectors": {
+                "checked": {
+                    "size": 2,
+                    "distance": "Dot",
+                    "ingest": {"normalize": True, "reject_non_finite": True, "reject_zero": True},
+                },
+                "raw": {"size": 2, "distance": "Dot"},
+            },
+        },
+    )
+    assert response.ok, response.text
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert(vector):
+    return request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={"points": [{"id": 1, "vector": vector}]},
+    )
+
+
+def test_vector_ingest_normalize():
+    response = upsert({"checked": [3.0, 4.0], "raw": [3.0, 4.0]})
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/{id}",
+        method="GET",
+        path_params={"collection_name": collection_name, "id": 1},
+    )
+    assert response.ok, response.text
+    vectors = response.json()["result"]["vector"]
+    assert all(math.isclose(a, b, rel_tol=1e-6) for a, b in zip(vectors["checked"], [0.6, 0.8]))
+    assert vectors["raw"] == [3.0, 4.0]
+
+
+def test_vector_ingest_reject():
+    response = upsert({"checked": [0.0, 0.0]})
+    assert response.status_code == 400, response.text
+    assert "Vector `checked` of point 1 is a zero vector" in response.json()["status"]["error"]
+
+    response = upsert({"checked": [1.0, 2.0, 3.0]})
+    assert response.status_code == 400, response.text
+    assert "Vector `checked` of point 1 has dimension 3, expected 2" in response.json()["status"]["error"]
+
+    # vectors without a policy are not checked
+    response = upsert({"raw": [0.0, 0.0]})
+    assert response.ok, response.text
+
+
+def test_vector_ingest_update_policy():
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PATCH",
+        path_params={"collection_name": collection_name},
+        body={"vectors": {"raw": {"ingest": {"reject_zero": True}}}},
+    )
+    assert response.ok, response.text
+
+    response = upsert({"raw": [0.0, 0.0]})
+    assert response.status_code == 400, response.text
@@ -0,0 +1,117 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_vector_routing"
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={
+            "vectors": {
+                "english": {"size": 2, "distance": "Dot"},
+                "german": {"size": 2, "distance": "Dot"},
+            },
+        },
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={
+            "points": [
+                {"id": 1, "vector": {"english": [1.0, 0.0]}, "payload": {"lang": "en"}},
+                {"id": 2, "vector": {"english": [0.0, 1.0]}, "payload": {"lang": "en"}},
+                {"id": 3, "vector": {"german": [1.0, 0.0]}, "payload": {"lang": "de"}},
+                {"id": 4, "vector": {"german": [0.0, 1.0]}, "payload": {"lang": "de"}},
+            ]
+        },
+    )
+    assert response.ok, response.text
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_vector_routing(vector_routing):
+    return request_with_validation(
+        api="/collections/{collection_name}",
+        method="PATCH",
+        path_params={"collection_name": collection_name},
+        body={"params": {"vector_routing": vector_routing}},
+    )
+
+
+def query_lang(lang, **extra):
+    return request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": [1.0, 0.0],
+            "filter": {"must": [{"key": "lang", "match": {"value": lang}}]},
+            **extra,
+        },
+    )
+
+
+def test_vector_routing():
+    # without routing, the default vector is used, which doesn't exist
+    response = query_lang("de")
+    assert not response.ok
+
+    response = set_vector_routing({"key": "lang", "vectors": {"en": "english", "de": "german"}})
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok, response.text
+    assert response.json()["result"]["config"]["params"]["vector_routing"] == {
+        "key": "lang",
+        "vectors": {"en": "english", "de": "german"},
+    }
+
+    response = query_lang("de")
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [3, 4]
+
+    response = query_lang("en")
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [1, 2]
+
+    # an explicit vector takes precedence over routing
+    response = query_lang("en", using="german")
+    assert response.ok, response.text
+    assert response.json()["result"]["points"] == []
+
+    # routing also applies to prefetches, which get the filter of the request
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": [{"query": [0.0, 1.0]}],
+            "query": {"fusion": "rrf"},
+            "filter": {"must": [{"key": "lang", "match": {"value": "de"}}]},
+        },
+    )
+    assert response.ok, response.text
+    assert [point["id"] for point in response.json()["result"]["points"]] == [4, 3]
+
+
+def test_vector_routing_unknown_vector():
+    response = set_vector_routing({"key": "lang", "vectors": {"fr": "french"}})
+    assert response.status_code == 400, response.text
@@ -0,0 +1,70 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_wal_compaction'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def compact_wal():
+    response = request_with_validation(
+        api='/collections/{collection_name}/wal/compact',
+        method="POST",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    return response.json()['result']
+
+
+def test_wal_compaction():
+    # nothing is covered by a snapshot yet
+    result = compact_wal()
+    assert len(result) == 1
+    assert result[0]['shard_id'] == 0
+    assert 'truncated_until' not in result[0]
+    assert result[0]['skipped']
+
+    # deleting missing points doesn't change any segment, so the WAL keeps the operations
+    for point_id in range(100, 110):
+        response = request_with_validation(
+            api='/collections/{collection_name}/points/delete',
+            method="POST",
+            path_params={'collection_name': collection_name},
+            query_params={'wait': 'true'},
+            body={"points": [point_id]},
+        )
+        assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok, response.text
+
+    result = compact_wal()
+    assert len(result) == 1
+    assert 'skipped' not in result[0], result
+    assert result[0]['truncated_until'] > 0
+
+    # entries are truncated already
+    result = compact_wal()
+    assert 'truncated_until' not in result[0]
+
+    # points are still there
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok, response.text
+    assert response.json()['result']['count'] == 10